@@ -1,6 +1,9 @@
 ## general
 
 process-already-running = Another process ({$process}) is already running, please cancel it first sending /{-cancel-command}.
+trip-closed = Travel plan {$name} is closed and can't be changed anymore. An administrator can reopen it by sending `/{-reopen-trip-command}`.
+admin-only = Only chat administrators can use this command.
+file-too-large = The file is too large: bots can only download files up to 20 MB.
 running-process-unknown = unknown
 running-process-add-expense = /{-add-expense-command}
 running-process-add-traveler = /{-add-traveler-command}
@@ -10,12 +13,17 @@ running-process-show-expense = /{-show-expense-command}
 running-process-delete-transfer = /{-delete-transfer-command}
 running-process-set-language = /{-set-language-command}
 running-process-set-currency = /{-set-currency-command}
+running-process-set-timezone = /{-set-timezone-command}
 running-process-list-expenses = /{-list-expenses-command}
 running-process-transfer = /{-transfer-command}
 running-process-clear-travelers = /{-clear-travelers-command}
 running-process-clear-expenses = /{-clear-expenses-command}
 running-process-clear-transfers = /{-clear-transfers-command}
 running-process-clear-all = /{-clear-all-command}
+running-process-new-trip = /{-new-trip-command}
+running-process-rename-trip = /{-rename-trip-command}
+running-process-restore = /{-restore-command}
+running-process-import = /{-import-command}
 
 ## /setlanguage
 
@@ -31,6 +39,74 @@ set-language-ok = Chat language correctly set to {$language}.
 set-currency-ok = Chat default currency correctly set to {$currency}.
 set-currency-not-available = "{$currency}" is not a recognized currency code. Please use a valid ISO 4217 code (e.g. `USD`, `EUR`) or a known crypto code (e.g. `BTC`, `ETH`).
 
+## /settimezone
+
+set-timezone-ok = Chat time zone correctly set to {$timezone}. Dates and times are now shown in it.
+set-timezone-not-available = "{$timezone}" is not a recognized time zone. Please use an IANA time zone name (e.g. `Europe/Rome`, `America/New_York`, `UTC`).
+
+## /setsettlement
+
+set-settlement-ok = Settlement mode set to { $mode ->
+    [greedy] greedy: the largest debtor pays the largest creditor first.
+   *[exact] exact: debts are settled with as few transfers as possible.
+}
+set-settlement-status = The current settlement mode is { $mode ->
+    [greedy] greedy: the largest debtor pays the largest creditor first.
+   *[exact] exact: debts are settled with as few transfers as possible.
+}
+set-settlement-usage = Usage: `/{-set-settlement-command} exact` or `/{-set-settlement-command} greedy`.
+
+## /setsimplification
+
+set-simplification-ok = Debt simplification mode set to { $mode ->
+    [none] none: every traveler pays back whoever paid for them.
+    [pairwise] pairwise: only debts between the same two travelers cancel out.
+   *[full] full: debts are settled from everyone's net balance.
+}
+set-simplification-status = The current debt simplification mode is { $mode ->
+    [none] none: every traveler pays back whoever paid for them.
+    [pairwise] pairwise: only debts between the same two travelers cancel out.
+   *[full] full: debts are settled from everyone's net balance.
+}
+set-simplification-usage = Usage: `/{-set-simplification-command} none`, `/{-set-simplification-command} pairwise` or `/{-set-simplification-command} full`.
+
+## /newtrip
+
+new-trip-already-exists = A travel plan named {$name} already exists.
+new-trip-ok = Travel plan {$name} created successfully. It is now the active travel plan.
+
+## /switchtrip
+
+switch-trip-already-active = Travel plan {$name} is already the active one.
+switch-trip-choose = The active travel plan is {$name}. Choose the travel plan to switch to:
+switch-trip-not-found = Couldn't find travel plan {$name}. Use `/{-list-trips-command}` to see the available travel plans.
+switch-trip-ok = Switched to travel plan {$name}.
+
+## /renametrip
+
+rename-trip-already-exists = Another travel plan named {$name} already exists.
+rename-trip-ok = Travel plan {$old-name} renamed to {$name} successfully.
+
+## /closetrip
+
+close-trip-already-closed = Travel plan {$name} is already closed.
+close-trip-not-settled = Travel plan {$name} can't be closed until everyone has settled up. Use `/{-show-balances-command}` to see who still owes what.
+close-trip-ok = Travel plan {$name} closed successfully. Here is the final summary:
+
+## /reopentrip
+
+reopen-trip-not-closed = Travel plan {$name} is not closed.
+reopen-trip-ok = Travel plan {$name} reopened successfully.
+
+## /topicledger
+
+topic-ledger-disabled = This topic now uses the ledger of the whole chat.
+topic-ledger-enabled = This topic now keeps its own ledger.
+topic-ledger-not-a-topic = This command can only be used inside a forum topic.
+topic-ledger-status-disabled = This topic uses the ledger of the whole chat. Send `/{-topic-ledger-command} on` to give it its own ledger.
+topic-ledger-status-enabled = This topic keeps its own ledger. Send `/{-topic-ledger-command} off` to use the ledger of the whole chat.
+topic-ledger-usage = Usage: `/{-topic-ledger-command} [on|off]`
+
 ## /addtraveler
 
 add-traveler-already-added = Traveler {$name} has already been added to the travel plan.
@@ -58,9 +134,22 @@ delete-expense-ok = Expense #{$number} deleted successfully.
 
 ## /listexpenses
 
-list-expenses-descr-not-found = No expenses match the specified description (~ "{$description}").
+list-expenses-filter-not-found = No expenses match the filter `{$filter}`.
 list-expenses-not-found = No expenses found. Use `/{-add-expense-command}` to add one.
 
+## /listexpenses and /listtransfers filters
+
+filter-invalid = { $kind ->
+    [unknown-key] `{$token}` is not a filter.
+    [not-available] The filter `{$token}` can't be used with this command.
+    [unclosed-quote] The quote in `{$token}` is never closed.
+   *[invalid-value] The value of `{$token}` is not valid.
+} Use `/{-help-command} {$command}` to see the filter syntax.
+
+## Paginated lists
+
+list-page-expired = This list is no longer available, send the command again to browse it.
+
 ## /showexpense
 
 show-expense-not-found = Couldn't find expense #{$number} to show the details.
@@ -72,6 +161,7 @@ transfer-receiver-not-found = Couldn't find traveler "{$name}" to transfer money
 transfer-sender-not-found = Couldn't find traveler "{$name}" to transfer money from.
 transfer-same-sender-receiver = Sender and receiver cannot be the same traveler ("{$name}").
 transfer-non-positive-amount = The transfer amount must be greater than zero.
+transfer-nothing-owed = Traveler {$sender} doesn't owe anything to {$receiver}.
 
 ## /deletetransfer
 
@@ -80,12 +170,13 @@ delete-transfer-ok = Transfer #{$number} deleted successfully.
 
 ## /listtransfers
 
-list-transfers-name-not-found = No transfers related to traveler "{$name}" found.
+list-transfers-filter-not-found = No transfers match the filter `{$filter}`.
 list-transfers-not-found = No transfers found. Use `/{-transfer-command} <sender> <receiver> <amount>` to add one.
 
 ## /showbalances
 
 show-balances-ok = {$debtor} owes {$debt} to {$creditor}.
+show-balances-mode = Debt simplification: {$mode}. Use `/{-set-simplification-command}` to change it.
 show-balances-settled-up = All travelers are settled up with everyone.
 show-balances-traveler-ok = 
     {$traveler-name} { $traveler-is -> 
@@ -95,6 +186,154 @@ show-balances-traveler-ok =
 show-balances-traveler-settled-up = Traveler {$name} is settled up with everyone.
 show-balances-traveler-not-found = Couldn't find traveler "{$name}" to show the balance.
 
+## /statement
+
+statement-header = Statement of {$name}:
+statement-page = Page {$number} of {$count}. Use `/{-statement-command} {$name} <page>` to see another page.
+statement-page-not-found = The statement of {$name} has { $count ->
+    [one] only 1 page.
+   *[other] only {$count} pages.
+}
+statement-empty = Traveler {$name} has no expenses or transfers yet.
+statement-choose-traveler = Choose the traveler to show the statement of.
+statement-traveler-not-found = Couldn't find traveler "{$name}" to show the statement.
+statement-usage = Usage: `/{-statement-command} <name>` or `/{-statement-command} <name> <page>`.
+
+## /summary
+
+summary-header = What each traveler paid and consumed:
+summary-not-found = No travelers in the travel plan.
+summary-unbalanced = ⚠️ The net positions add up to {$amount} instead of zero: the ledger is inconsistent.
+
+## /showstats
+
+show-stats-usage = Usage: `/{-show-stats-command} [<section>]`. Available sections: {$sections}.
+show-stats-no-expenses = No expenses recorded yet.
+
+## /rebuildbalances
+
+rebuild-balances-ok = Balances rebuilt from every expense and transfer.
+
+## /audit
+
+audit-ok = No problems found in the ledger.
+audit-header = Problems found in the ledger:
+audit-repair-hint = Tap "🔧 Repair" or use `/{-audit-command} {-fix-kword}` to rebuild the balances and remove the relations pointing to deleted records.
+audit-manual-hint = Expenses whose shares or payers are wrong can't be repaired automatically: delete them and add them again.
+audit-repaired = { $count ->
+    [one] Repaired 1 problem.
+   *[other] Repaired {$count} problems.
+}
+audit-usage = Usage: `/{-audit-command}` to check the ledger, or `/{-audit-command} {-fix-kword}` to repair what can be fixed safely.
+
+## /export
+
+export-ok = { $expenses-count ->
+    [one] Exported 1 expense
+   *[other] Exported {$expenses-count} expenses
+} and { $transfers-count ->
+    [one] 1 transfer
+   *[other] {$transfers-count} transfers
+} as {$format}.
+export-usage = Usage: `/{-export-command} <format>`. Available formats: {$formats}.
+
+## /report
+
+report-ok = Here is the report of the travel plan, with { $expenses-count ->
+    [one] 1 expense
+   *[other] {$expenses-count} expenses
+}. Open it in a browser to read or print it.
+
+## /chart
+
+chart-usage = Usage: `/{-chart-command} <chart>`. Available charts: {$charts}.
+chart-no-data = There is nothing to chart yet.
+
+## /debtgraph
+
+debt-graph-usage = Usage: `/{-debt-graph-command} [{-raw-kword}] [{-dot-kword}]`.
+debt-graph-settled = Nobody owes anything to anyone: there are no debts to draw.
+debt-graph-dot-ok = Debt graph in DOT format. Lay it out with Graphviz, e.g. `dot -Tsvg debts.dot -o debts.svg`, or with any tool reading DOT.
+
+## /backup
+
+backup-ok = Backed up { $travelers-count ->
+    [one] 1 traveler
+   *[other] {$travelers-count} travelers
+}, { $expenses-count ->
+    [one] 1 expense
+   *[other] {$expenses-count} expenses
+} and { $transfers-count ->
+    [one] 1 transfer
+   *[other] {$transfers-count} transfers
+}. Send the file back with `/{-restore-command}` to restore them.
+
+## /restore
+
+restore-ok = Restored { $travelers-count ->
+    [one] 1 traveler
+   *[other] {$travelers-count} travelers
+}, { $expenses-count ->
+    [one] 1 expense
+   *[other] {$expenses-count} expenses
+} and { $transfers-count ->
+    [one] 1 transfer
+   *[other] {$transfers-count} transfers
+}.
+restore-usage = Usage: send a backup file with `/{-restore-command}` as its caption, or reply `/{-restore-command}` to a message containing one.
+
+## /import
+
+import-ok = Imported { $expenses-count ->
+    [one] 1 expense
+   *[other] {$expenses-count} expenses
+} and { $transfers-count ->
+    [one] 1 transfer
+   *[other] {$transfers-count} transfers
+}{ $travelers-count ->
+    [0] {""}
+    [one] , adding 1 traveler
+   *[other] , adding {$travelers-count} travelers
+}.
+import-usage = Usage: send a CSV export of Splitwise or Tricount with `/{-import-command}` as its caption, or reply `/{-import-command}` to a message containing one.
+import-nothing = The file has no expenses or transfers that can be imported.
+import-skipped = { $count ->
+    [one] 1 row can't be imported:
+   *[other] {$count} rows can't be imported:
+}
+import-skipped-row = • Row {$line}: { $reason ->
+    [other-currency] it's in {$currency}, not in the currency of the chat
+    [several-payers] it was paid by several members
+    [no-payer] nobody paid it
+    [unsupported] it has no equivalent, such as an income
+    [self-transfer] its sender and receiver are the same traveler
+   *[invalid] it's incomplete or its amounts don't add up
+}.
+
+## /settle
+
+settle-plan-hint = Tap "Paid ✓" once a payment is done, or "Settle all" to record every payment at once.
+settle-all-ok = { $count ->
+    [one] Recorded 1 transfer: all travelers are settled up with everyone.
+   *[other] Recorded {$count} transfers: all travelers are settled up with everyone.
+}
+settle-usage = Usage: `/{-settle-command}` to show who should pay whom, or `/{-settle-command} {-all-kword}` to record every payment.
+settle-preference-unmet = ⚠️ {$debtor} can't pay {$creditor}, but there is no other way to settle up.
+settle-preference-via-unmet = ⚠️ {$name} prefers to settle via {$other-traveler-name}, but still has to settle up with someone else.
+
+## /payprefs
+
+pay-prefs-cannot-pay = {$name} can't pay {$other-traveler-name}.
+pay-prefs-settles-via = {$name} prefers to settle via {$other-traveler-name}.
+pay-prefs-none = No payment preferences set. Use `/{-pay-prefs-command} <name> {-cannot-kword} <name>` or `/{-pay-prefs-command} <name> {-via-kword} <name>` to add one.
+pay-prefs-cannot-pay-ok = {$name} won't be asked to pay {$other-traveler-name}, whenever possible.
+pay-prefs-settles-via-ok = {$name} will settle via {$other-traveler-name}, whenever possible.
+pay-prefs-clear-ok = Payment preferences of {$name} removed successfully.
+pay-prefs-same-traveler = Traveler {$name} can't have a payment preference about themselves.
+pay-prefs-traveler-not-found = Couldn't find traveler "{$name}" to set the payment preference.
+pay-prefs-usage = Usage: `/{-pay-prefs-command} <name> {-cannot-kword} <name>`, `/{-pay-prefs-command} <name> {-via-kword} <name>` or `/{-pay-prefs-command} <name> {-clear-kword}`.
+pay-prefs-full-only = ℹ️ Payment preferences only apply when debts are fully simplified. Use `/{-set-simplification-command} full` to apply them.
+
 ## /cancel
 
 cancel-no-process-to-cancel = There is no process to cancel.
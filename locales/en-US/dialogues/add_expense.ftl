@@ -2,6 +2,7 @@
 
 add-expense-start = The process can be interrupted at any time by sending `/{-cancel-command}`.
 add-expense-ask-description = Set a description for this expense.
+add-expense-invalid-date-option = Invalid date. Use `/{-add-expense-command} {-date-option} <date>`, where `<date>` is `{-today-kword}`, `{-yesterday-kword}` or a past date like `YYYY-MM-DD` or `YYYY-MM-DD HH:MM`.
 
 ## receive_description
 
@@ -10,10 +11,15 @@ add-expense-invalid-description = You sent an invalid text, please retry.
 
 ## receive_amount
 
-add-expense-ask-paid-by = Who paid for this?
+add-expense-ask-date = When was the expense made? Pick a day or send a date like `YYYY-MM-DD`, optionally followed by a time like `HH:MM`.
 add-expense-invalid-amount = You sent an invalid amount, please retry.
 add-expense-non-positive-amount = The amount must be greater than zero.
 
+## receive_date
+
+add-expense-ask-paid-by = Who paid for this?
+add-expense-invalid-date = You sent an invalid date, please retry. Send `{-today-kword}`, `{-yesterday-kword}` or a past date like `YYYY-MM-DD` or `YYYY-MM-DD HH:MM`.
+
 ## receive_paid_by
 
 add-expense-invalid-paid-by = You sent an invalid name, please retry.
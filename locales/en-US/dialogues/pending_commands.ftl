@@ -3,6 +3,16 @@
 add-traveler-ask-name = What's the name of the traveler? The process can be interrupted at any time by sending `/{-cancel-command}`.
 add-traveler-invalid-name = You sent an invalid name, please retry.
 
+## /new_trip
+
+new-trip-ask-name = What's the name of the new travel plan? The process can be interrupted at any time by sending `/{-cancel-command}`.
+new-trip-invalid-name = You sent an invalid name, please retry.
+
+## /rename_trip
+
+rename-trip-ask-name = What's the new name of the active travel plan? The process can be interrupted at any time by sending `/{-cancel-command}`.
+rename-trip-invalid-name = You sent an invalid name, please retry.
+
 ## /delete_traveler
 
 delete-traveler-ask-name = Which traveler do you want to delete? The process can be interrupted at any time by sending `/{-cancel-command}`.
@@ -38,9 +48,14 @@ set-language-invalid-langid = You sent an invalid language identifier, please re
 set-currency-ask-currency = Which currency do you want to set? Select one from the list below or send the currency code if not present.
 set-currency-invalid-currency = You sent an invalid currency code, please retry.
 
+## /set_timezone
+
+set-timezone-ask-timezone = Which time zone do you want to set? Select one from the list below or send its IANA name (e.g. `Europe/Paris`) if not present.
+set-timezone-invalid-timezone = You sent an invalid time zone, please retry.
+
 ## /list_expenses
 
-list-expenses-ask-description = Send a description (or part of it) to filter expenses by.
+list-expenses-ask-description = Send the filters to list expenses by, e.g. a description (or part of it) or `payer:Alice amount>20`. Use `/{-help-command} {-list-expenses-command}` to see all the filters.
 
 ## /transfer
 
@@ -50,7 +65,7 @@ transfer-from-not-found = Traveler "{$name}" not found. Who is the sender?
 transfer-ask-to = Who is the receiver of the transfer from {$name}?
 transfer-ask-to-reprompt = You sent an invalid name, please retry. Who is the receiver?
 transfer-to-not-found = Traveler "{$name}" not found. Who is the receiver?
-transfer-ask-amount = How much did {$name} transfer?
+transfer-ask-amount = How much did {$name} transfer? Send "{-all-kword}" to transfer exactly what is owed.
 transfer-invalid-amount = You sent an invalid amount, please retry.
 
 ## /cleartravelers
@@ -73,3 +88,34 @@ clear-transfers-confirm = Are you sure you want to delete all transfers?
 clear-all-confirm =
     Are you sure you want to delete all travelers, expenses and transfers?
     ⚠️ This action cannot be undone.
+
+## /restore
+
+restore-confirm =
+    Restore the backup of {DATETIME($datetime, dateStyle: "long")}, with { $travelers-count ->
+        [one] 1 traveler
+       *[other] {$travelers-count} travelers
+    }, { $expenses-count ->
+        [one] 1 expense
+       *[other] {$expenses-count} expenses
+    } and { $transfers-count ->
+        [one] 1 transfer
+       *[other] {$transfers-count} transfers
+    }?
+    ⚠️ The current travel plan and the chat settings will be replaced.
+
+## /import
+
+import-ask-traveler = Which traveler is "{$member}"? Choose one, or send the name of a traveler to add.
+import-traveler-taken = {$name} is already matched to another member of the group.
+import-member-new = {$member} → {$name} (new)
+import-member-existing = {$member} → {$name}
+import-confirm =
+    Import { $expenses-count ->
+        [one] 1 expense
+       *[other] {$expenses-count} expenses
+    } and { $transfers-count ->
+        [one] 1 transfer
+       *[other] {$transfers-count} transfers
+    } from {$format}, with these travelers?
+    {$travelers}
@@ -3,6 +3,16 @@
 command-error-help = No help available for command /{$command}.
 command-error-set-language = Couldn't set language "{$langid}".
 command-error-set-currency = Couldn't set currency "{$currency}".
+command-error-set-timezone = Couldn't set time zone "{$timezone}".
+command-error-set-settlement = Couldn't change the settlement mode.
+command-error-set-simplification = Couldn't change the debt simplification mode.
+command-error-new-trip = Couldn't create travel plan named "{$name}".
+command-error-switch-trip = Couldn't switch to travel plan "{$name}".
+command-error-list-trips = Couldn't list travel plans.
+command-error-rename-trip = Couldn't rename travel plan to "{$name}".
+command-error-close-trip = Couldn't close the travel plan.
+command-error-reopen-trip = Couldn't reopen the travel plan.
+command-error-topic-ledger = Couldn't change the ledger of the topic.
 command-error-add-traveler = Couldn't add traveler named "{$name}".
 command-error-delete-traveler = Couldn't delete traveler named "{$name}".
 command-error-list-travelers = Couldn't list travelers.
@@ -10,11 +20,25 @@ command-error-delete-expense = Couldn't delete expense #{$number}.
 command-error-list-expenses = Couldn't list expenses.
 command-error-show-expense = Couldn't show expense #{$number}.
 command-error-transfer = Couldn't transfer {$amount} from traveler "{$sender}" to "{$receiver}".
+command-error-transfer-all = Couldn't transfer what traveler "{$sender}" owes to "{$receiver}".
 command-error-delete-transfer = Couldn't delete transfer #{$number}.
 command-error-list-transfers = Couldn't list transfers.
 command-error-show-balance = Couldn't show balance for traveler "{$name}".
 command-error-show-balances = Couldn't show balances.
+command-error-statement = Couldn't show the statement.
+command-error-summary = Couldn't show the summary.
+command-error-settle = Couldn't settle up the debts.
+command-error-pay-prefs = Couldn't update the payment preferences.
 command-error-show-stats = Couldn't show statistics.
+command-error-rebuild-balances = Couldn't rebuild the balances.
+command-error-audit = Couldn't audit the ledger.
+command-error-export = Couldn't export the travel plan.
+command-error-report = Couldn't create the report of the travel plan.
+command-error-chart = Couldn't draw the chart.
+command-error-debt-graph = Couldn't draw the debt graph.
+command-error-backup = Couldn't back up the travel plan.
+command-error-restore = Couldn't restore the travel plan.
+command-error-import = Couldn't import the file.
 command-error-clear-travelers = Couldn't clear travelers.
 command-error-clear-expenses = Couldn't clear expenses.
 command-error-clear-transfers = Couldn't clear transfers.
@@ -41,3 +65,15 @@ add-expense-error-no-travelers-specified = No travelers have been specified.
 
 end-error-closing-dialogue = An error occured while closing the process.
 end-error-no-expense-created = No expense has been created.
+
+## BackupError
+
+backup-error-invalid = The file isn't a valid travel-rs backup.
+backup-error-unsupported-version = The backup uses version {$version} of the format, but only version {$supported-version} is supported.
+backup-error-unknown-traveler = The backup refers to traveler "{$name}", who isn't among its travelers.
+
+## ImportError
+
+import-error-invalid = The file isn't a valid CSV file.
+import-error-unknown-format = The file isn't a CSV export of Splitwise or Tricount.
+import-error-unmapped-member = Member "{$member}" isn't matched to any traveler, so nothing was imported.
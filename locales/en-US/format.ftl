@@ -8,6 +8,20 @@ format-expense-details =
     {$shares}
 format-expense = [{DATETIME($datetime, dateStyle: "short")}] Expense #{$number}: {$description} - {$amount}
 format-transfer = [{DATETIME($datetime, dateStyle: "short")}] Transfer #{$number}: {$sender} sent {$amount} to {$receiver}
+format-statement-entry = [{DATETIME($datetime, dateStyle: "short")}] {$kind ->
+    [paid] Paid expense #{$number}: {$description}
+    [share] Share of expense #{$number}: {$description}, paid by {$other-traveler-name}
+    [sent] Sent transfer #{$number} to {$other-traveler-name}
+   *[received] Received transfer #{$number} from {$other-traveler-name}
+} ({$amount}) → {$net}
+format-trip = #{$number}: {$name}{$closed ->
+    [true] {" "}(closed)
+   *[false] {""}
+}
+format-active-trip = #{$number}: {$name} (active{$closed ->
+    [true] , closed
+   *[false] {""}
+})
 format-traveler-stats-amount = {$traveler-name}: {$amount}
 format-traveler-stats-frequency = 
     {$traveler-name}: {$count ->
@@ -23,6 +37,7 @@ format-expense-stats =
             - Count: {$count}
             - Sum: {$sum}
             - Mean: {$mean}
+            - Median: {$median}
             - Maximum expenses: {$max}
             - Minimum expenses: {$min}
             - Average per day: {$average-per-day}
@@ -64,9 +79,48 @@ format-traveler-stats =
                     - Major debtors: {$major-debtors}
             }
     }
+format-traveler-summary = {$traveler-name}: paid {$paid}, consumed {$consumed}, sent {$sent}, received {$received}, net {$net}
+format-audit-problem = {$kind ->
+    [splits-mismatch] Expense #{$number}: the shares add up to {$sum} instead of {$amount}
+    [payer-count] Expense #{$number}: {$count} payers instead of one
+    [stale-net] {$traveler-name}: net position {$net} instead of {$amount}
+    [stale-debts] The debts don't match the expenses and transfers
+   *[orphaned-relations] {$count} {$table} relations pointing to deleted records
+}
 format-stats =
     {$expense-stats}
 
     {$transfer-stats}
 
     {$traveler-stats}
+format-stats-period = - {$period}: {$amount} ({ $count ->
+    [one] 1 expense
+   *[other] {$count} expenses
+})
+format-stats-week = week of {$date}
+format-weekday = { $weekday ->
+    [1] Monday
+    [2] Tuesday
+    [3] Wednesday
+    [4] Thursday
+    [5] Friday
+    [6] Saturday
+   *[7] Sunday
+}
+format-stats-days =
+    Spending per day:
+    {$periods}
+    Busiest day: {$date}, {$amount}
+format-stats-weeks =
+    Spending per week:
+    {$periods}
+format-stats-weekdays =
+    Spending by day of the week:
+    {$periods}
+format-stats-hours =
+    Spending by hour of the day:
+    {$periods}
+format-stats-travelers =
+    Consumption per traveler, week by week ({$weeks}):
+    {$trends}
+format-stats-trend = - {$traveler-name}: {$amounts} (total {$sum})
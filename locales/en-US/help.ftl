@@ -29,6 +29,98 @@ help-set-currency =
 
     Usage: /{-set-currency-command} <currency code>
 
+## /settimezone
+
+descr-set-timezone = Set the time zone used for dates and times.
+help-set-timezone =
+    /{-set-timezone-command} — {descr-set-timezone}
+
+    Usage: /{-set-timezone-command} <time zone>
+
+    Time zones are IANA names, e.g. `Europe/Rome` or `Asia/Tokyo`. Dates sent to the bot are read in the chat time zone and the statistics count days in it.
+
+## /setsettlement
+
+descr-set-settlement = Show or set how balances are settled.
+help-set-settlement =
+    /{-set-settlement-command} — {descr-set-settlement}
+
+    With exact (the default), debts are settled with as few transfers as possible. Travel plans with many travelers owing money fall back to greedy, where the largest debtor pays the largest creditor first.
+
+    Usage: /{-set-settlement-command} [exact|greedy]
+
+## /setsimplification
+
+descr-set-simplification = Show or set how far debts are simplified.
+help-set-simplification =
+    /{-set-simplification-command} — {descr-set-simplification}
+
+    With none, every traveler pays back whoever paid for them. With pairwise, only debts between the same two travelers cancel out. With full (the default), debts are settled from everyone's net balance, as chosen by /{-set-settlement-command}.
+
+    Usage: /{-set-simplification-command} [none|pairwise|full]
+
+## /newtrip
+
+descr-new-trip = Create a new travel plan with the specified name and make it the active one.
+help-new-trip =
+    /{-new-trip-command} — {descr-new-trip}
+
+    Travelers, expenses and transfers belong to the active travel plan.
+
+    Usage: /{-new-trip-command} <name>
+
+## /switchtrip
+
+descr-switch-trip = Switch the active travel plan to the one with the specified name or number.
+help-switch-trip =
+    /{-switch-trip-command} — {descr-switch-trip}
+
+    Usage: /{-switch-trip-command} <name or number>
+
+## /listtrips
+
+descr-list-trips = Show the travel plans of the chat.
+help-list-trips =
+    /{-list-trips-command} — {descr-list-trips}
+
+    Usage: /{-list-trips-command}
+
+## /renametrip
+
+descr-rename-trip = Rename the active travel plan.
+help-rename-trip =
+    /{-rename-trip-command} — {descr-rename-trip}
+
+    Usage: /{-rename-trip-command} <name>
+
+## /closetrip
+
+descr-close-trip = Close the active travel plan once everyone has settled up, making it read-only.
+help-close-trip =
+    /{-close-trip-command} — {descr-close-trip}
+
+    The travel plan can only be closed when all balances are settled. Once closed, travelers, expenses and transfers can't be added, changed or deleted anymore, while they can still be listed and shown. A final summary of the travel plan is sent.
+
+    Usage: /{-close-trip-command}
+
+## /reopentrip
+
+descr-reopen-trip = Reopen the active travel plan after it was closed (administrators only).
+help-reopen-trip =
+    /{-reopen-trip-command} — {descr-reopen-trip}
+
+    Usage: /{-reopen-trip-command}
+
+## /topicledger
+
+descr-topic-ledger = Show or change whether the current forum topic keeps its own ledger.
+help-topic-ledger =
+    /{-topic-ledger-command} — {descr-topic-ledger}
+
+    A topic with its own ledger has its own travel plans, travelers, expenses and transfers. When it is turned off, the topic uses the ledger of the whole chat again; its data is kept and comes back when it is turned on.
+
+    Usage: /{-topic-ledger-command} [on|off]
+
 ## /addtraveler
 
 descr-add-traveler = Add a traveler with the specified name to the travel plan.
@@ -60,8 +152,9 @@ help-add-expense =
     /{-add-expense-command} — {descr-add-expense}
 
     In the session, you will be asked to:
-    - Send a message with the description of the expense.
+    - Send a message with the description of the expense. Add hashtags like `#food` to give it categories.
     - Send a message with the amount of the expense.
+    - Pick the day of the expense, or send a date like `YYYY-MM-DD`, optionally followed by a time like `HH:MM`.
     - Send a message with the name of the traveler who paid the expense.
     - Send one or more messages with the travelers who partecipate in the expense and their shares.
 
@@ -78,7 +171,10 @@ help-add-expense =
 
     - Enter `{-all-kword}` to split it evenly among all travelers.
 
-    Usage: /{-add-expense-command}
+    To record an expense made on another day, skipping the date step, add `{-date-option}` followed by `{-today-kword}`, `{-yesterday-kword}` or a past date.
+    > Example: `/{-add-expense-command} {-date-option} 2026-08-14 20:30`
+
+    Usage: /{-add-expense-command} [{-date-option} <date>]
 
 ## /deleteexpense
 
@@ -90,11 +186,21 @@ help-delete-expense =
 
 ## /listexpenses
 
-descr-list-expenses = Show the expenses in the travel plan. If a description is specified, show only the expenses that match the given description. Supports fuzzy search for more flexible matching.
+descr-list-expenses = Show the expenses in the travel plan. If filters are specified, show only the expenses that match all of them.
 help-list-expenses = 
     /{-list-expenses-command} — {descr-list-expenses}
 
-    Usage: /{-list-expenses-command} [description]
+    Available filters:
+    - `payer:<name>`: expenses paid by the traveler.
+    - `with:<name>`: expenses shared with the traveler.
+    - `amount<op><amount>`: expenses whose amount is `<`, `<=`, `=`, `>=` or `>` than the given one.
+    - `date:<date>` or `date:<from>..<to>`: expenses made on that day or between those days, both included. Either end of the range can be omitted.
+    - `cat:<category>`: expenses with the `#<category>` hashtag in their description.
+    - `sort:<date|amount|number>`: sort the expenses by that field, in descending order if preceded by `-`.
+    - Any other text: expenses whose description has every word of the text, or a word starting with it, listed by relevance. Text made only of words shorter than 3 characters is fuzzy-matched. Wrap text or names in double quotes to keep them together.
+    > Example: `/{-list-expenses-command} payer:Alice with:Bob amount>20 date:2026-08-01..2026-08-05 cat:food "gelato" sort:-amount`
+
+    Usage: /{-list-expenses-command} [filters]
 
 ## /showexpense
 
@@ -110,7 +216,9 @@ descr-transfer = Transfer the specified amount from one traveler to another.
 help-transfer = 
     /{-transfer-command} — {descr-transfer}
 
-    Usage: /{-transfer-command} <sender> <receiver> <amount>
+    Usage: /{-transfer-command} <sender> <receiver> <amount|{-all-kword}>
+
+    With {-all-kword}, the sender transfers exactly what they owe to the receiver.
 
 ## /deletetransfer
 
@@ -122,11 +230,20 @@ help-delete-transfer =
 
 ## /listtransfers
 
-descr-list-transfers = Show the transfers in the travel plan. If a name is specified, show only the transfers that involve that traveler.
+descr-list-transfers = Show the transfers in the travel plan. If filters are specified, show only the transfers that match all of them.
 help-list-transfers = 
     /{-list-transfers-command} — {descr-list-transfers}
 
-    Usage: /{-list-transfers-command} [name]
+    Available filters:
+    - `from:<name>`: transfers sent by the traveler.
+    - `to:<name>`: transfers received by the traveler.
+    - `with:<name>` or just `<name>`: transfers sent or received by the traveler.
+    - `amount<op><amount>`: transfers whose amount is `<`, `<=`, `=`, `>=` or `>` than the given one.
+    - `date:<date>` or `date:<from>..<to>`: transfers made on that day or between those days, both included. Either end of the range can be omitted.
+    - `sort:<date|amount|number>`: sort the transfers by that field, in descending order if preceded by `-`.
+    > Example: `/{-list-transfers-command} from:Alice amount>=50 sort:-date`
+
+    Usage: /{-list-transfers-command} [filters]
 
 ## /showbalances
 
@@ -136,13 +253,150 @@ help-show-balances =
 
     Usage: /{-show-balances-command} [name]
 
+## /statement
+
+descr-statement = Show, in date order, every expense paid, share owed and transfer sent or received by a traveler, with their running net position.
+help-statement = 
+    /{-statement-command} — {descr-statement}
+
+    Usage: /{-statement-command} [name] [page]
+
+    A positive net position means the traveler is owed money, a negative one that they owe it. Long statements are split into pages.
+
+## /summary
+
+descr-summary = Show, for each traveler, the total paid for expenses, the total of their shares, the transfers sent and received, and the resulting net position.
+help-summary = 
+    /{-summary-command} — {descr-summary}
+
+    Usage: /{-summary-command}
+
+    Figures are rounded to the currency precision, keeping the net positions adding up to zero.
+
+## /settle
+
+descr-settle = Show who should pay whom to settle up, and record the payments.
+help-settle = 
+    /{-settle-command} — {descr-settle}
+
+    Usage: /{-settle-command} [{-all-kword}]
+
+    Without arguments, each payment comes with a "Paid ✓" button recording it as a transfer. With {-all-kword}, every payment is recorded at once.
+
+## /payprefs
+
+descr-pay-prefs = Show or set who can't pay whom and who prefers to settle via someone else.
+help-pay-prefs = 
+    /{-pay-prefs-command} — {descr-pay-prefs}
+
+    Usage: /{-pay-prefs-command} [<name> {-cannot-kword} <name> | <name> {-via-kword} <name> | <name> {-clear-kword}]
+
+    With {-cannot-kword}, the first traveler is never asked to pay the second one, unless there is no other way to settle up. With {-via-kword}, the first traveler pays and is paid through the second one, e.g. the group's banker. With {-clear-kword}, the preferences of the traveler are removed. Preferences apply when debts are fully simplified.
+
 ## /showstats
 
 descr-show-stats = Show the statistics for expenses, transfers and travelers in the travel plan.
 help-show-stats = 
     /{-show-stats-command} — {descr-show-stats}
 
-    Usage: /{-show-stats-command}
+    Usage: /{-show-stats-command} [<section>]
+
+    Without a section, the overview is shown, with buttons for the other sections:
+    - `days`: spending per day, and the busiest day
+    - `weeks`: spending per week
+    - `weekdays`: spending by day of the week
+    - `hours`: spending by hour of the day
+    - `travelers`: what each traveler consumed, week by week
+
+## /rebuildbalances
+
+descr-rebuild-balances = Recompute every balance from the expenses and transfers of the travel plan.
+help-rebuild-balances = 
+    /{-rebuild-balances-command} — {descr-rebuild-balances}
+
+    Usage: /{-rebuild-balances-command}
+
+    Balances are normally kept up to date by each expense and transfer. Use this command to repair them if they look wrong.
+
+## /audit
+
+descr-audit = Check the ledger of the travel plan for inconsistencies, and repair them.
+help-audit = 
+    /{-audit-command} — {descr-audit}
+
+    Usage: /{-audit-command} [{-fix-kword}]
+
+    Checks that the shares of every expense add up to its amount, that every expense has exactly one payer, that balances match the expenses and transfers, and that no relation points to a deleted record. With {-fix-kword}, balances are rebuilt and such relations removed.
+
+## /export
+
+descr-export = Export the expenses, shares, transfers and balances of the travel plan to files.
+help-export = 
+    /{-export-command} — {descr-export}
+
+    Usage: /{-export-command} csv | ledger | beancount
+
+    With csv, sends one CSV file each for expenses, shares, transfers and balances. With ledger or beancount, sends a journal for Ledger and hledger or for Beancount, where each expense debits the expense account of every traveler with their share and credits the payer, and each transfer moves money between the accounts of two travelers. Amounts are in the currency of the chat and dates in its time zone.
+
+## /report
+
+descr-report = Send a report of the travel plan as an HTML file.
+help-report =
+    /{-report-command} — {descr-report}
+
+    Usage: /{-report-command}
+
+    The report shows the totals, what each traveler paid and consumed, the spending by category (the first #hashtag of the description) and by day, who should pay whom to settle up, and every expense. It is a single file that can be opened in any browser, even offline.
+
+## /chart
+
+descr-chart = Send a chart of the spending as an image.
+help-chart =
+    /{-chart-command} — {descr-chart}
+
+    Usage: /{-chart-command} daily | travelers | categories
+
+    With daily, shows the total spent each day. With travelers, shows what each traveler paid and consumed. With categories, shows a pie of the spending by category (the first #hashtag of the description).
+
+## /debtgraph
+
+descr-debt-graph = Send a graph of who owes whom as an image.
+help-debt-graph =
+    /{-debt-graph-command} — {descr-debt-graph}
+
+    Usage: /{-debt-graph-command} [{-raw-kword}] [{-dot-kword}]
+
+    Draws each traveler with an arrow to every traveler they owe money to, labelled with the amount. By default, the debts are the ones shown by /{-show-balances-command}. With {-raw-kword}, they are the debts owed for each expense and transfer, before they are simplified, to see where the simplified ones come from. With {-dot-kword}, the graph is sent as a DOT file instead, to lay it out with Graphviz or any other tool.
+
+## /backup
+
+descr-backup = Back up the travel plan and the chat settings to a file.
+help-backup =
+    /{-backup-command} — {descr-backup}
+
+    Usage: /{-backup-command}
+
+    Sends a JSON file with the settings of the chat, the travelers, the expenses with their shares, and the transfers. Use /{-restore-command} to load it back.
+
+## /restore
+
+descr-restore = Replace the travel plan and the chat settings with a backup.
+help-restore =
+    /{-restore-command} — {descr-restore}
+
+    Usage: send a file made by /{-backup-command} with /{-restore-command} as its caption, or reply /{-restore-command} to it.
+
+    After a confirmation, the travelers, expenses and transfers of the travel plan are replaced with the ones in the backup, keeping their numbers and dates.
+
+## /import
+
+descr-import = Import the expenses and transfers of a Splitwise or Tricount export.
+help-import =
+    /{-import-command} — {descr-import}
+
+    Usage: send the CSV file exported by Splitwise or Tricount with /{-import-command} as its caption, or reply /{-import-command} to it.
+
+    Each member of the group is matched to a traveler with the same name, or you are asked to choose a traveler or create a new one. Expenses paid by a single member become expenses with fixed shares, payments become transfers, and the rows that can't be imported are listed. Everything is added at once after a confirmation.
 
 ## /cleartravelers
 
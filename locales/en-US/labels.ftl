@@ -8,6 +8,74 @@ show-all-button = Show all
 help-button = ❓ Help
 confirm-yes-button = ✓ Yes
 confirm-no-button = ✗ No
+paid-button = Paid ✓ {$debtor} → {$creditor}
+settle-all-button = Settle all
+repair-button = 🔧 Repair
+stats-section-button = { $section ->
+    [days] 📅 Days
+    [weeks] 🗓 Weeks
+    [weekdays] 📆 Weekdays
+    [hours] 🕒 Hours
+    [travelers] 🧳 Travelers
+   *[overview] 📊 Overview
+}
+today-button = Today
+yesterday-button = Yesterday
+new-traveler-button = ➕ New: {$name}
+
+## Column headers of exported files
+
+column-number = Number
+column-date = Date
+column-description = Description
+column-amount = Amount
+column-currency = Currency
+column-payer = Payer
+column-expense = Expense
+column-traveler = Traveler
+column-sender = Sender
+column-receiver = Receiver
+column-debtor = Debtor
+column-creditor = Creditor
+
+## Headings and labels of the HTML report
+
+report-title = Travel report
+report-title-trip = Travel report: {$name}
+report-generated = Generated on {DATETIME($datetime, dateStyle: "long")}
+report-totals = Totals
+report-spent = Spent
+report-transferred = Transferred
+report-expenses-count = Expenses
+report-travelers-count = Travelers
+report-days = Days with expenses
+report-average-per-day = Average per day
+report-travelers = Paid and consumed
+report-paid = Paid
+report-consumed = Consumed
+report-net = Balance
+report-categories = Categories
+report-category = Category
+report-uncategorized = Uncategorized
+report-percentage = %
+report-timeline = Daily spending
+report-settlement = Settlement plan
+report-settled-up = All travelers are settled up with everyone.
+report-expenses = Expenses
+report-shares = Shares
+report-no-expenses = No expenses recorded.
+
+## Titles and labels of the charts
+
+chart-daily = Spending per day
+chart-travelers = Paid and consumed per traveler
+chart-categories = Spending by category
+chart-other = Other
+
+## Titles of the debt graph
+
+debt-graph = Who owes whom
+debt-graph-raw = Who owes whom, before simplification
 
 ## Language labels: <flag emoji> <long name>
 
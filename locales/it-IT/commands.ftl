@@ -1,6 +1,9 @@
 ## general
 
 process-already-running = Un altro processo ({$process}) è già in esecuzione, per favore annullalo prima inviando /{-cancel-command}.
+trip-closed = Il piano di viaggio {$name} è chiuso e non può più essere modificato. Un amministratore può riaprirlo inviando `/{-reopen-trip-command}`.
+admin-only = Solo gli amministratori della chat possono usare questo comando.
+file-too-large = Il file è troppo grande: i bot possono scaricare solo file fino a 20 MB.
 running-process-unknown = sconosciuto
 running-process-add-expense = /{-add-expense-command}
 running-process-add-traveler = /{-add-traveler-command}
@@ -10,12 +13,17 @@ running-process-show-expense = /{-show-expense-command}
 running-process-delete-transfer = /{-delete-transfer-command}
 running-process-set-language = /{-set-language-command}
 running-process-set-currency = /{-set-currency-command}
+running-process-set-timezone = /{-set-timezone-command}
 running-process-list-expenses = /{-list-expenses-command}
 running-process-transfer = /{-transfer-command}
 running-process-clear-travelers = /{-clear-travelers-command}
 running-process-clear-expenses = /{-clear-expenses-command}
 running-process-clear-transfers = /{-clear-transfers-command}
 running-process-clear-all = /{-clear-all-command}
+running-process-new-trip = /{-new-trip-command}
+running-process-rename-trip = /{-rename-trip-command}
+running-process-restore = /{-restore-command}
+running-process-import = /{-import-command}
 
 ## /setlanguage
 
@@ -31,6 +39,74 @@ set-language-ok = Lingua della chat impostata correttamente su {$language}.
 set-currency-ok = Valuta predefinita della chat impostata correttamente su {$currency}.
 set-currency-not-available = "{$currency}" non è un codice valuta riconosciuto. Usa un codice ISO 4217 valido (es. `USD`, `EUR`) o un codice crypto noto (es. `BTC`, `ETH`).
 
+## /settimezone
+
+set-timezone-ok = Fuso orario della chat impostato correttamente su {$timezone}. Date e orari vengono ora mostrati in questo fuso orario.
+set-timezone-not-available = "{$timezone}" non è un fuso orario riconosciuto. Usa un nome di fuso orario IANA (es. `Europe/Rome`, `America/New_York`, `UTC`).
+
+## /setsettlement
+
+set-settlement-ok = Modalità di saldo impostata su { $mode ->
+    [greedy] greedy: il debitore maggiore paga per primo il creditore maggiore.
+   *[exact] exact: i debiti vengono saldati con il minor numero possibile di trasferimenti.
+}
+set-settlement-status = La modalità di saldo attuale è { $mode ->
+    [greedy] greedy: il debitore maggiore paga per primo il creditore maggiore.
+   *[exact] exact: i debiti vengono saldati con il minor numero possibile di trasferimenti.
+}
+set-settlement-usage = Uso: `/{-set-settlement-command} exact` oppure `/{-set-settlement-command} greedy`.
+
+## /setsimplification
+
+set-simplification-ok = Modalità di semplificazione dei debiti impostata su { $mode ->
+    [none] none: ogni viaggiatore rimborsa chi ha pagato per lui.
+    [pairwise] pairwise: si compensano solo i debiti tra gli stessi due viaggiatori.
+   *[full] full: i debiti vengono saldati a partire dal saldo netto di ciascuno.
+}
+set-simplification-status = La modalità di semplificazione dei debiti attuale è { $mode ->
+    [none] none: ogni viaggiatore rimborsa chi ha pagato per lui.
+    [pairwise] pairwise: si compensano solo i debiti tra gli stessi due viaggiatori.
+   *[full] full: i debiti vengono saldati a partire dal saldo netto di ciascuno.
+}
+set-simplification-usage = Uso: `/{-set-simplification-command} none`, `/{-set-simplification-command} pairwise` oppure `/{-set-simplification-command} full`.
+
+## /newtrip
+
+new-trip-already-exists = Esiste già un piano di viaggio chiamato {$name}.
+new-trip-ok = Piano di viaggio {$name} creato con successo. Ora è il piano di viaggio attivo.
+
+## /switchtrip
+
+switch-trip-already-active = Il piano di viaggio {$name} è già quello attivo.
+switch-trip-choose = Il piano di viaggio attivo è {$name}. Scegli il piano di viaggio da attivare:
+switch-trip-not-found = Impossibile trovare il piano di viaggio {$name}. Usa `/{-list-trips-command}` per vedere i piani di viaggio disponibili.
+switch-trip-ok = Piano di viaggio {$name} attivato.
+
+## /renametrip
+
+rename-trip-already-exists = Esiste già un altro piano di viaggio chiamato {$name}.
+rename-trip-ok = Piano di viaggio {$old-name} rinominato in {$name} con successo.
+
+## /closetrip
+
+close-trip-already-closed = Il piano di viaggio {$name} è già chiuso.
+close-trip-not-settled = Impossibile chiudere il piano di viaggio {$name} finché tutti non hanno saldato i propri debiti. Usa `/{-show-balances-command}` per vedere chi deve ancora quanto.
+close-trip-ok = Piano di viaggio {$name} chiuso con successo. Ecco il riepilogo finale:
+
+## /reopentrip
+
+reopen-trip-not-closed = Il piano di viaggio {$name} non è chiuso.
+reopen-trip-ok = Piano di viaggio {$name} riaperto con successo.
+
+## /topicledger
+
+topic-ledger-disabled = Questo argomento ora usa il registro dell'intera chat.
+topic-ledger-enabled = Questo argomento ora ha un registro proprio.
+topic-ledger-not-a-topic = Questo comando può essere usato solo all'interno di un argomento del forum.
+topic-ledger-status-disabled = Questo argomento usa il registro dell'intera chat. Invia `/{-topic-ledger-command} on` per dargli un registro proprio.
+topic-ledger-status-enabled = Questo argomento ha un registro proprio. Invia `/{-topic-ledger-command} off` per usare il registro dell'intera chat.
+topic-ledger-usage = Uso: `/{-topic-ledger-command} [on|off]`
+
 ## /addtraveler
 
 add-traveler-already-added = Il viaggiatore {$name} è già stato aggiunto al piano di viaggio.
@@ -58,9 +134,22 @@ delete-expense-ok = Spesa #{$number} eliminata con successo.
 
 ## /listexpenses
 
-list-expenses-descr-not-found = Nessuna spesa corrisponde alla descrizione specificata (~ "{$description}").
+list-expenses-filter-not-found = Nessuna spesa corrisponde al filtro `{$filter}`.
 list-expenses-not-found = Nessuna spesa trovata. Usa `/{-add-expense-command}` per aggiungerne una.
 
+## Filtri di /listexpenses e /listtransfers
+
+filter-invalid = { $kind ->
+    [unknown-key] `{$token}` non è un filtro.
+    [not-available] Il filtro `{$token}` non può essere usato con questo comando.
+    [unclosed-quote] Le virgolette in `{$token}` non vengono mai chiuse.
+   *[invalid-value] Il valore di `{$token}` non è valido.
+} Usa `/{-help-command} {$command}` per vedere la sintassi dei filtri.
+
+## Liste paginate
+
+list-page-expired = Questa lista non è più disponibile, invia di nuovo il comando per sfogliarla.
+
 ## /showexpense
 
 show-expense-not-found = Impossibile trovare la spesa #{$number} per mostrare i dettagli.
@@ -72,6 +161,7 @@ transfer-receiver-not-found = Impossibile trovare il viaggiatore "{$name}" a cui
 transfer-sender-not-found = Impossibile trovare il viaggiatore "{$name}" da cui trasferire denaro.
 transfer-same-sender-receiver = Mittente e destinatario non possono essere lo stesso viaggiatore ("{$name}").
 transfer-non-positive-amount = L'importo del trasferimento deve essere maggiore di zero.
+transfer-nothing-owed = Il viaggiatore {$sender} non deve niente a {$receiver}.
 
 ## /deletetransfer
 
@@ -80,12 +170,13 @@ delete-transfer-ok = Trasferimento #{$number} eliminato con successo.
 
 ## /listtransfers
 
-list-transfers-name-not-found = Nessun trasferimento relativo al viaggiatore "{$name}" trovato.
+list-transfers-filter-not-found = Nessun trasferimento corrisponde al filtro `{$filter}`.
 list-transfers-not-found = Nessun trasferimento trovato. Usa `/{-transfer-command} <mittente> <destinatario> <importo>` per aggiungerne uno.
 
 ## /showbalances
 
 show-balances-ok = {$debtor} deve {$debt} a {$creditor}.
+show-balances-mode = Semplificazione dei debiti: {$mode}. Usa `/{-set-simplification-command}` per modificarla.
 show-balances-settled-up = Tutti i viaggiatori sono in pari con tutti.
 show-balances-traveler-ok = 
     {$traveler-name} { $traveler-is -> 
@@ -95,6 +186,154 @@ show-balances-traveler-ok =
 show-balances-traveler-settled-up = Il viaggiatore {$name} è in pari con tutti.
 show-balances-traveler-not-found = Impossibile trovare il viaggiatore "{$name}" per mostrare il saldo.
 
+## /statement
+
+statement-header = Estratto conto di {$name}:
+statement-page = Pagina {$number} di {$count}. Usa `/{-statement-command} {$name} <pagina>` per vedere un'altra pagina.
+statement-page-not-found = L'estratto conto di {$name} ha { $count ->
+    [one] solo 1 pagina.
+   *[other] solo {$count} pagine.
+}
+statement-empty = Il viaggiatore {$name} non ha ancora spese o trasferimenti.
+statement-choose-traveler = Scegli il viaggiatore di cui mostrare l'estratto conto.
+statement-traveler-not-found = Impossibile trovare il viaggiatore "{$name}" per mostrare l'estratto conto.
+statement-usage = Uso: `/{-statement-command} <nome>` oppure `/{-statement-command} <nome> <pagina>`.
+
+## /summary
+
+summary-header = Quanto ha pagato e consumato ogni viaggiatore:
+summary-not-found = Nessun viaggiatore nel piano di viaggio.
+summary-unbalanced = ⚠️ Le posizioni nette sommano a {$amount} invece che a zero: il registro non è coerente.
+
+## /showstats
+
+show-stats-usage = Uso: `/{-show-stats-command} [<sezione>]`. Sezioni disponibili: {$sections}.
+show-stats-no-expenses = Nessuna spesa registrata finora.
+
+## /rebuildbalances
+
+rebuild-balances-ok = Saldi ricalcolati da tutte le spese e i trasferimenti.
+
+## /audit
+
+audit-ok = Nessun problema trovato nel registro.
+audit-header = Problemi trovati nel registro:
+audit-repair-hint = Tocca "🔧 Ripara" oppure usa `/{-audit-command} {-fix-kword}` per ricalcolare i saldi e rimuovere le relazioni che puntano a record eliminati.
+audit-manual-hint = Le spese con quote o pagatori errati non possono essere riparate automaticamente: eliminale e aggiungile di nuovo.
+audit-repaired = { $count ->
+    [one] Riparato 1 problema.
+   *[other] Riparati {$count} problemi.
+}
+audit-usage = Uso: `/{-audit-command}` per verificare il registro, oppure `/{-audit-command} {-fix-kword}` per riparare ciò che si può correggere in sicurezza.
+
+## /export
+
+export-ok = { $expenses-count ->
+    [one] Esportata 1 spesa
+   *[other] Esportate {$expenses-count} spese
+} e { $transfers-count ->
+    [one] 1 trasferimento
+   *[other] {$transfers-count} trasferimenti
+} in formato {$format}.
+export-usage = Uso: `/{-export-command} <formato>`. Formati disponibili: {$formats}.
+
+## /report
+
+report-ok = Ecco il resoconto del piano di viaggio, con { $expenses-count ->
+    [one] 1 spesa
+   *[other] {$expenses-count} spese
+}. Aprilo in un browser per leggerlo o stamparlo.
+
+## /chart
+
+chart-usage = Uso: `/{-chart-command} <grafico>`. Grafici disponibili: {$charts}.
+chart-no-data = Non c'è ancora niente da rappresentare.
+
+## /debtgraph
+
+debt-graph-usage = Uso: `/{-debt-graph-command} [{-raw-kword}] [{-dot-kword}]`.
+debt-graph-settled = Nessuno deve niente a nessuno: non ci sono debiti da disegnare.
+debt-graph-dot-ok = Grafo dei debiti in formato DOT. Disegnalo con Graphviz, ad es. `dot -Tsvg debts.dot -o debts.svg`, o con qualsiasi strumento che legga il DOT.
+
+## /backup
+
+backup-ok = Salvati { $travelers-count ->
+    [one] 1 viaggiatore
+   *[other] {$travelers-count} viaggiatori
+}, { $expenses-count ->
+    [one] 1 spesa
+   *[other] {$expenses-count} spese
+} e { $transfers-count ->
+    [one] 1 trasferimento
+   *[other] {$transfers-count} trasferimenti
+}. Invia di nuovo il file con `/{-restore-command}` per ripristinarli.
+
+## /restore
+
+restore-ok = Ripristinati { $travelers-count ->
+    [one] 1 viaggiatore
+   *[other] {$travelers-count} viaggiatori
+}, { $expenses-count ->
+    [one] 1 spesa
+   *[other] {$expenses-count} spese
+} e { $transfers-count ->
+    [one] 1 trasferimento
+   *[other] {$transfers-count} trasferimenti
+}.
+restore-usage = Uso: invia un file di backup con `/{-restore-command}` come didascalia, oppure rispondi `/{-restore-command}` a un messaggio che ne contiene uno.
+
+## /import
+
+import-ok = Importati { $expenses-count ->
+    [one] 1 spesa
+   *[other] {$expenses-count} spese
+} e { $transfers-count ->
+    [one] 1 trasferimento
+   *[other] {$transfers-count} trasferimenti
+}{ $travelers-count ->
+    [0] {""}
+    [one] , aggiungendo 1 viaggiatore
+   *[other] , aggiungendo {$travelers-count} viaggiatori
+}.
+import-usage = Uso: invia un'esportazione CSV di Splitwise o Tricount con `/{-import-command}` come didascalia, oppure rispondi `/{-import-command}` a un messaggio che ne contiene una.
+import-nothing = Il file non ha spese o trasferimenti che possano essere importati.
+import-skipped = { $count ->
+    [one] 1 riga non può essere importata:
+   *[other] {$count} righe non possono essere importate:
+}
+import-skipped-row = • Riga {$line}: { $reason ->
+    [other-currency] è in {$currency}, non nella valuta della chat
+    [several-payers] è stata pagata da più membri
+    [no-payer] nessuno l'ha pagata
+    [unsupported] non ha un equivalente, come un'entrata
+    [self-transfer] mittente e destinatario sono lo stesso viaggiatore
+   *[invalid] è incompleta o i suoi importi non tornano
+}.
+
+## /settle
+
+settle-plan-hint = Tocca "Pagato ✓" quando un pagamento è stato fatto, oppure "Salda tutto" per registrare tutti i pagamenti insieme.
+settle-all-ok = { $count ->
+    [one] Registrato 1 trasferimento: tutti i viaggiatori sono in pari con tutti.
+   *[other] Registrati {$count} trasferimenti: tutti i viaggiatori sono in pari con tutti.
+}
+settle-usage = Uso: `/{-settle-command}` per mostrare chi deve pagare chi, oppure `/{-settle-command} {-all-kword}` per registrare tutti i pagamenti.
+settle-preference-unmet = ⚠️ {$debtor} non può pagare {$creditor}, ma non c'è altro modo per saldare i debiti.
+settle-preference-via-unmet = ⚠️ {$name} preferisce saldare tramite {$other-traveler-name}, ma deve ancora saldare i debiti con qualcun altro.
+
+## /payprefs
+
+pay-prefs-cannot-pay = {$name} non può pagare {$other-traveler-name}.
+pay-prefs-settles-via = {$name} preferisce saldare tramite {$other-traveler-name}.
+pay-prefs-none = Nessuna preferenza di pagamento impostata. Usa `/{-pay-prefs-command} <nome> {-cannot-kword} <nome>` oppure `/{-pay-prefs-command} <nome> {-via-kword} <nome>` per aggiungerne una.
+pay-prefs-cannot-pay-ok = A {$name} non verrà chiesto di pagare {$other-traveler-name}, quando possibile.
+pay-prefs-settles-via-ok = {$name} salderà tramite {$other-traveler-name}, quando possibile.
+pay-prefs-clear-ok = Preferenze di pagamento di {$name} rimosse con successo.
+pay-prefs-same-traveler = Il viaggiatore {$name} non può avere una preferenza di pagamento su sé stesso.
+pay-prefs-traveler-not-found = Impossibile trovare il viaggiatore "{$name}" per impostare la preferenza di pagamento.
+pay-prefs-usage = Uso: `/{-pay-prefs-command} <nome> {-cannot-kword} <nome>`, `/{-pay-prefs-command} <nome> {-via-kword} <nome>` oppure `/{-pay-prefs-command} <nome> {-clear-kword}`.
+pay-prefs-full-only = ℹ️ Le preferenze di pagamento si applicano solo quando i debiti sono semplificati del tutto. Usa `/{-set-simplification-command} full` per applicarle.
+
 ## /cancel
 
 cancel-no-process-to-cancel = Non c'è nessun processo da annullare.
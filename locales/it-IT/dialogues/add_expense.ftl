@@ -2,6 +2,7 @@
 
 add-expense-start = Il processo può essere interrotto in qualsiasi momento inviando `/{-cancel-command}`.
 add-expense-ask-description = Imposta una descrizione per questa spesa.
+add-expense-invalid-date-option = Data non valida. Usa `/{-add-expense-command} {-date-option} <data>`, dove `<data>` è `{-today-kword}`, `{-yesterday-kword}` o una data passata come `AAAA-MM-GG` o `AAAA-MM-GG HH:MM`.
 
 ## receive_description
 
@@ -10,10 +11,15 @@ add-expense-invalid-description = Hai inviato un testo non valido, per favore ri
 
 ## receive_amount
 
-add-expense-ask-paid-by = Chi ha pagato per questo?
+add-expense-ask-date = Quando è stata fatta la spesa? Scegli un giorno o invia una data come `AAAA-MM-GG`, eventualmente seguita da un orario come `HH:MM`.
 add-expense-invalid-amount = Hai inviato un importo non valido, per favore riprova.
 add-expense-non-positive-amount = L'importo deve essere maggiore di zero.
 
+## receive_date
+
+add-expense-ask-paid-by = Chi ha pagato per questo?
+add-expense-invalid-date = Hai inviato una data non valida, per favore riprova. Invia `{-today-kword}`, `{-yesterday-kword}` o una data passata come `AAAA-MM-GG` o `AAAA-MM-GG HH:MM`.
+
 ## receive_paid_by
 
 add-expense-invalid-paid-by = Hai inviato un nome non valido, per favore riprova.
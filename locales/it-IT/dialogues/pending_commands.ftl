@@ -3,6 +3,16 @@
 add-traveler-ask-name = Qual è il nome del viaggiatore? Il processo può essere interrotto in qualsiasi momento inviando `/{-cancel-command}`.
 add-traveler-invalid-name = Hai inviato un nome non valido, per favore riprova.
 
+## /new_trip
+
+new-trip-ask-name = Qual è il nome del nuovo piano di viaggio? Il processo può essere interrotto in qualsiasi momento inviando `/{-cancel-command}`.
+new-trip-invalid-name = Hai inviato un nome non valido, per favore riprova.
+
+## /rename_trip
+
+rename-trip-ask-name = Qual è il nuovo nome del piano di viaggio attivo? Il processo può essere interrotto in qualsiasi momento inviando `/{-cancel-command}`.
+rename-trip-invalid-name = Hai inviato un nome non valido, per favore riprova.
+
 ## /delete_traveler
 
 delete-traveler-ask-name = Quale viaggiatore vuoi eliminare? Il processo può essere interrotto in qualsiasi momento inviando `/{-cancel-command}`.
@@ -38,9 +48,14 @@ set-language-invalid-langid = Hai inviato un identificatore di lingua non valido
 set-currency-ask-currency = Quale valuta vuoi impostare? Selezionane una dalla lista sottostante o invia il codice valuta se non presente.
 set-currency-invalid-currency = Hai inviato un codice valuta non valido, per favore riprova.
 
+## /set_timezone
+
+set-timezone-ask-timezone = Quale fuso orario vuoi impostare? Selezionane uno dalla lista sottostante o invia il suo nome IANA (es. `Europe/Paris`) se non presente.
+set-timezone-invalid-timezone = Hai inviato un fuso orario non valido, per favore riprova.
+
 ## /list_expenses
 
-list-expenses-ask-description = Invia una descrizione (o parte di essa) per filtrare le spese.
+list-expenses-ask-description = Invia i filtri con cui elencare le spese, ad esempio una descrizione (o parte di essa) o `payer:Alice amount>20`. Usa `/{-help-command} {-list-expenses-command}` per vedere tutti i filtri.
 
 ## /transfer
 
@@ -50,7 +65,7 @@ transfer-from-not-found = Viaggiatore "{$name}" non trovato. Chi è il mittente?
 transfer-ask-to = Chi è il destinatario del trasferimento da {$name}?
 transfer-ask-to-reprompt = Hai inviato un nome non valido, per favore riprova. Chi è il destinatario?
 transfer-to-not-found = Viaggiatore "{$name}" non trovato. Chi è il destinatario?
-transfer-ask-amount = Quanto ha trasferito {$name}?
+transfer-ask-amount = Quanto ha trasferito {$name}? Invia "{-all-kword}" per trasferire esattamente quanto dovuto.
 transfer-invalid-amount = Hai inviato un importo non valido, per favore riprova.
 
 ## /cleartravelers
@@ -73,3 +88,34 @@ clear-transfers-confirm = Sei sicuro di voler eliminare tutti i trasferimenti?
 clear-all-confirm =
     Sei sicuro di voler eliminare tutti i viaggiatori, spese e trasferimenti?
     ⚠️ Questa azione non può essere annullata.
+
+## /restore
+
+restore-confirm =
+    Ripristinare il backup del {DATETIME($datetime, dateStyle: "long")}, con { $travelers-count ->
+        [one] 1 viaggiatore
+       *[other] {$travelers-count} viaggiatori
+    }, { $expenses-count ->
+        [one] 1 spesa
+       *[other] {$expenses-count} spese
+    } e { $transfers-count ->
+        [one] 1 trasferimento
+       *[other] {$transfers-count} trasferimenti
+    }?
+    ⚠️ Il piano di viaggio attuale e le impostazioni della chat verranno sostituiti.
+
+## /import
+
+import-ask-traveler = Quale viaggiatore è "{$member}"? Scegline uno, oppure invia il nome di un viaggiatore da aggiungere.
+import-traveler-taken = {$name} è già associato a un altro membro del gruppo.
+import-member-new = {$member} → {$name} (nuovo)
+import-member-existing = {$member} → {$name}
+import-confirm =
+    Importare { $expenses-count ->
+        [one] 1 spesa
+       *[other] {$expenses-count} spese
+    } e { $transfers-count ->
+        [one] 1 trasferimento
+       *[other] {$transfers-count} trasferimenti
+    } da {$format}, con questi viaggiatori?
+    {$travelers}
@@ -3,6 +3,16 @@
 command-error-help = Nessun aiuto disponibile per il comando /{$command}.
 command-error-set-language = Impossibile impostare la lingua "{$langid}".
 command-error-set-currency = Impossibile impostare la valuta "{$currency}".
+command-error-set-timezone = Impossibile impostare il fuso orario "{$timezone}".
+command-error-set-settlement = Impossibile modificare la modalità di saldo.
+command-error-set-simplification = Impossibile modificare la modalità di semplificazione dei debiti.
+command-error-new-trip = Impossibile creare il piano di viaggio chiamato "{$name}".
+command-error-switch-trip = Impossibile attivare il piano di viaggio "{$name}".
+command-error-list-trips = Impossibile elencare i piani di viaggio.
+command-error-rename-trip = Impossibile rinominare il piano di viaggio in "{$name}".
+command-error-close-trip = Impossibile chiudere il piano di viaggio.
+command-error-reopen-trip = Impossibile riaprire il piano di viaggio.
+command-error-topic-ledger = Impossibile modificare il registro dell'argomento.
 command-error-add-traveler = Impossibile aggiungere il viaggiatore chiamato "{$name}".
 command-error-delete-traveler = Impossibile eliminare il viaggiatore chiamato "{$name}".
 command-error-list-travelers = Impossibile elencare i viaggiatori.
@@ -10,11 +20,25 @@ command-error-delete-expense = Impossibile eliminare la spesa #{$number}.
 command-error-list-expenses = Impossibile elencare le spese.
 command-error-show-expense = Impossibile mostrare la spesa #{$number}.
 command-error-transfer = Impossibile trasferire {$amount} dal viaggiatore "{$sender}" a "{$receiver}".
+command-error-transfer-all = Impossibile trasferire quanto il viaggiatore "{$sender}" deve a "{$receiver}".
 command-error-delete-transfer = Impossibile eliminare il trasferimento #{$number}.
 command-error-list-transfers = Impossibile elencare i trasferimenti.
 command-error-show-balance = Impossibile mostrare il saldo per il viaggiatore "{$name}".
 command-error-show-balances = Impossibile mostrare i saldi.
+command-error-statement = Impossibile mostrare l'estratto conto.
+command-error-summary = Impossibile mostrare il riepilogo.
+command-error-settle = Impossibile saldare i debiti.
+command-error-pay-prefs = Impossibile aggiornare le preferenze di pagamento.
 command-error-show-stats = Impossibile mostrare le statistiche.
+command-error-rebuild-balances = Impossibile ricalcolare i saldi.
+command-error-audit = Impossibile verificare il registro.
+command-error-export = Impossibile esportare il piano di viaggio.
+command-error-report = Impossibile creare il resoconto del piano di viaggio.
+command-error-chart = Impossibile disegnare il grafico.
+command-error-debt-graph = Impossibile disegnare il grafo dei debiti.
+command-error-backup = Impossibile salvare il piano di viaggio.
+command-error-restore = Impossibile ripristinare il piano di viaggio.
+command-error-import = Impossibile importare il file.
 command-error-clear-travelers = Impossibile eliminare i viaggiatori.
 command-error-clear-expenses = Impossibile eliminare le spese.
 command-error-clear-transfers = Impossibile eliminare i trasferimenti.
@@ -41,3 +65,15 @@ add-expense-error-no-travelers-specified = Nessun viaggiatore è stato specifica
 
 end-error-closing-dialogue = Si è verificato un errore durante la chiusura del processo.
 end-error-no-expense-created = Nessuna spesa è stata creata.
+
+## BackupError
+
+backup-error-invalid = Il file non è un backup valido di travel-rs.
+backup-error-unsupported-version = Il backup usa la versione {$version} del formato, ma è supportata solo la versione {$supported-version}.
+backup-error-unknown-traveler = Il backup fa riferimento al viaggiatore "{$name}", che non è tra i suoi viaggiatori.
+
+## ImportError
+
+import-error-invalid = Il file non è un file CSV valido.
+import-error-unknown-format = Il file non è un'esportazione CSV di Splitwise o Tricount.
+import-error-unmapped-member = Il membro "{$member}" non corrisponde a nessun viaggiatore, quindi non è stato importato nulla.
@@ -8,6 +8,20 @@ format-expense-details =
     {$shares}
 format-expense = [{DATETIME($datetime, dateStyle: "short")}] Spesa #{$number}: {$description} - {$amount}
 format-transfer = [{DATETIME($datetime, dateStyle: "short")}] Trasferimento #{$number}: {$sender} ha inviato {$amount} a {$receiver}
+format-statement-entry = [{DATETIME($datetime, dateStyle: "short")}] {$kind ->
+    [paid] Ha pagato la spesa #{$number}: {$description}
+    [share] Quota della spesa #{$number}: {$description}, pagata da {$other-traveler-name}
+    [sent] Ha inviato il trasferimento #{$number} a {$other-traveler-name}
+   *[received] Ha ricevuto il trasferimento #{$number} da {$other-traveler-name}
+} ({$amount}) → {$net}
+format-trip = #{$number}: {$name}{$closed ->
+    [true] {" "}(chiuso)
+   *[false] {""}
+}
+format-active-trip = #{$number}: {$name} (attivo{$closed ->
+    [true] , chiuso
+   *[false] {""}
+})
 format-traveler-stats-amount = {$traveler-name}: {$amount}
 format-traveler-stats-frequency =
     {$traveler-name}: {$count ->
@@ -23,6 +37,7 @@ format-expense-stats =
             - Numero: {$count}
             - Somma: {$sum}
             - Media: {$mean}
+            - Mediana: {$median}
             - Spese massime: {$max}
             - Spese minime: {$min}
             - Media al giorno: {$average-per-day}
@@ -64,9 +79,48 @@ format-traveler-stats =
                     - Maggiori debitori: {$major-debtors}
             }
     }
+format-traveler-summary = {$traveler-name}: pagato {$paid}, consumato {$consumed}, inviato {$sent}, ricevuto {$received}, netto {$net}
+format-audit-problem = {$kind ->
+    [splits-mismatch] Spesa #{$number}: le quote sommano a {$sum} invece di {$amount}
+    [payer-count] Spesa #{$number}: {$count} pagatori invece di uno
+    [stale-net] {$traveler-name}: saldo netto {$net} invece di {$amount}
+    [stale-debts] I debiti non corrispondono alle spese e ai trasferimenti
+   *[orphaned-relations] {$count} relazioni {$table} che puntano a record eliminati
+}
 format-stats =
     {$expense-stats}
 
     {$transfer-stats}
 
     {$traveler-stats}
+format-stats-period = - {$period}: {$amount} ({ $count ->
+    [one] 1 spesa
+   *[other] {$count} spese
+})
+format-stats-week = settimana del {$date}
+format-weekday = { $weekday ->
+    [1] Lunedì
+    [2] Martedì
+    [3] Mercoledì
+    [4] Giovedì
+    [5] Venerdì
+    [6] Sabato
+   *[7] Domenica
+}
+format-stats-days =
+    Spese per giorno:
+    {$periods}
+    Giorno con più spese: {$date}, {$amount}
+format-stats-weeks =
+    Spese per settimana:
+    {$periods}
+format-stats-weekdays =
+    Spese per giorno della settimana:
+    {$periods}
+format-stats-hours =
+    Spese per ora del giorno:
+    {$periods}
+format-stats-travelers =
+    Consumi per viaggiatore, settimana per settimana ({$weeks}):
+    {$trends}
+format-stats-trend = - {$traveler-name}: {$amounts} (totale {$sum})
@@ -29,6 +29,98 @@ help-set-currency =
 
     Uso: /{-set-currency-command} <codice valuta>
 
+## /settimezone
+
+descr-set-timezone = Imposta il fuso orario usato per date e orari.
+help-set-timezone =
+    /{-set-timezone-command} — {descr-set-timezone}
+
+    Uso: /{-set-timezone-command} <fuso orario>
+
+    I fusi orari sono nomi IANA, es. `Europe/Rome` o `Asia/Tokyo`. Le date inviate al bot sono lette nel fuso orario della chat e le statistiche contano i giorni in esso.
+
+## /setsettlement
+
+descr-set-settlement = Mostra o imposta come vengono saldati i debiti.
+help-set-settlement =
+    /{-set-settlement-command} — {descr-set-settlement}
+
+    Con exact (predefinita), i debiti vengono saldati con il minor numero possibile di trasferimenti. I piani di viaggio con molti viaggiatori in debito ricadono su greedy, dove il debitore maggiore paga per primo il creditore maggiore.
+
+    Uso: /{-set-settlement-command} [exact|greedy]
+
+## /setsimplification
+
+descr-set-simplification = Mostra o imposta quanto vengono semplificati i debiti.
+help-set-simplification =
+    /{-set-simplification-command} — {descr-set-simplification}
+
+    Con none, ogni viaggiatore rimborsa chi ha pagato per lui. Con pairwise, si compensano solo i debiti tra gli stessi due viaggiatori. Con full (predefinita), i debiti vengono saldati a partire dal saldo netto di ciascuno, come scelto da /{-set-settlement-command}.
+
+    Uso: /{-set-simplification-command} [none|pairwise|full]
+
+## /newtrip
+
+descr-new-trip = Crea un nuovo piano di viaggio con il nome specificato e rendilo quello attivo.
+help-new-trip =
+    /{-new-trip-command} — {descr-new-trip}
+
+    Viaggiatori, spese e trasferimenti appartengono al piano di viaggio attivo.
+
+    Uso: /{-new-trip-command} <nome>
+
+## /switchtrip
+
+descr-switch-trip = Attiva il piano di viaggio con il nome o il numero specificato.
+help-switch-trip =
+    /{-switch-trip-command} — {descr-switch-trip}
+
+    Uso: /{-switch-trip-command} <nome o numero>
+
+## /listtrips
+
+descr-list-trips = Mostra i piani di viaggio della chat.
+help-list-trips =
+    /{-list-trips-command} — {descr-list-trips}
+
+    Uso: /{-list-trips-command}
+
+## /renametrip
+
+descr-rename-trip = Rinomina il piano di viaggio attivo.
+help-rename-trip =
+    /{-rename-trip-command} — {descr-rename-trip}
+
+    Uso: /{-rename-trip-command} <nome>
+
+## /closetrip
+
+descr-close-trip = Chiude il piano di viaggio attivo quando tutti hanno saldato i propri debiti, rendendolo di sola lettura.
+help-close-trip =
+    /{-close-trip-command} — {descr-close-trip}
+
+    Il piano di viaggio può essere chiuso solo quando tutti i saldi sono a zero. Una volta chiuso, non è più possibile aggiungere, modificare o eliminare viaggiatori, spese e trasferimenti, che possono comunque essere elencati e mostrati. Viene inviato un riepilogo finale del piano di viaggio.
+
+    Uso: /{-close-trip-command}
+
+## /reopentrip
+
+descr-reopen-trip = Riapre il piano di viaggio attivo dopo che è stato chiuso (solo amministratori).
+help-reopen-trip =
+    /{-reopen-trip-command} — {descr-reopen-trip}
+
+    Uso: /{-reopen-trip-command}
+
+## /topicledger
+
+descr-topic-ledger = Mostra o modifica se l'argomento del forum corrente ha un registro proprio.
+help-topic-ledger =
+    /{-topic-ledger-command} — {descr-topic-ledger}
+
+    Un argomento con un registro proprio ha i propri piani di viaggio, viaggiatori, spese e trasferimenti. Quando viene disattivato, l'argomento torna a usare il registro dell'intera chat; i suoi dati vengono conservati e tornano disponibili quando viene riattivato.
+
+    Uso: /{-topic-ledger-command} [on|off]
+
 ## /addtraveler
 
 descr-add-traveler = Aggiungi un viaggiatore con il nome specificato al piano di viaggio.
@@ -60,8 +152,9 @@ help-add-expense =
     /{-add-expense-command} — {descr-add-expense}
 
     Durante la sessione, ti verrà chiesto di:
-    - Inviare un messaggio con la descrizione della spesa.
+    - Inviare un messaggio con la descrizione della spesa. Aggiungi hashtag come `#cibo` per assegnarle delle categorie.
     - Inviare un messaggio con l'importo della spesa.
+    - Scegliere il giorno della spesa, o inviare una data come `AAAA-MM-GG`, eventualmente seguita da un orario come `HH:MM`.
     - Inviare un messaggio con il nome del viaggiatore che ha pagato la spesa.
     - Inviare uno o più messaggi con i viaggiatori che partecipano alla spesa e le loro quote.
 
@@ -78,7 +171,10 @@ help-add-expense =
 
     - Inserire `{-all-kword}` per dividerlo equamente tra tutti i viaggiatori.
 
-    Uso: /{-add-expense-command}
+    Per registrare una spesa fatta in un altro giorno, saltando la richiesta della data, aggiungi `{-date-option}` seguito da `{-today-kword}`, `{-yesterday-kword}` o una data passata.
+    > Esempio: `/{-add-expense-command} {-date-option} 2026-08-14 20:30`
+
+    Uso: /{-add-expense-command} [{-date-option} <data>]
 
 ## /deleteexpense
 
@@ -90,11 +186,21 @@ help-delete-expense =
 
 ## /listexpenses
 
-descr-list-expenses = Mostra le spese nel piano di viaggio. Se viene specificata una descrizione, mostra solo le spese che corrispondono alla descrizione fornita. Supporta la ricerca approssimativa (fuzzy search) per una corrispondenza più flessibile.
+descr-list-expenses = Mostra le spese nel piano di viaggio. Se vengono specificati dei filtri, mostra solo le spese che li soddisfano tutti.
 help-list-expenses = 
     /{-list-expenses-command} — {descr-list-expenses}
 
-    Uso: /{-list-expenses-command} [descrizione]
+    Filtri disponibili:
+    - `payer:<nome>`: spese pagate dal viaggiatore.
+    - `with:<nome>`: spese condivise con il viaggiatore.
+    - `amount<op><importo>`: spese con importo `<`, `<=`, `=`, `>=` o `>` di quello indicato.
+    - `date:<data>` o `date:<da>..<a>`: spese fatte in quel giorno o tra quei giorni, inclusi. Uno dei due estremi può essere omesso.
+    - `cat:<categoria>`: spese con l'hashtag `#<categoria>` nella descrizione.
+    - `sort:<date|amount|number>`: ordina le spese per quel campo, in ordine decrescente se preceduto da `-`.
+    - Qualsiasi altro testo: spese la cui descrizione contiene ogni parola del testo, o una parola che inizia con essa, elencate per pertinenza. I testi fatti solo di parole più corte di 3 caratteri sono cercati in modo approssimativo. Racchiudi testi o nomi tra virgolette per tenerli insieme.
+    > Esempio: `/{-list-expenses-command} payer:Alice with:Bob amount>20 date:2026-08-01..2026-08-05 cat:cibo "gelato" sort:-amount`
+
+    Uso: /{-list-expenses-command} [filtri]
 
 ## /showexpense
 
@@ -110,7 +216,9 @@ descr-transfer = Trasferisci l'importo specificato da un viaggiatore a un altro.
 help-transfer = 
     /{-transfer-command} — {descr-transfer}
 
-    Uso: /{-transfer-command} <mittente> <destinatario> <importo>
+    Uso: /{-transfer-command} <mittente> <destinatario> <importo|{-all-kword}>
+
+    Con {-all-kword}, il mittente trasferisce esattamente quanto deve al destinatario.
 
 ## /deletetransfer
 
@@ -122,11 +230,20 @@ help-delete-transfer =
 
 ## /listtransfers
 
-descr-list-transfers = Mostra i trasferimenti nel piano di viaggio. Se viene specificato un nome, mostra solo i trasferimenti che coinvolgono quel viaggiatore.
+descr-list-transfers = Mostra i trasferimenti nel piano di viaggio. Se vengono specificati dei filtri, mostra solo i trasferimenti che li soddisfano tutti.
 help-list-transfers = 
     /{-list-transfers-command} — {descr-list-transfers}
 
-    Uso: /{-list-transfers-command} [nome]
+    Filtri disponibili:
+    - `from:<nome>`: trasferimenti inviati dal viaggiatore.
+    - `to:<nome>`: trasferimenti ricevuti dal viaggiatore.
+    - `with:<nome>` o solo `<nome>`: trasferimenti inviati o ricevuti dal viaggiatore.
+    - `amount<op><importo>`: trasferimenti con importo `<`, `<=`, `=`, `>=` o `>` di quello indicato.
+    - `date:<data>` o `date:<da>..<a>`: trasferimenti fatti in quel giorno o tra quei giorni, inclusi. Uno dei due estremi può essere omesso.
+    - `sort:<date|amount|number>`: ordina i trasferimenti per quel campo, in ordine decrescente se preceduto da `-`.
+    > Esempio: `/{-list-transfers-command} from:Alice amount>=50 sort:-date`
+
+    Uso: /{-list-transfers-command} [filtri]
 
 ## /showbalances
 
@@ -136,12 +253,149 @@ help-show-balances =
 
     Uso: /{-show-balances-command} [nome]
 
+## /statement
+
+descr-statement = Mostra, in ordine di data, ogni spesa pagata, quota dovuta e trasferimento inviato o ricevuto da un viaggiatore, con la sua posizione netta progressiva.
+help-statement = 
+    /{-statement-command} — {descr-statement}
+
+    Uso: /{-statement-command} [nome] [pagina]
+
+    Una posizione netta positiva significa che il viaggiatore deve ricevere denaro, una negativa che lo deve. Gli estratti conto lunghi sono divisi in pagine.
+
+## /summary
+
+descr-summary = Mostra, per ogni viaggiatore, il totale pagato per le spese, il totale delle sue quote, i trasferimenti inviati e ricevuti e la posizione netta risultante.
+help-summary = 
+    /{-summary-command} — {descr-summary}
+
+    Uso: /{-summary-command}
+
+    Gli importi sono arrotondati alla precisione della valuta, mantenendo a zero la somma delle posizioni nette.
+
+## /settle
+
+descr-settle = Mostra chi deve pagare chi per saldare i debiti e registra i pagamenti.
+help-settle = 
+    /{-settle-command} — {descr-settle}
+
+    Uso: /{-settle-command} [{-all-kword}]
+
+    Senza argomenti, ogni pagamento ha un pulsante "Pagato ✓" che lo registra come trasferimento. Con {-all-kword}, tutti i pagamenti vengono registrati insieme.
+
+## /payprefs
+
+descr-pay-prefs = Mostra o imposta chi non può pagare chi e chi preferisce saldare tramite qualcun altro.
+help-pay-prefs = 
+    /{-pay-prefs-command} — {descr-pay-prefs}
+
+    Uso: /{-pay-prefs-command} [<nome> {-cannot-kword} <nome> | <nome> {-via-kword} <nome> | <nome> {-clear-kword}]
+
+    Con {-cannot-kword}, al primo viaggiatore non viene mai chiesto di pagare il secondo, a meno che non ci sia altro modo per saldare i debiti. Con {-via-kword}, il primo viaggiatore paga e viene pagato tramite il secondo, ad esempio il tesoriere del gruppo. Con {-clear-kword}, le preferenze del viaggiatore vengono rimosse. Le preferenze si applicano quando i debiti sono semplificati del tutto.
+
 ## /showstats
 descr-show-stats = Mostra le statistiche per spese, trasferimenti e viaggiatori nel piano di viaggio.
 help-show-stats = 
     /{-show-stats-command} — {descr-show-stats}
 
-    Uso: /{-show-stats-command}
+    Uso: /{-show-stats-command} [<sezione>]
+
+    Senza sezione viene mostrato il riepilogo, con i pulsanti per le altre sezioni:
+    - `days`: spesa per giorno, e il giorno con la spesa maggiore
+    - `weeks`: spesa per settimana
+    - `weekdays`: spesa per giorno della settimana
+    - `hours`: spesa per ora del giorno
+    - `travelers`: quanto ha consumato ogni viaggiatore, settimana per settimana
+
+## /rebuildbalances
+
+descr-rebuild-balances = Ricalcola tutti i saldi dalle spese e dai trasferimenti del piano di viaggio.
+help-rebuild-balances = 
+    /{-rebuild-balances-command} — {descr-rebuild-balances}
+
+    Uso: /{-rebuild-balances-command}
+
+    I saldi vengono normalmente aggiornati da ogni spesa e trasferimento. Usa questo comando per ripararli se sembrano errati.
+
+## /audit
+
+descr-audit = Verifica la coerenza del registro del piano di viaggio e ripara i problemi.
+help-audit = 
+    /{-audit-command} — {descr-audit}
+
+    Uso: /{-audit-command} [{-fix-kword}]
+
+    Verifica che le quote di ogni spesa sommino al suo importo, che ogni spesa abbia esattamente un pagatore, che i saldi corrispondano alle spese e ai trasferimenti e che nessuna relazione punti a un record eliminato. Con {-fix-kword}, i saldi vengono ricalcolati e tali relazioni rimosse.
+
+## /export
+
+descr-export = Esporta su file le spese, le quote, i trasferimenti e i saldi del piano di viaggio.
+help-export = 
+    /{-export-command} — {descr-export}
+
+    Uso: /{-export-command} csv | ledger | beancount
+
+    Con csv, invia un file CSV ciascuno per spese, quote, trasferimenti e saldi. Con ledger o beancount, invia un registro per Ledger e hledger o per Beancount, in cui ogni spesa addebita il conto spese di ogni viaggiatore con la sua quota e accredita chi ha pagato, e ogni trasferimento sposta denaro tra i conti di due viaggiatori. Gli importi sono nella valuta della chat e le date nel suo fuso orario.
+
+## /report
+
+descr-report = Invia un resoconto del piano di viaggio come file HTML.
+help-report =
+    /{-report-command} — {descr-report}
+
+    Uso: /{-report-command}
+
+    Il resoconto mostra i totali, quanto ogni viaggiatore ha pagato e consumato, le spese per categoria (il primo #hashtag della descrizione) e per giorno, chi deve pagare chi per saldare i debiti e tutte le spese. È un unico file che si può aprire in qualsiasi browser, anche offline.
+
+## /chart
+
+descr-chart = Invia un grafico delle spese come immagine.
+help-chart =
+    /{-chart-command} — {descr-chart}
+
+    Uso: /{-chart-command} daily | travelers | categories
+
+    Con daily, mostra il totale speso ogni giorno. Con travelers, mostra quanto ogni viaggiatore ha pagato e consumato. Con categories, mostra una torta delle spese per categoria (il primo #hashtag della descrizione).
+
+## /debtgraph
+
+descr-debt-graph = Invia un grafo di chi deve a chi come immagine.
+help-debt-graph =
+    /{-debt-graph-command} — {descr-debt-graph}
+
+    Uso: /{-debt-graph-command} [{-raw-kword}] [{-dot-kword}]
+
+    Disegna ogni viaggiatore con una freccia verso ogni viaggiatore a cui deve dei soldi, con l'importo. Di base, i debiti sono quelli mostrati da /{-show-balances-command}. Con {-raw-kword}, sono i debiti dovuti per ogni spesa e trasferimento, prima di essere semplificati, per vedere da dove vengono quelli semplificati. Con {-dot-kword}, il grafo viene invece inviato come file DOT, da disegnare con Graphviz o con qualsiasi altro strumento.
+
+## /backup
+
+descr-backup = Salva su file il piano di viaggio e le impostazioni della chat.
+help-backup =
+    /{-backup-command} — {descr-backup}
+
+    Uso: /{-backup-command}
+
+    Invia un file JSON con le impostazioni della chat, i viaggiatori, le spese con le loro quote e i trasferimenti. Usa /{-restore-command} per ricaricarlo.
+
+## /restore
+
+descr-restore = Sostituisci il piano di viaggio e le impostazioni della chat con un backup.
+help-restore =
+    /{-restore-command} — {descr-restore}
+
+    Uso: invia un file creato da /{-backup-command} con /{-restore-command} come didascalia, oppure rispondi /{-restore-command} al file.
+
+    Dopo una conferma, i viaggiatori, le spese e i trasferimenti del piano di viaggio vengono sostituiti con quelli del backup, mantenendo numeri e date.
+
+## /import
+
+descr-import = Importa le spese e i trasferimenti di un'esportazione di Splitwise o Tricount.
+help-import =
+    /{-import-command} — {descr-import}
+
+    Uso: invia il file CSV esportato da Splitwise o Tricount con /{-import-command} come didascalia, oppure rispondi /{-import-command} al file.
+
+    Ogni membro del gruppo viene associato al viaggiatore con lo stesso nome, oppure ti viene chiesto di scegliere un viaggiatore o di crearne uno nuovo. Le spese pagate da un solo membro diventano spese con quote fisse, i pagamenti diventano trasferimenti e le righe che non possono essere importate vengono elencate. Tutto viene aggiunto in una volta dopo una conferma.
 
 ## /cleartravelers
 
@@ -8,6 +8,74 @@ show-all-button = Mostra tutto
 help-button = ❓ Aiuto
 confirm-yes-button = ✓ Sì
 confirm-no-button = ✗ No
+paid-button = Pagato ✓ {$debtor} → {$creditor}
+settle-all-button = Salda tutto
+repair-button = 🔧 Ripara
+stats-section-button = { $section ->
+    [days] 📅 Giorni
+    [weeks] 🗓 Settimane
+    [weekdays] 📆 Giorni della settimana
+    [hours] 🕒 Orari
+    [travelers] 🧳 Viaggiatori
+   *[overview] 📊 Riepilogo
+}
+today-button = Oggi
+yesterday-button = Ieri
+new-traveler-button = ➕ Nuovo: {$name}
+
+## Intestazioni delle colonne dei file esportati
+
+column-number = Numero
+column-date = Data
+column-description = Descrizione
+column-amount = Importo
+column-currency = Valuta
+column-payer = Pagante
+column-expense = Spesa
+column-traveler = Viaggiatore
+column-sender = Mittente
+column-receiver = Destinatario
+column-debtor = Debitore
+column-creditor = Creditore
+
+## Titoli ed etichette del resoconto HTML
+
+report-title = Resoconto del viaggio
+report-title-trip = Resoconto del viaggio: {$name}
+report-generated = Generato il {DATETIME($datetime, dateStyle: "long")}
+report-totals = Totali
+report-spent = Speso
+report-transferred = Trasferito
+report-expenses-count = Spese
+report-travelers-count = Viaggiatori
+report-days = Giorni con spese
+report-average-per-day = Media al giorno
+report-travelers = Pagato e consumato
+report-paid = Pagato
+report-consumed = Consumato
+report-net = Saldo
+report-categories = Categorie
+report-category = Categoria
+report-uncategorized = Senza categoria
+report-percentage = %
+report-timeline = Spese giornaliere
+report-settlement = Chi deve pagare chi
+report-settled-up = Tutti i viaggiatori sono in pari con tutti.
+report-expenses = Spese
+report-shares = Quote
+report-no-expenses = Nessuna spesa registrata.
+
+## Titoli ed etichette dei grafici
+
+chart-daily = Spese per giorno
+chart-travelers = Pagato e consumato per viaggiatore
+chart-categories = Spese per categoria
+chart-other = Altro
+
+## Titoli del grafo dei debiti
+
+debt-graph = Chi deve a chi
+debt-graph-raw = Chi deve a chi, prima della semplificazione
 
 ## Etichette delle lingue: <emoji bandiera> <nome esteso>
 
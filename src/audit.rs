@@ -0,0 +1,425 @@
+use std::sync::Arc;
+
+use crate::{
+    debt::{self, Debt, NET},
+    i18n::{self, Translate, TranslateWithArgs},
+    money_wrapper::MoneyWrapper,
+    traveler::Name,
+};
+use maplit::hashmap;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use surrealdb::{RecordId, Surreal, engine::any::Any};
+use teloxide::types::ChatId;
+use travel_rs_core::{is_zero, ledger};
+
+/// A broken invariant of the ledger of a travel plan.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    /// The shares of an expense don't add up to its amount.
+    SplitsMismatch {
+        number: i64,
+        amount: Decimal,
+        splits: Decimal,
+    },
+    /// An expense has no payer, or more than one.
+    PayerCount { number: i64, payers: usize },
+    /// The net position of a traveler doesn't match their expenses and
+    /// transfers.
+    StaleNet {
+        name: Name,
+        net: Decimal,
+        expected: Decimal,
+    },
+    /// The debts don't match the ones recomputed from the expenses and
+    /// transfers.
+    StaleDebts,
+    /// Relations of a table whose traveler or expense doesn't exist anymore.
+    OrphanedRelations {
+        table: &'static str,
+        ids: Vec<RecordId>,
+    },
+}
+
+impl Problem {
+    fn kind(&self) -> &'static str {
+        match self {
+            Problem::SplitsMismatch { .. } => "splits-mismatch",
+            Problem::PayerCount { .. } => "payer-count",
+            Problem::StaleNet { .. } => "stale-net",
+            Problem::StaleDebts => "stale-debts",
+            Problem::OrphanedRelations { .. } => "orphaned-relations",
+        }
+    }
+
+    /// Whether [`repair`] can fix the problem without losing any expense or
+    /// transfer. The shares and the payer of an expense can only be fixed by
+    /// deleting the expense and adding it again.
+    pub fn is_repairable(&self) -> bool {
+        !matches!(
+            self,
+            Problem::SplitsMismatch { .. } | Problem::PayerCount { .. }
+        )
+    }
+}
+
+impl Translate for Problem {
+    fn translate_with_indent(
+        &self,
+        ctx: Arc<std::sync::Mutex<crate::Context>>,
+        indent_lvl: usize,
+    ) -> String {
+        let money =
+            |amount: Decimal| MoneyWrapper::new_with_context(amount, ctx.clone()).to_string();
+        let mut args = hashmap! {
+            i18n::args::KIND.into() => self.kind().into(),
+        };
+        match self {
+            Problem::SplitsMismatch {
+                number,
+                amount,
+                splits,
+            } => {
+                args.insert(i18n::args::NUMBER.into(), (*number).into());
+                args.insert(i18n::args::AMOUNT.into(), money(*amount).into());
+                args.insert(i18n::args::SUM.into(), money(*splits).into());
+            }
+            Problem::PayerCount { number, payers } => {
+                args.insert(i18n::args::NUMBER.into(), (*number).into());
+                args.insert(i18n::args::COUNT.into(), (*payers).into());
+            }
+            Problem::StaleNet {
+                name,
+                net,
+                expected,
+            } => {
+                args.insert(i18n::args::TRAVELER_NAME.into(), name.clone().into());
+                args.insert(i18n::args::NET.into(), money(*net).into());
+                args.insert(i18n::args::AMOUNT.into(), money(*expected).into());
+            }
+            Problem::StaleDebts => {}
+            Problem::OrphanedRelations { table, ids } => {
+                args.insert(i18n::args::TABLE.into(), (*table).into());
+                args.insert(i18n::args::COUNT.into(), ids.len().into());
+            }
+        }
+        i18n::format::FORMAT_AUDIT_PROBLEM.translate_with_args_indent(ctx, &args, indent_lvl)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ExpenseCheck {
+    number: i64,
+    amount: Decimal,
+    splits: Decimal,
+    payers: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct TravelerCheck {
+    id: RecordId,
+    name: Name,
+    net: Decimal,
+}
+
+/// Checks the invariants of the ledger of the active trip of a given chat:
+/// the shares of every expense add up to its amount, every expense has
+/// exactly one payer, the net positions and the debts match the ones
+/// recomputed from the expenses and transfers, and no relation is left
+/// pointing to a deleted traveler or expense.
+pub async fn audit(
+    db: Arc<Surreal<Any>>,
+    chat_id: ChatId,
+) -> Result<Vec<Problem>, surrealdb::Error> {
+    use crate::{
+        chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+        expense::{AMOUNT, NUMBER, TABLE as EXPENSE_TB, TRIP},
+        owes::{AMOUNT as OWES_AMOUNT, IN as OWES_IN, OUT as OWES_OUT, TABLE as OWES_TB},
+        paid_for::TABLE as PAID_FOR_TB,
+        split::{AMOUNT as SPLIT_AMOUNT, TABLE as SPLIT_TB},
+        traveler::{NAME, NUMBER as TRAVELER_NUMBER, TABLE as TRAVELER_TB},
+    };
+    const SPLITS: &str = "splits";
+    const PAYERS: &str = "payers";
+
+    let mut response = db
+        .query(format!(
+            "SELECT
+                {NUMBER},
+                {AMOUNT},
+                <decimal> math::sum((<-{SPLIT_TB}).{SPLIT_AMOUNT}) AS {SPLITS},
+                array::len(<-{PAID_FOR_TB}) AS {PAYERS}
+            FROM {EXPENSE_TB}
+            WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
+            ORDER BY {NUMBER}"
+        ))
+        .query(format!(
+            "SELECT id, {NAME}, {NET}, {TRAVELER_NUMBER}
+            FROM {TRAVELER_TB}
+            WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
+            ORDER BY {TRAVELER_NUMBER}"
+        ))
+        .query(format!(
+            "SELECT {OWES_IN} AS debtor, {OWES_OUT} AS creditor, {OWES_AMOUNT} AS debt
+            FROM {OWES_TB}
+            WHERE {OWES_IN}.{TRIP}.id = ${CHAT_ID}.{ACTIVE_TRIP}"
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .await?;
+    let expenses = response.take::<Vec<ExpenseCheck>>(0)?;
+    let travelers = response.take::<Vec<TravelerCheck>>(1)?;
+    let mut debts = response.take::<Vec<Debt>>(2)?;
+
+    let mut problems = Vec::new();
+    for expense in expenses {
+        if !is_zero(expense.splits - expense.amount) {
+            problems.push(Problem::SplitsMismatch {
+                number: expense.number,
+                amount: expense.amount,
+                splits: expense.splits,
+            });
+        }
+        if expense.payers != 1 {
+            problems.push(Problem::PayerCount {
+                number: expense.number,
+                payers: expense.payers,
+            });
+        }
+    }
+
+    let positions = ledger::net_positions(
+        &debt::ledger_expenses(db.clone(), chat_id, None).await?,
+        &debt::ledger_transfers(db.clone(), chat_id, None).await?,
+    )
+    .into_iter()
+    .collect::<Vec<_>>();
+    for traveler in travelers {
+        let expected = positions
+            .iter()
+            .find(|(id, _)| *id == traveler.id)
+            .map_or(Decimal::ZERO, |(_, net)| *net);
+        if !is_zero(traveler.net - expected) {
+            problems.push(Problem::StaleNet {
+                name: traveler.name,
+                net: traveler.net,
+                expected,
+            });
+        }
+    }
+
+    let mut expected_debts = debt::compute_debts(db.clone(), chat_id, true).await?;
+    let by_travelers = |debt: &Debt| (debt.debtor.clone(), debt.creditor.clone());
+    debts.sort_by_key(by_travelers);
+    expected_debts.sort_by_key(by_travelers);
+    let same_debts = debts.len() == expected_debts.len()
+        && debts.iter().zip(&expected_debts).all(|(debt, expected)| {
+            by_travelers(debt) == by_travelers(expected) && is_zero(debt.debt - expected.debt)
+        });
+    if !same_debts {
+        problems.push(Problem::StaleDebts);
+    }
+
+    problems.extend(orphaned_relations(db, chat_id).await?);
+    Ok(problems)
+}
+
+/// Returns, for every relation table, the relations with one end in the
+/// active trip of a given chat and the other one deleted.
+async fn orphaned_relations(
+    db: Arc<Surreal<Any>>,
+    chat_id: ChatId,
+) -> Result<Vec<Problem>, surrealdb::Error> {
+    use crate::{
+        cannot_pay,
+        chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+        owes, paid_for, settles_via, split, transferred_to,
+        traveler::TRIP,
+    };
+    const RELATION_TABLES: [&str; 6] = [
+        paid_for::TABLE,
+        split::TABLE,
+        transferred_to::TABLE,
+        owes::TABLE,
+        cannot_pay::TABLE,
+        settles_via::TABLE,
+    ];
+
+    let query = RELATION_TABLES
+        .iter()
+        .map(|table| {
+            format!(
+                "SELECT VALUE id
+                FROM {table}
+                WHERE
+                    (in.{TRIP}.id = ${CHAT_ID}.{ACTIVE_TRIP} && out.id = NONE)
+                    || (out.{TRIP}.id = ${CHAT_ID}.{ACTIVE_TRIP} && in.id = NONE)"
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(";\n");
+    let mut response = db
+        .query(query)
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .await?;
+
+    let mut problems = Vec::new();
+    for (i, table) in RELATION_TABLES.into_iter().enumerate() {
+        let ids = response.take::<Vec<RecordId>>(i)?;
+        if !ids.is_empty() {
+            problems.push(Problem::OrphanedRelations { table, ids });
+        }
+    }
+    Ok(problems)
+}
+
+/// Repairs the `problems` found by [`audit`] that can be fixed safely:
+/// orphaned relations are deleted and net positions and debts are rebuilt
+/// from the expenses and transfers.
+pub async fn repair(
+    db: Arc<Surreal<Any>>,
+    chat_id: ChatId,
+    problems: &[Problem],
+) -> Result<(), surrealdb::Error> {
+    let orphans = problems
+        .iter()
+        .filter_map(|problem| match problem {
+            Problem::OrphanedRelations { ids, .. } => Some(ids.clone()),
+            _ => None,
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+    if !orphans.is_empty() {
+        db.query("DELETE $orphans")
+            .bind(("orphans", orphans))
+            .await?
+            .check()?;
+    }
+
+    if problems.iter().any(Problem::is_repairable) {
+        debt::rebuild_debts(db, chat_id).await?;
+    }
+    Ok(())
+}
+
+/// Audits the active trip of every chat, logging the problems found, and
+/// repairs them if `fix` is set. Returns the number of problems left.
+pub async fn audit_all(db: Arc<Surreal<Any>>, fix: bool) -> Result<usize, surrealdb::Error> {
+    use crate::chat::{ACTIVE_TRIP, TABLE as CHAT_TB};
+
+    let chat_ids = db
+        .query(format!(
+            "SELECT VALUE record::id(id) FROM {CHAT_TB} WHERE {ACTIVE_TRIP} != NONE"
+        ))
+        .await
+        .and_then(|mut response| response.take::<Vec<i64>>(0))?;
+
+    let mut left = 0;
+    for chat_id in chat_ids.into_iter().map(ChatId) {
+        let mut problems = audit(db.clone(), chat_id).await?;
+        if fix && problems.iter().any(Problem::is_repairable) {
+            repair(db.clone(), chat_id, &problems).await?;
+            tracing::info!("Repaired the ledger of chat {chat_id}");
+            problems = audit(db.clone(), chat_id).await?;
+        }
+        for problem in &problems {
+            tracing::warn!("Chat {chat_id}: {}", problem.translate_default());
+        }
+        left += problems.len();
+    }
+    tracing::info!("Audit completed: {left} problems found");
+    Ok(left)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::db,
+        expense::Expense as ExpenseRecord,
+        tests::{TestBot, helpers},
+    };
+
+    test! { audit_clean_ledger,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_traveler(&mut bot, "Charlie").await;
+        helpers::add_expense(&mut bot, "Dinner", 100.into(), "Alice", &["all"]).await;
+        helpers::transfer(&mut bot, "Bob", "Alice", 20.into()).await;
+
+        assert_eq!(audit(db, bot.chat_id()).await.unwrap(), vec![]);
+    }
+
+    test! { audit_and_repair,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+        let chat_id = bot.chat_id();
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 60.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "Taxi", 10.into(), "Bob", &["all"]).await;
+
+        // Break the shares of the first expense, drop the payer of the
+        // second one and corrupt the nets
+        db.query("UPDATE split SET amount = 1dec WHERE out.number = 1").await.unwrap();
+        db.query("DELETE paid_for WHERE out.number = 2").await.unwrap();
+        db.query(format!("UPDATE traveler SET {NET} = 1000dec WHERE name = 'Bob'")).await.unwrap();
+
+        let problems = audit(db.clone(), chat_id).await.unwrap();
+        let kinds = problems.iter().map(Problem::kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec!["splits-mismatch", "payer-count", "stale-net", "stale-net", "stale-debts"]
+        );
+        assert_eq!(
+            problems[0],
+            Problem::SplitsMismatch { number: 1, amount: 60.into(), splits: 2.into() }
+        );
+        assert_eq!(problems[1], Problem::PayerCount { number: 2, payers: 0 });
+
+        // Only nets and debts can be repaired
+        repair(db.clone(), chat_id, &problems).await.unwrap();
+        let problems = audit(db.clone(), chat_id).await.unwrap();
+        assert!(problems.iter().all(|problem| !problem.is_repairable()));
+        assert_eq!(problems.len(), 2);
+
+        // Deleting the broken expenses leaves a clean ledger
+        ExpenseRecord::db_delete_all(db.clone(), chat_id).await.unwrap();
+        debt::update_debts(db.clone(), chat_id).await.unwrap();
+        assert_eq!(audit(db, chat_id).await.unwrap(), vec![]);
+    }
+
+    test! { audit_orphaned_relations,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+        let chat_id = bot.chat_id();
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+
+        // Deleting a record deletes its relations, so an orphaned relation
+        // can only be left by data written before the schema assertions
+        db.query(
+            "DEFINE FIELD OVERWRITE in ON settles_via TYPE record<traveler>;
+            DEFINE FIELD OVERWRITE out ON settles_via TYPE record<traveler>;
+            LET $bob = (SELECT VALUE id FROM traveler WHERE name = 'Bob')[0];
+            RELATE $bob->settles_via->traveler:deleted",
+        )
+        .await
+        .unwrap()
+        .check()
+        .unwrap();
+
+        let problems = audit(db.clone(), chat_id).await.unwrap();
+        assert!(matches!(
+            problems.as_slice(),
+            [Problem::OrphanedRelations { table: "settles_via", ids }] if ids.len() == 1
+        ));
+
+        repair(db.clone(), chat_id, &problems).await.unwrap();
+        assert_eq!(audit(db, chat_id).await.unwrap(), vec![]);
+    }
+}
@@ -0,0 +1,487 @@
+//! Backups of the travel plan of a chat, made by `/backup` and restored by
+//! `/restore`.
+//!
+//! A backup is a JSON document holding the settings of the chat together with
+//! the travelers, expenses with their shares and transfers of the active travel
+//! plan. Travelers are referred to by name, while expenses and transfers keep
+//! their numbers and timestamps. [`BACKUP_VERSION`] must be increased whenever
+//! the format changes: backups of other versions are refused.
+
+use crate::{
+    chat::Chat,
+    debt::{SettlementMode, SimplificationMode},
+    errors::BackupError,
+    settings::SETTINGS,
+    transfer::Transfer,
+    traveler::Name,
+};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, sync::Arc};
+use surrealdb::{
+    Datetime, RecordId, Surreal,
+    engine::any::Any,
+    sql::statements::{BeginStatement, CommitStatement},
+};
+use teloxide::types::ChatId;
+
+/// Identifies the JSON documents made by `/backup`.
+pub const BACKUP_FORMAT: &str = "travel-rs-backup";
+/// Version of the format of the backups.
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Settings of the chat the travel plan belongs to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupSettings {
+    pub lang: String,
+    pub currency: String,
+    pub timezone: String,
+    pub settlement: SettlementMode,
+    pub simplification: SimplificationMode,
+}
+
+/// A traveler, with their payment preferences.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupTraveler {
+    pub number: i64,
+    pub name: Name,
+    /// Travelers this one can't pay.
+    #[serde(default)]
+    pub cannot_pay: Vec<Name>,
+    /// Traveler this one prefers to settle via.
+    #[serde(default)]
+    pub settles_via: Option<Name>,
+}
+
+/// Share of an expense owed by a traveler.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupShare {
+    pub traveler: Name,
+    pub amount: Decimal,
+}
+
+/// An expense, with its payer and the share of each traveler.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupExpense {
+    pub number: i64,
+    pub description: String,
+    pub amount: Decimal,
+    pub occurred_at: DateTime<Utc>,
+    pub timestamp_utc: DateTime<Utc>,
+    /// Name of the payer, if the expense has one.
+    pub payer: Option<Name>,
+    pub shares: Vec<BackupShare>,
+}
+
+/// A transfer between two travelers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupTransfer {
+    pub number: i64,
+    pub sender: Name,
+    pub receiver: Name,
+    pub amount: Decimal,
+    pub timestamp_utc: DateTime<Utc>,
+}
+
+/// Header shared by every version of the format, read before the rest of the
+/// backup.
+#[derive(Deserialize)]
+struct BackupHeader {
+    format: String,
+    version: u32,
+}
+
+/// Backup of the travel plan of a chat.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Backup {
+    pub format: String,
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub settings: BackupSettings,
+    pub travelers: Vec<BackupTraveler>,
+    pub expenses: Vec<BackupExpense>,
+    pub transfers: Vec<BackupTransfer>,
+}
+
+/// Row of the travelers query of [`Backup::db_select`].
+#[derive(Deserialize)]
+struct TravelerRow {
+    number: i64,
+    name: Name,
+    cannot_pay: Vec<Name>,
+    settles_via: Option<Name>,
+}
+
+/// Row of the expenses query of [`Backup::db_select`].
+#[derive(Deserialize)]
+struct ExpenseRow {
+    number: i64,
+    description: String,
+    amount: Decimal,
+    occurred_at: Datetime,
+    timestamp_utc: Datetime,
+    payer: Option<Name>,
+    shares: Vec<BackupShare>,
+}
+
+impl Backup {
+    /// Backs up the active travel plan of a given chat.
+    pub async fn db_select(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+    ) -> Result<Self, surrealdb::Error> {
+        use crate::{
+            cannot_pay::TABLE as CANNOT_PAY_TB,
+            chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+            expense::{
+                AMOUNT as EXPENSE_AMOUNT, DESCRIPTION, NUMBER as EXPENSE_NUMBER, OCCURRED_AT,
+                TABLE as EXPENSE_TB, TIMESTAMP_UTC, TRIP as EXPENSE_TRIP,
+            },
+            paid_for::TABLE as PAID_FOR_TB,
+            settles_via::TABLE as SETTLES_VIA_TB,
+            split::{AMOUNT as SPLIT_AMOUNT, IN as SPLIT_IN, OUT as SPLIT_OUT, TABLE as SPLIT_TB},
+            traveler::{NAME, NUMBER, TABLE as TRAVELER_TB, TRIP},
+        };
+        const CANNOT_PAY: &str = "cannot_pay";
+        const SETTLES_VIA: &str = "settles_via";
+        const PAYER: &str = "payer";
+        const SHARES: &str = "shares";
+        const TRAVELER: &str = "traveler";
+
+        let chat = Chat::db_select_by_id(db.clone(), chat_id).await?;
+        let mut response = db
+            .query(format!(
+                "SELECT
+                    {NUMBER},
+                    {NAME},
+                    (->{CANNOT_PAY_TB}->{TRAVELER_TB}.{NAME}) AS {CANNOT_PAY},
+                    (->{SETTLES_VIA_TB}->{TRAVELER_TB})[0].{NAME} AS {SETTLES_VIA}
+                FROM {TRAVELER_TB}
+                WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
+                ORDER BY {NUMBER} ASC"
+            ))
+            .query(format!(
+                "SELECT
+                    {EXPENSE_NUMBER},
+                    {DESCRIPTION},
+                    {EXPENSE_AMOUNT},
+                    {OCCURRED_AT},
+                    {TIMESTAMP_UTC},
+                    (<-{PAID_FOR_TB}<-{TRAVELER_TB})[0].{NAME} AS {PAYER},
+                    (
+                        SELECT {SPLIT_IN}.{NAME} AS {TRAVELER}, {SPLIT_AMOUNT}
+                        FROM {SPLIT_TB}
+                        WHERE {SPLIT_OUT} = $parent.id
+                        ORDER BY {TRAVELER} ASC
+                    ) AS {SHARES}
+                FROM {EXPENSE_TB}
+                WHERE {EXPENSE_TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
+                ORDER BY {EXPENSE_NUMBER} ASC"
+            ))
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+            .await?;
+        let travelers = response.take::<Vec<TravelerRow>>(0)?;
+        let expenses = response.take::<Vec<ExpenseRow>>(1)?;
+        let transfers = Transfer::transfers(db, chat_id).await?;
+
+        let settings = match chat {
+            Some(chat) => BackupSettings {
+                lang: chat.lang,
+                currency: chat.currency,
+                timezone: chat.timezone,
+                settlement: chat.settlement,
+                simplification: chat.simplification,
+            },
+            None => BackupSettings {
+                lang: SETTINGS.i18n.default_locale.to_string(),
+                currency: SETTINGS.i18n.default_currency.clone(),
+                timezone: String::from("UTC"),
+                settlement: SettlementMode::default(),
+                simplification: SimplificationMode::default(),
+            },
+        };
+
+        Ok(Self {
+            format: BACKUP_FORMAT.to_owned(),
+            version: BACKUP_VERSION,
+            created_at: Utc::now(),
+            settings,
+            travelers: travelers
+                .into_iter()
+                .map(|row| BackupTraveler {
+                    number: row.number,
+                    name: row.name,
+                    cannot_pay: row.cannot_pay,
+                    settles_via: row.settles_via,
+                })
+                .collect(),
+            expenses: expenses
+                .into_iter()
+                .map(|row| BackupExpense {
+                    number: row.number,
+                    description: row.description,
+                    amount: row.amount,
+                    occurred_at: row.occurred_at.into_inner().0,
+                    timestamp_utc: row.timestamp_utc.into_inner().0,
+                    payer: row.payer,
+                    shares: row.shares,
+                })
+                .collect(),
+            transfers: transfers
+                .into_iter()
+                .map(|transfer| BackupTransfer {
+                    number: transfer.number,
+                    sender: transfer.sender_name,
+                    receiver: transfer.receiver_name,
+                    amount: transfer.amount,
+                    timestamp_utc: transfer.timestamp_utc.into_inner().0,
+                })
+                .collect(),
+        })
+    }
+
+    /// Replaces the active travel plan of a given chat and the settings of the
+    /// chat with the backed up ones, in a single transaction. The balances are
+    /// not restored: they must be rebuilt afterwards.
+    pub async fn db_restore(
+        &self,
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+    ) -> Result<(), surrealdb::Error> {
+        use crate::{
+            cannot_pay::TABLE as CANNOT_PAY_TB,
+            chat::{
+                ACTIVE_TRIP, CURRENCY, ID as CHAT_ID, LANG, SETTLEMENT, SIMPLIFICATION,
+                TABLE as CHAT_TB, TIMEZONE,
+            },
+            expense::{
+                AMOUNT as EXPENSE_AMOUNT, DESCRIPTION, NUMBER as EXPENSE_NUMBER, OCCURRED_AT,
+                TABLE as EXPENSE_TB, TIMESTAMP_UTC as EXPENSE_TIMESTAMP_UTC, TRIP as EXPENSE_TRIP,
+            },
+            paid_for::TABLE as PAID_FOR_TB,
+            settles_via::TABLE as SETTLES_VIA_TB,
+            split::{AMOUNT as SPLIT_AMOUNT, TABLE as SPLIT_TB},
+            transferred_to::{
+                AMOUNT as TRANSFER_AMOUNT, NUMBER as TRANSFER_NUMBER, TABLE as TRANSFERRED_TO_TB,
+                TIMESTAMP_UTC as TRANSFER_TIMESTAMP_UTC,
+            },
+            traveler::{NAME, NUMBER, TABLE as TRAVELER_TB, TRIP},
+        };
+        const SETTINGS: &str = "settings";
+        const TRAVELERS: &str = "travelers";
+        const EXPENSES: &str = "expenses";
+        const TRANSFERS: &str = "transfers";
+        const NAME_LOWER: &str = "name_lower";
+
+        // Returns the traveler of the restored trip named as `name`
+        let traveler = |name: &str| {
+            format!(
+                "(SELECT VALUE id FROM {TRAVELER_TB} WHERE {TRIP} = $trip AND {NAME} = {name})[0]"
+            )
+        };
+
+        db.query(BeginStatement::default())
+            .query(format!("LET $trip = ${CHAT_ID}.{ACTIVE_TRIP}"))
+            .query(format!("DELETE {EXPENSE_TB} WHERE {EXPENSE_TRIP} = $trip"))
+            .query(format!("DELETE {TRAVELER_TB} WHERE {TRIP} = $trip"))
+            .query(format!(
+                "UPDATE ${CHAT_ID} SET
+                    {LANG} = ${SETTINGS}.{LANG},
+                    {CURRENCY} = ${SETTINGS}.{CURRENCY},
+                    {TIMEZONE} = ${SETTINGS}.{TIMEZONE},
+                    {SETTLEMENT} = ${SETTINGS}.{SETTLEMENT},
+                    {SIMPLIFICATION} = ${SETTINGS}.{SIMPLIFICATION}"
+            ))
+            .query(format!(
+                "FOR $traveler IN ${TRAVELERS} {{
+                    CREATE {TRAVELER_TB} CONTENT {{
+                        {TRIP}: $trip,
+                        {NUMBER}: $traveler.{NUMBER},
+                        {NAME}: $traveler.{NAME},
+                        {NAME_LOWER}: string::lowercase($traveler.{NAME}),
+                    }};
+                }}"
+            ))
+            .query(format!(
+                "FOR $traveler IN ${TRAVELERS} {{
+                    LET $debtor = {debtor};
+                    FOR $name IN $traveler.cannot_pay {{
+                        LET $creditor = {creditor};
+                        RELATE $debtor->{CANNOT_PAY_TB}->$creditor;
+                    }};
+                    IF $traveler.settles_via != NONE {{
+                        LET $via = {via};
+                        RELATE $debtor->{SETTLES_VIA_TB}->$via;
+                    }};
+                }}",
+                debtor = traveler(&format!("$traveler.{NAME}")),
+                creditor = traveler("$name"),
+                via = traveler("$traveler.settles_via"),
+            ))
+            .query(format!(
+                "FOR $expense IN ${EXPENSES} {{
+                    LET $record = CREATE ONLY {EXPENSE_TB} CONTENT {{
+                        {EXPENSE_TRIP}: $trip,
+                        {EXPENSE_NUMBER}: $expense.{EXPENSE_NUMBER},
+                        {DESCRIPTION}: $expense.{DESCRIPTION},
+                        {EXPENSE_AMOUNT}: <decimal> $expense.{EXPENSE_AMOUNT},
+                        {OCCURRED_AT}: <datetime> $expense.{OCCURRED_AT},
+                        {EXPENSE_TIMESTAMP_UTC}: <datetime> $expense.{EXPENSE_TIMESTAMP_UTC},
+                    }};
+                    IF $expense.payer != NONE {{
+                        LET $payer = {payer};
+                        RELATE $payer->{PAID_FOR_TB}->$record;
+                    }};
+                    FOR $share IN $expense.shares {{
+                        LET $debtor = {debtor};
+                        RELATE $debtor->{SPLIT_TB}->$record
+                        SET {SPLIT_AMOUNT} = <decimal> $share.amount;
+                    }};
+                }}",
+                payer = traveler("$expense.payer"),
+                debtor = traveler("$share.traveler"),
+            ))
+            .query(format!(
+                "FOR $transfer IN ${TRANSFERS} {{
+                    LET $sender = {sender};
+                    LET $receiver = {receiver};
+                    RELATE $sender->{TRANSFERRED_TO_TB}->$receiver SET
+                        {TRANSFER_NUMBER} = $transfer.{TRANSFER_NUMBER},
+                        {TRANSFER_AMOUNT} = <decimal> $transfer.{TRANSFER_AMOUNT},
+                        {TRANSFER_TIMESTAMP_UTC} = <datetime> $transfer.{TRANSFER_TIMESTAMP_UTC};
+                }}",
+                sender = traveler("$transfer.sender"),
+                receiver = traveler("$transfer.receiver"),
+            ))
+            .query(CommitStatement::default())
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+            .bind((SETTINGS, self.settings.clone()))
+            .bind((TRAVELERS, self.travelers.clone()))
+            .bind((EXPENSES, self.expenses.clone()))
+            .bind((TRANSFERS, self.transfers.clone()))
+            .await?
+            .check()
+            .map(|_| {})
+    }
+
+    /// Serializes the backup to pretty-printed JSON.
+    pub fn to_json(&self) -> Result<Vec<u8>, serde_json::Error> {
+        serde_json::to_vec_pretty(self)
+    }
+
+    /// Reads a backup made by `/backup`, checking that its version is
+    /// understood and that it only refers to backed up travelers.
+    pub fn from_json(json: &[u8]) -> Result<Self, BackupError> {
+        let header = serde_json::from_slice::<BackupHeader>(json).map_err(|err| {
+            tracing::warn!("Invalid backup: {err}");
+            BackupError::Invalid
+        })?;
+        if header.format != BACKUP_FORMAT {
+            tracing::warn!("Invalid backup format: {}", header.format);
+            return Err(BackupError::Invalid);
+        }
+        if header.version != BACKUP_VERSION {
+            return Err(BackupError::UnsupportedVersion(header.version));
+        }
+        let backup = serde_json::from_slice::<Self>(json).map_err(|err| {
+            tracing::warn!("Invalid backup: {err}");
+            BackupError::Invalid
+        })?;
+
+        let names = backup
+            .travelers
+            .iter()
+            .map(|traveler| &traveler.name)
+            .collect::<HashSet<_>>();
+        let referenced = backup
+            .travelers
+            .iter()
+            .flat_map(|traveler| traveler.cannot_pay.iter().chain(&traveler.settles_via))
+            .chain(backup.expenses.iter().flat_map(|expense| {
+                expense
+                    .payer
+                    .iter()
+                    .chain(expense.shares.iter().map(|share| &share.traveler))
+            }))
+            .chain(
+                backup
+                    .transfers
+                    .iter()
+                    .flat_map(|transfer| [&transfer.sender, &transfer.receiver]),
+            );
+        for name in referenced {
+            if !names.contains(name) {
+                return Err(BackupError::UnknownTraveler(name.clone()));
+            }
+        }
+        Ok(backup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::db,
+        tests::{TestBot, helpers},
+    };
+
+    test! { backup_round_trip,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 90.into(), "Alice", &["all"]).await;
+        helpers::transfer(&mut bot, "Bob", "Alice", 20.into()).await;
+        bot.update("/payprefs Bob cannot Alice");
+        bot.dispatch().await;
+
+        let backup = Backup::db_select(db.clone(), bot.chat_id()).await.unwrap();
+        let json = backup.to_json().unwrap();
+        let restored = Backup::from_json(&json).unwrap();
+        assert_eq!(restored, backup);
+
+        // Restoring to another chat recreates the same travel plan
+        drop(bot);
+        let mut other = TestBot::new(db.clone(), "/listtravelers");
+        other.dispatch().await;
+        restored.db_restore(db.clone(), other.chat_id()).await.unwrap();
+        let mut copy = Backup::db_select(db.clone(), other.chat_id()).await.unwrap();
+        copy.created_at = backup.created_at;
+        assert_eq!(copy, backup);
+        assert_eq!(copy.travelers[1].cannot_pay, vec!["Alice".parse().unwrap()]);
+    }
+
+    test! { from_json_unsupported_version,
+        let json = format!(r#"{{"format": "{BACKUP_FORMAT}", "version": 99}}"#);
+        assert!(matches!(
+            Backup::from_json(json.as_bytes()),
+            Err(BackupError::UnsupportedVersion(99))
+        ));
+    }
+
+    test! { from_json_invalid,
+        assert!(matches!(Backup::from_json(b"Hello, world!"), Err(BackupError::Invalid)));
+        let json = r#"{"format": "something-else", "version": 1}"#;
+        assert!(matches!(Backup::from_json(json.as_bytes()), Err(BackupError::Invalid)));
+    }
+
+    test! { from_json_unknown_traveler,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::transfer(&mut bot, "Bob", "Alice", 20.into()).await;
+
+        let mut backup = Backup::db_select(db, bot.chat_id()).await.unwrap();
+        backup.travelers.retain(|traveler| &*traveler.name == "Alice");
+        let json = backup.to_json().unwrap();
+        assert!(matches!(
+            Backup::from_json(&json),
+            Err(BackupError::UnknownTraveler(name)) if &*name == "Bob"
+        ));
+    }
+}
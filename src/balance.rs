@@ -14,7 +14,7 @@ pub struct Balance {
     pub debt: Decimal,
     pub debtor_name: Name,
     pub creditor_name: Name,
-    pub chat: RecordId,
+    pub trip: RecordId,
 }
 
 impl Balance {
@@ -22,17 +22,17 @@ impl Balance {
         db: Arc<Surreal<Any>>,
         chat_id: ChatId,
     ) -> Result<Vec<Self>, surrealdb::Error> {
-        use crate::chat::TABLE as CHAT_TB;
+        use crate::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(format!(
             "SELECT *
-            FROM {FN_GET_BALANCES}(${CHAT})
+            FROM {FN_GET_BALANCES}(${CHAT_ID}.{ACTIVE_TRIP})
             ORDER BY 
                 {DEBT} DESC, 
                 {DEBTOR_NAME} ASC, 
                 {CREDITOR_NAME} ASC",
         ))
-        .bind((CHAT, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
         .await
         .and_then(|mut response| response.take::<Vec<Self>>(0))
     }
@@ -42,11 +42,14 @@ impl Balance {
         chat_id: ChatId,
         name: Name,
     ) -> Result<Vec<Self>, surrealdb::Error> {
-        use crate::{chat::TABLE as CHAT_TB, traveler::NAME};
+        use crate::{
+            chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+            traveler::NAME,
+        };
 
         db.query(format!(
             "SELECT *
-            FROM {FN_GET_BALANCES}(${CHAT})
+            FROM {FN_GET_BALANCES}(${CHAT_ID}.{ACTIVE_TRIP})
             WHERE string::lowercase({DEBTOR_NAME}) = string::lowercase(${NAME})
                 || string::lowercase({CREDITOR_NAME}) = string::lowercase(${NAME})
             ORDER BY 
@@ -54,9 +57,31 @@ impl Balance {
                 {DEBTOR_NAME} ASC, 
                 {CREDITOR_NAME} ASC",
         ))
-        .bind((CHAT, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
         .bind((NAME, name))
         .await
         .and_then(|mut response| response.take::<Vec<Self>>(0))
     }
+
+    /// Returns the amount `debtor` owes to `creditor`, if any.
+    pub async fn balance_between(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        debtor: &Name,
+        creditor: &Name,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        use crate::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
+
+        db.query(format!(
+            "SELECT *
+            FROM {FN_GET_BALANCES}(${CHAT_ID}.{ACTIVE_TRIP})
+            WHERE string::lowercase({DEBTOR_NAME}) = string::lowercase(${DEBTOR_NAME})
+                && string::lowercase({CREDITOR_NAME}) = string::lowercase(${CREDITOR_NAME})",
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .bind((DEBTOR_NAME, debtor.clone()))
+        .bind((CREDITOR_NAME, creditor.clone()))
+        .await
+        .and_then(|mut response| response.take::<Option<Self>>(0))
+    }
 }
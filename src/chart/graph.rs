@@ -0,0 +1,327 @@
+//! Drawing of a debt graph: the travelers around an ellipse, with an arrow
+//! from each debtor to their creditor labelled with the amount.
+
+use super::{
+    Area, BACKGROUND, DrawResult, GAP, GRID, LABEL_SIZE, MARGIN, PALETTE, TEXT, WIDTH, draw_png,
+    text_height, text_style, title, truncate,
+};
+use crate::{
+    Context, debt_graph::DebtGraph, i18n::Translate, money_wrapper::MoneyWrapper, traveler::Name,
+};
+use plotters::{
+    prelude::*,
+    style::text_anchor::{HPos, Pos, VPos},
+};
+use rust_decimal::Decimal;
+use std::{
+    collections::HashMap,
+    f64::consts::{PI, TAU},
+    io,
+    sync::{Arc, Mutex},
+};
+
+pub const FILE_NAME: &str = "debts.png";
+
+const EDGE: RGBColor = RGBColor(96, 96, 96);
+/// Travelers owing or owed money.
+const INVOLVED: RGBColor = PALETTE[0];
+/// Travelers who are settled up.
+const SETTLED: RGBColor = GRID;
+/// Widest box of a traveler: longer names are truncated.
+const NODE_MAX_WIDTH: u32 = 160;
+const EDGE_THICKNESS: u32 = 2;
+const ARROW_LENGTH: f64 = 14.0;
+/// Half of the width of the base of an arrow.
+const ARROW_HALF_WIDTH: f64 = 6.0;
+/// How far each of two edges in opposite directions is moved aside, so that
+/// they don't overlap.
+const PARALLEL_SHIFT: f64 = 8.0;
+/// Space around the text of an amount.
+const LABEL_PADDING: u32 = 3;
+
+type Point = (f64, f64);
+
+/// Box of a traveler.
+struct Node {
+    label: String,
+    center: Point,
+    /// Half of the width and of the height.
+    half: (f64, f64),
+}
+
+/// Returns `count` points evenly spread around the ellipse centered in
+/// `center`, clockwise from the leftmost one.
+fn layout(count: usize, center: Point, radii: (f64, f64)) -> Vec<Point> {
+    if count == 1 {
+        return vec![center];
+    }
+    (0..count)
+        .map(|i| {
+            let angle = PI + TAU * i as f64 / count as f64;
+            (
+                center.0 + radii.0 * angle.cos(),
+                center.1 + radii.1 * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+/// Returns the point where the segment from `center` to `towards` leaves
+/// the box centered in `center` with half sizes `half`.
+fn exit_point(center: Point, half: (f64, f64), towards: Point) -> Point {
+    let (dx, dy) = (towards.0 - center.0, towards.1 - center.1);
+    let scale = [(half.0, dx), (half.1, dy)]
+        .into_iter()
+        .filter(|(_, delta)| *delta != 0.0)
+        .map(|(half, delta)| half / delta.abs())
+        .fold(f64::INFINITY, f64::min);
+    if scale.is_finite() {
+        (center.0 + scale * dx, center.1 + scale * dy)
+    } else {
+        center
+    }
+}
+
+fn point((x, y): Point) -> (i32, i32) {
+    (x.round() as i32, y.round() as i32)
+}
+
+/// Draws `graph` as a PNG image.
+pub fn render(graph: &DebtGraph, ctx: Arc<Mutex<Context>>) -> io::Result<Vec<u8>> {
+    let currency = ctx.lock().expect("Failed to lock context").currency.clone();
+    let money = |amount: Decimal| MoneyWrapper::new(amount, &currency).to_string();
+    let graph_title = graph.title().translate(ctx);
+    draw_png(|root| draw(root, graph, &graph_title, money))
+}
+
+fn draw(
+    root: &Area,
+    graph: &DebtGraph,
+    graph_title: &str,
+    money: impl Fn(Decimal) -> String,
+) -> DrawResult<()> {
+    let area = title(root, graph_title)?;
+    let style = text_style(LABEL_SIZE);
+    let text_height = text_height(&area, &style)?;
+    let (width, height) = area.dim_in_pixel();
+
+    let labels = graph
+        .travelers
+        .iter()
+        .map(|name| truncate(&area, name, NODE_MAX_WIDTH - 2 * GAP, &style))
+        .collect::<Result<Vec<_>, _>>()?;
+    let label_widths = labels
+        .iter()
+        .map(|label| {
+            area.estimate_text_size(label, &style)
+                .map(|(width, _)| width)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let node_height = text_height + 2 * GAP;
+    let widest = label_widths.iter().max().copied().unwrap_or_default() + 2 * GAP;
+    let center = (width as f64 / 2.0, (height - MARGIN) as f64 / 2.0);
+    let radii = (
+        (WIDTH - 2 * MARGIN - widest) as f64 / 2.0,
+        (height - MARGIN - node_height) as f64 / 2.0,
+    );
+    let nodes = graph
+        .travelers
+        .iter()
+        .zip(labels.into_iter().zip(label_widths))
+        .zip(layout(graph.travelers.len(), center, radii))
+        .map(|((name, (label, label_width)), center)| {
+            let half = (
+                (label_width + 2 * GAP) as f64 / 2.0,
+                node_height as f64 / 2.0,
+            );
+            (
+                name,
+                Node {
+                    label,
+                    center,
+                    half,
+                },
+            )
+        })
+        .collect::<HashMap<&Name, Node>>();
+
+    let edge_style = EDGE.stroke_width(EDGE_THICKNESS);
+    let mut amounts = Vec::new();
+    for edge in &graph.edges {
+        let (Some(from), Some(to)) = (nodes.get(&edge.debtor), nodes.get(&edge.creditor)) else {
+            continue;
+        };
+        let (dx, dy) = (to.center.0 - from.center.0, to.center.1 - from.center.1);
+        let length = dx.hypot(dy);
+        if length == 0.0 {
+            continue;
+        }
+        let (ux, uy) = (dx / length, dy / length);
+        // Edges in both directions between two travelers are moved apart,
+        // each to its right, with their amounts on the same side
+        let parallel = graph
+            .edges
+            .iter()
+            .any(|other| other.debtor == edge.creditor && other.creditor == edge.debtor);
+        let shift = if parallel { PARALLEL_SHIFT } else { 0.0 };
+        let from_center = (from.center.0 - uy * shift, from.center.1 + ux * shift);
+        let to_center = (to.center.0 - uy * shift, to.center.1 + ux * shift);
+
+        let start = exit_point(from_center, from.half, to_center);
+        let tip = exit_point(to_center, to.half, from_center);
+        let base = (tip.0 - ux * ARROW_LENGTH, tip.1 - uy * ARROW_LENGTH);
+        area.draw(&PathElement::new(
+            vec![point(start), point(base)],
+            edge_style,
+        ))?;
+        area.draw(&Polygon::new(
+            vec![
+                point(tip),
+                point((
+                    base.0 - uy * ARROW_HALF_WIDTH,
+                    base.1 + ux * ARROW_HALF_WIDTH,
+                )),
+                point((
+                    base.0 + uy * ARROW_HALF_WIDTH,
+                    base.1 - ux * ARROW_HALF_WIDTH,
+                )),
+            ],
+            EDGE.filled(),
+        ))?;
+
+        let offset = if parallel {
+            (text_height / 2 + GAP) as f64
+        } else {
+            0.0
+        };
+        let middle = (
+            (start.0 + tip.0) / 2.0 - uy * offset,
+            (start.1 + tip.1) / 2.0 + ux * offset,
+        );
+        amounts.push((middle, money(edge.amount)));
+    }
+
+    // Amounts go over the edges, and travelers over both
+    for (middle, amount) in amounts {
+        let width = area.estimate_text_size(&amount, &style)?.0;
+        let half = (
+            (width / 2 + LABEL_PADDING) as f64,
+            (text_height / 2 + LABEL_PADDING) as f64,
+        );
+        area.draw(&Rectangle::new(
+            [
+                point((middle.0 - half.0, middle.1 - half.1)),
+                point((middle.0 + half.0, middle.1 + half.1)),
+            ],
+            BACKGROUND.filled(),
+        ))?;
+        centered_text(&area, &amount, &style, middle)?;
+    }
+    for (name, node) in &nodes {
+        let involved = graph
+            .edges
+            .iter()
+            .any(|edge| &edge.debtor == *name || &edge.creditor == *name);
+        let (fill, text) = if involved {
+            (INVOLVED, BACKGROUND)
+        } else {
+            (SETTLED, TEXT)
+        };
+        area.draw(&Rectangle::new(
+            [
+                point((node.center.0 - node.half.0, node.center.1 - node.half.1)),
+                point((node.center.0 + node.half.0, node.center.1 + node.half.1)),
+            ],
+            fill.filled(),
+        ))?;
+        centered_text(&area, &node.label, &style.color(&text), node.center)?;
+    }
+    Ok(())
+}
+
+/// Writes `text` centered in `center`.
+fn centered_text(area: &Area, text: &str, style: &TextStyle, center: Point) -> DrawResult<()> {
+    let (width, _) = area.estimate_text_size(text, style)?;
+    let height = text_height(area, style)?;
+    super::text(
+        area,
+        text,
+        &style.pos(Pos::new(HPos::Left, VPos::Top)),
+        point((
+            center.0 - width as f64 / 2.0,
+            center.1 - height as f64 / 2.0,
+        )),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EDGE, INVOLVED, SETTLED, draw, exit_point, layout, render};
+    use crate::{
+        Context,
+        chart::{HEIGHT, WIDTH, draw_pixels},
+        debt_graph::{DebtEdge, DebtGraph},
+    };
+    use plotters::style::RGBColor;
+    use rust_decimal::Decimal;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn exit_points() {
+        let half = (10.0, 5.0);
+        assert_eq!(exit_point((0.0, 0.0), half, (100.0, 0.0)), (10.0, 0.0));
+        assert_eq!(exit_point((0.0, 0.0), half, (0.0, -100.0)), (0.0, -5.0));
+        assert_eq!(exit_point((0.0, 0.0), half, (20.0, 20.0)), (5.0, 5.0));
+        assert_eq!(exit_point((0.0, 0.0), half, (0.0, 0.0)), (0.0, 0.0));
+    }
+
+    #[test]
+    fn layout_points() {
+        assert_eq!(layout(1, (50.0, 50.0), (40.0, 20.0)), [(50.0, 50.0)]);
+        let points = layout(4, (50.0, 50.0), (40.0, 20.0));
+        let rounded = points
+            .iter()
+            .map(|(x, y)| (x.round(), y.round()))
+            .collect::<Vec<_>>();
+        // Leftmost first, then clockwise
+        assert_eq!(
+            rounded,
+            [(10.0, 50.0), (50.0, 30.0), (90.0, 50.0), (50.0, 70.0)]
+        );
+    }
+
+    #[test]
+    fn render_graph() {
+        let graph = DebtGraph {
+            travelers: ["Zoë", "Дмитрий", "Νίκος", "Carol"]
+                .map(|name| name.parse().unwrap())
+                .to_vec(),
+            edges: vec![
+                DebtEdge {
+                    debtor: "Дмитрий".parse().unwrap(),
+                    creditor: "Zoë".parse().unwrap(),
+                    amount: Decimal::new(2400, 2),
+                },
+                DebtEdge {
+                    debtor: "Zoë".parse().unwrap(),
+                    creditor: "Νίκος".parse().unwrap(),
+                    amount: Decimal::new(1050, 2),
+                },
+            ],
+            raw: false,
+        };
+        let png = render(&graph, Arc::new(Mutex::new(Context::default()))).unwrap();
+        // PNG signature, then the size in the IHDR chunk
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[16..20], WIDTH.to_be_bytes());
+        assert_eq!(&png[20..24], HEIGHT.to_be_bytes());
+
+        let pixels =
+            draw_pixels(|root| draw(root, &graph, "Debts", |amount| amount.to_string())).unwrap();
+        let has = |RGBColor(r, g, b): RGBColor| pixels.chunks(3).any(|pixel| pixel == [r, g, b]);
+        // Travelers owing or owed money, Carol who is settled, and the arrows
+        assert!(has(INVOLVED));
+        assert!(has(SETTLED));
+        assert!(has(EDGE));
+    }
+}
@@ -0,0 +1,537 @@
+//! Charts of the spending of a travel plan, sent by `/chart` as PNG images.
+//!
+//! Charts are drawn from the same [`Report`] as `/report` with [`plotters`],
+//! writing text with an embedded DejaVu Sans font so that names in Latin,
+//! Greek and Cyrillic scripts are shown, and encoded as PNG by plotters too. Titles and legends are localized,
+//! and amounts are formatted by [`MoneyWrapper`] in the chat currency. The
+//! debt graph sent by `/debtgraph` is drawn the same way, see [`graph`].
+
+pub mod graph;
+
+use crate::{
+    Context,
+    i18n::{
+        Translate,
+        labels::{
+            CHART_CATEGORIES, CHART_DAILY, CHART_OTHER, CHART_TRAVELERS, REPORT_CONSUMED,
+            REPORT_PAID, REPORT_UNCATEGORIZED,
+        },
+    },
+    money_wrapper::MoneyWrapper,
+    report::Report,
+};
+use plotters::{
+    backend::DrawingBackend,
+    coord::Shift,
+    prelude::*,
+    style::{FontStyle, register_font},
+};
+use rust_decimal::prelude::*;
+use std::{
+    collections::HashSet,
+    f64::consts::TAU,
+    io,
+    sync::{Arc, Mutex, Once},
+};
+use strum::{AsRefStr, EnumIter, EnumString};
+
+const WIDTH: u32 = 800;
+const HEIGHT: u32 = 500;
+const MARGIN: u32 = 20;
+/// Space between a label and what it describes.
+const GAP: u32 = 8;
+/// Font sizes, in pixels.
+const TITLE_SIZE: f64 = 28.0;
+const LABEL_SIZE: f64 = 16.0;
+/// Number of intervals of the value axis, at most.
+const TICKS: u32 = 5;
+/// Share of the width of a group of bars actually filled by the bars.
+const BARS_FILL: f64 = 0.7;
+/// Number of sides of the polygon drawing a whole pie.
+const PIE_SIDES: f64 = 360.0;
+
+/// Name the embedded font is registered with.
+const FONT_FAMILY: &str = "sans-serif";
+/// DejaVu Sans, without hinting and subset to the Latin, Greek and Cyrillic
+/// scripts, punctuation and currency symbols to keep the binary small. See
+/// `assets/fonts/LICENSE-DejaVu.txt` for its license.
+static FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans-Subset.ttf");
+
+const BACKGROUND: RGBColor = RGBColor(255, 255, 255);
+const TEXT: RGBColor = RGBColor(34, 34, 34);
+const GRID: RGBColor = RGBColor(221, 221, 221);
+const AXIS: RGBColor = RGBColor(136, 136, 136);
+/// Colors of the series and of the slices of a pie. A pie has as many
+/// slices at most: the smallest categories are merged into the last one.
+const PALETTE: [RGBColor; 8] = [
+    RGBColor(76, 139, 245),
+    RGBColor(245, 166, 35),
+    RGBColor(46, 160, 67),
+    RGBColor(207, 34, 46),
+    RGBColor(130, 80, 223),
+    RGBColor(23, 162, 184),
+    RGBColor(191, 135, 0),
+    RGBColor(128, 128, 128),
+];
+
+type Area<'a> = DrawingArea<BitMapBackend<'a>, Shift>;
+type DrawResult<T> =
+    Result<T, DrawingAreaErrorKind<<BitMapBackend<'static> as DrawingBackend>::ErrorType>>;
+
+/// Charts that can be drawn of the travel plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr, EnumIter, EnumString)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum ChartKind {
+    /// Bars of the total spent each day.
+    Daily,
+    /// Bars of what each traveler paid and consumed.
+    Travelers,
+    /// Pie of the total spent in each category.
+    Categories,
+}
+
+impl ChartKind {
+    /// Key of the localized title of the chart.
+    pub fn title(&self) -> &'static str {
+        match self {
+            ChartKind::Daily => CHART_DAILY,
+            ChartKind::Travelers => CHART_TRAVELERS,
+            ChartKind::Categories => CHART_CATEGORIES,
+        }
+    }
+}
+
+/// A series of a bar chart, with a value for each label.
+struct Series {
+    name: String,
+    color: RGBColor,
+    values: Vec<Decimal>,
+}
+
+fn to_f64(amount: Decimal) -> f64 {
+    amount.to_f64().unwrap_or_default()
+}
+
+/// Registers the embedded font, the first time it is needed.
+fn register_fonts() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        assert!(
+            register_font(FONT_FAMILY, FontStyle::Normal, FONT).is_ok(),
+            "The embedded font is invalid"
+        );
+    });
+}
+
+fn text_style(size: f64) -> TextStyle<'static> {
+    (FONT_FAMILY, size).into_font().color(&TEXT)
+}
+
+/// Returns the height of the text written in `style`.
+fn text_height(area: &Area, style: &TextStyle) -> DrawResult<u32> {
+    area.estimate_text_size("Hg", style)
+        .map(|(_, height)| height)
+}
+
+/// Writes `text` with its top left corner at `x`, `y`.
+fn text(area: &Area, text: &str, style: &TextStyle, (x, y): (i32, i32)) -> DrawResult<()> {
+    // The font draws the baseline half of its size below the position
+    // given, so the text is moved down by what rises above it
+    let rise = (style.font.get_size() * 0.25).round() as i32;
+    area.draw_text(text, style, (x, y + rise))
+}
+
+/// Returns `text`, shortened with an ellipsis to be no wider than `width`.
+fn truncate(area: &Area, text: &str, width: u32, style: &TextStyle) -> DrawResult<String> {
+    let fits = |text: &str| {
+        area.estimate_text_size(text, style)
+            .map(|(text_width, _)| text_width <= width)
+    };
+    if fits(text)? {
+        return Ok(text.to_owned());
+    }
+    let mut truncated = text.to_owned();
+    while truncated.pop().is_some() && !fits(&format!("{truncated}…"))? {}
+    Ok(format!("{truncated}…"))
+}
+
+/// Returns a round step dividing `max` into [`TICKS`] intervals at most.
+fn tick_step(max: f64) -> f64 {
+    if max <= 0.0 {
+        return 1.0;
+    }
+    let raw = max / TICKS as f64;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| step * TICKS as f64 >= max)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// Writes the title at the top of `area`, and returns the rest of it.
+fn title<'a>(area: &Area<'a>, title: &str) -> DrawResult<Area<'a>> {
+    let style = text_style(TITLE_SIZE);
+    let width = area.dim_in_pixel().0 - 2 * MARGIN;
+    let title = truncate(area, title, width, &style)?;
+    text(area, &title, &style, (MARGIN as i32, MARGIN as i32))?;
+    let top = MARGIN + text_height(area, &style)? + 2 * GAP;
+    Ok(area.split_vertically(top).1)
+}
+
+/// Writes a legend entry at `x`, `y`, and returns its width.
+fn legend_entry(area: &Area, (x, y): (i32, i32), label: &str, color: RGBColor) -> DrawResult<u32> {
+    let style = text_style(LABEL_SIZE);
+    let size = text_height(area, &style)?;
+    area.draw(&Rectangle::new(
+        [(x, y), (x + size as i32, y + size as i32)],
+        color.filled(),
+    ))?;
+    text(area, label, &style, (x + (size + GAP) as i32, y))?;
+    Ok(size + GAP + area.estimate_text_size(label, &style)?.0)
+}
+
+/// Draws bars of each of `series` above each of `labels`, with the value
+/// axis labelled by `money`.
+fn bar_chart(
+    root: &Area,
+    chart_title: &str,
+    labels: &[String],
+    series: &[Series],
+    money: impl Fn(Decimal) -> String,
+) -> DrawResult<()> {
+    let mut area = title(root, chart_title)?;
+    let style = text_style(LABEL_SIZE);
+    let label_height = text_height(&area, &style)?;
+
+    if series.len() > 1 {
+        let mut x = MARGIN as i32;
+        for series in series {
+            x += (legend_entry(&area, (x, 0), &series.name, series.color)? + 3 * GAP) as i32;
+        }
+        area = area.split_vertically(label_height + 2 * GAP).1;
+    }
+
+    let max = series
+        .iter()
+        .flat_map(|series| &series.values)
+        .map(|value| to_f64(*value))
+        .fold(0.0, f64::max);
+    let step = tick_step(max);
+    let ticks = ((max / step).ceil() as u32).max(1);
+    let tick_values = (0..=ticks)
+        .map(|tick| step * tick as f64)
+        .collect::<Vec<_>>();
+    let tick_label = |value: &f64| money(Decimal::from_f64(*value).unwrap_or_default());
+    let axis_width = tick_values
+        .iter()
+        .map(|value| area.estimate_text_size(&tick_label(value), &style))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .map(|(width, _)| width)
+        .max()
+        .unwrap_or_default();
+
+    // Only every `every`-th label is written if they don't fit
+    let plot_width = WIDTH - 2 * MARGIN - axis_width - 2 * GAP;
+    let group_width = plot_width as f64 / labels.len().max(1) as f64;
+    let widest = labels
+        .iter()
+        .map(|label| area.estimate_text_size(label, &style))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .map(|(width, _)| width + 2 * GAP)
+        .max()
+        .unwrap_or_default();
+    let every = ((widest as f64 / group_width).ceil() as usize).max(1);
+    let label_width = (group_width * every as f64) as u32;
+    let labels = labels
+        .iter()
+        .map(|label| truncate(&area, label, label_width, &style))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut chart = ChartBuilder::on(&area)
+        .margin_left(MARGIN)
+        .margin_right(MARGIN)
+        .margin_bottom(MARGIN)
+        .x_label_area_size(label_height + 2 * GAP)
+        .y_label_area_size(axis_width + 2 * GAP)
+        .build_cartesian_2d(
+            (0..labels.len().max(1) - 1).into_segmented(),
+            0.0..step * ticks as f64,
+        )?;
+    let x_label = |x: &SegmentValue<usize>| match x {
+        SegmentValue::CenterOf(i) => labels.get(*i).cloned().unwrap_or_default(),
+        _ => String::new(),
+    };
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .bold_line_style(GRID)
+        .light_line_style(TRANSPARENT)
+        .axis_style(AXIS)
+        .x_labels(labels.len().div_ceil(every))
+        .y_labels(ticks as usize + 1)
+        .x_label_style(style.clone())
+        .y_label_style(style.clone())
+        .x_label_formatter(&x_label)
+        .y_label_formatter(&tick_label)
+        .draw()?;
+
+    // Bars are drawn in pixels, as they take just a part of their segment
+    let bar_width = BARS_FILL / series.len().max(1) as f64;
+    for i in 0..labels.len() {
+        let (left, bottom) = chart.backend_coord(&(SegmentValue::Exact(i), 0.0));
+        let right = match i + 1 {
+            next if next < labels.len() => chart.backend_coord(&(SegmentValue::Exact(next), 0.0)).0,
+            _ => chart.backend_coord(&(SegmentValue::Last, 0.0)).0,
+        };
+        let width = (right - left) as f64;
+        for (j, series) in series.iter().enumerate() {
+            let value = series.values.get(i).copied().unwrap_or_default();
+            let top = chart
+                .backend_coord(&(SegmentValue::Exact(i), to_f64(value)))
+                .1;
+            let x = left as f64 + width * ((1.0 - BARS_FILL) / 2.0 + bar_width * j as f64);
+            root.draw(&Rectangle::new(
+                [
+                    (x.round() as i32, top),
+                    ((x + width * bar_width).round() as i32, bottom),
+                ],
+                series.color.filled(),
+            ))?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the point of the circle centered in `center` at `angle`, in
+/// radians clockwise from the top of the circle.
+fn circle_point(center: (f64, f64), radius: f64, angle: f64) -> (i32, i32) {
+    (
+        (center.0 + radius * angle.sin()).round() as i32,
+        (center.1 - radius * angle.cos()).round() as i32,
+    )
+}
+
+/// Draws a pie of `slices`, with a legend of their amounts and shares.
+fn pie_chart(
+    area: &Area,
+    chart_title: &str,
+    slices: &[(String, Decimal)],
+    money: impl Fn(Decimal) -> String,
+) -> DrawResult<()> {
+    let area = title(area, chart_title)?;
+    let style = text_style(LABEL_SIZE);
+    let label_height = text_height(&area, &style)?;
+    let radius = (area.dim_in_pixel().1 - MARGIN) / 2;
+    let center = ((MARGIN + radius) as f64, radius as f64);
+
+    let total = slices.iter().map(|(_, amount)| *amount).sum::<Decimal>();
+    let legend_left = (MARGIN + 2 * radius + 4 * GAP) as i32;
+    // The box of the color is as wide as the text is high
+    let legend_width = WIDTH - legend_left as u32 - MARGIN - label_height - GAP;
+    // The category, and below it the amount and its share
+    let entry_height = 2 * label_height + GAP / 2 + 2 * GAP;
+    let mut start = 0.0;
+    for (i, ((label, amount), color)) in slices.iter().zip(PALETTE).enumerate() {
+        let share = if total.is_zero() {
+            Decimal::ZERO
+        } else {
+            amount / total
+        };
+        let end = if i + 1 == slices.len() {
+            TAU
+        } else {
+            start + to_f64(share) * TAU
+        };
+        let sides = ((end - start) / TAU * PIE_SIDES).ceil().max(1.0) as usize;
+        let mut points = vec![(center.0.round() as i32, center.1.round() as i32)];
+        points.extend((0..=sides).map(|side| {
+            let angle = start + (end - start) * side as f64 / sides as f64;
+            circle_point(center, radius as f64, angle)
+        }));
+        area.draw(&Polygon::new(points, color.filled()))?;
+        start = end;
+
+        let y = (i as u32 * entry_height) as i32;
+        let label = truncate(&area, label, legend_width, &style)?;
+        legend_entry(&area, (legend_left, y), &label, color)?;
+        let percentage = (share * Decimal::ONE_HUNDRED).round_dp(1);
+        let amount = truncate(
+            &area,
+            &format!("{} ({percentage}%)", money(*amount)),
+            legend_width,
+            &style,
+        )?;
+        text(
+            &area,
+            &amount,
+            &style,
+            (
+                legend_left + (label_height + GAP) as i32,
+                y + (label_height + GAP / 2) as i32,
+            ),
+        )?;
+    }
+    Ok(())
+}
+
+/// Draws on a white image with `draw`, and returns its RGB pixels.
+fn draw_pixels(draw: impl FnOnce(&Area) -> DrawResult<()>) -> io::Result<Vec<u8>> {
+    register_fonts();
+    let mut pixels = vec![0; (WIDTH * HEIGHT * 3) as usize];
+    {
+        let area = BitMapBackend::with_buffer(&mut pixels, (WIDTH, HEIGHT)).into_drawing_area();
+        area.fill(&BACKGROUND).map_err(io::Error::other)?;
+        draw(&area).map_err(io::Error::other)?;
+        area.present().map_err(io::Error::other)?;
+    }
+    Ok(pixels)
+}
+
+/// Draws on a white image with `draw`, and returns it as PNG.
+///
+/// Plotters only encodes images into files, so the PNG goes through a
+/// temporary one.
+fn draw_png(draw: impl FnOnce(&Area) -> DrawResult<()>) -> io::Result<Vec<u8>> {
+    let pixels = draw_pixels(draw)?;
+    let file = tempfile::Builder::new().suffix(".png").tempfile()?;
+    {
+        let mut backend = BitMapBackend::new(file.path(), (WIDTH, HEIGHT));
+        backend
+            .blit_bitmap((0, 0), (WIDTH, HEIGHT), &pixels)
+            .map_err(io::Error::other)?;
+        backend.present().map_err(io::Error::other)?;
+    }
+    std::fs::read(file.path())
+}
+
+/// Draws the chart of `kind` of `report`, as a PNG image.
+///
+/// Returns `None` if there is nothing to draw: no expenses, or no
+/// travelers for [`ChartKind::Travelers`].
+pub fn render(
+    kind: ChartKind,
+    report: &Report,
+    ctx: Arc<Mutex<Context>>,
+) -> io::Result<Option<Vec<u8>>> {
+    let currency = ctx.lock().expect("Failed to lock context").currency.clone();
+    let money = |amount: Decimal| MoneyWrapper::new(amount, &currency).to_string();
+    let label = |key: &str| key.translate(ctx.clone());
+    let chart_title = label(kind.title());
+
+    if report.data.expenses.is_empty()
+        || (kind == ChartKind::Travelers && report.summaries.is_empty())
+    {
+        return Ok(None);
+    }
+    draw_png(|area| match kind {
+        ChartKind::Daily => {
+            let timeline = report.timeline();
+            // Dates without the year, if they all have the same one
+            let years = timeline
+                .iter()
+                .map(|(date, _)| date.get(..4))
+                .collect::<HashSet<_>>();
+            let labels = timeline
+                .iter()
+                .map(|(date, _)| match years.len() {
+                    1 => date.get(5..).unwrap_or(date).to_owned(),
+                    _ => date.clone(),
+                })
+                .collect::<Vec<_>>();
+            let series = Series {
+                name: chart_title.clone(),
+                color: PALETTE[0],
+                values: timeline.into_iter().map(|(_, amount)| amount).collect(),
+            };
+            bar_chart(area, &chart_title, &labels, &[series], money)
+        }
+        ChartKind::Travelers => {
+            let labels = report
+                .summaries
+                .iter()
+                .map(|summary| summary.traveler_name.to_string())
+                .collect::<Vec<_>>();
+            let series = [
+                Series {
+                    name: label(REPORT_PAID),
+                    color: PALETTE[0],
+                    values: report.summaries.iter().map(|s| s.paid).collect(),
+                },
+                Series {
+                    name: label(REPORT_CONSUMED),
+                    color: PALETTE[1],
+                    values: report.summaries.iter().map(|s| s.consumed).collect(),
+                },
+            ];
+            bar_chart(area, &chart_title, &labels, &series, money)
+        }
+        ChartKind::Categories => {
+            let mut slices = report
+                .categories()
+                .into_iter()
+                .map(|(category, amount)| {
+                    let name =
+                        category.map_or_else(|| label(REPORT_UNCATEGORIZED), |c| format!("#{c}"));
+                    (name, amount)
+                })
+                .collect::<Vec<_>>();
+            if slices.len() > PALETTE.len() {
+                let other = slices
+                    .split_off(PALETTE.len() - 1)
+                    .into_iter()
+                    .map(|(_, amount)| amount)
+                    .sum();
+                slices.push((label(CHART_OTHER), other));
+            }
+            pie_chart(area, &chart_title, &slices, money)
+        }
+    })
+    .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        HEIGHT, LABEL_SIZE, WIDTH, draw_png, register_fonts, text_style, tick_step, truncate,
+    };
+    use plotters::prelude::*;
+
+    #[test]
+    fn ticks() {
+        assert_eq!(tick_step(0.0), 1.0);
+        assert_eq!(tick_step(100.0), 20.0);
+        assert_eq!(tick_step(120.0), 50.0);
+        assert_eq!(tick_step(7.0), 2.0);
+        assert_eq!(tick_step(3000.0), 1000.0);
+    }
+
+    #[test]
+    fn truncate_text() {
+        register_fonts();
+        let mut pixels = vec![0; (WIDTH * HEIGHT * 3) as usize];
+        let area = BitMapBackend::with_buffer(&mut pixels, (WIDTH, HEIGHT)).into_drawing_area();
+        let style = text_style(LABEL_SIZE);
+        let width = |text: &str| area.estimate_text_size(text, &style).unwrap().0;
+
+        assert_eq!(truncate(&area, "Alice", 100, &style).unwrap(), "Alice");
+        let truncated = truncate(&area, "Alice", width("Alice") - 1, &style).unwrap();
+        assert!(truncated.ends_with('…') && "Alice".starts_with(truncated.trim_end_matches('…')));
+        assert!(width(&truncated) < width("Alice"));
+        assert_eq!(truncate(&area, "Alice", 0, &style).unwrap(), "…");
+    }
+
+    #[test]
+    fn non_latin_text() {
+        // Letters missing from the font would all be drawn as the same box
+        let draw = |text: &'static str| {
+            draw_png(|area| area.draw_text(text, &text_style(LABEL_SIZE), (10, 10))).unwrap()
+        };
+        let missing = draw("\u{E000}\u{E000}\u{E000}\u{E000}");
+        for name in ["Zoë", "Дима", "Νίκος", "Ümit"] {
+            assert_ne!(draw(name), missing, "{name} is not drawn");
+        }
+    }
+}
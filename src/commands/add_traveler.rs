@@ -4,6 +4,7 @@ use crate::{
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     errors::CommandError,
     i18n::{self, TranslateWithArgs},
+    topics::MessageExt,
     traveler::{Name, Traveler},
 };
 use macro_rules_attribute::apply;
@@ -23,7 +24,7 @@ pub async fn add_traveler(
     tracing::debug!("{LOG_DEBUG_START}");
 
     // Check if traveler exists on db
-    let count_res = Traveler::db_count(db.clone(), msg.chat.id, &name).await;
+    let count_res = Traveler::db_count(db.clone(), msg.ledger_id(), &name).await;
     match count_res {
         Ok(Some(count)) if *count > 0 => {
             tracing::warn!(
@@ -39,7 +40,7 @@ pub async fn add_traveler(
         }
         Ok(_) => {
             // Create traveler on db
-            let create_res = Traveler::db_create(db, msg.chat.id, &name).await;
+            let create_res = Traveler::db_create(db, msg.ledger_id(), &name).await;
             match create_res {
                 Ok(_) => {
                     tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -0,0 +1,164 @@
+use crate::{
+    Context,
+    audit::{self as ledger_audit, Problem},
+    commands::{AUDIT_FIX_CALLBACK, CommandOutcome, inline_keyboards},
+    consts::{FIX_KWORD, LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, Translate, TranslateWithArgs},
+    topics::MessageExt,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::{
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup},
+};
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn audit(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    args: &str,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let args = args.trim();
+    let fix = args.eq_ignore_ascii_case(FIX_KWORD);
+    if !args.is_empty() && !fix {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::AUDIT_USAGE.translate(ctx),
+        ));
+    }
+
+    let chat_id = msg.ledger_id();
+    let mut problems = match ledger_audit::audit(db.clone(), chat_id).await {
+        Ok(problems) => problems,
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::Audit);
+        }
+    };
+
+    let mut replies = Vec::new();
+    if fix {
+        let repairable = problems.iter().filter(|p| p.is_repairable()).count();
+        if repairable > 0 {
+            let repaired = match ledger_audit::repair(db.clone(), chat_id, &problems).await {
+                Ok(()) => ledger_audit::audit(db, chat_id).await,
+                Err(err) => Err(err),
+            };
+            problems = match repaired {
+                Ok(problems) => problems,
+                Err(err) => {
+                    tracing::error!("{err}");
+                    return Err(CommandError::Audit);
+                }
+            };
+        }
+        replies.push(i18n::commands::AUDIT_REPAIRED.translate_with_args(
+            ctx.clone(),
+            &hashmap! {i18n::args::COUNT.into() => repairable.into()},
+        ));
+    }
+
+    if problems.is_empty() {
+        replies.push(i18n::commands::AUDIT_OK.translate(ctx));
+    } else {
+        tracing::warn!("Ledger audit found {} problems", problems.len());
+        let lines = problems
+            .iter()
+            .map(|problem| format!("- {}", problem.translate(ctx.clone())))
+            .collect::<Vec<_>>()
+            .join("\n");
+        replies.push(format!(
+            "{header}\n{lines}",
+            header = i18n::commands::AUDIT_HEADER.translate(ctx.clone())
+        ));
+        if problems.iter().any(Problem::is_repairable) {
+            replies.push(i18n::commands::AUDIT_REPAIR_HINT.translate(ctx.clone()));
+        }
+        if !problems.iter().all(Problem::is_repairable) {
+            replies.push(i18n::commands::AUDIT_MANUAL_HINT.translate(ctx));
+        }
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    Ok(CommandOutcome::Success(replies.join("\n\n")))
+}
+
+/// Builds the keyboard of `/audit`: a "Repair" button, shown only if some of
+/// the problems found can be repaired.
+///
+/// Returns `None` if nothing can be repaired or if the DB query fails.
+pub async fn audit_keyboard(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    ctx: Arc<Mutex<Context>>,
+) -> Option<InlineKeyboardMarkup> {
+    let problems = ledger_audit::audit(db, msg.ledger_id()).await.ok()?;
+    if !problems.iter().any(Problem::is_repairable) {
+        return None;
+    }
+    let button = InlineKeyboardButton::callback(
+        i18n::labels::REPAIR_BUTTON.translate(ctx),
+        AUDIT_FIX_CALLBACK,
+    );
+    Some(inline_keyboards::buttons_keyboard(vec![button], 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        debt::NET,
+        i18n::{self, Translate, TranslateWithArgs},
+        tests::{TestBot, helpers},
+    };
+    use maplit::hashmap;
+
+    test! { audit_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 60.into(), "Alice", &["all"]).await;
+
+        bot.update("/audit");
+        let response = i18n::commands::AUDIT_OK.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { audit_fix,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 60.into(), "Alice", &["all"]).await;
+
+        db.query(format!("UPDATE traveler SET {NET} = 0dec")).await.unwrap();
+        bot.update("/audit fix");
+        let response = format!(
+            "{repaired}\n\n{ok}",
+            repaired = i18n::commands::AUDIT_REPAIRED.translate_with_args_default(
+                &hashmap! {i18n::args::COUNT.into() => 2.into()},
+            ),
+            ok = i18n::commands::AUDIT_OK.translate_default(),
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { audit_usage,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/audit now");
+        let response = i18n::commands::AUDIT_USAGE.translate_default();
+        bot.test_last_message(&response).await;
+    }
+}
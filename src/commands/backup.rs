@@ -0,0 +1,100 @@
+use crate::{
+    Context,
+    backup::{BACKUP_FORMAT, Backup},
+    commands::{CommandOutcome, Document},
+    consts::{DATE_FORMAT, LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, TranslateWithArgs},
+    timezone,
+    topics::MessageExt,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn backup(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let backup = match Backup::db_select(db, msg.ledger_id()).await {
+        Ok(backup) => backup,
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::Backup);
+        }
+    };
+    let content = match backup.to_json() {
+        Ok(content) => content,
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::Backup);
+        }
+    };
+
+    let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
+    let date = timezone::to_zoned(backup.created_at, &tz)
+        .map(|zoned| zoned.strftime(DATE_FORMAT).to_string())
+        .unwrap_or_default();
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Travel plan backed up");
+    Ok(CommandOutcome::Documents {
+        message: i18n::commands::BACKUP_OK.translate_with_args(
+            ctx,
+            &hashmap! {
+                i18n::args::TRAVELERS_COUNT.into() => backup.travelers.len().into(),
+                i18n::args::EXPENSES_COUNT.into() => backup.expenses.len().into(),
+                i18n::args::TRANSFERS_COUNT.into() => backup.transfers.len().into(),
+            },
+        ),
+        documents: vec![Document {
+            file_name: format!("{BACKUP_FORMAT}-{date}.json"),
+            content,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backup::{BACKUP_FORMAT, BACKUP_VERSION, Backup},
+        db::db,
+        i18n::{self, TranslateWithArgs},
+        tests::{TestBot, helpers},
+    };
+    use maplit::hashmap;
+
+    test! { backup_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 100.into(), "Alice", &["all"]).await;
+
+        bot.update("/backup");
+        let response = i18n::commands::BACKUP_OK.translate_with_args_default(&hashmap! {
+            i18n::args::TRAVELERS_COUNT.into() => 2.into(),
+            i18n::args::EXPENSES_COUNT.into() => 1.into(),
+            i18n::args::TRANSFERS_COUNT.into() => 0.into(),
+        });
+        bot.dispatch().await;
+        assert!(bot.sent_texts().contains(&response));
+
+        let documents = bot.sent_documents();
+        let (file_name, content) = documents.last().unwrap();
+        assert!(file_name.starts_with(BACKUP_FORMAT) && file_name.ends_with(".json"));
+        let backup = Backup::from_json(content.as_bytes()).unwrap();
+        assert_eq!(backup.version, BACKUP_VERSION);
+        assert_eq!(backup.travelers.len(), 2);
+        assert_eq!(backup.expenses[0].description, "Dinner");
+        assert_eq!(backup.expenses[0].shares.len(), 2);
+    }
+}
@@ -2,6 +2,7 @@ use crate::{
     Context, HandlerResult,
     dialogues::storage::DialogueRegistry,
     i18n::{self, Translate, TranslateWithArgs, args},
+    topics::{InTopic, MessageExt},
 };
 use macro_rules_attribute::apply;
 use maplit::hashmap;
@@ -16,12 +17,12 @@ pub async fn cancel(
     msg: Message,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
-    let chat_id = msg.chat.id;
+    let key = msg.dialogue_key().0;
 
     // Capture the running dialogue's label *before* exiting so the cancel
     // confirmation can name the process that was just cancelled.
-    let running_label = registry.running_label(chat_id).await;
-    let cancelled_any = registry.exit_all(chat_id).await?;
+    let running_label = registry.running_label(key).await;
+    let cancelled_any = registry.exit_all(key).await?;
 
     let text = if cancelled_any {
         let process_name = match running_label {
@@ -37,7 +38,9 @@ pub async fn cancel(
     } else {
         i18n::commands::CANCEL_NO_PROCESS_TO_CANCEL.translate(ctx)
     };
-    bot.send_message(chat_id, text).await?;
+    bot.send_message(msg.chat.id, text)
+        .in_topic(msg.topic_id())
+        .await?;
     Ok(())
 }
 
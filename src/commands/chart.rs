@@ -0,0 +1,130 @@
+use crate::{
+    Context,
+    chart::{self, ChartKind},
+    commands::{CommandOutcome, Document},
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, Translate, TranslateWithArgs},
+    report::Report,
+    topics::MessageExt,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use strum::IntoEnumIterator;
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn chart(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    kind: &str,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let Ok(kind) = ChartKind::from_str(kind.trim()) else {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        let charts = ChartKind::iter()
+            .map(|kind| kind.as_ref().to_owned())
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::CHART_USAGE
+                .translate_with_args(ctx, &hashmap! {i18n::args::CHARTS.into() => charts.into()}),
+        ));
+    };
+
+    let (currency, timezone) = {
+        let ctx_guard = ctx.lock().expect("Failed to lock context");
+        (ctx_guard.currency.clone(), ctx_guard.timezone.clone())
+    };
+    let report = match Report::db_select(db, msg.ledger_id(), currency, timezone).await {
+        Ok(report) => report,
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::Chart);
+        }
+    };
+
+    match chart::render(kind, &report, ctx.clone()) {
+        Ok(Some(content)) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            tracing::info!("Chart of the {} sent", kind.as_ref());
+            Ok(CommandOutcome::Photo {
+                caption: kind.title().translate(ctx),
+                photo: Document {
+                    file_name: format!("{}.png", kind.as_ref()),
+                    content,
+                },
+            })
+        }
+        Ok(None) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            Ok(CommandOutcome::Success(
+                i18n::commands::CHART_NO_DATA.translate(ctx),
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            Err(CommandError::Chart)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, Translate, TranslateWithArgs},
+        tests::{TestBot, helpers},
+    };
+    use maplit::hashmap;
+
+    test! { chart_usage,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "/chart pie");
+        let response = i18n::commands::CHART_USAGE.translate_with_args_default(&hashmap! {
+            i18n::args::CHARTS.into() => "daily, travelers, categories".into(),
+        });
+        bot.test_last_message(&response).await;
+    }
+
+    test! { chart_no_data,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "/chart daily");
+        let response = i18n::commands::CHART_NO_DATA.translate_default();
+        bot.test_last_message(&response).await;
+        assert!(bot.sent_photos().is_empty());
+    }
+
+    test! { chart_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner #food", 60.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "Taxi", 40.into(), "Bob", &["all"]).await;
+
+        for (kind, title) in [
+            ("daily", i18n::labels::CHART_DAILY),
+            ("Travelers", i18n::labels::CHART_TRAVELERS),
+            ("categories", i18n::labels::CHART_CATEGORIES),
+        ] {
+            bot.update(&format!("/chart {kind}"));
+            bot.dispatch().await;
+            let photos = bot.sent_photos();
+            let [(file_name, caption)] = photos.as_slice() else {
+                panic!("expected a single photo, got {photos:?}");
+            };
+            assert_eq!(file_name, &format!("{}.png", kind.to_lowercase()));
+            assert_eq!(caption.as_deref(), Some(title.translate_default().as_str()));
+        }
+    }
+}
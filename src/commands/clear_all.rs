@@ -6,6 +6,7 @@ use crate::{
     errors::CommandError,
     expense::Expense,
     i18n::{self, Translate},
+    topics::MessageExt,
     transfer::Transfer,
     transferred_to::TransferredTo,
     traveler::Traveler,
@@ -25,15 +26,15 @@ pub async fn clear_all(
     tracing::debug!("{LOG_DEBUG_START}");
 
     // Check if there is anything to clear.
-    let has_travelers = Traveler::db_select(db.clone(), msg.chat.id)
+    let has_travelers = Traveler::db_select(db.clone(), msg.ledger_id())
         .await
         .map(|v| !v.is_empty())
         .unwrap_or(false);
-    let has_expenses = Expense::db_select(db.clone(), msg.chat.id)
+    let has_expenses = Expense::db_select(db.clone(), msg.ledger_id())
         .await
         .map(|v| !v.is_empty())
         .unwrap_or(false);
-    let has_transfers = Transfer::transfers(db.clone(), msg.chat.id)
+    let has_transfers = Transfer::transfers(db.clone(), msg.ledger_id())
         .await
         .map(|v| !v.is_empty())
         .unwrap_or(false);
@@ -45,19 +46,19 @@ pub async fn clear_all(
         ));
     }
 
-    if let Err(err) = Expense::db_delete_all(db.clone(), msg.chat.id).await {
+    if let Err(err) = Expense::db_delete_all(db.clone(), msg.ledger_id()).await {
         tracing::error!("{err}");
         return Err(CommandError::ClearAll);
     }
-    if let Err(err) = TransferredTo::db_delete_all(db.clone(), msg.chat.id).await {
+    if let Err(err) = TransferredTo::db_delete_all(db.clone(), msg.ledger_id()).await {
         tracing::error!("{err}");
         return Err(CommandError::ClearAll);
     }
-    if let Err(err) = Traveler::db_delete_all(db.clone(), msg.chat.id).await {
+    if let Err(err) = Traveler::db_delete_all(db.clone(), msg.ledger_id()).await {
         tracing::error!("{err}");
         return Err(CommandError::ClearAll);
     }
-    if let Err(err_update) = update_debts(db, msg.chat.id).await {
+    if let Err(err_update) = update_debts(db, msg.ledger_id()).await {
         tracing::warn!("{err_update}");
     }
 
@@ -6,6 +6,7 @@ use crate::{
     errors::CommandError,
     expense::Expense,
     i18n::{self, Translate},
+    topics::MessageExt,
 };
 use macro_rules_attribute::apply;
 use std::sync::{Arc, Mutex};
@@ -21,12 +22,12 @@ pub async fn clear_expenses(
 ) -> Result<CommandOutcome, CommandError> {
     tracing::debug!("{LOG_DEBUG_START}");
 
-    let expenses = Expense::db_select(db.clone(), msg.chat.id).await;
+    let expenses = Expense::db_select(db.clone(), msg.ledger_id()).await;
     match expenses {
         Ok(list) if !list.is_empty() => {
-            match Expense::db_delete_all(db.clone(), msg.chat.id).await {
+            match Expense::db_delete_all(db.clone(), msg.ledger_id()).await {
                 Ok(_) => {
-                    if let Err(err_update) = update_debts(db, msg.chat.id).await {
+                    if let Err(err_update) = update_debts(db, msg.ledger_id()).await {
                         tracing::warn!("{err_update}");
                     }
                     tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -5,6 +5,7 @@ use crate::{
     debt::update_debts,
     errors::CommandError,
     i18n::{self, Translate},
+    topics::MessageExt,
     transfer::Transfer,
     transferred_to::TransferredTo,
 };
@@ -22,12 +23,12 @@ pub async fn clear_transfers(
 ) -> Result<CommandOutcome, CommandError> {
     tracing::debug!("{LOG_DEBUG_START}");
 
-    let transfers = Transfer::transfers(db.clone(), msg.chat.id).await;
+    let transfers = Transfer::transfers(db.clone(), msg.ledger_id()).await;
     match transfers {
         Ok(list) if !list.is_empty() => {
-            match TransferredTo::db_delete_all(db.clone(), msg.chat.id).await {
+            match TransferredTo::db_delete_all(db.clone(), msg.ledger_id()).await {
                 Ok(_) => {
-                    if let Err(err_update) = update_debts(db, msg.chat.id).await {
+                    if let Err(err_update) = update_debts(db, msg.ledger_id()).await {
                         tracing::warn!("{err_update}");
                     }
                     tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -5,6 +5,7 @@ use crate::{
     debt::update_debts,
     errors::CommandError,
     i18n::{self, Translate, TranslateWithArgs},
+    topics::MessageExt,
     traveler::Traveler,
 };
 use macro_rules_attribute::apply;
@@ -22,10 +23,11 @@ pub async fn clear_travelers(
 ) -> Result<CommandOutcome, CommandError> {
     tracing::debug!("{LOG_DEBUG_START}");
 
-    let travelers = Traveler::db_select(db.clone(), msg.chat.id).await;
+    let travelers = Traveler::db_select(db.clone(), msg.ledger_id()).await;
     match travelers {
         Ok(list) if !list.is_empty() => {
-            let with_expenses = Traveler::travelers_with_expenses(db.clone(), msg.chat.id).await?;
+            let with_expenses =
+                Traveler::travelers_with_expenses(db.clone(), msg.ledger_id()).await?;
 
             if !with_expenses.is_empty() {
                 let names = with_expenses
@@ -46,9 +48,9 @@ pub async fn clear_travelers(
 
             // Only delete travelers; transfers cascade via DB relationships.
             let count = list.len();
-            match Traveler::db_delete_all(db.clone(), msg.chat.id).await {
+            match Traveler::db_delete_all(db.clone(), msg.ledger_id()).await {
                 Ok(_) => {
-                    if let Err(err_update) = update_debts(db, msg.chat.id).await {
+                    if let Err(err_update) = update_debts(db, msg.ledger_id()).await {
                         tracing::warn!("{err_update}");
                     }
                     tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -0,0 +1,308 @@
+use crate::{
+    Context, HandlerResult,
+    balance::Balance,
+    commands::{Command, CommandOutcome},
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, Translate, TranslateWithArgs},
+    money_wrapper::MoneyWrapper,
+    stats::Stats,
+    topics::{InTopic, MessageExt},
+    trip::Trip,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn close_trip(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let active = Trip::db_select_active(db.clone(), msg.ledger_id()).await;
+    let balances = Balance::balances(db.clone(), msg.ledger_id()).await;
+    let (active, balances) = match (active, balances) {
+        (Ok(Some(active)), Ok(balances)) => (active, balances),
+        (Ok(None), _) => {
+            tracing::error!("No active trip found for chat {}", msg.chat.id);
+            return Err(CommandError::CloseTrip);
+        }
+        (Err(err), _) | (_, Err(err)) => {
+            tracing::error!("{err}");
+            return Err(CommandError::CloseTrip);
+        }
+    };
+
+    if active.closed {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::CLOSE_TRIP_ALREADY_CLOSED.translate_with_args(
+                ctx,
+                &hashmap! {i18n::args::NAME.into() => active.name.into()},
+            ),
+        ));
+    }
+
+    // Only a trip where everyone has settled up can be closed
+    let currency = ctx.lock().expect("Failed to lock context").currency.clone();
+    if balances.iter().any(|balance| {
+        !MoneyWrapper::new(balance.debt, &currency)
+            .round_value()
+            .is_zero()
+    }) {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::CLOSE_TRIP_NOT_SETTLED.translate_with_args(
+                ctx,
+                &hashmap! {i18n::args::NAME.into() => active.name.into()},
+            ),
+        ));
+    }
+
+    if let Err(err) = active.db_set_closed(db.clone(), true).await {
+        tracing::error!("{err}");
+        return Err(CommandError::CloseTrip);
+    }
+
+    let reply = i18n::commands::CLOSE_TRIP_OK.translate_with_args(
+        ctx.clone(),
+        &hashmap! {i18n::args::NAME.into() => active.name.clone().into()},
+    );
+    // The final summary is best effort: the trip is closed anyway
+    let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
+    let reply = match Stats::stats(db, msg.ledger_id(), &tz).await {
+        Ok(Some(stats)) => format!("{reply}\n\n{}", stats.translate(ctx)),
+        Ok(None) => reply,
+        Err(err) => {
+            tracing::error!("{err}");
+            reply
+        }
+    };
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Trip '{}' closed", active.name);
+    Ok(CommandOutcome::Success(reply))
+}
+
+/// Localized refusal of a command editing a closed trip.
+#[derive(Debug, Clone)]
+pub struct TripClosed(pub String);
+
+/// Returns the localized refusal if the active trip of the chat that sent
+/// `msg` is closed. Errors are logged and let the command through, so that a
+/// failing lookup never locks a chat out.
+pub async fn closed_trip_refusal(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    ctx: Arc<Mutex<Context>>,
+) -> Option<String> {
+    match Trip::db_select_active(db, msg.ledger_id()).await {
+        Ok(Some(active)) if active.closed => Some(i18n::commands::TRIP_CLOSED.translate_with_args(
+            ctx,
+            &hashmap! {i18n::args::NAME.into() => active.name.into()},
+        )),
+        Ok(_) => None,
+        Err(err) => {
+            tracing::error!("{err}");
+            None
+        }
+    }
+}
+
+/// Handler filter matching the commands that would edit a closed trip.
+pub async fn closed_trip_guard(
+    db: Arc<Surreal<Any>>,
+    msg: Message,
+    cmd: Command,
+    ctx: Arc<Mutex<Context>>,
+) -> Option<TripClosed> {
+    if !cmd.is_mutating() {
+        return None;
+    }
+    closed_trip_refusal(db, &msg, ctx).await.map(TripClosed)
+}
+
+#[apply(trace_skip_all)]
+pub async fn closed_trip_endpoint(bot: Bot, msg: Message, refusal: TripClosed) -> HandlerResult {
+    bot.send_message(msg.chat.id, refusal.0)
+        .in_topic(msg.topic_id())
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        consts::TODAY_KWORD,
+        db::db,
+        i18n::{self, Translate, TranslateWithArgs},
+        tests::{TestBot, helpers},
+    };
+    use maplit::hashmap;
+
+    test! { close_trip_ok,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "");
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 50.into(), "Alice", &["all"]).await;
+        helpers::transfer(&mut bot, "Bob", "Alice", 25.into()).await;
+
+        bot.update("/closetrip");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        let header = i18n::commands::CLOSE_TRIP_OK.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Default".into()},
+        );
+        assert!(response.starts_with(&header));
+        assert!(response.len() > header.len());
+
+        bot.update("/closetrip");
+        let response = i18n::commands::CLOSE_TRIP_ALREADY_CLOSED.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Default".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { close_trip_not_settled,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "");
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 50.into(), "Alice", &["all"]).await;
+
+        bot.update("/closetrip");
+        let response = i18n::commands::CLOSE_TRIP_NOT_SETTLED.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Default".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        // The trip is still open
+        bot.update("/addtraveler Charlie");
+        let response = i18n::commands::ADD_TRAVELER_OK.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Charlie".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { close_trip_read_only,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "");
+        helpers::add_traveler(&mut bot, "Alice").await;
+        bot.update("/closetrip");
+        bot.dispatch().await;
+
+        let closed = i18n::commands::TRIP_CLOSED.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Default".into()},
+        );
+        // Mutating commands are refused, with or without arguments
+        for command in [
+            "/addtraveler Bob",
+            "/addtraveler",
+            "/deletetraveler Alice",
+            "/addexpense",
+            "/transfer Alice Bob 10",
+            "/transfer",
+            "/deleteexpense 1",
+            "/clearall",
+            "/renametrip Japan",
+            "/settle all",
+            "/audit fix",
+            "/rebuildbalances",
+            "/setsettlement greedy",
+            "/setsimplification none",
+        ] {
+            bot.update(command);
+            bot.test_last_message(&closed).await;
+        }
+
+        // So are the buttons doing the same
+        bot.update_callback("auditfix:");
+        bot.test_last_message(&closed).await;
+
+        // Read-only commands keep working
+        bot.update("/listtravelers");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        assert!(response.contains("Alice"));
+        for command in ["/showbalances", "/audit", "/setsettlement", "/setsimplification"] {
+            bot.update(command);
+            let response = bot.dispatch_and_last_message().await.unwrap();
+            assert_ne!(response, closed);
+        }
+
+        // Other trips are not affected
+        bot.update("/newtrip Japan");
+        bot.dispatch().await;
+        bot.update("/addtraveler Bob");
+        let response = i18n::commands::ADD_TRAVELER_OK.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Bob".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { close_trip_from_other_topic,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "");
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+
+        // Dialogues waiting for their last step in topics sharing the ledger
+        bot.set_topic(Some(1));
+        for text in ["/addexpense", "Dinner", "30", TODAY_KWORD, "Alice"] {
+            bot.update(text);
+            bot.dispatch().await;
+        }
+        bot.set_topic(Some(2));
+        for text in ["/transfer", "Alice", "Bob"] {
+            bot.update(text);
+            bot.dispatch().await;
+        }
+
+        bot.set_topic(Some(3));
+        bot.update("/closetrip");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        let header = i18n::commands::CLOSE_TRIP_OK.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Default".into()},
+        );
+        assert!(response.starts_with(&header));
+
+        // Neither commits into the closed trip, and both end
+        let closed = i18n::commands::TRIP_CLOSED.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Default".into()},
+        );
+        let no_process = i18n::commands::CANCEL_NO_PROCESS_TO_CANCEL.translate_default();
+        for (topic, text) in [(1, "all"), (2, "10")] {
+            bot.set_topic(Some(topic));
+            bot.update(text);
+            bot.test_last_message(&closed).await;
+            bot.update("/cancel");
+            bot.test_last_message(&no_process).await;
+        }
+        bot.update("/showbalances");
+        let response = i18n::commands::SHOW_BALANCES_SETTLED_UP.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { close_trip_dialogue_running,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/addtraveler");
+        bot.dispatch().await;
+
+        bot.update("/closetrip");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        let header = i18n::commands::CLOSE_TRIP_OK.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Default".into()},
+        );
+        assert!(!response.starts_with(&header));
+    }
+}
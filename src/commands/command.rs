@@ -1,14 +1,19 @@
 use crate::{
     Context, HandlerResult,
     commands::{
-        CommandArg, CommandOutcome, HelpMessage, add_traveler, clear_all, clear_expenses,
-        clear_transfers, clear_travelers, delete_expense, delete_transfer, delete_traveler, help,
-        inline_keyboards, list_expenses, list_transfers, list_travelers, set_currency,
-        set_language, show_balances, show_expense, show_stats, transfer,
+        CommandArg, CommandOutcome, HelpMessage, add_traveler, audit, audit_keyboard, backup,
+        chart, clear_all, clear_expenses, clear_transfers, clear_travelers, close_trip,
+        closed_trip_refusal, debt_graph, delete_expense, delete_transfer, delete_traveler, export,
+        help, inline_keyboards, list_expenses, list_transfers, list_travelers, list_trips,
+        new_trip, pay_prefs, rebuild_balances, rename_trip, reopen_trip, report, send_outcome,
+        set_currency, set_language, set_settlement, set_simplification, set_timezone, settle,
+        settle_keyboard, show_balances, show_expense, show_stats, show_stats_keyboard, statement,
+        summary, switch_trip, topic_ledger, transfer, transfer_all,
     },
-    consts::MIN_SIMILARITY_SCORE,
+    consts::{ALL_KWORD, FIX_KWORD, MIN_SIMILARITY_SCORE},
     i18n::{self, Translate, TranslateWithArgs, help::*},
     keyboard,
+    topics::MessageExt,
     traveler::Name,
 };
 use maplit::hashmap;
@@ -20,7 +25,6 @@ use std::sync::{Arc, Mutex};
 use strum::{AsRefStr, EnumIter, EnumString, IntoEnumIterator};
 use surrealdb::{Surreal, engine::any::Any};
 use teloxide::{
-    payloads::SendMessageSetters,
     prelude::*,
     types::{BotCommand, BotCommandScope, InlineKeyboardButton, InlineKeyboardMarkup, Recipient},
     utils::command::BotCommands,
@@ -45,6 +49,26 @@ pub enum Command {
     },
     #[command(description = "{descr-set-currency}")]
     SetCurrency { currency: CommandArg<String> },
+    #[command(description = "{descr-set-timezone}")]
+    SetTimezone { timezone: CommandArg<String> },
+    #[command(description = "{descr-set-settlement}")]
+    SetSettlement { mode: String },
+    #[command(description = "{descr-set-simplification}")]
+    SetSimplification { mode: String },
+    #[command(description = "{descr-new-trip}")]
+    NewTrip { name: CommandArg<Name> },
+    #[command(description = "{descr-switch-trip}")]
+    SwitchTrip { name: CommandArg<Name> },
+    #[command(description = "{descr-list-trips}")]
+    ListTrips,
+    #[command(description = "{descr-rename-trip}")]
+    RenameTrip { name: CommandArg<Name> },
+    #[command(description = "{descr-close-trip}")]
+    CloseTrip,
+    #[command(description = "{descr-reopen-trip}")]
+    ReopenTrip,
+    #[command(description = "{descr-topic-ledger}")]
+    TopicLedger { mode: String },
     #[command(description = "{descr-add-traveler}")]
     AddTraveler { name: CommandArg<Name> },
     #[command(description = "{descr-delete-traveler}")]
@@ -52,11 +76,11 @@ pub enum Command {
     #[command(description = "{descr-list-travelers}")]
     ListTravelers,
     #[command(description = "{descr-add-expense}")]
-    AddExpense,
+    AddExpense { args: String },
     #[command(description = "{descr-delete-expense}")]
     DeleteExpense { number: CommandArg<i64> },
     #[command(description = "{descr-list-expenses}")]
-    ListExpenses { description: String },
+    ListExpenses { filter: String },
     #[command(description = "{descr-show-expense}")]
     ShowExpense { number: CommandArg<i64> },
     #[command(description = "{descr-transfer}")]
@@ -64,11 +88,37 @@ pub enum Command {
     #[command(description = "{descr-delete-transfer}")]
     DeleteTransfer { number: CommandArg<i64> },
     #[command(description = "{descr-list-transfers}")]
-    ListTransfers { name: CommandArg<Name> },
+    ListTransfers { filter: CommandArg<String> },
     #[command(description = "{descr-show-balances}")]
     ShowBalances { name: CommandArg<Name> },
+    #[command(description = "{descr-statement}")]
+    Statement { args: String },
+    #[command(description = "{descr-summary}")]
+    Summary,
+    #[command(description = "{descr-settle}")]
+    Settle { args: String },
+    #[command(description = "{descr-pay-prefs}")]
+    PayPrefs { args: String },
     #[command(description = "{descr-show-stats}")]
-    ShowStats,
+    ShowStats { section: String },
+    #[command(description = "{descr-rebuild-balances}")]
+    RebuildBalances,
+    #[command(description = "{descr-audit}")]
+    Audit { args: String },
+    #[command(description = "{descr-export}")]
+    Export { format: String },
+    #[command(description = "{descr-report}")]
+    Report,
+    #[command(description = "{descr-chart}")]
+    Chart { kind: String },
+    #[command(description = "{descr-debt-graph}")]
+    DebtGraph { args: String },
+    #[command(description = "{descr-backup}")]
+    Backup,
+    #[command(description = "{descr-restore}")]
+    Restore,
+    #[command(description = "{descr-import}")]
+    Import,
     #[command(description = "{descr-clear-travelers}")]
     ClearTravelers,
     #[command(description = "{descr-clear-expenses}")]
@@ -81,6 +131,45 @@ pub enum Command {
     Cancel,
 }
 
+impl Command {
+    /// Returns `true` if the command edits the travel plan, and is thus
+    /// refused while the travel plan is closed.
+    pub fn is_mutating(&self) -> bool {
+        use Command::*;
+        // A bare `/settle` or `/payprefs` only shows the settlement plan or
+        // the payment preferences, and a bare `/setsettlement` or
+        // `/setsimplification` the mode in effect
+        if let Settle { args: arg }
+        | PayPrefs { args: arg }
+        | SetSettlement { mode: arg }
+        | SetSimplification { mode: arg } = self
+        {
+            return !arg.trim().is_empty();
+        }
+        // `/audit` only checks the ledger, unless asked to repair it
+        if let Audit { args } = self {
+            return args.trim().eq_ignore_ascii_case(FIX_KWORD);
+        }
+        matches!(
+            self,
+            RenameTrip { .. }
+                | AddTraveler { .. }
+                | DeleteTraveler { .. }
+                | AddExpense { .. }
+                | DeleteExpense { .. }
+                | Transfer { .. }
+                | DeleteTransfer { .. }
+                | ClearTravelers
+                | ClearExpenses
+                | ClearTransfers
+                | RebuildBalances
+                | Restore
+                | Import
+                | ClearAll
+        )
+    }
+}
+
 pub enum ParseCommand {
     ValidCommandName(Command),
     BestMatch(Command),
@@ -102,6 +191,46 @@ impl Command {
                 variant_to_string!(Command::SetCurrency),
                 i18n::help::DESCR_SET_CURRENCY.translate(ctx.clone()),
             ),
+            BotCommand::new(
+                variant_to_string!(Command::SetTimezone),
+                i18n::help::DESCR_SET_TIMEZONE.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::SetSettlement),
+                i18n::help::DESCR_SET_SETTLEMENT.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::SetSimplification),
+                i18n::help::DESCR_SET_SIMPLIFICATION.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::NewTrip),
+                i18n::help::DESCR_NEW_TRIP.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::SwitchTrip),
+                i18n::help::DESCR_SWITCH_TRIP.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::ListTrips),
+                i18n::help::DESCR_LIST_TRIPS.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::RenameTrip),
+                i18n::help::DESCR_RENAME_TRIP.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::CloseTrip),
+                i18n::help::DESCR_CLOSE_TRIP.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::ReopenTrip),
+                i18n::help::DESCR_REOPEN_TRIP.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::TopicLedger),
+                i18n::help::DESCR_TOPIC_LEDGER.translate(ctx.clone()),
+            ),
             BotCommand::new(
                 variant_to_string!(Command::AddTraveler),
                 i18n::help::DESCR_ADD_TRAVELER.translate(ctx.clone()),
@@ -146,10 +275,62 @@ impl Command {
                 variant_to_string!(Command::ShowBalances),
                 i18n::help::DESCR_SHOW_BALANCES.translate(ctx.clone()),
             ),
+            BotCommand::new(
+                variant_to_string!(Command::Statement),
+                i18n::help::DESCR_STATEMENT.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::Summary),
+                i18n::help::DESCR_SUMMARY.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::Settle),
+                i18n::help::DESCR_SETTLE.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::PayPrefs),
+                i18n::help::DESCR_PAY_PREFS.translate(ctx.clone()),
+            ),
             BotCommand::new(
                 variant_to_string!(Command::ShowStats),
                 i18n::help::DESCR_SHOW_STATS.translate(ctx.clone()),
             ),
+            BotCommand::new(
+                variant_to_string!(Command::RebuildBalances),
+                i18n::help::DESCR_REBUILD_BALANCES.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::Audit),
+                i18n::help::DESCR_AUDIT.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::Export),
+                i18n::help::DESCR_EXPORT.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::Report),
+                i18n::help::DESCR_REPORT.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::Chart),
+                i18n::help::DESCR_CHART.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::DebtGraph),
+                i18n::help::DESCR_DEBT_GRAPH.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::Backup),
+                i18n::help::DESCR_BACKUP.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::Restore),
+                i18n::help::DESCR_RESTORE.translate(ctx.clone()),
+            ),
+            BotCommand::new(
+                variant_to_string!(Command::Import),
+                i18n::help::DESCR_IMPORT.translate(ctx.clone()),
+            ),
             BotCommand::new(
                 variant_to_string!(Command::ClearTravelers),
                 i18n::help::DESCR_CLEAR_TRAVELERS.translate(ctx.clone()),
@@ -229,18 +410,41 @@ impl HelpMessage for Command {
                 },
             ),
             SetCurrency { currency: _ } => HELP_SET_CURRENCY.translate(ctx),
+            SetTimezone { timezone: _ } => HELP_SET_TIMEZONE.translate(ctx),
+            SetSettlement { mode: _ } => HELP_SET_SETTLEMENT.translate(ctx),
+            SetSimplification { mode: _ } => HELP_SET_SIMPLIFICATION.translate(ctx),
+            NewTrip { name: _ } => HELP_NEW_TRIP.translate(ctx),
+            SwitchTrip { name: _ } => HELP_SWITCH_TRIP.translate(ctx),
+            ListTrips => HELP_LIST_TRIPS.translate(ctx),
+            RenameTrip { name: _ } => HELP_RENAME_TRIP.translate(ctx),
+            CloseTrip => HELP_CLOSE_TRIP.translate(ctx),
+            ReopenTrip => HELP_REOPEN_TRIP.translate(ctx),
+            TopicLedger { mode: _ } => HELP_TOPIC_LEDGER.translate(ctx),
             AddTraveler { name: _ } => HELP_ADD_TRAVELER.translate(ctx),
             DeleteTraveler { name: _ } => HELP_DELETE_TRAVELER.translate(ctx),
             ListTravelers => HELP_LIST_TRAVELERS.translate(ctx),
-            AddExpense => HELP_ADD_EXPENSE.translate(ctx),
+            AddExpense { args: _ } => HELP_ADD_EXPENSE.translate(ctx),
             DeleteExpense { number: _ } => HELP_DELETE_EXPENSE.translate(ctx),
-            ListExpenses { description: _ } => HELP_LIST_EXPENSES.translate(ctx),
+            ListExpenses { filter: _ } => HELP_LIST_EXPENSES.translate(ctx),
             ShowExpense { number: _ } => HELP_SHOW_EXPENSE.translate(ctx),
             Transfer { args: _ } => HELP_TRANSFER.translate(ctx),
             DeleteTransfer { number: _ } => HELP_DELETE_TRANSFER.translate(ctx),
-            ListTransfers { name: _ } => HELP_LIST_TRANSFERS.translate(ctx),
+            ListTransfers { filter: _ } => HELP_LIST_TRANSFERS.translate(ctx),
             ShowBalances { name: _ } => HELP_SHOW_BALANCES.translate(ctx),
-            ShowStats => HELP_SHOW_STATS.translate(ctx),
+            Statement { args: _ } => HELP_STATEMENT.translate(ctx),
+            Summary => HELP_SUMMARY.translate(ctx),
+            Settle { args: _ } => HELP_SETTLE.translate(ctx),
+            PayPrefs { args: _ } => HELP_PAY_PREFS.translate(ctx),
+            ShowStats { section: _ } => HELP_SHOW_STATS.translate(ctx),
+            RebuildBalances => HELP_REBUILD_BALANCES.translate(ctx),
+            Audit { args: _ } => HELP_AUDIT.translate(ctx),
+            Export { format: _ } => HELP_EXPORT.translate(ctx),
+            Report => HELP_REPORT.translate(ctx),
+            Chart { kind: _ } => HELP_CHART.translate(ctx),
+            DebtGraph { args: _ } => HELP_DEBT_GRAPH.translate(ctx),
+            Backup => HELP_BACKUP.translate(ctx),
+            Restore => HELP_RESTORE.translate(ctx),
+            Import => HELP_IMPORT.translate(ctx),
             ClearTravelers => HELP_CLEAR_TRAVELERS.translate(ctx),
             ClearExpenses => HELP_CLEAR_EXPENSES.translate(ctx),
             ClearTransfers => HELP_CLEAR_TRANSFERS.translate(ctx),
@@ -257,18 +461,12 @@ pub async fn commands_handler(
     cmd: Command,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
-    let reply = command_reply(db.clone(), &msg, &cmd, ctx.clone())
-        .await
-        .into_message();
+    let outcome = command_reply(db.clone(), &msg, &cmd, ctx.clone()).await;
 
     // Build an optional inline keyboard based on the command variant.
     let keyboard = command_inline_keyboard(db, &msg, &cmd, ctx.clone()).await;
 
-    let mut send = bot.send_message(msg.chat.id, reply);
-    if let Some(markup) = keyboard {
-        send = send.reply_markup(markup);
-    }
-    send.await?;
+    send_outcome(&bot, &msg, &outcome, keyboard).await?;
 
     // After a successful /setlanguage, re-register the bot commands for this
     // chat so Telegram shows the descriptions in the newly selected language.
@@ -283,7 +481,7 @@ pub async fn commands_handler(
         {
             tracing::error!(
                 "Failed updating bot commands for chat {}: {err}",
-                msg.chat.id
+                msg.ledger_id()
             );
         }
     }
@@ -299,8 +497,23 @@ pub const HELP_CALLBACK_PREFIX: &str = "help:";
 pub const LIST_TRANSFERS_CALLBACK_PREFIX: &str = "lstxfr:";
 /// Prefix for `/showbalances` traveler-name buttons.
 pub const SHOW_BALANCES_CALLBACK_PREFIX: &str = "showbal:";
+/// Prefix for `/statement` traveler-name buttons.
+pub const STATEMENT_CALLBACK_PREFIX: &str = "stmt:";
+/// Prefix for `/switchtrip` trip-number buttons.
+pub const SWITCH_TRIP_CALLBACK_PREFIX: &str = "swtrip:";
+/// Prefix for the `/settle` "Paid ✓" buttons, followed by the numbers of the
+/// debtor and of the creditor.
+pub const SETTLE_CALLBACK_PREFIX: &str = "settle:";
+/// Callback data for the `/settle` "Settle all" button.
+pub const SETTLE_ALL_CALLBACK: &str = "settleall:";
+/// Callback data for the `/audit` "Repair" button.
+pub const AUDIT_FIX_CALLBACK: &str = "auditfix:";
+/// Prefix for the `/showstats` section buttons.
+pub const SHOW_STATS_CALLBACK_PREFIX: &str = "stats:";
 /// Callback data for the "Filter…" button in `/listexpenses`.
 pub const LIST_EXPENSES_FILTER_CALLBACK: &str = "lstexp:__filter__";
+/// Prefix for the `◀` / `▶` buttons of lists sent a page at a time.
+pub const LIST_PAGE_CALLBACK_PREFIX: &str = "lstpage:";
 
 /// All stateless callback mappings registered for command keyboards.
 pub static STATELESS_CALLBACKS: LazyLock<Vec<inline_keyboards::CallbackMapping>> =
@@ -313,7 +526,7 @@ pub static STATELESS_CALLBACKS: LazyLock<Vec<inline_keyboards::CallbackMapping>>
                         command: value.to_owned(),
                     })
                 },
-                resolve_traveler_number: false,
+                value: inline_keyboards::CallbackValue::Raw,
             },
             inline_keyboards::CallbackMapping {
                 prefix: LIST_TRANSFERS_CALLBACK_PREFIX,
@@ -321,10 +534,10 @@ pub static STATELESS_CALLBACKS: LazyLock<Vec<inline_keyboards::CallbackMapping>>
                     Name::from_str(value)
                         .ok()
                         .map(|name| Command::ListTransfers {
-                            name: CommandArg::Provided(name),
+                            filter: CommandArg::Provided(format!("\"{name}\"")),
                         })
                 },
-                resolve_traveler_number: true,
+                value: inline_keyboards::CallbackValue::TravelerNumber,
             },
             inline_keyboards::CallbackMapping {
                 prefix: SHOW_BALANCES_CALLBACK_PREFIX,
@@ -335,7 +548,63 @@ pub static STATELESS_CALLBACKS: LazyLock<Vec<inline_keyboards::CallbackMapping>>
                             name: CommandArg::Provided(name),
                         })
                 },
-                resolve_traveler_number: true,
+                value: inline_keyboards::CallbackValue::TravelerNumber,
+            },
+            inline_keyboards::CallbackMapping {
+                prefix: STATEMENT_CALLBACK_PREFIX,
+                to_command: |value| {
+                    Some(Command::Statement {
+                        args: value.to_owned(),
+                    })
+                },
+                value: inline_keyboards::CallbackValue::TravelerNumber,
+            },
+            inline_keyboards::CallbackMapping {
+                prefix: SWITCH_TRIP_CALLBACK_PREFIX,
+                to_command: |value| {
+                    Name::from_str(value).ok().map(|name| Command::SwitchTrip {
+                        name: CommandArg::Provided(name),
+                    })
+                },
+                value: inline_keyboards::CallbackValue::TripNumber,
+            },
+            inline_keyboards::CallbackMapping {
+                prefix: SETTLE_CALLBACK_PREFIX,
+                to_command: |value| {
+                    let (debtor, creditor) =
+                        value.split_once(inline_keyboards::TRAVELER_NAMES_SEP)?;
+                    Some(Command::Transfer {
+                        args: format!("{debtor} {creditor} {ALL_KWORD}"),
+                    })
+                },
+                value: inline_keyboards::CallbackValue::TravelerNumbers,
+            },
+            inline_keyboards::CallbackMapping {
+                prefix: SETTLE_ALL_CALLBACK,
+                to_command: |_| {
+                    Some(Command::Settle {
+                        args: ALL_KWORD.to_owned(),
+                    })
+                },
+                value: inline_keyboards::CallbackValue::Raw,
+            },
+            inline_keyboards::CallbackMapping {
+                prefix: AUDIT_FIX_CALLBACK,
+                to_command: |_| {
+                    Some(Command::Audit {
+                        args: FIX_KWORD.to_owned(),
+                    })
+                },
+                value: inline_keyboards::CallbackValue::Raw,
+            },
+            inline_keyboards::CallbackMapping {
+                prefix: SHOW_STATS_CALLBACK_PREFIX,
+                to_command: |value| {
+                    Some(Command::ShowStats {
+                        section: value.to_owned(),
+                    })
+                },
+                value: inline_keyboards::CallbackValue::Raw,
             },
         ]
     });
@@ -379,10 +648,10 @@ async fn command_inline_keyboard(
                 .collect();
             Some(inline_keyboards::buttons_keyboard(buttons, 2))
         }
-        Command::ListTransfers { name } if name.is_missing() => {
+        Command::ListTransfers { filter } if filter.is_missing() => {
             keyboard::travelers_keyboard(keyboard::TravelersKeyboardConfig {
                 db,
-                chat_id: msg.chat.id,
+                chat_id: msg.ledger_id(),
                 prefix: LIST_TRANSFERS_CALLBACK_PREFIX,
                 cancel_callback: "",
                 noop_callback: &format!("{LIST_TRANSFERS_CALLBACK_PREFIX}__noop__"),
@@ -394,7 +663,7 @@ async fn command_inline_keyboard(
         Command::ShowBalances { name } if name.is_missing() => {
             keyboard::travelers_keyboard(keyboard::TravelersKeyboardConfig {
                 db,
-                chat_id: msg.chat.id,
+                chat_id: msg.ledger_id(),
                 prefix: SHOW_BALANCES_CALLBACK_PREFIX,
                 cancel_callback: "",
                 noop_callback: &format!("{SHOW_BALANCES_CALLBACK_PREFIX}__noop__"),
@@ -403,7 +672,34 @@ async fn command_inline_keyboard(
             })
             .await
         }
-        Command::ListExpenses { description } if description.is_empty() => {
+        Command::Statement { args } if args.trim().is_empty() => {
+            keyboard::travelers_keyboard(keyboard::TravelersKeyboardConfig {
+                db,
+                chat_id: msg.ledger_id(),
+                prefix: STATEMENT_CALLBACK_PREFIX,
+                cancel_callback: "",
+                noop_callback: &format!("{STATEMENT_CALLBACK_PREFIX}__noop__"),
+                show_cancel: false,
+                ctx,
+            })
+            .await
+        }
+        Command::SwitchTrip { name } if name.is_missing() => {
+            keyboard::trips_keyboard(keyboard::TripsKeyboardConfig {
+                db,
+                chat_id: msg.ledger_id(),
+                prefix: SWITCH_TRIP_CALLBACK_PREFIX,
+                noop_callback: &format!("{SWITCH_TRIP_CALLBACK_PREFIX}__noop__"),
+                ctx,
+            })
+            .await
+        }
+        Command::Settle { args } if args.trim().is_empty() => settle_keyboard(db, msg, ctx).await,
+        Command::Audit { args } if args.trim().is_empty() => audit_keyboard(db, msg, ctx).await,
+        Command::ShowStats { section } if section.trim().is_empty() => {
+            Some(show_stats_keyboard(ctx))
+        }
+        Command::ListExpenses { filter } if filter.trim().is_empty() => {
             let filter_button = InlineKeyboardButton::callback(
                 i18n::labels::FILTER_BUTTON.translate(ctx),
                 LIST_EXPENSES_FILTER_CALLBACK,
@@ -422,6 +718,13 @@ pub async fn command_reply(
 ) -> CommandOutcome {
     use Command::*;
 
+    // Also covers commands issued through inline keyboards
+    if cmd.is_mutating()
+        && let Some(refusal) = closed_trip_refusal(db.clone(), msg, ctx.clone()).await
+    {
+        return CommandOutcome::Failure(refusal);
+    }
+
     let result: Result<CommandOutcome, _> = match cmd.clone() {
         Help { command } => help(msg, &command, ctx.clone()).map(CommandOutcome::Success),
         SetLanguage { langid } => {
@@ -436,6 +739,28 @@ pub async fn command_reply(
             )
             .await
         }
+        SetTimezone { timezone } => {
+            set_timezone(
+                db,
+                msg,
+                &timezone.expect_provided("settimezone"),
+                ctx.clone(),
+            )
+            .await
+        }
+        SetSettlement { mode } => set_settlement(db, msg, &mode, ctx.clone()).await,
+        SetSimplification { mode } => set_simplification(db, msg, &mode, ctx.clone()).await,
+        NewTrip { name } => new_trip(db, msg, name.expect_provided("newtrip"), ctx.clone()).await,
+        SwitchTrip { name } => switch_trip(db, msg, name.provided(), ctx.clone()).await,
+        ListTrips => list_trips(db, msg, ctx.clone())
+            .await
+            .map(CommandOutcome::Success),
+        RenameTrip { name } => {
+            rename_trip(db, msg, name.expect_provided("renametrip"), ctx.clone()).await
+        }
+        CloseTrip => close_trip(db, msg, ctx.clone()).await,
+        ReopenTrip => reopen_trip(db, msg, ctx.clone()).await,
+        TopicLedger { mode } => topic_ledger(db, msg, &mode, ctx.clone()).await,
         AddTraveler { name } => {
             add_traveler(db, msg, name.expect_provided("addtraveler"), ctx.clone()).await
         }
@@ -454,9 +779,7 @@ pub async fn command_reply(
             )
             .await
         }
-        ListExpenses { description } => list_expenses(db, msg, &description, ctx.clone())
-            .await
-            .map(CommandOutcome::Success),
+        ListExpenses { filter } => list_expenses(db, msg, &filter, ctx.clone()).await,
         ShowExpense { number } => {
             show_expense(db, msg, number.expect_provided("showexpense"), ctx.clone()).await
         }
@@ -477,15 +800,21 @@ pub async fn command_reply(
                     return invalid_transfer_usage(cmd, ctx);
                 }
             };
-            let amount = match Decimal::from_str(parts[2]) {
-                Ok(d) => d,
-                Err(_) => {
-                    return invalid_transfer_usage(cmd, ctx);
-                }
-            };
-            transfer(db, msg, from, to, amount, ctx.clone())
-                .await
-                .map(CommandOutcome::Success)
+            if parts[2].trim().eq_ignore_ascii_case(ALL_KWORD) {
+                transfer_all(db, msg, from, to, ctx.clone())
+                    .await
+                    .map(CommandOutcome::Success)
+            } else {
+                let amount = match Decimal::from_str(parts[2]) {
+                    Ok(d) => d,
+                    Err(_) => {
+                        return invalid_transfer_usage(cmd, ctx);
+                    }
+                };
+                transfer(db, msg, from, to, amount, ctx.clone())
+                    .await
+                    .map(CommandOutcome::Success)
+            }
         }
         DeleteTransfer { number } => {
             delete_transfer(
@@ -496,11 +825,9 @@ pub async fn command_reply(
             )
             .await
         }
-        ListTransfers { name } => {
-            let name = name.provided();
-            list_transfers(db, msg, name, ctx.clone())
-                .await
-                .map(CommandOutcome::Success)
+        ListTransfers { filter } => {
+            let filter = filter.provided().unwrap_or_default();
+            list_transfers(db, msg, &filter, ctx.clone()).await
         }
         ShowBalances { name } => {
             let name = name.provided();
@@ -508,14 +835,27 @@ pub async fn command_reply(
                 .await
                 .map(CommandOutcome::Success)
         }
-        ShowStats => show_stats(db, msg, ctx.clone())
+        Statement { args } => statement(db, msg, &args, ctx.clone()).await,
+        Summary => summary(db, msg, ctx.clone())
+            .await
+            .map(CommandOutcome::Success),
+        Settle { args } => settle(db, msg, &args, ctx.clone()).await,
+        PayPrefs { args } => pay_prefs(db, msg, &args, ctx.clone()).await,
+        ShowStats { section } => show_stats(db, msg, &section, ctx.clone()).await,
+        RebuildBalances => rebuild_balances(db, msg, ctx.clone())
             .await
             .map(CommandOutcome::Success),
+        Audit { args } => audit(db, msg, &args, ctx.clone()).await,
+        Export { format } => export(db, msg, &format, ctx.clone()).await,
+        Report => report(db, msg, ctx.clone()).await,
+        Chart { kind } => chart(db, msg, &kind, ctx.clone()).await,
+        DebtGraph { args } => debt_graph(db, msg, &args, ctx.clone()).await,
+        Backup => backup(db, msg, ctx.clone()).await,
         ClearTravelers => clear_travelers(db, msg, ctx.clone()).await,
         ClearExpenses => clear_expenses(db, msg, ctx.clone()).await,
         ClearTransfers => clear_transfers(db, msg, ctx.clone()).await,
         ClearAll => clear_all(db, msg, ctx.clone()).await,
-        Cancel | AddExpense => {
+        Cancel | AddExpense { .. } | Restore | Import => {
             unreachable!("This command is handled before calling this function.")
         }
     };
@@ -599,6 +939,32 @@ mod tests {
             assert!(is_stateless_callback("showbal:1"));
         }
 
+        #[test]
+        fn switch_trip_prefix_recognized() {
+            assert!(is_stateless_callback("swtrip:2"));
+        }
+
+        #[test]
+        fn settle_prefixes_recognized() {
+            assert!(is_stateless_callback("settle:2:1"));
+            assert!(is_stateless_callback("settleall:"));
+        }
+
+        #[test]
+        fn settle_mapping_produces_command() {
+            let mappings = &*STATELESS_CALLBACKS;
+            let mapping = mappings
+                .iter()
+                .find(|m| m.prefix == SETTLE_CALLBACK_PREFIX)
+                .unwrap();
+            let cmd = (mapping.to_command)("Bob → Alice");
+            assert!(matches!(
+                cmd.unwrap(),
+                Command::Transfer { args } if args == "Bob Alice all"
+            ));
+            assert!((mapping.to_command)("Bob").is_none());
+        }
+
         #[test]
         fn unrelated_data_not_recognized() {
             assert!(!is_stateless_callback("lstexp:__filter__"));
@@ -629,7 +995,7 @@ mod tests {
             assert!(cmd.is_some());
             assert!(matches!(
                 cmd.unwrap(),
-                Command::ListTransfers { name } if name == CommandArg::Provided(Name::from_str("Alice").unwrap())
+                Command::ListTransfers { filter } if filter == CommandArg::Provided(String::from("\"Alice\""))
             ));
         }
 
@@ -648,6 +1014,21 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn switch_trip_mapping_produces_command() {
+            let mappings = &*STATELESS_CALLBACKS;
+            let mapping = mappings
+                .iter()
+                .find(|m| m.prefix == SWITCH_TRIP_CALLBACK_PREFIX)
+                .unwrap();
+            let cmd = (mapping.to_command)("Japan");
+            assert!(cmd.is_some());
+            assert!(matches!(
+                cmd.unwrap(),
+                Command::SwitchTrip { name } if name == CommandArg::Provided(Name::from_str("Japan").unwrap())
+            ));
+        }
+
         #[test]
         fn invalid_name_returns_none() {
             let mappings = &*STATELESS_CALLBACKS;
@@ -3,6 +3,15 @@
 /// `Success` indicates the command completed successfully; the calling
 /// dialogue should send the message and exit.
 ///
+/// `List` is a successful outcome made of list entries (e.g. expenses or
+/// transfers); it is sent a page at a time by [`super::send_outcome`].
+///
+/// `Documents` is a successful outcome made of a message followed by files
+/// (e.g. the exported travel plan), sent as Telegram documents.
+///
+/// `Photo` is a successful outcome made of an image (e.g. a chart), sent as
+/// a Telegram photo with a caption.
+///
 /// `Failure` indicates a user-visible failure such as a validation or
 /// lookup mismatch (e.g. "traveler not found", "language not available"),
 /// or a non-fatal error raised during dispatch; the calling dialogue
@@ -11,23 +20,30 @@
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CommandOutcome {
     Success(String),
+    List(Vec<String>),
+    Documents {
+        message: String,
+        documents: Vec<Document>,
+    },
+    Photo {
+        caption: String,
+        photo: Document,
+    },
     Failure(String),
 }
 
-impl CommandOutcome {
-    pub fn message(&self) -> &str {
-        match self {
-            Self::Success(m) | Self::Failure(m) => m,
-        }
-    }
-
-    pub fn into_message(self) -> String {
-        match self {
-            Self::Success(m) | Self::Failure(m) => m,
-        }
-    }
+/// A file sent as a Telegram document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Document {
+    pub file_name: String,
+    pub content: Vec<u8>,
+}
 
+impl CommandOutcome {
     pub fn is_success(&self) -> bool {
-        matches!(self, Self::Success(_))
+        matches!(
+            self,
+            Self::Success(_) | Self::List(_) | Self::Documents { .. } | Self::Photo { .. }
+        )
     }
 }
@@ -0,0 +1,180 @@
+use crate::{
+    Context,
+    chart::graph,
+    commands::{CommandOutcome, Document},
+    consts::{DOT_KWORD, LOG_DEBUG_START, LOG_DEBUG_SUCCESS, RAW_KWORD},
+    debt_graph::{DebtGraph, dot},
+    errors::CommandError,
+    i18n::{self, Translate},
+    money_wrapper::MoneyWrapper,
+    topics::MessageExt,
+};
+use macro_rules_attribute::apply;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn debt_graph(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    args: &str,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let (mut raw, mut as_dot) = (false, false);
+    for word in args.split_whitespace() {
+        if word.eq_ignore_ascii_case(RAW_KWORD) {
+            raw = true;
+        } else if word.eq_ignore_ascii_case(DOT_KWORD) {
+            as_dot = true;
+        } else {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            return Ok(CommandOutcome::Failure(
+                i18n::commands::DEBT_GRAPH_USAGE.translate(ctx),
+            ));
+        }
+    }
+
+    let graph = match DebtGraph::db_select(db, msg.ledger_id(), raw).await {
+        Ok(graph) => graph,
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::DebtGraph);
+        }
+    };
+    if graph.edges.is_empty() {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Success(
+            i18n::commands::DEBT_GRAPH_SETTLED.translate(ctx),
+        ));
+    }
+
+    let title = graph.title().translate(ctx.clone());
+    if as_dot {
+        let currency = ctx.lock().expect("Failed to lock context").currency.clone();
+        let document = dot::export(&graph, &title, |amount| {
+            MoneyWrapper::new(amount, &currency).to_string()
+        });
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        tracing::info!("Debt graph sent in DOT format");
+        return Ok(CommandOutcome::Documents {
+            message: i18n::commands::DEBT_GRAPH_DOT_OK.translate(ctx),
+            documents: vec![document],
+        });
+    }
+
+    match graph::render(&graph, ctx) {
+        Ok(content) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            tracing::info!("Debt graph sent");
+            Ok(CommandOutcome::Photo {
+                caption: title,
+                photo: Document {
+                    file_name: graph::FILE_NAME.to_owned(),
+                    content,
+                },
+            })
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            Err(CommandError::DebtGraph)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        chart::graph,
+        db::db,
+        debt_graph::dot,
+        i18n::{self, Translate},
+        tests::{TestBot, helpers},
+    };
+
+    /// Alice pays for Bob, who pays for Carol: simplified, Carol owes Alice.
+    async fn chain(bot: &mut TestBot) {
+        helpers::add_traveler(bot, "Alice").await;
+        helpers::add_traveler(bot, "Bob").await;
+        helpers::add_traveler(bot, "Carol").await;
+        helpers::add_traveler(bot, "Dave").await;
+        helpers::add_expense(bot, "Dinner", 30.into(), "Alice", &["Bob:30", "end"]).await;
+        helpers::add_expense(bot, "Taxi", 30.into(), "Bob", &["Carol:30", "end"]).await;
+    }
+
+    /// Returns the DOT file sent in reply to `command`.
+    async fn dot_file(bot: &mut TestBot, command: &str) -> String {
+        bot.update(command);
+        bot.dispatch().await;
+        let documents = bot.sent_documents();
+        let [(file_name, dot)] = documents.as_slice() else {
+            panic!("expected a single document, got {documents:?}");
+        };
+        assert_eq!(file_name, dot::FILE_NAME);
+        dot.clone()
+    }
+
+    test! { debt_graph_usage,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "/debtgraph svg");
+        let response = i18n::commands::DEBT_GRAPH_USAGE.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { debt_graph_settled,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 30.into(), "Alice", &["Alice:30", "end"]).await;
+
+        bot.update("/debtgraph");
+        let response = i18n::commands::DEBT_GRAPH_SETTLED.translate_default();
+        bot.test_last_message(&response).await;
+        assert!(bot.sent_photos().is_empty());
+    }
+
+    test! { debt_graph_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+        chain(&mut bot).await;
+
+        for (command, title) in [
+            ("/debtgraph", i18n::labels::DEBT_GRAPH),
+            ("/debtgraph RAW", i18n::labels::DEBT_GRAPH_RAW),
+        ] {
+            bot.update(command);
+            bot.dispatch().await;
+            let photos = bot.sent_photos();
+            let [(file_name, caption)] = photos.as_slice() else {
+                panic!("expected a single photo, got {photos:?}");
+            };
+            assert_eq!(file_name, graph::FILE_NAME);
+            assert_eq!(caption.as_deref(), Some(title.translate_default().as_str()));
+        }
+    }
+
+    test! { debt_graph_dot,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+        chain(&mut bot).await;
+
+        let dot = dot_file(&mut bot, "/debtgraph dot").await;
+        let response = i18n::commands::DEBT_GRAPH_DOT_OK.translate_default();
+        assert!(bot.sent_texts().contains(&response));
+        assert!(dot.starts_with("digraph debts {\n    label=\"Who owes whom\";\n"));
+        // Settled travelers are still nodes
+        assert!(dot.contains("\n    \"Dave\";\n"));
+        assert!(dot.contains("\n    \"Carol\" -> \"Alice\" [label=\"$30.00\"];\n"));
+        assert_eq!(dot.matches("->").count(), 1);
+
+        let dot = dot_file(&mut bot, "/debtgraph raw dot").await;
+        assert!(dot.contains("label=\"Who owes whom, before simplification\";"));
+        assert!(dot.contains("\n    \"Bob\" -> \"Alice\" [label=\"$30.00\"];\n"));
+        assert!(dot.contains("\n    \"Carol\" -> \"Bob\" [label=\"$30.00\"];\n"));
+        assert_eq!(dot.matches("->").count(), 2);
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
     errors::CommandError,
     expense::Expense,
     i18n::{self, TranslateWithArgs},
+    topics::MessageExt,
 };
 use macro_rules_attribute::apply;
 use maplit::hashmap;
@@ -24,14 +25,15 @@ pub async fn delete_expense(
     tracing::debug!("{LOG_DEBUG_START}");
 
     // Check if expense exists on db
-    let count_res = Expense::db_count_by_number(db.clone(), msg.chat.id, number).await;
+    let count_res = Expense::db_count_by_number(db.clone(), msg.ledger_id(), number).await;
     match count_res {
         Ok(Some(count)) if *count > 0 => {
             // Delete expense from db
-            let delete_res = Expense::db_delete_by_number(db.clone(), msg.chat.id, number).await;
+            let delete_res =
+                Expense::db_delete_by_number(db.clone(), msg.ledger_id(), number).await;
             match delete_res {
                 Ok(_) => {
-                    if let Err(err_update) = update_debts(db, msg.chat.id).await {
+                    if let Err(err_update) = update_debts(db, msg.ledger_id()).await {
                         tracing::warn!("{err_update}");
                     }
                     tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -95,10 +97,13 @@ mod tests {
         // 2. Set amount
         bot.update("100");
         bot.dispatch().await;
-        // 3. Set payer
+        // 3. Set date
+        bot.update("today");
+        bot.dispatch().await;
+        // 4. Set payer
         bot.update("Alice");
         bot.dispatch().await;
-        // 4. Split expense
+        // 5. Split expense
         bot.update("all");
         bot.dispatch().await;
 
@@ -137,10 +142,13 @@ mod tests {
         // 2. Set amount
         bot.update("100");
         bot.dispatch().await;
-        // 3. Set payer
+        // 3. Set date
+        bot.update("today");
+        bot.dispatch().await;
+        // 4. Set payer
         bot.update("Alice");
         bot.dispatch().await;
-        // 4. Split expense
+        // 5. Split expense
         bot.update("all");
         bot.dispatch().await;
 
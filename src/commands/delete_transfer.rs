@@ -5,6 +5,7 @@ use crate::{
     debt::update_debts,
     errors::CommandError,
     i18n::{self, TranslateWithArgs},
+    topics::MessageExt,
     transferred_to::TransferredTo,
 };
 use macro_rules_attribute::apply;
@@ -24,14 +25,14 @@ pub async fn delete_transfer(
     tracing::debug!("{LOG_DEBUG_START}");
 
     // Check if transfer relation exists on db
-    let count_res = TransferredTo::db_count(db.clone(), msg.chat.id, number).await;
+    let count_res = TransferredTo::db_count(db.clone(), msg.ledger_id(), number).await;
     match count_res {
         Ok(Some(count)) if *count > 0 => {
             // Delete transfer relation from db
-            let delete_res = TransferredTo::db_delete(db.clone(), msg.chat.id, number).await;
+            let delete_res = TransferredTo::db_delete(db.clone(), msg.ledger_id(), number).await;
             match delete_res {
                 Ok(_) => {
-                    if let Err(err_update) = update_debts(db, msg.chat.id).await {
+                    if let Err(err_update) = update_debts(db, msg.ledger_id()).await {
                         tracing::warn!("{err_update}");
                     }
                     tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -2,10 +2,11 @@ use crate::{
     Context,
     commands::CommandOutcome,
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
-    debt::update_debts,
+    debt::rebuild_debts,
     errors::CommandError,
     expense::Expense,
     i18n::{self, Translate, TranslateWithArgs},
+    topics::MessageExt,
     traveler::{Name, Traveler},
 };
 use macro_rules_attribute::apply;
@@ -25,7 +26,7 @@ pub async fn delete_traveler(
     tracing::debug!("{LOG_DEBUG_START}");
 
     // Retrieve traveler from db
-    let select_res = Traveler::db_select_by_name(db.clone(), msg.chat.id, &name).await;
+    let select_res = Traveler::db_select_by_name(db.clone(), msg.ledger_id(), &name).await;
     match select_res {
         Ok(Some(traveler)) => {
             // Use the traveler name as stored in the database (canonical
@@ -36,10 +37,10 @@ pub async fn delete_traveler(
             match list_res {
                 Ok(expenses) if expenses.is_empty() => {
                     // Delete traveler from db
-                    let delete_res = Traveler::db_delete(db.clone(), msg.chat.id, &name).await;
+                    let delete_res = Traveler::db_delete(db.clone(), msg.ledger_id(), &name).await;
                     match delete_res {
                         Ok(_) => {
-                            if let Err(err_update) = update_debts(db, msg.chat.id).await {
+                            if let Err(err_update) = rebuild_debts(db, msg.ledger_id()).await {
                                 tracing::warn!("{err_update}");
                             }
                             tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -186,10 +187,13 @@ mod tests {
         // 2. Set amount
         bot.update("100");
         bot.dispatch().await;
-        // 3. Set payer
+        // 3. Set date
+        bot.update("today");
+        bot.dispatch().await;
+        // 4. Set payer
         bot.update("Alice");
         bot.dispatch().await;
-        // 4. Split expense
+        // 5. Split expense
         bot.update("all");
         bot.dispatch().await;
 
@@ -0,0 +1,237 @@
+use crate::{
+    Context,
+    commands::CommandOutcome,
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    export::{self, ExportData, ExportFormat},
+    i18n::{self, TranslateWithArgs},
+    topics::MessageExt,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use strum::IntoEnumIterator;
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn export(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    format: &str,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let Ok(format) = ExportFormat::from_str(format.trim()) else {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        let formats = ExportFormat::iter()
+            .map(|format| format.as_ref().to_owned())
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::EXPORT_USAGE.translate_with_args(
+                ctx,
+                &hashmap! {i18n::args::FORMATS.into() => formats.into()},
+            ),
+        ));
+    };
+
+    let (currency, timezone) = {
+        let ctx_guard = ctx.lock().expect("Failed to lock context");
+        (ctx_guard.currency.clone(), ctx_guard.timezone.clone())
+    };
+    let data = match ExportData::db_select(db, msg.ledger_id(), currency, timezone).await {
+        Ok(data) => data,
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::Export);
+        }
+    };
+
+    let documents = match format {
+        ExportFormat::Csv => export::csv::export(&data, ctx.clone()),
+        ExportFormat::Ledger => Ok(export::ledger::export(&data)),
+        ExportFormat::Beancount => Ok(export::beancount::export(&data)),
+    };
+    match documents {
+        Ok(documents) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            tracing::info!("Travel plan exported as {}", format.as_ref());
+            Ok(CommandOutcome::Documents {
+                message: i18n::commands::EXPORT_OK.translate_with_args(
+                    ctx,
+                    &hashmap! {
+                        i18n::args::FORMAT.into() => format.as_ref().into(),
+                        i18n::args::EXPENSES_COUNT.into() => data.expenses.len().into(),
+                        i18n::args::TRANSFERS_COUNT.into() => data.transfers.len().into(),
+                    },
+                ),
+                documents,
+            })
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            Err(CommandError::Export)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        export::{
+            beancount,
+            csv::{BALANCES_FILE_NAME, EXPENSES_FILE_NAME, SHARES_FILE_NAME, TRANSFERS_FILE_NAME},
+            ledger,
+        },
+        i18n::{self, TranslateWithArgs},
+        tests::{TestBot, helpers},
+    };
+    use indoc::indoc;
+    use maplit::hashmap;
+
+    test! { export_csv_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner, \"fancy\"", 100.into(), "Alice", &["all"]).await;
+        helpers::transfer(&mut bot, "Bob", "Alice", 20.into()).await;
+        db.query("UPDATE expense SET occurred_at = d'2026-10-19T20:00:00Z'")
+            .await
+            .unwrap();
+        bot.update("/settimezone Asia/Tokyo");
+        bot.dispatch().await;
+
+        bot.update("/export CSV");
+        let response = i18n::commands::EXPORT_OK.translate_with_args_default(&hashmap! {
+            i18n::args::FORMAT.into() => "csv".into(),
+            i18n::args::EXPENSES_COUNT.into() => 1.into(),
+            i18n::args::TRANSFERS_COUNT.into() => 1.into(),
+        });
+        bot.dispatch().await;
+        let documents = bot.sent_documents();
+        assert!(bot.sent_texts().contains(&response));
+
+        let file = |name: &str| {
+            documents
+                .iter()
+                .find(|(file_name, _)| file_name == name)
+                .map(|(_, content)| content.clone())
+                .unwrap()
+        };
+        assert_eq!(
+            file(EXPENSES_FILE_NAME),
+            "Number,Date,Description,Amount,Currency,Payer\n\
+            1,2026-10-20 05:00,\"Dinner, \"\"fancy\"\"\",100.00,USD,Alice\n"
+        );
+        assert_eq!(
+            file(SHARES_FILE_NAME),
+            "Expense,Traveler,Amount,Currency\n1,Alice,50.00,USD\n1,Bob,50.00,USD\n"
+        );
+        assert!(file(TRANSFERS_FILE_NAME).ends_with(",Bob,Alice,20.00,USD\n"));
+        assert_eq!(
+            file(BALANCES_FILE_NAME),
+            "Debtor,Creditor,Amount,Currency\nBob,Alice,30.00,USD\n"
+        );
+    }
+
+    test! { export_ledger_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_traveler(&mut bot, "Carol").await;
+        helpers::add_expense(&mut bot, "Dinner; wine", 100.into(), "Alice", &["all"]).await;
+        helpers::transfer(&mut bot, "Bob", "Alice", 20.into()).await;
+        db.query("UPDATE expense SET occurred_at = d'2026-01-19T20:00:00Z'")
+            .await
+            .unwrap();
+
+        bot.update("/export ledger");
+        bot.dispatch().await;
+        let documents = bot.sent_documents();
+        let [(file_name, journal)] = documents.as_slice() else {
+            panic!("expected a single document, got {documents:?}");
+        };
+        assert_eq!(file_name, ledger::FILE_NAME);
+        // Transfers are dated when they are recorded
+        let (expense, transfer) = journal.split_once("\n\n").unwrap();
+        assert_eq!(
+            expense,
+            indoc! {"
+                2026-01-19 * (E1) Dinner, wine
+                    Expenses:Travel:Alice  33.33 USD
+                    Expenses:Travel:Bob    33.33 USD
+                    Expenses:Travel:Carol  33.33 USD
+                    Assets:Travel:Alice    -99.99 USD"}
+        );
+        assert!(transfer.ends_with(indoc! {"
+            * (T1) Bob → Alice
+                Assets:Travel:Alice  20.00 USD
+                Assets:Travel:Bob    -20.00 USD
+
+        "}));
+    }
+
+    test! { export_beancount_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "bob-smith").await;
+        helpers::add_expense(&mut bot, "Dinner \"fancy\"", 100.into(), "Alice", &["all"]).await;
+        helpers::transfer(&mut bot, "bob-smith", "Alice", 20.into()).await;
+        db.query("UPDATE expense SET occurred_at = d'2026-01-19T20:00:00Z'")
+            .await
+            .unwrap();
+
+        bot.update("/export beancount");
+        bot.dispatch().await;
+        let documents = bot.sent_documents();
+        let [(file_name, ledger)] = documents.as_slice() else {
+            panic!("expected a single document, got {documents:?}");
+        };
+        assert_eq!(file_name, beancount::FILE_NAME);
+        assert!(ledger.starts_with(indoc! {r#"
+            option "operating_currency" "USD"
+
+            2026-01-19 open Assets:Travel:Alice USD
+            2026-01-19 open Assets:Travel:Bob-Smith USD
+            2026-01-19 open Expenses:Travel:Alice USD
+            2026-01-19 open Expenses:Travel:Bob-Smith USD
+
+            2026-01-19 * "Dinner \"fancy\""
+              code: "E1"
+              Expenses:Travel:Alice      50.00 USD
+              Expenses:Travel:Bob-Smith  50.00 USD
+              Assets:Travel:Alice        -100.00 USD
+
+        "#}));
+        assert!(ledger.ends_with(indoc! {r#"
+            * "bob-smith → Alice"
+              code: "T1"
+              Assets:Travel:Alice      20.00 USD
+              Assets:Travel:Bob-Smith  -20.00 USD
+
+        "#}));
+    }
+
+    test! { export_usage,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/export pdf");
+        let response = i18n::commands::EXPORT_USAGE.translate_with_args_default(
+            &hashmap! {i18n::args::FORMATS.into() => "csv, ledger, beancount".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+}
@@ -0,0 +1,308 @@
+use crate::{
+    Context,
+    commands::{CommandOutcome, closed_trip_refusal},
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    debt::update_debts,
+    errors::CommandError,
+    i18n::{self, Translate, TranslateWithArgs},
+    import::{Import, MemberMapping},
+    topics::MessageExt,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+/// Adds the expenses and transfers of `import` to the travel plan of the
+/// chat, with its members matched to travelers as in `mappings`, once the
+/// `/import` dialogue is confirmed.
+#[apply(trace_skip_all)]
+pub async fn import(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    import: &Import,
+    mappings: &[MemberMapping],
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    if let Some(refusal) = closed_trip_refusal(db.clone(), msg, ctx.clone()).await {
+        return Ok(CommandOutcome::Failure(refusal));
+    }
+
+    if let Err(err) = import.check_mappings(mappings) {
+        tracing::warn!("{err}");
+        return Ok(CommandOutcome::Failure(err.translate(ctx)));
+    }
+    let mut import = import.clone();
+    import.skip_self_transfers(mappings);
+
+    if let Err(err) = import
+        .db_import(db.clone(), msg.ledger_id(), mappings)
+        .await
+    {
+        tracing::error!("{err}");
+        return Err(CommandError::Import);
+    }
+    if let Err(err) = update_debts(db, msg.ledger_id()).await {
+        tracing::warn!("{err}");
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!(
+        "Imported {} expenses and {} transfers from {}",
+        import.expenses.len(),
+        import.transfers.len(),
+        import.format.as_ref()
+    );
+    Ok(CommandOutcome::Success(
+        i18n::commands::IMPORT_OK.translate_with_args(
+            ctx,
+            &hashmap! {
+                i18n::args::EXPENSES_COUNT.into() => import.expenses.len().into(),
+                i18n::args::TRANSFERS_COUNT.into() => import.transfers.len().into(),
+                i18n::args::TRAVELERS_COUNT.into() => mappings.iter().filter(|mapping| mapping.create).count().into(),
+            },
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backup::{Backup, BackupShare},
+        balance::Balance,
+        consts::MAX_DOWNLOAD_SIZE,
+        db::db,
+        dialogues::pending_command_dialogue::{
+            PendingCommandState,
+            import::{ImportState, PendingImport},
+        },
+        i18n::{self, Translate, TranslateWithArgs},
+        import::{Import, MemberMapping},
+        tests::{TestBot, helpers},
+        traveler::Name,
+    };
+    use indoc::indoc;
+    use jiff::tz::TimeZone;
+    use maplit::hashmap;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+    use teloxide::dispatching::dialogue::Storage;
+
+    const EXPORT: &str = indoc! {"
+        Date,Description,Category,Cost,Currency,alice,Bobby
+        2024-05-01,Dinner,Dining out,90.00,EUR,45.00,-45.00
+        2024-05-03,Payment,Payment,20.00,EUR,-20.00,20.00
+    "};
+
+    fn pending_import(mappings: Vec<MemberMapping>) -> Box<PendingImport> {
+        Box::new(PendingImport {
+            import: Import::from_csv(EXPORT.as_bytes(), "EUR", &TimeZone::UTC).unwrap(),
+            mappings,
+        })
+    }
+
+    fn mapping(member: &str, traveler: &str, create: bool) -> MemberMapping {
+        MemberMapping {
+            member: member.to_owned(),
+            traveler: Name::from_str(traveler).unwrap(),
+            create,
+        }
+    }
+
+    test! { import_usage,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "/import");
+        let response = i18n::commands::IMPORT_USAGE.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { import_file_too_large,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+        bot.update_document("/import", MAX_DOWNLOAD_SIZE + 1);
+        let response = i18n::commands::FILE_TOO_LARGE.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { import_unknown_format,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+        bot.update_document("/import", 1024);
+        let response = i18n::errors::IMPORT_ERROR_UNKNOWN_FORMAT.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { import_new_traveler,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        bot.pending_command_storage()
+            .update_dialogue(
+                bot.chat_id(),
+                PendingCommandState::Import(ImportState::AskTraveler(pending_import(vec![
+                    mapping("alice", "Alice", false),
+                ]))),
+            )
+            .await
+            .unwrap();
+
+        // Alice already stands for another member
+        bot.update("alice");
+        let response = i18n::dialogues::IMPORT_TRAVELER_TAKEN.translate_with_args_default(
+            &hashmap! { i18n::args::NAME.into() => "alice".into() },
+        );
+        bot.test_last_message(&response).await;
+
+        bot.update("Bob");
+        bot.dispatch().await;
+        let state = bot
+            .pending_command_storage()
+            .get_dialogue(bot.chat_id())
+            .await
+            .unwrap();
+        let Some(PendingCommandState::Import(ImportState::Confirm(pending))) = state else {
+            panic!("Expected the import confirmation, got {state:?}");
+        };
+        assert_eq!(
+            pending.mappings,
+            [mapping("alice", "Alice", false), mapping("Bobby", "Bob", true)]
+        );
+    }
+
+    test! { import_confirm_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        bot.pending_command_storage()
+            .update_dialogue(
+                bot.chat_id(),
+                PendingCommandState::Import(ImportState::Confirm(pending_import(vec![
+                    mapping("alice", "Alice", false),
+                    mapping("Bobby", "Bob", true),
+                ]))),
+            )
+            .await
+            .unwrap();
+        bot.update("yes");
+        let response = i18n::commands::IMPORT_OK.translate_with_args_default(&hashmap! {
+            i18n::args::EXPENSES_COUNT.into() => 1.into(),
+            i18n::args::TRANSFERS_COUNT.into() => 1.into(),
+            i18n::args::TRAVELERS_COUNT.into() => 1.into(),
+        });
+        bot.test_last_message(&response).await;
+
+        let backup = Backup::db_select(db.clone(), bot.chat_id()).await.unwrap();
+        assert_eq!(backup.travelers.len(), 2);
+        assert_eq!(backup.expenses.len(), 1);
+        assert_eq!(backup.expenses[0].payer.as_deref(), Some("Alice"));
+        assert_eq!(
+            backup.expenses[0].shares,
+            [
+                BackupShare {
+                    traveler: Name::from_str("Alice").unwrap(),
+                    amount: Decimal::from(45),
+                },
+                BackupShare {
+                    traveler: Name::from_str("Bob").unwrap(),
+                    amount: Decimal::from(45),
+                },
+            ]
+        );
+        assert_eq!(backup.transfers.len(), 1);
+        assert_eq!(backup.transfers[0].amount, Decimal::from(20));
+
+        // Bob paid back 20 of his 45 share
+        let balances = Balance::balances(db, bot.chat_id()).await.unwrap();
+        assert_eq!(balances.len(), 1);
+        assert_eq!(*balances[0].debtor_name, *"Bob");
+        assert_eq!(*balances[0].creditor_name, *"Alice");
+        assert_eq!(balances[0].debt, Decimal::from(25));
+    }
+
+    test! { import_confirm_deny,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+
+        bot.pending_command_storage()
+            .update_dialogue(
+                bot.chat_id(),
+                PendingCommandState::Import(ImportState::Confirm(pending_import(vec![
+                    mapping("alice", "Alice", true),
+                    mapping("Bobby", "Bob", true),
+                ]))),
+            )
+            .await
+            .unwrap();
+        bot.update("no");
+        let response = helpers::cancel_ok_for(i18n::commands::RUNNING_PROCESS_IMPORT);
+        bot.test_last_message(&response).await;
+    }
+
+    test! { import_unmapped_member,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        bot.pending_command_storage()
+            .update_dialogue(
+                bot.chat_id(),
+                PendingCommandState::Import(ImportState::Confirm(pending_import(vec![
+                    mapping("alice", "Alice", false),
+                ]))),
+            )
+            .await
+            .unwrap();
+        bot.update("yes");
+        let response = i18n::errors::IMPORT_ERROR_UNMAPPED_MEMBER.translate_with_args_default(
+            &hashmap! { i18n::args::MEMBER.into() => "Bobby".into() },
+        );
+        bot.test_last_message(&response).await;
+
+        let backup = Backup::db_select(db, bot.chat_id()).await.unwrap();
+        assert!(backup.expenses.is_empty());
+        assert!(backup.transfers.is_empty());
+    }
+
+    test! { import_self_transfer,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        // Both members stand for Alice: the payment between them is skipped
+        helpers::add_traveler(&mut bot, "Alice").await;
+        bot.pending_command_storage()
+            .update_dialogue(
+                bot.chat_id(),
+                PendingCommandState::Import(ImportState::Confirm(pending_import(vec![
+                    mapping("alice", "Alice", false),
+                    mapping("Bobby", "Alice", false),
+                ]))),
+            )
+            .await
+            .unwrap();
+        bot.update("yes");
+        let response = i18n::commands::IMPORT_OK.translate_with_args_default(&hashmap! {
+            i18n::args::EXPENSES_COUNT.into() => 1.into(),
+            i18n::args::TRANSFERS_COUNT.into() => 0.into(),
+            i18n::args::TRAVELERS_COUNT.into() => 0.into(),
+        });
+        bot.test_last_message(&response).await;
+
+        // Their shares of the dinner are merged too
+        let backup = Backup::db_select(db, bot.chat_id()).await.unwrap();
+        assert_eq!(backup.expenses.len(), 1);
+        assert_eq!(
+            backup.expenses[0].shares,
+            [BackupShare {
+                traveler: Name::from_str("Alice").unwrap(),
+                amount: Decimal::from(90),
+            }]
+        );
+        assert!(backup.transfers.is_empty());
+    }
+}
@@ -7,16 +7,18 @@
 
 use crate::{
     Context,
-    commands::{Command, command_reply},
+    commands::{Command, command_reply, send_outcome},
     keyboard,
+    topics::MessageExt,
     traveler::Traveler,
+    trip::Trip,
 };
 use std::sync::{Arc, Mutex};
 use surrealdb::{Surreal, engine::any::Any};
 use teloxide::{
     Bot,
     requests::Requester,
-    types::{CallbackQuery, InlineKeyboardButton, InlineKeyboardMarkup},
+    types::{CallbackQuery, ChatId, InlineKeyboardButton, InlineKeyboardMarkup},
 };
 
 // ─── Generic keyboard builder ────────────────────────────────────────────────
@@ -36,6 +38,28 @@ pub fn buttons_keyboard(
 
 // ─── Stateless callback dispatcher ──────────────────────────────────────────
 
+/// How the raw callback value (after the prefix) is interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallbackValue {
+    /// The value is passed to [`CallbackMapping::to_command`] as is.
+    Raw,
+    /// The value is a traveler number that must be resolved to the
+    /// traveler's name via DB.
+    TravelerNumber,
+    /// The value is a trip number that must be resolved to the trip's name
+    /// via DB.
+    TripNumber,
+    /// The value is a pair of traveler numbers separated by
+    /// [`TRAVELER_NUMBERS_SEP`], resolved to the two names joined by
+    /// [`TRAVELER_NAMES_SEP`].
+    TravelerNumbers,
+}
+
+/// Separator of the two numbers of a [`CallbackValue::TravelerNumbers`].
+pub const TRAVELER_NUMBERS_SEP: char = ':';
+/// Separator of the two names a [`CallbackValue::TravelerNumbers`] resolves to.
+pub const TRAVELER_NAMES_SEP: &str = " → ";
+
 /// A registered stateless callback: a prefix to match and a function that
 /// maps the stripped value (after the prefix) to a `Command`.
 pub struct CallbackMapping {
@@ -44,9 +68,34 @@ pub struct CallbackMapping {
     /// Builds a `Command` from the value stripped of the prefix.
     /// Returns `None` if the value is invalid (callback is silently ignored).
     pub to_command: fn(&str) -> Option<Command>,
-    /// When `true`, the raw callback value is a traveler number that
-    /// must be resolved to a name via DB before calling [`Self::to_command`].
-    pub resolve_traveler_number: bool,
+    /// How the raw callback value is resolved before calling
+    /// [`Self::to_command`].
+    pub value: CallbackValue,
+}
+
+/// Resolves a raw callback value according to `kind`. Returns `None` if a
+/// numeric ID doesn't match any record.
+async fn resolve_callback_value(
+    db: Arc<Surreal<Any>>,
+    chat_id: ChatId,
+    kind: CallbackValue,
+    value: &str,
+) -> Option<String> {
+    match kind {
+        CallbackValue::Raw => Some(value.to_owned()),
+        CallbackValue::TravelerNumber => Traveler::db_resolve_by_number(db, chat_id, value)
+            .await
+            .map(|t| t.name.to_string()),
+        CallbackValue::TripNumber => Trip::db_resolve_by_number(db, chat_id, value)
+            .await
+            .map(|t| t.name.to_string()),
+        CallbackValue::TravelerNumbers => {
+            let (first, second) = value.split_once(TRAVELER_NUMBERS_SEP)?;
+            let first = Traveler::db_resolve_by_number(db.clone(), chat_id, first).await?;
+            let second = Traveler::db_resolve_by_number(db, chat_id, second).await?;
+            Some(format!("{}{TRAVELER_NAMES_SEP}{}", first.name, second.name))
+        }
+    }
 }
 
 /// Returns `true` if `data` matches any of the registered stateless prefixes.
@@ -79,54 +128,25 @@ pub async fn handle_stateless_callback(
 
     // Edit the original message to show which option was selected and
     // remove the keyboard.
-    let selected_value = mappings.iter().find_map(|m| data.strip_prefix(m.prefix));
-    if let Some(val) = selected_value {
-        // For traveler-number callbacks, resolve the name for the echo;
-        // for other callbacks, echo the raw value.
-        let display_val =
-            if let Some(mapping) = mappings.iter().find(|m| data.starts_with(m.prefix)) {
-                if mapping.resolve_traveler_number {
-                    Traveler::db_resolve_by_number(db.clone(), msg.chat.id, val)
-                        .await
-                        .map(|t| t.name.to_string())
-                        .unwrap_or_else(|| val.to_owned())
-                } else {
-                    val.to_owned()
-                }
-            } else {
-                val.to_owned()
-            };
-        keyboard::echo_callback_selection(&bot, &msg, &display_val).await;
-    }
+    let Some((mapping, value)) = mappings
+        .iter()
+        .find_map(|m| data.strip_prefix(m.prefix).map(|value| (m, value)))
+    else {
+        return Ok(());
+    };
 
-    for mapping in mappings {
-        if let Some(value) = data.strip_prefix(mapping.prefix) {
-            // Resolve traveler number to name if needed.
-            let resolved_value;
-            let effective_value = if mapping.resolve_traveler_number {
-                resolved_value =
-                    Traveler::db_resolve_by_number(db.clone(), msg.chat.id, value)
-                        .await
-                        .map(|t| t.name.to_string());
-                match resolved_value.as_deref() {
-                    Some(name) => name,
-                    None => {
-                        tracing::warn!(
-                            "Could not resolve traveler number from callback data: {value:?}"
-                        );
-                        break;
-                    }
-                }
-            } else {
-                value
-            };
-            if let Some(cmd) = (mapping.to_command)(effective_value) {
-                let outcome = command_reply(db, &msg, &cmd, ctx).await;
-                bot.send_message(msg.chat.id, outcome.into_message())
-                    .await?;
-            }
-            break;
-        }
+    // For numeric-ID callbacks, resolve the name (also used for the echo);
+    // for other callbacks, use the raw value.
+    let resolved = resolve_callback_value(db.clone(), msg.ledger_id(), mapping.value, value).await;
+    keyboard::echo_callback_selection(&bot, &msg, resolved.as_deref().unwrap_or(value)).await;
+
+    let Some(effective_value) = resolved else {
+        tracing::warn!("Could not resolve numeric ID from callback data: {value:?}");
+        return Ok(());
+    };
+    if let Some(cmd) = (mapping.to_command)(&effective_value) {
+        let outcome = command_reply(db, &msg, &cmd, ctx).await;
+        send_outcome(&bot, &msg, &outcome, None).await?;
     }
 
     Ok(())
@@ -176,12 +196,12 @@ mod tests {
             CallbackMapping {
                 prefix: "foo:",
                 to_command: |_| None,
-                resolve_traveler_number: false,
+                value: CallbackValue::Raw,
             },
             CallbackMapping {
                 prefix: "bar:",
                 to_command: |_| None,
-                resolve_traveler_number: false,
+                value: CallbackValue::Raw,
             },
         ];
         assert!(is_stateless_callback("foo:something", &mappings));
@@ -1,9 +1,12 @@
 use crate::{
     Context,
+    commands::CommandOutcome,
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     errors::CommandError,
     expense::Expense,
+    filter::{Filter, Target},
     i18n::{self, Translate, TranslateWithArgs},
+    topics::MessageExt,
 };
 use macro_rules_attribute::apply;
 use maplit::hashmap;
@@ -16,43 +19,56 @@ use tracing::Level;
 pub async fn list_expenses(
     db: Arc<Surreal<Any>>,
     msg: &Message,
-    description: &str,
+    filter: &str,
     ctx: Arc<Mutex<Context>>,
-) -> Result<String, CommandError> {
+) -> Result<CommandOutcome, CommandError> {
     tracing::debug!("{LOG_DEBUG_START}");
+    let filter = filter.trim();
 
-    let list_res = if description.is_empty() {
-        Expense::db_select(db, msg.chat.id).await
-    } else {
-        Expense::db_select_by_descr(db, msg.chat.id, description.to_owned()).await
+    let parsed = match Filter::parse(filter, Target::Expenses) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            return Ok(CommandOutcome::Failure(err.translate(ctx)));
+        }
     };
 
+    let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
+    let list_res = Expense::db_select_filtered(db, msg.ledger_id(), &parsed, &tz).await;
     match list_res {
         Ok(expenses) => {
-            let reply = if expenses.is_empty() {
-                if description.is_empty() {
+            let outcome = if expenses.is_empty() {
+                CommandOutcome::Success(if parsed.is_empty() {
                     i18n::commands::LIST_EXPENSES_NOT_FOUND.translate(ctx)
                 } else {
-                    i18n::commands::LIST_EXPENSES_DESCR_NOT_FOUND.translate_with_args(
+                    i18n::commands::LIST_EXPENSES_FILTER_NOT_FOUND.translate_with_args(
                         ctx,
-                        &hashmap! {i18n::args::DESCRIPTION.into() => description.into()},
+                        &hashmap! {i18n::args::FILTER.into() => filter.into()},
                     )
-                }
+                })
             } else {
-                expenses
-                    .into_iter()
-                    .map(|expense| expense.translate(ctx.clone()))
-                    .collect::<Vec<_>>()
-                    .join("\n")
+                CommandOutcome::List(
+                    expenses
+                        .into_iter()
+                        .map(|(expense, highlighted)| match highlighted {
+                            Some(description) => Expense {
+                                description,
+                                ..expense
+                            },
+                            None => expense,
+                        })
+                        .map(|expense| expense.translate(ctx.clone()))
+                        .collect(),
+                )
             };
             tracing::debug!("{LOG_DEBUG_SUCCESS}");
             tracing::info!("Listed expenses");
-            Ok(reply)
+            Ok(outcome)
         }
         Err(err) => {
             tracing::error!("{err}");
             Err(CommandError::ListExpenses {
-                description: description.to_owned(),
+                filter: filter.to_owned(),
             })
         }
     }
@@ -63,9 +79,11 @@ mod tests {
     use crate::{
         db::db,
         expense::Expense,
+        filter::{Filter, FilterError, FilterErrorKind, Target},
         i18n::{self, Translate, TranslateWithArgs},
         tests::{TestBot, helpers},
     };
+    use jiff::tz::TimeZone;
     use maplit::hashmap;
 
     test! { list_expenses_ok,
@@ -133,12 +151,16 @@ mod tests {
 
         // List expenses with description matching "1"
         bot.update("/listexpenses 1");
-        let expenses = Expense::db_select_by_descr(db, bot.chat_id(), String::from("1")).await.unwrap();
-        // Check that only one expense is returned
+        let filter = Filter::parse("1", Target::Expenses).unwrap();
+        let expenses = Expense::db_select_filtered(db, bot.chat_id(), &filter, &TimeZone::UTC).await.unwrap();
+        // Check that only one expense is returned, fuzzy-matched
         assert_eq!(expenses.len(), 1);
         let response = expenses
             .into_iter()
-            .map(|expense| expense.translate_default())
+            .map(|(expense, highlighted)| {
+                assert!(highlighted.is_none());
+                expense.translate_default()
+            })
             .collect::<Vec<_>>()
             .join("\n");
         bot.test_last_message(&response).await;
@@ -152,7 +174,7 @@ mod tests {
         bot.test_last_message(&response).await;
     }
 
-    test! { list_expenses_descr_not_found,
+    test! { list_expenses_filter_not_found,
         let db = db().await;
         let mut bot = TestBot::new(db.clone(), "");
 
@@ -171,8 +193,96 @@ mod tests {
 
         // List expenses with description matching "2" -> not found
         bot.update("/listexpenses 2");
-        let response = i18n::commands::LIST_EXPENSES_DESCR_NOT_FOUND.translate_with_args_default(&hashmap! {i18n::args::DESCRIPTION.into() => "2".into()},
+        let response = i18n::commands::LIST_EXPENSES_FILTER_NOT_FOUND.translate_with_args_default(&hashmap! {i18n::args::FILTER.into() => "2".into()},
         );
         bot.test_last_message(&response).await;
     }
+
+    test! { list_expenses_filters_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        // Add travelers "Alice", "Bob" and "Charlie"
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_traveler(&mut bot, "Charlie").await;
+
+        helpers::add_expense(&mut bot, "Gelato #food", 12.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "Hotel", 300.into(), "Bob", &["Alice;Bob", "end"]).await;
+        helpers::add_expense(&mut bot, "Pizza #Food", 40.into(), "Alice", &["Alice;Charlie", "end"]).await;
+        db.query("UPDATE expense SET occurred_at = d'2026-08-03T10:00:00Z' WHERE description = 'Hotel'")
+            .await
+            .unwrap();
+
+        // Expenses paid by Alice, whatever the case of the name
+        bot.update("/listexpenses payer:alice");
+        let reply = bot.dispatch_and_last_message().await.unwrap();
+        assert!(reply.contains("Gelato") && reply.contains("Pizza") && !reply.contains("Hotel"));
+
+        // Expenses shared with Charlie above 20
+        bot.update("/listexpenses with:Charlie amount>20");
+        let reply = bot.dispatch_and_last_message().await.unwrap();
+        assert!(reply.contains("Pizza") && !reply.contains("Gelato") && !reply.contains("Hotel"));
+
+        // Expenses made in a range of days
+        bot.update("/listexpenses date:2026-08-01..2026-08-05");
+        let reply = bot.dispatch_and_last_message().await.unwrap();
+        assert!(reply.contains("Hotel") && !reply.contains("Gelato") && !reply.contains("Pizza"));
+
+        // Expenses of a category, the most expensive first
+        bot.update("/listexpenses cat:FOOD sort:-amount");
+        let reply = bot.dispatch_and_last_message().await.unwrap();
+        assert!(!reply.contains("Hotel"));
+        assert!(reply.find("Pizza").unwrap() < reply.find("Gelato").unwrap());
+    }
+
+    test! { list_expenses_search_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_expense(&mut bot, "Highway toll", 10.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "Hotel", 100.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "Gelato and caffè at the beach bar", 12.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "Gelateria", 8.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "Gelato", 4.into(), "Alice", &["all"]).await;
+
+        // Words match whole or as a prefix, ignoring case and accents
+        bot.update("/listexpenses GELATO caffe");
+        let reply = bot.dispatch_and_last_message().await.unwrap();
+        assert!(reply.contains("*Gelato* and *caffè* at the beach bar"), "Unexpected reply: {reply}");
+        assert_eq!(reply.lines().count(), 1);
+
+        // Texts searched apart are all highlighted
+        bot.update(r#"/listexpenses "gelato" payer:Alice "caffe""#);
+        let reply = bot.dispatch_and_last_message().await.unwrap();
+        assert!(reply.contains("*Gelato* and *caffè* at the beach bar"), "Unexpected reply: {reply}");
+        assert_eq!(reply.lines().count(), 1);
+
+        // The shorter description is more relevant
+        bot.update("/listexpenses gelato");
+        let reply = bot.dispatch_and_last_message().await.unwrap();
+        let short = reply.find("#5: *Gelato*").unwrap();
+        let long = reply.find("#3: *Gelato* and").unwrap();
+        assert!(short < long, "Unexpected order: {reply}");
+        assert!(!reply.contains("Gelateria"));
+
+        // Too short to be searched: fuzzy-matched, without highlights
+        bot.update("/listexpenses hi");
+        let reply = bot.dispatch_and_last_message().await.unwrap();
+        assert!(reply.contains("Highway toll") && !reply.contains('*'));
+    }
+
+    test! { list_expenses_invalid_filter,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/listexpenses payer:Alice colour:red");
+        let response = FilterError {
+            target: Target::Expenses,
+            kind: FilterErrorKind::UnknownKey,
+            token: String::from("colour:red"),
+        }
+        .translate_default();
+        bot.test_last_message(&response).await;
+    }
 }
@@ -1,10 +1,12 @@
 use crate::{
     Context,
+    commands::CommandOutcome,
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     errors::CommandError,
+    filter::{Filter, Target},
     i18n::{self, Translate, TranslateWithArgs},
+    topics::MessageExt,
     transfer::Transfer,
-    traveler::Name,
 };
 use macro_rules_attribute::apply;
 use maplit::hashmap;
@@ -17,42 +19,49 @@ use tracing::Level;
 pub async fn list_transfers(
     db: Arc<Surreal<Any>>,
     msg: &Message,
-    name: Option<Name>,
+    filter: &str,
     ctx: Arc<Mutex<Context>>,
-) -> Result<String, CommandError> {
+) -> Result<CommandOutcome, CommandError> {
     tracing::debug!("{LOG_DEBUG_START}");
+    let filter = filter.trim();
 
-    let list_res = match &name {
-        None => Transfer::transfers(db, msg.chat.id).await,
-        Some(name) => Transfer::transfers_by_name(db, msg.chat.id, name.clone()).await,
+    let parsed = match Filter::parse(filter, Target::Transfers) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            return Ok(CommandOutcome::Failure(err.translate(ctx)));
+        }
     };
 
+    let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
+    let list_res = Transfer::transfers_filtered(db, msg.ledger_id(), &parsed, &tz).await;
     match list_res {
         Ok(transfers) => {
-            let reply = if transfers.is_empty() {
-                match &name {
-                    None => i18n::commands::LIST_TRANSFERS_NOT_FOUND.translate(ctx),
-                    Some(name) => i18n::commands::LIST_TRANSFERS_NAME_NOT_FOUND
-                        .translate_with_args(
-                            ctx,
-                            &hashmap! {i18n::args::NAME.into() => name.clone().into()},
-                        ),
-                }
+            let outcome = if transfers.is_empty() {
+                CommandOutcome::Success(if parsed.is_empty() {
+                    i18n::commands::LIST_TRANSFERS_NOT_FOUND.translate(ctx)
+                } else {
+                    i18n::commands::LIST_TRANSFERS_FILTER_NOT_FOUND.translate_with_args(
+                        ctx,
+                        &hashmap! {i18n::args::FILTER.into() => filter.into()},
+                    )
+                })
             } else {
-                transfers
-                    .into_iter()
-                    .map(|transfer| transfer.translate(ctx.clone()))
-                    .collect::<Vec<_>>()
-                    .join("\n")
+                CommandOutcome::List(
+                    transfers
+                        .into_iter()
+                        .map(|transfer| transfer.translate(ctx.clone()))
+                        .collect(),
+                )
             };
             tracing::debug!("{LOG_DEBUG_SUCCESS}");
             tracing::info!("Listed transfers");
-            Ok(reply)
+            Ok(outcome)
         }
         Err(err) => {
             tracing::error!("{err}");
             Err(CommandError::ListTransfers {
-                name: name.unwrap_or_default(),
+                filter: filter.to_owned(),
             })
         }
     }
@@ -62,13 +71,13 @@ pub async fn list_transfers(
 mod tests {
     use crate::{
         db::db,
+        filter::{Filter, FilterError, FilterErrorKind, Target},
         i18n::{self, Translate, TranslateWithArgs},
         tests::{TestBot, helpers},
         transfer::Transfer,
-        traveler::Name,
     };
+    use jiff::tz::TimeZone;
     use maplit::hashmap;
-    use std::str::FromStr;
 
     test! { list_transfers_ok,
         let db = db().await;
@@ -113,7 +122,8 @@ mod tests {
 
         // List transfers related to Alice
         bot.update("/listtransfers Alice");
-        let transfers = Transfer::transfers_by_name(db.clone(), bot.chat_id(), Name::from_str("Alice").unwrap()).await.unwrap();
+        let filter = Filter::parse("Alice", Target::Transfers).unwrap();
+        let transfers = Transfer::transfers_filtered(db.clone(), bot.chat_id(), &filter, &TimeZone::UTC).await.unwrap();
         // Check that only one transfer is returned
         assert_eq!(transfers.len(), 1);
         let response = transfers
@@ -125,7 +135,8 @@ mod tests {
 
         // List transfers related to Bob
         bot.update("/listtransfers Bob");
-        let transfers = Transfer::transfers_by_name(db, bot.chat_id(), Name::from_str("Bob").unwrap()).await.unwrap();
+        let filter = Filter::parse("Bob", Target::Transfers).unwrap();
+        let transfers = Transfer::transfers_filtered(db, bot.chat_id(), &filter, &TimeZone::UTC).await.unwrap();
         // Check that two transfers are returned
         assert_eq!(transfers.len(), 2);
         let response = transfers
@@ -144,7 +155,7 @@ mod tests {
         bot.test_last_message(&response).await;
     }
 
-    test! { list_transfers_name_not_found,
+    test! { list_transfers_filter_not_found,
         let db = db().await;
         let mut bot = TestBot::new(db.clone(), "");
 
@@ -157,7 +168,7 @@ mod tests {
 
         // List transfers related to Charlie -> not found
         bot.update("/listtransfers Charlie");
-        let response = i18n::commands::LIST_TRANSFERS_NAME_NOT_FOUND.translate_with_args_default(&hashmap! {i18n::args::NAME.into() => "Charlie".into()},
+        let response = i18n::commands::LIST_TRANSFERS_FILTER_NOT_FOUND.translate_with_args_default(&hashmap! {i18n::args::FILTER.into() => "Charlie".into()},
         );
         bot.test_last_message(&response).await;
     }
@@ -175,7 +186,8 @@ mod tests {
 
         // /listtransfers ALICE -> should match "Alice" case-insensitively
         bot.update("/listtransfers ALICE");
-        let transfers = Transfer::transfers_by_name(db, bot.chat_id(), Name::from_str("Alice").unwrap()).await.unwrap();
+        let filter = Filter::parse("Alice", Target::Transfers).unwrap();
+        let transfers = Transfer::transfers_filtered(db, bot.chat_id(), &filter, &TimeZone::UTC).await.unwrap();
         assert_eq!(transfers.len(), 1);
         let response = transfers
             .into_iter()
@@ -184,4 +196,49 @@ mod tests {
             .join("\n");
         bot.test_last_message(&response).await;
     }
+
+    test! { list_transfers_filters_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        // Add travelers "Alice", "Bob" and "Charlie"
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_traveler(&mut bot, "Charlie").await;
+
+        // Transfer 100 from Alice to Bob, 50 from Bob to Charlie, 20 from Alice to Charlie
+        helpers::transfer(&mut bot, "Alice", "Bob", 100.into()).await;
+        helpers::transfer(&mut bot, "Bob", "Charlie", 50.into()).await;
+        helpers::transfer(&mut bot, "Alice", "Charlie", 20.into()).await;
+
+        // Transfers sent by Alice, the largest last
+        bot.update("/listtransfers from:Alice sort:amount");
+        let filter = Filter::parse("from:Alice sort:amount", Target::Transfers).unwrap();
+        let transfers = Transfer::transfers_filtered(db.clone(), bot.chat_id(), &filter, &TimeZone::UTC).await.unwrap();
+        assert_eq!(transfers.iter().map(|t| t.number).collect::<Vec<_>>(), vec![3, 1]);
+        let response = transfers
+            .into_iter()
+            .map(|transfer| transfer.translate_default())
+            .collect::<Vec<_>>()
+            .join("\n");
+        bot.test_last_message(&response).await;
+
+        // Transfers received by Charlie of at least 50
+        let filter = Filter::parse("to:charlie amount>=50", Target::Transfers).unwrap();
+        let transfers = Transfer::transfers_filtered(db, bot.chat_id(), &filter, &TimeZone::UTC).await.unwrap();
+        assert_eq!(transfers.iter().map(|t| t.number).collect::<Vec<_>>(), vec![2]);
+    }
+
+    test! { list_transfers_filter_not_available,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/listtransfers cat:food");
+        let response = FilterError {
+            target: Target::Transfers,
+            kind: FilterErrorKind::NotAvailable,
+            token: String::from("cat:food"),
+        }
+        .translate_default();
+        bot.test_last_message(&response).await;
+    }
 }
@@ -3,6 +3,7 @@ use crate::{
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     errors::CommandError,
     i18n::{self, Translate},
+    topics::MessageExt,
     traveler::Traveler,
 };
 use macro_rules_attribute::apply;
@@ -18,7 +19,7 @@ pub async fn list_travelers(
     ctx: Arc<Mutex<Context>>,
 ) -> Result<String, CommandError> {
     tracing::debug!("{LOG_DEBUG_START}");
-    let list_res = Traveler::db_select(db, msg.chat.id).await;
+    let list_res = Traveler::db_select(db, msg.ledger_id()).await;
     match list_res {
         Ok(travelers) => {
             let reply = if travelers.is_empty() {
@@ -0,0 +1,107 @@
+use crate::{
+    Context,
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, TranslateWithArgs},
+    topics::MessageExt,
+    trip::Trip,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn list_trips(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<String, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+    let list_res = Trip::db_select(db.clone(), msg.ledger_id()).await;
+    let active_res = Trip::db_select_active(db, msg.ledger_id()).await;
+    match (list_res, active_res) {
+        (Ok(trips), Ok(active)) => {
+            let reply = trips
+                .into_iter()
+                .map(|trip| {
+                    let key = if active.as_ref().is_some_and(|active| active.id == trip.id) {
+                        i18n::format::FORMAT_ACTIVE_TRIP
+                    } else {
+                        i18n::format::FORMAT_TRIP
+                    };
+                    key.translate_with_args(
+                        ctx.clone(),
+                        &hashmap! {
+                            i18n::args::NUMBER.into() => trip.number.into(),
+                            i18n::args::NAME.into() => trip.name.into(),
+                            i18n::args::CLOSED.into() => trip.closed.to_string().into(),
+                        },
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            tracing::info!("Listed trips");
+            Ok(reply)
+        }
+        (Err(err), _) | (_, Err(err)) => {
+            tracing::error!("{err}");
+            Err(CommandError::ListTrips)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, TranslateWithArgs},
+        tests::TestBot,
+    };
+    use maplit::hashmap;
+
+    test! { list_trips_ok,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/newtrip Japan");
+        bot.dispatch().await;
+
+        bot.update("/listtrips");
+        let default_trip = i18n::format::FORMAT_TRIP.translate_with_args_default(
+            &hashmap! {
+                i18n::args::NUMBER.into() => 1.into(),
+                i18n::args::NAME.into() => "Default".into(),
+                i18n::args::CLOSED.into() => "false".into(),
+            },
+        );
+        let japan = i18n::format::FORMAT_ACTIVE_TRIP.translate_with_args_default(
+            &hashmap! {
+                i18n::args::NUMBER.into() => 2.into(),
+                i18n::args::NAME.into() => "Japan".into(),
+                i18n::args::CLOSED.into() => "false".into(),
+            },
+        );
+        bot.test_last_message(&format!("{default_trip}\n{japan}")).await;
+    }
+
+    test! { list_trips_closed,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/closetrip");
+        bot.dispatch().await;
+
+        bot.update("/listtrips");
+        let default_trip = i18n::format::FORMAT_ACTIVE_TRIP.translate_with_args_default(
+            &hashmap! {
+                i18n::args::NUMBER.into() => 1.into(),
+                i18n::args::NAME.into() => "Default".into(),
+                i18n::args::CLOSED.into() => "true".into(),
+            },
+        );
+        assert!(default_trip.contains("closed"));
+        bot.test_last_message(&default_trip).await;
+    }
+}
@@ -1,50 +1,98 @@
 mod add_traveler;
+mod audit;
+mod backup;
 mod cancel;
+mod chart;
 mod clear_all;
 mod clear_expenses;
 mod clear_transfers;
 pub(crate) mod clear_travelers;
+mod close_trip;
 mod command;
 mod command_arg;
 mod command_outcome;
+mod debt_graph;
 mod delete_expense;
 mod delete_transfer;
 mod delete_traveler;
+mod export;
 mod help;
 mod help_message;
+mod import;
 pub(crate) mod inline_keyboards;
 mod list_expenses;
 mod list_transfers;
 mod list_travelers;
+mod list_trips;
+mod new_trip;
+mod pay_prefs;
+mod rebuild_balances;
+mod rename_trip;
+mod reopen_trip;
+mod reply;
+mod report;
+mod restore;
 mod set_currency;
 mod set_language;
+mod set_settlement;
+mod set_simplification;
+mod set_timezone;
+mod settle;
 mod show_balances;
 mod show_expense;
 mod show_stats;
+mod statement;
+mod summary;
+mod switch_trip;
+mod topic_ledger;
 mod transfer;
 mod unknown_command;
 
 pub use add_traveler::add_traveler;
+pub use audit::{audit, audit_keyboard};
+pub use backup::backup;
 pub use cancel::cancel;
+pub use chart::chart;
 pub use clear_all::clear_all;
 pub use clear_expenses::clear_expenses;
 pub use clear_transfers::clear_transfers;
 pub use clear_travelers::clear_travelers;
+pub use close_trip::{close_trip, closed_trip_endpoint, closed_trip_guard, closed_trip_refusal};
 pub use command::*;
 pub use command_arg::CommandArg;
-pub use command_outcome::CommandOutcome;
+pub use command_outcome::{CommandOutcome, Document};
+pub use debt_graph::debt_graph;
 pub use delete_expense::delete_expense;
 pub use delete_transfer::delete_transfer;
 pub use delete_traveler::delete_traveler;
+pub use export::export;
 pub use help::help;
 pub use help_message::HelpMessage;
+pub use import::import;
 pub use list_expenses::list_expenses;
 pub use list_transfers::list_transfers;
 pub use list_travelers::list_travelers;
+pub use list_trips::list_trips;
+pub use new_trip::new_trip;
+pub use pay_prefs::{pay_prefs, unmet_preferences};
+pub use rebuild_balances::rebuild_balances;
+pub use rename_trip::rename_trip;
+pub use reopen_trip::{admin_only_endpoint, reopen_trip, sender_is_admin};
+pub use reply::{is_list_page_callback, list_page_endpoint, send_outcome};
+pub use report::report;
+pub use restore::restore;
 pub use set_currency::set_currency;
 pub use set_language::set_language;
+pub use set_settlement::set_settlement;
+pub use set_simplification::set_simplification;
+pub use set_timezone::set_timezone;
+pub use settle::{settle, settle_keyboard};
 pub use show_balances::show_balances;
 pub use show_expense::show_expense;
-pub use show_stats::show_stats;
-pub use transfer::transfer;
+pub use show_stats::{show_stats, show_stats_keyboard};
+pub use statement::statement;
+pub use summary::summary;
+pub use switch_trip::switch_trip;
+pub use topic_ledger::topic_ledger;
+pub use transfer::{transfer, transfer_all};
 pub use unknown_command::unknown_command;
@@ -0,0 +1,121 @@
+use crate::{
+    Context,
+    commands::CommandOutcome,
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, TranslateWithArgs},
+    topics::MessageExt,
+    traveler::Name,
+    trip::Trip,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn new_trip(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    name: Name,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    // Check if a trip with the same name exists on db
+    let count_res = Trip::db_count(db.clone(), msg.ledger_id(), &name).await;
+    match count_res {
+        Ok(Some(count)) if *count > 0 => {
+            tracing::warn!(
+                "{}",
+                i18n::commands::NEW_TRIP_ALREADY_EXISTS.translate_with_args_default(
+                    &hashmap! {i18n::args::NAME.into() => name.clone().into()},
+                )
+            );
+            Ok(CommandOutcome::Failure(
+                i18n::commands::NEW_TRIP_ALREADY_EXISTS
+                    .translate_with_args(ctx, &hashmap! {i18n::args::NAME.into() => name.into()}),
+            ))
+        }
+        Ok(_) => {
+            // Create trip on db, it becomes the active one
+            let create_res = Trip::db_create(db, msg.ledger_id(), &name).await;
+            match create_res {
+                Ok(_) => {
+                    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+                    tracing::info!("Trip '{name}' created");
+                    Ok(CommandOutcome::Success(
+                        i18n::commands::NEW_TRIP_OK.translate_with_args(
+                            ctx,
+                            &hashmap! {i18n::args::NAME.into() => name.into()},
+                        ),
+                    ))
+                }
+                Err(err) => {
+                    tracing::error!("{err}");
+                    Err(CommandError::NewTrip { name })
+                }
+            }
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            Err(CommandError::NewTrip { name })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, Translate, TranslateWithArgs},
+        tests::{TestBot, helpers},
+    };
+    use maplit::hashmap;
+
+    test! { new_trip_ok,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/newtrip Japan");
+        let response = i18n::commands::NEW_TRIP_OK.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Japan".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { new_trip_already_exists_case_insensitive,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/newtrip Japan");
+        bot.dispatch().await;
+
+        bot.update("/newtrip japan");
+        let response = i18n::commands::NEW_TRIP_ALREADY_EXISTS.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "japan".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { new_trip_starts_empty,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "");
+        helpers::add_traveler(&mut bot, "Alice").await;
+
+        // The new trip becomes the active one and has its own travelers
+        bot.update("/newtrip Japan");
+        bot.dispatch().await;
+        bot.update("/listtravelers");
+        let response = i18n::commands::LIST_TRAVELERS_NOT_FOUND.translate_default();
+        bot.test_last_message(&response).await;
+
+        // The same name can be reused for a traveler in the new trip
+        bot.update("/addtraveler Alice");
+        let response = i18n::commands::ADD_TRAVELER_OK.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Alice".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+}
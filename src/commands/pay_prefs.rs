@@ -0,0 +1,518 @@
+use crate::{
+    Context,
+    cannot_pay::CannotPay,
+    chat::Chat,
+    commands::CommandOutcome,
+    consts::{CANNOT_KWORD, CLEAR_KWORD, LOG_DEBUG_START, LOG_DEBUG_SUCCESS, VIA_KWORD},
+    debt::SimplificationMode,
+    errors::CommandError,
+    i18n::{self, Translate, TranslateWithArgs},
+    settles_via::SettlesVia,
+    topics::MessageExt,
+    traveler::{Name, Traveler},
+    update_debts,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::{prelude::*, types::ChatId};
+use tracing::Level;
+
+/// A change to the payment preferences of a traveler.
+enum Change {
+    CannotPay(Name),
+    SettlesVia(Name),
+    Clear,
+}
+
+#[apply(trace_command_db)]
+pub async fn pay_prefs(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    args: &str,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+    let chat_id = msg.ledger_id();
+
+    let parts: Vec<&str> = args.split_whitespace().collect();
+    let (name, change) = match parts.as_slice() {
+        [] => {
+            return match list_preferences(db, chat_id, ctx).await {
+                Ok(reply) => {
+                    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+                    Ok(CommandOutcome::Success(reply))
+                }
+                Err(err) => {
+                    tracing::error!("{err}");
+                    Err(CommandError::PayPrefs)
+                }
+            };
+        }
+        [name, kword] if kword.eq_ignore_ascii_case(CLEAR_KWORD) => (*name, Some(Change::Clear)),
+        [name, kword, other] => {
+            let change = Name::from_str(other).ok().and_then(|other| {
+                if kword.eq_ignore_ascii_case(CANNOT_KWORD) {
+                    Some(Change::CannotPay(other))
+                } else if kword.eq_ignore_ascii_case(VIA_KWORD) {
+                    Some(Change::SettlesVia(other))
+                } else {
+                    None
+                }
+            });
+            (*name, change)
+        }
+        _ => ("", None),
+    };
+    let (Ok(name), Some(change)) = (Name::from_str(name), change) else {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::PAY_PREFS_USAGE.translate(ctx),
+        ));
+    };
+
+    let traveler = match find_traveler(db.clone(), chat_id, &name, ctx.clone()).await? {
+        Ok(traveler) => traveler,
+        Err(outcome) => return Ok(outcome),
+    };
+    let (other, is_cannot_pay) = match change {
+        Change::Clear => {
+            let res = match CannotPay::db_delete_by_traveler(db.clone(), traveler.id.clone()).await
+            {
+                Ok(()) => SettlesVia::db_delete_by_traveler(db.clone(), traveler.id).await,
+                Err(err) => Err(err),
+            };
+            let reply = i18n::commands::PAY_PREFS_CLEAR_OK.translate_with_args(
+                ctx.clone(),
+                &hashmap! {i18n::args::NAME.into() => traveler.name.into()},
+            );
+            return updated(db, chat_id, &name, reply, res, ctx).await;
+        }
+        Change::CannotPay(other) => (other, true),
+        Change::SettlesVia(other) => (other, false),
+    };
+    if other.to_lowercase() == traveler.name.to_lowercase() {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::PAY_PREFS_SAME_TRAVELER.translate_with_args(
+                ctx,
+                &hashmap! {i18n::args::NAME.into() => traveler.name.into()},
+            ),
+        ));
+    }
+    let other = match find_traveler(db.clone(), chat_id, &other, ctx.clone()).await? {
+        Ok(other) => other,
+        Err(outcome) => return Ok(outcome),
+    };
+
+    let (res, key) = if is_cannot_pay {
+        (
+            CannotPay::db_relate(db.clone(), traveler.id, other.id)
+                .await
+                .map(|_| {}),
+            i18n::commands::PAY_PREFS_CANNOT_PAY_OK,
+        )
+    } else {
+        (
+            SettlesVia::db_relate(db.clone(), traveler.id, other.id)
+                .await
+                .map(|_| {}),
+            i18n::commands::PAY_PREFS_SETTLES_VIA_OK,
+        )
+    };
+    let reply = key.translate_with_args(
+        ctx.clone(),
+        &hashmap! {
+            i18n::args::NAME.into() => traveler.name.into(),
+            i18n::args::OTHER_TRAVELER_NAME.into() => other.name.into(),
+        },
+    );
+    updated(db, chat_id, &name, reply, res, ctx).await
+}
+
+/// Completes a change to the payment preferences of `name`, settling the
+/// current balances again if it succeeded, and noting if the preferences
+/// don't apply in the current debt simplification mode.
+async fn updated(
+    db: Arc<Surreal<Any>>,
+    chat_id: ChatId,
+    name: &Name,
+    reply: String,
+    res: Result<(), surrealdb::Error>,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    if let Err(err) = res {
+        tracing::error!("{err}");
+        return Err(CommandError::PayPrefs);
+    }
+    if let Err(err) = update_debts(db.clone(), chat_id).await {
+        tracing::warn!("{err}");
+    }
+    let reply = match full_only_note(db, chat_id, ctx).await {
+        Ok(Some(note)) => format!("{reply}\n\n{note}"),
+        Ok(None) => reply,
+        Err(err) => {
+            tracing::warn!("{err}");
+            reply
+        }
+    };
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Payment preferences of {name} updated");
+    Ok(CommandOutcome::Success(reply))
+}
+
+/// Looks up the traveler named `name`, returning the reply to send if there
+/// is no such traveler.
+async fn find_traveler(
+    db: Arc<Surreal<Any>>,
+    chat_id: ChatId,
+    name: &Name,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<Result<Traveler, CommandOutcome>, CommandError> {
+    match Traveler::db_select_by_name(db, chat_id, name).await {
+        Ok(Some(traveler)) => Ok(Ok(traveler)),
+        Ok(None) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            Ok(Err(CommandOutcome::Failure(
+                i18n::commands::PAY_PREFS_TRAVELER_NOT_FOUND.translate_with_args(
+                    ctx,
+                    &hashmap! {i18n::args::NAME.into() => name.clone().into()},
+                ),
+            )))
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            Err(CommandError::PayPrefs)
+        }
+    }
+}
+
+/// Returns the names of the travelers of the active trip, by ID.
+async fn traveler_names(
+    db: Arc<Surreal<Any>>,
+    chat_id: ChatId,
+) -> Result<HashMap<String, Name>, surrealdb::Error> {
+    Ok(Traveler::db_select(db, chat_id)
+        .await?
+        .into_iter()
+        .map(|traveler| (traveler.id.to_string(), traveler.name))
+        .collect())
+}
+
+async fn list_preferences(
+    db: Arc<Surreal<Any>>,
+    chat_id: ChatId,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<String, surrealdb::Error> {
+    let names = traveler_names(db.clone(), chat_id).await?;
+    let cannot_pay = CannotPay::db_select(db.clone(), chat_id)
+        .await?
+        .into_iter()
+        .map(|preference| {
+            (
+                i18n::commands::PAY_PREFS_CANNOT_PAY,
+                preference.r#in,
+                preference.out,
+            )
+        });
+    let settles_via = SettlesVia::db_select(db.clone(), chat_id)
+        .await?
+        .into_iter()
+        .map(|preference| {
+            (
+                i18n::commands::PAY_PREFS_SETTLES_VIA,
+                preference.r#in,
+                preference.out,
+            )
+        });
+
+    let mut lines = cannot_pay
+        .chain(settles_via)
+        .filter_map(|(key, traveler, other)| {
+            Some((
+                names.get(&traveler.to_string())?.clone(),
+                key,
+                names.get(&other.to_string())?.clone(),
+            ))
+        })
+        .collect::<Vec<_>>();
+    lines.sort();
+    if lines.is_empty() {
+        return Ok(i18n::commands::PAY_PREFS_NONE.translate(ctx));
+    }
+    let list = lines
+        .into_iter()
+        .map(|(name, key, other)| {
+            key.translate_with_args(
+                ctx.clone(),
+                &hashmap! {
+                    i18n::args::NAME.into() => name.into(),
+                    i18n::args::OTHER_TRAVELER_NAME.into() => other.into(),
+                },
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(match full_only_note(db, chat_id, ctx).await? {
+        Some(note) => format!("{list}\n\n{note}"),
+        None => list,
+    })
+}
+
+/// Returns the note telling that payment preferences only apply to fully
+/// simplified debts, if the chat simplifies them less.
+async fn full_only_note(
+    db: Arc<Surreal<Any>>,
+    chat_id: ChatId,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<Option<String>, surrealdb::Error> {
+    let mode = Chat::db_select_by_id(db, chat_id)
+        .await?
+        .map(|chat| chat.simplification)
+        .unwrap_or_default();
+    Ok((mode != SimplificationMode::Full)
+        .then(|| i18n::commands::PAY_PREFS_FULL_ONLY.translate(ctx)))
+}
+
+/// Explains the debts of the active trip that break a payment preference,
+/// because there was no other way to settle, or tells that the preferences
+/// don't apply if debts aren't fully simplified. Returns `None` if every
+/// preference is met.
+pub async fn unmet_preferences(
+    db: Arc<Surreal<Any>>,
+    chat_id: ChatId,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<Option<String>, surrealdb::Error> {
+    let cannot_pay = CannotPay::db_select_unmet(db.clone(), chat_id).await?;
+    let settles_via = SettlesVia::db_select_unmet(db.clone(), chat_id).await?;
+    if cannot_pay.is_empty() && settles_via.is_empty() {
+        return Ok(None);
+    }
+    if let Some(note) = full_only_note(db.clone(), chat_id, ctx.clone()).await? {
+        return Ok(Some(note));
+    }
+    let names = traveler_names(db, chat_id).await?;
+    let cannot_pay = cannot_pay.into_iter().filter_map(|preference| {
+        Some(i18n::commands::SETTLE_PREFERENCE_UNMET.translate_with_args(
+            ctx.clone(),
+            &hashmap! {
+                i18n::args::DEBTOR.into() => names.get(&preference.r#in.to_string())?.clone().into(),
+                i18n::args::CREDITOR.into() => names.get(&preference.out.to_string())?.clone().into(),
+            },
+        ))
+    });
+    let settles_via = settles_via.into_iter().filter_map(|preference| {
+        Some(i18n::commands::SETTLE_PREFERENCE_VIA_UNMET.translate_with_args(
+            ctx.clone(),
+            &hashmap! {
+                i18n::args::NAME.into() => names.get(&preference.r#in.to_string())?.clone().into(),
+                i18n::args::OTHER_TRAVELER_NAME.into() => names.get(&preference.out.to_string())?.clone().into(),
+            },
+        ))
+    });
+    let lines = cannot_pay.chain(settles_via).collect::<Vec<_>>();
+    Ok((!lines.is_empty()).then(|| lines.join("\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        balance::Balance,
+        db::db,
+        i18n::{self, Translate, TranslateWithArgs},
+        settles_via::SettlesVia,
+        tests::{TestBot, helpers},
+    };
+    use maplit::hashmap;
+    use std::sync::Arc;
+    use surrealdb::{Surreal, engine::any::Any};
+
+    /// Returns the debts of the active trip as `(debtor, creditor)` names.
+    async fn debts(db: Arc<Surreal<Any>>, bot: &TestBot) -> Vec<(String, String)> {
+        let mut debts = Balance::balances(db, bot.chat_id())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|b| (b.debtor_name.to_string(), b.creditor_name.to_string()))
+            .collect::<Vec<_>>();
+        debts.sort();
+        debts
+    }
+
+    test! { pay_prefs_list,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "/payprefs");
+        let response = i18n::commands::PAY_PREFS_NONE.translate_default();
+        bot.test_last_message(&response).await;
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_traveler(&mut bot, "Carol").await;
+
+        bot.update("/payprefs bob cannot Alice");
+        let response = i18n::commands::PAY_PREFS_CANNOT_PAY_OK.translate_with_args_default(
+            &hashmap! {
+                i18n::args::NAME.into() => "Bob".into(),
+                i18n::args::OTHER_TRAVELER_NAME.into() => "Alice".into(),
+            },
+        );
+        bot.test_last_message(&response).await;
+
+        bot.update("/payprefs Alice via Carol");
+        bot.dispatch().await;
+        bot.update("/payprefs");
+        let response = [
+            i18n::commands::PAY_PREFS_SETTLES_VIA.translate_with_args_default(&hashmap! {
+                i18n::args::NAME.into() => "Alice".into(),
+                i18n::args::OTHER_TRAVELER_NAME.into() => "Carol".into(),
+            }),
+            i18n::commands::PAY_PREFS_CANNOT_PAY.translate_with_args_default(&hashmap! {
+                i18n::args::NAME.into() => "Bob".into(),
+                i18n::args::OTHER_TRAVELER_NAME.into() => "Alice".into(),
+            }),
+        ]
+        .join("\n");
+        bot.test_last_message(&response).await;
+
+        bot.update("/payprefs Bob clear");
+        bot.dispatch().await;
+        bot.update("/payprefs Alice clear");
+        bot.dispatch().await;
+        bot.update("/payprefs");
+        let response = i18n::commands::PAY_PREFS_NONE.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { pay_prefs_invalid,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "/payprefs Alice pays Bob");
+        let response = i18n::commands::PAY_PREFS_USAGE.translate_default();
+        bot.test_last_message(&response).await;
+
+        bot.update("/payprefs Alice cannot Bob");
+        let response = i18n::commands::PAY_PREFS_TRAVELER_NOT_FOUND
+            .translate_with_args_default(&hashmap! {i18n::args::NAME.into() => "Alice".into()});
+        bot.test_last_message(&response).await;
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        bot.update("/payprefs Alice via alice");
+        let response = i18n::commands::PAY_PREFS_SAME_TRAVELER
+            .translate_with_args_default(&hashmap! {i18n::args::NAME.into() => "Alice".into()});
+        bot.test_last_message(&response).await;
+    }
+
+    test! { pay_prefs_cannot_pay,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_traveler(&mut bot, "Carol").await;
+        // Carol owes Alice 10, and Bob owes Carol 10
+        helpers::add_expense(&mut bot, "Dinner", 10.into(), "Alice", &["Carol", "end"]).await;
+        helpers::add_expense(&mut bot, "Taxi", 10.into(), "Carol", &["Bob", "end"]).await;
+        assert_eq!(debts(db.clone(), &bot).await, vec![("Bob".into(), "Alice".into())]);
+
+        // Bob can't pay Alice: the money goes through Carol
+        bot.update("/payprefs Bob cannot Alice");
+        bot.dispatch().await;
+        assert_eq!(
+            debts(db.clone(), &bot).await,
+            vec![("Bob".into(), "Carol".into()), ("Carol".into(), "Alice".into())]
+        );
+
+        // Nor Carol: Bob has to pay Alice anyway, which is explained
+        bot.update("/payprefs Bob cannot Carol");
+        bot.dispatch().await;
+        assert_eq!(debts(db.clone(), &bot).await, vec![("Bob".into(), "Alice".into())]);
+        bot.update("/showbalances");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        let unmet = i18n::commands::SETTLE_PREFERENCE_UNMET.translate_with_args_default(
+            &hashmap! {
+                i18n::args::DEBTOR.into() => "Bob".into(),
+                i18n::args::CREDITOR.into() => "Alice".into(),
+            },
+        );
+        assert!(response.contains(&unmet), "{response}");
+    }
+
+    test! { pay_prefs_settles_via,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+        for name in ["Alice", "Bob", "Carol", "Dave"] {
+            helpers::add_traveler(&mut bot, name).await;
+        }
+        helpers::add_expense(&mut bot, "Hotel", 30.into(), "Alice", &["Bob; Carol; Dave", "end"])
+            .await;
+
+        // Dave collects the money as the group's banker
+        for name in ["Alice", "Bob", "Carol"] {
+            bot.update(&format!("/payprefs {name} via Dave"));
+            bot.dispatch().await;
+        }
+        assert_eq!(
+            debts(db.clone(), &bot).await,
+            vec![
+                ("Bob".into(), "Dave".into()),
+                ("Carol".into(), "Dave".into()),
+                ("Dave".into(), "Alice".into()),
+            ]
+        );
+    }
+
+    test! { pay_prefs_settles_via_unmet,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+        let chat_id = bot.chat_id();
+        for name in ["Alice", "Bob", "Carol"] {
+            helpers::add_traveler(&mut bot, name).await;
+        }
+        helpers::add_expense(&mut bot, "Hotel", 30.into(), "Alice", &["all"]).await;
+
+        bot.update("/payprefs Bob via Carol");
+        bot.dispatch().await;
+        let unmet = SettlesVia::db_select_unmet(db.clone(), chat_id).await.unwrap();
+        assert!(unmet.is_empty(), "{unmet:?}");
+
+        // Without simplification, Bob pays Alice directly
+        bot.update("/setsimplification none");
+        bot.dispatch().await;
+        let unmet = SettlesVia::db_select_unmet(db.clone(), chat_id).await.unwrap();
+        assert_eq!(unmet.len(), 1, "{unmet:?}");
+    }
+
+    test! { pay_prefs_full_only,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Hotel", 30.into(), "Alice", &["all"]).await;
+        bot.update("/setsimplification pairwise");
+        bot.dispatch().await;
+
+        let note = i18n::commands::PAY_PREFS_FULL_ONLY.translate_default();
+        bot.update("/payprefs Bob cannot Alice");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        assert!(response.ends_with(&note), "{response}");
+
+        bot.update("/payprefs");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        assert!(response.ends_with(&note), "{response}");
+
+        bot.update("/showbalances");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        assert!(response.contains(&note), "{response}");
+
+        // With full simplification the preferences apply
+        bot.update("/setsimplification full");
+        bot.dispatch().await;
+        bot.update("/payprefs");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        assert!(!response.contains(&note), "{response}");
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{
+    Context,
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    debt::rebuild_debts,
+    errors::CommandError,
+    i18n::{self, Translate},
+    topics::MessageExt,
+};
+use macro_rules_attribute::apply;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn rebuild_balances(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<String, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    match rebuild_debts(db, msg.ledger_id()).await {
+        Ok(()) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            Ok(i18n::commands::REBUILD_BALANCES_OK.translate(ctx))
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            Err(CommandError::RebuildBalances)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        debt::NET,
+        i18n::{self, Translate},
+        tests::{TestBot, helpers},
+    };
+    use rust_decimal::Decimal;
+
+    test! { rebuild_balances_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 60.into(), "Alice", &["all"]).await;
+
+        // Corrupt the net positions, then rebuild them
+        db.query(format!("UPDATE traveler SET {NET} = 1000dec")).await.unwrap();
+        bot.update("/rebuildbalances");
+        let response = i18n::commands::REBUILD_BALANCES_OK.translate_default();
+        bot.test_last_message(&response).await;
+
+        let mut nets: Vec<Decimal> = db
+            .query(format!("SELECT VALUE {NET} FROM traveler"))
+            .await
+            .and_then(|mut r| r.take(0))
+            .unwrap();
+        nets.sort();
+        assert_eq!(nets, vec![Decimal::from(-30), Decimal::from(30)]);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{
+    Context,
+    commands::CommandOutcome,
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, TranslateWithArgs},
+    topics::MessageExt,
+    traveler::Name,
+    trip::Trip,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn rename_trip(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    name: Name,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let active = Trip::db_select_active(db.clone(), msg.ledger_id()).await;
+    let existing = Trip::db_select_by_name(db.clone(), msg.ledger_id(), &name).await;
+    let active = match (active, existing) {
+        (Ok(Some(active)), Ok(existing)) => {
+            // Renaming a trip to a name differing only in case is allowed
+            if existing.is_some_and(|existing| existing.id != active.id) {
+                tracing::debug!("{LOG_DEBUG_SUCCESS}");
+                return Ok(CommandOutcome::Failure(
+                    i18n::commands::RENAME_TRIP_ALREADY_EXISTS.translate_with_args(
+                        ctx,
+                        &hashmap! {i18n::args::NAME.into() => name.into()},
+                    ),
+                ));
+            }
+            active
+        }
+        (Ok(None), _) => {
+            tracing::error!("No active trip found for chat {}", msg.chat.id);
+            return Err(CommandError::RenameTrip { name });
+        }
+        (Err(err), _) | (_, Err(err)) => {
+            tracing::error!("{err}");
+            return Err(CommandError::RenameTrip { name });
+        }
+    };
+
+    match Trip::db_rename_active(db, msg.ledger_id(), &name).await {
+        Ok(_) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            tracing::info!("Trip '{}' renamed to '{name}'", active.name);
+            Ok(CommandOutcome::Success(
+                i18n::commands::RENAME_TRIP_OK.translate_with_args(
+                    ctx,
+                    &hashmap! {
+                        i18n::args::OLD_NAME.into() => active.name.into(),
+                        i18n::args::NAME.into() => name.into(),
+                    },
+                ),
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            Err(CommandError::RenameTrip { name })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, TranslateWithArgs},
+        tests::TestBot,
+    };
+    use maplit::hashmap;
+
+    test! { rename_trip_ok,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/renametrip Japan");
+        let response = i18n::commands::RENAME_TRIP_OK.translate_with_args_default(
+            &hashmap! {
+                i18n::args::OLD_NAME.into() => "Default".into(),
+                i18n::args::NAME.into() => "Japan".into(),
+            },
+        );
+        bot.test_last_message(&response).await;
+
+        bot.update("/switchtrip Japan");
+        let response = i18n::commands::SWITCH_TRIP_ALREADY_ACTIVE.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Japan".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { rename_trip_change_case,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/renametrip default");
+        let response = i18n::commands::RENAME_TRIP_OK.translate_with_args_default(
+            &hashmap! {
+                i18n::args::OLD_NAME.into() => "Default".into(),
+                i18n::args::NAME.into() => "default".into(),
+            },
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { rename_trip_already_exists,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/newtrip Japan");
+        bot.dispatch().await;
+
+        bot.update("/renametrip default");
+        let response = i18n::commands::RENAME_TRIP_ALREADY_EXISTS.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "default".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{
+    Context, HandlerResult,
+    commands::CommandOutcome,
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, Translate, TranslateWithArgs},
+    topics::{InTopic, MessageExt},
+    trip::Trip,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn reopen_trip(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let active = match Trip::db_select_active(db.clone(), msg.ledger_id()).await {
+        Ok(Some(active)) => active,
+        Ok(None) => {
+            tracing::error!("No active trip found for chat {}", msg.chat.id);
+            return Err(CommandError::ReopenTrip);
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::ReopenTrip);
+        }
+    };
+
+    if !active.closed {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::REOPEN_TRIP_NOT_CLOSED.translate_with_args(
+                ctx,
+                &hashmap! {i18n::args::NAME.into() => active.name.into()},
+            ),
+        ));
+    }
+
+    match active.db_set_closed(db, false).await {
+        Ok(_) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            tracing::info!("Trip '{}' reopened", active.name);
+            Ok(CommandOutcome::Success(
+                i18n::commands::REOPEN_TRIP_OK.translate_with_args(
+                    ctx,
+                    &hashmap! {i18n::args::NAME.into() => active.name.into()},
+                ),
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            Err(CommandError::ReopenTrip)
+        }
+    }
+}
+
+/// Returns `true` if the sender of `msg` is an administrator of the chat.
+/// In private chats the only user is always considered one, as are
+/// anonymous administrators posting on behalf of the group.
+pub async fn sender_is_admin(bot: Bot, msg: Message) -> bool {
+    if msg.chat.is_private()
+        || msg
+            .sender_chat
+            .as_ref()
+            .is_some_and(|c| c.id == msg.chat.id)
+    {
+        return true;
+    }
+    let Some(user) = msg.from.as_ref() else {
+        return false;
+    };
+    match bot.get_chat_member(msg.chat.id, user.id).await {
+        Ok(member) => member.is_privileged(),
+        Err(err) => {
+            tracing::error!("{err}");
+            false
+        }
+    }
+}
+
+/// Endpoint replying to admin-only commands sent by other users.
+#[apply(trace_skip_all)]
+pub async fn admin_only_endpoint(
+    bot: Bot,
+    msg: Message,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    bot.send_message(msg.chat.id, i18n::commands::ADMIN_ONLY.translate(ctx))
+        .in_topic(msg.topic_id())
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, TranslateWithArgs},
+        tests::{TestBot, helpers},
+    };
+    use maplit::hashmap;
+
+    test! { reopen_trip_ok,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/closetrip");
+        bot.dispatch().await;
+
+        bot.update("/reopentrip");
+        let response = i18n::commands::REOPEN_TRIP_OK.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Default".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        bot.update("/listtravelers");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        assert!(response.contains("Alice"));
+    }
+
+    test! { reopen_trip_not_closed,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/reopentrip");
+        let response = i18n::commands::REOPEN_TRIP_NOT_CLOSED.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Default".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+}
@@ -0,0 +1,360 @@
+//! Sending command replies that don't fit in a single Telegram message.
+//!
+//! Plain replies longer than [`MAX_MESSAGE_LEN`] are split into several
+//! messages, breaking at line boundaries. Lists ([`CommandOutcome::List`])
+//! are instead sent a page at a time: the message gets a `◀` / `▶`
+//! navigation row and is edited in place when the user turns the page.
+//!
+//! The pages are kept in memory, for the last [`LIST_PAGES_CAPACITY`] lists
+//! only: older lists (or lists sent before a restart) reply with
+//! `list-page-expired` and must be requested again.
+
+use crate::{
+    Context, HandlerResult,
+    commands::{CommandOutcome, LIST_PAGE_CALLBACK_PREFIX},
+    consts::{LIST_PAGE_SIZE, MAX_MESSAGE_LEN},
+    i18n::{self, Translate},
+    keyboard::{self, PAGE_MARKER},
+    topics::{InTopic, MessageExt},
+};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, LazyLock, Mutex},
+};
+use teloxide::{
+    RequestError,
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup, InputFile, MessageId},
+};
+
+/// Number of paginated lists whose pages are kept in memory.
+const LIST_PAGES_CAPACITY: usize = 100;
+
+/// A list sent a page at a time.
+struct PagedList {
+    pages: Vec<String>,
+    /// Keyboard of the command reply, shown below the navigation row.
+    markup: Option<InlineKeyboardMarkup>,
+}
+
+/// Identifies the message showing a list.
+type ListKey = (ChatId, MessageId);
+
+/// Pages of the most recent lists, keyed by the message showing them.
+static LIST_PAGES: LazyLock<Mutex<VecDeque<(ListKey, PagedList)>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::new()));
+
+/// Length of `text` as counted by Telegram.
+fn text_len(text: &str) -> usize {
+    text.encode_utf16().count()
+}
+
+/// Splits `line` into pieces of at most `max_len` UTF-16 code units, at
+/// character boundaries.
+fn split_line(line: &str, max_len: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut len = 0;
+    for (i, c) in line.char_indices() {
+        if len + c.len_utf16() > max_len {
+            pieces.push(&line[start..i]);
+            start = i;
+            len = 0;
+        }
+        len += c.len_utf16();
+    }
+    pieces.push(&line[start..]);
+    pieces
+}
+
+/// Splits `text` into chunks of at most `max_len` UTF-16 code units,
+/// breaking at line boundaries. Lines longer than `max_len` are broken
+/// wherever needed.
+pub fn split_message(text: &str, max_len: usize) -> Vec<String> {
+    if text_len(text) <= max_len {
+        return vec![text.to_owned()];
+    }
+
+    let mut chunks = Vec::new();
+    let mut chunk = String::new();
+    let mut chunk_len = 0;
+    for piece in text.lines().flat_map(|line| split_line(line, max_len)) {
+        let piece_len = text_len(piece);
+        if chunk_len > 0 && chunk_len + 1 + piece_len > max_len {
+            chunks.push(std::mem::take(&mut chunk));
+            chunk_len = 0;
+        }
+        if chunk_len > 0 {
+            chunk.push('\n');
+            chunk_len += 1;
+        }
+        chunk.push_str(piece);
+        chunk_len += piece_len;
+    }
+    chunks.push(chunk);
+    chunks
+}
+
+/// Groups list `entries` into pages of at most `page_size` entries and
+/// `max_len` UTF-16 code units.
+fn paginate(entries: &[String], page_size: usize, max_len: usize) -> Vec<String> {
+    let mut pages = Vec::new();
+    let mut page: Vec<String> = Vec::new();
+    let mut page_len = 0;
+    for piece in entries
+        .iter()
+        .flat_map(|entry| split_message(entry, max_len))
+    {
+        let piece_len = text_len(&piece);
+        if !page.is_empty() && (page.len() == page_size || page_len + 1 + piece_len > max_len) {
+            pages.push(page.join("\n"));
+            page.clear();
+            page_len = 0;
+        }
+        page_len += piece_len + usize::from(!page.is_empty());
+        page.push(piece);
+    }
+    if !page.is_empty() {
+        pages.push(page.join("\n"));
+    }
+    pages
+}
+
+/// Builds the keyboard of page `page` of a list: the navigation row,
+/// followed by the rows of the command reply keyboard.
+fn page_keyboard(
+    page: usize,
+    total_pages: usize,
+    markup: Option<&InlineKeyboardMarkup>,
+) -> InlineKeyboardMarkup {
+    let mut rows: Vec<Vec<InlineKeyboardButton>> = vec![keyboard::navigation_row(
+        page,
+        total_pages,
+        LIST_PAGE_CALLBACK_PREFIX,
+        &format!("{LIST_PAGE_CALLBACK_PREFIX}__noop__"),
+    )];
+    if let Some(markup) = markup {
+        rows.extend(markup.inline_keyboard.iter().cloned());
+    }
+    InlineKeyboardMarkup::new(rows)
+}
+
+/// Sends `outcome` in reply to `msg`, with the optional `markup` attached
+/// to the last text message sent, followed by its documents if any. Photos
+/// are sent alone, with the markup attached to them.
+///
+/// Replies too long for a single message are split, and lists spanning more
+/// than one page get a navigation row to browse them (see
+/// [`list_page_endpoint`]).
+pub async fn send_outcome(
+    bot: &Bot,
+    msg: &Message,
+    outcome: &CommandOutcome,
+    markup: Option<InlineKeyboardMarkup>,
+) -> Result<(), RequestError> {
+    let (chunks, documents) = match outcome {
+        CommandOutcome::Success(text) | CommandOutcome::Failure(text) => {
+            (split_message(text, MAX_MESSAGE_LEN), &[][..])
+        }
+        CommandOutcome::Documents { message, documents } => {
+            (split_message(message, MAX_MESSAGE_LEN), &documents[..])
+        }
+        CommandOutcome::Photo { caption, photo } => {
+            let file = InputFile::memory(photo.content.clone()).file_name(photo.file_name.clone());
+            let mut send = bot
+                .send_photo(msg.chat.id, file)
+                .caption(caption.clone())
+                .in_topic(msg.topic_id());
+            if let Some(markup) = markup {
+                send = send.reply_markup(markup);
+            }
+            send.await?;
+            return Ok(());
+        }
+        CommandOutcome::List(entries) => {
+            let pages = paginate(entries, LIST_PAGE_SIZE, MAX_MESSAGE_LEN);
+            if pages.len() > 1 {
+                let keyboard = page_keyboard(0, pages.len(), markup.as_ref());
+                let sent = bot
+                    .send_message(msg.chat.id, pages[0].clone())
+                    .in_topic(msg.topic_id())
+                    .reply_markup(keyboard)
+                    .await?;
+
+                let mut lists = LIST_PAGES.lock().expect("Failed to lock LIST_PAGES");
+                if lists.len() == LIST_PAGES_CAPACITY {
+                    lists.pop_front();
+                }
+                lists.push_back(((sent.chat.id, sent.id), PagedList { pages, markup }));
+                return Ok(());
+            }
+            (pages, &[][..])
+        }
+    };
+
+    let last = chunks.len().saturating_sub(1);
+    for (i, chunk) in chunks.into_iter().enumerate() {
+        let mut send = bot
+            .send_message(msg.chat.id, chunk)
+            .in_topic(msg.topic_id());
+        if i == last
+            && let Some(markup) = markup.clone()
+        {
+            send = send.reply_markup(markup);
+        }
+        send.await?;
+    }
+    for document in documents {
+        let file =
+            InputFile::memory(document.content.clone()).file_name(document.file_name.clone());
+        bot.send_document(msg.chat.id, file)
+            .in_topic(msg.topic_id())
+            .await?;
+    }
+    Ok(())
+}
+
+/// Returns `true` if `data` comes from the navigation row of a list.
+pub fn is_list_page_callback(data: &str) -> bool {
+    data.starts_with(LIST_PAGE_CALLBACK_PREFIX)
+}
+
+/// Endpoint for the navigation buttons of lists: edits the message in place
+/// to show the requested page.
+pub async fn list_page_endpoint(
+    bot: Bot,
+    q: CallbackQuery,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    let Some(msg) = q.regular_message() else {
+        let _ = bot.answer_callback_query(q.id.clone()).await;
+        return Ok(());
+    };
+
+    // Blank spacers and the page indicator carry the noop callback.
+    let page = q
+        .data
+        .as_deref()
+        .and_then(|data| data.strip_prefix(LIST_PAGE_CALLBACK_PREFIX))
+        .and_then(|value| value.strip_prefix(PAGE_MARKER))
+        .and_then(|value| value.strip_prefix(':'))
+        .and_then(|page| page.parse::<usize>().ok());
+    let Some(page) = page else {
+        let _ = bot.answer_callback_query(q.id.clone()).await;
+        return Ok(());
+    };
+
+    let found = LIST_PAGES
+        .lock()
+        .expect("Failed to lock LIST_PAGES")
+        .iter()
+        .find(|(key, _)| *key == (msg.chat.id, msg.id))
+        .and_then(|(_, list)| {
+            list.pages.get(page).map(|text| {
+                (
+                    text.clone(),
+                    page_keyboard(page, list.pages.len(), list.markup.as_ref()),
+                )
+            })
+        });
+    let Some((text, keyboard)) = found else {
+        tracing::debug!("List page {page} of message {} is not available", msg.id);
+        bot.answer_callback_query(q.id.clone())
+            .text(i18n::commands::LIST_PAGE_EXPIRED.translate(ctx))
+            .await?;
+        return Ok(());
+    };
+
+    let _ = bot.answer_callback_query(q.id.clone()).await;
+    bot.edit_message_text(msg.chat.id, msg.id, text)
+        .reply_markup(keyboard)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::db,
+        i18n::{self, Translate},
+        tests::{TestBot, helpers},
+        transfer::Transfer,
+    };
+
+    #[test]
+    fn split_message_short() {
+        assert_eq!(split_message("one\ntwo", 10), vec!["one\ntwo"]);
+    }
+
+    #[test]
+    fn split_message_at_lines() {
+        let chunks = split_message("aaa\nbbb\nccc\nddd", 8);
+        assert_eq!(chunks, vec!["aaa\nbbb", "ccc\nddd"]);
+    }
+
+    #[test]
+    fn split_message_long_line() {
+        // "€" is a single UTF-16 code unit, "🚀" takes two
+        let chunks = split_message("ab\n€€€€€🚀🚀", 4);
+        assert_eq!(chunks, vec!["ab", "€€€€", "€🚀", "🚀"]);
+        assert!(chunks.iter().all(|chunk| text_len(chunk) <= 4));
+    }
+
+    #[test]
+    fn paginate_by_size_and_length() {
+        let entries: Vec<String> = (1..=5).map(|i| format!("entry {i}")).collect();
+        assert_eq!(
+            paginate(&entries, 2, 100),
+            vec!["entry 1\nentry 2", "entry 3\nentry 4", "entry 5"]
+        );
+        assert_eq!(
+            paginate(&entries, 10, 16),
+            vec!["entry 1\nentry 2", "entry 3\nentry 4", "entry 5"]
+        );
+    }
+
+    test! { list_transfers_paginated,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        for _ in 0..LIST_PAGE_SIZE + 1 {
+            helpers::transfer(&mut bot, "Alice", "Bob", 1.into()).await;
+        }
+
+        let lines: Vec<String> = Transfer::transfers(db, bot.chat_id())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|transfer| transfer.translate_default())
+            .collect();
+
+        // First page, with the navigation row
+        bot.update("/listtransfers Alice");
+        bot.test_last_message(&lines[..LIST_PAGE_SIZE].join("\n")).await;
+        let sent = bot.last_sent_message().unwrap();
+        let markup = sent.reply_markup().unwrap();
+        assert_eq!(markup.inline_keyboard[0][1].text, "1/2");
+
+        // Second page, edited in place
+        bot.update_callback_on(&format!("{LIST_PAGE_CALLBACK_PREFIX}{PAGE_MARKER}:1"), sent);
+        bot.dispatch().await;
+        assert_eq!(bot.last_edited_message().unwrap(), lines[LIST_PAGE_SIZE]);
+    }
+
+    test! { list_page_expired,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+
+        // Pages of lists sent before a restart are gone
+        bot.update_callback(&format!("{LIST_PAGE_CALLBACK_PREFIX}{PAGE_MARKER}:1"));
+        bot.dispatch().await;
+        assert!(bot.last_edited_message().is_none());
+        assert_eq!(
+            bot.last_callback_answer().unwrap(),
+            i18n::commands::LIST_PAGE_EXPIRED.translate_default()
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    Context,
+    commands::{CommandOutcome, Document},
+    consts::{DATE_FORMAT, LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, TranslateWithArgs},
+    report::{FILE_NAME_PREFIX, Report, html},
+    timezone,
+    topics::MessageExt,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn report(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let (currency, tz) = {
+        let ctx_guard = ctx.lock().expect("Failed to lock context");
+        (ctx_guard.currency.clone(), ctx_guard.timezone.clone())
+    };
+    let report = match Report::db_select(db, msg.ledger_id(), currency, tz.clone()).await {
+        Ok(report) => report,
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::Report);
+        }
+    };
+    let content = html::render(&report, ctx.clone());
+    let date = timezone::to_zoned(report.created_at, &tz)
+        .map(|zoned| zoned.strftime(DATE_FORMAT).to_string())
+        .unwrap_or_default();
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Report of the travel plan sent");
+    Ok(CommandOutcome::Documents {
+        message: i18n::commands::REPORT_OK.translate_with_args(
+            ctx,
+            &hashmap! {
+                i18n::args::EXPENSES_COUNT.into() => report.data.expenses.len().into(),
+            },
+        ),
+        documents: vec![Document {
+            file_name: format!("{FILE_NAME_PREFIX}-{date}.html"),
+            content: content.into_bytes(),
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, TranslateWithArgs},
+        report::FILE_NAME_PREFIX,
+        tests::{TestBot, helpers},
+    };
+    use maplit::hashmap;
+
+    test! { report_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner #food", 60.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "<b>Taxi</b>", 40.into(), "Bob", &["all"]).await;
+        helpers::add_expense(&mut bot, "Gelato #Food", 20.into(), "Bob", &["all"]).await;
+        db.query("UPDATE expense SET occurred_at = d'2026-08-01T12:00:00Z' WHERE number < 3")
+            .await
+            .unwrap();
+        db.query("UPDATE expense SET occurred_at = d'2026-08-02T12:00:00Z' WHERE number = 3")
+            .await
+            .unwrap();
+
+        bot.update("/report");
+        let response = i18n::commands::REPORT_OK.translate_with_args_default(&hashmap! {
+            i18n::args::EXPENSES_COUNT.into() => 3.into(),
+        });
+        bot.dispatch().await;
+        assert!(bot.sent_texts().contains(&response));
+
+        let documents = bot.sent_documents();
+        let [(file_name, html)] = documents.as_slice() else {
+            panic!("expected a single document, got {documents:?}");
+        };
+        assert!(file_name.starts_with(FILE_NAME_PREFIX));
+        assert!(file_name.ends_with(".html"));
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en-US\">"));
+        // Self-contained
+        assert!(!html.contains("<link") && !html.contains("<script") && !html.contains("src="));
+        // Descriptions are escaped
+        assert!(html.contains("<td>&lt;b&gt;Taxi&lt;/b&gt;</td>"));
+        assert!(!html.contains("{{"));
+        // Totals and paid vs consumed
+        assert!(html.contains("<dd>$120.00</dd>"));
+        assert!(html.contains(
+            "<tr><td>Alice</td><td class=\"amount\">$60.00</td>\
+            <td class=\"amount\">$60.00</td><td class=\"amount\">$0.00</td></tr>"
+        ));
+        // Categories, the largest first
+        assert!(html.contains(
+            "<tr><td>#food</td><td class=\"amount\">$80.00</td><td class=\"amount\">66.7</td>"
+        ));
+        assert!(html.contains(
+            "<tr><td>Uncategorized</td><td class=\"amount\">$40.00</td><td class=\"amount\">33.3</td>"
+        ));
+        // Timeline
+        assert!(html.contains("<tr><td>2026-08-01</td><td class=\"amount\">$100.00</td>"));
+        assert!(html.contains("<tr><td>2026-08-02</td><td class=\"amount\">$20.00</td>"));
+        // Settled up, since each traveler paid what they consumed
+        assert!(html.contains("All travelers are settled up with everyone."));
+    }
+
+    test! { report_empty,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "/report");
+        bot.dispatch().await;
+
+        let documents = bot.sent_documents();
+        let [(_, html)] = documents.as_slice() else {
+            panic!("expected a single document, got {documents:?}");
+        };
+        assert!(html.contains("No expenses recorded."));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    Context,
+    backup::Backup,
+    commands::{CommandOutcome, closed_trip_refusal},
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    debt::rebuild_debts,
+    errors::CommandError,
+    i18n::{self, TranslateWithArgs},
+    topics::MessageExt,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+/// Replaces the travel plan and the settings of the chat with the ones in
+/// `backup`, once the `/restore` dialogue is confirmed.
+#[apply(trace_skip_all)]
+pub async fn restore(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    backup: &Backup,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    if let Some(refusal) = closed_trip_refusal(db.clone(), msg, ctx.clone()).await {
+        return Ok(CommandOutcome::Failure(refusal));
+    }
+
+    if let Err(err) = backup.db_restore(db.clone(), msg.ledger_id()).await {
+        tracing::error!("{err}");
+        return Err(CommandError::Restore);
+    }
+    if let Err(err) = rebuild_debts(db, msg.ledger_id()).await {
+        tracing::warn!("{err}");
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!(
+        "Travel plan restored from the backup of {}",
+        backup.created_at
+    );
+    Ok(CommandOutcome::Success(
+        i18n::commands::RESTORE_OK.translate_with_args(
+            ctx,
+            &hashmap! {
+                i18n::args::TRAVELERS_COUNT.into() => backup.travelers.len().into(),
+                i18n::args::EXPENSES_COUNT.into() => backup.expenses.len().into(),
+                i18n::args::TRANSFERS_COUNT.into() => backup.transfers.len().into(),
+            },
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backup::Backup,
+        consts::MAX_DOWNLOAD_SIZE,
+        db::db,
+        dialogues::pending_command_dialogue::{PendingCommandState, restore::RestoreState},
+        i18n::{self, Translate, TranslateWithArgs},
+        tests::{TestBot, helpers},
+    };
+    use maplit::hashmap;
+    use teloxide::dispatching::dialogue::Storage;
+
+    test! { restore_usage,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "/restore");
+        let response = i18n::commands::RESTORE_USAGE.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { restore_file_too_large,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+        bot.update_document("/restore", MAX_DOWNLOAD_SIZE + 1);
+        let response = i18n::commands::FILE_TOO_LARGE.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { restore_invalid_file,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+        bot.update_document("/restore", 1024);
+        let response = i18n::errors::BACKUP_ERROR_INVALID.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { restore_confirm_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 100.into(), "Alice", &["all"]).await;
+        let backup = Backup::db_select(db.clone(), bot.chat_id()).await.unwrap();
+
+        // The travel plan changes after the backup
+        helpers::add_traveler(&mut bot, "Carol").await;
+        helpers::transfer(&mut bot, "Bob", "Alice", 50.into()).await;
+
+        bot.pending_command_storage()
+            .update_dialogue(
+                bot.chat_id(),
+                PendingCommandState::Restore(RestoreState::Confirm(Box::new(backup))),
+            )
+            .await
+            .unwrap();
+        bot.update("yes");
+        let response = i18n::commands::RESTORE_OK.translate_with_args_default(&hashmap! {
+            i18n::args::TRAVELERS_COUNT.into() => 2.into(),
+            i18n::args::EXPENSES_COUNT.into() => 1.into(),
+            i18n::args::TRANSFERS_COUNT.into() => 0.into(),
+        });
+        bot.test_last_message(&response).await;
+
+        let restored = Backup::db_select(db, bot.chat_id()).await.unwrap();
+        assert_eq!(restored.travelers.len(), 2);
+        assert_eq!(restored.expenses.len(), 1);
+        assert!(restored.transfers.is_empty());
+    }
+
+    test! { restore_confirm_deny,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        let backup = Backup::db_select(db, bot.chat_id()).await.unwrap();
+
+        bot.pending_command_storage()
+            .update_dialogue(
+                bot.chat_id(),
+                PendingCommandState::Restore(RestoreState::Confirm(Box::new(backup))),
+            )
+            .await
+            .unwrap();
+        bot.update("no");
+        let response = helpers::cancel_ok_for(i18n::commands::RUNNING_PROCESS_RESTORE);
+        bot.test_last_message(&response).await;
+    }
+}
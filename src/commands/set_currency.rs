@@ -6,6 +6,7 @@ use crate::{
     errors::CommandError,
     i18n::{self, TranslateWithArgs},
     money_wrapper::currency_label,
+    topics::MessageExt,
 };
 use macro_rules_attribute::apply;
 use maplit::hashmap;
@@ -37,7 +38,7 @@ pub async fn set_currency(
     }
 
     // Update chat currency on db
-    let update_res = Chat::db_update_currency(db, msg.chat.id, &currency).await;
+    let update_res = Chat::db_update_currency(db, msg.ledger_id(), &currency).await;
     match update_res {
         Ok(_) => {
             tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -111,10 +112,13 @@ mod tests {
         // 2. Set amount
         bot.update("100");
         bot.dispatch().await;
-        // 3. Set payer
+        // 3. Set date
+        bot.update("today");
+        bot.dispatch().await;
+        // 4. Set payer
         bot.update("Alice");
         bot.dispatch().await;
-        // 4. Split expense
+        // 5. Split expense
         bot.update("all");
         bot.dispatch().await;
 
@@ -5,6 +5,7 @@ use crate::{
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     errors::CommandError,
     i18n::{self, TranslateWithArgs, TryTranslate},
+    topics::MessageExt,
 };
 use macro_rules_attribute::apply;
 use maplit::hashmap;
@@ -42,7 +43,7 @@ pub async fn set_language(
     }
 
     // Update chat language on db
-    let update_res = Chat::db_update_lang(db, msg.chat.id, &langid).await;
+    let update_res = Chat::db_update_lang(db, msg.ledger_id(), &langid).await;
     match update_res {
         Ok(_) => {
             tracing::debug!("{LOG_DEBUG_SUCCESS}");
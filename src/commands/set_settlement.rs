@@ -0,0 +1,137 @@
+use crate::{
+    Context,
+    chat::Chat,
+    commands::CommandOutcome,
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    debt::SettlementMode,
+    errors::CommandError,
+    i18n::{self, Translate, TranslateWithArgs},
+    topics::MessageExt,
+    update_debts,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn set_settlement(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    mode: &str,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+    let chat_id = msg.ledger_id();
+
+    let mode = mode.trim();
+    if mode.is_empty() {
+        return match Chat::db_select_by_id(db, chat_id).await {
+            Ok(chat) => {
+                let current = chat.map(|chat| chat.settlement).unwrap_or_default();
+                tracing::debug!("{LOG_DEBUG_SUCCESS}");
+                Ok(CommandOutcome::Success(
+                    i18n::commands::SET_SETTLEMENT_STATUS.translate_with_args(
+                        ctx,
+                        &hashmap! {i18n::args::MODE.into() => current.as_ref().into()},
+                    ),
+                ))
+            }
+            Err(err) => {
+                tracing::error!("{err}");
+                Err(CommandError::SetSettlement)
+            }
+        };
+    }
+
+    let Ok(mode) = SettlementMode::from_str(mode) else {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::SET_SETTLEMENT_USAGE.translate(ctx),
+        ));
+    };
+
+    if let Err(err) = Chat::db_update_settlement(db.clone(), chat_id, mode).await {
+        tracing::error!("{err}");
+        return Err(CommandError::SetSettlement);
+    }
+    // Settle the current balances again with the new mode
+    if let Err(err) = update_debts(db, chat_id).await {
+        tracing::warn!("{err}");
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Settlement mode set to '{}'", mode.as_ref());
+    Ok(CommandOutcome::Success(
+        i18n::commands::SET_SETTLEMENT_OK.translate_with_args(
+            ctx,
+            &hashmap! {i18n::args::MODE.into() => mode.as_ref().into()},
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        balance::Balance,
+        db::db,
+        i18n::{self, Translate, TranslateWithArgs},
+        tests::{TestBot, helpers},
+    };
+    use maplit::hashmap;
+
+    test! { set_settlement_status,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/setsettlement");
+        let response = i18n::commands::SET_SETTLEMENT_STATUS.translate_with_args_default(
+            &hashmap! {i18n::args::MODE.into() => "exact".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        bot.update("/setsettlement Greedy");
+        let response = i18n::commands::SET_SETTLEMENT_OK.translate_with_args_default(
+            &hashmap! {i18n::args::MODE.into() => "greedy".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        bot.update("/setsettlement");
+        let response = i18n::commands::SET_SETTLEMENT_STATUS.translate_with_args_default(
+            &hashmap! {i18n::args::MODE.into() => "greedy".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        bot.update("/setsettlement fastest");
+        let response = i18n::commands::SET_SETTLEMENT_USAGE.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { set_settlement_fewer_transfers,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db.clone(), "/setsettlement greedy");
+        bot.dispatch().await;
+        for name in ["Alice", "Bob", "Charlie", "Dave", "Eve"] {
+            helpers::add_traveler(&mut bot, name).await;
+        }
+        // Nets: Alice +5, Bob +4, Charlie -4, Dave -3, Eve -2. Greedy matches
+        // Alice with Charlie first and needs 4 transfers, while {Bob, Charlie}
+        // and {Alice, Dave, Eve} settle in 3
+        helpers::add_expense(&mut bot, "Tickets", 5.into(), "Alice", &["Dave:3; Eve:2", "end"])
+            .await;
+        helpers::add_expense(&mut bot, "Museum", 4.into(), "Bob", &["Charlie:4", "end"]).await;
+
+        let greedy = Balance::balances(db.clone(), bot.chat_id()).await.unwrap();
+        assert_eq!(greedy.len(), 4);
+
+        bot.update("/setsettlement exact");
+        bot.dispatch().await;
+        let exact = Balance::balances(db.clone(), bot.chat_id()).await.unwrap();
+        assert_eq!(exact.len(), 3);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{
+    Context,
+    chat::Chat,
+    commands::CommandOutcome,
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    debt::SimplificationMode,
+    errors::CommandError,
+    i18n::{self, Translate, TranslateWithArgs},
+    topics::MessageExt,
+    update_debts,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn set_simplification(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    mode: &str,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+    let chat_id = msg.ledger_id();
+
+    let mode = mode.trim();
+    if mode.is_empty() {
+        return match Chat::db_select_by_id(db, chat_id).await {
+            Ok(chat) => {
+                let current = chat.map(|chat| chat.simplification).unwrap_or_default();
+                tracing::debug!("{LOG_DEBUG_SUCCESS}");
+                Ok(CommandOutcome::Success(
+                    i18n::commands::SET_SIMPLIFICATION_STATUS.translate_with_args(
+                        ctx,
+                        &hashmap! {i18n::args::MODE.into() => current.as_ref().into()},
+                    ),
+                ))
+            }
+            Err(err) => {
+                tracing::error!("{err}");
+                Err(CommandError::SetSimplification)
+            }
+        };
+    }
+
+    let Ok(mode) = SimplificationMode::from_str(mode) else {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::SET_SIMPLIFICATION_USAGE.translate(ctx),
+        ));
+    };
+
+    if let Err(err) = Chat::db_update_simplification(db.clone(), chat_id, mode).await {
+        tracing::error!("{err}");
+        return Err(CommandError::SetSimplification);
+    }
+    // Simplify the current debts again with the new mode
+    if let Err(err) = update_debts(db, chat_id).await {
+        tracing::warn!("{err}");
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Debt simplification mode set to '{}'", mode.as_ref());
+    Ok(CommandOutcome::Success(
+        i18n::commands::SET_SIMPLIFICATION_OK.translate_with_args(
+            ctx,
+            &hashmap! {i18n::args::MODE.into() => mode.as_ref().into()},
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        balance::Balance,
+        db::db,
+        i18n::{self, Translate, TranslateWithArgs},
+        tests::{TestBot, helpers},
+    };
+    use maplit::hashmap;
+
+    test! { set_simplification_status,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/setsimplification");
+        let response = i18n::commands::SET_SIMPLIFICATION_STATUS.translate_with_args_default(
+            &hashmap! {i18n::args::MODE.into() => "full".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        bot.update("/setsimplification Pairwise");
+        let response = i18n::commands::SET_SIMPLIFICATION_OK.translate_with_args_default(
+            &hashmap! {i18n::args::MODE.into() => "pairwise".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        bot.update("/setsimplification");
+        let response = i18n::commands::SET_SIMPLIFICATION_STATUS.translate_with_args_default(
+            &hashmap! {i18n::args::MODE.into() => "pairwise".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        bot.update("/setsimplification some");
+        let response = i18n::commands::SET_SIMPLIFICATION_USAGE.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { set_simplification_modes,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db.clone(), "");
+        for name in ["Alice", "Bob", "Carol"] {
+            helpers::add_traveler(&mut bot, name).await;
+        }
+        // Alice owes Bob 10, while Bob owes Alice 4 and Carol 6
+        helpers::add_expense(&mut bot, "Dinner", 10.into(), "Bob", &["Alice:10", "end"]).await;
+        helpers::add_expense(&mut bot, "Lunch", 4.into(), "Alice", &["Bob:4", "end"]).await;
+        helpers::add_expense(&mut bot, "Taxi", 6.into(), "Carol", &["Bob:6", "end"]).await;
+
+        let pairs = |balances: Vec<Balance>| {
+            balances
+                .into_iter()
+                .map(|b| (b.debtor_name.to_string(), b.creditor_name.to_string(), b.debt))
+                .collect::<Vec<_>>()
+        };
+
+        // Full: Alice pays Carol directly, although Carol never paid for her
+        let full = pairs(Balance::balances(db.clone(), bot.chat_id()).await.unwrap());
+        assert_eq!(full, vec![("Alice".into(), "Carol".into(), 6.into())]);
+
+        bot.update("/setsimplification pairwise");
+        bot.dispatch().await;
+        let pairwise = pairs(Balance::balances(db.clone(), bot.chat_id()).await.unwrap());
+        assert_eq!(
+            pairwise,
+            vec![
+                ("Alice".into(), "Bob".into(), 6.into()),
+                ("Bob".into(), "Carol".into(), 6.into()),
+            ]
+        );
+
+        bot.update("/setsimplification none");
+        bot.dispatch().await;
+        let none = pairs(Balance::balances(db.clone(), bot.chat_id()).await.unwrap());
+        assert_eq!(
+            none,
+            vec![
+                ("Alice".into(), "Bob".into(), 10.into()),
+                ("Bob".into(), "Carol".into(), 6.into()),
+                ("Bob".into(), "Alice".into(), 4.into()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    Context,
+    chat::Chat,
+    commands::CommandOutcome,
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, TranslateWithArgs},
+    timezone,
+    topics::MessageExt,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn set_timezone(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    timezone: &str,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+    let timezone = timezone.trim();
+
+    // Reject names that are not in the IANA time zone database
+    let Some(tz) = timezone::find(timezone) else {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::SET_TIMEZONE_NOT_AVAILABLE.translate_with_args(
+                ctx,
+                &hashmap! {i18n::args::TIMEZONE.into() => timezone.to_owned().into()},
+            ),
+        ));
+    };
+    // Store the canonical name, whatever the case used by the user
+    let name = timezone::name(&tz).to_owned();
+
+    // Update chat time zone on db
+    let update_res = Chat::db_update_timezone(db, msg.ledger_id(), &name).await;
+    match update_res {
+        Ok(_) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            tracing::info!("Time zone set to '{name}'");
+            {
+                let mut ctx_guard = ctx.lock().expect("Failed to lock context");
+                ctx_guard.timezone = tz;
+            }
+
+            Ok(CommandOutcome::Success(
+                i18n::commands::SET_TIMEZONE_OK.translate_with_args(
+                    ctx.clone(),
+                    &hashmap! {i18n::args::TIMEZONE.into() => name.into()},
+                ),
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            Err(CommandError::SetTimezone { timezone: name })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        chat::Chat,
+        db::db,
+        i18n::{self, TranslateWithArgs},
+        tests::{TestBot, helpers},
+        timezone,
+    };
+    use maplit::hashmap;
+
+    test! { set_timezone_ok,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db.clone(), "/settimezone asia/tokyo");
+        let response = i18n::commands::SET_TIMEZONE_OK.translate_with_args_default(
+            &hashmap! {i18n::args::TIMEZONE.into() => "Asia/Tokyo".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        let chat = Chat::db_select_by_id(db, bot.chat_id()).await.unwrap().unwrap();
+        assert_eq!(chat.timezone, "Asia/Tokyo");
+        let tz = bot.context().lock().unwrap().timezone.clone();
+        assert_eq!(timezone::name(&tz), "Asia/Tokyo");
+    }
+
+    test! { set_timezone_not_available,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/settimezone Mars/Olympus_Mons");
+        let response = i18n::commands::SET_TIMEZONE_NOT_AVAILABLE.translate_with_args_default(
+            &hashmap! {i18n::args::TIMEZONE.into() => "Mars/Olympus_Mons".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { set_timezone_format_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_expense(&mut bot, "Dinner", 60.into(), "Alice", &["all"]).await;
+        // Late in the evening in UTC, already the next day in Tokyo
+        db.query("UPDATE expense SET occurred_at = d'2026-10-19T20:00:00Z'")
+            .await
+            .unwrap();
+
+        bot.update("/listexpenses");
+        let utc = bot.dispatch_and_last_message().await.unwrap();
+        assert!(utc.contains("10/19/26"));
+
+        bot.update("/settimezone Asia/Tokyo");
+        bot.dispatch().await;
+        bot.update("/listexpenses");
+        let tokyo = bot.dispatch_and_last_message().await.unwrap();
+        assert!(tokyo.contains("10/20/26"));
+    }
+}
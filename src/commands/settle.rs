@@ -0,0 +1,312 @@
+use crate::{
+    Context,
+    balance::Balance,
+    commands::{
+        CommandOutcome, SETTLE_ALL_CALLBACK, SETTLE_CALLBACK_PREFIX, inline_keyboards,
+        inline_keyboards::TRAVELER_NUMBERS_SEP, unmet_preferences,
+    },
+    consts::{ALL_KWORD, LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, Translate, TranslateWithArgs},
+    money_wrapper::MoneyWrapper,
+    topics::MessageExt,
+    transferred_to::TransferredTo,
+    traveler::{Name, Traveler},
+    update_debts,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use rust_decimal::Decimal;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::{
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup},
+};
+use tracing::Level;
+
+/// A debt still to be paid, rounded to the precision of the chat currency.
+struct Payment {
+    debtor: Name,
+    creditor: Name,
+    amount: Decimal,
+}
+
+#[apply(trace_command_db)]
+pub async fn settle(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    args: &str,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let args = args.trim();
+    if !args.is_empty() && !args.eq_ignore_ascii_case(ALL_KWORD) {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::SETTLE_USAGE.translate(ctx),
+        ));
+    }
+
+    let payments = match payments(db.clone(), msg, ctx.clone()).await {
+        Ok(payments) => payments,
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::Settle);
+        }
+    };
+    if payments.is_empty() {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Success(
+            i18n::commands::SHOW_BALANCES_SETTLED_UP.translate(ctx),
+        ));
+    }
+
+    // Plain `/settle`: show the settlement plan
+    if args.is_empty() {
+        let currency = ctx.lock().expect("Failed to lock context").currency.clone();
+        let plan = payments
+            .into_iter()
+            .map(|payment| {
+                i18n::commands::SHOW_BALANCES_OK.translate_with_args(
+                    ctx.clone(),
+                    &hashmap! {
+                        i18n::args::DEBTOR.into() => payment.debtor.into(),
+                        i18n::args::DEBT.into() =>
+                            MoneyWrapper::new(payment.amount, &currency).to_string().into(),
+                        i18n::args::CREDITOR.into() => payment.creditor.into(),
+                    },
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let plan = match unmet_preferences(db, msg.ledger_id(), ctx.clone()).await {
+            Ok(Some(unmet)) => format!("{plan}\n\n{unmet}"),
+            Ok(None) => plan,
+            Err(err) => {
+                tracing::error!("{err}");
+                return Err(CommandError::Settle);
+            }
+        };
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        tracing::info!("Settlement plan shown");
+        return Ok(CommandOutcome::Success(format!(
+            "{plan}\n\n{}",
+            i18n::commands::SETTLE_PLAN_HINT.translate(ctx)
+        )));
+    }
+
+    // `/settle all`: record every payment of the plan
+    let travelers = match Traveler::db_select(db.clone(), msg.ledger_id()).await {
+        Ok(travelers) => travelers
+            .into_iter()
+            .map(|traveler| (traveler.name.clone(), traveler))
+            .collect::<HashMap<_, _>>(),
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::Settle);
+        }
+    };
+    let count = payments.len();
+    let mut transfers = Vec::with_capacity(count);
+    for payment in payments {
+        let (Some(debtor), Some(creditor)) = (
+            travelers.get(&payment.debtor),
+            travelers.get(&payment.creditor),
+        ) else {
+            tracing::error!(
+                "Travelers {} or {} not found",
+                payment.debtor,
+                payment.creditor
+            );
+            return Err(CommandError::Settle);
+        };
+        transfers.push((payment.amount, debtor.id.clone(), creditor.id.clone()));
+    }
+    if let Err(err) = TransferredTo::db_relate_all(db.clone(), transfers).await {
+        tracing::error!("{err}");
+        return Err(CommandError::Settle);
+    }
+    if let Err(err) = update_debts(db, msg.ledger_id()).await {
+        tracing::warn!("{err}");
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Settled up with {count} transfers");
+    Ok(CommandOutcome::Success(
+        i18n::commands::SETTLE_ALL_OK
+            .translate_with_args(ctx, &hashmap! {i18n::args::COUNT.into() => count.into()}),
+    ))
+}
+
+/// Returns the debts of the active trip that don't round to zero.
+async fn payments(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<Vec<Payment>, surrealdb::Error> {
+    let currency = ctx.lock().expect("Failed to lock context").currency.clone();
+    let balances = Balance::balances(db, msg.ledger_id()).await?;
+    Ok(balances
+        .into_iter()
+        .map(|balance| Payment {
+            debtor: balance.debtor_name,
+            creditor: balance.creditor_name,
+            amount: MoneyWrapper::new(balance.debt, &currency).round_value(),
+        })
+        .filter(|payment| payment.amount > Decimal::ZERO)
+        .collect())
+}
+
+/// Builds the keyboard of `/settle`: a "Paid ✓" button per payment of the
+/// settlement plan and a final "Settle all" button.
+///
+/// Returns `None` if everyone is settled up or if the DB query fails.
+pub async fn settle_keyboard(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    ctx: Arc<Mutex<Context>>,
+) -> Option<InlineKeyboardMarkup> {
+    let payments = payments(db.clone(), msg, ctx.clone()).await.ok()?;
+    if payments.is_empty() {
+        return None;
+    }
+    let numbers = Traveler::db_select(db, msg.ledger_id())
+        .await
+        .ok()?
+        .into_iter()
+        .map(|traveler| (traveler.name, traveler.number))
+        .collect::<HashMap<_, _>>();
+
+    let mut buttons = payments
+        .into_iter()
+        .filter_map(|payment| {
+            let debtor = numbers.get(&payment.debtor)?;
+            let creditor = numbers.get(&payment.creditor)?;
+            Some(InlineKeyboardButton::callback(
+                i18n::labels::PAID_BUTTON.translate_with_args(
+                    ctx.clone(),
+                    &hashmap! {
+                        i18n::args::DEBTOR.into() => payment.debtor.into(),
+                        i18n::args::CREDITOR.into() => payment.creditor.into(),
+                    },
+                ),
+                format!("{SETTLE_CALLBACK_PREFIX}{debtor}{TRAVELER_NUMBERS_SEP}{creditor}"),
+            ))
+        })
+        .collect::<Vec<_>>();
+    buttons.push(InlineKeyboardButton::callback(
+        i18n::labels::SETTLE_ALL_BUTTON.translate(ctx),
+        SETTLE_ALL_CALLBACK,
+    ));
+    Some(inline_keyboards::buttons_keyboard(buttons, 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, Translate, TranslateWithArgs},
+        money_wrapper::MoneyWrapper,
+        tests::{TestBot, helpers},
+        transferred_to::TransferredTo,
+    };
+    use maplit::hashmap;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    test! { settle_plan,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_traveler(&mut bot, "Charlie").await;
+        helpers::add_expense(&mut bot, "Dinner", 100.into(), "Alice", &["all"]).await;
+
+        bot.update("/settle");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        let ctx = bot.context().clone();
+        let currency = ctx.lock().expect("Failed to lock context").currency.clone();
+        let debt = MoneyWrapper::new(Decimal::from_str("33.33").unwrap(), &currency).to_string();
+        for debtor in ["Bob", "Charlie"] {
+            let line = i18n::commands::SHOW_BALANCES_OK.translate_with_args_default(
+                &hashmap! {
+                    i18n::args::DEBTOR.into() => debtor.into(),
+                    i18n::args::DEBT.into() => debt.clone().into(),
+                    i18n::args::CREDITOR.into() => "Alice".into(),
+                },
+            );
+            assert!(response.contains(&line), "{response}");
+        }
+    }
+
+    test! { settle_paid_callback,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_traveler(&mut bot, "Charlie").await;
+        helpers::add_expense(&mut bot, "Dinner", 90.into(), "Alice", &["all"]).await;
+
+        // Bob (#2) pays Alice (#1)
+        bot.update_callback("settle:2:1");
+        let response = i18n::commands::TRANSFER_OK.translate_default();
+        bot.test_last_message(&response).await;
+
+        bot.update("/showbalances Bob");
+        let response = i18n::commands::SHOW_BALANCES_TRAVELER_SETTLED_UP
+            .translate_with_args_default(&hashmap! {i18n::args::NAME.into() => "Bob".into()});
+        bot.test_last_message(&response).await;
+
+        // Tapping again doesn't pay twice
+        bot.update_callback("settle:2:1");
+        let response = i18n::commands::TRANSFER_NOTHING_OWED.translate_with_args_default(
+            &hashmap! {
+                i18n::args::SENDER.into() => "Bob".into(),
+                i18n::args::RECEIVER.into() => "Alice".into(),
+            },
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { settle_all,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+        let chat_id = bot.chat_id();
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_traveler(&mut bot, "Charlie").await;
+        helpers::add_expense(&mut bot, "Dinner", 100.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "Taxi", 20.into(), "Bob", &["all"]).await;
+
+        bot.update_callback("settleall:");
+        let response = i18n::commands::SETTLE_ALL_OK
+            .translate_with_args_default(&hashmap! {i18n::args::COUNT.into() => 2.into()});
+        bot.test_last_message(&response).await;
+
+        // The transfers are numbered in order
+        for number in 1..=2 {
+            let count = TransferredTo::db_count(db.clone(), chat_id, number).await.unwrap();
+            assert_eq!(count.map(|count| *count), Some(1));
+        }
+
+        bot.update("/settle");
+        let response = i18n::commands::SHOW_BALANCES_SETTLED_UP.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { settle_usage,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "/settle everything");
+
+        let response = i18n::commands::SETTLE_USAGE.translate_default();
+        bot.test_last_message(&response).await;
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
     Context,
     balance::Balance,
+    chat::Chat,
+    commands::unmet_preferences,
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     errors::CommandError,
     i18n::{
@@ -8,6 +10,7 @@ use crate::{
         args::{TRAVELER_IS_CASE_CREDITOR, TRAVELER_IS_CASE_DEBTOR},
     },
     money_wrapper::MoneyWrapper,
+    topics::MessageExt,
     traveler::{Name, Traveler},
 };
 use macro_rules_attribute::apply;
@@ -31,7 +34,7 @@ pub async fn show_balances(
             // Look up the traveler so we can use the canonical name (as
             // stored in the database) in the response and in any further
             // comparisons.
-            let select_res = Traveler::db_select_by_name(db.clone(), msg.chat.id, name).await;
+            let select_res = Traveler::db_select_by_name(db.clone(), msg.ledger_id(), name).await;
             match select_res {
                 Ok(Some(traveler)) => {
                     show_balances_with_name(db, msg, traveler.name, ctx.clone()).await
@@ -78,7 +81,7 @@ async fn show_balances_no_name(
     msg: &Message,
     ctx: Arc<Mutex<Context>>,
 ) -> Result<String, surrealdb::Error> {
-    let list_res = Balance::balances(db, msg.chat.id).await;
+    let list_res = Balance::balances(db.clone(), msg.ledger_id()).await;
     match list_res {
         Ok(balances) => {
             let currency = ctx.lock().expect("Failed to lock context").currency.clone();
@@ -111,7 +114,13 @@ async fn show_balances_no_name(
                 .collect::<Vec<_>>();
 
             let reply = if any_nonzero && !formatted_balances.is_empty() {
-                formatted_balances.join("\n")
+                let mut reply = formatted_balances.join("\n");
+                if let Some(unmet) =
+                    unmet_preferences(db.clone(), msg.ledger_id(), ctx.clone()).await?
+                {
+                    reply = format!("{reply}\n\n{unmet}");
+                }
+                format!("{reply}\n\n{}", simplification_mode(db, msg, ctx).await?)
             } else {
                 // If there are no balances or all are zero after rounding, show a special message
                 i18n::commands::SHOW_BALANCES_SETTLED_UP.translate(ctx)
@@ -129,7 +138,7 @@ pub async fn show_balances_with_name(
     ctx: Arc<Mutex<Context>>,
 ) -> Result<String, surrealdb::Error> {
     // Retrieve balances from db
-    let list_res = Balance::balances_by_name(db, msg.chat.id, name.to_owned()).await;
+    let list_res = Balance::balances_by_name(db.clone(), msg.ledger_id(), name.to_owned()).await;
     match list_res {
         Ok(balances) => {
             let currency = ctx.lock().expect("Failed to lock context").currency.clone();
@@ -163,7 +172,11 @@ pub async fn show_balances_with_name(
                 .collect::<Vec<_>>();
 
             let reply = if any_nonzero && !formatted_balances.is_empty() {
-                formatted_balances.join("\n")
+                format!(
+                    "{}\n\n{}",
+                    formatted_balances.join("\n"),
+                    simplification_mode(db, msg, ctx).await?
+                )
             } else {
                 // If there are no balances or all are zero after rounding, show a special message
                 i18n::commands::SHOW_BALANCES_TRAVELER_SETTLED_UP
@@ -175,6 +188,22 @@ pub async fn show_balances_with_name(
     }
 }
 
+/// Tells which debt simplification mode the balances were computed with.
+async fn simplification_mode(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<String, surrealdb::Error> {
+    let mode = Chat::db_select_by_id(db, msg.ledger_id())
+        .await?
+        .map(|chat| chat.simplification)
+        .unwrap_or_default();
+    Ok(i18n::commands::SHOW_BALANCES_MODE.translate_with_args(
+        ctx,
+        &hashmap! {i18n::args::MODE.into() => mode.as_ref().into()},
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -193,6 +222,15 @@ mod tests {
     use maplit::hashmap;
     use rust_decimal::Decimal;
 
+    /// Appends the line telling the debt simplification mode to `balances`.
+    fn with_mode(balances: &str, mode: &str) -> String {
+        format!(
+            "{balances}\n\n{}",
+            i18n::commands::SHOW_BALANCES_MODE
+                .translate_with_args_default(&hashmap! {i18n::args::MODE.into() => mode.into()})
+        )
+    }
+
     test! { show_balances_no_name,
         let db = db().await;
         let mut bot = TestBot::new(db.clone(), "");
@@ -304,7 +342,7 @@ mod tests {
             )
             .collect::<Vec<_>>()
             .join("\n");
-        bot.test_last_message(&response).await;
+        bot.test_last_message(&with_mode(&response, "full")).await;
     }
 
     test! { show_balances_settled_up,
@@ -470,7 +508,7 @@ mod tests {
             )
             .collect::<Vec<_>>()
             .join("\n");
-        bot.test_last_message(&response).await;
+        bot.test_last_message(&with_mode(&response, "full")).await;
 
         // Show Bob balances
         let name = "Bob";
@@ -506,7 +544,7 @@ mod tests {
             )
             .collect::<Vec<_>>()
             .join("\n");
-        bot.test_last_message(&response).await;
+        bot.test_last_message(&with_mode(&response, "full")).await;
     }
 
     test! { show_balances_traveler_settled_up,
@@ -600,4 +638,22 @@ mod tests {
             .translate_with_args_default(&hashmap! {i18n::args::NAME.into() => "Alice".into()});
         assert_ne!(last, settled);
     }
+
+    test! { show_balances_simplification_mode,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "/setsimplification none");
+        bot.dispatch().await;
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 10.into(), "Alice", &["Bob", "end"]).await;
+
+        bot.update("/showbalances");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        assert!(response.ends_with(&with_mode("", "none")), "{response}");
+
+        bot.update("/showbalances Bob");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        assert!(response.ends_with(&with_mode("", "none")), "{response}");
+    }
 }
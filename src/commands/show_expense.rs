@@ -6,6 +6,7 @@ use crate::{
     expense::Expense,
     expense_details::ExpenseDetails,
     i18n::{self, Translate, TranslateWithArgs},
+    topics::MessageExt,
 };
 use macro_rules_attribute::apply;
 use maplit::hashmap;
@@ -24,11 +25,11 @@ pub async fn show_expense(
     tracing::debug!("{LOG_DEBUG_START}");
 
     // Check if expense exists on db
-    let count_res = Expense::db_count_by_number(db.clone(), msg.chat.id, number).await;
+    let count_res = Expense::db_count_by_number(db.clone(), msg.ledger_id(), number).await;
     match count_res {
         Ok(Some(count)) if *count > 0 => {
             // Retrieve expense details from db
-            let select_res = ExpenseDetails::expense_details(db, msg.chat.id, number).await;
+            let select_res = ExpenseDetails::expense_details(db, msg.ledger_id(), number).await;
             match select_res {
                 Ok(Some(expense_details)) => {
                     let reply = expense_details.translate(ctx.clone());
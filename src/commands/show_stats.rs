@@ -1,30 +1,99 @@
 use crate::{
     Context,
-    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    commands::{CommandOutcome, SHOW_STATS_CALLBACK_PREFIX, inline_keyboards},
+    consts::{DATE_FORMAT, LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     errors::CommandError,
-    i18n::Translate,
-    stats::Stats,
+    i18n::{self, Translate, TranslateWithArgs},
+    money_wrapper::MoneyWrapper,
+    stats::{Period, Stats, StatsSection, TimeStats},
+    topics::MessageExt,
 };
+use jiff::tz::TimeZone;
 use macro_rules_attribute::apply;
-use std::sync::{Arc, Mutex};
+use maplit::hashmap;
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use strum::IntoEnumIterator;
 use surrealdb::{Surreal, engine::any::Any};
-use teloxide::prelude::*;
+use teloxide::{
+    prelude::*,
+    types::{InlineKeyboardButton, InlineKeyboardMarkup},
+};
 use tracing::Level;
 
 #[apply(trace_command_db)]
 pub async fn show_stats(
     db: Arc<Surreal<Any>>,
     msg: &Message,
+    section: &str,
     ctx: Arc<Mutex<Context>>,
-) -> Result<String, CommandError> {
+) -> Result<CommandOutcome, CommandError> {
     tracing::debug!("{LOG_DEBUG_START}");
-    let stats_res = Stats::stats(db, msg.chat.id).await;
-    match stats_res {
+
+    let section = section.trim();
+    let section = if section.is_empty() {
+        StatsSection::Overview
+    } else if let Ok(section) = StatsSection::from_str(section) {
+        section
+    } else {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        let sections = StatsSection::iter()
+            .map(|section| section.as_ref().to_owned())
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::SHOW_STATS_USAGE.translate_with_args(
+                ctx,
+                &hashmap! {i18n::args::SECTIONS.into() => sections.into()},
+            ),
+        ));
+    };
+
+    let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
+    let render: fn(&TimeStats, Arc<Mutex<Context>>) -> String = match section {
+        StatsSection::Overview => return overview(db, msg, &tz, ctx).await,
+        StatsSection::Days => days,
+        StatsSection::Weeks => weeks,
+        StatsSection::Weekdays => weekdays,
+        StatsSection::Hours => hours,
+        StatsSection::Travelers => travelers,
+    };
+
+    let time_stats = match TimeStats::db_select(db, msg.ledger_id(), &tz).await {
+        Ok(time_stats) => time_stats,
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::ShowStats);
+        }
+    };
+    if time_stats.is_empty() {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Success(
+            i18n::commands::SHOW_STATS_NO_EXPENSES.translate(ctx),
+        ));
+    }
+
+    let reply = render(&time_stats, ctx);
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Stats by {} retrieved", section.as_ref());
+    Ok(CommandOutcome::Success(reply))
+}
+
+/// Sends the statistics computed by the database.
+async fn overview(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    tz: &TimeZone,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    match Stats::stats(db, msg.ledger_id(), tz).await {
         Ok(Some(stats)) => {
             let reply = stats.translate(ctx);
             tracing::debug!("{LOG_DEBUG_SUCCESS}");
             tracing::info!("Stats retrieved");
-            Ok(reply)
+            Ok(CommandOutcome::Success(reply))
         }
         Ok(_) => {
             tracing::warn!("Couldn't retrieve stats, no data found.");
@@ -37,14 +106,152 @@ pub async fn show_stats(
     }
 }
 
+/// Returns the buttons to show the sections of `/showstats` other than the
+/// overview.
+pub fn show_stats_keyboard(ctx: Arc<Mutex<Context>>) -> InlineKeyboardMarkup {
+    let buttons = StatsSection::iter()
+        .filter(|section| *section != StatsSection::Overview)
+        .map(|section| {
+            InlineKeyboardButton::callback(
+                i18n::labels::STATS_SECTION_BUTTON.translate_with_args(
+                    ctx.clone(),
+                    &hashmap! {i18n::args::SECTION.into() => section.as_ref().to_owned().into()},
+                ),
+                format!("{SHOW_STATS_CALLBACK_PREFIX}{}", section.as_ref()),
+            )
+        })
+        .collect();
+    inline_keyboards::buttons_keyboard(buttons, 3)
+}
+
+/// Formats one line per period, labelled by `label`.
+fn periods<T>(
+    periods: impl IntoIterator<Item = (T, Period)>,
+    label: impl Fn(T) -> String,
+    ctx: Arc<Mutex<Context>>,
+) -> String {
+    periods
+        .into_iter()
+        .map(|(key, period)| {
+            i18n::format::FORMAT_STATS_PERIOD.translate_with_args(
+                ctx.clone(),
+                &hashmap! {
+                    i18n::args::PERIOD.into() => label(key).into(),
+                    i18n::args::AMOUNT.into() =>
+                        MoneyWrapper::new_with_context(period.total, ctx.clone()).to_string().into(),
+                    i18n::args::COUNT.into() => period.count.into(),
+                },
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn days(time_stats: &TimeStats, ctx: Arc<Mutex<Context>>) -> String {
+    let lines = periods(
+        time_stats.days(),
+        |date| date.strftime(DATE_FORMAT).to_string(),
+        ctx.clone(),
+    );
+    let (date, busiest) = time_stats.busiest_day().unwrap_or_default();
+    i18n::format::FORMAT_STATS_DAYS.translate_with_args(
+        ctx.clone(),
+        &hashmap! {
+            i18n::args::PERIODS.into() => lines.into(),
+            i18n::args::DATE.into() => date.strftime(DATE_FORMAT).to_string().into(),
+            i18n::args::AMOUNT.into() =>
+                MoneyWrapper::new_with_context(busiest.total, ctx).to_string().into(),
+        },
+    )
+}
+
+fn weeks(time_stats: &TimeStats, ctx: Arc<Mutex<Context>>) -> String {
+    let lines = periods(
+        time_stats.weeks(),
+        |date| {
+            i18n::format::FORMAT_STATS_WEEK.translate_with_args(
+                ctx.clone(),
+                &hashmap! {
+                    i18n::args::DATE.into() => date.strftime(DATE_FORMAT).to_string().into(),
+                },
+            )
+        },
+        ctx.clone(),
+    );
+    i18n::format::FORMAT_STATS_WEEKS
+        .translate_with_args(ctx, &hashmap! {i18n::args::PERIODS.into() => lines.into()})
+}
+
+fn weekdays(time_stats: &TimeStats, ctx: Arc<Mutex<Context>>) -> String {
+    let lines = periods(
+        (1..=7).zip(time_stats.weekdays()),
+        |weekday: u8| {
+            i18n::format::FORMAT_WEEKDAY.translate_with_args(
+                ctx.clone(),
+                &hashmap! {i18n::args::WEEKDAY.into() => weekday.into()},
+            )
+        },
+        ctx.clone(),
+    );
+    i18n::format::FORMAT_STATS_WEEKDAYS
+        .translate_with_args(ctx, &hashmap! {i18n::args::PERIODS.into() => lines.into()})
+}
+
+fn hours(time_stats: &TimeStats, ctx: Arc<Mutex<Context>>) -> String {
+    let lines = periods(
+        (0..24)
+            .zip(time_stats.hours())
+            .filter(|(_, period)| period.count > 0),
+        |hour: u8| format!("{hour:02}:00"),
+        ctx.clone(),
+    );
+    i18n::format::FORMAT_STATS_HOURS
+        .translate_with_args(ctx, &hashmap! {i18n::args::PERIODS.into() => lines.into()})
+}
+
+fn travelers(time_stats: &TimeStats, ctx: Arc<Mutex<Context>>) -> String {
+    let trends = time_stats
+        .traveler_trends()
+        .into_iter()
+        .map(|(name, amounts)| {
+            let sum: rust_decimal::Decimal = amounts.iter().sum();
+            let amounts = amounts
+                .into_iter()
+                .map(|amount| MoneyWrapper::new_with_context(amount, ctx.clone()).to_string())
+                .collect::<Vec<_>>()
+                .join(" → ");
+            i18n::format::FORMAT_STATS_TREND.translate_with_args(
+                ctx.clone(),
+                &hashmap! {
+                    i18n::args::TRAVELER_NAME.into() => name.to_string().into(),
+                    i18n::args::AMOUNTS.into() => amounts.into(),
+                    i18n::args::SUM.into() =>
+                        MoneyWrapper::new_with_context(sum, ctx.clone()).to_string().into(),
+                },
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    i18n::format::FORMAT_STATS_TRAVELERS.translate_with_args(
+        ctx,
+        &hashmap! {
+            i18n::args::WEEKS.into() => time_stats.weeks().len().into(),
+            i18n::args::TRENDS.into() => trends.into(),
+        },
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         db::db,
-        i18n::Translate,
-        stats::Stats,
+        i18n::{self, Translate, TranslateWithArgs},
+        money_wrapper::MoneyWrapper,
+        stats::{ExpenseStats, Stats},
         tests::{TestBot, helpers},
     };
+    use jiff::tz::TimeZone;
+    use maplit::hashmap;
     use rust_decimal::Decimal;
     use std::str::FromStr;
 
@@ -91,10 +298,31 @@ mod tests {
 
         // Show stats
         bot.update("/showstats");
-        let response = Stats::stats(db, bot.chat_id()).await.unwrap().unwrap().translate_default();
+        let response = Stats::stats(db, bot.chat_id(), &TimeZone::UTC).await.unwrap().unwrap().translate_default();
         bot.test_last_message(&response).await;
     }
 
+    test! { show_stats_backdated_expense,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_expense(&mut bot, "Dinner", 90.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "Hotel", 300.into(), "Alice", &["all"]).await;
+
+        // The hotel was paid ten days ago
+        db.query("UPDATE expense SET occurred_at = time::now() - 10d WHERE number = 2")
+            .await
+            .unwrap();
+
+        let stats = ExpenseStats::expense_stats(db, bot.chat_id(), &TimeZone::UTC).await.unwrap().unwrap();
+        assert_eq!(stats.oldest_expense.unwrap().number, 2);
+        assert_eq!(stats.newest_expense.unwrap().number, 1);
+        // 390 spent over 11 days
+        let average_per_day = stats.average_per_day.unwrap().amount;
+        assert_eq!(average_per_day.round_dp(2), Decimal::from_str("35.45").unwrap());
+    }
+
     test! { show_stats_ok_empty,
         let db = db().await;
 
@@ -102,4 +330,102 @@ mod tests {
         let response = Stats::default().translate_default();
         bot.test_last_message(&response).await;
     }
+
+    test! { show_stats_keyboard,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_expense(&mut bot, "Dinner", 90.into(), "Alice", &["all"]).await;
+
+        bot.update("/showstats");
+        bot.dispatch().await;
+        let message = bot.last_sent_message().unwrap();
+        assert!(message.reply_markup().is_some());
+    }
+
+    test! { show_stats_usage,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/showstats months");
+        let response = i18n::commands::SHOW_STATS_USAGE.translate_with_args_default(
+            &hashmap! {
+                i18n::args::SECTIONS.into() =>
+                    "overview, days, weeks, weekdays, hours, travelers".into(),
+            },
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { show_stats_no_expenses,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/showstats days");
+        let response = i18n::commands::SHOW_STATS_NO_EXPENSES.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { show_stats_days,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 60.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "Taxi", 40.into(), "Bob", &["all"]).await;
+        helpers::add_expense(&mut bot, "Hotel", 200.into(), "Alice", &["all"]).await;
+        db.query("UPDATE expense SET occurred_at = d'2026-08-01T12:00:00Z' WHERE number IN [1, 2]")
+            .query("UPDATE expense SET occurred_at = d'2026-08-03T09:00:00Z' WHERE number = 3")
+            .await
+            .unwrap();
+
+        bot.update("/showstats days");
+        bot.dispatch().await;
+        let reply = bot.sent_texts().pop().unwrap();
+        let currency = bot.context().lock().expect("Failed to lock context").currency.clone();
+        let money = |amount: i64| MoneyWrapper::new(amount.into(), &currency).to_string();
+        assert!(reply.contains(&format!("- 2026-08-01: {} (2 expenses)", money(100))));
+        assert!(reply.contains(&format!("- 2026-08-03: {} (1 expense)", money(200))));
+        assert!(reply.contains(&format!("Busiest day: 2026-08-03, {}", money(200))));
+    }
+
+    test! { show_stats_sections_callback,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 60.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "Hotel", 200.into(), "Alice", &["all"]).await;
+        db.query("UPDATE expense SET occurred_at = d'2026-08-01T20:00:00Z' WHERE number = 1")
+            .query("UPDATE expense SET occurred_at = d'2026-08-03T09:00:00Z' WHERE number = 2")
+            .await
+            .unwrap();
+        let currency = bot.context().lock().expect("Failed to lock context").currency.clone();
+        let money = |amount: i64| MoneyWrapper::new(amount.into(), &currency).to_string();
+
+        // 2026-08-01 is a Saturday, in the week starting on Monday 2026-07-27
+        bot.update_callback("stats:weeks");
+        bot.dispatch().await;
+        let reply = bot.sent_texts().pop().unwrap();
+        assert!(reply.contains(&format!("- week of 2026-07-27: {} (1 expense)", money(60))));
+        assert!(reply.contains(&format!("- week of 2026-08-03: {} (1 expense)", money(200))));
+
+        bot.update_callback("stats:weekdays");
+        bot.dispatch().await;
+        let reply = bot.sent_texts().pop().unwrap();
+        assert!(reply.contains(&format!("- Monday: {} (1 expense)", money(200))));
+        assert!(reply.contains(&format!("- Saturday: {} (1 expense)", money(60))));
+
+        bot.update_callback("stats:hours");
+        bot.dispatch().await;
+        let reply = bot.sent_texts().pop().unwrap();
+        assert!(reply.contains(&format!("- 09:00: {} (1 expense)", money(200))));
+        assert!(reply.contains(&format!("- 20:00: {} (1 expense)", money(60))));
+
+        bot.update_callback("stats:travelers");
+        bot.dispatch().await;
+        let reply = bot.sent_texts().pop().unwrap();
+        assert!(reply.contains(&format!("- Alice: {} → {} (total {})", money(30), money(100), money(130))));
+    }
 }
@@ -0,0 +1,278 @@
+use crate::{
+    Context,
+    commands::CommandOutcome,
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS, STATEMENT_PAGE_SIZE},
+    errors::CommandError,
+    i18n::{self, Translate, TranslateWithArgs},
+    statement::StatementEntry,
+    topics::MessageExt,
+    traveler::{Name, Traveler},
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+/// Splits `/statement` arguments into the traveler name and the one-based
+/// page number, which defaults to the first page.
+fn parse_args(args: &str) -> Option<(Name, usize)> {
+    let args = args.trim();
+    let (name, page) = match args.rsplit_once(char::is_whitespace) {
+        Some((name, page)) if page.parse::<usize>().is_ok() => (name, page.parse().ok()?),
+        _ => (args, 1),
+    };
+    if page == 0 {
+        return None;
+    }
+    Name::from_str(name).ok().map(|name| (name, page))
+}
+
+#[apply(trace_command_db)]
+pub async fn statement(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    args: &str,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    if args.trim().is_empty() {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Success(
+            i18n::commands::STATEMENT_CHOOSE_TRAVELER.translate(ctx),
+        ));
+    }
+    let Some((name, page)) = parse_args(args) else {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::STATEMENT_USAGE.translate(ctx),
+        ));
+    };
+
+    let traveler = match Traveler::db_select_by_name(db.clone(), msg.ledger_id(), &name).await {
+        Ok(Some(traveler)) => traveler,
+        Ok(None) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            return Ok(CommandOutcome::Failure(
+                i18n::commands::STATEMENT_TRAVELER_NOT_FOUND
+                    .translate_with_args(ctx, &hashmap! {i18n::args::NAME.into() => name.into()}),
+            ));
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::Statement { name });
+        }
+    };
+
+    let entries = match StatementEntry::statement(db, traveler.id).await {
+        Ok(entries) => entries,
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::Statement { name });
+        }
+    };
+
+    let name = traveler.name;
+    if entries.is_empty() {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Success(
+            i18n::commands::STATEMENT_EMPTY
+                .translate_with_args(ctx, &hashmap! {i18n::args::NAME.into() => name.into()}),
+        ));
+    }
+
+    let pages = entries.len().div_ceil(STATEMENT_PAGE_SIZE);
+    if page > pages {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::STATEMENT_PAGE_NOT_FOUND.translate_with_args(
+                ctx,
+                &hashmap! {
+                    i18n::args::NAME.into() => name.into(),
+                    i18n::args::COUNT.into() => pages.into(),
+                },
+            ),
+        ));
+    }
+
+    let lines = entries
+        .iter()
+        .skip((page - 1) * STATEMENT_PAGE_SIZE)
+        .take(STATEMENT_PAGE_SIZE)
+        .map(|entry| entry.translate(ctx.clone()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let header = i18n::commands::STATEMENT_HEADER.translate_with_args(
+        ctx.clone(),
+        &hashmap! {i18n::args::NAME.into() => name.clone().into()},
+    );
+    let mut reply = format!("{header}\n{lines}");
+    if pages > 1 {
+        let footer = i18n::commands::STATEMENT_PAGE.translate_with_args(
+            ctx,
+            &hashmap! {
+                i18n::args::NAME.into() => name.clone().into(),
+                i18n::args::NUMBER.into() => page.into(),
+                i18n::args::COUNT.into() => pages.into(),
+            },
+        );
+        reply = format!("{reply}\n\n{footer}");
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Statement of {name} shown (page {page} of {pages})");
+    Ok(CommandOutcome::Success(reply))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, Translate, TranslateWithArgs},
+        statement::StatementEntry,
+        tests::{TestBot, helpers},
+        traveler::{Name, Traveler},
+    };
+    use maplit::hashmap;
+    use std::str::FromStr;
+
+    test! { statement_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+
+        // Alice pays 100 for both, then Bob pays back 50
+        helpers::add_expense(&mut bot, "Dinner", 100.into(), "Alice", &["all"]).await;
+        helpers::transfer(&mut bot, "Bob", "Alice", 50.into()).await;
+
+        let alice = Traveler::db_select_by_name(db.clone(), bot.chat_id(), &Name::from_str("Alice").unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        let entries = StatementEntry::statement(db.clone(), alice.id).await.unwrap();
+        // Paid 100, owes a share of 50, received 50
+        assert_eq!(
+            entries.iter().map(|entry| entry.net).collect::<Vec<_>>(),
+            vec![100.into(), 50.into(), 0.into()]
+        );
+
+        bot.update("/statement alice");
+        let header = i18n::commands::STATEMENT_HEADER.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Alice".into()},
+        );
+        let lines = entries
+            .iter()
+            .map(|entry| entry.translate_default())
+            .collect::<Vec<_>>()
+            .join("\n");
+        bot.test_last_message(&format!("{header}\n{lines}")).await;
+    }
+
+    test! { statement_in_date_order,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+
+        // Bob pays back 50, then Alice records a dinner she paid earlier
+        helpers::transfer(&mut bot, "Bob", "Alice", 50.into()).await;
+        helpers::add_expense(&mut bot, "Dinner", 100.into(), "Alice", &["all"]).await;
+        db.query("UPDATE expense SET occurred_at = d'2026-01-01T20:00:00Z'").await.unwrap();
+
+        let alice = Traveler::db_select_by_name(db.clone(), bot.chat_id(), &Name::from_str("Alice").unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        let entries = StatementEntry::statement(db, alice.id).await.unwrap();
+        // The dinner comes first: paid 100, owes a share of 50, received 50
+        assert_eq!(
+            entries.iter().map(|entry| entry.net).collect::<Vec<_>>(),
+            vec![100.into(), 50.into(), 0.into()]
+        );
+    }
+
+    test! { statement_paginated,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+
+        // 21 transfers from Alice to Bob fill more than one page
+        for _ in 0..21 {
+            helpers::transfer(&mut bot, "Alice", "Bob", 1.into()).await;
+        }
+
+        let alice = Traveler::db_select_by_name(db.clone(), bot.chat_id(), &Name::from_str("Alice").unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        let entries = StatementEntry::statement(db.clone(), alice.id).await.unwrap();
+        assert_eq!(entries.len(), 21);
+
+        let header = i18n::commands::STATEMENT_HEADER.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Alice".into()},
+        );
+        let footer = |page: usize| i18n::commands::STATEMENT_PAGE.translate_with_args_default(
+            &hashmap! {
+                i18n::args::NAME.into() => "Alice".into(),
+                i18n::args::NUMBER.into() => page.into(),
+                i18n::args::COUNT.into() => 2.into(),
+            },
+        );
+
+        bot.update("/statement Alice");
+        let lines = entries[..20]
+            .iter()
+            .map(|entry| entry.translate_default())
+            .collect::<Vec<_>>()
+            .join("\n");
+        bot.test_last_message(&format!("{header}\n{lines}\n\n{}", footer(1))).await;
+
+        bot.update("/statement Alice 2");
+        let lines = entries[20].translate_default();
+        bot.test_last_message(&format!("{header}\n{lines}\n\n{}", footer(2))).await;
+        // The running net carries over to the second page
+        assert_eq!(entries[20].net, 21.into());
+
+        bot.update("/statement Alice 3");
+        let response = i18n::commands::STATEMENT_PAGE_NOT_FOUND.translate_with_args_default(
+            &hashmap! {
+                i18n::args::NAME.into() => "Alice".into(),
+                i18n::args::COUNT.into() => 2.into(),
+            },
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { statement_empty_and_not_found,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+
+        bot.update("/statement Alice");
+        let response = i18n::commands::STATEMENT_EMPTY.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Alice".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        bot.update("/statement Bob");
+        let response = i18n::commands::STATEMENT_TRAVELER_NOT_FOUND.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Bob".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        bot.update("/statement");
+        let response = i18n::commands::STATEMENT_CHOOSE_TRAVELER.translate_default();
+        bot.test_last_message(&response).await;
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    Context,
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, Translate, TranslateWithArgs},
+    money_wrapper::MoneyWrapper,
+    stats::TravelerSummary,
+    topics::MessageExt,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn summary(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<String, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let currency = ctx.lock().expect("Failed to lock context").currency.clone();
+    let (summaries, total) =
+        match TravelerSummary::traveler_summaries(db, msg.ledger_id(), &currency).await {
+            Ok(res) => res,
+            Err(err) => {
+                tracing::error!("{err}");
+                return Err(CommandError::Summary);
+            }
+        };
+
+    if summaries.is_empty() {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(i18n::commands::SUMMARY_NOT_FOUND.translate(ctx));
+    }
+
+    let lines = summaries
+        .iter()
+        .map(|summary| summary.translate(ctx.clone()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut reply = format!(
+        "{header}\n{lines}",
+        header = i18n::commands::SUMMARY_HEADER.translate(ctx.clone())
+    );
+    if !total.is_zero() {
+        tracing::warn!("Net positions add up to {total} instead of zero");
+        let warning = i18n::commands::SUMMARY_UNBALANCED.translate_with_args(
+            ctx.clone(),
+            &hashmap! {
+                i18n::args::AMOUNT.into() => MoneyWrapper::new(total, &currency).to_string().into(),
+            },
+        );
+        reply = format!("{reply}\n\n{warning}");
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Summary of {} travelers shown", summaries.len());
+    Ok(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, Translate},
+        stats::TravelerSummary,
+        tests::{TestBot, helpers},
+    };
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    test! { summary_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_traveler(&mut bot, "Charlie").await;
+
+        // Alice pays 100 for everyone, Bob pays Alice back 20
+        helpers::add_expense(&mut bot, "Dinner", 100.into(), "Alice", &["all"]).await;
+        helpers::transfer(&mut bot, "Bob", "Alice", 20.into()).await;
+
+        let currency = bot.context().lock().unwrap().currency.clone();
+        let (summaries, total) = TravelerSummary::traveler_summaries(db.clone(), bot.chat_id(), &currency)
+            .await
+            .unwrap();
+        assert_eq!(total, Decimal::ZERO);
+        let figures = summaries
+            .iter()
+            .map(|s| (s.traveler_name.to_string(), s.paid, s.consumed, s.sent, s.received, s.net))
+            .collect::<Vec<_>>();
+        let d = |s: &str| Decimal::from_str(s).unwrap();
+        assert_eq!(
+            figures,
+            vec![
+                ("Alice".to_owned(), d("100"), d("33.33"), d("0"), d("20"), d("46.67")),
+                ("Bob".to_owned(), d("0"), d("33.33"), d("20"), d("0"), d("-13.33")),
+                ("Charlie".to_owned(), d("0"), d("33.33"), d("0"), d("0"), d("-33.34")),
+            ]
+        );
+        // Rounded nets still add up to zero
+        assert_eq!(summaries.iter().map(|s| s.net).sum::<Decimal>(), Decimal::ZERO);
+
+        bot.update("/summary");
+        let lines = summaries
+            .iter()
+            .map(|s| s.translate_default())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let header = i18n::commands::SUMMARY_HEADER.translate_default();
+        bot.test_last_message(&format!("{header}\n{lines}")).await;
+    }
+
+    test! { summary_not_found,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/summary");
+        let response = i18n::commands::SUMMARY_NOT_FOUND.translate_default();
+        bot.test_last_message(&response).await;
+    }
+}
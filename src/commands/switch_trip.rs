@@ -0,0 +1,218 @@
+use crate::{
+    Context,
+    commands::CommandOutcome,
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, TranslateWithArgs},
+    topics::MessageExt,
+    traveler::Name,
+    trip::Trip,
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+#[apply(trace_command_db)]
+pub async fn switch_trip(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    name: Option<Name>,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let active = Trip::db_select_active(db.clone(), msg.ledger_id())
+        .await
+        .map_err(|err| {
+            tracing::error!("{err}");
+            CommandError::SwitchTrip {
+                name: name.clone().unwrap_or_default(),
+            }
+        })?;
+
+    // Without a name, just tell which trip is active: the trip picker
+    // keyboard is attached to the reply.
+    let Some(name) = name else {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        let active_name = active.map(|trip| trip.name).unwrap_or_default();
+        return Ok(CommandOutcome::Success(
+            i18n::commands::SWITCH_TRIP_CHOOSE.translate_with_args(
+                ctx,
+                &hashmap! {i18n::args::NAME.into() => active_name.into()},
+            ),
+        ));
+    };
+
+    // Look the trip up by name first, then by its number.
+    let mut select_res = Trip::db_select_by_name(db.clone(), msg.ledger_id(), &name).await;
+    if let Ok(None) = select_res
+        && let Ok(number) = name.parse::<i64>()
+    {
+        select_res = Trip::db_select_by_number(db.clone(), msg.ledger_id(), number).await;
+    }
+
+    match select_res {
+        Ok(Some(trip)) if active.as_ref().is_some_and(|active| active.id == trip.id) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            Ok(CommandOutcome::Failure(
+                i18n::commands::SWITCH_TRIP_ALREADY_ACTIVE.translate_with_args(
+                    ctx,
+                    &hashmap! {i18n::args::NAME.into() => trip.name.into()},
+                ),
+            ))
+        }
+        Ok(Some(trip)) => match trip.db_activate(db).await {
+            Ok(_) => {
+                tracing::debug!("{LOG_DEBUG_SUCCESS}");
+                tracing::info!("Switched to trip '{}'", trip.name);
+                Ok(CommandOutcome::Success(
+                    i18n::commands::SWITCH_TRIP_OK.translate_with_args(
+                        ctx,
+                        &hashmap! {i18n::args::NAME.into() => trip.name.into()},
+                    ),
+                ))
+            }
+            Err(err) => {
+                tracing::error!("{err}");
+                Err(CommandError::SwitchTrip { name })
+            }
+        },
+        Ok(None) => {
+            tracing::warn!(
+                "{}",
+                i18n::commands::SWITCH_TRIP_NOT_FOUND.translate_with_args_default(
+                    &hashmap! {i18n::args::NAME.into() => name.clone().into()},
+                )
+            );
+            Ok(CommandOutcome::Failure(
+                i18n::commands::SWITCH_TRIP_NOT_FOUND
+                    .translate_with_args(ctx, &hashmap! {i18n::args::NAME.into() => name.into()}),
+            ))
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            Err(CommandError::SwitchTrip { name })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, TranslateWithArgs},
+        tests::{TestBot, helpers},
+    };
+    use maplit::hashmap;
+
+    test! { switch_trip_ok,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "");
+        helpers::add_traveler(&mut bot, "Alice").await;
+        bot.update("/newtrip Japan");
+        bot.dispatch().await;
+
+        // Switching back to the default trip restores its travelers
+        bot.update("/switchtrip Default");
+        let response = i18n::commands::SWITCH_TRIP_OK.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Default".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        bot.update("/listtravelers");
+        bot.test_last_message("Alice").await;
+    }
+
+    test! { switch_trip_by_number,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/newtrip Japan");
+        bot.dispatch().await;
+
+        bot.update("/switchtrip 1");
+        let response = i18n::commands::SWITCH_TRIP_OK.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Default".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { switch_trip_already_active,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/newtrip Japan");
+        bot.dispatch().await;
+
+        bot.update("/switchtrip japan");
+        let response = i18n::commands::SWITCH_TRIP_ALREADY_ACTIVE.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Japan".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { switch_trip_not_found,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/switchtrip Japan");
+        let response = i18n::commands::SWITCH_TRIP_NOT_FOUND.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Japan".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { switch_trip_without_name,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/switchtrip");
+        let response = i18n::commands::SWITCH_TRIP_CHOOSE.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Default".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { switch_trip_callback,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/newtrip Japan");
+        bot.dispatch().await;
+
+        bot.update_callback("swtrip:1");
+        let response = i18n::commands::SWITCH_TRIP_OK.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Default".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { switch_trip_dialogue_running,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/newtrip Japan");
+        bot.dispatch().await;
+        bot.update("/addtraveler");
+        bot.dispatch().await;
+
+        // Neither the command nor the trip picker may switch trips under the
+        // running dialogue.
+        let refused = i18n::commands::PROCESS_ALREADY_RUNNING.translate_with_args_default(
+            &hashmap! {i18n::args::PROCESS.into() => "/addtraveler".into()},
+        );
+        bot.update("/switchtrip Default");
+        bot.test_last_message(&refused).await;
+        bot.update_callback("swtrip:1");
+        bot.test_last_message(&refused).await;
+
+        // The dialogue still saves into the trip it was started under.
+        bot.update("Alice");
+        bot.dispatch().await;
+        bot.update("/switchtrip Japan");
+        let response = i18n::commands::SWITCH_TRIP_ALREADY_ACTIVE.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Japan".into()},
+        );
+        bot.test_last_message(&response).await;
+        bot.update("/listtravelers");
+        bot.test_last_message("Alice").await;
+    }
+}
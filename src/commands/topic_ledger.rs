@@ -0,0 +1,221 @@
+use crate::{
+    Context,
+    commands::CommandOutcome,
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    errors::CommandError,
+    i18n::{self, Translate},
+    topic::Topic,
+    topics::{self, MessageExt},
+};
+use macro_rules_attribute::apply;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::prelude::*;
+use tracing::Level;
+
+const MODE_ON: &str = "on";
+const MODE_OFF: &str = "off";
+
+#[apply(trace_command_db)]
+pub async fn topic_ledger(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    mode: &str,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<CommandOutcome, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let Some(thread_id) = msg.topic_id() else {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Failure(
+            i18n::commands::TOPIC_LEDGER_NOT_A_TOPIC.translate(ctx),
+        ));
+    };
+
+    let topic = match Topic::db_select_by_thread(db.clone(), msg.chat.id, thread_id).await {
+        Ok(topic) => topic,
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::TopicLedger);
+        }
+    };
+    let enabled = topic.as_ref().is_some_and(|topic| topic.enabled);
+
+    let enable = match mode.trim().to_lowercase().as_str() {
+        "" => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            return Ok(CommandOutcome::Success(status(enabled, ctx)));
+        }
+        MODE_ON => true,
+        MODE_OFF => false,
+        _ => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            return Ok(CommandOutcome::Failure(
+                i18n::commands::TOPIC_LEDGER_USAGE.translate(ctx),
+            ));
+        }
+    };
+    if enable == enabled {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(CommandOutcome::Success(status(enabled, ctx)));
+    }
+
+    let res = match topic {
+        Some(topic) => topic.db_set_enabled(db, enable).await,
+        None => Topic::db_create(db, msg.chat.id, thread_id, enable).await,
+    };
+    match res {
+        Ok(Some(topic)) => {
+            topics::cache_topic(&topic);
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            tracing::info!(
+                "Ledger of topic {thread_id} of chat {} turned {}",
+                msg.chat.id,
+                if enable { MODE_ON } else { MODE_OFF }
+            );
+            let reply = if enable {
+                i18n::commands::TOPIC_LEDGER_ENABLED
+            } else {
+                i18n::commands::TOPIC_LEDGER_DISABLED
+            };
+            Ok(CommandOutcome::Success(reply.translate(ctx)))
+        }
+        Ok(None) => {
+            tracing::error!(
+                "Topic {thread_id} of chat {} not found after update",
+                msg.chat.id
+            );
+            Err(CommandError::TopicLedger)
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            Err(CommandError::TopicLedger)
+        }
+    }
+}
+
+fn status(enabled: bool, ctx: Arc<Mutex<Context>>) -> String {
+    if enabled {
+        i18n::commands::TOPIC_LEDGER_STATUS_ENABLED.translate(ctx)
+    } else {
+        i18n::commands::TOPIC_LEDGER_STATUS_DISABLED.translate(ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, Translate, TranslateWithArgs},
+        tests::{TestBot, helpers},
+    };
+    use maplit::hashmap;
+
+    const THREAD: i32 = 42;
+
+    test! { topic_ledger_not_a_topic,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/topicledger on");
+        let response = i18n::commands::TOPIC_LEDGER_NOT_A_TOPIC.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { topic_ledger_status,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "");
+        bot.set_topic(Some(THREAD));
+        bot.update("/topicledger");
+        let response = i18n::commands::TOPIC_LEDGER_STATUS_DISABLED.translate_default();
+        bot.test_last_message(&response).await;
+        assert_eq!(bot.last_message_topic(), Some(THREAD));
+
+        bot.update("/topicledger on");
+        let response = i18n::commands::TOPIC_LEDGER_ENABLED.translate_default();
+        bot.test_last_message(&response).await;
+
+        bot.update("/topicledger on");
+        let response = i18n::commands::TOPIC_LEDGER_STATUS_ENABLED.translate_default();
+        bot.test_last_message(&response).await;
+
+        bot.update("/topicledger maybe");
+        let response = i18n::commands::TOPIC_LEDGER_USAGE.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { topic_ledger_isolated,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "");
+        bot.set_topic(Some(THREAD));
+        bot.update("/topicledger on");
+        bot.dispatch().await;
+        helpers::add_traveler(&mut bot, "Alice").await;
+
+        // The chat ledger doesn't see the travelers of the topic
+        bot.set_topic(None);
+        helpers::add_traveler(&mut bot, "Bob").await;
+        bot.update("/listtravelers");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        assert!(response.contains("Bob"));
+        assert!(!response.contains("Alice"));
+
+        // Neither do other topics without their own ledger
+        bot.set_topic(Some(THREAD + 1));
+        bot.update("/listtravelers");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        assert!(response.contains("Bob"));
+        assert!(!response.contains("Alice"));
+        assert_eq!(bot.last_message_topic(), Some(THREAD + 1));
+
+        bot.set_topic(Some(THREAD));
+        bot.update("/listtravelers");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        assert!(response.contains("Alice"));
+        assert!(!response.contains("Bob"));
+
+        // Turning the ledger off falls back to the chat one, turning it back
+        // on restores the topic data
+        bot.update("/topicledger off");
+        let response = i18n::commands::TOPIC_LEDGER_DISABLED.translate_default();
+        bot.test_last_message(&response).await;
+        bot.update("/addtraveler Bob");
+        let response = i18n::commands::ADD_TRAVELER_ALREADY_ADDED.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Bob".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        bot.update("/topicledger on");
+        bot.dispatch().await;
+        bot.update("/addtraveler Alice");
+        let response = i18n::commands::ADD_TRAVELER_ALREADY_ADDED.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Alice".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { topic_dialogues_independent,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "");
+        bot.set_topic(Some(THREAD));
+        bot.update("/addtraveler");
+        bot.dispatch().await;
+
+        // A dialogue running in another topic doesn't block this one
+        bot.set_topic(Some(THREAD + 1));
+        bot.update("/addtraveler Bob");
+        let response = i18n::commands::ADD_TRAVELER_OK.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Bob".into()},
+        );
+        bot.test_last_message(&response).await;
+
+        // The dialogue of the first topic is still waiting for a name
+        bot.set_topic(Some(THREAD));
+        bot.update("Alice");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        assert!(response.contains("Alice"));
+        assert_eq!(bot.last_message_topic(), Some(THREAD));
+    }
+}
@@ -1,8 +1,11 @@
 use crate::{
     Context,
+    balance::Balance,
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     errors::CommandError,
     i18n::{self, Translate, TranslateWithArgs},
+    money_wrapper::MoneyWrapper,
+    topics::MessageExt,
     transferred_to::TransferredTo,
     traveler::{Name, Traveler},
     update_debts,
@@ -34,7 +37,7 @@ pub async fn transfer(
         return Ok(i18n::commands::TRANSFER_SAME_SENDER_RECEIVER
             .translate_with_args(ctx, &hashmap! {i18n::args::NAME.into() => from.into()}));
     }
-    let chat_id = msg.chat.id;
+    let chat_id = msg.ledger_id();
 
     // Get sender from db
     let select_from_res = Traveler::db_select_by_name(db.clone(), chat_id, &from).await;
@@ -116,6 +119,51 @@ pub async fn transfer(
     }
 }
 
+/// Transfers from `from` to `to` exactly the amount `from` currently owes to
+/// `to`, rounded to the precision of the chat currency.
+#[apply(trace_command_db)]
+pub async fn transfer_all(
+    db: Arc<Surreal<Any>>,
+    msg: &Message,
+    from: Name,
+    to: Name,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<String, CommandError> {
+    tracing::debug!("{LOG_DEBUG_START}");
+    let owed = match Balance::balance_between(db.clone(), msg.ledger_id(), &from, &to).await {
+        Ok(balance) => {
+            let currency = ctx.lock().expect("Failed to lock context").currency.clone();
+            balance
+                .map(|balance| MoneyWrapper::new(balance.debt, &currency).round_value())
+                .filter(|owed| *owed > Decimal::ZERO)
+        }
+        Err(err) => {
+            tracing::error!("{err}");
+            return Err(CommandError::TransferAll {
+                sender: from,
+                receiver: to,
+            });
+        }
+    };
+
+    match owed {
+        Some(amount) => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            transfer(db, msg, from, to, amount, ctx).await
+        }
+        None => {
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+            Ok(i18n::commands::TRANSFER_NOTHING_OWED.translate_with_args(
+                ctx,
+                &hashmap! {
+                    i18n::args::SENDER.into() => from.into(),
+                    i18n::args::RECEIVER.into() => to.into(),
+                },
+            ))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -208,4 +256,48 @@ mod tests {
             .translate_with_args_default(&hashmap! {i18n::args::NAME.into() => "Alice".into()});
         bot.test_last_message(&response).await;
     }
+
+    test! { transfer_all_ok,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_traveler(&mut bot, "Charlie").await;
+        helpers::add_expense(&mut bot, "Dinner", 100.into(), "Alice", &["all"]).await;
+
+        // Bob owes 33.333... to Alice, the transfer is rounded to 33.33
+        bot.update("/transfer bob alice all");
+        let response = i18n::commands::TRANSFER_OK.translate_default();
+        bot.test_last_message(&response).await;
+
+        bot.update("/listtransfers");
+        let response = bot.dispatch_and_last_message().await.unwrap();
+        assert!(response.contains("33.33"));
+        assert!(!response.contains("33.333"));
+
+        bot.update("/showbalances Bob");
+        let response = i18n::commands::SHOW_BALANCES_TRAVELER_SETTLED_UP
+            .translate_with_args_default(&hashmap! {i18n::args::NAME.into() => "Bob".into()});
+        bot.test_last_message(&response).await;
+    }
+
+    test! { transfer_all_nothing_owed,
+        let db = db().await;
+        let mut bot = TestBot::new(db, "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 100.into(), "Alice", &["all"]).await;
+
+        // Alice is the creditor, she owes nothing to Bob
+        bot.update("/transfer Alice Bob all");
+        let response = i18n::commands::TRANSFER_NOTHING_OWED.translate_with_args_default(
+            &hashmap! {
+                i18n::args::SENDER.into() => "Alice".into(),
+                i18n::args::RECEIVER.into() => "Bob".into(),
+            },
+        );
+        bot.test_last_message(&response).await;
+    }
 }
@@ -3,6 +3,7 @@ use crate::{
     Context, HandlerResult,
     commands::{Command, HelpMessage},
     i18n::{self, TranslateWithArgs},
+    topics::{InTopic, MessageExt},
 };
 use macro_rules_attribute::apply;
 use maplit::hashmap;
@@ -56,6 +57,8 @@ pub async fn unknown_command(bot: Bot, msg: Message, ctx: Arc<Mutex<Context>>) -
             .translate_with_args(ctx, &hashmap! {i18n::args::COMMAND.into() => text.into()}),
     };
 
-    bot.send_message(msg.chat.id, reply).await?;
+    bot.send_message(msg.chat.id, reply)
+        .in_topic(msg.topic_id())
+        .await?;
     Ok(())
 }
@@ -8,12 +8,35 @@ pub const SPLIT_AMONG_REGEX_PERCENTAGE_GRP: &str = "percentage";
 
 pub const ALL_KWORD: &str = "all";
 pub const END_KWORD: &str = "end";
+pub const CANNOT_KWORD: &str = "cannot";
+pub const VIA_KWORD: &str = "via";
+pub const CLEAR_KWORD: &str = "clear";
+pub const FIX_KWORD: &str = "fix";
+pub const RAW_KWORD: &str = "raw";
+pub const DOT_KWORD: &str = "dot";
+pub const TODAY_KWORD: &str = "today";
+pub const YESTERDAY_KWORD: &str = "yesterday";
 pub const RESERVED_KWORDS: [&str; 2] = [ALL_KWORD, END_KWORD];
 
 pub const BLANK_LABEL: &str = "\u{2800}"; // Braille Pattern Blank — invisible but non-empty
 pub const BACK_LABEL: &str = "◀";
 pub const NEXT_LABEL: &str = "▶";
 
+/// Option of `/addexpense` setting when the expense was made.
+pub const DATE_OPTION: &str = "--date";
+/// Formats accepted for the date of an expense, with and without a time.
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+pub const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Number of entries shown on each page of a traveler statement.
+pub const STATEMENT_PAGE_SIZE: usize = 20;
+/// Number of entries shown on each page of a long list (e.g. `/listexpenses`).
+pub const LIST_PAGE_SIZE: usize = 20;
+/// Maximum length of the text of a Telegram message, in UTF-16 code units.
+pub const MAX_MESSAGE_LEN: usize = 4096;
+/// Largest file a bot can download from Telegram, in bytes.
+pub const MAX_DOWNLOAD_SIZE: u32 = 20 * 1024 * 1024;
+
 pub const NAME_INVALID_CHARS: [char; 4] = [
     SPLIT_AMONG_ENTRIES_SEP,
     SPLIT_AMONG_NAME_AMOUNT_SEP,
@@ -1,120 +1,321 @@
-use rust_decimal::prelude::*;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
 use surrealdb::{
     RecordId, Surreal,
     engine::any::Any,
+    method::Query,
     sql::statements::{BeginStatement, CommitStatement},
 };
 use teloxide::types::ChatId;
-use travel_rs_derive::Table;
+use travel_rs_core::{
+    Expense, Transfer, ledger,
+    settlement::{settle_nets, simplify_debts},
+};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Table)]
-pub struct Debt {
-    pub debtor: RecordId,
-    pub creditor: RecordId,
-    pub debt: Decimal,
-}
+pub use travel_rs_core::{SettlementMode, SimplificationMode};
+
+pub type Debt = travel_rs_core::Debt<RecordId>;
 
-const FN_CALC_DEBTS: &str = "fn::calc_debts";
 const FN_CLEAR_DEBTS: &str = "fn::clear_debts";
 
-/// Simplifies a list of debts by calculating the net balance for each participant
-/// and creating new debt transactions that reflect the simplified balances.
-fn simplify_balances(debts: &mut Vec<Debt>) {
-    let keys: HashMap<String, RecordId> = debts
-        .iter()
-        .flat_map(|d| {
-            vec![
-                (d.debtor.to_string(), d.debtor.clone()),
-                (d.creditor.to_string(), d.creditor.clone()),
-            ]
-        })
-        .collect();
-
-    let mut balance_map: HashMap<String, Decimal> = HashMap::new();
-
-    for debt in debts.iter() {
-        *balance_map
-            .entry(debt.debtor.to_string())
-            .or_insert(Decimal::ZERO) -= debt.debt;
-        *balance_map
-            .entry(debt.creditor.to_string())
-            .or_insert(Decimal::ZERO) += debt.debt;
-    }
+/// Field of a traveler holding their net position: positive if the traveler
+/// is owed money, negative if they owe it.
+pub const NET: &str = "net";
 
-    let mut creditors: Vec<_> = balance_map
-        .clone()
-        .into_iter()
-        .filter(|&(_, v)| v > Decimal::ZERO)
-        .collect();
-    let mut debtors: Vec<_> = balance_map
-        .into_iter()
-        .filter(|&(_, v)| v < Decimal::ZERO)
-        .collect();
-
-    creditors.sort_by_key(|a| std::cmp::Reverse(a.1));
-    debtors.sort_by_key(|a| a.1);
-
-    debts.clear();
-
-    while !debtors.is_empty() && !creditors.is_empty() {
-        let (debtor, mut debtor_amount) = debtors.pop().unwrap();
-        let (creditor, mut creditor_amount) = creditors.pop().unwrap();
-
-        let amount = debtor_amount.abs().min(creditor_amount);
-
-        let debtor_id = keys
-            .get(&debtor)
-            .unwrap_or_else(|| panic!("Debtor with id {debtor} should exist"))
-            .clone();
-        let creditor_id = keys
-            .get(&creditor)
-            .unwrap_or_else(|| panic!("Creditor with id {creditor} should exist"))
-            .clone();
-
-        debts.push(Debt {
-            debtor: debtor_id,
-            creditor: creditor_id,
-            debt: amount,
-        });
-
-        debtor_amount += amount;
-        if debtor_amount < Decimal::ZERO {
-            debtors.push((debtor, debtor_amount));
-        }
+/// Net position of a traveler, as kept up to date by [`with_net_changes`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct TravelerNet {
+    id: RecordId,
+    net: Decimal,
+    #[serde(default)]
+    number: i64,
+}
 
-        creditor_amount -= amount;
-        if creditor_amount > Decimal::ZERO {
-            creditors.push((creditor, creditor_amount));
-        }
+/// Returns the expenses of the active trip of a given chat, or only the one
+/// numbered `number`, with the traveler who paid and the shares of each.
+/// Expenses without a payer are left out, as nobody is owed their shares.
+pub async fn ledger_expenses(
+    db: Arc<Surreal<Any>>,
+    chat_id: ChatId,
+    number: Option<i64>,
+) -> Result<Vec<Expense<RecordId>>, surrealdb::Error> {
+    use crate::{
+        chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+        expense::{AMOUNT, NUMBER, TABLE as EXPENSE_TB, TRIP},
+        paid_for::TABLE as PAID_FOR_TB,
+        split::{AMOUNT as SPLIT_AMOUNT, IN as SPLIT_IN, OUT as SPLIT_OUT, TABLE as SPLIT_TB},
+        traveler::TABLE as TRAVELER_TB,
+    };
+    const PAYER: &str = "payer";
+    const SPLITS: &str = "splits";
+    const TRAVELER: &str = "traveler";
+
+    db.query(format!(
+        "SELECT
+            (<-{PAID_FOR_TB}<-{TRAVELER_TB})[0] AS {PAYER},
+            {AMOUNT},
+            (
+                SELECT {SPLIT_IN} AS {TRAVELER}, {SPLIT_AMOUNT} AS amount
+                FROM {SPLIT_TB}
+                WHERE {SPLIT_OUT} = $parent.id
+            ) AS {SPLITS}
+        FROM {EXPENSE_TB}
+        WHERE
+            {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
+            && array::len(<-{PAID_FOR_TB}) > 0
+            && (${NUMBER} = NONE || {NUMBER} = ${NUMBER})"
+    ))
+    .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+    .bind((NUMBER, number))
+    .await
+    .and_then(|mut response| response.take::<Vec<Expense<RecordId>>>(0))
+}
+
+/// Returns the transfers of the active trip of a given chat, or only the one
+/// numbered `number`.
+pub async fn ledger_transfers(
+    db: Arc<Surreal<Any>>,
+    chat_id: ChatId,
+    number: Option<i64>,
+) -> Result<Vec<Transfer<RecordId>>, surrealdb::Error> {
+    use crate::{
+        chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+        transferred_to::{AMOUNT, IN, NUMBER, OUT, TABLE as TRANSFER_TB},
+        traveler::TRIP,
+    };
+    const FROM: &str = "from";
+    const TO: &str = "to";
+
+    db.query(format!(
+        "SELECT {IN} AS {FROM}, {OUT} AS {TO}, {AMOUNT}
+        FROM {TRANSFER_TB}
+        WHERE
+            {IN}.{TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
+            && (${NUMBER} = NONE || {NUMBER} = ${NUMBER})"
+    ))
+    .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+    .bind((NUMBER, number))
+    .await
+    .and_then(|mut response| response.take::<Vec<Transfer<RecordId>>>(0))
+}
+
+/// Appends to `query` the statements adding `changes` to the net positions
+/// of the travelers, so that they are applied in the same transaction as the
+/// expense or transfer they come from.
+pub fn with_net_changes<'r>(
+    mut query: Query<'r, Any>,
+    changes: impl IntoIterator<Item = (RecordId, Decimal)>,
+) -> Query<'r, Any> {
+    for (i, (traveler, change)) in changes.into_iter().enumerate() {
+        query = query
+            .query(format!(
+                "UPDATE $net_traveler_{i} SET {NET} += <decimal> $net_change_{i}"
+            ))
+            .bind((format!("net_traveler_{i}"), traveler))
+            .bind((format!("net_change_{i}"), change));
     }
+    query
 }
 
-/// Updates the debts for a given chat by recalculating the net balances and simplifying the transactions.
-/// This function retrieves the current debts from the database, simplifies them, and then updates the database with the new simplified debts.
-pub async fn update_debts(db: Arc<Surreal<Any>>, chat_id: ChatId) -> Result<(), surrealdb::Error> {
+/// Appends to `query` the statements deleting the expenses of the active
+/// trip of `$chat_id` matching `condition`, and taking back from the net
+/// positions the shares deleted with them. The changes are computed from the
+/// rows actually deleted, in the same transaction, so that deleting the same
+/// expense twice, or concurrently, doesn't take them back twice.
+pub fn delete_expenses<'r>(query: Query<'r, Any>, condition: &str) -> Query<'r, Any> {
     use crate::{
-        chat::{ID as CHAT_ID, TABLE as CHAT_TB},
-        owes::{AMOUNT, TABLE as OWES},
+        chat::{ACTIVE_TRIP, ID as CHAT_ID},
+        expense::{ID, TABLE as EXPENSE_TB, TRIP},
+        paid_for::TABLE as PAID_FOR_TB,
+        split::{AMOUNT, IN, OUT, TABLE as SPLIT_TB},
     };
+    const EXPENSES: &str = "expenses";
+    const PAYERS: &str = "payers";
+    const SPLITS: &str = "splits";
 
-    let mut debts = db
+    query
+        .query(format!(
+            "LET ${EXPENSES} = SELECT VALUE {ID}
+            FROM {EXPENSE_TB}
+            WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP} && ({condition})"
+        ))
+        .query(format!(
+            "LET ${PAYERS} = (DELETE {PAID_FOR_TB} WHERE {OUT} IN ${EXPENSES} RETURN BEFORE)"
+        ))
+        .query(format!(
+            "LET ${SPLITS} = (DELETE {SPLIT_TB} WHERE {OUT} IN ${EXPENSES} RETURN BEFORE)"
+        ))
+        .query(format!("DELETE {EXPENSE_TB} WHERE {ID} IN ${EXPENSES}"))
+        // Expenses without a payer never changed the nets
+        .query(format!(
+            "FOR $split IN ${SPLITS} {{
+                LET $payer = ${PAYERS}[WHERE {OUT} = $split.{OUT}][0].{IN};
+                IF $payer != NONE {{
+                    UPDATE $payer SET {NET} -= <decimal> $split.{AMOUNT};
+                    UPDATE $split.{IN} SET {NET} += <decimal> $split.{AMOUNT};
+                }};
+            }}"
+        ))
+}
+
+/// Appends to `query` the statements deleting the transfers of the active
+/// trip of `$chat_id` matching `condition`, and taking back from the net
+/// positions the amounts of the transfers actually deleted, as
+/// [`delete_expenses`] does.
+pub fn delete_transfers<'r>(query: Query<'r, Any>, condition: &str) -> Query<'r, Any> {
+    use crate::{
+        chat::{ACTIVE_TRIP, ID as CHAT_ID},
+        transferred_to::{AMOUNT, IN, OUT, TABLE as TRANSFER_TB},
+        traveler::TRIP,
+    };
+    const TRANSFERS: &str = "transfers";
+
+    query
+        .query(format!(
+            "LET ${TRANSFERS} = (
+                DELETE {TRANSFER_TB}
+                WHERE {IN}.{TRIP} = ${CHAT_ID}.{ACTIVE_TRIP} && ({condition})
+                RETURN BEFORE
+            )"
+        ))
+        .query(format!(
+            "FOR $transfer IN ${TRANSFERS} {{
+                UPDATE $transfer.{IN} SET {NET} -= <decimal> $transfer.{AMOUNT};
+                UPDATE $transfer.{OUT} SET {NET} += <decimal> $transfer.{AMOUNT};
+            }}"
+        ))
+}
+
+/// Recomputes from scratch the net positions of the travelers of the active
+/// trip of a given chat, from every expense and transfer, then updates the
+/// debts. Nets are otherwise kept up to date one expense or transfer at a
+/// time: this repairs them, and brings them up to date after travelers are
+/// deleted along with their expenses and transfers.
+pub async fn rebuild_debts(db: Arc<Surreal<Any>>, chat_id: ChatId) -> Result<(), surrealdb::Error> {
+    use crate::{
+        chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+        traveler::{TABLE as TRAVELER_TB, TRIP},
+    };
+
+    let expenses = ledger_expenses(db.clone(), chat_id, None).await?;
+    let transfers = ledger_transfers(db.clone(), chat_id, None).await?;
+
+    let query = db
+        .query(BeginStatement::default())
         .query(format!(
-            "SELECT {DEBTOR}, {CREDITOR}, {DEBT} 
-            FROM {FN_CALC_DEBTS}(${CHAT_ID})"
+            "UPDATE {TRAVELER_TB} SET {NET} = 0dec WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}"
         ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)));
+    with_net_changes(query, ledger::net_positions(&expenses, &transfers))
+        .query(CommitStatement::default())
+        .await?
+        .check()?;
+
+    update_debts(db, chat_id).await
+}
+
+/// Computes the debts of the active trip of a given chat and simplifies the transactions. Since settling needs the net
+/// positions only, fully simplified debts are derived from the nets kept up to date by every expense and transfer, or
+/// from nets recomputed from the expenses and transfers if `from_ledger` is set. Debts that are not simplified fully
+/// are always recomputed from the expenses and transfers, as they depend on who paid for whom.
+pub async fn compute_debts(
+    db: Arc<Surreal<Any>>,
+    chat_id: ChatId,
+    from_ledger: bool,
+) -> Result<Vec<Debt>, surrealdb::Error> {
+    use crate::{
+        cannot_pay::CannotPay,
+        chat::{ACTIVE_TRIP, ID as CHAT_ID, SETTLEMENT, SIMPLIFICATION, TABLE as CHAT_TB},
+        settles_via::SettlesVia,
+        traveler::{NUMBER, TABLE as TRAVELER_TB, TRIP},
+    };
+
+    let mut response = db
+        .query(format!("RETURN ${CHAT_ID}.{SETTLEMENT}"))
+        .query(format!("RETURN ${CHAT_ID}.{SIMPLIFICATION}"))
         .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
-        .await
-        .and_then(|mut response| response.take::<Vec<Debt>>(0))?;
+        .await?;
+    let settlement = response
+        .take::<Option<SettlementMode>>(0)?
+        .unwrap_or_default();
+    let simplification = response
+        .take::<Option<SimplificationMode>>(1)?
+        .unwrap_or_default();
+
+    let debts = match simplification {
+        SimplificationMode::Full => {
+            let mut nets = db
+                .query(format!(
+                    "SELECT id, {NET}, {NUMBER}
+                    FROM {TRAVELER_TB}
+                    WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
+                    ORDER BY {NUMBER}"
+                ))
+                .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+                .await
+                .and_then(|mut response| response.take::<Vec<TravelerNet>>(0))?
+                .into_iter()
+                .map(|traveler| (traveler.id, traveler.net))
+                .collect::<Vec<_>>();
+            if from_ledger {
+                let expenses = ledger_expenses(db.clone(), chat_id, None).await?;
+                let transfers = ledger_transfers(db.clone(), chat_id, None).await?;
+                let positions = ledger::net_positions(&expenses, &transfers)
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                for (traveler, net) in nets.iter_mut() {
+                    *net = positions
+                        .iter()
+                        .find(|(id, _)| id == traveler)
+                        .map_or(Decimal::ZERO, |(_, net)| *net);
+                }
+            }
+            let cannot_pay = CannotPay::db_select(db.clone(), chat_id)
+                .await?
+                .into_iter()
+                .map(|preference| (preference.r#in, preference.out))
+                .collect::<Vec<_>>();
+            let settles_via = SettlesVia::db_select(db.clone(), chat_id)
+                .await?
+                .into_iter()
+                .map(|preference| (preference.r#in, preference.out))
+                .collect::<Vec<_>>();
+            settle_nets(&nets, settlement, &cannot_pay, &settles_via)
+        }
+        // Payment preferences only apply to fully simplified debts, as the other modes keep who paid for whom
+        SimplificationMode::None | SimplificationMode::Pairwise => {
+            let expenses = ledger_expenses(db.clone(), chat_id, None).await?;
+            let transfers = ledger_transfers(db.clone(), chat_id, None).await?;
+            simplify_debts(
+                ledger::debts(&expenses, &transfers),
+                simplification,
+                settlement,
+                &[],
+                &[],
+            )
+        }
+    };
+    Ok(debts)
+}
+
+/// Updates the debts for the active trip of a given chat, as computed by [`compute_debts`] from the net positions of
+/// the travelers.
+pub async fn update_debts(db: Arc<Surreal<Any>>, chat_id: ChatId) -> Result<(), surrealdb::Error> {
+    use crate::{
+        chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+        owes::{AMOUNT, TABLE as OWES},
+    };
+    const DEBTOR: &str = "debtor";
+    const CREDITOR: &str = "creditor";
+
+    let debts = compute_debts(db.clone(), chat_id, false).await?;
 
     let mut query = db
         .query(BeginStatement::default())
-        .query(format!("{FN_CLEAR_DEBTS}(${CHAT_ID})"))
+        .query(format!("{FN_CLEAR_DEBTS}(${CHAT_ID}.{ACTIVE_TRIP})"))
         .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)));
 
-    simplify_balances(&mut debts);
-
     for (
         i,
         Debt {
@@ -126,7 +327,7 @@ pub async fn update_debts(db: Arc<Surreal<Any>>, chat_id: ChatId) -> Result<(),
     {
         query = query
             .query(format!(
-                "RELATE ${DEBTOR}_{i}->{OWES}->${CREDITOR}_{i} 
+                "RELATE ${DEBTOR}_{i}->{OWES}->${CREDITOR}_{i}
                 SET {AMOUNT} = <decimal> ${AMOUNT}_{i}"
             ))
             .bind((format!("{DEBTOR}_{i}"), debtor))
@@ -137,3 +338,108 @@ pub async fn update_debts(db: Arc<Surreal<Any>>, chat_id: ChatId) -> Result<(),
     query = query.query(CommitStatement::default());
     query.await.map(|_| {})
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::db,
+        expense::Expense as ExpenseRecord,
+        tests::{TestBot, helpers},
+        transferred_to::TransferredTo,
+    };
+    use std::collections::BTreeMap;
+
+    /// Net positions kept up to date by expenses and transfers, by traveler.
+    async fn nets(db: Arc<Surreal<Any>>, chat_id: ChatId) -> BTreeMap<String, Decimal> {
+        use crate::{
+            chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+            traveler::{TABLE as TRAVELER_TB, TRIP},
+        };
+
+        db.query(format!(
+            "SELECT id, {NET} FROM {TRAVELER_TB} WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}"
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .await
+        .and_then(|mut response| response.take::<Vec<TravelerNet>>(0))
+        .unwrap()
+        .into_iter()
+        .filter(|traveler| !traveler.net.is_zero())
+        .map(|traveler| (traveler.id.to_string(), traveler.net))
+        .collect()
+    }
+
+    /// Net positions recomputed from every expense and transfer.
+    async fn recomputed_nets(db: Arc<Surreal<Any>>, chat_id: ChatId) -> BTreeMap<String, Decimal> {
+        let expenses = ledger_expenses(db.clone(), chat_id, None).await.unwrap();
+        let transfers = ledger_transfers(db, chat_id, None).await.unwrap();
+        ledger::net_positions(&expenses, &transfers)
+            .into_iter()
+            .filter(|(_, net)| !net.is_zero())
+            .map(|(traveler, net)| (traveler.to_string(), net))
+            .collect()
+    }
+
+    test! { nets_follow_expenses_and_transfers,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+        let chat_id = bot.chat_id();
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_traveler(&mut bot, "Charlie").await;
+
+        helpers::add_expense(&mut bot, "Dinner", 90.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "Taxi", 20.into(), "Bob", &["Alice", "Bob"]).await;
+        helpers::transfer(&mut bot, "Charlie", "Alice", 15.into()).await;
+        helpers::transfer(&mut bot, "Bob", "Charlie", 5.into()).await;
+        assert!(!nets(db.clone(), chat_id).await.is_empty());
+        assert_eq!(nets(db.clone(), chat_id).await, recomputed_nets(db.clone(), chat_id).await);
+
+        ExpenseRecord::db_delete_by_number(db.clone(), chat_id, 1).await.unwrap();
+        TransferredTo::db_delete(db.clone(), chat_id, 2).await.unwrap();
+        assert_eq!(nets(db.clone(), chat_id).await, recomputed_nets(db.clone(), chat_id).await);
+
+        ExpenseRecord::db_delete_all(db.clone(), chat_id).await.unwrap();
+        TransferredTo::db_delete_all(db.clone(), chat_id).await.unwrap();
+        assert!(nets(db.clone(), chat_id).await.is_empty());
+    }
+
+    test! { deleting_twice_keeps_nets,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+        let chat_id = bot.chat_id();
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 60.into(), "Alice", &["all"]).await;
+        helpers::add_expense(&mut bot, "Taxi", 20.into(), "Bob", &["all"]).await;
+        helpers::transfer(&mut bot, "Bob", "Alice", 10.into()).await;
+
+        // The second delete finds no rows and must leave the nets untouched
+        ExpenseRecord::db_delete_by_number(db.clone(), chat_id, 1).await.unwrap();
+        ExpenseRecord::db_delete_by_number(db.clone(), chat_id, 1).await.unwrap();
+        TransferredTo::db_delete(db.clone(), chat_id, 1).await.unwrap();
+        TransferredTo::db_delete(db.clone(), chat_id, 1).await.unwrap();
+        assert!(!nets(db.clone(), chat_id).await.is_empty());
+        assert_eq!(nets(db.clone(), chat_id).await, recomputed_nets(db.clone(), chat_id).await);
+    }
+
+    test! { rebuild_repairs_nets,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+        let chat_id = bot.chat_id();
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+        helpers::add_expense(&mut bot, "Dinner", 60.into(), "Alice", &["all"]).await;
+        let expected = nets(db.clone(), chat_id).await;
+
+        // Corrupt the net positions, then rebuild them
+        db.query(format!("UPDATE traveler SET {NET} = 1000dec")).await.unwrap();
+        rebuild_debts(db.clone(), chat_id).await.unwrap();
+        assert_eq!(nets(db.clone(), chat_id).await, expected);
+        assert_eq!(expected, recomputed_nets(db, chat_id).await);
+    }
+}
@@ -0,0 +1,95 @@
+//! DOT export of a debt graph, to be laid out by Graphviz or any other tool
+//! reading it.
+
+use super::DebtGraph;
+use crate::commands::Document;
+use rust_decimal::Decimal;
+
+pub const FILE_NAME: &str = "debts.dot";
+
+/// Returns `text` as a quoted DOT identifier.
+fn quoted(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' | '\r' => quoted.push(' '),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Exports `graph` as a directed graph titled `title`, going from each
+/// debtor to their creditor, with edges labelled by `money`.
+pub fn export(graph: &DebtGraph, title: &str, money: impl Fn(Decimal) -> String) -> Document {
+    let mut dot = String::from("digraph debts {\n");
+    dot.push_str(&format!("    label={};\n", quoted(title)));
+    dot.push_str("    labelloc=t;\n");
+    dot.push_str("    node [shape=box];\n");
+    for traveler in &graph.travelers {
+        dot.push_str(&format!("    {};\n", quoted(traveler)));
+    }
+    for edge in &graph.edges {
+        dot.push_str(&format!(
+            "    {} -> {} [label={}];\n",
+            quoted(&edge.debtor),
+            quoted(&edge.creditor),
+            quoted(&money(edge.amount))
+        ));
+    }
+    dot.push_str("}\n");
+
+    Document {
+        file_name: FILE_NAME.to_owned(),
+        content: dot.into_bytes(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export, quoted};
+    use crate::debt_graph::{DebtEdge, DebtGraph};
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn quote_identifiers() {
+        assert_eq!(quoted("Alice"), "\"Alice\"");
+        assert_eq!(quoted("\"Al\" \\ice"), "\"\\\"Al\\\" \\\\ice\"");
+        assert_eq!(quoted("Al\nice"), "\"Al ice\"");
+    }
+
+    #[test]
+    fn export_graph() {
+        let graph = DebtGraph {
+            travelers: ["Alice", "Bob", "Carol"]
+                .map(|name| name.parse().unwrap())
+                .to_vec(),
+            edges: vec![DebtEdge {
+                debtor: "Bob".parse().unwrap(),
+                creditor: "Alice".parse().unwrap(),
+                amount: Decimal::new(1050, 2),
+            }],
+            raw: false,
+        };
+        let document = export(&graph, "Who owes whom", |amount| format!("${amount}"));
+        assert_eq!(document.file_name, "debts.dot");
+        assert_eq!(
+            String::from_utf8(document.content).unwrap(),
+            "digraph debts {\n    \
+                label=\"Who owes whom\";\n    \
+                labelloc=t;\n    \
+                node [shape=box];\n    \
+                \"Alice\";\n    \
+                \"Bob\";\n    \
+                \"Carol\";\n    \
+                \"Bob\" -> \"Alice\" [label=\"$10.50\"];\n\
+            }\n"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Graph of who owes whom, sent by `/debtgraph` as an image or as a DOT file.
+//!
+//! Edges are the debts kept for the active trip, as shown by
+//! `/showbalances`, or the debts computed from each expense and transfer
+//! before any simplification, to see where the simplified ones come from.
+
+pub mod dot;
+
+use crate::{
+    balance::Balance,
+    debt::{ledger_expenses, ledger_transfers},
+    i18n::labels::{DEBT_GRAPH, DEBT_GRAPH_RAW},
+    traveler::{Name, Traveler},
+};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use surrealdb::{RecordId, Surreal, engine::any::Any};
+use teloxide::types::ChatId;
+use travel_rs_core::{is_zero, ledger};
+
+/// Money a traveler owes to another one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebtEdge {
+    pub debtor: Name,
+    pub creditor: Name,
+    pub amount: Decimal,
+}
+
+/// Travelers of a trip and the debts among them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebtGraph {
+    /// Every traveler of the trip, by name, including the settled ones.
+    pub travelers: Vec<Name>,
+    /// Debts, the largest first.
+    pub edges: Vec<DebtEdge>,
+    /// Whether the debts are the ones before simplification.
+    pub raw: bool,
+}
+
+impl DebtGraph {
+    /// Collects the debts of the active trip of a given chat: the ones kept
+    /// after simplification, or the ones owed for each expense and transfer
+    /// if `raw` is set.
+    pub async fn db_select(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        raw: bool,
+    ) -> Result<Self, surrealdb::Error> {
+        let travelers = Traveler::db_select(db.clone(), chat_id).await?;
+        let mut edges = if raw {
+            let expenses = ledger_expenses(db.clone(), chat_id, None).await?;
+            let transfers = ledger_transfers(db, chat_id, None).await?;
+            let name = |id: &RecordId| {
+                travelers
+                    .iter()
+                    .find(|traveler| &traveler.id == id)
+                    .map(|traveler| traveler.name.clone())
+            };
+            ledger::debts(&expenses, &transfers)
+                .into_iter()
+                .filter_map(|debt| {
+                    Some(DebtEdge {
+                        debtor: name(&debt.debtor)?,
+                        creditor: name(&debt.creditor)?,
+                        amount: debt.debt,
+                    })
+                })
+                .collect::<Vec<_>>()
+        } else {
+            Balance::balances(db, chat_id)
+                .await?
+                .into_iter()
+                .map(|balance| DebtEdge {
+                    debtor: balance.debtor_name,
+                    creditor: balance.creditor_name,
+                    amount: balance.debt,
+                })
+                .collect()
+        };
+        edges.retain(|edge| !is_zero(edge.amount));
+        edges.sort_by(|a, b| {
+            b.amount
+                .cmp(&a.amount)
+                .then_with(|| a.debtor.cmp(&b.debtor))
+                .then_with(|| a.creditor.cmp(&b.creditor))
+        });
+
+        Ok(Self {
+            travelers: travelers
+                .into_iter()
+                .map(|traveler| traveler.name)
+                .collect(),
+            edges,
+            raw,
+        })
+    }
+
+    /// Key of the localized title of the graph.
+    pub fn title(&self) -> &'static str {
+        if self.raw { DEBT_GRAPH_RAW } else { DEBT_GRAPH }
+    }
+}
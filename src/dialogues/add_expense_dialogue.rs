@@ -1,13 +1,17 @@
 use crate::{
     Context, HandlerResult,
+    commands::closed_trip_refusal,
     consts::*,
     errors::{AddExpenseError, EndError},
     expense::Expense,
     i18n::{self, Translate, TranslateWithArgs},
-    keyboard,
+    keyboard, timezone,
+    topics::{DialogueKey, InTopic, MessageExt},
     traveler::{Name, Traveler},
     update_debts,
 };
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, Utc};
+use jiff::tz::TimeZone;
 use macro_rules_attribute::apply;
 use maplit::hashmap;
 use regex::Regex;
@@ -19,7 +23,7 @@ use std::{
     sync::{Arc, LazyLock, Mutex},
 };
 use surrealdb::{
-    RecordId, Surreal,
+    Datetime, RecordId, Surreal,
     engine::any::Any,
     sql::statements::{BeginStatement, CommitStatement},
 };
@@ -32,6 +36,7 @@ use teloxide::{
     types::{CallbackQuery, ChatId, InlineKeyboardButton, Message},
 };
 use tracing::Level;
+use travel_rs_core::shares::{ShareAmount, compute_shares};
 
 type AddExpenseDialogue = Dialogue<AddExpenseState, InMemStorage<AddExpenseState>>;
 
@@ -44,6 +49,13 @@ const CANCEL_CALLBACK: &str = "addexp_payer:__cancel__";
 /// Noop sentinel for spacer buttons.
 const NOOP_CALLBACK: &str = "addexp_payer:__noop__";
 
+/// Prefix for the date picker keyboard.
+pub const CALLBACK_PREFIX_DATE: &str = "addexp_date:";
+/// "Today" button callback.
+const TODAY_CALLBACK_DATE: &str = "addexp_date:today";
+/// "Yesterday" button callback.
+const YESTERDAY_CALLBACK_DATE: &str = "addexp_date:yesterday";
+
 /// Prefix for the split-among traveler picker keyboard.
 pub const CALLBACK_PREFIX_SPLIT: &str = "addexp_split:";
 /// Cancel sentinel for the split step.
@@ -70,24 +82,34 @@ static SPLIT_AMONG_REGEX: LazyLock<Regex> = LazyLock::new(|| {
 pub enum AddExpenseState {
     #[default]
     Start,
-    ReceiveDescription,
+    ReceiveDescription {
+        occurred_at: Option<Datetime>,
+    },
     ReceiveAmount {
         description: String,
+        occurred_at: Option<Datetime>,
+    },
+    ReceiveDate {
+        description: String,
+        amount: Decimal,
     },
     ReceivePaidBy {
         description: String,
         amount: Decimal,
+        occurred_at: Datetime,
     },
     StartSplitAmong {
         description: String,
         amount: Decimal,
+        occurred_at: Datetime,
         paid_by: Traveler,
     },
     ReceiveSplitAmong {
         description: String,
         amount: Decimal,
+        occurred_at: Datetime,
         paid_by: Traveler,
-        split_among: BTreeMap<Name, AmountEnum>,
+        split_among: BTreeMap<Name, ShareAmount>,
     },
 }
 
@@ -105,13 +127,6 @@ pub enum SplitAmongEnum {
     End,
 }
 
-#[derive(Debug, Clone)]
-pub enum AmountEnum {
-    Fixed(Decimal),
-    Percentage(Decimal),
-    Dynamic,
-}
-
 // Helper struct to handle split among input and update dialogue or end
 struct SplitAmongInput {
     db: Arc<Surreal<Any>>,
@@ -119,8 +134,9 @@ struct SplitAmongInput {
     dialogue: AddExpenseDialogue,
     description: String,
     amount: Decimal,
+    occurred_at: Datetime,
     paid_by: Traveler,
-    split_among: BTreeMap<Name, AmountEnum>,
+    split_among: BTreeMap<Name, ShareAmount>,
     msg: Message,
     ctx: Arc<Mutex<Context>>,
 }
@@ -129,17 +145,46 @@ struct SplitAmongInput {
 pub async fn start(
     bot: Bot,
     dialogue: AddExpenseDialogue,
+    args: String, // Available from `Command::AddExpense`.
     msg: Message,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
     tracing::debug!("{LOG_DEBUG_START}");
+    let args = args.trim();
+    let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
+    let occurred_at = if args.is_empty() {
+        None
+    } else {
+        let parsed = args
+            .strip_prefix(DATE_OPTION)
+            .filter(|date| date.starts_with(char::is_whitespace))
+            .and_then(|date| parse_occurred_at(date, Utc::now(), &tz));
+        match parsed {
+            Some(occurred_at) => Some(Datetime::from(occurred_at)),
+            None => {
+                tracing::warn!("Invalid arguments: received `{args}`.");
+                bot.send_message(
+                    msg.chat.id,
+                    i18n::dialogues::ADD_EXPENSE_INVALID_DATE_OPTION.translate(ctx),
+                )
+                .in_topic(msg.topic_id())
+                .await?;
+                dialogue.exit().await?;
+                return Ok(());
+            }
+        }
+    };
     let reply = format!(
         "{start}\n\n{ask_description}",
         start = i18n::dialogues::ADD_EXPENSE_START.translate(ctx.clone()),
         ask_description = i18n::dialogues::ADD_EXPENSE_ASK_DESCRIPTION.translate(ctx)
     );
-    bot.send_message(msg.chat.id, reply).await?;
-    dialogue.update(AddExpenseState::ReceiveDescription).await?;
+    bot.send_message(msg.chat.id, reply)
+        .in_topic(msg.topic_id())
+        .await?;
+    dialogue
+        .update(AddExpenseState::ReceiveDescription { occurred_at })
+        .await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     tracing::info!("Dialogue started: /addexpense");
     Ok(())
@@ -149,6 +194,7 @@ pub async fn start(
 pub async fn receive_description(
     bot: Bot,
     dialogue: AddExpenseDialogue,
+    occurred_at: Option<Datetime>, // Available from `AddExpenseState::ReceiveDescription`.
     msg: Message,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
@@ -162,6 +208,7 @@ pub async fn receive_description(
                     msg.chat.id,
                     i18n::dialogues::ADD_EXPENSE_INVALID_DESCRIPTION.translate(ctx),
                 )
+                .in_topic(msg.topic_id())
                 .await?;
                 return Ok(());
             }
@@ -169,10 +216,12 @@ pub async fn receive_description(
                 msg.chat.id,
                 i18n::dialogues::ADD_EXPENSE_ASK_AMOUNT.translate(ctx),
             )
+            .in_topic(msg.topic_id())
             .await?;
             dialogue
                 .update(AddExpenseState::ReceiveAmount {
                     description: trimmed.to_owned(),
+                    occurred_at,
                 })
                 .await?;
             tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -183,6 +232,7 @@ pub async fn receive_description(
                 msg.chat.id,
                 i18n::dialogues::ADD_EXPENSE_INVALID_DESCRIPTION.translate(ctx),
             )
+            .in_topic(msg.topic_id())
             .await?;
         }
     }
@@ -195,7 +245,7 @@ pub async fn receive_amount(
     db: Arc<Surreal<Any>>,
     bot: Bot,
     dialogue: AddExpenseDialogue,
-    description: String, // Available from `AddExpenseState::ReceiveAmount`.
+    (description, occurred_at): (String, Option<Datetime>), // Available from `AddExpenseState::ReceiveAmount`.
     msg: Message,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
@@ -209,16 +259,32 @@ pub async fn receive_amount(
                     msg.chat.id,
                     i18n::dialogues::ADD_EXPENSE_NON_POSITIVE_AMOUNT.translate(ctx),
                 )
+                .in_topic(msg.topic_id())
                 .await?;
                 return Ok(());
             }
-            send_ask_paid_by(&bot, db, msg.chat.id, ctx).await?;
-            dialogue
-                .update(AddExpenseState::ReceivePaidBy {
-                    description,
-                    amount,
-                })
-                .await?;
+            match occurred_at {
+                Some(occurred_at) => {
+                    send_ask_paid_by(&bot, db, &msg, ctx).await?;
+                    dialogue
+                        .update(AddExpenseState::ReceivePaidBy {
+                            description,
+                            amount,
+                            occurred_at,
+                        })
+                        .await?;
+                }
+                None => {
+                    let text = i18n::dialogues::ADD_EXPENSE_ASK_DATE.translate(ctx.clone());
+                    send_date_prompt(&bot, &msg, &text, ctx).await?;
+                    dialogue
+                        .update(AddExpenseState::ReceiveDate {
+                            description,
+                            amount,
+                        })
+                        .await?;
+                }
+            }
             tracing::debug!("{LOG_DEBUG_SUCCESS}");
         }
         _ => {
@@ -227,6 +293,7 @@ pub async fn receive_amount(
                 msg.chat.id,
                 i18n::dialogues::ADD_EXPENSE_INVALID_AMOUNT.translate(ctx),
             )
+            .in_topic(msg.topic_id())
             .await?;
         }
     }
@@ -234,12 +301,146 @@ pub async fn receive_amount(
     Ok(())
 }
 
+#[apply(trace_state_db)]
+pub async fn receive_date(
+    db: Arc<Surreal<Any>>,
+    bot: Bot,
+    dialogue: AddExpenseDialogue,
+    (description, amount): (String, Decimal), // Available from `AddExpenseState::ReceiveDate`.
+    msg: Message,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+    let text = msg.text();
+    let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
+    match text.and_then(|text| parse_occurred_at(text, Utc::now(), &tz)) {
+        Some(occurred_at) => {
+            send_ask_paid_by(&bot, db, &msg, ctx).await?;
+            dialogue
+                .update(AddExpenseState::ReceivePaidBy {
+                    description,
+                    amount,
+                    occurred_at: Datetime::from(occurred_at),
+                })
+                .await?;
+            tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        }
+        None => {
+            tracing::warn!("Invalid date: received `{text:?}`.");
+            let text = i18n::dialogues::ADD_EXPENSE_INVALID_DATE.translate(ctx.clone());
+            send_date_prompt(&bot, &msg, &text, ctx).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses when an expense was made: `today`, `yesterday`, or a date in the
+/// `YYYY-MM-DD` format, optionally followed by a time in the `HH:MM` format.
+/// Dates and times are local to `tz`; dates without a time are taken at
+/// midnight.
+///
+/// Returns `None` if the text is not a valid date or if it is later than `now`.
+pub fn parse_occurred_at(text: &str, now: DateTime<Utc>, tz: &TimeZone) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    let occurred_at = if text.eq_ignore_ascii_case(TODAY_KWORD) {
+        now
+    } else if text.eq_ignore_ascii_case(YESTERDAY_KWORD) {
+        now - TimeDelta::days(1)
+    } else if let Ok(date_time) = NaiveDateTime::parse_from_str(text, DATE_TIME_FORMAT) {
+        timezone::to_utc(date_time, tz)?
+    } else {
+        let date_time = NaiveDate::parse_from_str(text, DATE_FORMAT)
+            .ok()?
+            .and_hms_opt(0, 0, 0)?;
+        timezone::to_utc(date_time, tz)?
+    };
+    (occurred_at <= now).then_some(occurred_at)
+}
+
+// ─── Date keyboard helpers ───────────────────────────────────────────────────
+
+/// Sends the "when?" prompt with the "Today" and "Yesterday" buttons.
+async fn send_date_prompt(
+    bot: &Bot,
+    msg: &Message,
+    text: &str,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let kb = teloxide::types::InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback(
+            i18n::labels::TODAY_BUTTON.translate(ctx.clone()),
+            TODAY_CALLBACK_DATE.to_owned(),
+        ),
+        InlineKeyboardButton::callback(
+            i18n::labels::YESTERDAY_BUTTON.translate(ctx),
+            YESTERDAY_CALLBACK_DATE.to_owned(),
+        ),
+    ]]);
+    bot.send_message(msg.chat.id, text)
+        .in_topic(msg.topic_id())
+        .reply_markup(kb)
+        .await?;
+    Ok(())
+}
+
+// ─── Date callback handler ───────────────────────────────────────────────────
+
+/// Handles an inline-keyboard callback for the "when?" step.
+#[apply(trace_callback)]
+pub async fn receive_date_callback(
+    db: Arc<Surreal<Any>>,
+    bot: Bot,
+    dialogue: AddExpenseDialogue,
+    (description, amount): (String, Decimal),
+    q: CallbackQuery,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let _ = bot.answer_callback_query(q.id.clone()).await;
+
+    let Some(msg) = q.regular_message().cloned() else {
+        tracing::warn!("Callback query without an accessible message; ignoring");
+        return Ok(());
+    };
+
+    let data = q.data.as_deref().unwrap_or("");
+    let (label, kword) = match data {
+        TODAY_CALLBACK_DATE => (i18n::labels::TODAY_BUTTON, TODAY_KWORD),
+        YESTERDAY_CALLBACK_DATE => (i18n::labels::YESTERDAY_BUTTON, YESTERDAY_KWORD),
+        _ => {
+            tracing::warn!("Unexpected callback data in date step: {data:?}");
+            return Ok(());
+        }
+    };
+    let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
+    let Some(occurred_at) = parse_occurred_at(kword, Utc::now(), &tz) else {
+        return Ok(());
+    };
+
+    // Remove the inline keyboard and show the selected day.
+    keyboard::echo_callback_selection(&bot, &msg, &label.translate(ctx.clone())).await;
+
+    send_ask_paid_by(&bot, db, &msg, ctx).await?;
+    dialogue
+        .update(AddExpenseState::ReceivePaidBy {
+            description,
+            amount,
+            occurred_at: Datetime::from(occurred_at),
+        })
+        .await?;
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+
+    Ok(())
+}
+
 #[apply(trace_state_db)]
 pub async fn receive_paid_by(
     db: Arc<Surreal<Any>>,
     bot: Bot,
     dialogue: AddExpenseDialogue,
-    (description, amount): (String, Decimal), // Available from `AddExpenseState::ReceivePaidBy`.
+    (description, amount, occurred_at): (String, Decimal, Datetime), // Available from `AddExpenseState::ReceivePaidBy`.
     msg: Message,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
@@ -256,28 +457,29 @@ pub async fn receive_paid_by(
                         i18n::dialogues::ADD_EXPENSE_INVALID_PAID_BY.translate(ctx.clone()),
                     reason = err.translate(ctx.clone())
                 );
-                reprompt_paid_by(&bot, Arc::clone(&db), msg.chat.id, ctx, &reply).await?;
+                reprompt_paid_by(&bot, Arc::clone(&db), &msg, ctx, &reply).await?;
                 return Ok(());
             }
         },
         None => {
             tracing::warn!("Invalid name: received `{text:?}`.");
             let reply = i18n::dialogues::ADD_EXPENSE_INVALID_PAID_BY.translate(ctx.clone());
-            reprompt_paid_by(&bot, Arc::clone(&db), msg.chat.id, ctx, &reply).await?;
+            reprompt_paid_by(&bot, Arc::clone(&db), &msg, ctx, &reply).await?;
             return Ok(());
         }
     };
 
     // Select traveler from db
-    let select_res = Traveler::db_select_by_name(Arc::clone(&db), msg.chat.id, &name).await;
+    let select_res = Traveler::db_select_by_name(Arc::clone(&db), msg.ledger_id(), &name).await;
     match select_res {
         Ok(Some(traveler)) => {
             let text = i18n::dialogues::ADD_EXPENSE_ASK_SHARES.translate(ctx.clone());
-            send_split_prompt(&bot, Arc::clone(&db), msg.chat.id, &text, false, ctx).await?;
+            send_split_prompt(&bot, Arc::clone(&db), &msg, &text, false, ctx).await?;
             dialogue
                 .update(AddExpenseState::StartSplitAmong {
                     description,
                     amount,
+                    occurred_at,
                     paid_by: traveler,
                 })
                 .await?;
@@ -289,7 +491,7 @@ pub async fn receive_paid_by(
                 ctx.clone(),
                 &hashmap! {i18n::args::NAME.into() => name.into()},
             );
-            reprompt_paid_by(&bot, db, msg.chat.id, ctx, &reply).await?;
+            reprompt_paid_by(&bot, db, &msg, ctx, &reply).await?;
         }
         Err(err) => {
             tracing::error!("{err}");
@@ -297,7 +499,7 @@ pub async fn receive_paid_by(
                 ctx.clone(),
                 &hashmap! {i18n::args::NAME.into() => name.into()},
             );
-            reprompt_paid_by(&bot, db, msg.chat.id, ctx, &reply).await?;
+            reprompt_paid_by(&bot, db, &msg, ctx, &reply).await?;
         }
     }
 
@@ -310,13 +512,13 @@ pub async fn receive_paid_by(
 async fn send_ask_paid_by(
     bot: &Bot,
     db: Arc<Surreal<Any>>,
-    chat_id: ChatId,
+    msg: &Message,
     ctx: Arc<Mutex<Context>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let text = i18n::dialogues::ADD_EXPENSE_ASK_PAID_BY.translate(ctx.clone());
     let kb = keyboard::travelers_keyboard(keyboard::TravelersKeyboardConfig {
         db,
-        chat_id,
+        chat_id: msg.ledger_id(),
         prefix: CALLBACK_PREFIX,
         cancel_callback: CANCEL_CALLBACK,
         noop_callback: NOOP_CALLBACK,
@@ -326,10 +528,15 @@ async fn send_ask_paid_by(
     .await;
     match kb {
         Some(kb) => {
-            bot.send_message(chat_id, text).reply_markup(kb).await?;
+            bot.send_message(msg.chat.id, text)
+                .in_topic(msg.topic_id())
+                .reply_markup(kb)
+                .await?;
         }
         None => {
-            bot.send_message(chat_id, text).await?;
+            bot.send_message(msg.chat.id, text)
+                .in_topic(msg.topic_id())
+                .await?;
         }
     }
     Ok(())
@@ -339,13 +546,13 @@ async fn send_ask_paid_by(
 async fn reprompt_paid_by(
     bot: &Bot,
     db: Arc<Surreal<Any>>,
-    chat_id: ChatId,
+    msg: &Message,
     ctx: Arc<Mutex<Context>>,
     error_text: &str,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let kb = keyboard::travelers_keyboard(keyboard::TravelersKeyboardConfig {
         db,
-        chat_id,
+        chat_id: msg.ledger_id(),
         prefix: CALLBACK_PREFIX,
         cancel_callback: CANCEL_CALLBACK,
         noop_callback: NOOP_CALLBACK,
@@ -355,12 +562,15 @@ async fn reprompt_paid_by(
     .await;
     match kb {
         Some(kb) => {
-            bot.send_message(chat_id, error_text)
+            bot.send_message(msg.chat.id, error_text)
+                .in_topic(msg.topic_id())
                 .reply_markup(kb)
                 .await?;
         }
         None => {
-            bot.send_message(chat_id, error_text).await?;
+            bot.send_message(msg.chat.id, error_text)
+                .in_topic(msg.topic_id())
+                .await?;
         }
     }
     Ok(())
@@ -374,7 +584,7 @@ pub async fn receive_paid_by_callback(
     db: Arc<Surreal<Any>>,
     bot: Bot,
     dialogue: AddExpenseDialogue,
-    (description, amount): (String, Decimal),
+    (description, amount, occurred_at): (String, Decimal, Datetime),
     q: CallbackQuery,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
@@ -402,23 +612,22 @@ pub async fn receive_paid_by_callback(
             ctx,
             &hashmap! { i18n::args::PROCESS.into() => process_name.into() },
         );
-        bot.send_message(msg.chat.id, cancel_msg).await?;
+        bot.send_message(msg.chat.id, cancel_msg)
+            .in_topic(msg.topic_id())
+            .await?;
         dialogue.exit().await?;
         return Ok(());
     }
 
     // Strip prefix to get the selected index.
-    let raw = data
-        .strip_prefix(CALLBACK_PREFIX)
-        .unwrap_or("")
-        .to_owned();
+    let raw = data.strip_prefix(CALLBACK_PREFIX).unwrap_or("").to_owned();
     if raw.is_empty() {
         tracing::warn!("Empty value in callback data: {data:?}");
         return Ok(());
     }
 
     let Some(traveler) =
-        Traveler::db_resolve_by_number(Arc::clone(&db), msg.chat.id, &raw).await
+        Traveler::db_resolve_by_number(Arc::clone(&db), msg.ledger_id(), &raw).await
     else {
         tracing::warn!("Could not resolve traveler number from callback data: {raw:?}");
         return Ok(());
@@ -428,11 +637,12 @@ pub async fn receive_paid_by_callback(
     keyboard::echo_callback_selection(&bot, &msg, &traveler.name).await;
 
     let text = i18n::dialogues::ADD_EXPENSE_ASK_SHARES.translate(ctx.clone());
-    send_split_prompt(&bot, Arc::clone(&db), msg.chat.id, &text, false, ctx).await?;
+    send_split_prompt(&bot, Arc::clone(&db), &msg, &text, false, ctx).await?;
     dialogue
         .update(AddExpenseState::StartSplitAmong {
             description,
             amount,
+            occurred_at,
             paid_by: traveler,
         })
         .await?;
@@ -470,13 +680,16 @@ fn split_keyboard(
 async fn send_split_prompt(
     bot: &Bot,
     _db: Arc<Surreal<Any>>,
-    chat_id: ChatId,
+    msg: &Message,
     text: &str,
     has_travelers: bool,
     ctx: Arc<Mutex<Context>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let kb = split_keyboard(has_travelers, ctx);
-    bot.send_message(chat_id, text).reply_markup(kb).await?;
+    bot.send_message(msg.chat.id, text)
+        .in_topic(msg.topic_id())
+        .reply_markup(kb)
+        .await?;
     Ok(())
 }
 
@@ -488,7 +701,7 @@ pub async fn receive_split_callback(
     db: Arc<Surreal<Any>>,
     bot: Bot,
     dialogue: AddExpenseDialogue,
-    (description, amount, paid_by): (String, Decimal, Traveler),
+    (description, amount, occurred_at, paid_by): (String, Decimal, Datetime, Traveler),
     q: CallbackQuery,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
@@ -497,7 +710,7 @@ pub async fn receive_split_callback(
         db,
         bot,
         dialogue,
-        (description, amount, paid_by, BTreeMap::new()),
+        (description, amount, occurred_at, paid_by, BTreeMap::new()),
         q,
         ctx,
     )
@@ -510,11 +723,12 @@ pub async fn receive_split_continue_callback(
     db: Arc<Surreal<Any>>,
     bot: Bot,
     dialogue: AddExpenseDialogue,
-    (description, amount, paid_by, split_among): (
+    (description, amount, occurred_at, paid_by, split_among): (
         String,
         Decimal,
+        Datetime,
         Traveler,
-        BTreeMap<Name, AmountEnum>,
+        BTreeMap<Name, ShareAmount>,
     ),
     q: CallbackQuery,
     ctx: Arc<Mutex<Context>>,
@@ -524,7 +738,7 @@ pub async fn receive_split_continue_callback(
         db,
         bot,
         dialogue,
-        (description, amount, paid_by, split_among),
+        (description, amount, occurred_at, paid_by, split_among),
         q,
         ctx,
     )
@@ -535,11 +749,12 @@ async fn receive_split_callback_inner(
     db: Arc<Surreal<Any>>,
     bot: Bot,
     dialogue: AddExpenseDialogue,
-    (description, amount, paid_by, mut split_among): (
+    (description, amount, occurred_at, paid_by, mut split_among): (
         String,
         Decimal,
+        Datetime,
         Traveler,
-        BTreeMap<Name, AmountEnum>,
+        BTreeMap<Name, ShareAmount>,
     ),
     q: CallbackQuery,
     ctx: Arc<Mutex<Context>>,
@@ -560,8 +775,13 @@ async fn receive_split_callback_inner(
     // Help — show add_expense help text without dismissing the keyboard.
     if data == HELP_CALLBACK_SPLIT {
         use crate::commands::{Command, HelpMessage};
-        let help_text = Command::AddExpense.help_message(ctx);
-        bot.send_message(msg.chat.id, help_text).await?;
+        let help_text = Command::AddExpense {
+            args: String::new(),
+        }
+        .help_message(ctx);
+        bot.send_message(msg.chat.id, help_text)
+            .in_topic(msg.topic_id())
+            .await?;
         return Ok(());
     }
 
@@ -573,7 +793,9 @@ async fn receive_split_callback_inner(
             ctx,
             &hashmap! { i18n::args::PROCESS.into() => process_name.into() },
         );
-        bot.send_message(msg.chat.id, cancel_msg).await?;
+        bot.send_message(msg.chat.id, cancel_msg)
+            .in_topic(msg.topic_id())
+            .await?;
         dialogue.exit().await?;
         return Ok(());
     }
@@ -583,14 +805,16 @@ async fn receive_split_callback_inner(
         let label = i18n::labels::ALL_BUTTON.translate(ctx.clone());
         keyboard::echo_callback_selection(&bot, &msg, &label).await;
         // Simulate "all" text input
-        let result = parse_split_among(db.clone(), ALL_KWORD, msg.chat.id, &mut split_among).await;
+        let result =
+            parse_split_among(db.clone(), ALL_KWORD, msg.ledger_id(), &mut split_among).await;
         match result {
             Ok(SplitAmongEnum::End) => {
                 match end(
                     db.clone(),
                     &dialogue,
-                    (&description, amount, &paid_by, split_among),
-                    msg.chat.id,
+                    (&description, amount, &occurred_at, &paid_by, split_among),
+                    &msg,
+                    ctx.clone(),
                 )
                 .await
                 {
@@ -600,15 +824,24 @@ async fn receive_split_callback_inner(
                             expense_added = i18n::dialogues::ADD_EXPENSE_OK.translate(ctx.clone()),
                             format_expense = expense.translate(ctx)
                         );
-                        bot.send_message(msg.chat.id, reply).await?;
+                        bot.send_message(msg.chat.id, reply)
+                            .in_topic(msg.topic_id())
+                            .await?;
+                    }
+                    Err(err @ EndError::TripClosed(_)) => {
+                        bot.send_message(msg.chat.id, err.translate(ctx))
+                            .in_topic(msg.topic_id())
+                            .await?;
                     }
                     Err(err) => {
                         let reply = err.translate(ctx.clone());
-                        bot.send_message(msg.chat.id, reply).await?;
+                        bot.send_message(msg.chat.id, reply)
+                            .in_topic(msg.topic_id())
+                            .await?;
                         send_split_prompt(
                             &bot,
                             db,
-                            msg.chat.id,
+                            &msg,
                             &i18n::dialogues::ADD_EXPENSE_ASK_SHARES.translate(ctx.clone()),
                             false,
                             ctx,
@@ -622,7 +855,7 @@ async fn receive_split_callback_inner(
                 send_split_prompt(
                     &bot,
                     db,
-                    msg.chat.id,
+                    &msg,
                     &i18n::dialogues::ADD_EXPENSE_ASK_SHARES.translate(ctx.clone()),
                     !split_among.is_empty(),
                     ctx,
@@ -645,8 +878,9 @@ async fn receive_split_callback_inner(
         match end(
             db.clone(),
             &dialogue,
-            (&description, amount, &paid_by, split_among),
-            msg.chat.id,
+            (&description, amount, &occurred_at, &paid_by, split_among),
+            &msg,
+            ctx.clone(),
         )
         .await
         {
@@ -656,11 +890,15 @@ async fn receive_split_callback_inner(
                     expense_added = i18n::dialogues::ADD_EXPENSE_OK.translate(ctx.clone()),
                     format_expense = expense.translate(ctx)
                 );
-                bot.send_message(msg.chat.id, reply).await?;
+                bot.send_message(msg.chat.id, reply)
+                    .in_topic(msg.topic_id())
+                    .await?;
             }
             Err(err) => {
                 let reply = err.translate(ctx.clone());
-                bot.send_message(msg.chat.id, reply).await?;
+                bot.send_message(msg.chat.id, reply)
+                    .in_topic(msg.topic_id())
+                    .await?;
             }
         }
         tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -677,7 +915,7 @@ pub async fn start_split_among(
     db: Arc<Surreal<Any>>,
     bot: Bot,
     dialogue: AddExpenseDialogue,
-    (description, amount, paid_by): (String, Decimal, Traveler), // Available from `AddExpenseState::StartSplitAmong`.
+    (description, amount, occurred_at, paid_by): (String, Decimal, Datetime, Traveler), // Available from `AddExpenseState::StartSplitAmong`.
     msg: Message,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
@@ -687,6 +925,7 @@ pub async fn start_split_among(
         dialogue,
         description,
         amount,
+        occurred_at,
         paid_by,
         split_among: BTreeMap::new(),
         msg,
@@ -700,11 +939,12 @@ pub async fn receive_split_among(
     db: Arc<Surreal<Any>>,
     bot: Bot,
     dialogue: AddExpenseDialogue,
-    (description, amount, paid_by, split_among): (
+    (description, amount, occurred_at, paid_by, split_among): (
         String,
         Decimal,
+        Datetime,
         Traveler,
-        BTreeMap<Name, AmountEnum>,
+        BTreeMap<Name, ShareAmount>,
     ), // Available from `AddExpenseState::ReceiveSplitAmong`.
     msg: Message,
     ctx: Arc<Mutex<Context>>,
@@ -715,6 +955,7 @@ pub async fn receive_split_among(
         dialogue,
         description,
         amount,
+        occurred_at,
         paid_by,
         split_among,
         msg,
@@ -730,6 +971,7 @@ async fn handle_split_among_input(input: SplitAmongInput) -> HandlerResult {
         dialogue,
         description,
         amount,
+        occurred_at,
         paid_by,
         mut split_among,
         msg,
@@ -739,15 +981,15 @@ async fn handle_split_among_input(input: SplitAmongInput) -> HandlerResult {
     match msg.text() {
         Some(text) => {
             tracing::debug!("Received text: `{text}`.");
-            match parse_split_among(db.clone(), text, msg.chat.id, &mut split_among).await {
+            match parse_split_among(db.clone(), text, msg.ledger_id(), &mut split_among).await {
                 Ok(SplitAmongEnum::List) => {
                     let prompt = i18n::dialogues::ADD_EXPENSE_CONTINUE_SPLIT.translate(ctx.clone());
-                    send_split_prompt(&bot, Arc::clone(&db), msg.chat.id, &prompt, true, ctx)
-                        .await?;
+                    send_split_prompt(&bot, Arc::clone(&db), &msg, &prompt, true, ctx).await?;
                     dialogue
                         .update(AddExpenseState::ReceiveSplitAmong {
                             description,
                             amount,
+                            occurred_at,
                             paid_by,
                             split_among,
                         })
@@ -759,8 +1001,9 @@ async fn handle_split_among_input(input: SplitAmongInput) -> HandlerResult {
                     match end(
                         db,
                         &dialogue,
-                        (&description, amount, &paid_by, split_among),
-                        msg.chat.id,
+                        (&description, amount, &occurred_at, &paid_by, split_among),
+                        &msg,
+                        ctx.clone(),
                     )
                     .await
                     {
@@ -771,11 +1014,17 @@ async fn handle_split_among_input(input: SplitAmongInput) -> HandlerResult {
                                     i18n::dialogues::ADD_EXPENSE_OK.translate(ctx.clone()),
                                 format_expense = expense.translate(ctx)
                             );
-                            bot.send_message(msg.chat.id, reply).await?;
+                            bot.send_message(msg.chat.id, reply)
+                                .in_topic(msg.topic_id())
+                                .await?;
                         }
                         Err(err) => match err {
-                            EndError::ClosingDialogue | EndError::NoExpenseCreated => {
-                                bot.send_message(msg.chat.id, err.translate(ctx)).await?;
+                            EndError::ClosingDialogue
+                            | EndError::NoExpenseCreated
+                            | EndError::TripClosed(_) => {
+                                bot.send_message(msg.chat.id, err.translate(ctx))
+                                    .in_topic(msg.topic_id())
+                                    .await?;
                             }
                             EndError::AddExpense(err) => {
                                 let mut reply =
@@ -792,12 +1041,15 @@ async fn handle_split_among_input(input: SplitAmongInput) -> HandlerResult {
                                             .translate(ctx);
                                     }
                                 }
-                                bot.send_message(msg.chat.id, reply).await?;
+                                bot.send_message(msg.chat.id, reply)
+                                    .in_topic(msg.topic_id())
+                                    .await?;
                                 if expense_is_too_high {
                                     dialogue
                                         .update(AddExpenseState::ReceiveSplitAmong {
                                             description,
                                             amount,
+                                            occurred_at,
                                             paid_by,
                                             split_among: BTreeMap::new(),
                                         })
@@ -810,6 +1062,7 @@ async fn handle_split_among_input(input: SplitAmongInput) -> HandlerResult {
                                     i18n::dialogues::ADD_EXPENSE_CREATING_EXPENSE_GENERIC_ERROR
                                         .translate(ctx),
                                 )
+                                .in_topic(msg.topic_id())
                                 .await?;
                             }
                         },
@@ -828,12 +1081,15 @@ async fn handle_split_among_input(input: SplitAmongInput) -> HandlerResult {
                             reply += &i18n::dialogues::ADD_EXPENSE_SHARES_CLEARED.translate(ctx);
                         }
                     }
-                    bot.send_message(msg.chat.id, reply).await?;
+                    bot.send_message(msg.chat.id, reply)
+                        .in_topic(msg.topic_id())
+                        .await?;
                     if expense_is_too_high {
                         dialogue
                             .update(AddExpenseState::ReceiveSplitAmong {
                                 description,
                                 amount,
+                                occurred_at,
                                 paid_by,
                                 split_among: BTreeMap::new(),
                             })
@@ -848,6 +1104,7 @@ async fn handle_split_among_input(input: SplitAmongInput) -> HandlerResult {
                 msg.chat.id,
                 i18n::dialogues::ADD_EXPENSE_INVALID_SHARES.translate(ctx),
             )
+            .in_topic(msg.topic_id())
             .await?;
         }
     }
@@ -862,19 +1119,35 @@ async fn handle_split_among_input(input: SplitAmongInput) -> HandlerResult {
 pub async fn end(
     db: Arc<Surreal<Any>>,
     dialogue: &AddExpenseDialogue,
-    (description, amount, paid_by, split_among): (
+    (description, amount, occurred_at, paid_by, split_among): (
         &str,
         Decimal,
+        &Datetime,
         &Traveler,
-        BTreeMap<Name, AmountEnum>,
+        BTreeMap<Name, ShareAmount>,
     ),
-    chat_id: ChatId,
+    msg: &Message,
+    ctx: Arc<Mutex<Context>>,
 ) -> Result<Expense, EndError> {
     tracing::debug!("{LOG_DEBUG_START}");
-    match compute_shares(amount, split_among) {
+    // The trip may have been closed from another topic sharing the ledger
+    if let Some(refusal) = closed_trip_refusal(db.clone(), msg, ctx).await {
+        if let Err(err) = dialogue.exit().await {
+            tracing::warn!("{err}");
+        }
+        return Err(EndError::TripClosed(refusal));
+    }
+    let chat_id = msg.ledger_id();
+    match compute_shares(amount, split_among).map_err(AddExpenseError::from) {
         Ok(shares) => {
-            let create_res =
-                Expense::db_create(db.clone(), chat_id, String::from(description), amount).await;
+            let create_res = Expense::db_create(
+                db.clone(),
+                chat_id,
+                String::from(description),
+                amount,
+                occurred_at.clone(),
+            )
+            .await;
             match create_res {
                 Ok(Some(expense)) => {
                     if let Err(err_relate) =
@@ -931,7 +1204,7 @@ async fn parse_split_among(
     db: Arc<Surreal<Any>>,
     text: &str,
     chat_id: ChatId,
-    split_among: &mut BTreeMap<Name, AmountEnum>,
+    split_among: &mut BTreeMap<Name, ShareAmount>,
 ) -> Result<SplitAmongEnum, AddExpenseError> {
     let text = text.trim();
     let text_lower = text.to_lowercase();
@@ -956,7 +1229,7 @@ async fn parse_split_among(
             &mut travelers
                 .into_iter()
                 .filter(|traveler| !already_added.contains(&traveler.name.to_lowercase()))
-                .map(|traveler| (traveler.name, AmountEnum::Dynamic))
+                .map(|traveler| (traveler.name, ShareAmount::Dynamic))
                 .collect(),
         );
         Ok(SplitAmongEnum::End)
@@ -988,20 +1261,20 @@ async fn parse_split_among(
                     .expect("The string should represent a positive number"); // Can unwrap since the regex only matches positive numbers
 
                 if caps.name(SPLIT_AMONG_REGEX_PERCENTAGE_GRP).is_some() {
-                    split_among.insert(name, AmountEnum::Percentage(amount));
+                    split_among.insert(name, ShareAmount::Percentage(amount));
                 } else {
-                    split_among.insert(name, AmountEnum::Fixed(amount));
+                    split_among.insert(name, ShareAmount::Fixed(amount));
                 }
             } else {
-                split_among.insert(name, AmountEnum::Dynamic);
+                split_among.insert(name, ShareAmount::Dynamic);
             }
         }
 
         // Check if the traveler names are valid
         {
             use crate::{
-                chat::{ID as CHAT_ID, TABLE as CHAT_TB},
-                traveler::{CHAT, NAME, TABLE as TRAVELER_TB},
+                chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+                traveler::{NAME, TABLE as TRAVELER_TB, TRIP},
             };
             const NAMES: &str = "names";
 
@@ -1012,7 +1285,7 @@ async fn parse_split_among(
                     "SELECT *
                     FROM {TRAVELER_TB}
                     WHERE
-                        {CHAT} = ${CHAT_ID}
+                        {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
                         && string::lowercase({NAME}) IN ${NAMES}",
                 ))
                 .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
@@ -1047,75 +1320,6 @@ async fn parse_split_among(
     }
 }
 
-fn compute_shares(
-    tot_amount: Decimal,
-    mut split_among: BTreeMap<Name, AmountEnum>,
-) -> Result<BTreeMap<Name, Decimal>, AddExpenseError> {
-    // Start with the total amount to be split
-    let mut residual = tot_amount;
-    let mut count_dynamics = 0;
-
-    // First pass: subtract fixed shares and count dynamic shares
-    for share in split_among.values() {
-        match share {
-            AmountEnum::Fixed(amount) => {
-                residual -= amount;
-                // If the sum of fixed shares exceeds the total, return error
-                if residual < Decimal::ZERO {
-                    return Err(AddExpenseError::ExpenseTooHigh { tot_amount });
-                }
-            }
-            AmountEnum::Dynamic => count_dynamics += 1,
-            AmountEnum::Percentage(_) => {} // Percentages handled in next pass
-        }
-    }
-
-    // Save the current residual for percentage calculation
-    let residual_backup = residual;
-    // Second pass: convert percentage shares to fixed amounts
-    split_among.values_mut().for_each(|share| {
-        if let AmountEnum::Percentage(amount) = share {
-            // Calculate fixed amount for this percentage
-            let fixed = residual_backup * *amount / Decimal::from(100);
-            *share = AmountEnum::Fixed(fixed);
-            residual -= fixed;
-        }
-    });
-
-    // If there are no dynamic shares and residual remains, it's too low
-    if count_dynamics == 0 && residual > Decimal::ZERO {
-        return Err(AddExpenseError::ExpenseTooLow {
-            expense: tot_amount - residual,
-            tot_amount,
-        });
-    }
-
-    // Divide the remaining residual equally among dynamic shares
-    let split_residual = if count_dynamics > 0 {
-        residual
-            .checked_div(Decimal::from(count_dynamics))
-            .expect("count_blanks should be positive")
-    } else {
-        // No dynamic shares, so the remaining residual is not assigned to anyone
-        Decimal::ZERO
-    };
-
-    // Build the final shares map
-    Ok(split_among
-        .into_iter()
-        .map(|(name, share)| {
-            let amount = match share {
-                AmountEnum::Fixed(amount) => amount,
-                AmountEnum::Dynamic => split_residual,
-                AmountEnum::Percentage(_) => {
-                    unreachable!("Already converted to fixed amounts")
-                }
-            };
-            (name, amount)
-        })
-        .collect())
-}
-
 async fn relate_shares(
     db: Arc<Surreal<Any>>,
     paid_by: &Traveler,
@@ -1123,11 +1327,11 @@ async fn relate_shares(
     shares: BTreeMap<Name, Decimal>,
 ) -> Result<(), surrealdb::Error> {
     use crate::{
-        chat::TABLE as CHAT,
+        debt::NET,
         expense::TABLE as EXPENSE,
         paid_for::TABLE as PAID_FOR_TB,
         split::{AMOUNT, TABLE as SPLIT_TB},
-        traveler::{NAME, TABLE as TRAVELER_TB},
+        traveler::{NAME, TABLE as TRAVELER_TB, TRIP},
     };
     const PAID_BY: &str = "paid_by";
 
@@ -1136,7 +1340,29 @@ async fn relate_shares(
         .query(format!("RELATE ${PAID_BY}->{PAID_FOR_TB}->${EXPENSE}"))
         .bind((PAID_BY, paid_by.id.clone()))
         .bind((EXPENSE, expense.id.clone()))
-        .bind((CHAT, expense.chat.clone()));
+        .bind((TRIP, expense.trip.clone()));
+
+    // What the expense adds to the net positions of the travelers, by name
+    let net_changes = travel_rs_core::Expense {
+        payer: paid_by.name.clone(),
+        amount: expense.amount,
+        splits: shares
+            .iter()
+            .map(|(name, amount)| travel_rs_core::Split {
+                traveler: name.clone(),
+                amount: *amount,
+            })
+            .collect(),
+    }
+    .net_changes()
+    .into_iter()
+    .fold(
+        BTreeMap::<Name, Decimal>::new(),
+        |mut nets, (name, change)| {
+            *nets.entry(name).or_default() += change;
+            nets
+        },
+    );
 
     for (i, (name, amount)) in shares.into_iter().enumerate() {
         // Relate travelers with expense specifying their share of the expense
@@ -1145,7 +1371,7 @@ async fn relate_shares(
                 "RELATE (
                     SELECT * FROM {TRAVELER_TB} 
                     WHERE
-                        {CHAT} = ${CHAT}
+                        {TRIP} = ${TRIP}
                         && {NAME} = ${NAME}_{i}
                 )->{SPLIT_TB}->${EXPENSE}
                 SET {AMOUNT} = <decimal> ${AMOUNT}_{i}"
@@ -1154,6 +1380,19 @@ async fn relate_shares(
             .bind((format!("{AMOUNT}_{i}"), amount));
     }
 
+    for (i, (name, change)) in net_changes.into_iter().enumerate() {
+        query = query
+            .query(format!(
+                "UPDATE {TRAVELER_TB}
+                SET {NET} += <decimal> ${NET}_{i}
+                WHERE
+                    {TRIP} = ${TRIP}
+                    && {NAME} = ${NET}_{NAME}_{i}"
+            ))
+            .bind((format!("{NET}_{NAME}_{i}"), name))
+            .bind((format!("{NET}_{i}"), change));
+    }
+
     query = query.query(CommitStatement::default());
     query.await.map(|_| {})
 }
@@ -1167,20 +1406,35 @@ pub fn handler_branch() -> UpdateHandler<Box<dyn std::error::Error + Send + Sync
     dptree::entry()
         // Only enter this subtree if an AddExpense dialogue is active.
         .filter_async(crate::dialogues::storage::is_running::<AddExpenseState>)
-        .enter_dialogue::<Message, InMemStorage<AddExpenseState>, AddExpenseState>()
-        .branch(case![ReceiveDescription].endpoint(receive_description))
-        .branch(case![ReceiveAmount { description }].endpoint(receive_amount))
+        .enter_dialogue::<DialogueKey, InMemStorage<AddExpenseState>, AddExpenseState>()
+        .branch(case![ReceiveDescription { occurred_at }].endpoint(receive_description))
         .branch(
-            case![ReceivePaidBy {
+            case![ReceiveAmount {
+                description,
+                occurred_at
+            }]
+            .endpoint(receive_amount),
+        )
+        .branch(
+            case![ReceiveDate {
                 description,
                 amount
             }]
+            .endpoint(receive_date),
+        )
+        .branch(
+            case![ReceivePaidBy {
+                description,
+                amount,
+                occurred_at
+            }]
             .endpoint(receive_paid_by),
         )
         .branch(
             case![StartSplitAmong {
                 description,
                 amount,
+                occurred_at,
                 paid_by
             }]
             .endpoint(start_split_among),
@@ -1189,6 +1443,7 @@ pub fn handler_branch() -> UpdateHandler<Box<dyn std::error::Error + Send + Sync
             case![ReceiveSplitAmong {
                 description,
                 amount,
+                occurred_at,
                 paid_by,
                 split_among
             }]
@@ -1196,10 +1451,12 @@ pub fn handler_branch() -> UpdateHandler<Box<dyn std::error::Error + Send + Sync
         )
 }
 
-/// Returns `true` if the callback data matches either of the AddExpense
-/// keyboard prefixes (payer picker or split picker).
+/// Returns `true` if the callback data matches any of the AddExpense
+/// keyboard prefixes (date picker, payer picker or split picker).
 pub fn is_add_expense_callback(data: &str) -> bool {
-    data.starts_with(CALLBACK_PREFIX) || data.starts_with(CALLBACK_PREFIX_SPLIT)
+    data.starts_with(CALLBACK_PREFIX_DATE)
+        || data.starts_with(CALLBACK_PREFIX)
+        || data.starts_with(CALLBACK_PREFIX_SPLIT)
 }
 
 /// Returns the dispatcher subtree that handles inline-keyboard callbacks for
@@ -1210,18 +1467,27 @@ pub fn callback_branch() -> UpdateHandler<Box<dyn std::error::Error + Send + Syn
     use teloxide::dptree::{self, case};
 
     dptree::entry()
-        .enter_dialogue::<CallbackQuery, InMemStorage<AddExpenseState>, AddExpenseState>()
+        .enter_dialogue::<DialogueKey, InMemStorage<AddExpenseState>, AddExpenseState>()
         .branch(
-            case![ReceivePaidBy {
+            case![ReceiveDate {
                 description,
                 amount
             }]
+            .endpoint(receive_date_callback),
+        )
+        .branch(
+            case![ReceivePaidBy {
+                description,
+                amount,
+                occurred_at
+            }]
             .endpoint(receive_paid_by_callback),
         )
         .branch(
             case![StartSplitAmong {
                 description,
                 amount,
+                occurred_at,
                 paid_by
             }]
             .endpoint(receive_split_callback),
@@ -1230,6 +1496,7 @@ pub fn callback_branch() -> UpdateHandler<Box<dyn std::error::Error + Send + Syn
             case![ReceiveSplitAmong {
                 description,
                 amount,
+                occurred_at,
                 paid_by,
                 split_among
             }]
@@ -1250,8 +1517,11 @@ mod tests {
         tests::{TestBot, helpers},
         traveler::Name,
     };
+    use chrono::{DateTime, TimeDelta, Utc};
+    use jiff::tz::TimeZone;
     use maplit::hashmap;
     use rust_decimal::Decimal;
+    use surrealdb::Datetime;
 
     test! { add_expense_all_ok,
         let db = db().await;
@@ -1315,6 +1585,133 @@ mod tests {
         assert_eq!(last_message, response);
     }
 
+    test! { add_expense_backdated,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+        helpers::add_traveler(&mut bot, "Bob").await;
+
+        // Add expense with an inline date, skipping the date step
+        bot.update("/addexpense --date 2026-01-15 20:30");
+        bot.dispatch().await;
+        bot.update("Dinner");
+        bot.dispatch().await;
+        bot.update("60");
+        bot.dispatch().await;
+        bot.update("Alice");
+        bot.dispatch().await;
+        bot.update(consts::ALL_KWORD);
+        bot.dispatch().await;
+
+        let expense = Expense::db_select_by_number(db.clone(), bot.chat_id(), 1).await.unwrap().unwrap();
+        let occurred_at = DateTime::parse_from_rfc3339("2026-01-15T20:30:00Z").unwrap().to_utc();
+        assert_eq!(expense.occurred_at, Datetime::from(occurred_at));
+        assert_ne!(expense.timestamp_utc, expense.occurred_at);
+
+        // The creation timestamp is kept when the expense is updated
+        db.query("UPDATE expense SET occurred_at = d'2026-01-14T00:00:00Z'").await.unwrap();
+        let updated = Expense::db_select_by_number(db, bot.chat_id(), 1).await.unwrap().unwrap();
+        assert_eq!(updated.timestamp_utc, expense.timestamp_utc);
+    }
+
+    test! { add_expense_yesterday,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+
+        bot.update("/addexpense");
+        bot.dispatch().await;
+        bot.update("Dinner");
+        bot.dispatch().await;
+        bot.update("60");
+        bot.dispatch().await;
+        bot.update(consts::YESTERDAY_KWORD);
+        bot.dispatch().await;
+        bot.update("Alice");
+        bot.dispatch().await;
+        bot.update(consts::ALL_KWORD);
+        bot.dispatch().await;
+
+        let expense = Expense::db_select_by_number(db, bot.chat_id(), 1).await.unwrap().unwrap();
+        let elapsed = Utc::now() - expense.occurred_at.into_inner().0;
+        assert!(elapsed >= TimeDelta::days(1) && elapsed < TimeDelta::days(1) + TimeDelta::minutes(1));
+    }
+
+    test! { add_expense_invalid_date,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "");
+
+        helpers::add_traveler(&mut bot, "Alice").await;
+
+        bot.update("/addexpense");
+        bot.dispatch().await;
+        bot.update("Dinner");
+        bot.dispatch().await;
+        bot.update("60");
+        let response = i18n::dialogues::ADD_EXPENSE_ASK_DATE.translate_default();
+        bot.test_last_message(&response).await;
+
+        // Dates in the future are refused
+        bot.update("3000-01-01");
+        let response = i18n::dialogues::ADD_EXPENSE_INVALID_DATE.translate_default();
+        bot.test_last_message(&response).await;
+
+        bot.update("15/01/2026");
+        bot.test_last_message(&response).await;
+    }
+
+    test! { add_expense_invalid_date_option,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/addexpense --date tomorrow");
+        let response = i18n::dialogues::ADD_EXPENSE_INVALID_DATE_OPTION.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    #[test]
+    fn parse_occurred_at() {
+        let now = DateTime::parse_from_rfc3339("2026-10-19T10:00:00Z")
+            .unwrap()
+            .to_utc();
+        let parse = |text| super::parse_occurred_at(text, now, &TimeZone::UTC);
+
+        assert_eq!(parse("today"), Some(now));
+        assert_eq!(parse(" Yesterday "), Some(now - TimeDelta::days(1)));
+        assert_eq!(
+            parse("2026-10-18"),
+            Some(
+                DateTime::parse_from_rfc3339("2026-10-18T00:00:00Z")
+                    .unwrap()
+                    .to_utc()
+            )
+        );
+        assert_eq!(
+            parse("2026-10-19 09:30"),
+            Some(
+                DateTime::parse_from_rfc3339("2026-10-19T09:30:00Z")
+                    .unwrap()
+                    .to_utc()
+            )
+        );
+        assert_eq!(parse("2026-10-19 10:30"), None);
+        assert_eq!(parse("2026-10-20"), None);
+        assert_eq!(parse("2026-02-30"), None);
+        assert_eq!(parse("tomorrow"), None);
+
+        // Dates are read in the time zone of the chat
+        let tokyo = TimeZone::get("Asia/Tokyo").unwrap();
+        assert_eq!(
+            super::parse_occurred_at("2026-10-19", now, &tokyo),
+            Some(
+                DateTime::parse_from_rfc3339("2026-10-18T15:00:00Z")
+                    .unwrap()
+                    .to_utc()
+            )
+        );
+    }
+
     test! { add_expense_invalid_amount,
         let db = db().await;
         let mut bot = TestBot::new(db.clone(), "");
@@ -1354,7 +1751,10 @@ mod tests {
         // 2. Set amount
         bot.update("100.7");
         bot.dispatch().await;
-        // 3.1. Set payer to "/Alice" -> invalid name: starts with a slash
+        // 3. Set date
+        bot.update(consts::TODAY_KWORD);
+        bot.dispatch().await;
+        // 4.1. Set payer to "/Alice" -> invalid name: starts with a slash
         bot.update("/Alice");
         let response = format!(
             "{invalid_paid_by}\n\n{reason}",
@@ -1363,7 +1763,7 @@ mod tests {
         );
         bot.test_last_message(&response).await;
 
-        // 3.2. Set payer to "Alice," -> invalid name: ends with a comma
+        // 4.2. Set payer to "Alice," -> invalid name: ends with a comma
         bot.update("Alice,");
         let response = format!(
             "{invalid_paid_by}\n\n{reason}",
@@ -1372,7 +1772,7 @@ mod tests {
         );
         bot.test_last_message(&response).await;
 
-        // 3.3. Set payer to "all" -> invalid name: reserved keyword
+        // 4.3. Set payer to "all" -> invalid name: reserved keyword
         bot.update(consts::ALL_KWORD);
         let response = format!(
             "{invalid_paid_by}\n\n{reason}",
@@ -1399,7 +1799,10 @@ mod tests {
         // 2. Set amount
         bot.update("100.7");
         bot.dispatch().await;
-        // 3. Set payer
+        // 3. Set date
+        bot.update(consts::TODAY_KWORD);
+        bot.dispatch().await;
+        // 4. Set payer
         bot.update("Charlie");
         let response = i18n::dialogues::ADD_EXPENSE_TRAVELER_NOT_FOUND.translate_with_args_default(&hashmap! {i18n::args::NAME.into() => "Charlie".into()},
         );
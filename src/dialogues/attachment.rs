@@ -0,0 +1,62 @@
+//! Files sent along with the commands starting a dialogue, such as
+//! `/restore` and `/import`: either attached to the command (the command
+//! being the caption of the file) or in the message the command replies to.
+
+use crate::{
+    Context,
+    consts::MAX_DOWNLOAD_SIZE,
+    i18n::{self, Translate},
+    topics::{InTopic, MessageExt},
+};
+use std::{
+    error::Error,
+    sync::{Arc, Mutex},
+};
+use teloxide::{
+    Bot,
+    net::Download,
+    requests::Requester,
+    types::{Document, Message},
+};
+
+/// Returns the document attached to `msg`, or to the message it replies to.
+fn attached_document(msg: &Message) -> Option<&Document> {
+    msg.document()
+        .or_else(|| msg.reply_to_message().and_then(Message::document))
+}
+
+async fn download(bot: &Bot, document: &Document) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    let file = bot.get_file(document.file.id.clone()).await?;
+    let mut content = Vec::new();
+    bot.download_file(&file.path, &mut content).await?;
+    Ok(content)
+}
+
+/// Downloads the document sent along with `msg`. If there is none, it is too
+/// large or it can't be downloaded, replies with `usage_key` or `error_key`
+/// accordingly and returns `None`.
+pub async fn read(
+    bot: &Bot,
+    msg: &Message,
+    usage_key: &str,
+    error_key: &str,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<Option<Vec<u8>>, Box<dyn Error + Send + Sync>> {
+    let reply = match attached_document(msg) {
+        None => usage_key.translate(ctx),
+        Some(document) if document.file.size > MAX_DOWNLOAD_SIZE => {
+            i18n::commands::FILE_TOO_LARGE.translate(ctx)
+        }
+        Some(document) => match download(bot, document).await {
+            Ok(content) => return Ok(Some(content)),
+            Err(err) => {
+                tracing::error!("{err}");
+                error_key.translate(ctx)
+            }
+        },
+    };
+    bot.send_message(msg.chat.id, reply)
+        .in_topic(msg.topic_id())
+        .await?;
+    Ok(None)
+}
@@ -1,3 +1,4 @@
 pub mod add_expense_dialogue;
+pub mod attachment;
 pub mod pending_command_dialogue;
 pub mod storage;
@@ -4,10 +4,11 @@
 
 use crate::{
     Context, HandlerResult,
-    commands::{Command, CommandArg, command_reply},
+    commands::{Command, CommandArg, command_reply, send_outcome},
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     dialogues::pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
     i18n::{self, Translate},
+    topics::{InTopic, MessageExt},
     traveler::Name,
 };
 use macro_rules_attribute::apply;
@@ -36,6 +37,7 @@ pub async fn start(
         msg.chat.id,
         i18n::dialogues::ADD_TRAVELER_ASK_NAME.translate(ctx),
     )
+    .in_topic(msg.topic_id())
     .await?;
     dialogue
         .update(PendingCommandState::AddTraveler(AddTravelerState::AskName))
@@ -62,6 +64,7 @@ pub async fn receive_name(
             msg.chat.id,
             i18n::dialogues::ADD_TRAVELER_INVALID_NAME.translate(ctx),
         )
+        .in_topic(msg.topic_id())
         .await?;
         return Ok(());
     }
@@ -75,7 +78,9 @@ pub async fn receive_name(
                 invalid = i18n::dialogues::ADD_TRAVELER_INVALID_NAME.translate(ctx.clone()),
                 reason = err.translate(ctx),
             );
-            bot.send_message(msg.chat.id, reply).await?;
+            bot.send_message(msg.chat.id, reply)
+                .in_topic(msg.topic_id())
+                .await?;
             return Ok(());
         }
     };
@@ -84,7 +89,7 @@ pub async fn receive_name(
         name: CommandArg::Provided(name),
     };
     let outcome = command_reply(db, &msg, &cmd, ctx.clone()).await;
-    bot.send_message(msg.chat.id, outcome.message()).await?;
+    send_outcome(&bot, &msg, &outcome, None).await?;
     if outcome.is_success() {
         dialogue.exit().await?;
     } else {
@@ -92,6 +97,7 @@ pub async fn receive_name(
             msg.chat.id,
             i18n::dialogues::ADD_TRAVELER_ASK_NAME.translate(ctx),
         )
+        .in_topic(msg.topic_id())
         .await?;
     }
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -2,11 +2,12 @@
 
 use crate::{
     Context, HandlerResult,
-    commands::{Command, command_reply},
+    commands::{Command, command_reply, send_outcome},
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     dialogues::pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
     i18n::{self, Translate, TranslateWithArgs},
     keyboard::{self, ConfirmAnswer, ConfirmConfig, confirmation_keyboard, parse_confirm_answer},
+    topics::{InTopic, MessageExt},
 };
 use macro_rules_attribute::apply;
 use maplit::hashmap;
@@ -36,7 +37,7 @@ pub enum ClearAllState {
 async fn ask_confirmation(
     bot: &Bot,
     dialogue: &PendingCommandDialogue,
-    chat_id: teloxide::types::ChatId,
+    msg: &Message,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
     let prompt = i18n::dialogues::CLEAR_ALL_CONFIRM.translate(ctx.clone());
@@ -45,7 +46,10 @@ async fn ask_confirmation(
         deny_callback: DENY_CALLBACK,
         ctx,
     });
-    bot.send_message(chat_id, prompt).reply_markup(kb).await?;
+    bot.send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id())
+        .reply_markup(kb)
+        .await?;
     dialogue
         .update(PendingCommandState::ClearAll(ClearAllState::Confirm))
         .await?;
@@ -62,7 +66,7 @@ pub async fn start(
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
     tracing::debug!("{LOG_DEBUG_START}");
-    ask_confirmation(&bot, &dialogue, msg.chat.id, ctx).await?;
+    ask_confirmation(&bot, &dialogue, &msg, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     tracing::info!("Dialogue started: /clearall");
     Ok(())
@@ -85,7 +89,7 @@ pub async fn receive_confirm_text(
         ConfirmAnswer::Yes => {
             let cmd = Command::ClearAll;
             let outcome = command_reply(db, &msg, &cmd, ctx).await;
-            bot.send_message(msg.chat.id, outcome.message()).await?;
+            send_outcome(&bot, &msg, &outcome, None).await?;
             dialogue.exit().await?;
         }
         ConfirmAnswer::No => {
@@ -96,10 +100,12 @@ pub async fn receive_confirm_text(
                 ctx,
                 &hashmap! { i18n::args::PROCESS.into() => process_name.into() },
             );
-            bot.send_message(msg.chat.id, cancel_msg).await?;
+            bot.send_message(msg.chat.id, cancel_msg)
+                .in_topic(msg.topic_id())
+                .await?;
         }
         ConfirmAnswer::Unknown => {
-            ask_confirmation(&bot, &dialogue, msg.chat.id, ctx).await?;
+            ask_confirmation(&bot, &dialogue, &msg, ctx).await?;
         }
     }
 
@@ -138,7 +144,7 @@ pub async fn receive_confirm_callback(
     if data == CONFIRM_CALLBACK {
         let cmd = Command::ClearAll;
         let outcome = command_reply(db, &msg, &cmd, ctx).await;
-        bot.send_message(msg.chat.id, outcome.message()).await?;
+        send_outcome(&bot, &msg, &outcome, None).await?;
         dialogue.exit().await?;
     } else {
         dialogue.exit().await?;
@@ -148,7 +154,9 @@ pub async fn receive_confirm_callback(
             ctx,
             &hashmap! { i18n::args::PROCESS.into() => process_name.into() },
         );
-        bot.send_message(msg.chat.id, cancel_msg).await?;
+        bot.send_message(msg.chat.id, cancel_msg)
+            .in_topic(msg.topic_id())
+            .await?;
     }
 
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -2,12 +2,13 @@
 
 use crate::{
     Context, HandlerResult,
-    commands::{Command, command_reply},
+    commands::{Command, command_reply, send_outcome},
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     dialogues::pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
     expense::Expense,
     i18n::{self, Translate, TranslateWithArgs},
     keyboard::{self, ConfirmAnswer, ConfirmConfig, confirmation_keyboard, parse_confirm_answer},
+    topics::{InTopic, MessageExt},
     traveler::Traveler,
 };
 use macro_rules_attribute::apply;
@@ -44,7 +45,7 @@ pub enum ClearTravelersState {
 async fn ask_confirmation(
     bot: &Bot,
     dialogue: &PendingCommandDialogue,
-    chat_id: teloxide::types::ChatId,
+    msg: &Message,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
     let prompt = i18n::dialogues::CLEAR_TRAVELERS_CONFIRM.translate(ctx.clone());
@@ -53,7 +54,10 @@ async fn ask_confirmation(
         deny_callback: DENY_CALLBACK,
         ctx,
     });
-    bot.send_message(chat_id, prompt).reply_markup(kb).await?;
+    bot.send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id())
+        .reply_markup(kb)
+        .await?;
     dialogue
         .update(PendingCommandState::ClearTravelers(
             ClearTravelersState::Confirm,
@@ -118,7 +122,9 @@ async fn handle_confirm_yes(
                 );
             let expenses_reply = format_expenses(expenses, ctx);
             let reply = format!("{has_expenses_msg}\n\n{expenses_reply}");
-            bot.send_message(chat_id, reply).await?;
+            bot.send_message(chat_id, reply)
+                .in_topic(msg.topic_id())
+                .await?;
             dialogue.exit().await?;
         }
         Ok(list) if !list.is_empty() => {
@@ -133,7 +139,10 @@ async fn handle_confirm_yes(
                 i18n::dialogues::CLEAR_TRAVELERS_SHOW_EXPENSES_PROMPT.translate(ctx.clone()),
             );
             let kb = show_expenses_keyboard(&list, ctx);
-            bot.send_message(chat_id, prompt).reply_markup(kb).await?;
+            bot.send_message(chat_id, prompt)
+                .in_topic(msg.topic_id())
+                .reply_markup(kb)
+                .await?;
             dialogue
                 .update(PendingCommandState::ClearTravelers(
                     ClearTravelersState::ShowExpenses,
@@ -144,13 +153,13 @@ async fn handle_confirm_yes(
             // No travelers with expenses — proceed with deletion.
             let cmd = Command::ClearTravelers;
             let outcome = command_reply(db, msg, &cmd, ctx).await;
-            bot.send_message(chat_id, outcome.message()).await?;
+            send_outcome(bot, msg, &outcome, None).await?;
             dialogue.exit().await?;
         }
         Err(_) => {
             // Error already logged inside travelers_with_expenses.
             let outcome = command_reply(db, msg, &Command::ClearTravelers, ctx).await;
-            bot.send_message(chat_id, outcome.message()).await?;
+            send_outcome(bot, msg, &outcome, None).await?;
             dialogue.exit().await?;
         }
     }
@@ -175,7 +184,7 @@ pub async fn start(
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
     tracing::debug!("{LOG_DEBUG_START}");
-    ask_confirmation(&bot, &dialogue, msg.chat.id, ctx).await?;
+    ask_confirmation(&bot, &dialogue, &msg, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     tracing::info!("Dialogue started: /cleartravelers");
     Ok(())
@@ -196,14 +205,16 @@ pub async fn receive_confirm_text(
     let text = msg.text().map(str::trim).unwrap_or("");
     match parse_confirm_answer(text) {
         ConfirmAnswer::Yes => {
-            handle_confirm_yes(db, &bot, &dialogue, msg.chat.id, &msg, ctx).await?;
+            handle_confirm_yes(db, &bot, &dialogue, msg.ledger_id(), &msg, ctx).await?;
         }
         ConfirmAnswer::No => {
             dialogue.exit().await?;
-            bot.send_message(msg.chat.id, cancel_message(ctx)).await?;
+            bot.send_message(msg.chat.id, cancel_message(ctx))
+                .in_topic(msg.topic_id())
+                .await?;
         }
         ConfirmAnswer::Unknown => {
-            ask_confirmation(&bot, &dialogue, msg.chat.id, ctx).await?;
+            ask_confirmation(&bot, &dialogue, &msg, ctx).await?;
         }
     }
 
@@ -240,10 +251,12 @@ pub async fn receive_confirm_callback(
     keyboard::echo_callback_selection(&bot, &msg, &label).await;
 
     if data == CONFIRM_CALLBACK {
-        handle_confirm_yes(db, &bot, &dialogue, msg.chat.id, &msg, ctx).await?;
+        handle_confirm_yes(db, &bot, &dialogue, msg.ledger_id(), &msg, ctx).await?;
     } else {
         dialogue.exit().await?;
-        bot.send_message(msg.chat.id, cancel_message(ctx)).await?;
+        bot.send_message(msg.chat.id, cancel_message(ctx))
+            .in_topic(msg.topic_id())
+            .await?;
     }
 
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -276,7 +289,7 @@ pub async fn receive_show_expenses_callback(
         i18n::labels::ALL_BUTTON.translate(ctx.clone())
     } else if let Some(num_str) = data.strip_prefix(SHOW_PREFIX) {
         if let Ok(number) = num_str.parse::<i64>() {
-            Traveler::db_select_by_number(db.clone(), msg.chat.id, number)
+            Traveler::db_select_by_number(db.clone(), msg.ledger_id(), number)
                 .await
                 .ok()
                 .flatten()
@@ -294,7 +307,7 @@ pub async fn receive_show_expenses_callback(
 
     if data == SHOW_ALL_CALLBACK {
         // Show expenses for all travelers that have them, grouped by traveler.
-        match Traveler::travelers_with_expenses(db, msg.chat.id).await {
+        match Traveler::travelers_with_expenses(db, msg.ledger_id()).await {
             Ok(list) => {
                 let sections: Vec<String> = list
                     .iter()
@@ -305,7 +318,9 @@ pub async fn receive_show_expenses_callback(
                     tracing::info!("No expenses found (race condition or already deleted)");
                 } else {
                     let reply = sections.join("\n\n");
-                    bot.send_message(msg.chat.id, reply).await?;
+                    bot.send_message(msg.chat.id, reply)
+                        .in_topic(msg.topic_id())
+                        .await?;
                 }
             }
             Err(err) => {
@@ -319,13 +334,15 @@ pub async fn receive_show_expenses_callback(
             dialogue.exit().await?;
             return Ok(());
         };
-        match Traveler::db_select_by_number(db.clone(), msg.chat.id, number).await {
+        match Traveler::db_select_by_number(db.clone(), msg.ledger_id(), number).await {
             Ok(Some(t)) => {
                 let expenses = Expense::db_select_by_payer(db, t).await;
                 match expenses {
                     Ok(list) if !list.is_empty() => {
                         let reply = format_expenses(&list, ctx);
-                        bot.send_message(msg.chat.id, reply).await?;
+                        bot.send_message(msg.chat.id, reply)
+                            .in_topic(msg.topic_id())
+                            .await?;
                     }
                     Ok(_) => {
                         tracing::info!("Traveler #{number} has no expenses (already deleted?)");
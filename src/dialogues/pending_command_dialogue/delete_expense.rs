@@ -6,7 +6,7 @@
 
 use crate::{
     Context, HandlerResult,
-    commands::{Command, CommandArg, command_reply},
+    commands::{Command, CommandArg, command_reply, send_outcome},
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     dialogues::pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
     expense::Expense,
@@ -17,6 +17,7 @@ use crate::{
         parse_confirm_answer,
     },
     money_wrapper::MoneyWrapper,
+    topics::{InTopic, MessageExt},
 };
 use macro_rules_attribute::apply;
 use maplit::hashmap;
@@ -63,13 +64,15 @@ fn expense_picker_items(expenses: &[Expense], ctx: Arc<Mutex<Context>>) -> Vec<P
 async fn send_prompt_with_keyboard(
     db: Arc<Surreal<Any>>,
     bot: &Bot,
-    chat_id: teloxide::types::ChatId,
+    msg: &Message,
     prompt: String,
     page: usize,
     ctx: Arc<Mutex<Context>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let mut request = bot.send_message(chat_id, prompt);
-    if let Ok(expenses) = Expense::db_select(db, chat_id).await {
+    let mut request = bot
+        .send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id());
+    if let Ok(expenses) = Expense::db_select(db, msg.ledger_id()).await {
         let items = expense_picker_items(&expenses, ctx.clone());
         if let Some(kb) = keyboard::paginated_keyboard(PaginatedKeyboardConfig {
             items: &items,
@@ -95,7 +98,7 @@ async fn send_prompt_with_keyboard(
 async fn ask_confirmation(
     bot: &Bot,
     dialogue: &PendingCommandDialogue,
-    chat_id: teloxide::types::ChatId,
+    msg: &Message,
     number: i64,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
@@ -108,7 +111,10 @@ async fn ask_confirmation(
         deny_callback: DENY_CALLBACK,
         ctx,
     });
-    bot.send_message(chat_id, prompt).reply_markup(kb).await?;
+    bot.send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id())
+        .reply_markup(kb)
+        .await?;
     dialogue
         .update(PendingCommandState::DeleteExpense(
             DeleteExpenseState::Confirm(number),
@@ -129,7 +135,7 @@ pub async fn start(
 ) -> HandlerResult {
     tracing::debug!("{LOG_DEBUG_START}");
     let prompt = i18n::dialogues::DELETE_EXPENSE_ASK_NUMBER.translate(ctx.clone());
-    send_prompt_with_keyboard(db, &bot, msg.chat.id, prompt, 0, ctx).await?;
+    send_prompt_with_keyboard(db, &bot, &msg, prompt, 0, ctx).await?;
     dialogue
         .update(PendingCommandState::DeleteExpense(
             DeleteExpenseState::AskNumber,
@@ -160,12 +166,13 @@ pub async fn receive_number(
                 msg.chat.id,
                 i18n::dialogues::DELETE_EXPENSE_INVALID_NUMBER.translate(ctx),
             )
+            .in_topic(msg.topic_id())
             .await?;
             return Ok(());
         }
     };
 
-    ask_confirmation(&bot, &dialogue, msg.chat.id, number, ctx).await?;
+    ask_confirmation(&bot, &dialogue, &msg, number, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     Ok(())
 }
@@ -203,11 +210,11 @@ pub async fn receive_callback(
                 tracing::warn!("Invalid number in callback data: {value:?}");
                 return Ok(());
             };
-            ask_confirmation(&bot, &dialogue, msg.chat.id, number, ctx).await?;
+            ask_confirmation(&bot, &dialogue, &msg, number, ctx).await?;
         }
         PaginatedCallbackAction::PageChange { page, msg } => {
             // Rebuild the keyboard for the new page and edit in-place.
-            if let Ok(expenses) = Expense::db_select(db, msg.chat.id).await {
+            if let Ok(expenses) = Expense::db_select(db, msg.ledger_id()).await {
                 let items = expense_picker_items(&expenses, ctx.clone());
                 if let Some(kb) = keyboard::paginated_keyboard(PaginatedKeyboardConfig {
                     items: &items,
@@ -249,7 +256,7 @@ pub async fn start_confirm(
 ) -> HandlerResult {
     tracing::debug!("{LOG_DEBUG_START}");
     let number = number.expect_provided("deleteexpense");
-    ask_confirmation(&bot, &dialogue, msg.chat.id, number, ctx).await?;
+    ask_confirmation(&bot, &dialogue, &msg, number, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     tracing::info!("Dialogue started: /deleteexpense (inline confirm #{number})");
     Ok(())
@@ -276,7 +283,7 @@ pub async fn receive_confirm_text(
                 number: CommandArg::Provided(number),
             };
             let outcome = command_reply(db, &msg, &cmd, ctx).await;
-            bot.send_message(msg.chat.id, outcome.message()).await?;
+            send_outcome(&bot, &msg, &outcome, None).await?;
             dialogue.exit().await?;
         }
         ConfirmAnswer::No => {
@@ -287,11 +294,13 @@ pub async fn receive_confirm_text(
                 ctx,
                 &hashmap! { i18n::args::PROCESS.into() => process_name.into() },
             );
-            bot.send_message(msg.chat.id, cancel_msg).await?;
+            bot.send_message(msg.chat.id, cancel_msg)
+                .in_topic(msg.topic_id())
+                .await?;
         }
         ConfirmAnswer::Unknown => {
             // Re-send the confirmation prompt.
-            ask_confirmation(&bot, &dialogue, msg.chat.id, number, ctx).await?;
+            ask_confirmation(&bot, &dialogue, &msg, number, ctx).await?;
         }
     }
 
@@ -331,7 +340,7 @@ pub async fn receive_confirm_callback(
             number: CommandArg::Provided(number),
         };
         let outcome = command_reply(db, &msg, &cmd, ctx).await;
-        bot.send_message(msg.chat.id, outcome.message()).await?;
+        send_outcome(&bot, &msg, &outcome, None).await?;
         dialogue.exit().await?;
     } else {
         // Deny or unexpected data → cancel.
@@ -342,7 +351,9 @@ pub async fn receive_confirm_callback(
             ctx,
             &hashmap! { i18n::args::PROCESS.into() => process_name.into() },
         );
-        bot.send_message(msg.chat.id, cancel_msg).await?;
+        bot.send_message(msg.chat.id, cancel_msg)
+            .in_topic(msg.topic_id())
+            .await?;
     }
 
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -6,7 +6,7 @@
 
 use crate::{
     Context, HandlerResult,
-    commands::{Command, CommandArg, command_reply},
+    commands::{Command, CommandArg, command_reply, send_outcome},
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     dialogues::pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
     i18n::{self, Translate, TranslateWithArgs},
@@ -16,6 +16,7 @@ use crate::{
         parse_confirm_answer,
     },
     money_wrapper::MoneyWrapper,
+    topics::{InTopic, MessageExt},
     transfer::Transfer,
 };
 use macro_rules_attribute::apply;
@@ -66,13 +67,15 @@ fn transfer_picker_items(transfers: &[Transfer], ctx: Arc<Mutex<Context>>) -> Ve
 async fn send_prompt_with_keyboard(
     db: Arc<Surreal<Any>>,
     bot: &Bot,
-    chat_id: teloxide::types::ChatId,
+    msg: &Message,
     prompt: String,
     page: usize,
     ctx: Arc<Mutex<Context>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let mut request = bot.send_message(chat_id, prompt);
-    if let Ok(transfers) = Transfer::transfers(db, chat_id).await {
+    let mut request = bot
+        .send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id());
+    if let Ok(transfers) = Transfer::transfers(db, msg.ledger_id()).await {
         let items = transfer_picker_items(&transfers, ctx.clone());
         if let Some(kb) = keyboard::paginated_keyboard(PaginatedKeyboardConfig {
             items: &items,
@@ -98,7 +101,7 @@ async fn send_prompt_with_keyboard(
 async fn ask_confirmation(
     bot: &Bot,
     dialogue: &PendingCommandDialogue,
-    chat_id: teloxide::types::ChatId,
+    msg: &Message,
     number: i64,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
@@ -111,7 +114,10 @@ async fn ask_confirmation(
         deny_callback: DENY_CALLBACK,
         ctx,
     });
-    bot.send_message(chat_id, prompt).reply_markup(kb).await?;
+    bot.send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id())
+        .reply_markup(kb)
+        .await?;
     dialogue
         .update(PendingCommandState::DeleteTransfer(
             DeleteTransferState::Confirm(number),
@@ -132,7 +138,7 @@ pub async fn start(
 ) -> HandlerResult {
     tracing::debug!("{LOG_DEBUG_START}");
     let prompt = i18n::dialogues::DELETE_TRANSFER_ASK_NUMBER.translate(ctx.clone());
-    send_prompt_with_keyboard(db, &bot, msg.chat.id, prompt, 0, ctx).await?;
+    send_prompt_with_keyboard(db, &bot, &msg, prompt, 0, ctx).await?;
     dialogue
         .update(PendingCommandState::DeleteTransfer(
             DeleteTransferState::AskNumber,
@@ -163,12 +169,13 @@ pub async fn receive_number(
                 msg.chat.id,
                 i18n::dialogues::DELETE_TRANSFER_INVALID_NUMBER.translate(ctx),
             )
+            .in_topic(msg.topic_id())
             .await?;
             return Ok(());
         }
     };
 
-    ask_confirmation(&bot, &dialogue, msg.chat.id, number, ctx).await?;
+    ask_confirmation(&bot, &dialogue, &msg, number, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     Ok(())
 }
@@ -206,10 +213,10 @@ pub async fn receive_callback(
                 tracing::warn!("Invalid number in callback data: {value:?}");
                 return Ok(());
             };
-            ask_confirmation(&bot, &dialogue, msg.chat.id, number, ctx).await?;
+            ask_confirmation(&bot, &dialogue, &msg, number, ctx).await?;
         }
         PaginatedCallbackAction::PageChange { page, msg } => {
-            if let Ok(transfers) = Transfer::transfers(db, msg.chat.id).await {
+            if let Ok(transfers) = Transfer::transfers(db, msg.ledger_id()).await {
                 let items = transfer_picker_items(&transfers, ctx.clone());
                 if let Some(kb) = keyboard::paginated_keyboard(PaginatedKeyboardConfig {
                     items: &items,
@@ -251,7 +258,7 @@ pub async fn start_confirm(
 ) -> HandlerResult {
     tracing::debug!("{LOG_DEBUG_START}");
     let number = number.expect_provided("deletetransfer");
-    ask_confirmation(&bot, &dialogue, msg.chat.id, number, ctx).await?;
+    ask_confirmation(&bot, &dialogue, &msg, number, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     tracing::info!("Dialogue started: /deletetransfer (inline confirm #{number})");
     Ok(())
@@ -278,7 +285,7 @@ pub async fn receive_confirm_text(
                 number: CommandArg::Provided(number),
             };
             let outcome = command_reply(db, &msg, &cmd, ctx).await;
-            bot.send_message(msg.chat.id, outcome.message()).await?;
+            send_outcome(&bot, &msg, &outcome, None).await?;
             dialogue.exit().await?;
         }
         ConfirmAnswer::No => {
@@ -289,10 +296,12 @@ pub async fn receive_confirm_text(
                 ctx,
                 &hashmap! { i18n::args::PROCESS.into() => process_name.into() },
             );
-            bot.send_message(msg.chat.id, cancel_msg).await?;
+            bot.send_message(msg.chat.id, cancel_msg)
+                .in_topic(msg.topic_id())
+                .await?;
         }
         ConfirmAnswer::Unknown => {
-            ask_confirmation(&bot, &dialogue, msg.chat.id, number, ctx).await?;
+            ask_confirmation(&bot, &dialogue, &msg, number, ctx).await?;
         }
     }
 
@@ -332,7 +341,7 @@ pub async fn receive_confirm_callback(
             number: CommandArg::Provided(number),
         };
         let outcome = command_reply(db, &msg, &cmd, ctx).await;
-        bot.send_message(msg.chat.id, outcome.message()).await?;
+        send_outcome(&bot, &msg, &outcome, None).await?;
         dialogue.exit().await?;
     } else {
         // Deny or unexpected data → cancel.
@@ -343,7 +352,9 @@ pub async fn receive_confirm_callback(
             ctx,
             &hashmap! { i18n::args::PROCESS.into() => process_name.into() },
         );
-        bot.send_message(msg.chat.id, cancel_msg).await?;
+        bot.send_message(msg.chat.id, cancel_msg)
+            .in_topic(msg.topic_id())
+            .await?;
     }
 
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -6,11 +6,12 @@
 
 use crate::{
     Context, HandlerResult,
-    commands::{Command, CommandArg, command_reply},
+    commands::{Command, CommandArg, command_reply, send_outcome},
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     dialogues::pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
     i18n::{self, Translate, TranslateWithArgs},
     keyboard::{self, ConfirmAnswer, ConfirmConfig, confirmation_keyboard, parse_confirm_answer},
+    topics::{InTopic, MessageExt},
     traveler::{Name, Traveler},
 };
 use macro_rules_attribute::apply;
@@ -43,7 +44,7 @@ pub enum DeleteTravelerState {
 async fn ask_confirmation(
     bot: &Bot,
     dialogue: &PendingCommandDialogue,
-    chat_id: teloxide::types::ChatId,
+    msg: &Message,
     name: Name,
     ctx: Arc<Mutex<Context>>,
 ) -> HandlerResult {
@@ -56,7 +57,10 @@ async fn ask_confirmation(
         deny_callback: DENY_CALLBACK,
         ctx,
     });
-    bot.send_message(chat_id, prompt).reply_markup(kb).await?;
+    bot.send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id())
+        .reply_markup(kb)
+        .await?;
     dialogue
         .update(PendingCommandState::DeleteTraveler(
             DeleteTravelerState::Confirm(name),
@@ -75,10 +79,12 @@ pub async fn start(
 ) -> HandlerResult {
     tracing::debug!("{LOG_DEBUG_START}");
     let prompt = i18n::dialogues::DELETE_TRAVELER_ASK_NAME.translate(ctx.clone());
-    let mut request = bot.send_message(msg.chat.id, prompt);
+    let mut request = bot
+        .send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id());
     if let Some(kb) = keyboard::travelers_keyboard(keyboard::TravelersKeyboardConfig {
         db,
-        chat_id: msg.chat.id,
+        chat_id: msg.ledger_id(),
         prefix: CALLBACK_PREFIX,
         cancel_callback: CANCEL_CALLBACK,
         noop_callback: NOOP_CALLBACK,
@@ -116,6 +122,7 @@ pub async fn receive_name(
             msg.chat.id,
             i18n::dialogues::DELETE_TRAVELER_INVALID_NAME.translate(ctx),
         )
+        .in_topic(msg.topic_id())
         .await?;
         return Ok(());
     }
@@ -129,12 +136,14 @@ pub async fn receive_name(
                 invalid = i18n::dialogues::DELETE_TRAVELER_INVALID_NAME.translate(ctx.clone()),
                 reason = err.translate(ctx),
             );
-            bot.send_message(msg.chat.id, reply).await?;
+            bot.send_message(msg.chat.id, reply)
+                .in_topic(msg.topic_id())
+                .await?;
             return Ok(());
         }
     };
 
-    ask_confirmation(&bot, &dialogue, msg.chat.id, name, ctx).await?;
+    ask_confirmation(&bot, &dialogue, &msg, name, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     Ok(())
 }
@@ -168,7 +177,7 @@ pub async fn receive_callback(
         return Ok(());
     };
 
-    let Some(traveler) = Traveler::db_resolve_by_number(db, msg.chat.id, &raw).await else {
+    let Some(traveler) = Traveler::db_resolve_by_number(db, msg.ledger_id(), &raw).await else {
         tracing::warn!("Could not resolve traveler number from callback data: {raw:?}");
         return Ok(());
     };
@@ -176,7 +185,7 @@ pub async fn receive_callback(
 
     keyboard::echo_callback_selection(&bot, &msg, &name).await;
 
-    ask_confirmation(&bot, &dialogue, msg.chat.id, name, ctx).await?;
+    ask_confirmation(&bot, &dialogue, &msg, name, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     Ok(())
 }
@@ -195,7 +204,7 @@ pub async fn start_confirm(
 ) -> HandlerResult {
     tracing::debug!("{LOG_DEBUG_START}");
     let name = name.expect_provided("deletetraveler");
-    ask_confirmation(&bot, &dialogue, msg.chat.id, name.clone(), ctx).await?;
+    ask_confirmation(&bot, &dialogue, &msg, name.clone(), ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     tracing::info!("Dialogue started: /deletetraveler (inline confirm '{name}')");
     Ok(())
@@ -222,7 +231,7 @@ pub async fn receive_confirm_text(
                 name: CommandArg::Provided(name),
             };
             let outcome = command_reply(db, &msg, &cmd, ctx).await;
-            bot.send_message(msg.chat.id, outcome.message()).await?;
+            send_outcome(&bot, &msg, &outcome, None).await?;
             dialogue.exit().await?;
         }
         ConfirmAnswer::No => {
@@ -233,10 +242,12 @@ pub async fn receive_confirm_text(
                 ctx,
                 &hashmap! { i18n::args::PROCESS.into() => process_name.into() },
             );
-            bot.send_message(msg.chat.id, cancel_msg).await?;
+            bot.send_message(msg.chat.id, cancel_msg)
+                .in_topic(msg.topic_id())
+                .await?;
         }
         ConfirmAnswer::Unknown => {
-            ask_confirmation(&bot, &dialogue, msg.chat.id, name, ctx).await?;
+            ask_confirmation(&bot, &dialogue, &msg, name, ctx).await?;
         }
     }
 
@@ -276,7 +287,7 @@ pub async fn receive_confirm_callback(
             name: CommandArg::Provided(name),
         };
         let outcome = command_reply(db, &msg, &cmd, ctx).await;
-        bot.send_message(msg.chat.id, outcome.message()).await?;
+        send_outcome(&bot, &msg, &outcome, None).await?;
         dialogue.exit().await?;
     } else {
         // Deny or unexpected data → cancel.
@@ -287,7 +298,9 @@ pub async fn receive_confirm_callback(
             ctx,
             &hashmap! { i18n::args::PROCESS.into() => process_name.into() },
         );
-        bot.send_message(msg.chat.id, cancel_msg).await?;
+        bot.send_message(msg.chat.id, cancel_msg)
+            .in_topic(msg.topic_id())
+            .await?;
     }
 
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
@@ -0,0 +1,568 @@
+//! `/import` dialogue. The CSV export sent along with the command is read,
+//! then every member of the imported group is matched to a traveler: members
+//! named like an existing traveler are matched right away, the others are
+//! asked for one by one, offering the remaining travelers and the creation
+//! of a new one. A confirmation step summarizing the import comes last.
+
+use crate::{
+    Context, HandlerResult,
+    commands::{CommandOutcome, import, send_outcome},
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    dialogues::{
+        attachment,
+        pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
+    },
+    i18n::{self, Translate, TranslateWithArgs},
+    import::{Import, MemberMapping, SkipReason, SkippedRow},
+    keyboard::{
+        self, ConfirmAnswer, ConfirmConfig, DEFAULT_ROWS_PER_PAGE, PAGE_MARKER,
+        PaginatedKeyboardConfig, PickerItem, confirmation_keyboard, parse_confirm_answer,
+    },
+    topics::{InTopic, MessageExt},
+    traveler::{Name, Traveler},
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::{
+    Bot,
+    payloads::{EditMessageReplyMarkupSetters, SendMessageSetters},
+    requests::Requester,
+    types::{CallbackQuery, InlineKeyboardButton, InlineKeyboardMarkup, Message},
+};
+use tracing::Level;
+
+// ─── Callback constants ──────────────────────────────────────────────────────
+
+callback_consts!("import" => cancel, noop, confirm, deny);
+
+/// Callback value of the button creating a traveler named as the member.
+const NEW_TRAVELER_VALUE: &str = "__new__";
+
+const TRAVELERS_PER_ROW: usize = 2;
+
+// ─── State ───────────────────────────────────────────────────────────────────
+
+/// An import whose members are being matched to travelers. `mappings` holds
+/// the members matched so far, in the order of [`Import::members`].
+#[derive(Debug, Clone)]
+pub struct PendingImport {
+    pub import: Import,
+    pub mappings: Vec<MemberMapping>,
+}
+
+impl PendingImport {
+    /// Returns the first member not matched to a traveler yet.
+    fn next_member(&self) -> Option<&String> {
+        self.import.members.get(self.mappings.len())
+    }
+
+    /// Returns whether no member has been matched to `name` yet.
+    fn is_free(&self, name: &str) -> bool {
+        !self
+            .mappings
+            .iter()
+            .any(|mapping| mapping.traveler.to_lowercase() == name.to_lowercase())
+    }
+
+    /// Returns the name of the traveler to create for `member`, if it is a
+    /// valid name not taken by a traveler or another member.
+    fn new_traveler_name(&self, member: &str, travelers: &[Traveler]) -> Option<Name> {
+        Name::from_str(member).ok().filter(|name| {
+            self.is_free(name)
+                && !travelers
+                    .iter()
+                    .any(|traveler| traveler.name.to_lowercase() == name.to_lowercase())
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ImportState {
+    AskTraveler(Box<PendingImport>),
+    Confirm(Box<PendingImport>),
+}
+
+// ─── Helpers ─────────────────────────────────────────────────────────────────
+
+async fn send_text(bot: &Bot, msg: &Message, text: String) -> HandlerResult {
+    bot.send_message(msg.chat.id, text)
+        .in_topic(msg.topic_id())
+        .await?;
+    Ok(())
+}
+
+/// Lists the rows that couldn't be imported, or returns an empty string if
+/// there are none.
+fn skipped_rows(skipped: &[SkippedRow], ctx: Arc<Mutex<Context>>) -> String {
+    if skipped.is_empty() {
+        return String::new();
+    }
+    let header = i18n::commands::IMPORT_SKIPPED.translate_with_args(
+        ctx.clone(),
+        &hashmap! { i18n::args::COUNT.into() => skipped.len().into() },
+    );
+    let rows = skipped.iter().map(|row| {
+        let currency = match &row.reason {
+            SkipReason::OtherCurrency(currency) => currency.clone(),
+            _ => String::new(),
+        };
+        i18n::commands::IMPORT_SKIPPED_ROW.translate_with_args(
+            ctx.clone(),
+            &hashmap! {
+                i18n::args::LINE.into() => row.line.into(),
+                i18n::args::REASON.into() => row.reason.as_ref().to_owned().into(),
+                i18n::args::CURRENCY.into() => currency.into(),
+            },
+        )
+    });
+    std::iter::once(header)
+        .chain(rows)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Builds the keyboard offering the travelers not matched yet to another
+/// member, with a button creating a traveler named as `member` when possible.
+fn travelers_keyboard(
+    pending: &PendingImport,
+    member: &str,
+    travelers: &[Traveler],
+    page: usize,
+    ctx: Arc<Mutex<Context>>,
+) -> InlineKeyboardMarkup {
+    let items = travelers
+        .iter()
+        .filter(|traveler| pending.is_free(&traveler.name))
+        .map(|traveler| PickerItem {
+            label: traveler.name.to_string(),
+            value: traveler.number.to_string(),
+        })
+        .collect::<Vec<_>>();
+    let action_buttons = pending
+        .new_traveler_name(member, travelers)
+        .map(|name| {
+            InlineKeyboardButton::callback(
+                i18n::labels::NEW_TRAVELER_BUTTON.translate_with_args(
+                    ctx.clone(),
+                    &hashmap! { i18n::args::NAME.into() => name.to_string().into() },
+                ),
+                format!("{CALLBACK_PREFIX}{NEW_TRAVELER_VALUE}"),
+            )
+        })
+        .into_iter()
+        .collect::<Vec<_>>();
+    keyboard::paginated_keyboard(PaginatedKeyboardConfig {
+        items: &items,
+        page,
+        columns: TRAVELERS_PER_ROW,
+        rows_per_page: DEFAULT_ROWS_PER_PAGE,
+        prefix: CALLBACK_PREFIX,
+        cancel_callback: CANCEL_CALLBACK,
+        noop_callback: NOOP_CALLBACK,
+        action_buttons: &action_buttons,
+        show_cancel: true,
+        ctx: ctx.clone(),
+    })
+    .unwrap_or_else(|| {
+        let cancel = InlineKeyboardButton::callback(
+            i18n::labels::CANCEL_BUTTON.translate(ctx),
+            CANCEL_CALLBACK,
+        );
+        let rows = [action_buttons, vec![cancel]];
+        InlineKeyboardMarkup::new(rows.into_iter().filter(|row| !row.is_empty()))
+    })
+}
+
+/// Matches the members named like an existing traveler, then asks for the
+/// traveler of the first member left or, once all are matched, for the
+/// confirmation of the import.
+async fn next_step(
+    db: Arc<Surreal<Any>>,
+    bot: &Bot,
+    dialogue: &PendingCommandDialogue,
+    msg: &Message,
+    mut pending: Box<PendingImport>,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    let travelers = Traveler::db_select(db, msg.ledger_id()).await?;
+    while let Some(member) = pending.next_member().cloned() {
+        let namesake = travelers.iter().find(|traveler| {
+            traveler.name.to_lowercase() == member.to_lowercase() && pending.is_free(&traveler.name)
+        });
+        let Some(traveler) = namesake else {
+            let prompt = i18n::dialogues::IMPORT_ASK_TRAVELER.translate_with_args(
+                ctx.clone(),
+                &hashmap! { i18n::args::MEMBER.into() => member.clone().into() },
+            );
+            let kb = travelers_keyboard(&pending, &member, &travelers, 0, ctx);
+            bot.send_message(msg.chat.id, prompt)
+                .in_topic(msg.topic_id())
+                .reply_markup(kb)
+                .await?;
+            dialogue
+                .update(PendingCommandState::Import(ImportState::AskTraveler(
+                    pending,
+                )))
+                .await?;
+            return Ok(());
+        };
+        pending.mappings.push(MemberMapping {
+            member,
+            traveler: traveler.name.clone(),
+            create: false,
+        });
+    }
+    ask_confirmation(bot, dialogue, msg, pending, ctx).await
+}
+
+async fn ask_confirmation(
+    bot: &Bot,
+    dialogue: &PendingCommandDialogue,
+    msg: &Message,
+    mut pending: Box<PendingImport>,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    pending.import.skip_self_transfers(&pending.mappings);
+    let travelers = pending
+        .mappings
+        .iter()
+        .map(|mapping| {
+            let key = if mapping.create {
+                i18n::dialogues::IMPORT_MEMBER_NEW
+            } else {
+                i18n::dialogues::IMPORT_MEMBER_EXISTING
+            };
+            key.translate_with_args(
+                ctx.clone(),
+                &hashmap! {
+                    i18n::args::MEMBER.into() => mapping.member.clone().into(),
+                    i18n::args::NAME.into() => mapping.traveler.to_string().into(),
+                },
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut prompt = i18n::dialogues::IMPORT_CONFIRM.translate_with_args(
+        ctx.clone(),
+        &hashmap! {
+            i18n::args::FORMAT.into() => pending.import.format.as_ref().to_owned().into(),
+            i18n::args::EXPENSES_COUNT.into() => pending.import.expenses.len().into(),
+            i18n::args::TRANSFERS_COUNT.into() => pending.import.transfers.len().into(),
+            i18n::args::TRAVELERS.into() => travelers.into(),
+        },
+    );
+    let skipped = skipped_rows(&pending.import.skipped, ctx.clone());
+    if !skipped.is_empty() {
+        prompt = format!("{prompt}\n\n{skipped}");
+    }
+    let kb = confirmation_keyboard(ConfirmConfig {
+        confirm_callback: CONFIRM_CALLBACK,
+        deny_callback: DENY_CALLBACK,
+        ctx,
+    });
+    bot.send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id())
+        .reply_markup(kb)
+        .await?;
+    dialogue
+        .update(PendingCommandState::Import(ImportState::Confirm(pending)))
+        .await?;
+    Ok(())
+}
+
+async fn do_import(
+    db: Arc<Surreal<Any>>,
+    bot: &Bot,
+    msg: &Message,
+    pending: &PendingImport,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    let outcome = import(db, msg, &pending.import, &pending.mappings, ctx.clone())
+        .await
+        .unwrap_or_else(|err| CommandOutcome::Failure(err.translate(ctx)));
+    send_outcome(bot, msg, &outcome, None).await?;
+    Ok(())
+}
+
+async fn send_cancel(bot: &Bot, msg: &Message, ctx: Arc<Mutex<Context>>) -> HandlerResult {
+    let process_name = i18n::commands::RUNNING_PROCESS_IMPORT.translate(Arc::clone(&ctx));
+    let cancel_msg = i18n::commands::CANCEL_OK.translate_with_args(
+        ctx,
+        &hashmap! { i18n::args::PROCESS.into() => process_name.into() },
+    );
+    send_text(bot, msg, cancel_msg).await
+}
+
+// ─── Start ───────────────────────────────────────────────────────────────────
+
+#[apply(trace_state_db)]
+pub async fn start(
+    db: Arc<Surreal<Any>>,
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    msg: Message,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let Some(content) = attachment::read(
+        &bot,
+        &msg,
+        i18n::commands::IMPORT_USAGE,
+        i18n::errors::COMMAND_ERROR_IMPORT,
+        ctx.clone(),
+    )
+    .await?
+    else {
+        dialogue.exit().await?;
+        return Ok(());
+    };
+
+    let (currency, tz) = {
+        let ctx = ctx.lock().expect("Failed to lock context");
+        (ctx.currency.clone(), ctx.timezone.clone())
+    };
+    match Import::from_csv(&content, &currency, &tz) {
+        Ok(import) if import.expenses.is_empty() && import.transfers.is_empty() => {
+            dialogue.exit().await?;
+            let nothing = i18n::commands::IMPORT_NOTHING.translate(ctx.clone());
+            let skipped = skipped_rows(&import.skipped, ctx);
+            let reply = if skipped.is_empty() {
+                nothing
+            } else {
+                format!("{nothing}\n\n{skipped}")
+            };
+            send_text(&bot, &msg, reply).await?;
+        }
+        Ok(import) => {
+            let pending = Box::new(PendingImport {
+                import,
+                mappings: Vec::new(),
+            });
+            next_step(db, &bot, &dialogue, &msg, pending, ctx).await?;
+        }
+        Err(err) => {
+            tracing::warn!("{err}");
+            dialogue.exit().await?;
+            send_text(&bot, &msg, err.translate(ctx)).await?;
+        }
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Dialogue started: /import");
+    Ok(())
+}
+
+// ─── Traveler text handler ───────────────────────────────────────────────────
+
+/// Text handler for the AskTraveler state — accepts the name of an existing
+/// traveler, or of a new one to create.
+#[apply(trace_skip_all)]
+pub async fn receive_traveler(
+    db: Arc<Surreal<Any>>,
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    mut pending: Box<PendingImport>,
+    msg: Message,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let Some(member) = pending.next_member().cloned() else {
+        return ask_confirmation(&bot, &dialogue, &msg, pending, ctx).await;
+    };
+    let text = msg.text().map(str::trim).unwrap_or("");
+    let name = match Name::from_str(text) {
+        Ok(name) => name,
+        Err(err) => {
+            tracing::warn!("{err}");
+            return send_text(&bot, &msg, err.translate(ctx)).await;
+        }
+    };
+    if !pending.is_free(&name) {
+        let reply = i18n::dialogues::IMPORT_TRAVELER_TAKEN.translate_with_args(
+            ctx,
+            &hashmap! { i18n::args::NAME.into() => name.to_string().into() },
+        );
+        return send_text(&bot, &msg, reply).await;
+    }
+
+    let travelers = Traveler::db_select(db.clone(), msg.ledger_id()).await?;
+    let existing = travelers
+        .into_iter()
+        .find(|traveler| traveler.name.to_lowercase() == name.to_lowercase());
+    pending.mappings.push(match existing {
+        Some(traveler) => MemberMapping {
+            member,
+            traveler: traveler.name,
+            create: false,
+        },
+        None => MemberMapping {
+            member,
+            traveler: name,
+            create: true,
+        },
+    });
+    next_step(db, &bot, &dialogue, &msg, pending, ctx).await?;
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    Ok(())
+}
+
+// ─── Traveler callback handler ───────────────────────────────────────────────
+
+#[apply(trace_callback)]
+pub async fn receive_traveler_callback(
+    db: Arc<Surreal<Any>>,
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    mut pending: Box<PendingImport>,
+    q: CallbackQuery,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let action = keyboard::handle_callback_prelude(
+        &bot,
+        &dialogue,
+        &q,
+        &ctx,
+        &keyboard::CallbackConfig {
+            cancel_callback: CANCEL_CALLBACK,
+            noop_callback: NOOP_CALLBACK,
+            prefix: CALLBACK_PREFIX,
+            running_process_key: i18n::commands::RUNNING_PROCESS_IMPORT,
+        },
+    )
+    .await?;
+
+    let keyboard::CallbackAction::Selection { value, msg } = action else {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(());
+    };
+    let Some(member) = pending.next_member().cloned() else {
+        return ask_confirmation(&bot, &dialogue, &msg, pending, ctx).await;
+    };
+    let travelers = Traveler::db_select(db.clone(), msg.ledger_id()).await?;
+
+    // Page navigation: rebuild the keyboard for the new page in place.
+    if let Some(page) = value.strip_prefix(&format!("{PAGE_MARKER}:")) {
+        if let Ok(page) = page.parse::<usize>() {
+            let kb = travelers_keyboard(&pending, &member, &travelers, page, ctx);
+            let _ = bot
+                .edit_message_reply_markup(msg.chat.id, msg.id)
+                .reply_markup(kb)
+                .await;
+        }
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(());
+    }
+
+    let mapping = if value == NEW_TRAVELER_VALUE {
+        pending
+            .new_traveler_name(&member, &travelers)
+            .map(|name| MemberMapping {
+                member,
+                traveler: name,
+                create: true,
+            })
+    } else {
+        Traveler::db_resolve_by_number(db.clone(), msg.ledger_id(), &value)
+            .await
+            .filter(|traveler| pending.is_free(&traveler.name))
+            .map(|traveler| MemberMapping {
+                member,
+                traveler: traveler.name,
+                create: false,
+            })
+    };
+    let Some(mapping) = mapping else {
+        tracing::warn!("Could not match the member from callback data: {value:?}");
+        return Ok(());
+    };
+
+    keyboard::echo_callback_selection(&bot, &msg, &mapping.traveler).await;
+    pending.mappings.push(mapping);
+    next_step(db, &bot, &dialogue, &msg, pending, ctx).await?;
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    Ok(())
+}
+
+// ─── Confirm text handler ────────────────────────────────────────────────────
+
+#[apply(trace_skip_all)]
+pub async fn receive_confirm_text(
+    db: Arc<Surreal<Any>>,
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    pending: Box<PendingImport>,
+    msg: Message,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let text = msg.text().map(str::trim).unwrap_or("");
+    match parse_confirm_answer(text) {
+        ConfirmAnswer::Yes => {
+            do_import(db, &bot, &msg, &pending, ctx).await?;
+            dialogue.exit().await?;
+        }
+        ConfirmAnswer::No => {
+            dialogue.exit().await?;
+            send_cancel(&bot, &msg, ctx).await?;
+        }
+        ConfirmAnswer::Unknown => {
+            ask_confirmation(&bot, &dialogue, &msg, pending, ctx).await?;
+        }
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    Ok(())
+}
+
+// ─── Confirm callback handler ────────────────────────────────────────────────
+
+#[apply(trace_callback)]
+pub async fn receive_confirm_callback(
+    db: Arc<Surreal<Any>>,
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    pending: Box<PendingImport>,
+    q: CallbackQuery,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let _ = bot.answer_callback_query(q.id.clone()).await;
+
+    let Some(msg) = q.regular_message().cloned() else {
+        tracing::warn!("Callback query without an accessible message; ignoring");
+        return Ok(());
+    };
+
+    let data = q.data.as_deref().unwrap_or("");
+
+    let label = if data == CONFIRM_CALLBACK {
+        i18n::labels::CONFIRM_YES_BUTTON.translate(ctx.clone())
+    } else {
+        i18n::labels::CONFIRM_NO_BUTTON.translate(ctx.clone())
+    };
+    keyboard::echo_callback_selection(&bot, &msg, &label).await;
+
+    if data == CONFIRM_CALLBACK {
+        do_import(db, &bot, &msg, &pending, ctx).await?;
+        dialogue.exit().await?;
+    } else {
+        dialogue.exit().await?;
+        send_cancel(&bot, &msg, ctx).await?;
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    Ok(())
+}
@@ -1,9 +1,9 @@
-//! `/listexpenses` filter dialogue: asks the user for a description to filter
+//! `/listexpenses` filter dialogue: asks the user for the filters to list
 //! expenses by when the "Filter…" inline keyboard button is tapped.
 
 use crate::{
     Context, HandlerResult,
-    commands::{Command, command_reply},
+    commands::{Command, command_reply, send_outcome},
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     dialogues::{
         pending_command_dialogue::{
@@ -12,6 +12,7 @@ use crate::{
         storage::DialogueRegistry,
     },
     i18n::{self, Translate, TranslateWithArgs},
+    topics::{InTopic, MessageExt},
 };
 use macro_rules_attribute::apply;
 use maplit::hashmap;
@@ -31,7 +32,7 @@ pub enum ListExpensesState {
 }
 
 /// Callback handler for the "Filter…" button on `/listexpenses`. Starts the
-/// pending-command dialogue and prompts the user for a description.
+/// pending-command dialogue and prompts the user for the filters.
 #[apply(trace_callback)]
 pub async fn receive_filter_callback(
     bot: Bot,
@@ -50,8 +51,8 @@ pub async fn receive_filter_callback(
     let _ = bot.edit_message_reply_markup(msg.chat.id, msg.id).await;
 
     // If another dialogue is already running, refuse to start a new one.
-    if registry.any_running(msg.chat.id).await {
-        let process_name = match registry.running_label(msg.chat.id).await {
+    if registry.any_running(msg.dialogue_key().0).await {
+        let process_name = match registry.running_label(msg.dialogue_key().0).await {
             Some(label) => label.translate(Arc::clone(&ctx)),
             None => i18n::commands::RUNNING_PROCESS_UNKNOWN.translate(Arc::clone(&ctx)),
         };
@@ -62,6 +63,7 @@ pub async fn receive_filter_callback(
                 &hashmap! { i18n::args::PROCESS.into() => process_name.into() },
             ),
         )
+        .in_topic(msg.topic_id())
         .await?;
         return Ok(());
     }
@@ -69,7 +71,7 @@ pub async fn receive_filter_callback(
     // Build the dialogue handle manually (we intentionally skip
     // `enter_dialogue` in the handler tree to avoid writing a default
     // `Start` state that would trip the `any_running` check above).
-    let dialogue: PendingCommandDialogue = Dialogue::new(storage, msg.chat.id);
+    let dialogue: PendingCommandDialogue = Dialogue::new(storage, msg.dialogue_key().0);
     dialogue
         .update(PendingCommandState::ListExpenses(
             ListExpensesState::AskDescription,
@@ -80,6 +82,7 @@ pub async fn receive_filter_callback(
         msg.chat.id,
         i18n::dialogues::LIST_EXPENSES_ASK_DESCRIPTION.translate(ctx),
     )
+    .in_topic(msg.topic_id())
     .await?;
     tracing::info!("Dialogue started: /listexpenses filter");
 
@@ -102,16 +105,16 @@ pub async fn receive_description(
             msg.chat.id,
             i18n::dialogues::LIST_EXPENSES_ASK_DESCRIPTION.translate(ctx),
         )
+        .in_topic(msg.topic_id())
         .await?;
         return Ok(());
     }
 
     let cmd = Command::ListExpenses {
-        description: text.to_owned(),
+        filter: text.to_owned(),
     };
     let outcome = command_reply(db, &msg, &cmd, ctx).await;
-    bot.send_message(msg.chat.id, outcome.into_message())
-        .await?;
+    send_outcome(&bot, &msg, &outcome, None).await?;
     dialogue.exit().await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     Ok(())
@@ -157,8 +160,8 @@ mod tests {
         bot.update("Toll");
         let msg = bot.dispatch_and_last_message().await;
         let text = msg.unwrap();
-        // The filtered output should mention "Toll road" but not "Dinner".
-        assert!(text.contains("Toll road"), "Expected 'Toll road' in: {text}");
+        // The filtered output should mention "Toll road", highlighted, but not "Dinner".
+        assert!(text.contains("*Toll* road"), "Expected '*Toll* road' in: {text}");
         assert!(!text.contains("Dinner"), "Did not expect 'Dinner' in: {text}");
     }
 
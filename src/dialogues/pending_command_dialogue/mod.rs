@@ -16,12 +16,18 @@ pub mod clear_travelers;
 pub mod delete_expense;
 pub mod delete_transfer;
 pub mod delete_traveler;
+pub mod import;
 pub mod list_expenses;
+pub mod new_trip;
+pub mod rename_trip;
+pub mod restore;
 pub mod set_currency;
 pub mod set_language;
+pub mod set_timezone;
 pub mod show_expense;
 pub mod transfer;
 
+use crate::topics::DialogueKey;
 use add_traveler::AddTravelerState;
 use clear_all::ClearAllState;
 use clear_expenses::ClearExpensesState;
@@ -30,16 +36,18 @@ use clear_travelers::ClearTravelersState;
 use delete_expense::DeleteExpenseState;
 use delete_transfer::DeleteTransferState;
 use delete_traveler::DeleteTravelerState;
+use import::ImportState;
 use list_expenses::ListExpensesState;
+use new_trip::NewTripState;
+use rename_trip::RenameTripState;
+use restore::RestoreState;
 use set_currency::SetCurrencyState;
 use set_language::SetLanguageState;
+use set_timezone::SetTimezoneState;
 use show_expense::ShowExpenseState;
-use teloxide::{
-    dispatching::{
-        HandlerExt, UpdateHandler,
-        dialogue::{Dialogue, InMemStorage},
-    },
-    types::{CallbackQuery, Message},
+use teloxide::dispatching::{
+    HandlerExt, UpdateHandler,
+    dialogue::{Dialogue, InMemStorage},
 };
 use transfer::TransferState;
 
@@ -54,12 +62,17 @@ pub enum PendingCommandState {
     DeleteTransfer(DeleteTransferState),
     SetLanguage(SetLanguageState),
     SetCurrency(SetCurrencyState),
+    SetTimezone(SetTimezoneState),
     ListExpenses(ListExpensesState),
     Transfer(TransferState),
     ClearTravelers(ClearTravelersState),
     ClearExpenses(ClearExpensesState),
     ClearTransfers(ClearTransfersState),
     ClearAll(ClearAllState),
+    NewTrip(NewTripState),
+    RenameTrip(RenameTripState),
+    Restore(RestoreState),
+    Import(ImportState),
 }
 
 pub type PendingCommandStorage = InMemStorage<PendingCommandState>;
@@ -81,12 +94,17 @@ impl crate::dialogues::storage::DialogueState for PendingCommandState {
             PendingCommandState::DeleteTransfer(_) => RUNNING_PROCESS_DELETE_TRANSFER,
             PendingCommandState::SetLanguage(_) => RUNNING_PROCESS_SET_LANGUAGE,
             PendingCommandState::SetCurrency(_) => RUNNING_PROCESS_SET_CURRENCY,
+            PendingCommandState::SetTimezone(_) => RUNNING_PROCESS_SET_TIMEZONE,
             PendingCommandState::ListExpenses(_) => RUNNING_PROCESS_LIST_EXPENSES,
             PendingCommandState::Transfer(_) => RUNNING_PROCESS_TRANSFER,
             PendingCommandState::ClearTravelers(_) => RUNNING_PROCESS_CLEAR_TRAVELERS,
             PendingCommandState::ClearExpenses(_) => RUNNING_PROCESS_CLEAR_EXPENSES,
             PendingCommandState::ClearTransfers(_) => RUNNING_PROCESS_CLEAR_TRANSFERS,
             PendingCommandState::ClearAll(_) => RUNNING_PROCESS_CLEAR_ALL,
+            PendingCommandState::NewTrip(_) => RUNNING_PROCESS_NEW_TRIP,
+            PendingCommandState::RenameTrip(_) => RUNNING_PROCESS_RENAME_TRIP,
+            PendingCommandState::Restore(_) => RUNNING_PROCESS_RESTORE,
+            PendingCommandState::Import(_) => RUNNING_PROCESS_IMPORT,
         }
     }
 }
@@ -100,7 +118,7 @@ pub fn handler_branch() -> UpdateHandler<Box<dyn std::error::Error + Send + Sync
     dptree::entry()
         // Only enter this subtree if a pending-command dialogue is active.
         .filter_async(crate::dialogues::storage::is_running::<PendingCommandState>)
-        .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+        .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
         .branch(
             case![AddTraveler(state)]
                 .branch(case![AddTravelerState::AskName].endpoint(add_traveler::receive_name)),
@@ -146,6 +164,11 @@ pub fn handler_branch() -> UpdateHandler<Box<dyn std::error::Error + Send + Sync
                 case![SetCurrencyState::AskCurrency].endpoint(set_currency::receive_currency),
             ),
         )
+        .branch(
+            case![SetTimezone(state)].branch(
+                case![SetTimezoneState::AskTimezone].endpoint(set_timezone::receive_timezone),
+            ),
+        )
         .branch(case![ListExpenses(state)].branch(
             case![ListExpensesState::AskDescription].endpoint(list_expenses::receive_description),
         ))
@@ -178,12 +201,33 @@ pub fn handler_branch() -> UpdateHandler<Box<dyn std::error::Error + Send + Sync
             case![ClearAll(state)]
                 .branch(case![ClearAllState::Confirm].endpoint(clear_all::receive_confirm_text)),
         )
+        .branch(
+            case![NewTrip(state)]
+                .branch(case![NewTripState::AskName].endpoint(new_trip::receive_name)),
+        )
+        .branch(
+            case![RenameTrip(state)]
+                .branch(case![RenameTripState::AskName].endpoint(rename_trip::receive_name)),
+        )
+        .branch(
+            case![Restore(state)].branch(
+                case![RestoreState::Confirm(backup)].endpoint(restore::receive_confirm_text),
+            ),
+        )
+        .branch(
+            case![Import(state)]
+                .branch(case![ImportState::AskTraveler(pending)].endpoint(import::receive_traveler))
+                .branch(
+                    case![ImportState::Confirm(pending)].endpoint(import::receive_confirm_text),
+                ),
+        )
 }
 
 /// All callback-data prefixes used by pending-command dialogue keyboards.
 const CALLBACK_PREFIXES: &[&str] = &[
     set_language::CALLBACK_PREFIX,
     set_currency::CALLBACK_PREFIX,
+    set_timezone::CALLBACK_PREFIX,
     delete_traveler::CALLBACK_PREFIX,
     transfer::CALLBACK_PREFIX_FROM,
     transfer::CALLBACK_PREFIX_TO,
@@ -194,6 +238,8 @@ const CALLBACK_PREFIXES: &[&str] = &[
     clear_expenses::CALLBACK_PREFIX,
     clear_transfers::CALLBACK_PREFIX,
     clear_all::CALLBACK_PREFIX,
+    restore::CALLBACK_PREFIX,
+    import::CALLBACK_PREFIX,
 ];
 
 /// Returns `true` if the callback data matches any pending-command dialogue
@@ -212,7 +258,7 @@ pub fn callback_branch() -> UpdateHandler<Box<dyn std::error::Error + Send + Syn
     use teloxide::dptree::{self, case};
 
     dptree::entry()
-        .enter_dialogue::<CallbackQuery, PendingCommandStorage, PendingCommandState>()
+        .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
         .branch(
             case![SetLanguage(state)].branch(
                 case![SetLanguageState::AskLangid].endpoint(set_language::receive_callback),
@@ -223,6 +269,11 @@ pub fn callback_branch() -> UpdateHandler<Box<dyn std::error::Error + Send + Syn
                 case![SetCurrencyState::AskCurrency].endpoint(set_currency::receive_callback),
             ),
         )
+        .branch(
+            case![SetTimezone(state)].branch(
+                case![SetTimezoneState::AskTimezone].endpoint(set_timezone::receive_callback),
+            ),
+        )
         .branch(
             case![DeleteTraveler(state)]
                 .branch(
@@ -286,4 +337,17 @@ pub fn callback_branch() -> UpdateHandler<Box<dyn std::error::Error + Send + Syn
                 case![ClearAllState::Confirm].endpoint(clear_all::receive_confirm_callback),
             ),
         )
+        .branch(case![Restore(state)].branch(
+            case![RestoreState::Confirm(backup)].endpoint(restore::receive_confirm_callback),
+        ))
+        .branch(
+            case![Import(state)]
+                .branch(
+                    case![ImportState::AskTraveler(pending)]
+                        .endpoint(import::receive_traveler_callback),
+                )
+                .branch(
+                    case![ImportState::Confirm(pending)].endpoint(import::receive_confirm_callback),
+                ),
+        )
 }
@@ -0,0 +1,181 @@
+//! `/newtrip` dialogue: asks the user for the new trip's name when the
+//! command is invoked without an inline argument, then delegates to the
+//! regular command handler.
+
+use crate::{
+    Context, HandlerResult,
+    commands::{Command, CommandArg, command_reply, send_outcome},
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    dialogues::pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
+    i18n::{self, Translate},
+    topics::{InTopic, MessageExt},
+    traveler::Name,
+};
+use macro_rules_attribute::apply;
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::{Bot, requests::Requester, types::Message};
+use tracing::Level;
+
+#[derive(Debug, Clone)]
+pub enum NewTripState {
+    AskName,
+}
+
+#[apply(trace_state)]
+pub async fn start(
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    msg: Message,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+    bot.send_message(
+        msg.chat.id,
+        i18n::dialogues::NEW_TRIP_ASK_NAME.translate(ctx),
+    )
+    .in_topic(msg.topic_id())
+    .await?;
+    dialogue
+        .update(PendingCommandState::NewTrip(NewTripState::AskName))
+        .await?;
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Dialogue started: /newtrip");
+    Ok(())
+}
+
+#[apply(trace_state_db)]
+pub async fn receive_name(
+    db: Arc<Surreal<Any>>,
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    msg: Message,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let text = msg.text().map(str::trim).unwrap_or("");
+    if text.is_empty() {
+        tracing::warn!("Invalid name: received empty input.");
+        bot.send_message(
+            msg.chat.id,
+            i18n::dialogues::NEW_TRIP_INVALID_NAME.translate(ctx),
+        )
+        .in_topic(msg.topic_id())
+        .await?;
+        return Ok(());
+    }
+
+    let name = match Name::from_str(text) {
+        Ok(name) => name,
+        Err(err) => {
+            tracing::warn!("{err}");
+            let reply = format!(
+                "{invalid}\n\n{reason}",
+                invalid = i18n::dialogues::NEW_TRIP_INVALID_NAME.translate(ctx.clone()),
+                reason = err.translate(ctx),
+            );
+            bot.send_message(msg.chat.id, reply)
+                .in_topic(msg.topic_id())
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let cmd = Command::NewTrip {
+        name: CommandArg::Provided(name),
+    };
+    let outcome = command_reply(db, &msg, &cmd, ctx.clone()).await;
+    send_outcome(&bot, &msg, &outcome, None).await?;
+    if outcome.is_success() {
+        dialogue.exit().await?;
+    } else {
+        bot.send_message(
+            msg.chat.id,
+            i18n::dialogues::NEW_TRIP_ASK_NAME.translate(ctx),
+        )
+        .in_topic(msg.topic_id())
+        .await?;
+    }
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, Translate, TranslateWithArgs},
+        tests::TestBot,
+    };
+    use maplit::hashmap;
+
+    test! { ask_name_on_empty_invocation,
+        let db = db().await;
+
+        // New trip without specifying a name -> ask for name
+        let mut bot = TestBot::new(db, "/newtrip");
+        let response = i18n::dialogues::NEW_TRIP_ASK_NAME.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { receive_name_ok,
+        let db = db().await;
+
+        // New trip without specifying a name -> ask for name
+        let mut bot = TestBot::new(db, "/newtrip");
+        bot.dispatch().await;
+
+        // Provide the name as a follow-up message
+        bot.update("Japan");
+        let response = i18n::commands::NEW_TRIP_OK.translate_with_args_default(
+            &hashmap! {i18n::args::NAME.into() => "Japan".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { receive_name_empty_input_reprompts,
+        let db = db().await;
+
+        // New trip without specifying a name -> ask for name
+        let mut bot = TestBot::new(db, "/newtrip");
+        bot.dispatch().await;
+
+        // Reply with whitespace only -> the bot re-asks, not error out
+        bot.update("   ");
+        let response = i18n::dialogues::NEW_TRIP_INVALID_NAME.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { new_trip_already_exists_via_dialogue,
+        let db = db().await;
+
+        // The default trip already exists
+        let mut bot = TestBot::new(db, "/newtrip");
+        bot.dispatch().await;
+
+        // Provide the name as a follow-up message -> the bot replies that
+        // the trip already exists and re-prompts so the user can retry.
+        bot.update("Default");
+        let response = i18n::dialogues::NEW_TRIP_ASK_NAME.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { cancel_during_dialogue,
+        let db = db().await;
+
+        // Start the dialogue
+        let mut bot = TestBot::new(db, "/newtrip");
+        bot.dispatch().await;
+
+        // Cancel before sending the name
+        bot.update("/cancel");
+        let response = crate::tests::helpers::cancel_ok_for(
+            i18n::commands::RUNNING_PROCESS_NEW_TRIP,
+        );
+        bot.test_last_message(&response).await;
+    }
+}
@@ -0,0 +1,157 @@
+//! `/renametrip` dialogue: asks the user for the new name of the active trip
+//! when the command is invoked without an inline argument, then delegates
+//! to the regular command handler.
+
+use crate::{
+    Context, HandlerResult,
+    commands::{Command, CommandArg, command_reply, send_outcome},
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    dialogues::pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
+    i18n::{self, Translate},
+    topics::{InTopic, MessageExt},
+    traveler::Name,
+};
+use macro_rules_attribute::apply;
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::{Bot, requests::Requester, types::Message};
+use tracing::Level;
+
+#[derive(Debug, Clone)]
+pub enum RenameTripState {
+    AskName,
+}
+
+#[apply(trace_state)]
+pub async fn start(
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    msg: Message,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+    bot.send_message(
+        msg.chat.id,
+        i18n::dialogues::RENAME_TRIP_ASK_NAME.translate(ctx),
+    )
+    .in_topic(msg.topic_id())
+    .await?;
+    dialogue
+        .update(PendingCommandState::RenameTrip(RenameTripState::AskName))
+        .await?;
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Dialogue started: /renametrip");
+    Ok(())
+}
+
+#[apply(trace_state_db)]
+pub async fn receive_name(
+    db: Arc<Surreal<Any>>,
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    msg: Message,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let text = msg.text().map(str::trim).unwrap_or("");
+    if text.is_empty() {
+        tracing::warn!("Invalid name: received empty input.");
+        bot.send_message(
+            msg.chat.id,
+            i18n::dialogues::RENAME_TRIP_INVALID_NAME.translate(ctx),
+        )
+        .in_topic(msg.topic_id())
+        .await?;
+        return Ok(());
+    }
+
+    let name = match Name::from_str(text) {
+        Ok(name) => name,
+        Err(err) => {
+            tracing::warn!("{err}");
+            let reply = format!(
+                "{invalid}\n\n{reason}",
+                invalid = i18n::dialogues::RENAME_TRIP_INVALID_NAME.translate(ctx.clone()),
+                reason = err.translate(ctx),
+            );
+            bot.send_message(msg.chat.id, reply)
+                .in_topic(msg.topic_id())
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let cmd = Command::RenameTrip {
+        name: CommandArg::Provided(name),
+    };
+    let outcome = command_reply(db, &msg, &cmd, ctx.clone()).await;
+    send_outcome(&bot, &msg, &outcome, None).await?;
+    if outcome.is_success() {
+        dialogue.exit().await?;
+    } else {
+        bot.send_message(
+            msg.chat.id,
+            i18n::dialogues::RENAME_TRIP_ASK_NAME.translate(ctx),
+        )
+        .in_topic(msg.topic_id())
+        .await?;
+    }
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, Translate, TranslateWithArgs},
+        tests::TestBot,
+    };
+    use maplit::hashmap;
+
+    test! { ask_name_on_empty_invocation,
+        let db = db().await;
+
+        // Rename trip without specifying a name -> ask for name
+        let mut bot = TestBot::new(db, "/renametrip");
+        let response = i18n::dialogues::RENAME_TRIP_ASK_NAME.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { receive_name_ok,
+        let db = db().await;
+
+        // Rename trip without specifying a name -> ask for name
+        let mut bot = TestBot::new(db, "/renametrip");
+        bot.dispatch().await;
+
+        // Provide the name as a follow-up message
+        bot.update("Japan");
+        let response = i18n::commands::RENAME_TRIP_OK.translate_with_args_default(
+            &hashmap! {
+                i18n::args::OLD_NAME.into() => "Default".into(),
+                i18n::args::NAME.into() => "Japan".into(),
+            },
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { cancel_during_dialogue,
+        let db = db().await;
+
+        // Start the dialogue
+        let mut bot = TestBot::new(db, "/renametrip");
+        bot.dispatch().await;
+
+        // Cancel before sending the name
+        bot.update("/cancel");
+        let response = crate::tests::helpers::cancel_ok_for(
+            i18n::commands::RUNNING_PROCESS_RENAME_TRIP,
+        );
+        bot.test_last_message(&response).await;
+    }
+}
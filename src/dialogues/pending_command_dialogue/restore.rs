@@ -0,0 +1,213 @@
+//! `/restore` confirmation dialogue. The backup file sent along with the
+//! command is read and validated before asking to confirm.
+
+use crate::{
+    Context, HandlerResult,
+    backup::Backup,
+    commands::{CommandOutcome, restore, send_outcome},
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    dialogues::{
+        attachment,
+        pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
+    },
+    i18n::{self, ToFluentDateTime, Translate, TranslateWithArgs},
+    keyboard::{self, ConfirmAnswer, ConfirmConfig, confirmation_keyboard, parse_confirm_answer},
+    topics::{InTopic, MessageExt},
+};
+use macro_rules_attribute::apply;
+use maplit::hashmap;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Datetime, Surreal, engine::any::Any};
+use teloxide::{
+    Bot,
+    payloads::SendMessageSetters,
+    requests::Requester,
+    types::{CallbackQuery, Message},
+};
+use tracing::Level;
+
+// ─── Callback constants ──────────────────────────────────────────────────────
+
+callback_consts!("restore" => confirm, deny);
+
+// ─── State ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub enum RestoreState {
+    Confirm(Box<Backup>),
+}
+
+// ─── Helpers ─────────────────────────────────────────────────────────────────
+
+async fn send_text(bot: &Bot, msg: &Message, text: String) -> HandlerResult {
+    bot.send_message(msg.chat.id, text)
+        .in_topic(msg.topic_id())
+        .await?;
+    Ok(())
+}
+
+async fn ask_confirmation(
+    bot: &Bot,
+    dialogue: &PendingCommandDialogue,
+    msg: &Message,
+    backup: Box<Backup>,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
+    let prompt = i18n::dialogues::RESTORE_CONFIRM.translate_with_args(
+        ctx.clone(),
+        &hashmap! {
+            i18n::args::DATETIME.into() => Datetime::from(backup.created_at).to_fluent_datetime(&tz).unwrap().into(),
+            i18n::args::TRAVELERS_COUNT.into() => backup.travelers.len().into(),
+            i18n::args::EXPENSES_COUNT.into() => backup.expenses.len().into(),
+            i18n::args::TRANSFERS_COUNT.into() => backup.transfers.len().into(),
+        },
+    );
+    let kb = confirmation_keyboard(ConfirmConfig {
+        confirm_callback: CONFIRM_CALLBACK,
+        deny_callback: DENY_CALLBACK,
+        ctx,
+    });
+    bot.send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id())
+        .reply_markup(kb)
+        .await?;
+    dialogue
+        .update(PendingCommandState::Restore(RestoreState::Confirm(backup)))
+        .await?;
+    Ok(())
+}
+
+async fn do_restore(
+    db: Arc<Surreal<Any>>,
+    bot: &Bot,
+    msg: &Message,
+    backup: &Backup,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    let outcome = restore(db, msg, backup, ctx.clone())
+        .await
+        .unwrap_or_else(|err| CommandOutcome::Failure(err.translate(ctx)));
+    send_outcome(bot, msg, &outcome, None).await?;
+    Ok(())
+}
+
+async fn send_cancel(bot: &Bot, msg: &Message, ctx: Arc<Mutex<Context>>) -> HandlerResult {
+    let process_name = i18n::commands::RUNNING_PROCESS_RESTORE.translate(Arc::clone(&ctx));
+    let cancel_msg = i18n::commands::CANCEL_OK.translate_with_args(
+        ctx,
+        &hashmap! { i18n::args::PROCESS.into() => process_name.into() },
+    );
+    send_text(bot, msg, cancel_msg).await
+}
+
+// ─── Start ───────────────────────────────────────────────────────────────────
+
+#[apply(trace_state)]
+pub async fn start(
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    msg: Message,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let Some(content) = attachment::read(
+        &bot,
+        &msg,
+        i18n::commands::RESTORE_USAGE,
+        i18n::errors::COMMAND_ERROR_RESTORE,
+        ctx.clone(),
+    )
+    .await?
+    else {
+        dialogue.exit().await?;
+        return Ok(());
+    };
+
+    match Backup::from_json(&content) {
+        Ok(backup) => ask_confirmation(&bot, &dialogue, &msg, Box::new(backup), ctx).await?,
+        Err(err) => {
+            tracing::warn!("{err}");
+            dialogue.exit().await?;
+            send_text(&bot, &msg, err.translate(ctx)).await?;
+        }
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Dialogue started: /restore");
+    Ok(())
+}
+
+// ─── Confirm text handler ────────────────────────────────────────────────────
+
+#[apply(trace_skip_all)]
+pub async fn receive_confirm_text(
+    db: Arc<Surreal<Any>>,
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    backup: Box<Backup>,
+    msg: Message,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let text = msg.text().map(str::trim).unwrap_or("");
+    match parse_confirm_answer(text) {
+        ConfirmAnswer::Yes => {
+            do_restore(db, &bot, &msg, &backup, ctx).await?;
+            dialogue.exit().await?;
+        }
+        ConfirmAnswer::No => {
+            dialogue.exit().await?;
+            send_cancel(&bot, &msg, ctx).await?;
+        }
+        ConfirmAnswer::Unknown => {
+            ask_confirmation(&bot, &dialogue, &msg, backup, ctx).await?;
+        }
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    Ok(())
+}
+
+// ─── Confirm callback handler ────────────────────────────────────────────────
+
+#[apply(trace_callback)]
+pub async fn receive_confirm_callback(
+    db: Arc<Surreal<Any>>,
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    backup: Box<Backup>,
+    q: CallbackQuery,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let _ = bot.answer_callback_query(q.id.clone()).await;
+
+    let Some(msg) = q.regular_message().cloned() else {
+        tracing::warn!("Callback query without an accessible message; ignoring");
+        return Ok(());
+    };
+
+    let data = q.data.as_deref().unwrap_or("");
+
+    let label = if data == CONFIRM_CALLBACK {
+        i18n::labels::CONFIRM_YES_BUTTON.translate(ctx.clone())
+    } else {
+        i18n::labels::CONFIRM_NO_BUTTON.translate(ctx.clone())
+    };
+    keyboard::echo_callback_selection(&bot, &msg, &label).await;
+
+    if data == CONFIRM_CALLBACK {
+        do_restore(db, &bot, &msg, &backup, ctx).await?;
+        dialogue.exit().await?;
+    } else {
+        dialogue.exit().await?;
+        send_cancel(&bot, &msg, ctx).await?;
+    }
+
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    Ok(())
+}
@@ -3,13 +3,14 @@
 
 use crate::{
     Context, HandlerResult,
-    commands::{Command, CommandArg, command_reply},
+    commands::{Command, CommandArg, command_reply, send_outcome},
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     dialogues::pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
     i18n::{self, Translate},
     keyboard::{self, DEFAULT_ROWS_PER_PAGE, PaginatedKeyboardConfig, PickerItem},
     money_wrapper::currency_label,
     settings::SETTINGS,
+    topics::{InTopic, MessageExt},
 };
 use macro_rules_attribute::apply;
 use std::sync::{Arc, Mutex};
@@ -74,6 +75,7 @@ pub async fn start(
         msg.chat.id,
         i18n::dialogues::SET_CURRENCY_ASK_CURRENCY.translate(ctx.clone()),
     )
+    .in_topic(msg.topic_id())
     .reply_markup(popular_currencies_keyboard(ctx))
     .await?;
     dialogue
@@ -103,6 +105,7 @@ pub async fn receive_currency(
             msg.chat.id,
             i18n::dialogues::SET_CURRENCY_INVALID_CURRENCY.translate(ctx),
         )
+        .in_topic(msg.topic_id())
         .await?;
         return Ok(());
     }
@@ -111,7 +114,7 @@ pub async fn receive_currency(
         currency: CommandArg::Provided(text.to_owned()),
     };
     let outcome = command_reply(db, &msg, &cmd, ctx.clone()).await;
-    bot.send_message(msg.chat.id, outcome.message()).await?;
+    send_outcome(&bot, &msg, &outcome, None).await?;
     if outcome.is_success() {
         dialogue.exit().await?;
     } else {
@@ -119,6 +122,7 @@ pub async fn receive_currency(
             msg.chat.id,
             i18n::dialogues::SET_CURRENCY_ASK_CURRENCY.translate(ctx.clone()),
         )
+        .in_topic(msg.topic_id())
         .reply_markup(popular_currencies_keyboard(ctx))
         .await?;
     }
@@ -166,7 +170,7 @@ pub async fn receive_callback(
     };
     let outcome = command_reply(db, &msg, &cmd, ctx.clone()).await;
 
-    bot.send_message(msg.chat.id, outcome.message()).await?;
+    send_outcome(&bot, &msg, &outcome, None).await?;
     if outcome.is_success() {
         dialogue.exit().await?;
     } else {
@@ -176,6 +180,7 @@ pub async fn receive_callback(
             msg.chat.id,
             i18n::dialogues::SET_CURRENCY_ASK_CURRENCY.translate(ctx.clone()),
         )
+        .in_topic(msg.topic_id())
         .reply_markup(popular_currencies_keyboard(ctx))
         .await?;
     }
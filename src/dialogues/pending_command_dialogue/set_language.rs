@@ -3,11 +3,12 @@
 
 use crate::{
     Context, HandlerResult,
-    commands::{Command, CommandArg, command_reply},
+    commands::{Command, CommandArg, command_reply, send_outcome},
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     dialogues::pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
     i18n::{self, Translate, TryTranslate},
     keyboard::{self, DEFAULT_ROWS_PER_PAGE, PaginatedKeyboardConfig, PickerItem},
+    topics::{InTopic, MessageExt},
 };
 use macro_rules_attribute::apply;
 use std::{
@@ -80,6 +81,7 @@ pub async fn start(
         msg.chat.id,
         i18n::dialogues::SET_LANGUAGE_ASK_LANGID.translate(ctx.clone()),
     )
+    .in_topic(msg.topic_id())
     .reply_markup(available_langs_keyboard(ctx))
     .await?;
     dialogue
@@ -111,6 +113,7 @@ pub async fn receive_langid(
                 msg.chat.id,
                 i18n::dialogues::SET_LANGUAGE_INVALID_LANGID.translate(ctx),
             )
+            .in_topic(msg.topic_id())
             .await?;
             return Ok(());
         }
@@ -120,7 +123,7 @@ pub async fn receive_langid(
         langid: CommandArg::Provided(langid),
     };
     let outcome = command_reply(db, &msg, &cmd, ctx.clone()).await;
-    bot.send_message(msg.chat.id, outcome.message()).await?;
+    send_outcome(&bot, &msg, &outcome, None).await?;
     if outcome.is_success() {
         dialogue.exit().await?;
     } else {
@@ -128,6 +131,7 @@ pub async fn receive_langid(
             msg.chat.id,
             i18n::dialogues::SET_LANGUAGE_ASK_LANGID.translate(ctx.clone()),
         )
+        .in_topic(msg.topic_id())
         .reply_markup(available_langs_keyboard(ctx))
         .await?;
     }
@@ -176,7 +180,7 @@ pub async fn receive_callback(
     };
     let outcome = command_reply(db, &msg, &cmd, ctx.clone()).await;
 
-    bot.send_message(msg.chat.id, outcome.message()).await?;
+    send_outcome(&bot, &msg, &outcome, None).await?;
     if outcome.is_success() {
         dialogue.exit().await?;
     } else {
@@ -186,6 +190,7 @@ pub async fn receive_callback(
             msg.chat.id,
             i18n::dialogues::SET_LANGUAGE_ASK_LANGID.translate(ctx.clone()),
         )
+        .in_topic(msg.topic_id())
         .reply_markup(available_langs_keyboard(ctx))
         .await?;
     }
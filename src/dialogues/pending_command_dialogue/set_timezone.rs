@@ -0,0 +1,241 @@
+//! `/settimezone` dialogue: asks the user for the time zone when the
+//! command is invoked without an inline argument.
+
+use crate::{
+    Context, HandlerResult,
+    commands::{Command, CommandArg, command_reply, send_outcome},
+    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    dialogues::pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
+    i18n::{self, Translate},
+    keyboard::{self, DEFAULT_ROWS_PER_PAGE, PaginatedKeyboardConfig, PickerItem},
+    settings::SETTINGS,
+    topics::{InTopic, MessageExt},
+};
+use macro_rules_attribute::apply;
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::{
+    Bot,
+    payloads::SendMessageSetters,
+    requests::Requester,
+    types::{CallbackQuery, InlineKeyboardMarkup, Message},
+};
+use tracing::Level;
+
+// Prefix used to identify callback queries originating from the
+// `/settimezone` inline keyboard.
+callback_consts!("settz" => cancel, noop);
+
+/// Number of time zone buttons per row in the inline keyboard.
+const TIMEZONES_PER_ROW: usize = 2;
+
+#[derive(Debug, Clone)]
+pub enum SetTimezoneState {
+    AskTimezone,
+}
+
+/// Builds an inline keyboard with the popular time zones in a uniform grid.
+fn popular_timezones_keyboard(ctx: Arc<Mutex<Context>>) -> InlineKeyboardMarkup {
+    let items: Vec<PickerItem> = SETTINGS
+        .i18n
+        .popular_timezones
+        .iter()
+        .map(|name| PickerItem {
+            label: name.to_string(),
+            value: name.to_string(),
+        })
+        .collect();
+
+    // Popular-timezones list is small — always fits on one page.
+    keyboard::paginated_keyboard(PaginatedKeyboardConfig {
+        items: &items,
+        page: 0,
+        columns: TIMEZONES_PER_ROW,
+        rows_per_page: DEFAULT_ROWS_PER_PAGE,
+        prefix: CALLBACK_PREFIX,
+        cancel_callback: CANCEL_CALLBACK,
+        noop_callback: NOOP_CALLBACK,
+        action_buttons: &[],
+        show_cancel: true,
+        ctx,
+    })
+    .expect("at least one popular time zone must be configured")
+}
+
+#[apply(trace_state)]
+pub async fn start(
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    msg: Message,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+    bot.send_message(
+        msg.chat.id,
+        i18n::dialogues::SET_TIMEZONE_ASK_TIMEZONE.translate(ctx.clone()),
+    )
+    .in_topic(msg.topic_id())
+    .reply_markup(popular_timezones_keyboard(ctx))
+    .await?;
+    dialogue
+        .update(PendingCommandState::SetTimezone(
+            SetTimezoneState::AskTimezone,
+        ))
+        .await?;
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    tracing::info!("Dialogue started: /settimezone");
+    Ok(())
+}
+
+#[apply(trace_state_db)]
+pub async fn receive_timezone(
+    db: Arc<Surreal<Any>>,
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    msg: Message,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let text = msg.text().map(str::trim).unwrap_or("");
+    if text.is_empty() {
+        tracing::warn!("Empty time zone input.");
+        bot.send_message(
+            msg.chat.id,
+            i18n::dialogues::SET_TIMEZONE_INVALID_TIMEZONE.translate(ctx),
+        )
+        .in_topic(msg.topic_id())
+        .await?;
+        return Ok(());
+    }
+
+    let cmd = Command::SetTimezone {
+        timezone: CommandArg::Provided(text.to_owned()),
+    };
+    let outcome = command_reply(db, &msg, &cmd, ctx.clone()).await;
+    send_outcome(&bot, &msg, &outcome, None).await?;
+    if outcome.is_success() {
+        dialogue.exit().await?;
+    } else {
+        bot.send_message(
+            msg.chat.id,
+            i18n::dialogues::SET_TIMEZONE_ASK_TIMEZONE.translate(ctx.clone()),
+        )
+        .in_topic(msg.topic_id())
+        .reply_markup(popular_timezones_keyboard(ctx))
+        .await?;
+    }
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    Ok(())
+}
+
+#[apply(trace_callback)]
+pub async fn receive_callback(
+    db: Arc<Surreal<Any>>,
+    bot: Bot,
+    dialogue: PendingCommandDialogue,
+    q: CallbackQuery,
+    ctx: Arc<Mutex<Context>>,
+) -> HandlerResult {
+    tracing::debug!("{LOG_DEBUG_START}");
+
+    let action = keyboard::handle_callback_prelude(
+        &bot,
+        &dialogue,
+        &q,
+        &ctx,
+        &keyboard::CallbackConfig {
+            cancel_callback: CANCEL_CALLBACK,
+            noop_callback: NOOP_CALLBACK,
+            prefix: CALLBACK_PREFIX,
+            running_process_key: i18n::commands::RUNNING_PROCESS_SET_TIMEZONE,
+        },
+    )
+    .await?;
+
+    let keyboard::CallbackAction::Selection {
+        value: timezone,
+        msg,
+    } = action
+    else {
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(());
+    };
+
+    keyboard::echo_callback_selection(&bot, &msg, &timezone).await;
+
+    let cmd = Command::SetTimezone {
+        timezone: CommandArg::Provided(timezone),
+    };
+    let outcome = command_reply(db, &msg, &cmd, ctx.clone()).await;
+
+    send_outcome(&bot, &msg, &outcome, None).await?;
+    if outcome.is_success() {
+        dialogue.exit().await?;
+    } else {
+        // Dialogue stays alive: re-send the prompt with a fresh keyboard
+        // so the user knows they can retry.
+        bot.send_message(
+            msg.chat.id,
+            i18n::dialogues::SET_TIMEZONE_ASK_TIMEZONE.translate(ctx.clone()),
+        )
+        .in_topic(msg.topic_id())
+        .reply_markup(popular_timezones_keyboard(ctx))
+        .await?;
+    }
+    tracing::debug!("{LOG_DEBUG_SUCCESS}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db::db,
+        i18n::{self, Translate, TranslateWithArgs},
+        tests::{TestBot, helpers::cancel_ok_for},
+    };
+    use maplit::hashmap;
+
+    test! { ask_timezone_on_empty_invocation,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/settimezone");
+        let response = i18n::dialogues::SET_TIMEZONE_ASK_TIMEZONE.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { receive_timezone_empty_reprompts,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/settimezone");
+        bot.dispatch().await;
+
+        bot.update("   ");
+        let response = i18n::dialogues::SET_TIMEZONE_INVALID_TIMEZONE.translate_default();
+        bot.test_last_message(&response).await;
+    }
+
+    test! { receive_timezone_ok,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/settimezone");
+        bot.dispatch().await;
+
+        bot.update("Europe/Paris");
+        let response = i18n::commands::SET_TIMEZONE_OK.translate_with_args_default(
+            &hashmap! {i18n::args::TIMEZONE.into() => "Europe/Paris".into()},
+        );
+        bot.test_last_message(&response).await;
+    }
+
+    test! { cancel_during_dialogue,
+        let db = db().await;
+
+        let mut bot = TestBot::new(db, "/settimezone");
+        bot.dispatch().await;
+
+        bot.update("/cancel");
+        let response = cancel_ok_for(i18n::commands::RUNNING_PROCESS_SET_TIMEZONE);
+        bot.test_last_message(&response).await;
+    }
+}
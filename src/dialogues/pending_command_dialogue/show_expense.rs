@@ -5,7 +5,7 @@
 
 use crate::{
     Context, HandlerResult,
-    commands::{Command, CommandArg, command_reply},
+    commands::{Command, CommandArg, command_reply, send_outcome},
     consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     dialogues::pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
     expense::Expense,
@@ -15,6 +15,7 @@ use crate::{
         PaginatedKeyboardConfig, PickerItem,
     },
     money_wrapper::MoneyWrapper,
+    topics::{InTopic, MessageExt},
 };
 use macro_rules_attribute::apply;
 use std::sync::{Arc, Mutex};
@@ -59,13 +60,15 @@ fn expense_picker_items(expenses: &[Expense], ctx: Arc<Mutex<Context>>) -> Vec<P
 async fn send_prompt_with_keyboard(
     db: Arc<Surreal<Any>>,
     bot: &Bot,
-    chat_id: teloxide::types::ChatId,
+    msg: &Message,
     prompt: String,
     page: usize,
     ctx: Arc<Mutex<Context>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let mut request = bot.send_message(chat_id, prompt);
-    if let Ok(expenses) = Expense::db_select(db, chat_id).await {
+    let mut request = bot
+        .send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id());
+    if let Ok(expenses) = Expense::db_select(db, msg.ledger_id()).await {
         let items = expense_picker_items(&expenses, ctx.clone());
         if let Some(kb) = keyboard::paginated_keyboard(PaginatedKeyboardConfig {
             items: &items,
@@ -98,7 +101,7 @@ pub async fn start(
 ) -> HandlerResult {
     tracing::debug!("{LOG_DEBUG_START}");
     let prompt = i18n::dialogues::SHOW_EXPENSE_ASK_NUMBER.translate(ctx.clone());
-    send_prompt_with_keyboard(db, &bot, msg.chat.id, prompt, 0, ctx).await?;
+    send_prompt_with_keyboard(db, &bot, &msg, prompt, 0, ctx).await?;
     dialogue
         .update(PendingCommandState::ShowExpense(
             ShowExpenseState::AskNumber,
@@ -130,6 +133,7 @@ pub async fn receive_number(
                 msg.chat.id,
                 i18n::dialogues::SHOW_EXPENSE_INVALID_NUMBER.translate(ctx),
             )
+            .in_topic(msg.topic_id())
             .await?;
             return Ok(());
         }
@@ -139,12 +143,12 @@ pub async fn receive_number(
         number: CommandArg::Provided(number),
     };
     let outcome = command_reply(db.clone(), &msg, &cmd, ctx.clone()).await;
-    bot.send_message(msg.chat.id, outcome.message()).await?;
+    send_outcome(&bot, &msg, &outcome, None).await?;
     if outcome.is_success() {
         dialogue.exit().await?;
     } else {
         let prompt = i18n::dialogues::SHOW_EXPENSE_ASK_NUMBER.translate(ctx.clone());
-        send_prompt_with_keyboard(db, &bot, msg.chat.id, prompt, 0, ctx).await?;
+        send_prompt_with_keyboard(db, &bot, &msg, prompt, 0, ctx).await?;
     }
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     Ok(())
@@ -188,16 +192,16 @@ pub async fn receive_callback(
             };
             let fake_msg = msg.as_ref();
             let outcome = command_reply(db.clone(), fake_msg, &cmd, ctx.clone()).await;
-            bot.send_message(msg.chat.id, outcome.message()).await?;
+            send_outcome(&bot, &msg, &outcome, None).await?;
             if outcome.is_success() {
                 dialogue.exit().await?;
             } else {
                 let prompt = i18n::dialogues::SHOW_EXPENSE_ASK_NUMBER.translate(ctx.clone());
-                send_prompt_with_keyboard(db, &bot, msg.chat.id, prompt, 0, ctx).await?;
+                send_prompt_with_keyboard(db, &bot, &msg, prompt, 0, ctx).await?;
             }
         }
         PaginatedCallbackAction::PageChange { page, msg } => {
-            if let Ok(expenses) = Expense::db_select(db, msg.chat.id).await {
+            if let Ok(expenses) = Expense::db_select(db, msg.ledger_id()).await {
                 let items = expense_picker_items(&expenses, ctx.clone());
                 if let Some(kb) = keyboard::paginated_keyboard(PaginatedKeyboardConfig {
                     items: &items,
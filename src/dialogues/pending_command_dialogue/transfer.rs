@@ -5,11 +5,12 @@
 
 use crate::{
     Context, HandlerResult,
-    commands::transfer as cmd_transfer,
-    consts::{LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
+    commands::{closed_trip_refusal, transfer as cmd_transfer, transfer_all as cmd_transfer_all},
+    consts::{ALL_KWORD, LOG_DEBUG_START, LOG_DEBUG_SUCCESS},
     dialogues::pending_command_dialogue::{PendingCommandDialogue, PendingCommandState},
     i18n::{self, Translate, TranslateWithArgs},
     keyboard::{self, DEFAULT_ROWS_PER_PAGE, PaginatedKeyboardConfig, PickerItem},
+    topics::{InTopic, MessageExt},
     traveler::{Name, Traveler},
 };
 use macro_rules_attribute::apply;
@@ -75,10 +76,12 @@ pub async fn start(
 ) -> HandlerResult {
     tracing::debug!("{LOG_DEBUG_START}");
     let prompt = i18n::dialogues::TRANSFER_ASK_FROM.translate(ctx.clone());
-    let mut request = bot.send_message(msg.chat.id, prompt);
+    let mut request = bot
+        .send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id());
     if let Some(kb) = keyboard::travelers_keyboard(keyboard::TravelersKeyboardConfig {
         db,
-        chat_id: msg.chat.id,
+        chat_id: msg.ledger_id(),
         prefix: CALLBACK_PREFIX_FROM,
         cancel_callback: CANCEL_CALLBACK_FROM,
         noop_callback: NOOP_CALLBACK_FROM,
@@ -115,10 +118,12 @@ pub async fn start_with_from(
         Err(_) => {
             // Invalid from name — fall back to full dialogue starting from AskFrom.
             let prompt = i18n::dialogues::TRANSFER_ASK_FROM.translate(ctx.clone());
-            let mut request = bot.send_message(msg.chat.id, prompt);
+            let mut request = bot
+                .send_message(msg.chat.id, prompt)
+                .in_topic(msg.topic_id());
             if let Some(kb) = keyboard::travelers_keyboard(keyboard::TravelersKeyboardConfig {
                 db,
-                chat_id: msg.chat.id,
+                chat_id: msg.ledger_id(),
                 prefix: CALLBACK_PREFIX_FROM,
                 cancel_callback: CANCEL_CALLBACK_FROM,
                 noop_callback: NOOP_CALLBACK_FROM,
@@ -137,17 +142,17 @@ pub async fn start_with_from(
         }
     };
     // Check that the traveler exists in the DB.
-    if Traveler::db_select_by_name(db.clone(), msg.chat.id, &from)
+    if Traveler::db_select_by_name(db.clone(), msg.ledger_id(), &from)
         .await?
         .is_none()
     {
-        reprompt_from_not_found(db, &bot, msg.chat.id, &from, ctx.clone()).await?;
+        reprompt_from_not_found(db, &bot, &msg, &from, ctx.clone()).await?;
         dialogue
             .update(PendingCommandState::Transfer(TransferState::AskFrom))
             .await?;
         return Ok(());
     }
-    transition_to_ask_to(db, &bot, &dialogue, msg.chat.id, from, ctx).await?;
+    transition_to_ask_to(db, &bot, &dialogue, &msg, from, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     Ok(())
 }
@@ -172,10 +177,12 @@ pub async fn start_with_from_to(
         Err(_) => {
             // Invalid from — start full dialogue.
             let prompt = i18n::dialogues::TRANSFER_ASK_FROM.translate(ctx.clone());
-            let mut request = bot.send_message(msg.chat.id, prompt);
+            let mut request = bot
+                .send_message(msg.chat.id, prompt)
+                .in_topic(msg.topic_id());
             if let Some(kb) = keyboard::travelers_keyboard(keyboard::TravelersKeyboardConfig {
                 db,
-                chat_id: msg.chat.id,
+                chat_id: msg.ledger_id(),
                 prefix: CALLBACK_PREFIX_FROM,
                 cancel_callback: CANCEL_CALLBACK_FROM,
                 noop_callback: NOOP_CALLBACK_FROM,
@@ -194,11 +201,11 @@ pub async fn start_with_from_to(
         }
     };
     // Check that the "from" traveler exists in the DB.
-    if Traveler::db_select_by_name(db.clone(), msg.chat.id, &from)
+    if Traveler::db_select_by_name(db.clone(), msg.ledger_id(), &from)
         .await?
         .is_none()
     {
-        reprompt_from_not_found(db, &bot, msg.chat.id, &from, ctx.clone()).await?;
+        reprompt_from_not_found(db, &bot, &msg, &from, ctx.clone()).await?;
         dialogue
             .update(PendingCommandState::Transfer(TransferState::AskFrom))
             .await?;
@@ -208,16 +215,16 @@ pub async fn start_with_from_to(
         Ok(n) => n,
         Err(_) => {
             // Invalid to — start from AskTo with the valid from.
-            transition_to_ask_to(db, &bot, &dialogue, msg.chat.id, from, ctx).await?;
+            transition_to_ask_to(db, &bot, &dialogue, &msg, from, ctx).await?;
             return Ok(());
         }
     };
     // Check that the "to" traveler exists in the DB.
-    if Traveler::db_select_by_name(db.clone(), msg.chat.id, &to)
+    if Traveler::db_select_by_name(db.clone(), msg.ledger_id(), &to)
         .await?
         .is_none()
     {
-        reprompt_to_not_found(db, &bot, msg.chat.id, &from, &to, ctx.clone()).await?;
+        reprompt_to_not_found(db, &bot, &msg, &from, &to, ctx.clone()).await?;
         dialogue
             .update(PendingCommandState::Transfer(TransferState::AskTo(
                 TransferFrom(from),
@@ -226,7 +233,7 @@ pub async fn start_with_from_to(
         return Ok(());
     }
     let from_wrapper = TransferFrom(from);
-    transition_to_ask_amount(&bot, &dialogue, msg.chat.id, from_wrapper, to, ctx).await?;
+    transition_to_ask_amount(&bot, &dialogue, &msg, from_wrapper, to, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     Ok(())
 }
@@ -247,21 +254,21 @@ pub async fn receive_from_text(
     let name = match parse_name(text) {
         Ok(n) => n,
         Err(_) => {
-            reprompt_ask_from(db, &bot, msg.chat.id, ctx).await?;
+            reprompt_ask_from(db, &bot, &msg, ctx).await?;
             return Ok(());
         }
     };
 
     // Check that the traveler exists in the DB.
-    if Traveler::db_select_by_name(db.clone(), msg.chat.id, &name)
+    if Traveler::db_select_by_name(db.clone(), msg.ledger_id(), &name)
         .await?
         .is_none()
     {
-        reprompt_from_not_found(db, &bot, msg.chat.id, &name, ctx).await?;
+        reprompt_from_not_found(db, &bot, &msg, &name, ctx).await?;
         return Ok(());
     }
 
-    transition_to_ask_to(db, &bot, &dialogue, msg.chat.id, name, ctx).await?;
+    transition_to_ask_to(db, &bot, &dialogue, &msg, name, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     Ok(())
 }
@@ -297,8 +304,7 @@ pub async fn receive_from_callback(
         return Ok(());
     };
 
-    let Some(traveler) =
-        Traveler::db_resolve_by_number(db.clone(), msg.chat.id, &raw).await
+    let Some(traveler) = Traveler::db_resolve_by_number(db.clone(), msg.ledger_id(), &raw).await
     else {
         tracing::warn!("Could not resolve traveler number from callback data: {raw:?}");
         return Ok(());
@@ -307,7 +313,7 @@ pub async fn receive_from_callback(
 
     keyboard::echo_callback_selection(&bot, &msg, &name).await;
 
-    transition_to_ask_to(db, &bot, &dialogue, msg.chat.id, name, ctx).await?;
+    transition_to_ask_to(db, &bot, &dialogue, &msg, name, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     Ok(())
 }
@@ -330,8 +336,11 @@ pub async fn receive_to_text(
         Ok(n) => n,
         Err(_) => {
             let prompt = i18n::dialogues::TRANSFER_ASK_TO_REPROMPT.translate(ctx.clone());
-            let mut request = bot.send_message(msg.chat.id, prompt);
-            if let Some(kb) = travelers_keyboard_excluding(db, msg.chat.id, &from.0, ctx).await {
+            let mut request = bot
+                .send_message(msg.chat.id, prompt)
+                .in_topic(msg.topic_id());
+            if let Some(kb) = travelers_keyboard_excluding(db, msg.ledger_id(), &from.0, ctx).await
+            {
                 request = request.reply_markup(kb);
             }
             request.await?;
@@ -340,15 +349,15 @@ pub async fn receive_to_text(
     };
 
     // Check that the traveler exists in the DB.
-    if Traveler::db_select_by_name(db.clone(), msg.chat.id, &name)
+    if Traveler::db_select_by_name(db.clone(), msg.ledger_id(), &name)
         .await?
         .is_none()
     {
-        reprompt_to_not_found(db, &bot, msg.chat.id, &from.0, &name, ctx).await?;
+        reprompt_to_not_found(db, &bot, &msg, &from.0, &name, ctx).await?;
         return Ok(());
     }
 
-    transition_to_ask_amount(&bot, &dialogue, msg.chat.id, from, name, ctx).await?;
+    transition_to_ask_amount(&bot, &dialogue, &msg, from, name, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     Ok(())
 }
@@ -385,9 +394,7 @@ pub async fn receive_to_callback(
         return Ok(());
     };
 
-    let Some(traveler) =
-        Traveler::db_resolve_by_number(db, msg.chat.id, &raw).await
-    else {
+    let Some(traveler) = Traveler::db_resolve_by_number(db, msg.ledger_id(), &raw).await else {
         tracing::warn!("Could not resolve traveler number from callback data: {raw:?}");
         return Ok(());
     };
@@ -395,7 +402,7 @@ pub async fn receive_to_callback(
 
     keyboard::echo_callback_selection(&bot, &msg, &name).await;
 
-    transition_to_ask_amount(&bot, &dialogue, msg.chat.id, from, name, ctx).await?;
+    transition_to_ask_amount(&bot, &dialogue, &msg, from, name, ctx).await?;
     tracing::debug!("{LOG_DEBUG_SUCCESS}");
     Ok(())
 }
@@ -413,29 +420,47 @@ pub async fn receive_amount(
 ) -> HandlerResult {
     tracing::debug!("{LOG_DEBUG_START}");
 
-    let text = msg.text().map(str::trim).unwrap_or("");
-    let amount = match Decimal::from_str(text) {
-        Ok(d) => d,
-        Err(_) => {
-            bot.send_message(
-                msg.chat.id,
-                i18n::dialogues::TRANSFER_INVALID_AMOUNT.translate(ctx),
-            )
+    // The trip may have been closed from another topic sharing the ledger
+    if let Some(refusal) = closed_trip_refusal(db.clone(), &msg, ctx.clone()).await {
+        bot.send_message(msg.chat.id, refusal)
+            .in_topic(msg.topic_id())
             .await?;
-            return Ok(());
-        }
-    };
+        dialogue.exit().await?;
+        tracing::debug!("{LOG_DEBUG_SUCCESS}");
+        return Ok(());
+    }
 
-    let result = cmd_transfer(db.clone(), &msg, from.0, to.0, amount, ctx.clone()).await;
+    let text = msg.text().map(str::trim).unwrap_or("");
+    let result = if text.eq_ignore_ascii_case(ALL_KWORD) {
+        cmd_transfer_all(db.clone(), &msg, from.0, to.0, ctx.clone()).await
+    } else {
+        let amount = match Decimal::from_str(text) {
+            Ok(d) => d,
+            Err(_) => {
+                bot.send_message(
+                    msg.chat.id,
+                    i18n::dialogues::TRANSFER_INVALID_AMOUNT.translate(ctx),
+                )
+                .in_topic(msg.topic_id())
+                .await?;
+                return Ok(());
+            }
+        };
+        cmd_transfer(db.clone(), &msg, from.0, to.0, amount, ctx.clone()).await
+    };
     match result {
         Ok(reply) => {
-            bot.send_message(msg.chat.id, reply).await?;
+            bot.send_message(msg.chat.id, reply)
+                .in_topic(msg.topic_id())
+                .await?;
             dialogue.exit().await?;
         }
         Err(err) => {
             use crate::i18n::Translate as _;
             let reply = err.translate(ctx.clone());
-            bot.send_message(msg.chat.id, reply).await?;
+            bot.send_message(msg.chat.id, reply)
+                .in_topic(msg.topic_id())
+                .await?;
             // Stay in AskAmount — user can retry with a different amount.
         }
     }
@@ -460,14 +485,16 @@ fn parse_name(text: &str) -> Result<Name, ()> {
 async fn reprompt_ask_from(
     db: Arc<Surreal<Any>>,
     bot: &Bot,
-    chat_id: teloxide::types::ChatId,
+    msg: &Message,
     ctx: Arc<Mutex<Context>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let prompt = i18n::dialogues::TRANSFER_ASK_FROM_REPROMPT.translate(ctx.clone());
-    let mut request = bot.send_message(chat_id, prompt);
+    let mut request = bot
+        .send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id());
     if let Some(kb) = keyboard::travelers_keyboard(keyboard::TravelersKeyboardConfig {
         db,
-        chat_id,
+        chat_id: msg.ledger_id(),
         prefix: CALLBACK_PREFIX_FROM,
         cancel_callback: CANCEL_CALLBACK_FROM,
         noop_callback: NOOP_CALLBACK_FROM,
@@ -486,7 +513,7 @@ async fn reprompt_ask_from(
 async fn reprompt_from_not_found(
     db: Arc<Surreal<Any>>,
     bot: &Bot,
-    chat_id: teloxide::types::ChatId,
+    msg: &Message,
     name: &Name,
     ctx: Arc<Mutex<Context>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -494,10 +521,12 @@ async fn reprompt_from_not_found(
         ctx.clone(),
         &maplit::hashmap! { i18n::args::NAME.into() => name.to_string().into() },
     );
-    let mut request = bot.send_message(chat_id, prompt);
+    let mut request = bot
+        .send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id());
     if let Some(kb) = keyboard::travelers_keyboard(keyboard::TravelersKeyboardConfig {
         db,
-        chat_id,
+        chat_id: msg.ledger_id(),
         prefix: CALLBACK_PREFIX_FROM,
         cancel_callback: CANCEL_CALLBACK_FROM,
         noop_callback: NOOP_CALLBACK_FROM,
@@ -516,7 +545,7 @@ async fn reprompt_from_not_found(
 async fn reprompt_to_not_found(
     db: Arc<Surreal<Any>>,
     bot: &Bot,
-    chat_id: teloxide::types::ChatId,
+    msg: &Message,
     exclude: &Name,
     name: &Name,
     ctx: Arc<Mutex<Context>>,
@@ -525,8 +554,10 @@ async fn reprompt_to_not_found(
         ctx.clone(),
         &maplit::hashmap! { i18n::args::NAME.into() => name.to_string().into() },
     );
-    let mut request = bot.send_message(chat_id, prompt);
-    if let Some(kb) = travelers_keyboard_excluding(db, chat_id, exclude, ctx).await {
+    let mut request = bot
+        .send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id());
+    if let Some(kb) = travelers_keyboard_excluding(db, msg.ledger_id(), exclude, ctx).await {
         request = request.reply_markup(kb);
     }
     request.await?;
@@ -539,7 +570,7 @@ async fn transition_to_ask_to(
     db: Arc<Surreal<Any>>,
     bot: &Bot,
     dialogue: &PendingCommandDialogue,
-    chat_id: teloxide::types::ChatId,
+    msg: &Message,
     from: Name,
     ctx: Arc<Mutex<Context>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -547,10 +578,12 @@ async fn transition_to_ask_to(
         ctx.clone(),
         &maplit::hashmap! { i18n::args::NAME.into() => from.to_string().into() },
     );
-    let mut request = bot.send_message(chat_id, prompt);
+    let mut request = bot
+        .send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id());
 
     // Build keyboard excluding the "from" traveler.
-    if let Some(kb) = travelers_keyboard_excluding(db, chat_id, &from, ctx).await {
+    if let Some(kb) = travelers_keyboard_excluding(db, msg.ledger_id(), &from, ctx).await {
         request = request.reply_markup(kb);
     }
     request.await?;
@@ -567,7 +600,7 @@ async fn transition_to_ask_to(
 async fn transition_to_ask_amount(
     bot: &Bot,
     dialogue: &PendingCommandDialogue,
-    chat_id: teloxide::types::ChatId,
+    msg: &Message,
     from: TransferFrom,
     to: Name,
     ctx: Arc<Mutex<Context>>,
@@ -576,7 +609,9 @@ async fn transition_to_ask_amount(
         ctx,
         &maplit::hashmap! { i18n::args::NAME.into() => from.0.to_string().into() },
     );
-    bot.send_message(chat_id, prompt).await?;
+    bot.send_message(msg.chat.id, prompt)
+        .in_topic(msg.topic_id())
+        .await?;
     dialogue
         .update(PendingCommandState::Transfer(TransferState::AskAmount(
             from,
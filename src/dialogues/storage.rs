@@ -19,6 +19,7 @@ use crate::{
         pending_command_dialogue::{PendingCommandState, PendingCommandStorage},
     },
     i18n::{self, Translate, TranslateWithArgs, args},
+    topics::{InTopic, MessageExt},
 };
 use macro_rules_attribute::apply;
 use maplit::hashmap;
@@ -206,7 +207,7 @@ impl DialogueRegistry {
 /// `dptree`-friendly filter: `true` iff *any* known dialogue is currently
 /// active for the chat that sent `msg`.
 pub async fn any_running(registry: DialogueRegistry, msg: Message) -> bool {
-    registry.any_running(msg.chat.id).await
+    registry.any_running(msg.dialogue_key().0).await
 }
 
 /// `dptree`-friendly filter: `true` iff the dialogue using
@@ -216,7 +217,9 @@ pub async fn is_running<D>(storage: Arc<InMemStorage<D>>, msg: Message) -> bool
 where
     D: DialogueState,
 {
-    erase::<_, D>(storage).is_running(msg.chat.id).await
+    erase::<_, D>(storage)
+        .is_running(msg.dialogue_key().0)
+        .await
 }
 
 /// Endpoint used as a dispatcher leaf when a new dialogue would collide with
@@ -230,7 +233,7 @@ pub async fn process_already_running_endpoint(
     ctx: Arc<Mutex<Context>>,
     registry: DialogueRegistry,
 ) -> HandlerResult {
-    let process_name = match registry.running_label(msg.chat.id).await {
+    let process_name = match registry.running_label(msg.dialogue_key().0).await {
         Some(label) => label.translate(Arc::clone(&ctx)),
         // Race: the offending dialogue exited between the filter and here.
         // Fall back to a generic placeholder so the message still renders.
@@ -241,7 +244,9 @@ pub async fn process_already_running_endpoint(
         ctx,
         &hashmap! { args::PROCESS.into() => process_name.into() },
     );
-    bot.send_message(msg.chat.id, text).await?;
+    bot.send_message(msg.chat.id, text)
+        .in_topic(msg.topic_id())
+        .await?;
     Ok(())
 }
 
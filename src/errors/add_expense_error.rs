@@ -6,6 +6,7 @@ use crate::{
 use maplit::hashmap;
 use rust_decimal::Decimal;
 use std::fmt::Display;
+use travel_rs_core::ShareError;
 
 #[derive(Debug)]
 pub enum AddExpenseError {
@@ -89,11 +90,29 @@ impl Display for AddExpenseError {
 
 impl std::error::Error for AddExpenseError {}
 
+impl From<ShareError> for AddExpenseError {
+    fn from(err: ShareError) -> Self {
+        match err {
+            ShareError::ExpenseTooHigh { tot_amount } => Self::ExpenseTooHigh { tot_amount },
+            ShareError::ExpenseTooLow {
+                expense,
+                tot_amount,
+            } => Self::ExpenseTooLow {
+                expense,
+                tot_amount,
+            },
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum EndError {
     ClosingDialogue,
     NoExpenseCreated,
     AddExpense(AddExpenseError),
+    /// The trip was closed while the dialogue was running: holds the
+    /// localized refusal.
+    TripClosed(String),
     Generic(Box<dyn std::error::Error + Send + Sync>),
 }
 
@@ -112,6 +131,7 @@ impl Translate for EndError {
                 i18n::errors::END_ERROR_EXPENSE_CREATED.translate_with_indent(ctx, indent_lvl)
             }
             AddExpense(err) => err.translate_with_indent(ctx, indent_lvl),
+            TripClosed(refusal) => refusal.clone(),
             Generic(err) => err.to_string(),
         }
     }
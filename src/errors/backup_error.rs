@@ -0,0 +1,57 @@
+use std::{error::Error, fmt::Display};
+
+use maplit::hashmap;
+
+use crate::{
+    backup::BACKUP_VERSION,
+    i18n::{self, Translate, TranslateWithArgs},
+    traveler::Name,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackupError {
+    /// The file is not a backup made by `/backup`, or it is corrupted.
+    Invalid,
+    /// The backup was made with a version of the format not understood.
+    UnsupportedVersion(u32),
+    /// The backup refers to a traveler that is not part of it.
+    UnknownTraveler(Name),
+}
+
+impl Translate for BackupError {
+    fn translate_with_indent(
+        &self,
+        ctx: std::sync::Arc<std::sync::Mutex<crate::Context>>,
+        indent_lvl: usize,
+    ) -> String {
+        match self {
+            BackupError::Invalid => {
+                i18n::errors::BACKUP_ERROR_INVALID.translate_with_indent(ctx, indent_lvl)
+            }
+            BackupError::UnsupportedVersion(version) => {
+                i18n::errors::BACKUP_ERROR_UNSUPPORTED_VERSION.translate_with_args_indent(
+                    ctx,
+                    &hashmap! {
+                        i18n::args::VERSION.into() => (*version).into(),
+                        i18n::args::SUPPORTED_VERSION.into() => BACKUP_VERSION.into(),
+                    },
+                    indent_lvl,
+                )
+            }
+            BackupError::UnknownTraveler(name) => i18n::errors::BACKUP_ERROR_UNKNOWN_TRAVELER
+                .translate_with_args_indent(
+                    ctx,
+                    &hashmap! {i18n::args::NAME.into() => name.clone().into()},
+                    indent_lvl,
+                ),
+        }
+    }
+}
+
+impl Display for BackupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.translate_default())
+    }
+}
+
+impl Error for BackupError {}
@@ -23,6 +23,24 @@ pub enum CommandError {
     SetCurrency {
         currency: String,
     },
+    SetTimezone {
+        timezone: String,
+    },
+    SetSettlement,
+    SetSimplification,
+    NewTrip {
+        name: Name,
+    },
+    SwitchTrip {
+        name: Name,
+    },
+    ListTrips,
+    RenameTrip {
+        name: Name,
+    },
+    CloseTrip,
+    ReopenTrip,
+    TopicLedger,
     AddTraveler {
         name: Name,
     },
@@ -34,7 +52,7 @@ pub enum CommandError {
         number: i64,
     },
     ListExpenses {
-        description: String,
+        filter: String,
     },
     ShowExpense {
         number: i64,
@@ -44,16 +62,35 @@ pub enum CommandError {
         receiver: Name,
         amount: Decimal,
     },
+    TransferAll {
+        sender: Name,
+        receiver: Name,
+    },
     DeleteTransfer {
         number: i64,
     },
     ListTransfers {
-        name: Name,
+        filter: String,
     },
     ShowBalances {
         name: Name,
     },
+    Statement {
+        name: Name,
+    },
+    Summary,
+    Settle,
+    PayPrefs,
     ShowStats,
+    RebuildBalances,
+    Audit,
+    Export,
+    Report,
+    Chart,
+    DebtGraph,
+    Backup,
+    Restore,
+    Import,
     ClearTravelers,
     ClearExpenses,
     ClearTransfers,
@@ -105,6 +142,46 @@ impl Translate for CommandError {
                     &hashmap! {i18n::args::CURRENCY.into() => currency.into()},
                     indent_lvl,
                 ),
+            SetTimezone { timezone } => i18n::errors::COMMAND_ERROR_SET_TIMEZONE
+                .translate_with_args_indent(
+                    ctx,
+                    &hashmap! {i18n::args::TIMEZONE.into() => timezone.into()},
+                    indent_lvl,
+                ),
+            SetSettlement => {
+                i18n::errors::COMMAND_ERROR_SET_SETTLEMENT.translate_with_indent(ctx, indent_lvl)
+            }
+            SetSimplification => i18n::errors::COMMAND_ERROR_SET_SIMPLIFICATION
+                .translate_with_indent(ctx, indent_lvl),
+            NewTrip { name } => i18n::errors::COMMAND_ERROR_NEW_TRIP.translate_with_args_indent(
+                ctx,
+                &hashmap! {i18n::args::NAME.into() => name.clone().into()},
+                indent_lvl,
+            ),
+            SwitchTrip { name } => i18n::errors::COMMAND_ERROR_SWITCH_TRIP
+                .translate_with_args_indent(
+                    ctx,
+                    &hashmap! {i18n::args::NAME.into() => name.clone().into()},
+                    indent_lvl,
+                ),
+            ListTrips => {
+                i18n::errors::COMMAND_ERROR_LIST_TRIPS.translate_with_indent(ctx, indent_lvl)
+            }
+            RenameTrip { name } => i18n::errors::COMMAND_ERROR_RENAME_TRIP
+                .translate_with_args_indent(
+                    ctx,
+                    &hashmap! {i18n::args::NAME.into() => name.clone().into()},
+                    indent_lvl,
+                ),
+            CloseTrip => {
+                i18n::errors::COMMAND_ERROR_CLOSE_TRIP.translate_with_indent(ctx, indent_lvl)
+            }
+            ReopenTrip => {
+                i18n::errors::COMMAND_ERROR_REOPEN_TRIP.translate_with_indent(ctx, indent_lvl)
+            }
+            TopicLedger => {
+                i18n::errors::COMMAND_ERROR_TOPIC_LEDGER.translate_with_indent(ctx, indent_lvl)
+            }
             AddTraveler { name } => i18n::errors::COMMAND_ERROR_ADD_TRAVELER
                 .translate_with_args_indent(
                     ctx,
@@ -126,10 +203,10 @@ impl Translate for CommandError {
                     &hashmap! {i18n::args::NUMBER.into() => number.into()},
                     indent_lvl,
                 ),
-            ListExpenses { description } => i18n::errors::COMMAND_ERROR_LIST_EXPENSES
+            ListExpenses { filter } => i18n::errors::COMMAND_ERROR_LIST_EXPENSES
                 .translate_with_args_indent(
                     ctx,
-                    &hashmap! {i18n::args::DESCRIPTION.into() => description.into()},
+                    &hashmap! {i18n::args::FILTER.into() => filter.into()},
                     indent_lvl,
                 ),
             ShowExpense { number } => i18n::errors::COMMAND_ERROR_SHOW_EXPENSE
@@ -151,16 +228,25 @@ impl Translate for CommandError {
                 },
                 indent_lvl,
             ),
+            TransferAll { sender, receiver } => i18n::errors::COMMAND_ERROR_TRANSFER_ALL
+                .translate_with_args_indent(
+                    ctx,
+                    &hashmap! {
+                        i18n::args::SENDER.into() => sender.clone().into(),
+                        i18n::args::RECEIVER.into() => receiver.clone().into(),
+                    },
+                    indent_lvl,
+                ),
             DeleteTransfer { number } => i18n::errors::COMMAND_ERROR_DELETE_TRANSFER
                 .translate_with_args_indent(
                     ctx,
                     &hashmap! {i18n::args::NUMBER.into() => number.into()},
                     indent_lvl,
                 ),
-            ListTransfers { name } => i18n::errors::COMMAND_ERROR_LIST_TRANSFERS
+            ListTransfers { filter } => i18n::errors::COMMAND_ERROR_LIST_TRANSFERS
                 .translate_with_args_indent(
                     ctx,
-                    &hashmap! {i18n::args::NAME.into() => name.clone().into()},
+                    &hashmap! {i18n::args::FILTER.into() => filter.into()},
                     indent_lvl,
                 ),
             ShowBalances { name } => i18n::errors::COMMAND_ERROR_SHOW_BALANCES
@@ -169,9 +255,32 @@ impl Translate for CommandError {
                     &hashmap! {i18n::args::NAME.into() => name.clone().into()},
                     indent_lvl,
                 ),
+            Statement { name } => i18n::errors::COMMAND_ERROR_STATEMENT.translate_with_args_indent(
+                ctx,
+                &hashmap! {i18n::args::NAME.into() => name.clone().into()},
+                indent_lvl,
+            ),
+            Summary => i18n::errors::COMMAND_ERROR_SUMMARY.translate_with_indent(ctx, indent_lvl),
+            Settle => i18n::errors::COMMAND_ERROR_SETTLE.translate_with_indent(ctx, indent_lvl),
+            PayPrefs => {
+                i18n::errors::COMMAND_ERROR_PAY_PREFS.translate_with_indent(ctx, indent_lvl)
+            }
             ShowStats => {
                 i18n::errors::COMMAND_ERROR_SHOW_STATS.translate_with_indent(ctx, indent_lvl)
             }
+            RebuildBalances => {
+                i18n::errors::COMMAND_ERROR_REBUILD_BALANCES.translate_with_indent(ctx, indent_lvl)
+            }
+            Audit => i18n::errors::COMMAND_ERROR_AUDIT.translate_with_indent(ctx, indent_lvl),
+            Export => i18n::errors::COMMAND_ERROR_EXPORT.translate_with_indent(ctx, indent_lvl),
+            Report => i18n::errors::COMMAND_ERROR_REPORT.translate_with_indent(ctx, indent_lvl),
+            Chart => i18n::errors::COMMAND_ERROR_CHART.translate_with_indent(ctx, indent_lvl),
+            DebtGraph => {
+                i18n::errors::COMMAND_ERROR_DEBT_GRAPH.translate_with_indent(ctx, indent_lvl)
+            }
+            Backup => i18n::errors::COMMAND_ERROR_BACKUP.translate_with_indent(ctx, indent_lvl),
+            Restore => i18n::errors::COMMAND_ERROR_RESTORE.translate_with_indent(ctx, indent_lvl),
+            Import => i18n::errors::COMMAND_ERROR_IMPORT.translate_with_indent(ctx, indent_lvl),
             ClearTravelers => {
                 i18n::errors::COMMAND_ERROR_CLEAR_TRAVELERS.translate_with_indent(ctx, indent_lvl)
             }
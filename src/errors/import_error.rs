@@ -0,0 +1,46 @@
+use std::{error::Error, fmt::Display};
+
+use maplit::hashmap;
+
+use crate::i18n::{self, Translate, TranslateWithArgs};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError {
+    /// The file is not a CSV file, or it is corrupted.
+    Invalid,
+    /// The file is a CSV file, but not an export of a supported app.
+    UnknownFormat,
+    /// A member of the imported group is not matched to any traveler.
+    UnmappedMember(String),
+}
+
+impl Translate for ImportError {
+    fn translate_with_indent(
+        &self,
+        ctx: std::sync::Arc<std::sync::Mutex<crate::Context>>,
+        indent_lvl: usize,
+    ) -> String {
+        match self {
+            ImportError::Invalid => {
+                i18n::errors::IMPORT_ERROR_INVALID.translate_with_indent(ctx, indent_lvl)
+            }
+            ImportError::UnknownFormat => {
+                i18n::errors::IMPORT_ERROR_UNKNOWN_FORMAT.translate_with_indent(ctx, indent_lvl)
+            }
+            ImportError::UnmappedMember(member) => i18n::errors::IMPORT_ERROR_UNMAPPED_MEMBER
+                .translate_with_args_indent(
+                    ctx,
+                    &hashmap! {i18n::args::MEMBER.into() => member.clone().into()},
+                    indent_lvl,
+                ),
+        }
+    }
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.translate_default())
+    }
+}
+
+impl Error for ImportError {}
@@ -1,7 +1,11 @@
 mod add_expense_error;
+mod backup_error;
 mod command_error;
+mod import_error;
 mod name_validation_error;
 
 pub use add_expense_error::{AddExpenseError, EndError};
+pub use backup_error::BackupError;
 pub use command_error::CommandError;
+pub use import_error::ImportError;
 pub use name_validation_error::NameValidationError;
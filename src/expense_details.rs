@@ -50,8 +50,8 @@ pub struct ExpenseDetails {
     pub expense_amount: Decimal,
     pub creditor_name: Name,
     pub shares: Vec<ShareDetails>,
-    pub chat: RecordId,
-    pub timestamp_utc: Datetime,
+    pub trip: RecordId,
+    pub occurred_at: Datetime,
 }
 
 impl ExpenseDetails {
@@ -60,13 +60,13 @@ impl ExpenseDetails {
         chat_id: ChatId,
         number: i64,
     ) -> Result<Option<Self>, surrealdb::Error> {
-        use crate::chat::TABLE as CHAT_TB;
+        use crate::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(format!(
             "SELECT *
-            FROM {FN_GET_EXPENSE_DETAILS}(${CHAT}, ${EXPENSE_NUMBER})",
+            FROM {FN_GET_EXPENSE_DETAILS}(${CHAT_ID}.{ACTIVE_TRIP}, ${EXPENSE_NUMBER})",
         ))
-        .bind((CHAT, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
         .bind((EXPENSE_NUMBER, number))
         .await
         .and_then(|mut response| response.take::<Option<Self>>(0))
@@ -86,6 +86,7 @@ impl Translate for ExpenseDetails {
             .map(|share_details| share_details.translate(ctx.clone()))
             .collect::<Vec<_>>()
             .join("\n");
+        let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
         i18n::format::FORMAT_EXPENSE_DETAILS.translate_with_args_indent(
             ctx,
             &hashmap! {
@@ -94,7 +95,7 @@ impl Translate for ExpenseDetails {
                 i18n::args::AMOUNT.into() => amount.to_string().into(),
                 i18n::args::CREDITOR.into() => self.creditor_name.clone().into(),
                 i18n::args::SHARES.into() => shares_str.into(),
-                i18n::args::DATETIME.into() => self.timestamp_utc.to_fluent_datetime().unwrap().into(),
+                i18n::args::DATETIME.into() => self.occurred_at.to_fluent_datetime(&tz).unwrap().into(),
             },
             indent_lvl
         )
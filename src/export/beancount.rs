@@ -0,0 +1,152 @@
+//! Beancount export: a ledger that opens every account on the date of the
+//! first transaction.
+//!
+//! Beancount is stricter than Ledger: account components must start with an
+//! uppercase letter or a digit and only contain letters, digits and dashes,
+//! currencies must be uppercase, and strings are quoted with backslash
+//! escapes.
+
+use super::{Account, ExportData, account_names};
+use crate::commands::Document;
+use std::collections::BTreeSet;
+
+pub const FILE_NAME: &str = "travel.beancount";
+
+const EXPENSES_ACCOUNT: &str = "Expenses:Travel";
+const ASSETS_ACCOUNT: &str = "Assets:Travel";
+const UNPAID_ACCOUNT: &str = "Equity:Unpaid";
+/// Prefix of the account components that would not start with an uppercase
+/// letter or a digit.
+const COMPONENT_PREFIX: &str = "Traveler";
+/// Letter padding the currencies that would not start or end as required.
+const CURRENCY_PADDING: char = 'X';
+
+/// Returns `name` as a component of an account: its words, capitalized and
+/// joined by dashes.
+fn account_component(name: &str) -> String {
+    let component = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join("-");
+    match component.chars().next() {
+        Some(first) if first.is_uppercase() || first.is_ascii_digit() => component,
+        Some(_) => format!("{COMPONENT_PREFIX}-{component}"),
+        None => COMPONENT_PREFIX.to_owned(),
+    }
+}
+
+/// Returns `currency` in the form `[A-Z][A-Z0-9'._-]*[A-Z0-9]`.
+fn currency(currency: &str) -> String {
+    let mut currency = currency
+        .to_uppercase()
+        .chars()
+        .map(|c| match c {
+            'A'..='Z' | '0'..='9' | '\'' | '.' | '_' | '-' => c,
+            _ => '-',
+        })
+        .collect::<String>();
+    if !currency.starts_with(|c: char| c.is_ascii_uppercase()) {
+        currency.insert(0, CURRENCY_PADDING);
+    }
+    if currency.len() < 2 || !currency.ends_with(|c: char| c.is_ascii_alphanumeric()) {
+        currency.push(CURRENCY_PADDING);
+    }
+    currency
+}
+
+/// Returns `text` as a quoted string on a single line.
+fn string(text: &str) -> String {
+    let text = text
+        .split(|c: char| c.is_whitespace() || c.is_control())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    format!("\"{text}\"")
+}
+
+/// Exports `data` to a Beancount ledger.
+pub fn export(data: &ExportData) -> Vec<Document> {
+    let entries = data.entries();
+    let names = account_names(&entries, account_component);
+    let currency = currency(&data.currency);
+    let account = |account: &Account| match account {
+        Account::Expenses(name) => format!("{EXPENSES_ACCOUNT}:{}", names[name]),
+        Account::Assets(name) => format!("{ASSETS_ACCOUNT}:{}", names[name]),
+        Account::Unpaid => UNPAID_ACCOUNT.to_owned(),
+    };
+
+    let mut ledger = format!("option \"operating_currency\" \"{currency}\"\n\n");
+    if let Some(first) = entries.iter().map(|entry| &entry.date).min() {
+        let accounts = entries
+            .iter()
+            .flat_map(|entry| &entry.postings)
+            .map(|(posting, _)| account(posting))
+            .collect::<BTreeSet<_>>();
+        for opened in accounts {
+            ledger.push_str(&format!("{first} open {opened} {currency}\n"));
+        }
+        ledger.push('\n');
+    }
+    for entry in &entries {
+        ledger.push_str(&format!(
+            "{} * {}\n  code: {}\n",
+            entry.date,
+            string(&entry.description),
+            string(&entry.code)
+        ));
+        let postings = entry
+            .postings
+            .iter()
+            .map(|(posting, amount)| (account(posting), data.amount(*amount)))
+            .collect::<Vec<_>>();
+        let width = postings
+            .iter()
+            .map(|(account, _)| account.chars().count())
+            .max()
+            .unwrap_or_default();
+        for (account, amount) in postings {
+            ledger.push_str(&format!("  {account:<width$}  {amount} {currency}\n"));
+        }
+        ledger.push('\n');
+    }
+
+    vec![Document {
+        file_name: FILE_NAME.to_owned(),
+        content: ledger.into_bytes(),
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{account_component, currency, string};
+
+    #[test]
+    fn escape() {
+        assert_eq!(account_component("Bob"), "Bob");
+        assert_eq!(account_component("anna maria"), "Anna-Maria");
+        assert_eq!(account_component("O'Neil"), "O-Neil");
+        assert_eq!(account_component("2nd"), "2nd");
+        assert_eq!(account_component("李"), "Traveler-李");
+        assert_eq!(account_component("__"), "Traveler");
+
+        assert_eq!(currency("EUR"), "EUR");
+        assert_eq!(currency("usdt"), "USDT");
+        assert_eq!(currency("₿"), "X-X");
+        assert_eq!(currency("1INCH"), "X1INCH");
+
+        assert_eq!(
+            string("Say \"cheese\"\n\\o/"),
+            "\"Say \\\"cheese\\\" \\\\o/\""
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! CSV export: one file each for expenses, shares, transfers and balances.
+//!
+//! Columns are always in the same order and rows sorted by number, so that
+//! files exported at different times can be compared and imported again.
+//! Only the headers are localized.
+
+use super::ExportData;
+use crate::{
+    Context,
+    commands::Document,
+    i18n::{self, Translate},
+};
+use std::sync::{Arc, Mutex};
+
+pub const EXPENSES_FILE_NAME: &str = "expenses.csv";
+pub const SHARES_FILE_NAME: &str = "shares.csv";
+pub const TRANSFERS_FILE_NAME: &str = "transfers.csv";
+pub const BALANCES_FILE_NAME: &str = "balances.csv";
+
+/// Writes `rows` below the localized `headers` as a CSV document.
+fn document(
+    file_name: &str,
+    headers: &[&str],
+    rows: impl IntoIterator<Item = Vec<String>>,
+    ctx: Arc<Mutex<Context>>,
+) -> Result<Document, ::csv::Error> {
+    let mut writer = ::csv::Writer::from_writer(Vec::new());
+    writer.write_record(headers.iter().map(|header| header.translate(ctx.clone())))?;
+    for row in rows {
+        writer.write_record(row)?;
+    }
+    let content = writer
+        .into_inner()
+        .map_err(|err| ::csv::Error::from(err.into_error()))?;
+    Ok(Document {
+        file_name: file_name.to_owned(),
+        content,
+    })
+}
+
+/// Exports `data` to CSV documents.
+pub fn export(data: &ExportData, ctx: Arc<Mutex<Context>>) -> Result<Vec<Document>, ::csv::Error> {
+    use i18n::labels::{
+        COLUMN_AMOUNT, COLUMN_CREDITOR, COLUMN_CURRENCY, COLUMN_DATE, COLUMN_DEBTOR,
+        COLUMN_DESCRIPTION, COLUMN_EXPENSE, COLUMN_NUMBER, COLUMN_PAYER, COLUMN_RECEIVER,
+        COLUMN_SENDER, COLUMN_TRAVELER,
+    };
+
+    let expenses = document(
+        EXPENSES_FILE_NAME,
+        &[
+            COLUMN_NUMBER,
+            COLUMN_DATE,
+            COLUMN_DESCRIPTION,
+            COLUMN_AMOUNT,
+            COLUMN_CURRENCY,
+            COLUMN_PAYER,
+        ],
+        data.expenses.iter().map(|expense| {
+            vec![
+                expense.number.to_string(),
+                data.datetime(&expense.occurred_at),
+                expense.description.clone(),
+                data.amount(expense.amount),
+                data.currency.clone(),
+                expense
+                    .payer
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+            ]
+        }),
+        ctx.clone(),
+    )?;
+
+    let shares = document(
+        SHARES_FILE_NAME,
+        &[
+            COLUMN_EXPENSE,
+            COLUMN_TRAVELER,
+            COLUMN_AMOUNT,
+            COLUMN_CURRENCY,
+        ],
+        data.expenses.iter().flat_map(|expense| {
+            expense.shares.iter().map(|share| {
+                vec![
+                    expense.number.to_string(),
+                    share.name.to_string(),
+                    data.amount(share.amount),
+                    data.currency.clone(),
+                ]
+            })
+        }),
+        ctx.clone(),
+    )?;
+
+    let transfers = document(
+        TRANSFERS_FILE_NAME,
+        &[
+            COLUMN_NUMBER,
+            COLUMN_DATE,
+            COLUMN_SENDER,
+            COLUMN_RECEIVER,
+            COLUMN_AMOUNT,
+            COLUMN_CURRENCY,
+        ],
+        data.transfers.iter().map(|transfer| {
+            vec![
+                transfer.number.to_string(),
+                data.datetime(&transfer.timestamp_utc),
+                transfer.sender_name.to_string(),
+                transfer.receiver_name.to_string(),
+                data.amount(transfer.amount),
+                data.currency.clone(),
+            ]
+        }),
+        ctx.clone(),
+    )?;
+
+    let balances = document(
+        BALANCES_FILE_NAME,
+        &[
+            COLUMN_DEBTOR,
+            COLUMN_CREDITOR,
+            COLUMN_AMOUNT,
+            COLUMN_CURRENCY,
+        ],
+        data.balances.iter().map(|balance| {
+            vec![
+                balance.debtor_name.to_string(),
+                balance.creditor_name.to_string(),
+                data.amount(balance.debt),
+                data.currency.clone(),
+            ]
+        }),
+        ctx,
+    )?;
+
+    Ok(vec![expenses, shares, transfers, balances])
+}
@@ -0,0 +1,105 @@
+//! Ledger export: a journal read by both Ledger and hledger.
+//!
+//! Descriptions and account names are written on a single line, without the
+//! characters that would start a comment or split an account, and commodities
+//! that are not only made of letters are quoted.
+
+use super::{Account, ExportData, account_names};
+use crate::commands::Document;
+
+pub const FILE_NAME: &str = "travel.ledger";
+
+const EXPENSES_ACCOUNT: &str = "Expenses:Travel";
+const ASSETS_ACCOUNT: &str = "Assets:Travel";
+const UNPAID_ACCOUNT: &str = "Equity:Unpaid";
+
+/// Joins the words of `text` with single spaces, so that it fits on a line.
+fn single_line(text: &str) -> String {
+    text.split(|c: char| c.is_whitespace() || c.is_control())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns `name` as a component of an account. Colons would start a
+/// sub-account, and two spaces would end the account name.
+fn account_component(name: &str) -> String {
+    let component = single_line(&name.replace(':', "-"));
+    if component.is_empty() {
+        "-".to_owned()
+    } else {
+        component
+    }
+}
+
+/// Returns `description` as the payee of a transaction, where a semicolon
+/// would start a comment.
+fn description(description: &str) -> String {
+    single_line(&description.replace(';', ","))
+}
+
+/// Returns `currency` as a commodity, quoted unless it is only made of
+/// letters.
+fn commodity(currency: &str) -> String {
+    if currency.chars().all(char::is_alphabetic) {
+        currency.to_owned()
+    } else {
+        format!("\"{}\"", currency.replace('"', ""))
+    }
+}
+
+/// Exports `data` to a Ledger journal.
+pub fn export(data: &ExportData) -> Vec<Document> {
+    let entries = data.entries();
+    let names = account_names(&entries, account_component);
+    let commodity = commodity(&data.currency);
+    let mut journal = String::new();
+    for entry in &entries {
+        let postings = entry
+            .postings
+            .iter()
+            .map(|(account, amount)| {
+                let account = match account {
+                    Account::Expenses(name) => format!("{EXPENSES_ACCOUNT}:{}", names[name]),
+                    Account::Assets(name) => format!("{ASSETS_ACCOUNT}:{}", names[name]),
+                    Account::Unpaid => UNPAID_ACCOUNT.to_owned(),
+                };
+                (account, data.amount(*amount))
+            })
+            .collect::<Vec<_>>();
+        let width = postings
+            .iter()
+            .map(|(account, _)| account.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        journal.push_str(&format!(
+            "{} * ({}) {}\n",
+            entry.date,
+            entry.code,
+            description(&entry.description)
+        ));
+        for (account, amount) in postings {
+            journal.push_str(&format!("    {account:<width$}  {amount} {commodity}\n"));
+        }
+        journal.push('\n');
+    }
+
+    vec![Document {
+        file_name: FILE_NAME.to_owned(),
+        content: journal.into_bytes(),
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{account_component, commodity, description};
+
+    #[test]
+    fn escape() {
+        assert_eq!(account_component("Bob:  the\tdriver"), "Bob- the driver");
+        assert_eq!(description("Dinner;\n  wine"), "Dinner, wine");
+        assert_eq!(commodity("EUR"), "EUR");
+        assert_eq!(commodity("USD1"), "\"USD1\"");
+    }
+}
@@ -0,0 +1,274 @@
+//! Exports of the data of a travel plan to files, sent by `/export`.
+//!
+//! Every format is built from the same [`ExportedExpense`]s, transfers and
+//! balances. Amounts are rounded to the precision of the chat currency and
+//! dates are written in the time zone of the chat, in the same
+//! [`DATE_TIME_FORMAT`] accepted by `/addexpense --date`.
+//!
+//! The plain-text accounting formats share the [`Entry`]s returned by
+//! [`ExportData::entries`], and only differ in how they write them.
+
+pub mod beancount;
+pub mod csv;
+pub mod ledger;
+
+use crate::{
+    balance::Balance,
+    consts::{DATE_FORMAT, DATE_TIME_FORMAT},
+    money_wrapper::MoneyWrapper,
+    timezone,
+    transfer::Transfer,
+    traveler::Name,
+};
+use jiff::tz::TimeZone;
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use strum::{AsRefStr, EnumIter, EnumString};
+use surrealdb::{Datetime, RecordId, Surreal, engine::any::Any};
+use teloxide::types::ChatId;
+use travel_rs_derive::Table;
+
+/// Formats the travel plan can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr, EnumIter, EnumString)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum ExportFormat {
+    /// One CSV file each for expenses, shares, transfers and balances.
+    Csv,
+    /// A journal for Ledger and hledger.
+    Ledger,
+    /// A Beancount ledger.
+    Beancount,
+}
+
+/// Account of a posting of the plain-text accounting formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Account<'a> {
+    /// What a traveler consumed: debited with their shares.
+    Expenses(&'a Name),
+    /// The money of a traveler: credited with the expenses they paid and the
+    /// transfers they sent, debited with the transfers they received.
+    Assets(&'a Name),
+    /// Credited with the expenses without a payer.
+    Unpaid,
+}
+
+/// A transaction of the plain-text accounting formats: an expense, with a
+/// posting per share and one for the payer, or a transfer. The amounts of
+/// the postings always add up to zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry<'a> {
+    /// `E` or `T` followed by the number of the expense or transfer.
+    pub code: String,
+    pub date: String,
+    pub description: String,
+    pub postings: Vec<(Account<'a>, Decimal)>,
+}
+
+/// Gives each traveler a name that is valid in the accounts of a
+/// plain-text accounting format, as returned by `sanitize`. Names that
+/// become the same are told apart by a number.
+pub fn account_names<'a>(
+    entries: &[Entry<'a>],
+    sanitize: impl Fn(&str) -> String,
+) -> HashMap<&'a Name, String> {
+    let mut names = HashMap::new();
+    let mut taken = HashSet::new();
+    for (account, _) in entries.iter().flat_map(|entry| &entry.postings) {
+        let (Account::Expenses(name) | Account::Assets(name)) = account else {
+            continue;
+        };
+        names.entry(*name).or_insert_with(|| {
+            let sanitized = sanitize(name);
+            let unique = (1..)
+                .map(|n| match n {
+                    1 => sanitized.clone(),
+                    n => format!("{sanitized}-{n}"),
+                })
+                .find(|candidate| !taken.contains(candidate))
+                .unwrap_or(sanitized);
+            taken.insert(unique.clone());
+            unique
+        });
+    }
+    names
+}
+
+/// Share of an expense owed by a traveler.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExportedShare {
+    pub name: Name,
+    pub amount: Decimal,
+}
+
+/// An expense with the traveler who paid it and the share of each traveler.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Table)]
+pub struct ExportedExpense {
+    pub number: i64,
+    pub description: String,
+    pub amount: Decimal,
+    pub occurred_at: Datetime,
+    /// Name of the payer, if the expense has one.
+    pub payer: Option<Name>,
+    pub shares: Vec<ExportedShare>,
+}
+
+impl ExportedExpense {
+    /// Returns the expenses of the active trip of a given chat, by number,
+    /// with the shares sorted by traveler name.
+    pub async fn db_select(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+    ) -> Result<Vec<Self>, surrealdb::Error> {
+        use crate::{
+            chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+            expense::{
+                AMOUNT as EXPENSE_AMOUNT, DESCRIPTION as EXPENSE_DESCRIPTION,
+                NUMBER as EXPENSE_NUMBER, OCCURRED_AT as EXPENSE_OCCURRED_AT, TABLE as EXPENSE_TB,
+                TRIP,
+            },
+            paid_for::TABLE as PAID_FOR_TB,
+            split::{AMOUNT as SPLIT_AMOUNT, IN as SPLIT_IN, OUT as SPLIT_OUT, TABLE as SPLIT_TB},
+            traveler::{NAME as TRAVELER_NAME, TABLE as TRAVELER_TB},
+        };
+        const NAME: &str = "name";
+
+        db.query(format!(
+            "SELECT
+                {EXPENSE_NUMBER} AS {NUMBER},
+                {EXPENSE_DESCRIPTION} AS {DESCRIPTION},
+                {EXPENSE_AMOUNT} AS {AMOUNT},
+                {EXPENSE_OCCURRED_AT} AS {OCCURRED_AT},
+                (<-{PAID_FOR_TB}<-{TRAVELER_TB})[0].{TRAVELER_NAME} AS {PAYER},
+                (
+                    SELECT {SPLIT_IN}.{TRAVELER_NAME} AS {NAME}, {SPLIT_AMOUNT} AS amount
+                    FROM {SPLIT_TB}
+                    WHERE {SPLIT_OUT} = $parent.id
+                    ORDER BY {NAME} ASC
+                ) AS {SHARES}
+            FROM {EXPENSE_TB}
+            WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
+            ORDER BY {NUMBER} ASC"
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .await
+        .and_then(|mut response| response.take::<Vec<Self>>(0))
+    }
+}
+
+/// Everything exported from a travel plan.
+pub struct ExportData {
+    pub expenses: Vec<ExportedExpense>,
+    pub transfers: Vec<Transfer>,
+    /// Balances left after rounding, as shown by `/showbalances`.
+    pub balances: Vec<Balance>,
+    pub currency: String,
+    pub timezone: TimeZone,
+}
+
+impl ExportData {
+    /// Collects the data of the active trip of a given chat.
+    pub async fn db_select(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        currency: String,
+        timezone: TimeZone,
+    ) -> Result<Self, surrealdb::Error> {
+        let expenses = ExportedExpense::db_select(db.clone(), chat_id).await?;
+        let transfers = Transfer::transfers(db.clone(), chat_id).await?;
+        let balances = Balance::balances(db, chat_id)
+            .await?
+            .into_iter()
+            .filter(|balance| {
+                !MoneyWrapper::new(balance.debt, &currency)
+                    .round_value()
+                    .is_zero()
+            })
+            .collect();
+        Ok(Self {
+            expenses,
+            transfers,
+            balances,
+            currency,
+            timezone,
+        })
+    }
+
+    /// Formats `amount` with the number of decimal places of the currency,
+    /// without digit separators.
+    pub fn amount(&self, amount: Decimal) -> String {
+        let money = MoneyWrapper::new(amount, &self.currency);
+        match money.precision() {
+            Some(precision) => format!(
+                "{:.precision$}",
+                money.round_value(),
+                precision = precision as usize
+            ),
+            None => amount.normalize().to_string(),
+        }
+    }
+
+    /// Formats `datetime` in the time zone of the chat.
+    pub fn datetime(&self, datetime: &Datetime) -> String {
+        timezone::to_zoned(datetime.clone().into_inner().0, &self.timezone)
+            .map(|zoned| zoned.strftime(DATE_TIME_FORMAT).to_string())
+            .unwrap_or_default()
+    }
+
+    /// Formats the day of `datetime` in the time zone of the chat.
+    pub fn date(&self, datetime: &Datetime) -> String {
+        timezone::to_zoned(datetime.clone().into_inner().0, &self.timezone)
+            .map(|zoned| zoned.strftime(DATE_FORMAT).to_string())
+            .unwrap_or_default()
+    }
+
+    fn round(&self, amount: Decimal) -> Decimal {
+        MoneyWrapper::new(amount, &self.currency).round_value()
+    }
+
+    /// Returns the expenses and transfers as [`Entry`]s, by date. Amounts are
+    /// rounded to the precision of the currency, and the payer of an expense
+    /// is credited with the sum of the rounded shares, so that the entries
+    /// still balance.
+    pub fn entries(&self) -> Vec<Entry<'_>> {
+        let expenses = self.expenses.iter().map(|expense| {
+            let mut postings = expense
+                .shares
+                .iter()
+                .map(|share| (Account::Expenses(&share.name), self.round(share.amount)))
+                .collect::<Vec<_>>();
+            let paid = postings.iter().map(|(_, amount)| *amount).sum::<Decimal>();
+            let payer = expense
+                .payer
+                .as_ref()
+                .map_or(Account::Unpaid, Account::Assets);
+            postings.push((payer, -paid));
+            let entry = Entry {
+                code: format!("E{}", expense.number),
+                date: self.date(&expense.occurred_at),
+                description: expense.description.clone(),
+                postings,
+            };
+            (&expense.occurred_at, entry)
+        });
+        let transfers = self.transfers.iter().map(|transfer| {
+            let amount = self.round(transfer.amount);
+            let entry = Entry {
+                code: format!("T{}", transfer.number),
+                date: self.date(&transfer.timestamp_utc),
+                description: format!("{} → {}", transfer.sender_name, transfer.receiver_name),
+                postings: vec![
+                    (Account::Assets(&transfer.receiver_name), amount),
+                    (Account::Assets(&transfer.sender_name), -amount),
+                ],
+            };
+            (&transfer.timestamp_utc, entry)
+        });
+        let mut entries = expenses.chain(transfers).collect::<Vec<_>>();
+        entries.sort_by_key(|(occurred_at, _)| *occurred_at);
+        entries.into_iter().map(|(_, entry)| entry).collect()
+    }
+}
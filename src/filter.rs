@@ -0,0 +1,758 @@
+//! Filter language of `/listexpenses` and `/listtransfers`, e.g.
+//! `payer:Alice with:Bob amount>20 date:2026-08-01..2026-08-05 cat:food "gelato"`.
+//!
+//! A filter is parsed into a typed [`Filter`], then compiled into a
+//! SurrealQL condition whose values are bound as query parameters. Text is
+//! searched in the full-text index of expense descriptions, ranking the
+//! expenses by relevance.
+
+use crate::{
+    consts::DATE_FORMAT,
+    i18n::{self, TranslateWithArgs},
+    timezone,
+    traveler::Name,
+};
+use chrono::{Datelike, NaiveDate, TimeDelta};
+use jiff::tz::TimeZone;
+use maplit::hashmap;
+use rust_decimal::Decimal;
+use std::{str::FromStr, sync::Arc};
+use surrealdb::{Datetime, engine::any::Any, method::Query};
+
+const PAYER_KEY: &str = "payer";
+const FROM_KEY: &str = "from";
+const TO_KEY: &str = "to";
+const WITH_KEY: &str = "with";
+const AMOUNT_KEY: &str = "amount";
+const DATE_KEY: &str = "date";
+const CATEGORY_KEY: &str = "cat";
+const SORT_KEY: &str = "sort";
+
+const DATE_SORT: &str = "date";
+const AMOUNT_SORT: &str = "amount";
+const NUMBER_SORT: &str = "number";
+
+const VALUE_SEP: char = ':';
+const QUOTE: char = '"';
+const DATE_RANGE_SEP: &str = "..";
+const DESCENDING_PREFIX: char = '-';
+const CATEGORY_PREFIX: char = '#';
+
+/// Words shorter than this are not in the full-text index: text made only
+/// of such words is fuzzy-matched instead.
+const MIN_SEARCH_WORD_LEN: usize = 3;
+const HIGHLIGHT_START: &str = "*";
+const HIGHLIGHT_END: &str = "*";
+const RELEVANCE: &str = "relevance";
+/// Field with the description of a searched expense, its matches
+/// highlighted.
+pub const HIGHLIGHTED: &str = "highlighted";
+
+/// What a filter selects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Expenses,
+    Transfers,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    /// Splits the comparison operator off the start of `text`.
+    fn split(text: &str) -> Option<(Self, &str)> {
+        [
+            ("<=", Self::LessOrEqual),
+            (">=", Self::GreaterOrEqual),
+            ("<", Self::Less),
+            (">", Self::Greater),
+            ("=", Self::Equal),
+            (":", Self::Equal),
+        ]
+        .into_iter()
+        .find_map(|(op, cmp)| text.strip_prefix(op).map(|rest| (cmp, rest)))
+    }
+
+    fn operator(&self) -> &'static str {
+        match self {
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Equal => "=",
+            Self::GreaterOrEqual => ">=",
+            Self::Greater => ">",
+        }
+    }
+}
+
+/// A condition the expenses or transfers must meet.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// The expense was paid by the traveler.
+    Payer(Name),
+    /// The transfer was sent by the traveler.
+    From(Name),
+    /// The transfer was received by the traveler.
+    To(Name),
+    /// The traveler has a share of the expense, or sent or received the
+    /// transfer.
+    With(Name),
+    Amount(Comparison, Decimal),
+    /// Made between two days, both included, in the time zone of the chat.
+    Date {
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
+    /// The description of the expense has the category as a hashtag.
+    Category(String),
+    /// The description of the expense has every word of the text, or
+    /// fuzzy-matches it if the words are too short to be searched.
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Date,
+    Amount,
+    Number,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sort {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    pub target: Target,
+    pub conditions: Vec<Condition>,
+    /// If not given, searched expenses are listed by relevance, the others by
+    /// date, and transfers by number.
+    pub sort: Option<Sort>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterErrorKind {
+    /// The key before `:` is not a filter.
+    UnknownKey,
+    /// The filter exists but can't be applied to the target, e.g. `cat:`
+    /// on transfers.
+    NotAvailable,
+    InvalidValue,
+    UnclosedQuote,
+}
+
+impl FilterErrorKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::UnknownKey => "unknown-key",
+            Self::NotAvailable => "not-available",
+            Self::InvalidValue => "invalid-value",
+            Self::UnclosedQuote => "unclosed-quote",
+        }
+    }
+}
+
+/// A filter that couldn't be parsed, with the word it failed at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterError {
+    pub target: Target,
+    pub kind: FilterErrorKind,
+    pub token: String,
+}
+
+impl i18n::Translate for FilterError {
+    fn translate_with_indent(
+        &self,
+        ctx: Arc<std::sync::Mutex<crate::Context>>,
+        indent_lvl: usize,
+    ) -> String {
+        let command = match self.target {
+            Target::Expenses => variant_to_string!(Command::ListExpenses),
+            Target::Transfers => variant_to_string!(Command::ListTransfers),
+        };
+        i18n::commands::FILTER_INVALID.translate_with_args_indent(
+            ctx,
+            &hashmap! {
+                i18n::args::KIND.into() => self.kind.as_str().into(),
+                i18n::args::TOKEN.into() => self.token.clone().into(),
+                i18n::args::COMMAND.into() => command.into(),
+            },
+            indent_lvl,
+        )
+    }
+}
+
+/// A word of the filter: `raw` is the word as sent, `text` is the word
+/// without quotes.
+struct Token {
+    raw: String,
+    text: String,
+    quoted: bool,
+}
+
+/// Splits `text` at whitespace outside quotes.
+fn tokenize(text: &str, target: Target) -> Result<Vec<Token>, FilterError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            return Ok(tokens);
+        }
+
+        let mut token = Token {
+            raw: String::new(),
+            text: String::new(),
+            quoted: false,
+        };
+        let mut in_quotes = false;
+        while let Some(c) = chars.next_if(|c| in_quotes || !c.is_whitespace()) {
+            token.raw.push(c);
+            if c == QUOTE {
+                in_quotes = !in_quotes;
+                token.quoted = true;
+            } else {
+                token.text.push(c);
+            }
+        }
+        if in_quotes {
+            return Err(FilterError {
+                target,
+                kind: FilterErrorKind::UnclosedQuote,
+                token: token.raw,
+            });
+        }
+        tokens.push(token);
+    }
+}
+
+/// Splits a `key:value` word into the key and what follows it, operator
+/// included. Words without a key, or quoted before the operator, are text.
+fn split_key(raw: &str) -> Option<(&str, &str)> {
+    let i = raw.find([VALUE_SEP, '<', '>', '='])?;
+    let key = &raw[..i];
+    (!key.is_empty() && key.chars().all(|c| c.is_ascii_alphabetic())).then(|| (key, &raw[i..]))
+}
+
+/// Whether `text` has words long enough to be searched in the full-text
+/// index. Like its analyzer, splits words at punctuation.
+fn is_searchable(text: &str) -> bool {
+    text.split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.chars().count() >= MIN_SEARCH_WORD_LEN)
+}
+
+/// Returns the category of an expense: the first hashtag of its
+/// `description`, lowercased, as matched by `cat:`.
+pub fn category(description: &str) -> Option<String> {
+    description
+        .split_whitespace()
+        .filter_map(|word| word.strip_prefix(CATEGORY_PREFIX))
+        .find(|category| !category.is_empty())
+        .map(str::to_lowercase)
+}
+
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text, DATE_FORMAT)
+        .ok()
+        // Keep the day after in the range of local datetimes
+        .filter(|date| (1..=9998).contains(&date.year()))
+}
+
+impl Filter {
+    /// Parses the filters of `/listexpenses` or `/listtransfers`.
+    ///
+    /// Words without a key are text: consecutive ones are taken together,
+    /// quoted ones on their own. They match the description of expenses and
+    /// the name of a traveler of transfers.
+    pub fn parse(text: &str, target: Target) -> Result<Self, FilterError> {
+        let mut filter = Filter {
+            target,
+            conditions: Vec::new(),
+            sort: None,
+        };
+        let mut words = Vec::new();
+        for token in tokenize(text, target)? {
+            if let Some((key, rest)) = split_key(&token.raw) {
+                filter.push_text(&mut words)?;
+                filter.push_keyed(&token.raw, key, rest)?;
+            } else if token.quoted {
+                filter.push_text(&mut words)?;
+                filter.push_text(&mut vec![token])?;
+            } else {
+                words.push(token);
+            }
+        }
+        filter.push_text(&mut words)?;
+        Ok(filter)
+    }
+
+    /// Whether the filter selects every expense or transfer.
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+
+    fn error(&self, kind: FilterErrorKind, token: &str) -> FilterError {
+        FilterError {
+            target: self.target,
+            kind,
+            token: token.to_owned(),
+        }
+    }
+
+    fn name(&self, text: &str, token: &str) -> Result<Name, FilterError> {
+        Name::from_str(text).map_err(|_| self.error(FilterErrorKind::InvalidValue, token))
+    }
+
+    /// Adds the condition of the text `words`, if any, and clears them.
+    fn push_text(&mut self, words: &mut Vec<Token>) -> Result<(), FilterError> {
+        if words.is_empty() {
+            return Ok(());
+        }
+        let raw = words
+            .iter()
+            .map(|w| w.raw.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let text = words
+            .iter()
+            .map(|w| w.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        words.clear();
+        let condition = match self.target {
+            Target::Expenses => Condition::Text(text),
+            Target::Transfers => Condition::With(self.name(&text, &raw)?),
+        };
+        self.conditions.push(condition);
+        Ok(())
+    }
+
+    fn push_keyed(&mut self, raw: &str, key: &str, rest: &str) -> Result<(), FilterError> {
+        use FilterErrorKind::*;
+
+        let key = key.to_lowercase();
+        if key == AMOUNT_KEY {
+            let (cmp, amount) = Comparison::split(rest).ok_or(self.error(InvalidValue, raw))?;
+            let amount = Decimal::from_str(amount.trim_matches(QUOTE))
+                .map_err(|_| self.error(InvalidValue, raw))?;
+            self.conditions.push(Condition::Amount(cmp, amount));
+            return Ok(());
+        }
+
+        let value = rest
+            .strip_prefix(VALUE_SEP)
+            .ok_or(self.error(InvalidValue, raw))?
+            .replace(QUOTE, "");
+        let condition = match (key.as_str(), self.target) {
+            (PAYER_KEY, Target::Expenses) => Condition::Payer(self.name(&value, raw)?),
+            (FROM_KEY, Target::Transfers) => Condition::From(self.name(&value, raw)?),
+            (TO_KEY, Target::Transfers) => Condition::To(self.name(&value, raw)?),
+            (WITH_KEY, _) => Condition::With(self.name(&value, raw)?),
+            (CATEGORY_KEY, Target::Expenses) => {
+                let category = value.trim_start_matches(CATEGORY_PREFIX).to_lowercase();
+                if category.is_empty() || category.contains(char::is_whitespace) {
+                    return Err(self.error(InvalidValue, raw));
+                }
+                Condition::Category(category)
+            }
+            (DATE_KEY, _) => {
+                let (from, to) = match value.split_once(DATE_RANGE_SEP) {
+                    Some((from, to)) => (from, to),
+                    None => (value.as_str(), value.as_str()),
+                };
+                let parse = |date: &str| match date {
+                    "" => Ok(None),
+                    date => parse_date(date)
+                        .map(Some)
+                        .ok_or(self.error(InvalidValue, raw)),
+                };
+                let (from, to) = (parse(from)?, parse(to)?);
+                if from.is_none() && to.is_none() || from.zip(to).is_some_and(|(f, t)| f > t) {
+                    return Err(self.error(InvalidValue, raw));
+                }
+                Condition::Date { from, to }
+            }
+            (SORT_KEY, _) => {
+                let (descending, key) = match value.strip_prefix(DESCENDING_PREFIX) {
+                    Some(key) => (true, key),
+                    None => (false, value.as_str()),
+                };
+                let key = match key.to_lowercase().as_str() {
+                    DATE_SORT => SortKey::Date,
+                    AMOUNT_SORT => SortKey::Amount,
+                    NUMBER_SORT => SortKey::Number,
+                    _ => return Err(self.error(InvalidValue, raw)),
+                };
+                self.sort = Some(Sort { key, descending });
+                return Ok(());
+            }
+            (PAYER_KEY | CATEGORY_KEY, Target::Transfers)
+            | (FROM_KEY | TO_KEY, Target::Expenses) => return Err(self.error(NotAvailable, raw)),
+            _ => return Err(self.error(UnknownKey, raw)),
+        };
+        self.conditions.push(condition);
+        Ok(())
+    }
+
+    /// Compiles the filter into a SurrealQL condition and order on the
+    /// fields of expenses, or of `fn::get_transfers` rows. Dates are read in
+    /// the time zone `tz`.
+    ///
+    /// Each searched text refers to the full-text index by its own number,
+    /// and the relevance of an expense is the sum of their scores.
+    pub fn compile(&self, tz: &TimeZone) -> CompiledFilter {
+        use crate::{
+            expense, paid_for,
+            split::TABLE as SPLIT,
+            transfer,
+            traveler::{NAME, TABLE as TRAVELER},
+        };
+
+        let (amount, date, number) = match self.target {
+            Target::Expenses => (expense::AMOUNT, expense::OCCURRED_AT, expense::NUMBER),
+            Target::Transfers => (transfer::AMOUNT, transfer::TIMESTAMP_UTC, transfer::NUMBER),
+        };
+        let traveler_is = |edge: &str, param: &str| {
+            format!(
+                "count(<-{edge}<-({TRAVELER} WHERE string::lowercase({NAME}) = string::lowercase(${param}))) > 0"
+            )
+        };
+        let name_is = |field: &str, param: &str| {
+            format!("string::lowercase({field}) = string::lowercase(${param})")
+        };
+        let local_midnight =
+            |date: NaiveDate| timezone::to_utc(date.and_hms_opt(0, 0, 0)?, tz).map(Datetime::from);
+
+        let mut compiled = CompiledFilter {
+            fields: String::new(),
+            condition: String::new(),
+            order: String::new(),
+            params: Vec::new(),
+        };
+        let mut conditions = Vec::new();
+        // Searched texts are merged into a single predicate, so that one
+        // relevance score and one highlight cover all of them
+        let mut search = None;
+        for (i, condition) in self.conditions.iter().enumerate() {
+            let param = format!("filter_{i}");
+            let (surql, value) = match (condition, self.target) {
+                (Condition::Payer(name), _) => (
+                    traveler_is(paid_for::TABLE, &param),
+                    Param::Name(name.clone()),
+                ),
+                (Condition::With(name), Target::Expenses) => {
+                    (traveler_is(SPLIT, &param), Param::Name(name.clone()))
+                }
+                (Condition::With(name), Target::Transfers) => (
+                    format!(
+                        "({} || {})",
+                        name_is(transfer::SENDER_NAME, &param),
+                        name_is(transfer::RECEIVER_NAME, &param)
+                    ),
+                    Param::Name(name.clone()),
+                ),
+                (Condition::From(name), _) => (
+                    name_is(transfer::SENDER_NAME, &param),
+                    Param::Name(name.clone()),
+                ),
+                (Condition::To(name), _) => (
+                    name_is(transfer::RECEIVER_NAME, &param),
+                    Param::Name(name.clone()),
+                ),
+                (Condition::Amount(cmp, value), _) => (
+                    format!("{amount} {} <decimal> ${param}", cmp.operator()),
+                    Param::Amount(*value),
+                ),
+                (Condition::Date { from, to }, _) => {
+                    if let Some(from) = from.and_then(local_midnight) {
+                        let param = format!("{param}_from");
+                        conditions.push(format!("{date} >= ${param}"));
+                        compiled.params.push((param, Param::Datetime(from)));
+                    }
+                    let day_after = to.and_then(|to| to.checked_add_signed(TimeDelta::days(1)));
+                    if let Some(to) = day_after.and_then(local_midnight) {
+                        let param = format!("{param}_to");
+                        conditions.push(format!("{date} < ${param}"));
+                        compiled.params.push((param, Param::Datetime(to)));
+                    }
+                    continue;
+                }
+                (Condition::Category(category), _) => (
+                    format!(
+                        "${param} IN string::words(string::lowercase({}))",
+                        expense::DESCRIPTION
+                    ),
+                    Param::Text(format!("{CATEGORY_PREFIX}{category}")),
+                ),
+                (Condition::Text(text), _) if is_searchable(text) => {
+                    if let Some(index) = search {
+                        if let (_, Param::Text(searched)) = &mut compiled.params[index] {
+                            searched.push(' ');
+                            searched.push_str(text);
+                        }
+                        continue;
+                    }
+                    search = Some(compiled.params.len());
+                    (
+                        format!("{} @0@ ${param}", expense::DESCRIPTION),
+                        Param::Text(text.clone()),
+                    )
+                }
+                (Condition::Text(text), _) => (
+                    format!("{} ~ ${param}", expense::DESCRIPTION),
+                    Param::Text(text.clone()),
+                ),
+            };
+            conditions.push(surql);
+            compiled.params.push((param, value));
+        }
+
+        compiled.condition = if conditions.is_empty() {
+            String::from("true")
+        } else {
+            conditions.join(" && ")
+        };
+        if search.is_some() {
+            compiled.fields = format!(
+                ", search::score(0) AS {RELEVANCE}, \
+                search::highlight('{HIGHLIGHT_START}', '{HIGHLIGHT_END}', 0) AS {HIGHLIGHTED}"
+            );
+        }
+
+        compiled.order = match self.sort {
+            None if search.is_some() => format!("{RELEVANCE} DESC, {number} ASC"),
+            None => match self.target {
+                Target::Expenses => format!("{date} ASC, {number} ASC"),
+                Target::Transfers => format!("{number} ASC"),
+            },
+            Some(Sort { key, descending }) => {
+                let direction = if descending { "DESC" } else { "ASC" };
+                match key {
+                    SortKey::Date => format!("{date} {direction}, {number} ASC"),
+                    SortKey::Amount => format!("{amount} {direction}, {number} ASC"),
+                    SortKey::Number => format!("{number} {direction}"),
+                }
+            }
+        };
+        compiled
+    }
+}
+
+enum Param {
+    Name(Name),
+    Text(String),
+    Amount(Decimal),
+    Datetime(Datetime),
+}
+
+/// A [`Filter`] compiled into SurrealQL: `fields` follow the selected ones,
+/// `condition` and `order` go in the `WHERE` and `ORDER BY` clauses of a
+/// query, and the values they refer to are bound by [`CompiledFilter::bind`].
+pub struct CompiledFilter {
+    /// The relevance and [`HIGHLIGHTED`] description of searched expenses,
+    /// empty if nothing is searched.
+    pub fields: String,
+    pub condition: String,
+    pub order: String,
+    params: Vec<(String, Param)>,
+}
+
+impl CompiledFilter {
+    /// Binds the values of the filter to `query`.
+    pub fn bind<'r>(self, mut query: Query<'r, Any>) -> Query<'r, Any> {
+        for (param, value) in self.params {
+            query = match value {
+                Param::Name(name) => query.bind((param, name)),
+                Param::Text(text) => query.bind((param, text)),
+                Param::Amount(amount) => query.bind((param, amount)),
+                Param::Datetime(datetime) => query.bind((param, datetime)),
+            };
+        }
+        query
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+
+    fn name(name: &str) -> Name {
+        Name::from_str(name).unwrap()
+    }
+
+    fn date(date: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(date, DATE_FORMAT).ok()
+    }
+
+    #[test]
+    fn expense_category() {
+        assert_eq!(category("Gelato #Food #fun"), Some(String::from("food")));
+        assert_eq!(category("Price # 3 #"), None);
+        assert_eq!(category("Taxi"), None);
+    }
+
+    #[test]
+    fn parse_expense_filter() {
+        let filter = Filter::parse(
+            r#"payer:Alice with:Bob amount>20 date:2026-08-01..2026-08-05 cat:food "gelato" sort:-amount"#,
+            Target::Expenses,
+        )
+        .unwrap();
+        assert_eq!(
+            filter.conditions,
+            vec![
+                Condition::Payer(name("Alice")),
+                Condition::With(name("Bob")),
+                Condition::Amount(Comparison::Greater, Decimal::from(20)),
+                Condition::Date {
+                    from: date("2026-08-01"),
+                    to: date("2026-08-05"),
+                },
+                Condition::Category(String::from("food")),
+                Condition::Text(String::from("gelato")),
+            ]
+        );
+        assert_eq!(
+            filter.sort,
+            Some(Sort {
+                key: SortKey::Amount,
+                descending: true,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_text() {
+        // Consecutive words are a single text, quoted ones stand alone
+        let filter =
+            Filter::parse(r#"hotel room "late fee" amount<=100.5"#, Target::Expenses).unwrap();
+        assert_eq!(
+            filter.conditions,
+            vec![
+                Condition::Text(String::from("hotel room")),
+                Condition::Text(String::from("late fee")),
+                Condition::Amount(Comparison::LessOrEqual, Decimal::new(1005, 1)),
+            ]
+        );
+
+        let filter = Filter::parse(
+            r#"Mary Ann to:"Bob Jr" date:..2026-08-05"#,
+            Target::Transfers,
+        )
+        .unwrap();
+        assert_eq!(
+            filter.conditions,
+            vec![
+                Condition::With(name("Mary Ann")),
+                Condition::To(name("Bob Jr")),
+                Condition::Date {
+                    from: None,
+                    to: date("2026-08-05"),
+                },
+            ]
+        );
+        assert!(Filter::parse("  ", Target::Transfers).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |text, target| {
+            let err = Filter::parse(text, target).unwrap_err();
+            (err.kind, err.token)
+        };
+        use FilterErrorKind::*;
+        assert_eq!(
+            error("payer:Alice colour:red", Target::Expenses),
+            (UnknownKey, String::from("colour:red"))
+        );
+        assert_eq!(
+            error("cat:food", Target::Transfers),
+            (NotAvailable, String::from("cat:food"))
+        );
+        assert_eq!(
+            error("amount>>20", Target::Expenses),
+            (InvalidValue, String::from("amount>>20"))
+        );
+        assert_eq!(
+            error("date:2026-08-05..2026-08-01", Target::Expenses),
+            (InvalidValue, String::from("date:2026-08-05..2026-08-01"))
+        );
+        assert_eq!(
+            error("sort:payer", Target::Expenses),
+            (InvalidValue, String::from("sort:payer"))
+        );
+        assert_eq!(
+            error(r#"with:"Bob"#, Target::Expenses),
+            (UnclosedQuote, String::from(r#"with:"Bob"#))
+        );
+    }
+
+    #[test]
+    fn compile_dates_in_time_zone() {
+        let filter = Filter::parse("date:2026-08-01", Target::Expenses).unwrap();
+        let tokyo = timezone::find("Asia/Tokyo").unwrap();
+        let compiled = filter.compile(&tokyo);
+        assert_eq!(
+            compiled.condition,
+            format!(
+                "{date} >= $filter_0_from && {date} < $filter_0_to",
+                date = crate::expense::OCCURRED_AT
+            )
+        );
+        let datetimes = compiled
+            .params
+            .iter()
+            .filter_map(|(_, value)| match value {
+                Param::Datetime(datetime) => Some(datetime.clone().into_inner().0),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            datetimes,
+            vec![
+                "2026-07-31T15:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+                "2026-08-01T15:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn compile_searches() {
+        use crate::expense::{AMOUNT, DESCRIPTION, NUMBER};
+
+        // Texts with no word long enough are fuzzy-matched
+        let filter = Filter::parse(r#""hotel room" "A1" "late fee""#, Target::Expenses).unwrap();
+        let compiled = filter.compile(&TimeZone::UTC);
+        assert_eq!(
+            compiled.condition,
+            format!("{DESCRIPTION} @0@ $filter_0 && {DESCRIPTION} ~ $filter_1")
+        );
+        let searched = compiled
+            .params
+            .iter()
+            .filter_map(|(_, value)| match value {
+                Param::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(searched, vec!["hotel room late fee", "A1"]);
+        assert!(compiled.fields.contains("search::score(0) AS relevance"));
+        assert_eq!(compiled.order, format!("{RELEVANCE} DESC, {NUMBER} ASC"));
+
+        let filter = Filter::parse("hotel sort:-amount", Target::Expenses).unwrap();
+        let compiled = filter.compile(&TimeZone::UTC);
+        assert_eq!(compiled.order, format!("{AMOUNT} DESC, {NUMBER} ASC"));
+
+        let filter = Filter::parse("A1", Target::Expenses).unwrap();
+        let compiled = filter.compile(&TimeZone::UTC);
+        assert!(compiled.fields.is_empty());
+    }
+}
@@ -1,13 +1,18 @@
 pub const AMOUNT: &str = "amount";
+pub const AMOUNTS: &str = "amounts";
 pub const AVAILABLE_LANGS: &str = "available-langs";
 pub const AVERAGE_PER_DAY: &str = "average-per-day";
 pub const BALANCES_COUNT: &str = "balances-count";
 pub const BEST_MATCH: &str = "best-match";
 pub const CHAR: &str = "char";
+pub const CHARTS: &str = "charts";
+pub const CLOSED: &str = "closed";
 pub const COMMAND: &str = "command";
+pub const CONSUMED: &str = "consumed";
 pub const COUNT: &str = "count";
 pub const CREDITOR: &str = "creditor";
 pub const CURRENCY: &str = "currency";
+pub const DATE: &str = "date";
 pub const DATETIME: &str = "datetime";
 pub const DEBT: &str = "debt";
 pub const DEBTOR: &str = "debtor";
@@ -16,35 +21,63 @@ pub const EXPENSE: &str = "expense";
 pub const EXPENSE_STATS: &str = "expense-stats";
 pub const EXPENSES: &str = "expenses";
 pub const EXPENSES_COUNT: &str = "expenses-count";
+pub const FILTER: &str = "filter";
+pub const FORMAT: &str = "format";
+pub const FORMATS: &str = "formats";
 pub const HELP_MESSAGE: &str = "help-message";
 pub const INPUT: &str = "input";
+pub const KIND: &str = "kind";
 pub const LANGID: &str = "langid";
 pub const LANGUAGE: &str = "language";
+pub const LINE: &str = "line";
 pub const MAJOR_CREDITORS: &str = "major-creditors";
 pub const MAJOR_DEBTORS: &str = "major-debtors";
 pub const MAX: &str = "max";
 pub const MEAN: &str = "mean";
+pub const MEDIAN: &str = "median";
+pub const MEMBER: &str = "member";
 pub const MIN: &str = "min";
+pub const MODE: &str = "mode";
 pub const NAME: &str = "name";
+pub const NET: &str = "net";
 pub const NEWEST: &str = "newest";
 pub const NOW: &str = "now";
 pub const NUMBER: &str = "number";
+pub const OLD_NAME: &str = "old-name";
 pub const OLDEST: &str = "oldest";
 pub const OLDEST_TIMESTAMP: &str = "oldest-timestamp";
 pub const OTHER_TRAVELER_NAME: &str = "other-traveler-name";
+pub const PAID: &str = "paid";
+pub const PERIOD: &str = "period";
+pub const PERIODS: &str = "periods";
 pub const PROCESS: &str = "process";
+pub const REASON: &str = "reason";
+pub const RECEIVED: &str = "received";
 pub const RECEIVER: &str = "receiver";
+pub const SECTION: &str = "section";
+pub const SECTIONS: &str = "sections";
 pub const SENDER: &str = "sender";
+pub const SENT: &str = "sent";
 pub const SHARES: &str = "shares";
 pub const SUM: &str = "sum";
+pub const SUPPORTED_VERSION: &str = "supported-version";
+pub const TABLE: &str = "table";
+pub const TIMEZONE: &str = "timezone";
+pub const TOKEN: &str = "token";
 pub const TRANSFER_STATS: &str = "transfer-stats";
+pub const TRANSFERS_COUNT: &str = "transfers-count";
 pub const TRAVELER_IS: &str = "traveler-is";
 pub const TRAVELER_IS_CASE_CREDITOR: &str = "creditor";
 pub const TRAVELER_IS_CASE_DEBTOR: &str = "debtor";
 pub const TRAVELER_NAME: &str = "traveler-name";
 pub const TRAVELER_STATS: &str = "traveler-stats";
 pub const TRAVELERS: &str = "travelers";
+pub const TRAVELERS_COUNT: &str = "travelers-count";
 pub const TRAVELERS_PAID_LEAST: &str = "travelers-paid-least";
 pub const TRAVELERS_PAID_MOST: &str = "travelers-paid-most";
 pub const TRAVELERS_PAYS_LEAST_FREQUENTLY: &str = "travelers-pays-least-frequently";
 pub const TRAVELERS_PAYS_MOST_FREQUENTLY: &str = "travelers-pays-most-frequently";
+pub const TRENDS: &str = "trends";
+pub const VERSION: &str = "version";
+pub const WEEKDAY: &str = "weekday";
+pub const WEEKS: &str = "weeks";
@@ -1,6 +1,9 @@
 pub const COMMAND_DESCRIPTIONS: &str = "command-descriptions";
 
 pub const PROCESS_ALREADY_RUNNING: &str = "process-already-running";
+pub const TRIP_CLOSED: &str = "trip-closed";
+pub const ADMIN_ONLY: &str = "admin-only";
+pub const FILE_TOO_LARGE: &str = "file-too-large";
 
 pub const RUNNING_PROCESS_UNKNOWN: &str = "running-process-unknown";
 pub const RUNNING_PROCESS_ADD_EXPENSE: &str = "running-process-add-expense";
@@ -11,19 +14,55 @@ pub const RUNNING_PROCESS_SHOW_EXPENSE: &str = "running-process-show-expense";
 pub const RUNNING_PROCESS_DELETE_TRANSFER: &str = "running-process-delete-transfer";
 pub const RUNNING_PROCESS_SET_LANGUAGE: &str = "running-process-set-language";
 pub const RUNNING_PROCESS_SET_CURRENCY: &str = "running-process-set-currency";
+pub const RUNNING_PROCESS_SET_TIMEZONE: &str = "running-process-set-timezone";
 pub const RUNNING_PROCESS_LIST_EXPENSES: &str = "running-process-list-expenses";
 pub const RUNNING_PROCESS_TRANSFER: &str = "running-process-transfer";
 pub const RUNNING_PROCESS_CLEAR_TRAVELERS: &str = "running-process-clear-travelers";
 pub const RUNNING_PROCESS_CLEAR_EXPENSES: &str = "running-process-clear-expenses";
 pub const RUNNING_PROCESS_CLEAR_TRANSFERS: &str = "running-process-clear-transfers";
 pub const RUNNING_PROCESS_CLEAR_ALL: &str = "running-process-clear-all";
+pub const RUNNING_PROCESS_NEW_TRIP: &str = "running-process-new-trip";
+pub const RUNNING_PROCESS_RENAME_TRIP: &str = "running-process-rename-trip";
+pub const RUNNING_PROCESS_RESTORE: &str = "running-process-restore";
+pub const RUNNING_PROCESS_IMPORT: &str = "running-process-import";
 
 pub const SET_LANGUAGE_NOT_AVAILABLE: &str = "set-language-not-available";
 pub const SET_LANGUAGE_OK: &str = "set-language-ok";
 
 pub const SET_CURRENCY_OK: &str = "set-currency-ok";
+pub const SET_SETTLEMENT_OK: &str = "set-settlement-ok";
+pub const SET_SETTLEMENT_STATUS: &str = "set-settlement-status";
+pub const SET_SETTLEMENT_USAGE: &str = "set-settlement-usage";
+pub const SET_SIMPLIFICATION_OK: &str = "set-simplification-ok";
+pub const SET_SIMPLIFICATION_STATUS: &str = "set-simplification-status";
+pub const SET_SIMPLIFICATION_USAGE: &str = "set-simplification-usage";
 pub const SET_CURRENCY_NOT_AVAILABLE: &str = "set-currency-not-available";
 
+pub const SET_TIMEZONE_OK: &str = "set-timezone-ok";
+pub const SET_TIMEZONE_NOT_AVAILABLE: &str = "set-timezone-not-available";
+
+pub const NEW_TRIP_ALREADY_EXISTS: &str = "new-trip-already-exists";
+pub const NEW_TRIP_OK: &str = "new-trip-ok";
+
+pub const SWITCH_TRIP_ALREADY_ACTIVE: &str = "switch-trip-already-active";
+pub const SWITCH_TRIP_CHOOSE: &str = "switch-trip-choose";
+pub const SWITCH_TRIP_NOT_FOUND: &str = "switch-trip-not-found";
+pub const SWITCH_TRIP_OK: &str = "switch-trip-ok";
+
+pub const RENAME_TRIP_ALREADY_EXISTS: &str = "rename-trip-already-exists";
+pub const RENAME_TRIP_OK: &str = "rename-trip-ok";
+pub const CLOSE_TRIP_ALREADY_CLOSED: &str = "close-trip-already-closed";
+pub const CLOSE_TRIP_NOT_SETTLED: &str = "close-trip-not-settled";
+pub const CLOSE_TRIP_OK: &str = "close-trip-ok";
+pub const REOPEN_TRIP_NOT_CLOSED: &str = "reopen-trip-not-closed";
+pub const REOPEN_TRIP_OK: &str = "reopen-trip-ok";
+pub const TOPIC_LEDGER_DISABLED: &str = "topic-ledger-disabled";
+pub const TOPIC_LEDGER_ENABLED: &str = "topic-ledger-enabled";
+pub const TOPIC_LEDGER_NOT_A_TOPIC: &str = "topic-ledger-not-a-topic";
+pub const TOPIC_LEDGER_STATUS_DISABLED: &str = "topic-ledger-status-disabled";
+pub const TOPIC_LEDGER_STATUS_ENABLED: &str = "topic-ledger-status-enabled";
+pub const TOPIC_LEDGER_USAGE: &str = "topic-ledger-usage";
+
 pub const ADD_TRAVELER_ALREADY_ADDED: &str = "add-traveler-already-added";
 pub const ADD_TRAVELER_OK: &str = "add-traveler-ok";
 
@@ -36,12 +75,17 @@ pub const LIST_TRAVELERS_NOT_FOUND: &str = "list-travelers-not-found";
 pub const DELETE_EXPENSE_NOT_FOUND: &str = "delete-expense-not-found";
 pub const DELETE_EXPENSE_OK: &str = "delete-expense-ok";
 
-pub const LIST_EXPENSES_DESCR_NOT_FOUND: &str = "list-expenses-descr-not-found";
+pub const LIST_EXPENSES_FILTER_NOT_FOUND: &str = "list-expenses-filter-not-found";
 pub const LIST_EXPENSES_NOT_FOUND: &str = "list-expenses-not-found";
 
+pub const FILTER_INVALID: &str = "filter-invalid";
+
+pub const LIST_PAGE_EXPIRED: &str = "list-page-expired";
+
 pub const SHOW_EXPENSE_NOT_FOUND: &str = "show-expense-not-found";
 
 pub const SHOW_BALANCES_OK: &str = "show-balances-ok";
+pub const SHOW_BALANCES_MODE: &str = "show-balances-mode";
 pub const SHOW_BALANCES_SETTLED_UP: &str = "show-balances-settled-up";
 pub const SHOW_BALANCES_TRAVELER_OK: &str = "show-balances-traveler-ok";
 pub const SHOW_BALANCES_TRAVELER_NOT_FOUND: &str = "show-balances-traveler-not-found";
@@ -52,10 +96,74 @@ pub const TRANSFER_RECEIVER_NOT_FOUND: &str = "transfer-receiver-not-found";
 pub const TRANSFER_SENDER_NOT_FOUND: &str = "transfer-sender-not-found";
 pub const TRANSFER_SAME_SENDER_RECEIVER: &str = "transfer-same-sender-receiver";
 pub const TRANSFER_NON_POSITIVE_AMOUNT: &str = "transfer-non-positive-amount";
+pub const TRANSFER_NOTHING_OWED: &str = "transfer-nothing-owed";
 
 pub const DELETE_TRANSFER_NOT_FOUND: &str = "delete-transfer-not-found";
 pub const DELETE_TRANSFER_OK: &str = "delete-transfer-ok";
 
+pub const SETTLE_PLAN_HINT: &str = "settle-plan-hint";
+pub const SETTLE_ALL_OK: &str = "settle-all-ok";
+pub const STATEMENT_HEADER: &str = "statement-header";
+pub const STATEMENT_PAGE: &str = "statement-page";
+pub const STATEMENT_PAGE_NOT_FOUND: &str = "statement-page-not-found";
+pub const STATEMENT_EMPTY: &str = "statement-empty";
+pub const STATEMENT_CHOOSE_TRAVELER: &str = "statement-choose-traveler";
+pub const STATEMENT_TRAVELER_NOT_FOUND: &str = "statement-traveler-not-found";
+pub const STATEMENT_USAGE: &str = "statement-usage";
+
+pub const SUMMARY_HEADER: &str = "summary-header";
+pub const SUMMARY_NOT_FOUND: &str = "summary-not-found";
+pub const SUMMARY_UNBALANCED: &str = "summary-unbalanced";
+
+pub const SHOW_STATS_USAGE: &str = "show-stats-usage";
+pub const SHOW_STATS_NO_EXPENSES: &str = "show-stats-no-expenses";
+
+pub const REBUILD_BALANCES_OK: &str = "rebuild-balances-ok";
+
+pub const AUDIT_OK: &str = "audit-ok";
+pub const AUDIT_HEADER: &str = "audit-header";
+pub const AUDIT_REPAIR_HINT: &str = "audit-repair-hint";
+pub const AUDIT_MANUAL_HINT: &str = "audit-manual-hint";
+pub const AUDIT_REPAIRED: &str = "audit-repaired";
+pub const AUDIT_USAGE: &str = "audit-usage";
+
+pub const EXPORT_OK: &str = "export-ok";
+pub const EXPORT_USAGE: &str = "export-usage";
+
+pub const REPORT_OK: &str = "report-ok";
+
+pub const CHART_USAGE: &str = "chart-usage";
+pub const CHART_NO_DATA: &str = "chart-no-data";
+
+pub const DEBT_GRAPH_USAGE: &str = "debt-graph-usage";
+pub const DEBT_GRAPH_SETTLED: &str = "debt-graph-settled";
+pub const DEBT_GRAPH_DOT_OK: &str = "debt-graph-dot-ok";
+
+pub const BACKUP_OK: &str = "backup-ok";
+
+pub const RESTORE_OK: &str = "restore-ok";
+pub const RESTORE_USAGE: &str = "restore-usage";
+pub const IMPORT_OK: &str = "import-ok";
+pub const IMPORT_USAGE: &str = "import-usage";
+pub const IMPORT_NOTHING: &str = "import-nothing";
+pub const IMPORT_SKIPPED: &str = "import-skipped";
+pub const IMPORT_SKIPPED_ROW: &str = "import-skipped-row";
+
+pub const SETTLE_USAGE: &str = "settle-usage";
+pub const SETTLE_PREFERENCE_UNMET: &str = "settle-preference-unmet";
+pub const SETTLE_PREFERENCE_VIA_UNMET: &str = "settle-preference-via-unmet";
+
+pub const PAY_PREFS_CANNOT_PAY: &str = "pay-prefs-cannot-pay";
+pub const PAY_PREFS_SETTLES_VIA: &str = "pay-prefs-settles-via";
+pub const PAY_PREFS_NONE: &str = "pay-prefs-none";
+pub const PAY_PREFS_CANNOT_PAY_OK: &str = "pay-prefs-cannot-pay-ok";
+pub const PAY_PREFS_SETTLES_VIA_OK: &str = "pay-prefs-settles-via-ok";
+pub const PAY_PREFS_CLEAR_OK: &str = "pay-prefs-clear-ok";
+pub const PAY_PREFS_SAME_TRAVELER: &str = "pay-prefs-same-traveler";
+pub const PAY_PREFS_TRAVELER_NOT_FOUND: &str = "pay-prefs-traveler-not-found";
+pub const PAY_PREFS_USAGE: &str = "pay-prefs-usage";
+pub const PAY_PREFS_FULL_ONLY: &str = "pay-prefs-full-only";
+
 pub const CLEAR_TRAVELERS_OK: &str = "clear-travelers-ok";
 pub const CLEAR_TRAVELERS_NOT_FOUND: &str = "clear-travelers-not-found";
 pub const CLEAR_TRAVELERS_HAS_EXPENSES: &str = "clear-travelers-has-expenses";
@@ -69,7 +177,7 @@ pub const CLEAR_TRANSFERS_NOT_FOUND: &str = "clear-transfers-not-found";
 pub const CLEAR_ALL_OK: &str = "clear-all-ok";
 pub const CLEAR_ALL_NOT_FOUND: &str = "clear-all-not-found";
 
-pub const LIST_TRANSFERS_NAME_NOT_FOUND: &str = "list-transfers-name-not-found";
+pub const LIST_TRANSFERS_FILTER_NOT_FOUND: &str = "list-transfers-filter-not-found";
 pub const LIST_TRANSFERS_NOT_FOUND: &str = "list-transfers-not-found";
 
 pub const CANCEL_NO_PROCESS_TO_CANCEL: &str = "cancel-no-process-to-cancel";
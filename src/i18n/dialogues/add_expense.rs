@@ -1,13 +1,17 @@
 pub const ADD_EXPENSE_START: &str = "add-expense-start";
 pub const ADD_EXPENSE_ASK_DESCRIPTION: &str = "add-expense-ask-description";
+pub const ADD_EXPENSE_INVALID_DATE_OPTION: &str = "add-expense-invalid-date-option";
 
 pub const ADD_EXPENSE_ASK_AMOUNT: &str = "add-expense-ask-amount";
 pub const ADD_EXPENSE_INVALID_DESCRIPTION: &str = "add-expense-invalid-description";
 
-pub const ADD_EXPENSE_ASK_PAID_BY: &str = "add-expense-ask-paid-by";
+pub const ADD_EXPENSE_ASK_DATE: &str = "add-expense-ask-date";
 pub const ADD_EXPENSE_INVALID_AMOUNT: &str = "add-expense-invalid-amount";
 pub const ADD_EXPENSE_NON_POSITIVE_AMOUNT: &str = "add-expense-non-positive-amount";
 
+pub const ADD_EXPENSE_ASK_PAID_BY: &str = "add-expense-ask-paid-by";
+pub const ADD_EXPENSE_INVALID_DATE: &str = "add-expense-invalid-date";
+
 pub const ADD_EXPENSE_INVALID_PAID_BY: &str = "add-expense-invalid-paid-by";
 pub const ADD_EXPENSE_ASK_SHARES: &str = "add-expense-ask-shares";
 pub const ADD_EXPENSE_TRAVELER_NOT_FOUND: &str = "add-expense-traveler-not-found";
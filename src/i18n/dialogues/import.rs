@@ -0,0 +1,5 @@
+pub const IMPORT_ASK_TRAVELER: &str = "import-ask-traveler";
+pub const IMPORT_TRAVELER_TAKEN: &str = "import-traveler-taken";
+pub const IMPORT_MEMBER_NEW: &str = "import-member-new";
+pub const IMPORT_MEMBER_EXISTING: &str = "import-member-existing";
+pub const IMPORT_CONFIRM: &str = "import-confirm";
@@ -7,9 +7,14 @@ pub mod clear_travelers;
 pub mod delete_expense;
 pub mod delete_transfer;
 pub mod delete_traveler;
+pub mod import;
 pub mod list_expenses;
+pub mod new_trip;
+pub mod rename_trip;
+pub mod restore;
 pub mod set_currency;
 pub mod set_language;
+pub mod set_timezone;
 pub mod show_expense;
 pub mod transfer;
 
@@ -22,8 +27,13 @@ pub use clear_travelers::*;
 pub use delete_expense::*;
 pub use delete_transfer::*;
 pub use delete_traveler::*;
+pub use import::*;
 pub use list_expenses::*;
+pub use new_trip::*;
+pub use rename_trip::*;
+pub use restore::*;
 pub use set_currency::*;
 pub use set_language::*;
+pub use set_timezone::*;
 pub use show_expense::*;
 pub use transfer::*;
@@ -0,0 +1,2 @@
+pub const NEW_TRIP_ASK_NAME: &str = "new-trip-ask-name";
+pub const NEW_TRIP_INVALID_NAME: &str = "new-trip-invalid-name";
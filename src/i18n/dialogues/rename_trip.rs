@@ -0,0 +1,2 @@
+pub const RENAME_TRIP_ASK_NAME: &str = "rename-trip-ask-name";
+pub const RENAME_TRIP_INVALID_NAME: &str = "rename-trip-invalid-name";
@@ -0,0 +1 @@
+pub const RESTORE_CONFIRM: &str = "restore-confirm";
@@ -0,0 +1,2 @@
+pub const SET_TIMEZONE_ASK_TIMEZONE: &str = "set-timezone-ask-timezone";
+pub const SET_TIMEZONE_INVALID_TIMEZONE: &str = "set-timezone-invalid-timezone";
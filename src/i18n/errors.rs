@@ -1,6 +1,16 @@
 pub const COMMAND_ERROR_HELP: &str = "command-error-help";
 pub const COMMAND_ERROR_SET_LANGUAGE: &str = "command-error-set-language";
 pub const COMMAND_ERROR_SET_CURRENCY: &str = "command-error-set-currency";
+pub const COMMAND_ERROR_SET_TIMEZONE: &str = "command-error-set-timezone";
+pub const COMMAND_ERROR_SET_SETTLEMENT: &str = "command-error-set-settlement";
+pub const COMMAND_ERROR_SET_SIMPLIFICATION: &str = "command-error-set-simplification";
+pub const COMMAND_ERROR_NEW_TRIP: &str = "command-error-new-trip";
+pub const COMMAND_ERROR_SWITCH_TRIP: &str = "command-error-switch-trip";
+pub const COMMAND_ERROR_LIST_TRIPS: &str = "command-error-list-trips";
+pub const COMMAND_ERROR_RENAME_TRIP: &str = "command-error-rename-trip";
+pub const COMMAND_ERROR_CLOSE_TRIP: &str = "command-error-close-trip";
+pub const COMMAND_ERROR_REOPEN_TRIP: &str = "command-error-reopen-trip";
+pub const COMMAND_ERROR_TOPIC_LEDGER: &str = "command-error-topic-ledger";
 pub const COMMAND_ERROR_ADD_TRAVELER: &str = "command-error-add-traveler";
 pub const COMMAND_ERROR_DELETE_TRAVELER: &str = "command-error-delete-traveler";
 pub const COMMAND_ERROR_LIST_TRAVELERS: &str = "command-error-list-travelers";
@@ -8,10 +18,24 @@ pub const COMMAND_ERROR_DELETE_EXPENSE: &str = "command-error-delete-expense";
 pub const COMMAND_ERROR_LIST_EXPENSES: &str = "command-error-list-expenses";
 pub const COMMAND_ERROR_SHOW_EXPENSE: &str = "command-error-show-expense";
 pub const COMMAND_ERROR_TRANSFER: &str = "command-error-transfer";
+pub const COMMAND_ERROR_TRANSFER_ALL: &str = "command-error-transfer-all";
 pub const COMMAND_ERROR_DELETE_TRANSFER: &str = "command-error-delete-transfer";
 pub const COMMAND_ERROR_LIST_TRANSFERS: &str = "command-error-list-transfers";
 pub const COMMAND_ERROR_SHOW_BALANCES: &str = "command-error-show-balances";
+pub const COMMAND_ERROR_SETTLE: &str = "command-error-settle";
+pub const COMMAND_ERROR_STATEMENT: &str = "command-error-statement";
+pub const COMMAND_ERROR_SUMMARY: &str = "command-error-summary";
+pub const COMMAND_ERROR_PAY_PREFS: &str = "command-error-pay-prefs";
 pub const COMMAND_ERROR_SHOW_STATS: &str = "command-error-show-stats";
+pub const COMMAND_ERROR_REBUILD_BALANCES: &str = "command-error-rebuild-balances";
+pub const COMMAND_ERROR_AUDIT: &str = "command-error-audit";
+pub const COMMAND_ERROR_EXPORT: &str = "command-error-export";
+pub const COMMAND_ERROR_REPORT: &str = "command-error-report";
+pub const COMMAND_ERROR_CHART: &str = "command-error-chart";
+pub const COMMAND_ERROR_DEBT_GRAPH: &str = "command-error-debt-graph";
+pub const COMMAND_ERROR_BACKUP: &str = "command-error-backup";
+pub const COMMAND_ERROR_RESTORE: &str = "command-error-restore";
+pub const COMMAND_ERROR_IMPORT: &str = "command-error-import";
 pub const COMMAND_ERROR_CLEAR_TRAVELERS: &str = "command-error-clear-travelers";
 pub const COMMAND_ERROR_CLEAR_EXPENSES: &str = "command-error-clear-expenses";
 pub const COMMAND_ERROR_CLEAR_TRANSFERS: &str = "command-error-clear-transfers";
@@ -32,5 +56,13 @@ pub const ADD_EXPENSE_ERROR_INVALID_FORMAT: &str = "add-expense-error-invalid-fo
 pub const ADD_EXPENSE_ERROR_NO_TRAVELERS_SPECIFIED: &str =
     "add-expense-error-no-travelers-specified";
 
+pub const BACKUP_ERROR_INVALID: &str = "backup-error-invalid";
+pub const BACKUP_ERROR_UNSUPPORTED_VERSION: &str = "backup-error-unsupported-version";
+pub const BACKUP_ERROR_UNKNOWN_TRAVELER: &str = "backup-error-unknown-traveler";
+
+pub const IMPORT_ERROR_INVALID: &str = "import-error-invalid";
+pub const IMPORT_ERROR_UNKNOWN_FORMAT: &str = "import-error-unknown-format";
+pub const IMPORT_ERROR_UNMAPPED_MEMBER: &str = "import-error-unmapped-member";
+
 pub const END_ERROR_CLOSING_DIALOGUE: &str = "end-error-closing-dialogue";
 pub const END_ERROR_EXPENSE_CREATED: &str = "end-error-no-expense-created";
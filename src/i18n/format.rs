@@ -2,10 +2,24 @@ pub const FORMAT_SHARE_DETAILS: &str = "format-share-details";
 pub const FORMAT_EXPENSE_DETAILS: &str = "format-expense-details";
 pub const FORMAT_EXPENSE: &str = "format-expense";
 pub const FORMAT_TRANSFER: &str = "format-transfer";
+pub const FORMAT_STATEMENT_ENTRY: &str = "format-statement-entry";
+pub const FORMAT_TRIP: &str = "format-trip";
+pub const FORMAT_ACTIVE_TRIP: &str = "format-active-trip";
 pub const FORMAT_TRAVELER_STATS_AMOUNT: &str = "format-traveler-stats-amount";
 pub const FORMAT_TRAVELER_STATS_FREQUENCY: &str = "format-traveler-stats-frequency";
 pub const FORMAT_AVERAGE_PER_DAY: &str = "format-average-per-day";
 pub const FORMAT_EXPENSE_STATS: &str = "format-expense-stats";
 pub const FORMAT_TRANSFER_STATS: &str = "format-transfer-stats";
 pub const FORMAT_TRAVELER_STATS: &str = "format-traveler-stats";
+pub const FORMAT_TRAVELER_SUMMARY: &str = "format-traveler-summary";
+pub const FORMAT_AUDIT_PROBLEM: &str = "format-audit-problem";
 pub const FORMAT_STATS: &str = "format-stats";
+pub const FORMAT_STATS_PERIOD: &str = "format-stats-period";
+pub const FORMAT_STATS_WEEK: &str = "format-stats-week";
+pub const FORMAT_WEEKDAY: &str = "format-weekday";
+pub const FORMAT_STATS_DAYS: &str = "format-stats-days";
+pub const FORMAT_STATS_WEEKS: &str = "format-stats-weeks";
+pub const FORMAT_STATS_WEEKDAYS: &str = "format-stats-weekdays";
+pub const FORMAT_STATS_HOURS: &str = "format-stats-hours";
+pub const FORMAT_STATS_TRAVELERS: &str = "format-stats-travelers";
+pub const FORMAT_STATS_TREND: &str = "format-stats-trend";
@@ -4,6 +4,26 @@ pub const HELP_SET_LANGUAGE: &str = "help-set-language";
 pub const DESCR_SET_LANGUAGE: &str = "descr-set-language";
 pub const HELP_SET_CURRENCY: &str = "help-set-currency";
 pub const DESCR_SET_CURRENCY: &str = "descr-set-currency";
+pub const HELP_SET_TIMEZONE: &str = "help-set-timezone";
+pub const DESCR_SET_TIMEZONE: &str = "descr-set-timezone";
+pub const HELP_SET_SETTLEMENT: &str = "help-set-settlement";
+pub const DESCR_SET_SETTLEMENT: &str = "descr-set-settlement";
+pub const HELP_SET_SIMPLIFICATION: &str = "help-set-simplification";
+pub const DESCR_SET_SIMPLIFICATION: &str = "descr-set-simplification";
+pub const HELP_NEW_TRIP: &str = "help-new-trip";
+pub const DESCR_NEW_TRIP: &str = "descr-new-trip";
+pub const HELP_SWITCH_TRIP: &str = "help-switch-trip";
+pub const DESCR_SWITCH_TRIP: &str = "descr-switch-trip";
+pub const HELP_LIST_TRIPS: &str = "help-list-trips";
+pub const DESCR_LIST_TRIPS: &str = "descr-list-trips";
+pub const HELP_RENAME_TRIP: &str = "help-rename-trip";
+pub const DESCR_RENAME_TRIP: &str = "descr-rename-trip";
+pub const HELP_CLOSE_TRIP: &str = "help-close-trip";
+pub const DESCR_CLOSE_TRIP: &str = "descr-close-trip";
+pub const HELP_REOPEN_TRIP: &str = "help-reopen-trip";
+pub const DESCR_REOPEN_TRIP: &str = "descr-reopen-trip";
+pub const HELP_TOPIC_LEDGER: &str = "help-topic-ledger";
+pub const DESCR_TOPIC_LEDGER: &str = "descr-topic-ledger";
 pub const HELP_ADD_TRAVELER: &str = "help-add-traveler";
 pub const DESCR_ADD_TRAVELER: &str = "descr-add-traveler";
 pub const HELP_DELETE_TRAVELER: &str = "help-delete-traveler";
@@ -26,8 +46,36 @@ pub const HELP_LIST_TRANSFERS: &str = "help-list-transfers";
 pub const DESCR_LIST_TRANSFERS: &str = "descr-list-transfers";
 pub const HELP_SHOW_BALANCES: &str = "help-show-balances";
 pub const DESCR_SHOW_BALANCES: &str = "descr-show-balances";
+pub const HELP_SETTLE: &str = "help-settle";
+pub const DESCR_SETTLE: &str = "descr-settle";
+pub const HELP_STATEMENT: &str = "help-statement";
+pub const DESCR_STATEMENT: &str = "descr-statement";
+pub const HELP_SUMMARY: &str = "help-summary";
+pub const DESCR_SUMMARY: &str = "descr-summary";
+pub const HELP_PAY_PREFS: &str = "help-pay-prefs";
+pub const DESCR_PAY_PREFS: &str = "descr-pay-prefs";
 pub const HELP_SHOW_STATS: &str = "help-show-stats";
 pub const DESCR_SHOW_STATS: &str = "descr-show-stats";
+
+pub const HELP_REBUILD_BALANCES: &str = "help-rebuild-balances";
+pub const DESCR_REBUILD_BALANCES: &str = "descr-rebuild-balances";
+
+pub const HELP_AUDIT: &str = "help-audit";
+pub const DESCR_AUDIT: &str = "descr-audit";
+pub const HELP_EXPORT: &str = "help-export";
+pub const DESCR_EXPORT: &str = "descr-export";
+pub const HELP_REPORT: &str = "help-report";
+pub const DESCR_REPORT: &str = "descr-report";
+pub const HELP_CHART: &str = "help-chart";
+pub const DESCR_CHART: &str = "descr-chart";
+pub const HELP_DEBT_GRAPH: &str = "help-debt-graph";
+pub const DESCR_DEBT_GRAPH: &str = "descr-debt-graph";
+pub const HELP_BACKUP: &str = "help-backup";
+pub const DESCR_BACKUP: &str = "descr-backup";
+pub const HELP_RESTORE: &str = "help-restore";
+pub const DESCR_RESTORE: &str = "descr-restore";
+pub const HELP_IMPORT: &str = "help-import";
+pub const DESCR_IMPORT: &str = "descr-import";
 pub const HELP_CLEAR_TRAVELERS: &str = "help-clear-travelers";
 pub const DESCR_CLEAR_TRAVELERS: &str = "descr-clear-travelers";
 pub const HELP_CLEAR_EXPENSES: &str = "help-clear-expenses";
@@ -8,6 +8,59 @@ pub const FILTER_BUTTON: &str = "filter-button";
 pub const HELP_BUTTON: &str = "help-button";
 pub const CONFIRM_YES_BUTTON: &str = "confirm-yes-button";
 pub const CONFIRM_NO_BUTTON: &str = "confirm-no-button";
+pub const PAID_BUTTON: &str = "paid-button";
+pub const SETTLE_ALL_BUTTON: &str = "settle-all-button";
+pub const REPAIR_BUTTON: &str = "repair-button";
+pub const STATS_SECTION_BUTTON: &str = "stats-section-button";
+pub const TODAY_BUTTON: &str = "today-button";
+pub const YESTERDAY_BUTTON: &str = "yesterday-button";
+pub const NEW_TRAVELER_BUTTON: &str = "new-traveler-button";
+
+pub const COLUMN_NUMBER: &str = "column-number";
+pub const COLUMN_DATE: &str = "column-date";
+pub const COLUMN_DESCRIPTION: &str = "column-description";
+pub const COLUMN_AMOUNT: &str = "column-amount";
+pub const COLUMN_CURRENCY: &str = "column-currency";
+pub const COLUMN_PAYER: &str = "column-payer";
+pub const COLUMN_EXPENSE: &str = "column-expense";
+pub const COLUMN_TRAVELER: &str = "column-traveler";
+pub const COLUMN_SENDER: &str = "column-sender";
+pub const COLUMN_RECEIVER: &str = "column-receiver";
+pub const COLUMN_DEBTOR: &str = "column-debtor";
+pub const COLUMN_CREDITOR: &str = "column-creditor";
+
+pub const REPORT_TITLE: &str = "report-title";
+pub const REPORT_TITLE_TRIP: &str = "report-title-trip";
+pub const REPORT_GENERATED: &str = "report-generated";
+pub const REPORT_TOTALS: &str = "report-totals";
+pub const REPORT_SPENT: &str = "report-spent";
+pub const REPORT_TRANSFERRED: &str = "report-transferred";
+pub const REPORT_EXPENSES_COUNT: &str = "report-expenses-count";
+pub const REPORT_TRAVELERS_COUNT: &str = "report-travelers-count";
+pub const REPORT_DAYS: &str = "report-days";
+pub const REPORT_AVERAGE_PER_DAY: &str = "report-average-per-day";
+pub const REPORT_TRAVELERS: &str = "report-travelers";
+pub const REPORT_PAID: &str = "report-paid";
+pub const REPORT_CONSUMED: &str = "report-consumed";
+pub const REPORT_NET: &str = "report-net";
+pub const REPORT_CATEGORIES: &str = "report-categories";
+pub const REPORT_CATEGORY: &str = "report-category";
+pub const REPORT_UNCATEGORIZED: &str = "report-uncategorized";
+pub const REPORT_PERCENTAGE: &str = "report-percentage";
+pub const REPORT_TIMELINE: &str = "report-timeline";
+pub const REPORT_SETTLEMENT: &str = "report-settlement";
+pub const REPORT_SETTLED_UP: &str = "report-settled-up";
+pub const REPORT_EXPENSES: &str = "report-expenses";
+pub const REPORT_SHARES: &str = "report-shares";
+pub const REPORT_NO_EXPENSES: &str = "report-no-expenses";
+
+pub const CHART_DAILY: &str = "chart-daily";
+pub const CHART_TRAVELERS: &str = "chart-travelers";
+pub const CHART_CATEGORIES: &str = "chart-categories";
+pub const CHART_OTHER: &str = "chart-other";
+
+pub const DEBT_GRAPH: &str = "debt-graph";
+pub const DEBT_GRAPH_RAW: &str = "debt-graph-raw";
 
 /// Prefix for localized language label messages. The full key is built by
 /// appending the language identifier (e.g. `language-label-en-US`).
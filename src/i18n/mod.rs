@@ -34,6 +34,16 @@ static LOCALES: LazyLock<ArcLoader> = LazyLock::new(|| {
                 {HELP_COMMAND} = {help}
                 {SET_LANGUAGE_COMMAND} = {set_language}
                 {SET_CURRENCY_COMMAND} = {set_currency}
+                {SET_TIMEZONE_COMMAND} = {set_timezone}
+                {SET_SETTLEMENT_COMMAND} = {set_settlement}
+                {SET_SIMPLIFICATION_COMMAND} = {set_simplification}
+                {NEW_TRIP_COMMAND} = {new_trip}
+                {SWITCH_TRIP_COMMAND} = {switch_trip}
+                {LIST_TRIPS_COMMAND} = {list_trips}
+                {RENAME_TRIP_COMMAND} = {rename_trip}
+                {CLOSE_TRIP_COMMAND} = {close_trip}
+                {REOPEN_TRIP_COMMAND} = {reopen_trip}
+                {TOPIC_LEDGER_COMMAND} = {topic_ledger}
                 {ADD_TRAVELER_COMMAND} = {add_traveler}
                 {DELETE_TRAVELER_COMMAND} = {delete_traveler}
                 {LIST_TRAVELERS_COMMAND} = {list_travelers}
@@ -45,7 +55,20 @@ static LOCALES: LazyLock<ArcLoader> = LazyLock::new(|| {
                 {DELETE_TRANSFER_COMMAND} = {delete_transfer}
                 {LIST_TRANSFERS_COMMAND} = {list_transfers}
                 {SHOW_BALANCES_COMMAND} = {show_balances}
+                {STATEMENT_COMMAND} = {statement}
+                {SUMMARY_COMMAND} = {summary}
+                {SETTLE_COMMAND} = {settle}
+                {PAY_PREFS_COMMAND} = {pay_prefs}
                 {SHOW_STATS_COMMAND} = {show_stats}
+                {REBUILD_BALANCES_COMMAND} = {rebuild_balances}
+                {AUDIT_COMMAND} = {audit}
+                {EXPORT_COMMAND} = {export}
+                {REPORT_COMMAND} = {report}
+                {CHART_COMMAND} = {chart}
+                {DEBT_GRAPH_COMMAND} = {debt_graph}
+                {BACKUP_COMMAND} = {backup}
+                {RESTORE_COMMAND} = {restore}
+                {IMPORT_COMMAND} = {import}
                 {CLEAR_TRAVELERS_COMMAND} = {clear_travelers}
                 {CLEAR_EXPENSES_COMMAND} = {clear_expenses}
                 {CLEAR_TRANSFERS_COMMAND} = {clear_transfers}
@@ -55,6 +78,16 @@ static LOCALES: LazyLock<ArcLoader> = LazyLock::new(|| {
                 help = variant_to_string!(Command::Help),
                 set_language = variant_to_string!(Command::SetLanguage),
                 set_currency = variant_to_string!(Command::SetCurrency),
+                set_timezone = variant_to_string!(Command::SetTimezone),
+                set_settlement = variant_to_string!(Command::SetSettlement),
+                set_simplification = variant_to_string!(Command::SetSimplification),
+                new_trip = variant_to_string!(Command::NewTrip),
+                switch_trip = variant_to_string!(Command::SwitchTrip),
+                list_trips = variant_to_string!(Command::ListTrips),
+                rename_trip = variant_to_string!(Command::RenameTrip),
+                close_trip = variant_to_string!(Command::CloseTrip),
+                reopen_trip = variant_to_string!(Command::ReopenTrip),
+                topic_ledger = variant_to_string!(Command::TopicLedger),
                 add_traveler = variant_to_string!(Command::AddTraveler),
                 delete_traveler = variant_to_string!(Command::DeleteTraveler),
                 list_travelers = variant_to_string!(Command::ListTravelers),
@@ -66,7 +99,20 @@ static LOCALES: LazyLock<ArcLoader> = LazyLock::new(|| {
                 delete_transfer = variant_to_string!(Command::DeleteTransfer),
                 list_transfers = variant_to_string!(Command::ListTransfers),
                 show_balances = variant_to_string!(Command::ShowBalances),
+                statement = variant_to_string!(Command::Statement),
+                summary = variant_to_string!(Command::Summary),
+                settle = variant_to_string!(Command::Settle),
+                pay_prefs = variant_to_string!(Command::PayPrefs),
                 show_stats = variant_to_string!(Command::ShowStats),
+                rebuild_balances = variant_to_string!(Command::RebuildBalances),
+                audit = variant_to_string!(Command::Audit),
+                export = variant_to_string!(Command::Export),
+                report = variant_to_string!(Command::Report),
+                chart = variant_to_string!(Command::Chart),
+                debt_graph = variant_to_string!(Command::DebtGraph),
+                backup = variant_to_string!(Command::Backup),
+                restore = variant_to_string!(Command::Restore),
+                import = variant_to_string!(Command::Import),
                 clear_travelers = variant_to_string!(Command::ClearTravelers),
                 clear_expenses = variant_to_string!(Command::ClearExpenses),
                 clear_transfers = variant_to_string!(Command::ClearTransfers),
@@ -81,12 +127,30 @@ static LOCALES: LazyLock<ArcLoader> = LazyLock::new(|| {
                 {I18N_SPLIT_AMONG_NAME_AMOUNT_SEP} = {split_among_name_amount_sep}
                 {I18N_ALL_KWORD} = {all_kword}
                 {I18N_END_KWORD} = {end_kword}
+                {I18N_CANNOT_KWORD} = {cannot_kword}
+                {I18N_VIA_KWORD} = {via_kword}
+                {I18N_CLEAR_KWORD} = {clear_kword}
+                {I18N_FIX_KWORD} = {fix_kword}
+                {I18N_RAW_KWORD} = {raw_kword}
+                {I18N_DOT_KWORD} = {dot_kword}
+                {I18N_TODAY_KWORD} = {today_kword}
+                {I18N_YESTERDAY_KWORD} = {yesterday_kword}
+                {I18N_DATE_OPTION} = {date_option}
                 ",
                 decimal_sep = DECIMAL_SEP,
                 split_among_entries_sep = SPLIT_AMONG_ENTRIES_SEP,
                 split_among_name_amount_sep = SPLIT_AMONG_NAME_AMOUNT_SEP,
                 all_kword = ALL_KWORD,
-                end_kword = END_KWORD
+                end_kword = END_KWORD,
+                cannot_kword = CANNOT_KWORD,
+                via_kword = VIA_KWORD,
+                clear_kword = CLEAR_KWORD,
+                fix_kword = FIX_KWORD,
+                raw_kword = RAW_KWORD,
+                dot_kword = DOT_KWORD,
+                today_kword = TODAY_KWORD,
+                yesterday_kword = YESTERDAY_KWORD,
+                date_option = DATE_OPTION
             );
 
             let command_descriptions = formatdoc!(
@@ -7,10 +7,33 @@ pub const HELP_COMMAND: &str = "-help-command";
 pub const LIST_EXPENSES_COMMAND: &str = "-list-expenses-command";
 pub const LIST_TRAVELERS_COMMAND: &str = "-list-travelers-command";
 pub const SET_CURRENCY_COMMAND: &str = "-set-currency-command";
+pub const SET_TIMEZONE_COMMAND: &str = "-set-timezone-command";
 pub const SET_LANGUAGE_COMMAND: &str = "-set-language-command";
+pub const SET_SETTLEMENT_COMMAND: &str = "-set-settlement-command";
+pub const SET_SIMPLIFICATION_COMMAND: &str = "-set-simplification-command";
+pub const NEW_TRIP_COMMAND: &str = "-new-trip-command";
+pub const SWITCH_TRIP_COMMAND: &str = "-switch-trip-command";
+pub const LIST_TRIPS_COMMAND: &str = "-list-trips-command";
+pub const RENAME_TRIP_COMMAND: &str = "-rename-trip-command";
+pub const CLOSE_TRIP_COMMAND: &str = "-close-trip-command";
+pub const REOPEN_TRIP_COMMAND: &str = "-reopen-trip-command";
+pub const TOPIC_LEDGER_COMMAND: &str = "-topic-ledger-command";
 pub const SHOW_BALANCES_COMMAND: &str = "-show-balances-command";
 pub const SHOW_EXPENSE_COMMAND: &str = "-show-expense-command";
+pub const STATEMENT_COMMAND: &str = "-statement-command";
+pub const SUMMARY_COMMAND: &str = "-summary-command";
+pub const SETTLE_COMMAND: &str = "-settle-command";
+pub const PAY_PREFS_COMMAND: &str = "-pay-prefs-command";
 pub const SHOW_STATS_COMMAND: &str = "-show-stats-command";
+pub const REBUILD_BALANCES_COMMAND: &str = "-rebuild-balances-command";
+pub const AUDIT_COMMAND: &str = "-audit-command";
+pub const EXPORT_COMMAND: &str = "-export-command";
+pub const REPORT_COMMAND: &str = "-report-command";
+pub const CHART_COMMAND: &str = "-chart-command";
+pub const DEBT_GRAPH_COMMAND: &str = "-debt-graph-command";
+pub const BACKUP_COMMAND: &str = "-backup-command";
+pub const RESTORE_COMMAND: &str = "-restore-command";
+pub const IMPORT_COMMAND: &str = "-import-command";
 pub const TRANSFER_COMMAND: &str = "-transfer-command";
 pub const DELETE_TRANSFER_COMMAND: &str = "-delete-transfer-command";
 pub const LIST_TRANSFERS_COMMAND: &str = "-list-transfers-command";
@@ -24,3 +47,12 @@ pub const I18N_SPLIT_AMONG_ENTRIES_SEP: &str = "-split-among-entries-sep";
 pub const I18N_SPLIT_AMONG_NAME_AMOUNT_SEP: &str = "-split-among-name-amount-sep";
 pub const I18N_ALL_KWORD: &str = "-all-kword";
 pub const I18N_END_KWORD: &str = "-end-kword";
+pub const I18N_CANNOT_KWORD: &str = "-cannot-kword";
+pub const I18N_VIA_KWORD: &str = "-via-kword";
+pub const I18N_CLEAR_KWORD: &str = "-clear-kword";
+pub const I18N_FIX_KWORD: &str = "-fix-kword";
+pub const I18N_RAW_KWORD: &str = "-raw-kword";
+pub const I18N_DOT_KWORD: &str = "-dot-kword";
+pub const I18N_TODAY_KWORD: &str = "-today-kword";
+pub const I18N_YESTERDAY_KWORD: &str = "-yesterday-kword";
+pub const I18N_DATE_OPTION: &str = "-date-option";
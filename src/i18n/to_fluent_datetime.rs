@@ -1,33 +1,35 @@
-use chrono::{Datelike, Timelike};
+use crate::timezone;
 use fluent_datetime::FluentDateTime;
+use jiff::tz::TimeZone;
 
 /// An extension trait for `surrealdb::Datetime` to convert it to `FluentDateTime`.
 pub trait ToFluentDateTime {
-    /// Converts a `surrealdb::Datetime` into a `fluent_datetime::FluentDateTime`.
+    /// Converts a `surrealdb::Datetime` into a `fluent_datetime::FluentDateTime`
+    /// showing the local time in `tz`.
     ///
     /// This method extracts the internal `chrono::DateTime<Utc>` from the SurrealDB
-    /// datetime, then converts it to an `icu_calendar::DateTime` (using ISO calendar),
-    /// and finally wraps it in `FluentDateTime`.
+    /// datetime, converts it to the local time in `tz`, then to an
+    /// `icu_calendar::DateTime` (using ISO calendar), and finally wraps it in
+    /// `FluentDateTime`.
     ///
     /// Returns `None` if the conversion to `icu_calendar::DateTime` fails, though for valid
     /// `chrono::DateTime` instances, this should typically succeed.
-    fn to_fluent_datetime(&self) -> Option<FluentDateTime>;
+    fn to_fluent_datetime(&self, tz: &TimeZone) -> Option<FluentDateTime>;
 }
 
 // Implement the trait for `surrealdb::Datetime`
 impl ToFluentDateTime for surrealdb::Datetime {
-    fn to_fluent_datetime(&self) -> Option<FluentDateTime> {
-        // 1. Extract the inner chrono::DateTime<Utc>
-        let chrono_dt = self.clone().into_inner().0;
+    fn to_fluent_datetime(&self, tz: &TimeZone) -> Option<FluentDateTime> {
+        // 1. Extract the inner chrono::DateTime<Utc> and convert it to local time
+        let local_dt = timezone::to_zoned(self.clone().into_inner().0, tz)?;
 
         // 2. Extract components for icu_calendar::DateTime
-        let year = chrono_dt.year();
-        let month = chrono_dt.month() as u8;
-        let day = chrono_dt.day() as u8;
-        let hour = chrono_dt.hour() as u8;
-        let minute = chrono_dt.minute() as u8;
-        let second = chrono_dt.second() as u8;
-        let _nanosecond = chrono_dt.nanosecond();
+        let year = local_dt.year() as i32;
+        let month = local_dt.month() as u8;
+        let day = local_dt.day() as u8;
+        let hour = local_dt.hour() as u8;
+        let minute = local_dt.minute() as u8;
+        let second = local_dt.second() as u8;
 
         // 3. Create icu_calendar::DateTime using the ISO calendar
         let icu_dt =
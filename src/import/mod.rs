@@ -0,0 +1,575 @@
+//! Imports of the CSV exports of other expense-sharing apps, sent with
+//! `/import`.
+//!
+//! Every format is read into the same [`Import`]: the members of the group as
+//! named by the app, expenses with a single payer and fixed shares, transfers,
+//! and the rows that couldn't be turned into either. Members are matched to
+//! travelers with [`MemberMapping`]s before anything is written.
+
+pub mod splitwise;
+pub mod tricount;
+
+use crate::{errors::ImportError, timezone, traveler::Name};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use jiff::tz::TimeZone;
+use rust_decimal::prelude::*;
+use serde::Serialize;
+use std::{str::FromStr, sync::Arc};
+use strum::AsRefStr;
+use surrealdb::{
+    RecordId, Surreal,
+    engine::any::Any,
+    sql::statements::{BeginStatement, CommitStatement},
+};
+use teloxide::types::ChatId;
+
+/// Formats of the date and time of the rows, tried in order. Dates without a
+/// time are read as midnight.
+const DATE_TIME_FORMATS: [&str; 6] = [
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M:%S",
+    "%d/%m/%Y %H:%M:%S",
+    "%d/%m/%Y %H:%M",
+    "%d.%m.%Y %H:%M",
+];
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%d/%m/%Y", "%d.%m.%Y"];
+
+/// Apps whose CSV exports can be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr)]
+pub enum ImportFormat {
+    Splitwise,
+    Tricount,
+}
+
+/// Share of an imported expense owed by a member.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedShare {
+    pub member: String,
+    pub amount: Decimal,
+}
+
+/// An expense paid by a single member.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedExpense {
+    pub description: String,
+    pub amount: Decimal,
+    pub occurred_at: DateTime<Utc>,
+    pub payer: String,
+    pub shares: Vec<ImportedShare>,
+}
+
+/// Money sent by a member to another one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedTransfer {
+    pub sender: String,
+    pub receiver: String,
+    pub amount: Decimal,
+    pub occurred_at: DateTime<Utc>,
+    /// Line of the file, to report the transfer if it is skipped later.
+    pub line: u64,
+}
+
+/// Why a row couldn't be imported.
+#[derive(Debug, Clone, PartialEq, Eq, AsRefStr)]
+#[strum(serialize_all = "kebab-case")]
+pub enum SkipReason {
+    /// An amount, date or member is missing or can't be read.
+    Invalid,
+    /// The row is in a currency other than the one of the chat.
+    OtherCurrency(String),
+    /// The expense was paid by several members.
+    SeveralPayers,
+    /// No member paid the expense.
+    NoPayer,
+    /// The kind of the row, such as an income, has no equivalent.
+    Unsupported,
+    /// The sender and the receiver of the transfer are matched to the same
+    /// traveler.
+    SelfTransfer,
+}
+
+/// A row that couldn't be imported, by line of the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedRow {
+    pub line: u64,
+    pub reason: SkipReason,
+}
+
+/// A row of an export, once read.
+enum Row {
+    Expense(ImportedExpense),
+    Transfer(ImportedTransfer),
+    /// Rows without data, such as blank lines and totals.
+    Ignored,
+}
+
+/// The columns of a recognized export.
+enum Columns {
+    Splitwise(splitwise::Columns),
+    Tricount(tricount::Columns),
+}
+
+impl Columns {
+    fn from_headers(headers: &[String]) -> Option<Self> {
+        splitwise::Columns::from_headers(headers)
+            .map(Self::Splitwise)
+            .or_else(|| tricount::Columns::from_headers(headers).map(Self::Tricount))
+    }
+
+    fn format(&self) -> ImportFormat {
+        match self {
+            Self::Splitwise(_) => ImportFormat::Splitwise,
+            Self::Tricount(_) => ImportFormat::Tricount,
+        }
+    }
+
+    fn members(&self) -> &[String] {
+        match self {
+            Self::Splitwise(columns) => &columns.members,
+            Self::Tricount(columns) => &columns.members,
+        }
+    }
+
+    fn read_row(
+        &self,
+        record: &csv::StringRecord,
+        currency: &str,
+        tz: &TimeZone,
+    ) -> Result<Row, SkipReason> {
+        match self {
+            Self::Splitwise(columns) => columns.read_row(record, currency, tz),
+            Self::Tricount(columns) => columns.read_row(record, currency, tz),
+        }
+    }
+}
+
+/// Traveler a member of the imported group is imported as.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberMapping {
+    pub member: String,
+    pub traveler: Name,
+    /// Whether the traveler is created by the import.
+    pub create: bool,
+}
+
+/// Everything read from an export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Import {
+    pub format: ImportFormat,
+    /// Members involved in the imported expenses and transfers, in the order
+    /// of the file.
+    pub members: Vec<String>,
+    pub expenses: Vec<ImportedExpense>,
+    pub transfers: Vec<ImportedTransfer>,
+    pub skipped: Vec<SkippedRow>,
+}
+
+#[derive(Serialize)]
+struct ShareRow {
+    traveler: Name,
+    amount: Decimal,
+}
+
+#[derive(Serialize)]
+struct ExpenseRow {
+    description: String,
+    amount: Decimal,
+    occurred_at: DateTime<Utc>,
+    payer: Name,
+    shares: Vec<ShareRow>,
+}
+
+#[derive(Serialize)]
+struct TransferRow {
+    sender: Name,
+    receiver: Name,
+    amount: Decimal,
+    timestamp_utc: DateTime<Utc>,
+}
+
+/// Reads a date, with or without the time, in the time zone `tz`.
+fn parse_datetime(text: &str, tz: &TimeZone) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    let datetime = DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| {
+            DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })?;
+    timezone::to_utc(datetime, tz)
+}
+
+/// Reads an amount, with either a dot or a comma as the decimal separator.
+/// Empty cells are read as zero.
+fn parse_amount(text: &str) -> Option<Decimal> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Some(Decimal::ZERO);
+    }
+    if text.contains('.') {
+        Decimal::from_str(text).ok()
+    } else {
+        Decimal::from_str(&text.replace(',', ".")).ok()
+    }
+}
+
+/// Checks that a row in `row_currency` can be imported in a chat using
+/// `currency`. Rows without a currency are taken to be in the chat one.
+fn check_currency(row_currency: &str, currency: &str) -> Result<(), SkipReason> {
+    let row_currency = row_currency.trim();
+    if row_currency.is_empty() || row_currency.eq_ignore_ascii_case(currency) {
+        Ok(())
+    } else {
+        Err(SkipReason::OtherCurrency(row_currency.to_owned()))
+    }
+}
+
+impl Import {
+    /// Reads the CSV export of one of the [`ImportFormat`]s, recognized by
+    /// its headers. Amounts must be in `currency` and dates are read in the
+    /// time zone `tz`.
+    pub fn from_csv(content: &[u8], currency: &str, tz: &TimeZone) -> Result<Self, ImportError> {
+        let content = content
+            .strip_prefix("\u{feff}".as_bytes())
+            .unwrap_or(content);
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(content);
+        let headers = reader
+            .headers()
+            .map_err(|err| {
+                tracing::warn!("Invalid import: {err}");
+                ImportError::Invalid
+            })?
+            .iter()
+            .map(|header| header.trim().to_owned())
+            .collect::<Vec<_>>();
+        let columns = Columns::from_headers(&headers).ok_or(ImportError::UnknownFormat)?;
+
+        let mut expenses = Vec::new();
+        let mut transfers = Vec::new();
+        let mut skipped = Vec::new();
+        for record in reader.records() {
+            let row =
+                record.map_err(|err| (err.position().map(|pos| pos.line()), SkipReason::Invalid));
+            let row = row.and_then(|record| {
+                let line = record.position().map(|pos| pos.line());
+                columns
+                    .read_row(&record, currency, tz)
+                    .map_err(|reason| (line, reason))
+            });
+            match row {
+                Ok(Row::Expense(expense)) => expenses.push(expense),
+                Ok(Row::Transfer(transfer)) => transfers.push(transfer),
+                Ok(Row::Ignored) => {}
+                Err((line, reason)) => skipped.push(SkippedRow {
+                    line: line.unwrap_or_default(),
+                    reason,
+                }),
+            }
+        }
+
+        let involved = |member: &String| {
+            expenses.iter().any(|expense: &ImportedExpense| {
+                expense.payer == *member
+                    || expense.shares.iter().any(|share| share.member == *member)
+            }) || transfers.iter().any(|transfer: &ImportedTransfer| {
+                transfer.sender == *member || transfer.receiver == *member
+            })
+        };
+        let members = columns
+            .members()
+            .iter()
+            .filter(|member| involved(member))
+            .cloned()
+            .collect();
+        Ok(Self {
+            format: columns.format(),
+            members,
+            expenses,
+            transfers,
+            skipped,
+        })
+    }
+
+    /// Checks that every member is matched to a traveler in `mappings`.
+    pub fn check_mappings(&self, mappings: &[MemberMapping]) -> Result<(), ImportError> {
+        match self
+            .members
+            .iter()
+            .find(|member| !mappings.iter().any(|mapping| mapping.member == **member))
+        {
+            Some(member) => Err(ImportError::UnmappedMember(member.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Skips the transfers between members matched to the same traveler in
+    /// `mappings`, which would send money to oneself.
+    pub fn skip_self_transfers(&mut self, mappings: &[MemberMapping]) {
+        let traveler_of = |member: &str| {
+            mappings
+                .iter()
+                .find(|mapping| mapping.member == member)
+                .map(|mapping| mapping.traveler.to_lowercase())
+        };
+        let (self_transfers, transfers): (Vec<_>, Vec<_>) = std::mem::take(&mut self.transfers)
+            .into_iter()
+            .partition(|transfer| {
+                traveler_of(&transfer.sender)
+                    .is_some_and(|sender| Some(sender) == traveler_of(&transfer.receiver))
+            });
+        self.transfers = transfers;
+        if self_transfers.is_empty() {
+            return;
+        }
+        self.skipped
+            .extend(self_transfers.into_iter().map(|transfer| SkippedRow {
+                line: transfer.line,
+                reason: SkipReason::SelfTransfer,
+            }));
+        self.skipped.sort_by_key(|row| row.line);
+    }
+
+    /// Adds the imported expenses and transfers to the active travel plan of
+    /// a given chat in a single transaction, creating the travelers marked as
+    /// such in `mappings` and updating the net positions of the travelers.
+    /// Every member must be matched in `mappings`, see
+    /// [`Self::check_mappings`]. The debts must be updated afterwards.
+    pub async fn db_import(
+        &self,
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        mappings: &[MemberMapping],
+    ) -> Result<(), surrealdb::Error> {
+        use crate::{
+            chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+            debt::NET,
+            expense::{
+                AMOUNT as EXPENSE_AMOUNT, DESCRIPTION, NUMBER as EXPENSE_NUMBER, OCCURRED_AT,
+                TABLE as EXPENSE_TB, TRIP as EXPENSE_TRIP,
+            },
+            paid_for::TABLE as PAID_FOR_TB,
+            split::{AMOUNT as SPLIT_AMOUNT, TABLE as SPLIT_TB},
+            transferred_to::{
+                AMOUNT as TRANSFER_AMOUNT, IN as TRANSFER_IN, NUMBER as TRANSFER_NUMBER,
+                TABLE as TRANSFERRED_TO_TB, TIMESTAMP_UTC,
+            },
+            traveler::{NAME, NUMBER, TABLE as TRAVELER_TB, TRIP},
+        };
+        const NEW_TRAVELERS: &str = "new_travelers";
+        const EXPENSES: &str = "expenses";
+        const TRANSFERS: &str = "transfers";
+        const NAME_LOWER: &str = "name_lower";
+
+        let traveler_of = |member: &str| {
+            mappings
+                .iter()
+                .find(|mapping| mapping.member == member)
+                .map(|mapping| mapping.traveler.clone())
+                .unwrap_or_default()
+        };
+        let new_travelers = mappings
+            .iter()
+            .filter(|mapping| mapping.create)
+            .map(|mapping| mapping.traveler.clone())
+            .collect::<Vec<_>>();
+        let expenses = self
+            .expenses
+            .iter()
+            .map(|expense| ExpenseRow {
+                description: expense.description.clone(),
+                amount: expense.amount,
+                occurred_at: expense.occurred_at,
+                payer: traveler_of(&expense.payer),
+                // Members matched to the same traveler share as one
+                shares: expense.shares.iter().fold(Vec::new(), |mut shares, share| {
+                    let traveler = traveler_of(&share.member);
+                    match shares
+                        .iter_mut()
+                        .find(|row: &&mut ShareRow| row.traveler == traveler)
+                    {
+                        Some(row) => row.amount += share.amount,
+                        None => shares.push(ShareRow {
+                            traveler,
+                            amount: share.amount,
+                        }),
+                    }
+                    shares
+                }),
+            })
+            .collect::<Vec<_>>();
+        let transfers = self
+            .transfers
+            .iter()
+            .map(|transfer| TransferRow {
+                sender: traveler_of(&transfer.sender),
+                receiver: traveler_of(&transfer.receiver),
+                amount: transfer.amount,
+                timestamp_utc: transfer.occurred_at,
+            })
+            .collect::<Vec<_>>();
+
+        // Returns the traveler of the trip named as `name`
+        let traveler = |name: &str| {
+            format!(
+                "(SELECT VALUE id FROM {TRAVELER_TB} WHERE {TRIP} = $trip AND {NAME} = {name})[0]"
+            )
+        };
+
+        db.query(BeginStatement::default())
+            .query(format!("LET $trip = ${CHAT_ID}.{ACTIVE_TRIP}"))
+            .query(format!(
+                "FOR $name IN ${NEW_TRAVELERS} {{
+                    LET $max = math::max(
+                        SELECT VALUE {NUMBER} FROM {TRAVELER_TB} WHERE {TRIP} = $trip
+                    ) ?? 0;
+                    CREATE {TRAVELER_TB} CONTENT {{
+                        {TRIP}: $trip,
+                        {NAME}: $name,
+                        {NAME_LOWER}: string::lowercase($name),
+                        {NUMBER}: $max + 1,
+                    }};
+                }}"
+            ))
+            .query(format!(
+                "FOR $expense IN ${EXPENSES} {{
+                    LET $max = math::max(
+                        SELECT VALUE {EXPENSE_NUMBER} FROM {EXPENSE_TB} WHERE {EXPENSE_TRIP} = $trip
+                    ) ?? 0;
+                    LET $record = CREATE ONLY {EXPENSE_TB} CONTENT {{
+                        {EXPENSE_TRIP}: $trip,
+                        {EXPENSE_NUMBER}: $max + 1,
+                        {DESCRIPTION}: $expense.{DESCRIPTION},
+                        {EXPENSE_AMOUNT}: <decimal> $expense.{EXPENSE_AMOUNT},
+                        {OCCURRED_AT}: <datetime> $expense.{OCCURRED_AT},
+                    }};
+                    LET $payer = {payer};
+                    RELATE $payer->{PAID_FOR_TB}->$record;
+                    FOR $share IN $expense.shares {{
+                        LET $debtor = {debtor};
+                        RELATE $debtor->{SPLIT_TB}->$record
+                        SET {SPLIT_AMOUNT} = <decimal> $share.amount;
+                        UPDATE $payer SET {NET} += <decimal> $share.amount;
+                        UPDATE $debtor SET {NET} -= <decimal> $share.amount;
+                    }};
+                }}",
+                payer = traveler("$expense.payer"),
+                debtor = traveler("$share.traveler"),
+            ))
+            .query(format!(
+                "FOR $transfer IN ${TRANSFERS} {{
+                    LET $max = math::max(
+                        SELECT VALUE {TRANSFER_NUMBER} FROM {TRANSFERRED_TO_TB}
+                        WHERE {TRANSFER_IN}.{TRIP} = $trip
+                    ) ?? 0;
+                    LET $sender = {sender};
+                    LET $receiver = {receiver};
+                    RELATE $sender->{TRANSFERRED_TO_TB}->$receiver SET
+                        {TRANSFER_NUMBER} = $max + 1,
+                        {TRANSFER_AMOUNT} = <decimal> $transfer.{TRANSFER_AMOUNT},
+                        {TIMESTAMP_UTC} = <datetime> $transfer.{TIMESTAMP_UTC};
+                    UPDATE $sender SET {NET} += <decimal> $transfer.{TRANSFER_AMOUNT};
+                    UPDATE $receiver SET {NET} -= <decimal> $transfer.{TRANSFER_AMOUNT};
+                }}",
+                sender = traveler("$transfer.sender"),
+                receiver = traveler("$transfer.receiver"),
+            ))
+            .query(CommitStatement::default())
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+            .bind((NEW_TRAVELERS, new_travelers))
+            .bind((EXPENSES, expenses))
+            .bind((TRANSFERS, transfers))
+            .await?
+            .check()
+            .map(|_| {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_format() {
+        let tz = TimeZone::UTC;
+        assert_eq!(
+            Import::from_csv(b"Hello, world!", "EUR", &tz),
+            Err(ImportError::UnknownFormat)
+        );
+        assert_eq!(
+            Import::from_csv(b"Date,Description,Cost\n2024-05-01,Dinner,10", "EUR", &tz),
+            Err(ImportError::UnknownFormat)
+        );
+    }
+
+    #[test]
+    fn amounts_and_dates() {
+        assert_eq!(parse_amount("12.50"), Some(Decimal::new(1250, 2)));
+        assert_eq!(parse_amount("12,50"), Some(Decimal::new(1250, 2)));
+        assert_eq!(parse_amount(" "), Some(Decimal::ZERO));
+        assert_eq!(parse_amount("twelve"), None);
+
+        let tz = TimeZone::get("Europe/Rome").unwrap();
+        let expected = DateTime::parse_from_rfc3339("2024-05-01T16:30:00Z")
+            .unwrap()
+            .to_utc();
+        assert_eq!(parse_datetime("2024-05-01 18:30", &tz), Some(expected));
+        assert_eq!(parse_datetime("01/05/2024 18:30:00", &tz), Some(expected));
+        assert_eq!(parse_datetime("yesterday", &tz), None);
+    }
+
+    #[test]
+    fn self_transfers() {
+        let transfer = |sender: &str, receiver: &str, line| ImportedTransfer {
+            sender: sender.to_owned(),
+            receiver: receiver.to_owned(),
+            amount: Decimal::TEN,
+            occurred_at: DateTime::UNIX_EPOCH,
+            line,
+        };
+        let mapping = |member: &str, traveler: &str| MemberMapping {
+            member: member.to_owned(),
+            traveler: Name::from_str(traveler).unwrap(),
+            create: false,
+        };
+        let mut import = Import {
+            format: ImportFormat::Splitwise,
+            members: vec!["Al".to_owned(), "Alice".to_owned(), "Bob".to_owned()],
+            expenses: Vec::new(),
+            transfers: vec![transfer("Al", "Bob", 2), transfer("Al", "Alice", 3)],
+            skipped: vec![SkippedRow {
+                line: 4,
+                reason: SkipReason::NoPayer,
+            }],
+        };
+
+        let mut mappings = vec![mapping("Al", "Alice"), mapping("Alice", "alice")];
+        assert_eq!(
+            import.check_mappings(&mappings),
+            Err(ImportError::UnmappedMember("Bob".to_owned()))
+        );
+        mappings.push(mapping("Bob", "Bob"));
+        assert_eq!(import.check_mappings(&mappings), Ok(()));
+
+        import.skip_self_transfers(&mappings);
+        assert_eq!(import.transfers, [transfer("Al", "Bob", 2)]);
+        assert_eq!(
+            import.skipped,
+            [
+                SkippedRow {
+                    line: 3,
+                    reason: SkipReason::SelfTransfer,
+                },
+                SkippedRow {
+                    line: 4,
+                    reason: SkipReason::NoPayer,
+                },
+            ]
+        );
+    }
+}
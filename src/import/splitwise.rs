@@ -0,0 +1,229 @@
+//! Splitwise exports: one row per expense or payment, with the columns
+//! `Date`, `Description`, `Category`, `Cost` and `Currency` followed by a
+//! column per member holding what the member paid minus what they owe.
+//! The export ends with a `Total balance` row.
+
+use super::{
+    ImportedExpense, ImportedShare, ImportedTransfer, Row, SkipReason, check_currency,
+    parse_amount, parse_datetime,
+};
+use jiff::tz::TimeZone;
+use rust_decimal::prelude::*;
+
+const HEADERS: [&str; 5] = ["Date", "Description", "Category", "Cost", "Currency"];
+const PAYMENT_CATEGORY: &str = "Payment";
+const TOTAL_BALANCE: &str = "Total balance";
+
+pub(super) struct Columns {
+    pub(super) members: Vec<String>,
+}
+
+impl Columns {
+    pub(super) fn from_headers(headers: &[String]) -> Option<Self> {
+        let matches = headers.len() > HEADERS.len()
+            && HEADERS
+                .iter()
+                .zip(headers)
+                .all(|(expected, header)| header.eq_ignore_ascii_case(expected));
+        matches.then(|| Self {
+            members: headers[HEADERS.len()..].to_vec(),
+        })
+    }
+
+    pub(super) fn read_row(
+        &self,
+        record: &csv::StringRecord,
+        currency: &str,
+        tz: &TimeZone,
+    ) -> Result<Row, SkipReason> {
+        let field = |index: usize| record.get(index).unwrap_or("").trim();
+        let description = field(1);
+        if record.iter().all(|value| value.trim().is_empty())
+            || description.eq_ignore_ascii_case(TOTAL_BALANCE)
+        {
+            return Ok(Row::Ignored);
+        }
+        check_currency(field(4), currency)?;
+        let occurred_at = parse_datetime(field(0), tz).ok_or(SkipReason::Invalid)?;
+        let cost = parse_amount(field(3))
+            .filter(|cost| cost.is_sign_positive() && !cost.is_zero())
+            .ok_or(SkipReason::Invalid)?;
+        let balances = self
+            .members
+            .iter()
+            .enumerate()
+            .map(|(index, member)| {
+                parse_amount(field(HEADERS.len() + index)).map(|balance| (member, balance))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(SkipReason::Invalid)?;
+        let mut creditors = balances
+            .iter()
+            .filter(|(_, balance)| balance.is_sign_positive() && !balance.is_zero());
+
+        if field(2).eq_ignore_ascii_case(PAYMENT_CATEGORY) {
+            let (Some((sender, sent)), None) = (creditors.next(), creditors.next()) else {
+                return Err(SkipReason::Invalid);
+            };
+            let mut debtors = balances
+                .iter()
+                .filter(|(_, balance)| balance.is_sign_negative() && !balance.is_zero());
+            let (Some((receiver, received)), None) = (debtors.next(), debtors.next()) else {
+                return Err(SkipReason::Invalid);
+            };
+            if *sent != -*received {
+                return Err(SkipReason::Invalid);
+            }
+            return Ok(Row::Transfer(ImportedTransfer {
+                sender: (*sender).clone(),
+                receiver: (*receiver).clone(),
+                amount: *sent,
+                occurred_at,
+                line: record.position().map_or(0, |pos| pos.line()),
+            }));
+        }
+
+        let (payer, paid) = match (creditors.next(), creditors.next()) {
+            (Some(creditor), None) => *creditor,
+            (None, _) => return Err(SkipReason::NoPayer),
+            (Some(_), Some(_)) => return Err(SkipReason::SeveralPayers),
+        };
+        let payer_share = cost - paid;
+        if payer_share.is_sign_negative() {
+            return Err(SkipReason::Invalid);
+        }
+        let shares = balances
+            .iter()
+            .map(|(member, balance)| {
+                let amount = if *member == payer {
+                    payer_share
+                } else {
+                    -*balance
+                };
+                ImportedShare {
+                    member: (*member).clone(),
+                    amount,
+                }
+            })
+            .filter(|share| share.amount.is_sign_positive() && !share.amount.is_zero())
+            .collect::<Vec<_>>();
+        if shares.iter().map(|share| share.amount).sum::<Decimal>() != cost {
+            return Err(SkipReason::Invalid);
+        }
+        Ok(Row::Expense(ImportedExpense {
+            description: description.to_owned(),
+            amount: cost,
+            occurred_at,
+            payer: payer.clone(),
+            shares,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::import::{
+        Import, ImportFormat, ImportedShare, ImportedTransfer, SkipReason, SkippedRow,
+    };
+    use chrono::DateTime;
+    use indoc::indoc;
+    use jiff::tz::TimeZone;
+    use rust_decimal::Decimal;
+
+    const EXPORT: &str = indoc! {"
+        Date,Description,Category,Cost,Currency,Alice,Bob,Carol
+        2024-05-01,Dinner,Dining out,90.00,EUR,60.00,-30.00,-30.00
+        2024-05-02,Taxi,Taxi,30.00,EUR,-15.00,-15.00,30.00
+        2024-05-03,Payment,Payment,20.00,EUR,0.00,20.00,-20.00
+        2024-05-04,Hotel,Hotel,200.00,USD,100.00,-100.00,0.00
+        2024-05-05,Tickets,Entertainment,60.00,EUR,10.00,10.00,-20.00
+
+        2024-05-06,Total balance, , ,EUR,45.00,-35.00,-10.00
+    "};
+
+    fn share(member: &str, amount: i64) -> ImportedShare {
+        ImportedShare {
+            member: member.to_owned(),
+            amount: Decimal::from(amount),
+        }
+    }
+
+    #[test]
+    fn read_export() {
+        let import = Import::from_csv(EXPORT.as_bytes(), "EUR", &TimeZone::UTC).unwrap();
+        assert_eq!(import.format, ImportFormat::Splitwise);
+        assert_eq!(import.members, ["Alice", "Bob", "Carol"]);
+
+        assert_eq!(import.expenses.len(), 2);
+        let dinner = &import.expenses[0];
+        assert_eq!(dinner.description, "Dinner");
+        assert_eq!(dinner.amount, Decimal::from(90));
+        assert_eq!(dinner.payer, "Alice");
+        assert_eq!(
+            dinner.occurred_at,
+            DateTime::parse_from_rfc3339("2024-05-01T00:00:00Z")
+                .unwrap()
+                .to_utc()
+        );
+        assert_eq!(
+            dinner.shares,
+            [share("Alice", 30), share("Bob", 30), share("Carol", 30)]
+        );
+        // The payer of the taxi doesn't owe anything
+        let taxi = &import.expenses[1];
+        assert_eq!(taxi.payer, "Carol");
+        assert_eq!(taxi.shares, [share("Alice", 15), share("Bob", 15)]);
+
+        assert_eq!(
+            import.transfers,
+            [ImportedTransfer {
+                sender: "Bob".to_owned(),
+                receiver: "Carol".to_owned(),
+                amount: Decimal::from(20),
+                occurred_at: DateTime::parse_from_rfc3339("2024-05-03T00:00:00Z")
+                    .unwrap()
+                    .to_utc(),
+                line: 4,
+            }]
+        );
+
+        assert_eq!(
+            import.skipped,
+            [
+                SkippedRow {
+                    line: 5,
+                    reason: SkipReason::OtherCurrency("USD".to_owned()),
+                },
+                SkippedRow {
+                    line: 6,
+                    reason: SkipReason::SeveralPayers,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unbalanced_row() {
+        let export = indoc! {"
+            Date,Description,Category,Cost,Currency,Alice,Bob
+            2024-05-01,Dinner,Dining out,90.00,EUR,60.00,-20.00
+            2024-05-02,Gift,General,10.00,EUR,-5.00,-5.00
+        "};
+        let import = Import::from_csv(export.as_bytes(), "EUR", &TimeZone::UTC).unwrap();
+        assert!(import.expenses.is_empty());
+        assert!(import.members.is_empty());
+        assert_eq!(
+            import.skipped,
+            [
+                SkippedRow {
+                    line: 2,
+                    reason: SkipReason::Invalid,
+                },
+                SkippedRow {
+                    line: 3,
+                    reason: SkipReason::NoPayer,
+                },
+            ]
+        );
+    }
+}
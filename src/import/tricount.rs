@@ -0,0 +1,211 @@
+//! Tricount exports: one row per transaction, with the columns `Title`,
+//! `Amount`, `Currency`, the date, `Paid by`, a column `Impacted to <member>`
+//! per member holding their share and the `Transaction type`. Amounts may be
+//! negative, depending on the version of the app.
+
+use super::{
+    ImportedExpense, ImportedShare, ImportedTransfer, Row, SkipReason, check_currency,
+    parse_amount, parse_datetime,
+};
+use jiff::tz::TimeZone;
+use rust_decimal::prelude::*;
+
+const TITLE: &str = "Title";
+const AMOUNT: &str = "Amount";
+const CURRENCY: &str = "Currency";
+const DATE_PREFIX: &str = "Date";
+const PAID_BY: &str = "Paid by";
+const IMPACTED_PREFIX: &str = "Impacted to ";
+const TYPE_SUFFIX: &str = "type";
+const TRANSFER_TYPE: &str = "transfer";
+const BALANCE_TYPE: &str = "balance";
+const INCOME_TYPE: &str = "income";
+
+pub(super) struct Columns {
+    title: usize,
+    amount: usize,
+    currency: usize,
+    date: usize,
+    paid_by: usize,
+    kind: Option<usize>,
+    impacted: Vec<usize>,
+    pub(super) members: Vec<String>,
+}
+
+impl Columns {
+    pub(super) fn from_headers(headers: &[String]) -> Option<Self> {
+        let position = |found: &dyn Fn(&str) -> bool| headers.iter().position(|h| found(h));
+        let (impacted, members) = headers
+            .iter()
+            .enumerate()
+            .filter_map(|(index, header)| {
+                header
+                    .strip_prefix(IMPACTED_PREFIX)
+                    .map(|member| (index, member.trim().to_owned()))
+            })
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+        if members.is_empty() {
+            return None;
+        }
+        Some(Self {
+            title: position(&|h| h.eq_ignore_ascii_case(TITLE))?,
+            amount: position(&|h| h.eq_ignore_ascii_case(AMOUNT))?,
+            currency: position(&|h| h.eq_ignore_ascii_case(CURRENCY))?,
+            date: position(&|h| h.starts_with(DATE_PREFIX))?,
+            paid_by: position(&|h| h.eq_ignore_ascii_case(PAID_BY))?,
+            kind: position(&|h| h.to_lowercase().ends_with(TYPE_SUFFIX)),
+            impacted,
+            members,
+        })
+    }
+
+    pub(super) fn read_row(
+        &self,
+        record: &csv::StringRecord,
+        currency: &str,
+        tz: &TimeZone,
+    ) -> Result<Row, SkipReason> {
+        let field = |index: usize| record.get(index).unwrap_or("").trim();
+        if record.iter().all(|value| value.trim().is_empty()) {
+            return Ok(Row::Ignored);
+        }
+        let kind = self
+            .kind
+            .map(|index| field(index).to_lowercase())
+            .unwrap_or_default();
+        if kind == INCOME_TYPE {
+            return Err(SkipReason::Unsupported);
+        }
+        check_currency(field(self.currency), currency)?;
+        let occurred_at = parse_datetime(field(self.date), tz).ok_or(SkipReason::Invalid)?;
+        let amount = parse_amount(field(self.amount))
+            .map(|amount| amount.abs())
+            .filter(|amount| !amount.is_zero())
+            .ok_or(SkipReason::Invalid)?;
+        let payer = self
+            .members
+            .iter()
+            .find(|member| member.as_str() == field(self.paid_by))
+            .ok_or(SkipReason::Invalid)?;
+        let shares = self
+            .impacted
+            .iter()
+            .zip(&self.members)
+            .map(|(index, member)| {
+                parse_amount(field(*index)).map(|amount| ImportedShare {
+                    member: member.clone(),
+                    amount: amount.abs(),
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(SkipReason::Invalid)?
+            .into_iter()
+            .filter(|share| !share.amount.is_zero())
+            .collect::<Vec<_>>();
+        if shares.iter().map(|share| share.amount).sum::<Decimal>() != amount {
+            return Err(SkipReason::Invalid);
+        }
+
+        if kind.contains(TRANSFER_TYPE) || kind == BALANCE_TYPE {
+            let [share] = shares.as_slice() else {
+                return Err(SkipReason::Invalid);
+            };
+            if share.member == *payer {
+                return Err(SkipReason::Invalid);
+            }
+            return Ok(Row::Transfer(ImportedTransfer {
+                sender: payer.clone(),
+                receiver: share.member.clone(),
+                amount,
+                occurred_at,
+                line: record.position().map_or(0, |pos| pos.line()),
+            }));
+        }
+        Ok(Row::Expense(ImportedExpense {
+            description: field(self.title).to_owned(),
+            amount,
+            occurred_at,
+            payer: payer.clone(),
+            shares,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::import::{
+        Import, ImportFormat, ImportedShare, ImportedTransfer, SkipReason, SkippedRow,
+    };
+    use chrono::DateTime;
+    use indoc::indoc;
+    use jiff::tz::TimeZone;
+    use rust_decimal::Decimal;
+
+    const EXPORT: &str = indoc! {"
+        Title,Amount,Currency,Exchange rate,Amount in default currency,Date & time,Paid by,Impacted to Alice,Impacted to Bob,Impacted to Carol,Transaction type
+        Groceries,-30.00,EUR,1,-30.00,2024-05-01 18:30:00,Alice,-15.00,-15.00,0.00,Normal
+        Refund,-15.00,EUR,1,-15.00,2024-05-02 10:00:00,Bob,-15.00,0.00,0.00,Money transfer
+        Salary,100.00,EUR,1,100.00,2024-05-03 09:00:00,Alice,50.00,50.00,0.00,Income
+        Museum,-20.00,EUR,1,-20.00,not a date,Alice,-10.00,-10.00,0.00,Normal
+    "};
+
+    #[test]
+    fn read_export() {
+        let import = Import::from_csv(EXPORT.as_bytes(), "EUR", &TimeZone::UTC).unwrap();
+        assert_eq!(import.format, ImportFormat::Tricount);
+        // Carol has no share in the imported rows
+        assert_eq!(import.members, ["Alice", "Bob"]);
+
+        assert_eq!(import.expenses.len(), 1);
+        let groceries = &import.expenses[0];
+        assert_eq!(groceries.description, "Groceries");
+        assert_eq!(groceries.amount, Decimal::from(30));
+        assert_eq!(groceries.payer, "Alice");
+        assert_eq!(
+            groceries.occurred_at,
+            DateTime::parse_from_rfc3339("2024-05-01T18:30:00Z")
+                .unwrap()
+                .to_utc()
+        );
+        assert_eq!(
+            groceries.shares,
+            [
+                ImportedShare {
+                    member: "Alice".to_owned(),
+                    amount: Decimal::from(15),
+                },
+                ImportedShare {
+                    member: "Bob".to_owned(),
+                    amount: Decimal::from(15),
+                },
+            ]
+        );
+
+        assert_eq!(
+            import.transfers,
+            [ImportedTransfer {
+                sender: "Bob".to_owned(),
+                receiver: "Alice".to_owned(),
+                amount: Decimal::from(15),
+                occurred_at: DateTime::parse_from_rfc3339("2024-05-02T10:00:00Z")
+                    .unwrap()
+                    .to_utc(),
+                line: 3,
+            }]
+        );
+
+        assert_eq!(
+            import.skipped,
+            [
+                SkippedRow {
+                    line: 4,
+                    reason: SkipReason::Unsupported,
+                },
+                SkippedRow {
+                    line: 5,
+                    reason: SkipReason::Invalid,
+                },
+            ]
+        );
+    }
+}
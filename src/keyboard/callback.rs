@@ -4,6 +4,7 @@ use crate::{
     Context,
     dialogues::pending_command_dialogue::PendingCommandDialogue,
     i18n::{self, Translate, TranslateWithArgs},
+    topics::{InTopic, MessageExt},
 };
 use std::sync::{Arc, Mutex};
 use teloxide::Bot;
@@ -92,7 +93,9 @@ pub async fn handle_callback_prelude(
             ctx.clone(),
             &maplit::hashmap! { i18n::args::PROCESS.into() => process_name.into() },
         );
-        bot.send_message(msg.chat.id, cancel_msg).await?;
+        bot.send_message(msg.chat.id, cancel_msg)
+            .in_topic(msg.topic_id())
+            .await?;
         dialogue.exit().await?;
         return Ok(CallbackAction::Handled);
     }
@@ -5,13 +5,16 @@ mod callback;
 mod confirm;
 mod paginated;
 mod travelers;
+mod trips;
 
 pub use callback::{
     CallbackAction, CallbackConfig, echo_callback_selection, handle_callback_prelude,
 };
 pub use confirm::{ConfirmAnswer, ConfirmConfig, confirmation_keyboard, parse_confirm_answer};
 pub use paginated::{
-    DEFAULT_COLUMNS, DEFAULT_ROWS_PER_PAGE, PaginatedCallbackAction, PaginatedKeyboardConfig,
-    PickerItem, handle_paginated_callback, paginated_keyboard,
+    DEFAULT_COLUMNS, DEFAULT_ROWS_PER_PAGE, PAGE_MARKER, PaginatedCallbackAction,
+    PaginatedKeyboardConfig, PickerItem, handle_paginated_callback, navigation_row,
+    paginated_keyboard,
 };
 pub use travelers::{TravelersKeyboardConfig, travelers_keyboard};
+pub use trips::{TripsKeyboardConfig, trips_keyboard};
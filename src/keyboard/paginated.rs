@@ -9,6 +9,7 @@ use crate::{
     consts::{BACK_LABEL, BLANK_LABEL, NEXT_LABEL},
     dialogues::pending_command_dialogue::PendingCommandDialogue,
     i18n::{self, Translate, TranslateWithArgs},
+    topics::{InTopic, MessageExt},
 };
 use std::sync::{Arc, Mutex};
 use teloxide::Bot;
@@ -138,34 +139,7 @@ pub fn paginated_keyboard(cfg: PaginatedKeyboardConfig<'_>) -> Option<InlineKeyb
 
     // Navigation row (only if more than one page).
     if total_pages > 1 {
-        let mut nav_row = Vec::new();
-        if page > 0 {
-            nav_row.push(InlineKeyboardButton::callback(
-                BACK_LABEL.to_owned(),
-                format!("{prefix}{PAGE_MARKER}:{}", page - 1),
-            ));
-        } else {
-            nav_row.push(InlineKeyboardButton::callback(
-                BLANK_LABEL.to_owned(),
-                noop_callback.to_owned(),
-            ));
-        }
-        nav_row.push(InlineKeyboardButton::callback(
-            format!("{}/{total_pages}", page + 1),
-            noop_callback.to_owned(),
-        ));
-        if page + 1 < total_pages {
-            nav_row.push(InlineKeyboardButton::callback(
-                NEXT_LABEL.to_owned(),
-                format!("{prefix}{PAGE_MARKER}:{}", page + 1),
-            ));
-        } else {
-            nav_row.push(InlineKeyboardButton::callback(
-                BLANK_LABEL.to_owned(),
-                noop_callback.to_owned(),
-            ));
-        }
-        rows.push(nav_row);
+        rows.push(navigation_row(page, total_pages, prefix, noop_callback));
     }
 
     // Action buttons row (if any).
@@ -184,6 +158,47 @@ pub fn paginated_keyboard(cfg: PaginatedKeyboardConfig<'_>) -> Option<InlineKeyb
     Some(InlineKeyboardMarkup::new(rows))
 }
 
+/// Builds the `◀` / `page/total` / `▶` navigation row of a paginated
+/// keyboard.
+///
+/// The arrows carry `{prefix}{PAGE_MARKER}:{page}` callback data; missing
+/// arrows on the first and last page are replaced by blank spacers.
+pub fn navigation_row(
+    page: usize,
+    total_pages: usize,
+    prefix: &str,
+    noop_callback: &str,
+) -> Vec<InlineKeyboardButton> {
+    let mut nav_row = Vec::new();
+    if page > 0 {
+        nav_row.push(InlineKeyboardButton::callback(
+            BACK_LABEL.to_owned(),
+            format!("{prefix}{PAGE_MARKER}:{}", page - 1),
+        ));
+    } else {
+        nav_row.push(InlineKeyboardButton::callback(
+            BLANK_LABEL.to_owned(),
+            noop_callback.to_owned(),
+        ));
+    }
+    nav_row.push(InlineKeyboardButton::callback(
+        format!("{}/{total_pages}", page + 1),
+        noop_callback.to_owned(),
+    ));
+    if page + 1 < total_pages {
+        nav_row.push(InlineKeyboardButton::callback(
+            NEXT_LABEL.to_owned(),
+            format!("{prefix}{PAGE_MARKER}:{}", page + 1),
+        ));
+    } else {
+        nav_row.push(InlineKeyboardButton::callback(
+            BLANK_LABEL.to_owned(),
+            noop_callback.to_owned(),
+        ));
+    }
+    nav_row
+}
+
 /// Result of handling a paginated callback via [`handle_paginated_callback`].
 pub enum PaginatedCallbackAction {
     /// User selected an item; the value (stripped of prefix) is returned.
@@ -225,7 +240,9 @@ pub async fn handle_paginated_callback(
             ctx.clone(),
             &maplit::hashmap! { i18n::args::PROCESS.into() => process_name.into() },
         );
-        bot.send_message(msg.chat.id, cancel_msg).await?;
+        bot.send_message(msg.chat.id, cancel_msg)
+            .in_topic(msg.topic_id())
+            .await?;
         dialogue.exit().await?;
         return Ok(PaginatedCallbackAction::Handled);
     }
@@ -44,11 +44,9 @@ pub async fn travelers_keyboard(
     }
     let items: Vec<PickerItem> = travelers
         .into_iter()
-        .map(|t| {
-            PickerItem {
-                label: t.name.to_string(),
-                value: t.number.to_string(),
-            }
+        .map(|t| PickerItem {
+            label: t.name.to_string(),
+            value: t.number.to_string(),
         })
         .collect();
 
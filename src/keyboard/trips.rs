@@ -0,0 +1,52 @@
+//! Trip-picker inline keyboard.
+
+use crate::{Context, trip::Trip};
+use std::sync::{Arc, Mutex};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::types::{ChatId, InlineKeyboardMarkup};
+
+use super::paginated::{
+    DEFAULT_COLUMNS, DEFAULT_ROWS_PER_PAGE, PaginatedKeyboardConfig, PickerItem, paginated_keyboard,
+};
+
+/// Configuration for building a trip-picker inline keyboard.
+pub struct TripsKeyboardConfig<'a> {
+    /// Database connection.
+    pub db: Arc<Surreal<Any>>,
+    /// Chat to load trips from.
+    pub chat_id: ChatId,
+    /// Callback-data prefix prepended to each trip number.
+    pub prefix: &'a str,
+    /// Full callback data for blank spacer buttons.
+    pub noop_callback: &'a str,
+    /// Shared context for i18n.
+    pub ctx: Arc<Mutex<Context>>,
+}
+
+/// Loads the chat's trips and builds a paginated keyboard with one button
+/// per name (using `prefix` as the callback-data prefix).
+///
+/// Returns `None` if no trips exist or if the DB query fails.
+pub async fn trips_keyboard(config: TripsKeyboardConfig<'_>) -> Option<InlineKeyboardMarkup> {
+    let trips = Trip::db_select(config.db, config.chat_id).await.ok()?;
+    let items: Vec<PickerItem> = trips
+        .into_iter()
+        .map(|t| PickerItem {
+            label: t.name.to_string(),
+            value: t.number.to_string(),
+        })
+        .collect();
+
+    paginated_keyboard(PaginatedKeyboardConfig {
+        items: &items,
+        page: 0,
+        columns: DEFAULT_COLUMNS,
+        rows_per_page: DEFAULT_ROWS_PER_PAGE,
+        prefix: config.prefix,
+        cancel_callback: "",
+        noop_callback: config.noop_callback,
+        action_buttons: &[],
+        show_cancel: false,
+        ctx: config.ctx,
+    })
+}
@@ -10,21 +10,32 @@ mod macros;
 #[cfg(test)]
 mod tests;
 
+mod audit;
+mod backup;
 mod balance;
+mod chart;
 mod commands;
 mod consts;
 mod db;
 mod debt;
+mod debt_graph;
 mod dialogues;
 mod errors;
 mod expense_details;
+mod export;
+mod filter;
 mod i18n;
+mod import;
 pub(crate) mod keyboard;
 mod money_wrapper;
 mod relationships;
+mod report;
 mod settings;
+mod statement;
 mod stats;
 mod tables;
+mod timezone;
+mod topics;
 mod transfer;
 
 use chat::Chat;
@@ -41,14 +52,20 @@ use dialogues::pending_command_dialogue::{
     delete_expense::{self as pending_delete_expense},
     delete_transfer::{self as pending_delete_transfer},
     delete_traveler::{self as pending_delete_traveler},
+    import::{self as pending_import},
     list_expenses::{self as pending_list_expenses},
+    new_trip::{self as pending_new_trip},
+    rename_trip::{self as pending_rename_trip},
+    restore::{self as pending_restore},
     set_currency::{self as pending_set_currency},
     set_language::{self as pending_set_language},
+    set_timezone::{self as pending_set_timezone},
     show_expense::{self as pending_show_expense},
     transfer::{self as pending_transfer},
 };
 use dialogues::storage::{self as dialogue_storage, DialogueRegistry, DialogueStorages};
 use dptree::{case, deps};
+use jiff::tz::TimeZone;
 use macro_rules_attribute::apply;
 use settings::{Logging, SETTINGS};
 use std::collections::HashSet;
@@ -59,6 +76,7 @@ use teloxide::{
     prelude::*,
     types::{BotCommandScope, Recipient},
 };
+use topics::{DialogueKey, MessageExt};
 use tracing::Level;
 use tracing_appender::rolling::daily;
 use tracing_subscriber::{
@@ -77,6 +95,7 @@ pub type HandlerResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
 pub struct Context {
     langid: LanguageIdentifier,
     currency: String,
+    timezone: TimeZone,
 }
 
 impl Default for Context {
@@ -84,6 +103,7 @@ impl Default for Context {
         Self {
             langid: SETTINGS.i18n.default_locale.clone(),
             currency: SETTINGS.i18n.default_currency.clone(),
+            timezone: TimeZone::UTC,
         }
     }
 }
@@ -94,6 +114,13 @@ pub struct Args {
     /// Profile to use
     #[arg(short, long)]
     profile: Option<String>,
+    /// Audit the ledger of every chat, log the problems found and exit
+    /// instead of starting the bot
+    #[arg(long)]
+    audit: bool,
+    /// Repair the problems found by the audit that can be fixed safely
+    #[arg(long, requires = "audit")]
+    repair: bool,
 }
 
 pub static ARGS: LazyLock<Args> = LazyLock::new(Args::parse);
@@ -157,6 +184,12 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("Using profile {}", SETTINGS.profile);
     tracing::debug!("Settings: {:#?}", SETTINGS);
 
+    if ARGS.audit {
+        let left = audit::audit_all(db::db().await, ARGS.repair).await?;
+        anyhow::ensure!(left == 0, "The audit left {left} problems in the ledgers");
+        return Ok(());
+    }
+
     // Start the bot
     start_bot().await;
 
@@ -171,6 +204,7 @@ async fn start_bot() {
 
     // Initialize the database connection.
     let db_instance = db::db().await;
+    topics::load_topics(db_instance.clone()).await;
 
     // Push the default-locale command list to the global (default) scope so
     // the command menu is available immediately in new chats.
@@ -226,14 +260,49 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
         .filter_command::<Command>()
         // Cancel command
         .branch(case![Command::Cancel].endpoint(cancel))
+        // Commands editing a closed trip, including those starting a
+        // dialogue -> refuse them.
+        .branch(dptree::filter_map_async(closed_trip_guard).endpoint(closed_trip_endpoint))
+        // CloseTrip command -> refuse if any dialogue is already running, so
+        // that no dialogue can edit the trip after it is closed.
+        .branch(case![Command::CloseTrip].branch(any_dialogue_running_guard()))
+        // SwitchTrip command -> refuse if any dialogue is already running, so
+        // that a dialogue started under one trip cannot save into another.
+        .branch(case![Command::SwitchTrip { name }].branch(any_dialogue_running_guard()))
+        // ReopenTrip command -> admins only.
+        .branch(
+            case![Command::ReopenTrip]
+                .filter_async(
+                    |bot: Bot, msg: Message| async move { !sender_is_admin(bot, msg).await },
+                )
+                .endpoint(admin_only_endpoint),
+        )
         // AddExpense command -> start a new dialogue to add an expense.
         // Refuse if any dialogue is already running.
         .branch(
-            case![Command::AddExpense]
+            case![Command::AddExpense { args }]
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, InMemStorage<AddExpenseState>, AddExpenseState>()
+                .enter_dialogue::<DialogueKey, InMemStorage<AddExpenseState>, AddExpenseState>()
                 .branch(case![AddExpenseState::Start].endpoint(add_expense_dialogue::start)),
         )
+        // NewTrip command without an inline name -> start a dialogue to ask
+        // for the name.
+        .branch(
+            case![Command::NewTrip { name }]
+                .filter(|name: CommandArg<traveler::Name>| name.is_missing())
+                .branch(any_dialogue_running_guard())
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
+                .branch(case![PendingCommandState::Start].endpoint(pending_new_trip::start)),
+        )
+        // RenameTrip command without an inline name -> start a dialogue to
+        // ask for the new name.
+        .branch(
+            case![Command::RenameTrip { name }]
+                .filter(|name: CommandArg<traveler::Name>| name.is_missing())
+                .branch(any_dialogue_running_guard())
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
+                .branch(case![PendingCommandState::Start].endpoint(pending_rename_trip::start)),
+        )
         // AddTraveler command without an inline name -> start a dialogue to
         // ask for the name. If a name was supplied inline, this branch is
         // skipped and the message falls through to `commands_handler`.
@@ -242,7 +311,7 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
             case![Command::AddTraveler { name }]
                 .filter(|name: CommandArg<traveler::Name>| name.is_missing())
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(case![PendingCommandState::Start].endpoint(pending_add_traveler::start)),
         )
         // DeleteTraveler command without an inline name -> start a dialogue
@@ -251,14 +320,14 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
             case![Command::DeleteTraveler { name }]
                 .filter(|name: CommandArg<traveler::Name>| name.is_missing())
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(case![PendingCommandState::Start].endpoint(pending_delete_traveler::start)),
         )
         // DeleteTraveler with inline name -> start confirmation dialogue.
         .branch(
             case![Command::DeleteTraveler { name }]
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(
                     case![PendingCommandState::Start]
                         .endpoint(pending_delete_traveler::start_confirm),
@@ -269,14 +338,14 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
             case![Command::DeleteExpense { number }]
                 .filter(|number: CommandArg<i64>| number.is_missing())
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(case![PendingCommandState::Start].endpoint(pending_delete_expense::start)),
         )
         // DeleteExpense with inline number -> start confirmation dialogue.
         .branch(
             case![Command::DeleteExpense { number }]
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(
                     case![PendingCommandState::Start]
                         .endpoint(pending_delete_expense::start_confirm),
@@ -287,7 +356,7 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
             case![Command::ShowExpense { number }]
                 .filter(|number: CommandArg<i64>| number.is_missing())
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(case![PendingCommandState::Start].endpoint(pending_show_expense::start)),
         )
         // DeleteTransfer without an inline number -> start dialogue.
@@ -295,14 +364,14 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
             case![Command::DeleteTransfer { number }]
                 .filter(|number: CommandArg<i64>| number.is_missing())
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(case![PendingCommandState::Start].endpoint(pending_delete_transfer::start)),
         )
         // DeleteTransfer with inline number -> start confirmation dialogue.
         .branch(
             case![Command::DeleteTransfer { number }]
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(
                     case![PendingCommandState::Start]
                         .endpoint(pending_delete_transfer::start_confirm),
@@ -313,7 +382,7 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
             case![Command::SetLanguage { langid }]
                 .filter(|langid: CommandArg<LanguageIdentifier>| langid.is_missing())
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(case![PendingCommandState::Start].endpoint(pending_set_language::start)),
         )
         // SetCurrency without an inline currency -> start dialogue.
@@ -321,15 +390,23 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
             case![Command::SetCurrency { currency }]
                 .filter(|currency: CommandArg<String>| currency.is_missing())
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(case![PendingCommandState::Start].endpoint(pending_set_currency::start)),
         )
+        // SetTimezone without an inline time zone -> start dialogue.
+        .branch(
+            case![Command::SetTimezone { timezone }]
+                .filter(|timezone: CommandArg<String>| timezone.is_missing())
+                .branch(any_dialogue_running_guard())
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
+                .branch(case![PendingCommandState::Start].endpoint(pending_set_timezone::start)),
+        )
         // Transfer without inline args -> start multi-step dialogue.
         .branch(
             case![Command::Transfer { args }]
                 .filter(|args: String| args.is_empty())
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(case![PendingCommandState::Start].endpoint(pending_transfer::start)),
         )
         // Transfer with 1 arg (from only) -> start dialogue at AskTo.
@@ -337,7 +414,7 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
             case![Command::Transfer { args }]
                 .filter(|args: String| args.split_whitespace().count() == 1)
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(
                     case![PendingCommandState::Start].endpoint(pending_transfer::start_with_from),
                 ),
@@ -347,7 +424,7 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
             case![Command::Transfer { args }]
                 .filter(|args: String| args.split_whitespace().count() == 2)
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(
                     case![PendingCommandState::Start]
                         .endpoint(pending_transfer::start_with_from_to),
@@ -357,28 +434,42 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
         .branch(
             case![Command::ClearExpenses]
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(case![PendingCommandState::Start].endpoint(pending_clear_expenses::start)),
         )
         // ClearTransfers -> start confirmation dialogue.
         .branch(
             case![Command::ClearTransfers]
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(case![PendingCommandState::Start].endpoint(pending_clear_transfers::start)),
         )
         // ClearTravelers -> start confirmation dialogue.
         .branch(
             case![Command::ClearTravelers]
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(case![PendingCommandState::Start].endpoint(pending_clear_travelers::start)),
         )
+        // Restore -> read the backup file, then start confirmation dialogue.
+        .branch(
+            case![Command::Restore]
+                .branch(any_dialogue_running_guard())
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
+                .branch(case![PendingCommandState::Start].endpoint(pending_restore::start)),
+        )
+        // Import -> read the CSV export, then match its members to travelers.
+        .branch(
+            case![Command::Import]
+                .branch(any_dialogue_running_guard())
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
+                .branch(case![PendingCommandState::Start].endpoint(pending_import::start)),
+        )
         // ClearAll -> start confirmation dialogue.
         .branch(
             case![Command::ClearAll]
                 .branch(any_dialogue_running_guard())
-                .enter_dialogue::<Message, PendingCommandStorage, PendingCommandState>()
+                .enter_dialogue::<DialogueKey, PendingCommandStorage, PendingCommandState>()
                 .branch(case![PendingCommandState::Start].endpoint(pending_clear_all::start)),
         )
         // Otherwise -> handle other commands
@@ -391,6 +482,7 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
     let message_branch = Update::filter_message()
         .filter(|msg: Message| filter_auth(msg))
         .map_async(update_chat_db) // Create chat record on db if it does not exist yet or update it
+        .map(|msg: Message| msg.dialogue_key())
         .branch(command_branch)
         .branch(add_expense_dialogue_branch)
         .branch(pending_command_dialogue_branch)
@@ -408,8 +500,25 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
                 .map(|m| is_chat_whitelisted(m.chat.id))
                 .unwrap_or(false)
         })
+        .filter_map(|q: CallbackQuery| q.regular_message().map(MessageExt::dialogue_key))
         .branch(pending_command_dialogue::callback_branch());
 
+    // Trip-picker callbacks switch the active trip: refuse them while a
+    // dialogue is running, as for the `/switchtrip` command.
+    let switch_trip_guard_branch = Update::filter_callback_query()
+        .filter(|q: CallbackQuery| {
+            q.data
+                .as_deref()
+                .is_some_and(|d| d.starts_with(SWITCH_TRIP_CALLBACK_PREFIX))
+        })
+        .filter(|q: CallbackQuery| {
+            q.regular_message()
+                .map(|m| is_chat_whitelisted(m.chat.id))
+                .unwrap_or(false)
+        })
+        .filter_map(|q: CallbackQuery| q.regular_message().cloned())
+        .branch(any_dialogue_running_guard());
+
     // Stateless command-keyboard callbacks.
     // These don't require a dialogue — the callback data is self-contained.
     let stateless_callback_branch = Update::filter_callback_query()
@@ -421,6 +530,16 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
         })
         .endpoint(stateless_callback_endpoint);
 
+    // Navigation buttons of lists sent a page at a time.
+    let list_page_callback_branch = Update::filter_callback_query()
+        .filter(|q: CallbackQuery| q.data.as_deref().is_some_and(is_list_page_callback))
+        .filter(|q: CallbackQuery| {
+            q.regular_message()
+                .map(|m| is_chat_whitelisted(m.chat.id))
+                .unwrap_or(false)
+        })
+        .endpoint(list_page_endpoint);
+
     // `/listexpenses` "Filter…" callback: starts a pending-command dialogue
     // to ask for the description.
     let list_expenses_filter_callback_branch = Update::filter_callback_query()
@@ -449,13 +568,16 @@ pub(crate) fn handler_tree() -> UpdateHandler<Box<dyn std::error::Error + Send +
                 .map(|m| is_chat_whitelisted(m.chat.id))
                 .unwrap_or(false)
         })
+        .filter_map(|q: CallbackQuery| q.regular_message().map(MessageExt::dialogue_key))
         .branch(add_expense_dialogue::callback_branch());
 
     dptree::entry()
         .branch(message_branch)
         .branch(dialogue_callback_branch)
         .branch(add_expense_callback_branch)
+        .branch(switch_trip_guard_branch)
         .branch(stateless_callback_branch)
+        .branch(list_page_callback_branch)
         .branch(list_expenses_filter_callback_branch)
 }
 
@@ -489,7 +611,7 @@ async fn update_chat_db(
         Ok(Some(ref chat)) => {
             tracing::debug!("Chat found on db: {chat:?}");
 
-            chat_res = Chat::db_update_last_interaction_utc(db, msg.chat.id).await;
+            chat_res = Chat::db_update_last_interaction_utc(db.clone(), msg.chat.id).await;
             match chat_res {
                 Ok(Some(ref chat)) => {
                     tracing::debug!("Chat updated on db: {chat:?}")
@@ -504,7 +626,7 @@ async fn update_chat_db(
             tracing::debug!("Chat with id {} not found on db. Creating it.", msg.chat.id);
 
             chat_res = Chat::db_create(
-                db,
+                db.clone(),
                 msg.chat.id,
                 &SETTINGS.i18n.default_locale,
                 &SETTINGS.i18n.default_currency,
@@ -526,6 +648,18 @@ async fn update_chat_db(
         }
     }
 
+    // A forum topic keeping its own ledger has its own language and currency
+    topics::sync_topic(db.clone(), &msg).await;
+    let ledger_id = msg.ledger_id();
+    if ledger_id != msg.chat.id {
+        chat_res = Chat::db_update_last_interaction_utc(db, ledger_id).await;
+        match chat_res {
+            Ok(Some(ref chat)) => tracing::debug!("Topic ledger updated on db: {chat:?}"),
+            Ok(None) => tracing::error!("Error while updating topic ledger with id: {ledger_id}"),
+            Err(ref err) => tracing::error!("{err}"),
+        }
+    }
+
     if let Ok(Some(chat)) = chat_res {
         let langid = {
             let mut ctx_guard = ctx.lock().expect("Failed to lock context");
@@ -534,6 +668,7 @@ async fn update_chat_db(
                 .parse()
                 .unwrap_or(SETTINGS.i18n.default_locale.clone());
             ctx_guard.currency = chat.currency;
+            ctx_guard.timezone = timezone::find(&chat.timezone).unwrap_or(TimeZone::UTC);
             ctx_guard.langid.clone()
         };
 
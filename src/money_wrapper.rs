@@ -90,6 +90,16 @@ impl MoneyWrapper<'_> {
         Self::new(amount, currency_code)
     }
 
+    /// Number of decimal places of the currency, or `None` if it is unknown
+    /// and amounts are never rounded.
+    pub fn precision(&self) -> Option<u32> {
+        match self {
+            MoneyWrapper::Iso(money) => Some(money.currency().exponent),
+            MoneyWrapper::Crypto(money) => Some(money.currency().exponent),
+            MoneyWrapper::Other { .. } => None,
+        }
+    }
+
     pub fn round_value(&self) -> Decimal {
         match self {
             MoneyWrapper::Iso(money) => *money
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    RecordId, Surreal,
+    engine::any::Any,
+    sql::statements::{BeginStatement, CommitStatement},
+};
+use teloxide::types::ChatId;
+use travel_rs_derive::Table;
+
+/// A traveler who can't pay another one, e.g. because they don't share a
+/// payment method.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Table)]
+pub struct CannotPay {
+    pub id: RecordId,
+    pub r#in: RecordId,
+    pub out: RecordId,
+}
+
+impl CannotPay {
+    pub async fn db_relate(
+        db: Arc<Surreal<Any>>,
+        debtor: RecordId,
+        creditor: RecordId,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        db.query(BeginStatement::default())
+            .query(format!(
+                "DELETE {TABLE}
+                WHERE {IN} = ${IN} && {OUT} = ${OUT}"
+            ))
+            .query(format!("RELATE ${IN}->{TABLE}->${OUT}"))
+            .query(CommitStatement::default())
+            .bind((IN, debtor))
+            .bind((OUT, creditor))
+            .await
+            .and_then(|mut response| response.take::<Option<Self>>(1))
+    }
+
+    pub async fn db_select(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+    ) -> Result<Vec<Self>, surrealdb::Error> {
+        use crate::{
+            chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+            traveler::TRIP,
+        };
+
+        db.query(format!(
+            "SELECT *
+            FROM {TABLE}
+            WHERE {IN}.{TRIP}.{CHAT_ID} = ${CHAT_ID}.{ACTIVE_TRIP}",
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .await
+        .and_then(|mut response| response.take::<Vec<Self>>(0))
+    }
+
+    /// Returns the preferences the debts of the active trip don't meet,
+    /// because there was no other way to settle.
+    pub async fn db_select_unmet(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+    ) -> Result<Vec<Self>, surrealdb::Error> {
+        use crate::{
+            chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+            owes::{ID as OWES_ID, IN as OWES_IN, OUT as OWES_OUT, TABLE as OWES},
+            traveler::TRIP,
+        };
+
+        db.query(format!(
+            "SELECT *
+            FROM {TABLE}
+            WHERE {IN}.{TRIP}.{CHAT_ID} = ${CHAT_ID}.{ACTIVE_TRIP}
+                && count(
+                    SELECT {OWES_ID}
+                    FROM {OWES}
+                    WHERE {OWES_IN} = $parent.{IN} && {OWES_OUT} = $parent.{OUT}
+                ) > 0",
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .await
+        .and_then(|mut response| response.take::<Vec<Self>>(0))
+    }
+
+    /// Removes the travelers `traveler` can't pay.
+    pub async fn db_delete_by_traveler(
+        db: Arc<Surreal<Any>>,
+        traveler: RecordId,
+    ) -> Result<(), surrealdb::Error> {
+        db.query(format!(
+            "DELETE {TABLE}
+            WHERE {IN} = ${IN}",
+        ))
+        .bind((IN, traveler))
+        .await
+        .map(|_| {})
+    }
+}
@@ -1,4 +1,6 @@
+pub mod cannot_pay;
 pub mod owes;
 pub mod paid_for;
+pub mod settles_via;
 pub mod split;
 pub mod transferred_to;
@@ -37,14 +37,14 @@ impl Owes {
         chat_id: ChatId,
     ) -> Result<Vec<Self>, surrealdb::Error> {
         use crate::{
-            chat::{ID as CHAT_ID, TABLE as CHAT_TB},
-            traveler::CHAT,
+            chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+            traveler::TRIP,
         };
 
         db.query(format!(
             "SELECT *
             FROM {TABLE}
-            WHERE {IN}.{CHAT}.{CHAT_ID} = ${CHAT_ID}",
+            WHERE {IN}.{TRIP}.{CHAT_ID} = ${CHAT_ID}.{ACTIVE_TRIP}",
         ))
         .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
         .await
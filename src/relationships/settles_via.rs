@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use surrealdb::{
+    RecordId, Surreal,
+    engine::any::Any,
+    sql::statements::{BeginStatement, CommitStatement},
+};
+use teloxide::types::ChatId;
+use travel_rs_derive::Table;
+
+/// The traveler another one prefers to settle via, paying and being paid
+/// through them, e.g. the group's banker.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Table)]
+pub struct SettlesVia {
+    pub id: RecordId,
+    pub r#in: RecordId,
+    pub out: RecordId,
+}
+
+impl SettlesVia {
+    pub async fn db_relate(
+        db: Arc<Surreal<Any>>,
+        traveler: RecordId,
+        via: RecordId,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        // A traveler settles via a single other traveler
+        db.query(BeginStatement::default())
+            .query(format!(
+                "DELETE {TABLE}
+                WHERE {IN} = ${IN}"
+            ))
+            .query(format!("RELATE ${IN}->{TABLE}->${OUT}"))
+            .query(CommitStatement::default())
+            .bind((IN, traveler))
+            .bind((OUT, via))
+            .await
+            .and_then(|mut response| response.take::<Option<Self>>(1))
+    }
+
+    pub async fn db_select(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+    ) -> Result<Vec<Self>, surrealdb::Error> {
+        use crate::{
+            chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+            traveler::TRIP,
+        };
+
+        db.query(format!(
+            "SELECT *
+            FROM {TABLE}
+            WHERE {IN}.{TRIP}.{CHAT_ID} = ${CHAT_ID}.{ACTIVE_TRIP}",
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .await
+        .and_then(|mut response| response.take::<Vec<Self>>(0))
+    }
+
+    /// Returns the preferences of the active trip that the current debts don't
+    /// meet, i.e. of the travelers who still owe, or are owed by, someone other
+    /// than the traveler they prefer to settle via.
+    pub async fn db_select_unmet(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+    ) -> Result<Vec<Self>, surrealdb::Error> {
+        use crate::{
+            chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+            owes::{ID as OWES_ID, IN as OWES_IN, OUT as OWES_OUT, TABLE as OWES},
+            traveler::TRIP,
+        };
+
+        db.query(format!(
+            "SELECT *
+            FROM {TABLE}
+            WHERE {IN}.{TRIP}.{CHAT_ID} = ${CHAT_ID}.{ACTIVE_TRIP}
+                && count(
+                    SELECT {OWES_ID}
+                    FROM {OWES}
+                    WHERE
+                        [{OWES_IN}, {OWES_OUT}] CONTAINS $parent.{IN}
+                        && [{OWES_IN}, {OWES_OUT}] CONTAINSNOT $parent.{OUT}
+                ) > 0",
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .await
+        .and_then(|mut response| response.take::<Vec<Self>>(0))
+    }
+
+    /// Removes the traveler `traveler` prefers to settle via.
+    pub async fn db_delete_by_traveler(
+        db: Arc<Surreal<Any>>,
+        traveler: RecordId,
+    ) -> Result<(), surrealdb::Error> {
+        db.query(format!(
+            "DELETE {TABLE}
+            WHERE {IN} = ${IN}",
+        ))
+        .bind((IN, traveler))
+        .await
+        .map(|_| {})
+    }
+}
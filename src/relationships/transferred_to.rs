@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use crate::db::Count;
+use crate::{
+    db::Count,
+    debt::{delete_transfers, with_net_changes},
+};
 use rust_decimal::prelude::*;
 use serde::{Deserialize, Serialize};
 use surrealdb::{
@@ -28,14 +31,21 @@ impl TransferredTo {
         from: RecordId,
         to: RecordId,
     ) -> Result<Option<Self>, surrealdb::Error> {
-        use crate::traveler::CHAT;
+        use crate::traveler::TRIP;
 
-        db.query(BeginStatement::default())
+        let net_changes = travel_rs_core::Transfer {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+        }
+        .net_changes();
+        let query = db
+            .query(BeginStatement::default())
             .query(format!(
                 "LET $max = math::max(
                     SELECT VALUE {NUMBER} 
                     FROM {TABLE} 
-                    WHERE {IN}.{CHAT} = ${IN}.{CHAT}
+                    WHERE {IN}.{TRIP} = ${IN}.{TRIP}
                 ) ?? 0"
             ))
             .query(format!(
@@ -43,7 +53,8 @@ impl TransferredTo {
                 SET 
                     {AMOUNT} = <decimal> ${AMOUNT},
                     {NUMBER} = $max + 1",
-            ))
+            ));
+        with_net_changes(query, net_changes)
             .query(CommitStatement::default())
             .bind((IN, from))
             .bind((OUT, to))
@@ -52,21 +63,65 @@ impl TransferredTo {
             .and_then(|mut response| response.take::<Option<Self>>(1))
     }
 
+    /// Records all the `transfers`, each one as `(amount, from, to)`, in a
+    /// single transaction, numbering them in order.
+    pub async fn db_relate_all(
+        db: Arc<Surreal<Any>>,
+        transfers: Vec<(Decimal, RecordId, RecordId)>,
+    ) -> Result<(), surrealdb::Error> {
+        use crate::traveler::TRIP;
+
+        let mut query = db.query(BeginStatement::default()).query(format!(
+            "LET $max = math::max(
+                SELECT VALUE {NUMBER} 
+                FROM {TABLE} 
+                WHERE {IN}.{TRIP} = ${IN}_0.{TRIP}
+            ) ?? 0"
+        ));
+        let mut net_changes = Vec::new();
+        for (i, (amount, from, to)) in transfers.into_iter().enumerate() {
+            net_changes.extend(
+                travel_rs_core::Transfer {
+                    from: from.clone(),
+                    to: to.clone(),
+                    amount,
+                }
+                .net_changes(),
+            );
+            query = query
+                .query(format!(
+                    "RELATE ${IN}_{i}->{TABLE}->${OUT}_{i}
+                    SET 
+                        {AMOUNT} = <decimal> ${AMOUNT}_{i},
+                        {NUMBER} = $max + {}",
+                    i + 1
+                ))
+                .bind((format!("{IN}_{i}"), from))
+                .bind((format!("{OUT}_{i}"), to))
+                .bind((format!("{AMOUNT}_{i}"), amount));
+        }
+        with_net_changes(query, net_changes)
+            .query(CommitStatement::default())
+            .await?
+            .check()
+            .map(|_| {})
+    }
+
     pub async fn db_count(
         db: Arc<Surreal<Any>>,
         chat_id: ChatId,
         number: i64,
     ) -> Result<Option<Count>, surrealdb::Error> {
         use crate::{
-            chat::{ID as CHAT_ID, TABLE as CHAT_TB},
-            traveler::CHAT,
+            chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+            traveler::TRIP,
         };
 
         db.query(format!(
             "SELECT count()
             FROM {TABLE}
             WHERE 
-                {IN}.{CHAT} = ${CHAT_ID}
+                {IN}.{TRIP}.{CHAT_ID} = ${CHAT_ID}.{ACTIVE_TRIP}
                 && {NUMBER} = ${NUMBER}
             GROUP BY count",
         ))
@@ -81,38 +136,30 @@ impl TransferredTo {
         chat_id: ChatId,
         number: i64,
     ) -> Result<(), surrealdb::Error> {
-        use crate::{
-            chat::{ID as CHAT_ID, TABLE as CHAT_TB},
-            traveler::CHAT,
-        };
+        use crate::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
 
-        db.query(format!(
-            "DELETE {TABLE}
-             WHERE 
-                {IN}.{CHAT} = ${CHAT_ID}
-                && {NUMBER} = ${NUMBER}",
-        ))
-        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
-        .bind((NUMBER, number))
-        .await
-        .map(|_| {})
+        let query = db.query(BeginStatement::default());
+        delete_transfers(query, &format!("{NUMBER} = ${NUMBER}"))
+            .query(CommitStatement::default())
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+            .bind((NUMBER, number))
+            .await?
+            .check()
+            .map(|_| {})
     }
 
     pub async fn db_delete_all(
         db: Arc<Surreal<Any>>,
         chat_id: ChatId,
     ) -> Result<(), surrealdb::Error> {
-        use crate::{
-            chat::{ID as CHAT_ID, TABLE as CHAT_TB},
-            traveler::CHAT,
-        };
+        use crate::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
 
-        db.query(format!(
-            "DELETE {TABLE}
-             WHERE {IN}.{CHAT} = ${CHAT_ID}",
-        ))
-        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
-        .await
-        .map(|_| {})
+        let query = db.query(BeginStatement::default());
+        delete_transfers(query, "true")
+            .query(CommitStatement::default())
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+            .await?
+            .check()
+            .map(|_| {})
     }
 }
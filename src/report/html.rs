@@ -0,0 +1,341 @@
+//! HTML rendering of the [`Report`]: the sections are filled in the
+//! `report.html` template, which has inline CSS and no external assets so
+//! that the file can be opened and shared anywhere.
+//!
+//! Headings and labels come from the Fluent bundle of the chat, and any text
+//! entered by the users is escaped.
+
+use super::Report;
+use crate::{
+    Context,
+    i18n::{
+        self, ToFluentDateTime, Translate, TranslateWithArgs,
+        labels::{
+            COLUMN_AMOUNT, COLUMN_CREDITOR, COLUMN_DATE, COLUMN_DEBTOR, COLUMN_DESCRIPTION,
+            COLUMN_NUMBER, COLUMN_PAYER, COLUMN_TRAVELER, REPORT_AVERAGE_PER_DAY,
+            REPORT_CATEGORIES, REPORT_CATEGORY, REPORT_CONSUMED, REPORT_DAYS, REPORT_EXPENSES,
+            REPORT_EXPENSES_COUNT, REPORT_GENERATED, REPORT_NET, REPORT_NO_EXPENSES, REPORT_PAID,
+            REPORT_PERCENTAGE, REPORT_SETTLED_UP, REPORT_SETTLEMENT, REPORT_SHARES, REPORT_SPENT,
+            REPORT_TIMELINE, REPORT_TITLE, REPORT_TITLE_TRIP, REPORT_TOTALS, REPORT_TRANSFERRED,
+            REPORT_TRAVELERS, REPORT_TRAVELERS_COUNT, REPORT_UNCATEGORIZED,
+        },
+    },
+    money_wrapper::MoneyWrapper,
+};
+use maplit::hashmap;
+use rust_decimal::prelude::*;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use surrealdb::Datetime;
+
+const TEMPLATE: &str = include_str!("report.html");
+const PLACEHOLDER_START: &str = "{{";
+const PLACEHOLDER_END: &str = "}}";
+
+/// A cell of a table of the report.
+enum Cell {
+    Text(String),
+    Amount(String),
+    /// An amount colored by its sign.
+    Net(String, Decimal),
+    /// A bar as long as the given percentage of the cell.
+    Bar(Decimal),
+}
+
+/// Escapes `text` to be written in HTML content or attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Replaces every `{{name}}` placeholder of `template` with its value.
+/// Values are inserted as they are, and not searched for placeholders.
+fn fill(template: &str, values: &HashMap<&str, String>) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(PLACEHOLDER_START) {
+        let after = &rest[start + PLACEHOLDER_START.len()..];
+        let Some(end) = after.find(PLACEHOLDER_END) else {
+            break;
+        };
+        filled.push_str(&rest[..start]);
+        filled.push_str(values.get(&after[..end]).map_or("", String::as_str));
+        rest = &after[end + PLACEHOLDER_END.len()..];
+    }
+    filled.push_str(rest);
+    filled
+}
+
+fn section(title: &str, content: &str) -> String {
+    format!("<section>\n<h2>{}</h2>\n{content}</section>", escape(title))
+}
+
+fn empty(text: &str) -> String {
+    format!("<p class=\"empty\">{}</p>\n", escape(text))
+}
+
+fn table(headers: &[String], rows: impl IntoIterator<Item = Vec<Cell>>) -> String {
+    let mut table = String::from("<table>\n<thead><tr>");
+    for header in headers {
+        table.push_str(&format!("<th>{}</th>", escape(header)));
+    }
+    table.push_str("</tr></thead>\n<tbody>\n");
+    for row in rows {
+        table.push_str("<tr>");
+        for cell in row {
+            table.push_str(&match cell {
+                Cell::Text(text) => format!("<td>{}</td>", escape(&text)),
+                Cell::Amount(amount) => format!("<td class=\"amount\">{}</td>", escape(&amount)),
+                Cell::Net(amount, net) => {
+                    let class = match net.cmp(&Decimal::ZERO) {
+                        std::cmp::Ordering::Greater => " positive",
+                        std::cmp::Ordering::Less => " negative",
+                        std::cmp::Ordering::Equal => "",
+                    };
+                    format!("<td class=\"amount{class}\">{}</td>", escape(&amount))
+                }
+                Cell::Bar(percentage) => format!(
+                    "<td class=\"bar\"><span style=\"width: {}%\"></span></td>",
+                    percentage.round_dp(1)
+                ),
+            });
+        }
+        table.push_str("</tr>\n");
+    }
+    table.push_str("</tbody>\n</table>\n");
+    table
+}
+
+/// Returns `part` as a percentage of `whole`, or zero if `whole` is zero.
+fn percentage(part: Decimal, whole: Decimal) -> Decimal {
+    part.checked_div(whole)
+        .map(|ratio| ratio * Decimal::ONE_HUNDRED)
+        .unwrap_or_default()
+}
+
+/// Renders `report` as an HTML document in the language of the chat.
+pub fn render(report: &Report, ctx: Arc<Mutex<Context>>) -> String {
+    let (langid, currency, tz) = {
+        let ctx_guard = ctx.lock().expect("Failed to lock context");
+        (
+            ctx_guard.langid.to_string(),
+            ctx_guard.currency.clone(),
+            ctx_guard.timezone.clone(),
+        )
+    };
+    let label = |key: &str| key.translate(ctx.clone());
+    let money = |amount: Decimal| MoneyWrapper::new(amount, &currency).to_string();
+    let data = &report.data;
+
+    let title = match &report.trip_name {
+        Some(name) => REPORT_TITLE_TRIP.translate_with_args(
+            ctx.clone(),
+            &hashmap! {i18n::args::NAME.into() => name.to_string().into()},
+        ),
+        None => label(REPORT_TITLE),
+    };
+    let generated = Datetime::from(report.created_at)
+        .to_fluent_datetime(&tz)
+        .map(|datetime| {
+            REPORT_GENERATED.translate_with_args(
+                ctx.clone(),
+                &hashmap! {i18n::args::DATETIME.into() => datetime.into()},
+            )
+        })
+        .unwrap_or_default();
+
+    let spent = report.spent();
+    let timeline = report.timeline();
+    let days = timeline.len();
+    let average = match days {
+        0 => Decimal::ZERO,
+        days => spent / Decimal::from(days),
+    };
+    let totals = [
+        (REPORT_SPENT, money(spent)),
+        (REPORT_EXPENSES_COUNT, data.expenses.len().to_string()),
+        (REPORT_TRANSFERRED, money(report.transferred())),
+        (REPORT_TRAVELERS_COUNT, report.summaries.len().to_string()),
+        (REPORT_DAYS, days.to_string()),
+        (REPORT_AVERAGE_PER_DAY, money(average)),
+    ]
+    .into_iter()
+    .map(|(key, value)| {
+        format!(
+            "<div><dt>{}</dt><dd>{}</dd></div>\n",
+            escape(&label(key)),
+            escape(&value)
+        )
+    })
+    .collect::<String>();
+    let totals = section(
+        &label(REPORT_TOTALS),
+        &format!("<dl class=\"totals\">\n{totals}</dl>\n"),
+    );
+
+    let travelers = section(
+        &label(REPORT_TRAVELERS),
+        &table(
+            &[
+                label(COLUMN_TRAVELER),
+                label(REPORT_PAID),
+                label(REPORT_CONSUMED),
+                label(REPORT_NET),
+            ],
+            report.summaries.iter().map(|summary| {
+                vec![
+                    Cell::Text(summary.traveler_name.to_string()),
+                    Cell::Amount(money(summary.paid)),
+                    Cell::Amount(money(summary.consumed)),
+                    Cell::Net(money(summary.net), summary.net),
+                ]
+            }),
+        ),
+    );
+
+    let categories = report.categories();
+    let categories = if categories.is_empty() {
+        empty(&label(REPORT_NO_EXPENSES))
+    } else {
+        table(
+            &[
+                label(REPORT_CATEGORY),
+                label(COLUMN_AMOUNT),
+                label(REPORT_PERCENTAGE),
+                String::new(),
+            ],
+            categories.into_iter().map(|(category, amount)| {
+                let share = percentage(amount, spent);
+                vec![
+                    Cell::Text(
+                        category.map_or_else(|| label(REPORT_UNCATEGORIZED), |c| format!("#{c}")),
+                    ),
+                    Cell::Amount(money(amount)),
+                    Cell::Amount(format!("{}", share.round_dp(1))),
+                    Cell::Bar(share),
+                ]
+            }),
+        )
+    };
+    let categories = section(&label(REPORT_CATEGORIES), &categories);
+
+    let busiest = timeline
+        .iter()
+        .map(|(_, amount)| *amount)
+        .max()
+        .unwrap_or_default();
+    let timeline = if timeline.is_empty() {
+        empty(&label(REPORT_NO_EXPENSES))
+    } else {
+        table(
+            &[label(COLUMN_DATE), label(COLUMN_AMOUNT), String::new()],
+            timeline.into_iter().map(|(date, amount)| {
+                vec![
+                    Cell::Text(date),
+                    Cell::Amount(money(amount)),
+                    Cell::Bar(percentage(amount, busiest)),
+                ]
+            }),
+        )
+    };
+    let timeline = section(&label(REPORT_TIMELINE), &timeline);
+
+    let settlement = if data.balances.is_empty() {
+        empty(&label(REPORT_SETTLED_UP))
+    } else {
+        table(
+            &[
+                label(COLUMN_DEBTOR),
+                label(COLUMN_CREDITOR),
+                label(COLUMN_AMOUNT),
+            ],
+            data.balances.iter().map(|balance| {
+                vec![
+                    Cell::Text(balance.debtor_name.to_string()),
+                    Cell::Text(balance.creditor_name.to_string()),
+                    Cell::Amount(money(balance.debt)),
+                ]
+            }),
+        )
+    };
+    let settlement = section(&label(REPORT_SETTLEMENT), &settlement);
+
+    let expenses = if data.expenses.is_empty() {
+        empty(&label(REPORT_NO_EXPENSES))
+    } else {
+        table(
+            &[
+                label(COLUMN_NUMBER),
+                label(COLUMN_DATE),
+                label(COLUMN_DESCRIPTION),
+                label(COLUMN_PAYER),
+                label(REPORT_SHARES),
+                label(COLUMN_AMOUNT),
+            ],
+            data.expenses.iter().map(|expense| {
+                let shares = expense
+                    .shares
+                    .iter()
+                    .map(|share| format!("{} {}", share.name, data.amount(share.amount)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                vec![
+                    Cell::Text(expense.number.to_string()),
+                    Cell::Text(data.datetime(&expense.occurred_at)),
+                    Cell::Text(expense.description.clone()),
+                    Cell::Text(expense.payer.as_deref().unwrap_or_default().to_owned()),
+                    Cell::Text(shares),
+                    Cell::Amount(money(expense.amount)),
+                ]
+            }),
+        )
+    };
+    let expenses = section(&label(REPORT_EXPENSES), &expenses);
+
+    fill(
+        TEMPLATE,
+        &hashmap! {
+            "lang" => escape(&langid),
+            "title" => escape(&title),
+            "generated" => escape(&generated),
+            "totals" => totals,
+            "travelers" => travelers,
+            "categories" => categories,
+            "timeline" => timeline,
+            "settlement" => settlement,
+            "expenses" => expenses,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape, fill};
+    use maplit::hashmap;
+
+    #[test]
+    fn escape_text() {
+        assert_eq!(
+            escape(r#"<b>"Tom" & 'Jerry'</b>"#),
+            "&lt;b&gt;&quot;Tom&quot; &amp; &#39;Jerry&#39;&lt;/b&gt;"
+        );
+    }
+
+    #[test]
+    fn fill_template() {
+        let values = hashmap! {"a" => "{{b}}".to_owned(), "b" => "B".to_owned()};
+        assert_eq!(fill("<{{a}}|{{b}}|{{c}}|{{d", &values), "<{{b}}|B||{{d");
+    }
+}
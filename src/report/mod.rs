@@ -0,0 +1,102 @@
+//! Report of a travel plan, sent by `/report` as a self-contained HTML file.
+//!
+//! The report is built from the same [`ExportData`] as `/export`, with the
+//! summary of each traveler shown by `/summary`. Expenses are grouped by
+//! category, the first hashtag of their description, and by day in the time
+//! zone of the chat.
+
+pub mod html;
+
+/// Start of the name of the report files, followed by the date.
+pub const FILE_NAME_PREFIX: &str = "report";
+
+use crate::{export::ExportData, filter, stats::TravelerSummary, traveler::Name, trip::Trip};
+use chrono::{DateTime, Utc};
+use jiff::tz::TimeZone;
+use rust_decimal::prelude::*;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::types::ChatId;
+
+/// Everything shown in the report of a travel plan.
+pub struct Report {
+    /// Name of the trip, if the chat has one.
+    pub trip_name: Option<Name>,
+    pub data: ExportData,
+    pub summaries: Vec<TravelerSummary>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Report {
+    /// Collects the report of the active trip of a given chat.
+    pub async fn db_select(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        currency: String,
+        timezone: TimeZone,
+    ) -> Result<Self, surrealdb::Error> {
+        let trip_name = Trip::db_select_active(db.clone(), chat_id)
+            .await?
+            .map(|trip| trip.name);
+        let (summaries, _) =
+            TravelerSummary::traveler_summaries(db.clone(), chat_id, &currency).await?;
+        let data = ExportData::db_select(db, chat_id, currency, timezone).await?;
+        Ok(Self {
+            trip_name,
+            data,
+            summaries,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Total of the expenses.
+    pub fn spent(&self) -> Decimal {
+        self.data
+            .expenses
+            .iter()
+            .map(|expense| expense.amount)
+            .sum()
+    }
+
+    /// Total of the transfers.
+    pub fn transferred(&self) -> Decimal {
+        self.data
+            .transfers
+            .iter()
+            .map(|transfer| transfer.amount)
+            .sum()
+    }
+
+    /// Returns the total of the expenses of each category, the largest
+    /// first. Expenses without a category are grouped under `None`.
+    pub fn categories(&self) -> Vec<(Option<String>, Decimal)> {
+        let mut totals = HashMap::<Option<String>, Decimal>::new();
+        for expense in &self.data.expenses {
+            *totals
+                .entry(filter::category(&expense.description))
+                .or_default() += expense.amount;
+        }
+        let mut categories = totals.into_iter().collect::<Vec<_>>();
+        categories.sort_by(|(a_name, a_total), (b_name, b_total)| {
+            b_total
+                .cmp(a_total)
+                .then_with(|| a_name.is_none().cmp(&b_name.is_none()))
+                .then_with(|| a_name.cmp(b_name))
+        });
+        categories
+    }
+
+    /// Returns the total of the expenses of each day with any, by date.
+    pub fn timeline(&self) -> Vec<(String, Decimal)> {
+        let mut days = BTreeMap::<String, Decimal>::new();
+        for expense in &self.data.expenses {
+            *days
+                .entry(self.data.date(&expense.occurred_at))
+                .or_default() += expense.amount;
+        }
+        days.into_iter().collect()
+    }
+}
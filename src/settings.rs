@@ -197,6 +197,8 @@ pub struct Bot {
     pub token: HiddenString,
     pub chat_whitelist_source: Option<String>,
     pub chat_whitelist: Option<String>,
+    #[serde(default)]
+    pub topic_ledgers: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -215,6 +217,8 @@ pub struct I18n {
     pub default_currency: String,
     #[serde(default = "I18n::default_popular_currencies")]
     pub popular_currencies: Vec<String>,
+    #[serde(default = "I18n::default_popular_timezones")]
+    pub popular_timezones: Vec<String>,
 }
 
 impl I18n {
@@ -224,6 +228,22 @@ impl I18n {
             .map(String::from)
             .collect()
     }
+
+    fn default_popular_timezones() -> Vec<String> {
+        [
+            "UTC",
+            "Europe/London",
+            "Europe/Rome",
+            "America/New_York",
+            "America/Los_Angeles",
+            "Asia/Tokyo",
+            "Asia/Kolkata",
+            "Australia/Sydney",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect()
+    }
 }
 
 #[derive(Debug, Deserialize)]
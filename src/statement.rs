@@ -0,0 +1,244 @@
+use std::{
+    fmt::{Display, Formatter},
+    sync::Arc,
+};
+
+use crate::{
+    i18n::{self, ToFluentDateTime, Translate, TranslateWithArgs},
+    money_wrapper::MoneyWrapper,
+    traveler::Name,
+};
+use maplit::hashmap;
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+use surrealdb::{Datetime, RecordId, Surreal, engine::any::Any};
+use travel_rs_derive::Table;
+
+/// What a [`StatementEntry`] records, from the point of view of the traveler
+/// the statement belongs to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum StatementEntryKind {
+    /// The traveler paid for an expense.
+    Paid,
+    /// The traveler owes a share of an expense.
+    Share,
+    /// The traveler sent a transfer.
+    Sent,
+    /// The traveler received a transfer.
+    Received,
+}
+
+impl StatementEntryKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            StatementEntryKind::Paid => "paid",
+            StatementEntryKind::Share => "share",
+            StatementEntryKind::Sent => "sent",
+            StatementEntryKind::Received => "received",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Table)]
+pub struct StatementEntry {
+    pub kind: StatementEntryKind,
+    /// Number of the expense or of the transfer.
+    pub number: i64,
+    /// Description of the expense, if any.
+    pub description: Option<String>,
+    /// The other traveler involved: who paid for the expense the share
+    /// belongs to, or the other end of the transfer.
+    pub other_name: Option<Name>,
+    pub amount: Decimal,
+    pub timestamp_utc: Datetime,
+    /// Net position of the traveler after this entry: positive if the
+    /// traveler is owed money, negative if they owe it.
+    #[serde(default)]
+    pub net: Decimal,
+}
+
+impl StatementEntry {
+    /// Returns every expense paid, share owed and transfer sent or received
+    /// by `traveler`, in date order, each with the running net position of
+    /// the traveler.
+    pub async fn statement(
+        db: Arc<Surreal<Any>>,
+        traveler: RecordId,
+    ) -> Result<Vec<Self>, surrealdb::Error> {
+        use crate::{
+            expense::{
+                AMOUNT as EXPENSE_AMOUNT, DESCRIPTION as EXPENSE_DESCRIPTION,
+                NUMBER as EXPENSE_NUMBER, OCCURRED_AT as EXPENSE_OCCURRED_AT,
+            },
+            paid_for::{IN as PAID_FOR_IN, OUT as PAID_FOR_OUT, TABLE as PAID_FOR_TB},
+            split::{AMOUNT as SPLIT_AMOUNT, IN as SPLIT_IN, OUT as SPLIT_OUT, TABLE as SPLIT_TB},
+            transferred_to::{
+                AMOUNT as TRANSFER_AMOUNT, IN as TRANSFER_IN, NUMBER as TRANSFER_NUMBER,
+                OUT as TRANSFER_OUT, TABLE as TRANSFER_TB, TIMESTAMP_UTC as TRANSFER_TIMESTAMP_UTC,
+            },
+            traveler::{NAME as TRAVELER_NAME, TABLE as TRAVELER_TB},
+        };
+        const TRAVELER: &str = "traveler";
+
+        let mut response = db
+            .query(format!(
+                "SELECT
+                    '{paid}' AS {KIND},
+                    {PAID_FOR_OUT}.{EXPENSE_NUMBER} AS {NUMBER},
+                    {PAID_FOR_OUT}.{EXPENSE_DESCRIPTION} AS {DESCRIPTION},
+                    {PAID_FOR_OUT}.{EXPENSE_AMOUNT} AS {AMOUNT},
+                    {PAID_FOR_OUT}.{EXPENSE_OCCURRED_AT} AS {TIMESTAMP_UTC}
+                FROM {PAID_FOR_TB}
+                WHERE {PAID_FOR_IN} = ${TRAVELER}",
+                paid = StatementEntryKind::Paid.as_str(),
+            ))
+            .query(format!(
+                "SELECT
+                    '{share}' AS {KIND},
+                    {SPLIT_OUT}.{EXPENSE_NUMBER} AS {NUMBER},
+                    {SPLIT_OUT}.{EXPENSE_DESCRIPTION} AS {DESCRIPTION},
+                    ({SPLIT_OUT}<-{PAID_FOR_TB}<-{TRAVELER_TB})[0].{TRAVELER_NAME} AS {OTHER_NAME},
+                    {SPLIT_AMOUNT} AS {AMOUNT},
+                    {SPLIT_OUT}.{EXPENSE_OCCURRED_AT} AS {TIMESTAMP_UTC}
+                FROM {SPLIT_TB}
+                WHERE {SPLIT_IN} = ${TRAVELER}",
+                share = StatementEntryKind::Share.as_str(),
+            ))
+            .query(format!(
+                "SELECT
+                    '{sent}' AS {KIND},
+                    {TRANSFER_NUMBER} AS {NUMBER},
+                    {TRANSFER_OUT}.{TRAVELER_NAME} AS {OTHER_NAME},
+                    {TRANSFER_AMOUNT} AS {AMOUNT},
+                    {TRANSFER_TIMESTAMP_UTC} AS {TIMESTAMP_UTC}
+                FROM {TRANSFER_TB}
+                WHERE {TRANSFER_IN} = ${TRAVELER}",
+                sent = StatementEntryKind::Sent.as_str(),
+            ))
+            .query(format!(
+                "SELECT
+                    '{received}' AS {KIND},
+                    {TRANSFER_NUMBER} AS {NUMBER},
+                    {TRANSFER_IN}.{TRAVELER_NAME} AS {OTHER_NAME},
+                    {TRANSFER_AMOUNT} AS {AMOUNT},
+                    {TRANSFER_TIMESTAMP_UTC} AS {TIMESTAMP_UTC}
+                FROM {TRANSFER_TB}
+                WHERE {TRANSFER_OUT} = ${TRAVELER}",
+                received = StatementEntryKind::Received.as_str(),
+            ))
+            .bind((TRAVELER, traveler))
+            .await?;
+
+        let mut entries = Vec::new();
+        for index in 0..4 {
+            entries.extend(response.take::<Vec<Self>>(index)?);
+        }
+        Ok(with_running_net(entries))
+    }
+
+    /// Signed effect of the entry on the net position of the traveler.
+    pub fn change(&self) -> Decimal {
+        match self.kind {
+            StatementEntryKind::Paid | StatementEntryKind::Sent => self.amount,
+            StatementEntryKind::Share | StatementEntryKind::Received => -self.amount,
+        }
+    }
+}
+
+/// Sorts `entries` by date, listing what was paid for an expense before the
+/// shares of it, and fills in the running net position.
+fn with_running_net(mut entries: Vec<StatementEntry>) -> Vec<StatementEntry> {
+    entries.sort_by(|a, b| {
+        a.timestamp_utc
+            .cmp(&b.timestamp_utc)
+            .then(a.kind.cmp(&b.kind))
+            .then(a.number.cmp(&b.number))
+    });
+    let mut net = Decimal::ZERO;
+    for entry in &mut entries {
+        net += entry.change();
+        entry.net = net;
+    }
+    entries
+}
+
+impl Translate for StatementEntry {
+    fn translate_with_indent(
+        &self,
+        ctx: Arc<std::sync::Mutex<crate::Context>>,
+        indent_lvl: usize,
+    ) -> String {
+        let change = self.change();
+        let amount = MoneyWrapper::new_with_context(change, ctx.clone()).to_string();
+        let amount = if change.is_sign_positive() {
+            format!("+{amount}")
+        } else {
+            amount
+        };
+        let net = MoneyWrapper::new_with_context(self.net, ctx.clone());
+        let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
+        i18n::format::FORMAT_STATEMENT_ENTRY.translate_with_args_indent(
+            ctx,
+            &hashmap! {
+                i18n::args::KIND.into() => self.kind.as_str().into(),
+                i18n::args::NUMBER.into() => self.number.into(),
+                i18n::args::DESCRIPTION.into() => self.description.clone().unwrap_or_default().into(),
+                i18n::args::OTHER_TRAVELER_NAME.into() => self.other_name.clone().unwrap_or_default().into(),
+                i18n::args::AMOUNT.into() => amount.into(),
+                i18n::args::NET.into() => net.to_string().into(),
+                i18n::args::DATETIME.into() => self.timestamp_utc.to_fluent_datetime(&tz).unwrap().into(),
+            },
+            indent_lvl,
+        )
+    }
+}
+
+impl Display for StatementEntry {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.translate_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    fn entry(kind: StatementEntryKind, number: i64, amount: i64, day: i64) -> StatementEntry {
+        StatementEntry {
+            kind,
+            number,
+            description: None,
+            other_name: None,
+            amount: amount.into(),
+            timestamp_utc: Datetime::from(DateTime::from_timestamp(day * 86_400, 0).unwrap()),
+            net: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn running_net_follows_date_order() {
+        let entries = with_running_net(vec![
+            entry(StatementEntryKind::Received, 1, 30, 3),
+            entry(StatementEntryKind::Share, 1, 25, 1),
+            entry(StatementEntryKind::Paid, 1, 100, 1),
+            entry(StatementEntryKind::Share, 2, 20, 2),
+            entry(StatementEntryKind::Sent, 1, 5, 4),
+        ]);
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|entry| (entry.kind, entry.number, entry.net))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (StatementEntryKind::Paid, 1, 100.into()),
+                (StatementEntryKind::Share, 1, 75.into()),
+                (StatementEntryKind::Share, 2, 55.into()),
+                (StatementEntryKind::Received, 1, 25.into()),
+                (StatementEntryKind::Sent, 1, 30.into()),
+            ]
+        );
+    }
+}
@@ -1,10 +1,11 @@
-use super::AveragePerDay;
+use super::{AveragePerDay, median};
 use crate::{
     expense::Expense,
+    i18n::indent_multiline,
     i18n::{self, Translate, TranslateWithArgs},
     money_wrapper::MoneyWrapper,
-    i18n::indent_multiline,
 };
+use jiff::tz::TimeZone;
 use maplit::hashmap;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -19,27 +20,52 @@ pub struct ExpenseStats {
     pub expenses_count: i64,
     pub sum: Decimal,
     pub mean: Decimal,
+    /// Median amount of the expenses, filled in by
+    /// [`ExpenseStats::expense_stats`].
+    #[serde(default)]
+    pub median: Decimal,
     pub min_expenses: Vec<Expense>,
     pub max_expenses: Vec<Expense>,
+    /// Filled in by [`ExpenseStats::expense_stats`].
+    #[serde(default)]
     pub average_per_day: Option<AveragePerDay>,
     pub oldest_expense: Option<Expense>,
     pub newest_expense: Option<Expense>,
 }
 
 impl ExpenseStats {
+    /// Days are counted in the time zone `tz`.
     pub async fn expense_stats(
         db: Arc<Surreal<Any>>,
         chat_id: ChatId,
+        tz: &TimeZone,
     ) -> Result<Option<Self>, surrealdb::Error> {
-        use crate::chat::TABLE as CHAT_TB;
+        use crate::{
+            chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB},
+            expense::{AMOUNT, TABLE as EXPENSE_TB, TRIP},
+        };
 
-        db.query(format!(
-            "SELECT *
-            FROM {FN_EXPENSE_STATS}($chat)",
-        ))
-        .bind(("chat", RecordId::from_table_key(CHAT_TB, chat_id.0)))
-        .await
-        .and_then(|mut response| response.take::<Option<Self>>(0))
+        let mut response = db
+            .query(format!(
+                "SELECT *
+                FROM {FN_EXPENSE_STATS}(${CHAT_ID}.{ACTIVE_TRIP})",
+            ))
+            .query(format!(
+                "SELECT VALUE {AMOUNT}
+                FROM {EXPENSE_TB}
+                WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}",
+            ))
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+            .await?;
+        let mut stats = response.take::<Option<Self>>(0)?;
+        if let Some(stats) = stats.as_mut() {
+            stats.median = median(response.take::<Vec<Decimal>>(1)?).unwrap_or_default();
+            stats.average_per_day = stats
+                .oldest_expense
+                .as_ref()
+                .and_then(|oldest| AveragePerDay::new(stats.sum, oldest.occurred_at.clone(), tz));
+        }
+        Ok(stats)
     }
 }
 
@@ -51,6 +77,7 @@ impl Translate for ExpenseStats {
     ) -> String {
         let sum = MoneyWrapper::new_with_context(self.sum, ctx.clone());
         let mean = MoneyWrapper::new_with_context(self.mean, ctx.clone());
+        let median = MoneyWrapper::new_with_context(self.median, ctx.clone());
         let min_expenses = indent_multiline(&self.min_expenses, ctx.clone(), indent_lvl);
         let max_expenses = indent_multiline(&self.max_expenses, ctx.clone(), indent_lvl);
         let average_per_day = self.average_per_day.as_ref().map_or(String::new(), |avg| {
@@ -69,6 +96,7 @@ impl Translate for ExpenseStats {
                 i18n::args::COUNT.into() => self.expenses_count.into(),
                 i18n::args::SUM.into() => sum.to_string().into(),
                 i18n::args::MEAN.into() => mean.to_string().into(),
+                i18n::args::MEDIAN.into() => median.to_string().into(),
                 i18n::args::MIN.into() => min_expenses.into(),
                 i18n::args::MAX.into() => max_expenses.into(),
                 i18n::args::AVERAGE_PER_DAY.into() => average_per_day.into(),
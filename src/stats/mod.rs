@@ -1,21 +1,46 @@
 mod expense_stats;
+mod time_stats;
 mod transfer_stats;
 mod traveler_stats;
+mod traveler_summary;
 
 pub use expense_stats::ExpenseStats;
+pub use time_stats::{Period, TimeStats, median};
 pub use transfer_stats::TransferStats;
 pub use traveler_stats::TravelerStats;
+pub use traveler_summary::TravelerSummary;
 
 use crate::{
     i18n::{self, ToFluentDateTime, Translate, TranslateWithArgs},
     money_wrapper::MoneyWrapper,
+    timezone,
 };
+use jiff::tz::TimeZone;
 use maplit::hashmap;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use strum::{AsRefStr, EnumIter, EnumString};
 use surrealdb::Datetime;
 
+/// Sections of `/showstats`, each sent as its own message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, AsRefStr, EnumIter, EnumString)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum StatsSection {
+    /// Expenses, transfers and travelers, as computed by the database.
+    Overview,
+    /// Spending of each day, and the busiest one.
+    Days,
+    /// Spending of each week.
+    Weeks,
+    /// Spending by day of the week.
+    Weekdays,
+    /// Spending by hour of the day.
+    Hours,
+    /// What each traveler consumed each week.
+    Travelers,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct Stats {
     pub expense_stats: ExpenseStats,
@@ -27,9 +52,11 @@ impl Stats {
     pub async fn stats(
         db: Arc<surrealdb::Surreal<surrealdb::engine::any::Any>>,
         chat_id: teloxide::types::ChatId,
+        tz: &TimeZone,
     ) -> Result<Option<Self>, surrealdb::Error> {
-        if let Some(expense_stats) = ExpenseStats::expense_stats(db.clone(), chat_id).await?
-            && let Some(transfer_stats) = TransferStats::transfer_stats(db.clone(), chat_id).await?
+        if let Some(expense_stats) = ExpenseStats::expense_stats(db.clone(), chat_id, tz).await?
+            && let Some(transfer_stats) =
+                TransferStats::transfer_stats(db.clone(), chat_id, tz).await?
             && let Some(traveler_stats) = TravelerStats::traveler_stats(db.clone(), chat_id).await?
         {
             Ok(Some(Self {
@@ -72,6 +99,33 @@ pub struct AveragePerDay {
     pub now: Datetime,
 }
 
+impl AveragePerDay {
+    /// Spreads `sum` over the days from `oldest_timestamp` to now, both
+    /// included, counted in the time zone `tz`.
+    pub fn new(sum: Decimal, oldest_timestamp: Datetime, tz: &TimeZone) -> Option<Self> {
+        Self::until(sum, oldest_timestamp, chrono::Utc::now().into(), tz)
+    }
+
+    fn until(
+        sum: Decimal,
+        oldest_timestamp: Datetime,
+        now: Datetime,
+        tz: &TimeZone,
+    ) -> Option<Self> {
+        let date = |datetime: &Datetime| {
+            timezone::to_zoned(datetime.clone().into_inner().0, tz).map(|zoned| zoned.date())
+        };
+        let elapsed = date(&oldest_timestamp)?.until(date(&now)?).ok()?;
+        // A backdated expense in the future still counts as one day
+        let days = (elapsed.get_days() + 1).max(1);
+        Some(Self {
+            amount: sum / Decimal::from(days),
+            oldest_timestamp,
+            now,
+        })
+    }
+}
+
 impl Translate for AveragePerDay {
     fn translate_with_indent(
         &self,
@@ -79,14 +133,52 @@ impl Translate for AveragePerDay {
         indent_lvl: usize,
     ) -> String {
         let amount = MoneyWrapper::new_with_context(self.amount, ctx.clone());
+        let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
         i18n::format::FORMAT_AVERAGE_PER_DAY.translate_with_args_indent(
             ctx,
             &hashmap! {
                 i18n::args::AMOUNT.into() => amount.to_string().into(),
-                i18n::args::OLDEST_TIMESTAMP.into() => self.oldest_timestamp.to_fluent_datetime().unwrap().into(),
-                i18n::args::NOW.into() => self.now.to_fluent_datetime().unwrap().into(),
+                i18n::args::OLDEST_TIMESTAMP.into() => self.oldest_timestamp.to_fluent_datetime(&tz).unwrap().into(),
+                i18n::args::NOW.into() => self.now.to_fluent_datetime(&tz).unwrap().into(),
             },
             indent_lvl,
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::AveragePerDay;
+    use crate::timezone;
+    use rust_decimal::Decimal;
+    use surrealdb::Datetime;
+
+    fn datetime(rfc3339: &str) -> Datetime {
+        chrono::DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .to_utc()
+            .into()
+    }
+
+    #[test]
+    fn average_per_day_in_time_zone() {
+        let oldest = datetime("2026-10-18T14:30:00Z");
+        let now = datetime("2026-10-19T15:30:00Z");
+
+        // From the 18th to the 19th in UTC
+        let utc = timezone::find("UTC").unwrap();
+        let average = AveragePerDay::until(90.into(), oldest.clone(), now.clone(), &utc).unwrap();
+        assert_eq!(average.amount, Decimal::from(45));
+
+        // From 23:30 of the 18th to 00:30 of the 20th in Tokyo
+        let tokyo = timezone::find("Asia/Tokyo").unwrap();
+        let average = AveragePerDay::until(90.into(), oldest.clone(), now.clone(), &tokyo).unwrap();
+        assert_eq!(average.amount, Decimal::from(30));
+        assert_eq!(average.oldest_timestamp, oldest);
+        assert_eq!(average.now, now);
+
+        // An expense dated after now counts as one day
+        let average = AveragePerDay::until(90.into(), now.clone(), oldest, &utc).unwrap();
+        assert_eq!(average.amount, Decimal::from(90));
+    }
+}
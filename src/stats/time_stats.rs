@@ -0,0 +1,290 @@
+use crate::{export::ExportedExpense, timezone, traveler::Name};
+use jiff::{ToSpan, civil, tz::TimeZone};
+use rust_decimal::Decimal;
+use std::{collections::BTreeMap, sync::Arc};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::types::ChatId;
+
+/// An expense as counted by the time-based statistics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedExpense {
+    /// When the expense occurred, in the time zone of the chat.
+    pub datetime: civil::DateTime,
+    pub amount: Decimal,
+    /// Share of the expense of each traveler.
+    pub shares: Vec<(Name, Decimal)>,
+}
+
+/// Total and number of the expenses of a period.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Period {
+    pub total: Decimal,
+    pub count: usize,
+}
+
+impl Period {
+    fn add(&mut self, amount: Decimal) {
+        self.total += amount;
+        self.count += 1;
+    }
+}
+
+/// Spending of a trip over time: by day, week, day of the week and hour of
+/// the day, and what each traveler consumed each week.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TimeStats {
+    expenses: Vec<TimedExpense>,
+}
+
+/// Returns the Monday starting the week of `date`.
+fn week_start(date: civil::Date) -> civil::Date {
+    let offset = date.weekday().to_monday_zero_offset();
+    date.checked_sub(i64::from(offset).days()).unwrap_or(date)
+}
+
+/// Returns the middle value of `amounts`, or the mean of the two middle
+/// ones if they are even, or `None` if there are none.
+pub fn median(mut amounts: Vec<Decimal>) -> Option<Decimal> {
+    amounts.sort();
+    let middle = amounts.len() / 2;
+    match amounts.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(amounts[middle]),
+        _ => Some((amounts[middle - 1] + amounts[middle]) / Decimal::TWO),
+    }
+}
+
+impl TimeStats {
+    pub fn new(expenses: Vec<TimedExpense>) -> Self {
+        Self { expenses }
+    }
+
+    /// Collects the expenses of the active trip of a given chat, with their
+    /// dates and times in the time zone `tz`.
+    pub async fn db_select(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        tz: &TimeZone,
+    ) -> Result<Self, surrealdb::Error> {
+        let expenses = ExportedExpense::db_select(db, chat_id)
+            .await?
+            .into_iter()
+            .filter_map(|expense| {
+                let zoned = timezone::to_zoned(expense.occurred_at.into_inner().0, tz)?;
+                Some(TimedExpense {
+                    datetime: zoned.datetime(),
+                    amount: expense.amount,
+                    shares: expense
+                        .shares
+                        .into_iter()
+                        .map(|share| (share.name, share.amount))
+                        .collect(),
+                })
+            })
+            .collect();
+        Ok(Self::new(expenses))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expenses.is_empty()
+    }
+
+    /// Returns the spending of each day with any expense, by date.
+    pub fn days(&self) -> Vec<(civil::Date, Period)> {
+        let mut days = BTreeMap::<civil::Date, Period>::new();
+        for expense in &self.expenses {
+            days.entry(expense.datetime.date())
+                .or_default()
+                .add(expense.amount);
+        }
+        days.into_iter().collect()
+    }
+
+    /// Returns the spending of each week with any expense, by the Monday
+    /// starting it.
+    pub fn weeks(&self) -> Vec<(civil::Date, Period)> {
+        let mut weeks = BTreeMap::<civil::Date, Period>::new();
+        for expense in &self.expenses {
+            weeks
+                .entry(week_start(expense.datetime.date()))
+                .or_default()
+                .add(expense.amount);
+        }
+        weeks.into_iter().collect()
+    }
+
+    /// Returns the spending of each day of the week, from Monday.
+    pub fn weekdays(&self) -> [Period; 7] {
+        let mut weekdays = [Period::default(); 7];
+        for expense in &self.expenses {
+            let weekday = expense.datetime.date().weekday().to_monday_zero_offset();
+            weekdays[weekday as usize].add(expense.amount);
+        }
+        weekdays
+    }
+
+    /// Returns the spending of each hour of the day, from midnight.
+    pub fn hours(&self) -> [Period; 24] {
+        let mut hours = [Period::default(); 24];
+        for expense in &self.expenses {
+            hours[expense.datetime.hour() as usize].add(expense.amount);
+        }
+        hours
+    }
+
+    /// Returns the day with the largest spending, the earliest one if more
+    /// days have the same.
+    pub fn busiest_day(&self) -> Option<(civil::Date, Period)> {
+        self.days().into_iter().reduce(|busiest, day| {
+            if day.1.total > busiest.1.total {
+                day
+            } else {
+                busiest
+            }
+        })
+    }
+
+    /// Returns what each traveler consumed in each of the [`Self::weeks`],
+    /// by traveler name.
+    pub fn traveler_trends(&self) -> Vec<(Name, Vec<Decimal>)> {
+        let weeks = self
+            .weeks()
+            .into_iter()
+            .map(|(week, _)| week)
+            .collect::<Vec<_>>();
+        let mut trends = BTreeMap::<&Name, Vec<Decimal>>::new();
+        for expense in &self.expenses {
+            let Ok(week) = weeks.binary_search(&week_start(expense.datetime.date())) else {
+                continue;
+            };
+            for (name, amount) in &expense.shares {
+                trends
+                    .entry(name)
+                    .or_insert_with(|| vec![Decimal::ZERO; weeks.len()])[week] += amount;
+            }
+        }
+        trends
+            .into_iter()
+            .map(|(name, consumed)| (name.clone(), consumed))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Period, TimeStats, TimedExpense, median};
+    use jiff::civil::{date, datetime};
+    use rust_decimal::Decimal;
+
+    fn expense(
+        datetime: jiff::civil::DateTime,
+        amount: i64,
+        shares: &[(&str, i64)],
+    ) -> TimedExpense {
+        TimedExpense {
+            datetime,
+            amount: amount.into(),
+            shares: shares
+                .iter()
+                .map(|(name, amount)| (name.parse().unwrap(), (*amount).into()))
+                .collect(),
+        }
+    }
+
+    fn period(total: i64, count: usize) -> Period {
+        Period {
+            total: total.into(),
+            count,
+        }
+    }
+
+    /// Saturday 2026-08-01 to Monday 2026-08-10.
+    fn stats() -> TimeStats {
+        TimeStats::new(vec![
+            expense(
+                datetime(2026, 8, 1, 20, 15, 0, 0),
+                60,
+                &[("Alice", 30), ("Bob", 30)],
+            ),
+            expense(datetime(2026, 8, 1, 9, 0, 0, 0), 10, &[("Alice", 10)]),
+            expense(datetime(2026, 8, 3, 20, 45, 0, 0), 20, &[("Bob", 20)]),
+            expense(
+                datetime(2026, 8, 10, 13, 0, 0, 0),
+                70,
+                &[("Alice", 35), ("Bob", 35)],
+            ),
+        ])
+    }
+
+    #[test]
+    fn periods() {
+        let stats = stats();
+        assert_eq!(
+            stats.days(),
+            [
+                (date(2026, 8, 1), period(70, 2)),
+                (date(2026, 8, 3), period(20, 1)),
+                (date(2026, 8, 10), period(70, 1)),
+            ]
+        );
+        // Weeks start on Monday
+        assert_eq!(
+            stats.weeks(),
+            [
+                (date(2026, 7, 27), period(70, 2)),
+                (date(2026, 8, 3), period(20, 1)),
+                (date(2026, 8, 10), period(70, 1)),
+            ]
+        );
+
+        let weekdays = stats.weekdays();
+        assert_eq!(weekdays[0], period(90, 2));
+        assert_eq!(weekdays[5], period(70, 2));
+        assert_eq!(weekdays[6], Period::default());
+
+        let hours = stats.hours();
+        assert_eq!(hours[20], period(80, 2));
+        assert_eq!(hours[9], period(10, 1));
+        assert_eq!(hours.iter().filter(|hour| hour.count > 0).count(), 3);
+    }
+
+    #[test]
+    fn busiest_day() {
+        // The earliest of the days with the same spending
+        assert_eq!(
+            stats().busiest_day(),
+            Some((date(2026, 8, 1), period(70, 2)))
+        );
+        assert_eq!(TimeStats::default().busiest_day(), None);
+    }
+
+    #[test]
+    fn median_expense() {
+        let amounts = stats()
+            .expenses
+            .iter()
+            .map(|expense| expense.amount)
+            .collect();
+        assert_eq!(median(amounts), Some(Decimal::from(40)));
+        assert_eq!(median(vec![3.into(), 1.into(), 2.into()]), Some(2.into()));
+        assert_eq!(median(Vec::new()), None);
+    }
+
+    #[test]
+    fn traveler_trends() {
+        let trends = stats().traveler_trends();
+        assert_eq!(
+            trends,
+            [
+                (
+                    "Alice".parse().unwrap(),
+                    vec![40.into(), 0.into(), 35.into()]
+                ),
+                (
+                    "Bob".parse().unwrap(),
+                    vec![30.into(), 20.into(), 35.into()]
+                ),
+            ]
+        );
+    }
+}
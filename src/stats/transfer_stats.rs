@@ -5,6 +5,7 @@ use crate::{
     money_wrapper::MoneyWrapper,
     transfer::Transfer,
 };
+use jiff::tz::TimeZone;
 use maplit::hashmap;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -21,25 +22,37 @@ pub struct TransferStats {
     pub mean: Decimal,
     pub min_transfers: Vec<Transfer>,
     pub max_transfers: Vec<Transfer>,
+    /// Filled in by [`TransferStats::transfer_stats`].
+    #[serde(default)]
     pub average_per_day: Option<AveragePerDay>,
     pub oldest_transfer: Option<Transfer>,
     pub newest_transfer: Option<Transfer>,
 }
 
 impl TransferStats {
+    /// Days are counted in the time zone `tz`.
     pub async fn transfer_stats(
         db: Arc<Surreal<Any>>,
         chat_id: ChatId,
+        tz: &TimeZone,
     ) -> Result<Option<Self>, surrealdb::Error> {
-        use crate::chat::TABLE as CHAT_TB;
+        use crate::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
-        db.query(format!(
-            "SELECT *
-            FROM {FN_TRANSFER_STATS}($chat)",
-        ))
-        .bind(("chat", RecordId::from_table_key(CHAT_TB, chat_id.0)))
-        .await
-        .and_then(|mut response| response.take::<Option<Self>>(0))
+        let mut stats = db
+            .query(format!(
+                "SELECT *
+                FROM {FN_TRANSFER_STATS}(${CHAT_ID}.{ACTIVE_TRIP})",
+            ))
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+            .await
+            .and_then(|mut response| response.take::<Option<Self>>(0))?;
+        if let Some(stats) = stats.as_mut() {
+            stats.average_per_day = stats
+                .oldest_transfer
+                .as_ref()
+                .and_then(|oldest| AveragePerDay::new(stats.sum, oldest.timestamp_utc.clone(), tz));
+        }
+        Ok(stats)
     }
 }
 
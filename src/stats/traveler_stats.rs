@@ -31,13 +31,13 @@ impl TravelerStats {
         db: Arc<Surreal<Any>>,
         chat_id: ChatId,
     ) -> Result<Option<Self>, surrealdb::Error> {
-        use crate::chat::TABLE as CHAT_TB;
+        use crate::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(format!(
             "SELECT *
-            FROM {FN_TRAVELER_STATS}($chat)",
+            FROM {FN_TRAVELER_STATS}(${CHAT_ID}.{ACTIVE_TRIP})",
         ))
-        .bind(("chat", RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
         .await
         .and_then(|mut response| response.take::<Option<Self>>(0))
     }
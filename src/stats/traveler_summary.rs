@@ -0,0 +1,160 @@
+use crate::{
+    i18n::{self, Translate, TranslateWithArgs},
+    money_wrapper::MoneyWrapper,
+    traveler::Name,
+};
+use maplit::hashmap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use surrealdb::{RecordId, Surreal, engine::any::Any};
+use teloxide::types::ChatId;
+
+const FN_TRAVELER_SUMMARY: &str = "fn::traveler_summary";
+
+/// What a traveler paid and consumed within a trip, and the transfers they
+/// sent and received.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TravelerSummary {
+    pub traveler_name: Name,
+    /// Total of the expenses the traveler paid for.
+    pub paid: Decimal,
+    /// Total of the traveler's shares of the expenses.
+    pub consumed: Decimal,
+    pub sent: Decimal,
+    pub received: Decimal,
+    /// Net position of the traveler, filled in by
+    /// [`TravelerSummary::traveler_summaries`]: positive if the traveler is
+    /// owed money, negative if they owe it.
+    #[serde(default)]
+    pub net: Decimal,
+}
+
+impl TravelerSummary {
+    /// Returns the summary of each traveler in the active trip, with the
+    /// figures rounded to the precision of `currency`. Net positions are
+    /// rounded so that they still add up to zero.
+    ///
+    /// Also returns the sum of the net positions before rounding them, itself
+    /// rounded to the precision of `currency`: it is zero unless the ledger
+    /// is inconsistent.
+    pub async fn traveler_summaries(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        currency: &str,
+    ) -> Result<(Vec<Self>, Decimal), surrealdb::Error> {
+        use crate::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
+
+        let mut summaries = db
+            .query(format!(
+                "SELECT *
+                FROM {FN_TRAVELER_SUMMARY}(${CHAT_ID}.{ACTIVE_TRIP})",
+            ))
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+            .await
+            .and_then(|mut response| response.take::<Vec<Self>>(0))?;
+
+        let nets: Vec<Decimal> = summaries
+            .iter()
+            .map(|s| s.paid - s.consumed + s.sent - s.received)
+            .collect();
+        let total = MoneyWrapper::new(nets.iter().sum(), currency).round_value();
+
+        let precision = MoneyWrapper::new(Decimal::ZERO, currency).precision();
+        let nets = match precision {
+            Some(dp) => round_preserving_sum(&nets, dp),
+            None => nets,
+        };
+        let round = |amount: Decimal| MoneyWrapper::new(amount, currency).round_value();
+        for (summary, net) in summaries.iter_mut().zip(nets) {
+            summary.paid = round(summary.paid);
+            summary.consumed = round(summary.consumed);
+            summary.sent = round(summary.sent);
+            summary.received = round(summary.received);
+            summary.net = net;
+        }
+        Ok((summaries, total))
+    }
+}
+
+/// Rounds `values` to `dp` decimal places so that the rounded values add up
+/// to the rounded sum of `values`.
+///
+/// Every value is rounded down first; the units still missing are then given
+/// to the values that lost the most, so each value is rounded either down or
+/// up (largest remainder method).
+pub fn round_preserving_sum(values: &[Decimal], dp: u32) -> Vec<Decimal> {
+    let unit = Decimal::new(1, dp);
+    let mut rounded: Vec<Decimal> = values.iter().map(|v| (v / unit).floor() * unit).collect();
+    let target = values.iter().sum::<Decimal>().round_dp(dp);
+    let missing = ((target - rounded.iter().sum::<Decimal>()) / unit)
+        .round()
+        .try_into()
+        .unwrap_or(0usize);
+
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    // Stable sort: ties go to the first values
+    order.sort_by(|&a, &b| (values[b] - rounded[b]).cmp(&(values[a] - rounded[a])));
+    for &i in order.iter().take(missing) {
+        rounded[i] += unit;
+    }
+    rounded
+}
+
+impl Translate for TravelerSummary {
+    fn translate_with_indent(
+        &self,
+        ctx: Arc<std::sync::Mutex<crate::Context>>,
+        indent_lvl: usize,
+    ) -> String {
+        let money =
+            |amount: Decimal| MoneyWrapper::new_with_context(amount, ctx.clone()).to_string();
+        i18n::format::FORMAT_TRAVELER_SUMMARY.translate_with_args_indent(
+            ctx.clone(),
+            &hashmap! {
+                i18n::args::TRAVELER_NAME.into() => self.traveler_name.clone().into(),
+                i18n::args::PAID.into() => money(self.paid).into(),
+                i18n::args::CONSUMED.into() => money(self.consumed).into(),
+                i18n::args::SENT.into() => money(self.sent).into(),
+                i18n::args::RECEIVED.into() => money(self.received).into(),
+                i18n::args::NET.into() => money(self.net).into(),
+            },
+            indent_lvl,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn decimals(values: &[&str]) -> Vec<Decimal> {
+        values
+            .iter()
+            .map(|v| Decimal::from_str(v).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn round_preserving_sum_keeps_zero_total() {
+        // Three travelers sharing 100 evenly, paid by the first one
+        let nets = decimals(&["66.666666", "-33.333333", "-33.333333"]);
+        let rounded = round_preserving_sum(&nets, 2);
+        assert_eq!(rounded, decimals(&["66.66", "-33.33", "-33.33"]));
+        assert_eq!(rounded.iter().sum::<Decimal>(), Decimal::ZERO);
+    }
+
+    #[test]
+    fn round_preserving_sum_leaves_exact_values() {
+        let nets = decimals(&["10.50", "-4.25", "-6.25"]);
+        assert_eq!(round_preserving_sum(&nets, 2), nets);
+    }
+
+    #[test]
+    fn round_preserving_sum_without_decimals() {
+        let nets = decimals(&["0.5", "0.5", "-1"]);
+        let rounded = round_preserving_sum(&nets, 0);
+        assert_eq!(rounded, decimals(&["1", "0", "-1"]));
+    }
+}
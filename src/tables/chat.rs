@@ -1,9 +1,19 @@
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
-use surrealdb::{RecordId, Surreal, engine::any::Any, sql::Datetime};
+use surrealdb::{
+    RecordId, Surreal,
+    engine::any::Any,
+    sql::{
+        Datetime,
+        statements::{BeginStatement, CommitStatement},
+    },
+};
 use teloxide::types::ChatId;
 use travel_rs_derive::Table;
+
+use super::traveler::Name;
+use crate::debt::{SettlementMode, SimplificationMode};
 use unic_langid::LanguageIdentifier;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Table)]
@@ -12,6 +22,14 @@ pub struct Chat {
     pub last_interaction_utc: Datetime,
     pub lang: String,
     pub currency: String,
+    pub active_trip: Option<RecordId>,
+    #[serde(default)]
+    pub settlement: SettlementMode,
+    #[serde(default)]
+    pub simplification: SimplificationMode,
+    /// IANA name of the time zone the datetimes are shown in.
+    #[serde(default)]
+    pub timezone: String,
 }
 
 impl Chat {
@@ -22,26 +40,49 @@ impl Chat {
         db.select((TABLE, id.0)).await
     }
 
+    /// Creates the chat together with a default trip, which becomes the
+    /// active one.
     pub async fn db_create(
         db: Arc<Surreal<Any>>,
         id: ChatId,
         langid: &LanguageIdentifier,
         currency: &str,
     ) -> Result<Option<Self>, surrealdb::Error> {
-        db.query(format!(
-            "CREATE ${ID}
-            CONTENT {{
-                {LAST_INTERACTION_UTC}: ${LAST_INTERACTION_UTC}, 
-                {LANG}: ${LANG},
-                {CURRENCY}: ${CURRENCY},
-            }}",
-        ))
-        .bind((ID, RecordId::from_table_key(TABLE, id.0)))
-        .bind((LAST_INTERACTION_UTC, Datetime::default()))
-        .bind((LANG, langid.to_string()))
-        .bind((CURRENCY, currency.to_owned()))
-        .await
-        .and_then(|mut response| response.take::<Option<Self>>(0))
+        use super::trip::{
+            CHAT as TRIP_CHAT, NAME as TRIP_NAME, NAME_LOWER as TRIP_NAME_LOWER,
+            NUMBER as TRIP_NUMBER, TABLE as TRIP_TB,
+        };
+
+        db.query(BeginStatement::default())
+            .query(format!(
+                "CREATE ${ID}
+                CONTENT {{
+                    {LAST_INTERACTION_UTC}: ${LAST_INTERACTION_UTC}, 
+                    {LANG}: ${LANG},
+                    {CURRENCY}: ${CURRENCY},
+                }}",
+            ))
+            .query(format!(
+                "LET $trip = CREATE ONLY {TRIP_TB}
+                CONTENT {{
+                    {TRIP_CHAT}: ${ID},
+                    {TRIP_NAME}: ${TRIP_NAME},
+                    {TRIP_NAME_LOWER}: string::lowercase(${TRIP_NAME}),
+                    {TRIP_NUMBER}: 1,
+                }}",
+            ))
+            .query(format!(
+                "UPDATE ${ID}
+                SET {ACTIVE_TRIP} = $trip.{ID}",
+            ))
+            .query(CommitStatement::default())
+            .bind((ID, RecordId::from_table_key(TABLE, id.0)))
+            .bind((LAST_INTERACTION_UTC, Datetime::default()))
+            .bind((LANG, langid.to_string()))
+            .bind((CURRENCY, currency.to_owned()))
+            .bind((TRIP_NAME, Name::default()))
+            .await
+            .and_then(|mut response| response.take::<Option<Self>>(2))
     }
 
     pub async fn db_update_last_interaction_utc(
@@ -73,6 +114,36 @@ impl Chat {
         .and_then(|mut response| response.take::<Option<Self>>(0))
     }
 
+    pub async fn db_update_settlement(
+        db: Arc<Surreal<Any>>,
+        id: ChatId,
+        mode: SettlementMode,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        db.query(format!(
+            "UPDATE ${ID}
+            SET {SETTLEMENT} = ${SETTLEMENT}",
+        ))
+        .bind((ID, RecordId::from_table_key(TABLE, id.0)))
+        .bind((SETTLEMENT, mode))
+        .await
+        .and_then(|mut response| response.take::<Option<Self>>(0))
+    }
+
+    pub async fn db_update_simplification(
+        db: Arc<Surreal<Any>>,
+        id: ChatId,
+        mode: SimplificationMode,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        db.query(format!(
+            "UPDATE ${ID}
+            SET {SIMPLIFICATION} = ${SIMPLIFICATION}",
+        ))
+        .bind((ID, RecordId::from_table_key(TABLE, id.0)))
+        .bind((SIMPLIFICATION, mode))
+        .await
+        .and_then(|mut response| response.take::<Option<Self>>(0))
+    }
+
     pub async fn db_update_currency(
         db: Arc<Surreal<Any>>,
         id: ChatId,
@@ -87,4 +158,19 @@ impl Chat {
         .await
         .and_then(|mut response| response.take::<Option<Self>>(0))
     }
+
+    pub async fn db_update_timezone(
+        db: Arc<Surreal<Any>>,
+        id: ChatId,
+        timezone: &str,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        db.query(format!(
+            "UPDATE ${ID}
+            SET {TIMEZONE} = ${TIMEZONE}",
+        ))
+        .bind((ID, RecordId::from_table_key(TABLE, id.0)))
+        .bind((TIMEZONE, timezone.to_owned()))
+        .await
+        .and_then(|mut response| response.take::<Option<Self>>(0))
+    }
 }
@@ -1,8 +1,11 @@
 use crate::{
     db::Count,
+    debt::delete_expenses,
+    filter::{Filter, HIGHLIGHTED},
     i18n::{self, ToFluentDateTime, Translate, TranslateWithArgs},
     money_wrapper::MoneyWrapper,
 };
+use jiff::tz::TimeZone;
 use maplit::hashmap;
 use rust_decimal::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -23,11 +26,12 @@ use super::traveler::Traveler;
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Table)]
 pub struct Expense {
     pub id: RecordId,
-    pub chat: RecordId,
+    pub trip: RecordId,
     pub number: i64,
     pub description: String,
     pub amount: Decimal,
     pub timestamp_utc: Datetime,
+    pub occurred_at: Datetime,
 }
 
 impl Expense {
@@ -36,23 +40,25 @@ impl Expense {
         chat_id: ChatId,
         description: String,
         amount: Decimal,
+        occurred_at: Datetime,
     ) -> Result<Option<Self>, surrealdb::Error> {
-        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(BeginStatement::default())
             .query(format!(
                 "LET $max = math::max(
                     SELECT VALUE {NUMBER} 
                     FROM {TABLE} 
-                    WHERE {CHAT} = ${CHAT_ID}
+                    WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
                 ) ?? 0"
             ))
             .query(format!(
                 "CREATE {TABLE}
                 CONTENT {{
-                    {CHAT}: ${CHAT_ID},
+                    {TRIP}: ${CHAT_ID}.{ACTIVE_TRIP},
                     {DESCRIPTION}: ${DESCRIPTION},
                     {AMOUNT}: <decimal> ${AMOUNT},
+                    {OCCURRED_AT}: ${OCCURRED_AT},
                     {NUMBER}: $max + 1,
                 }}",
             ))
@@ -60,6 +66,7 @@ impl Expense {
             .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
             .bind((DESCRIPTION, description))
             .bind((AMOUNT, amount))
+            .bind((OCCURRED_AT, occurred_at))
             .await
             .and_then(|mut response| response.take::<Option<Self>>(1))
     }
@@ -69,13 +76,13 @@ impl Expense {
         chat_id: ChatId,
         number: i64,
     ) -> Result<Option<Count>, surrealdb::Error> {
-        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(format!(
             "SELECT count()
             FROM {TABLE}
             WHERE 
-                {CHAT} = ${CHAT_ID}
+                {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
                 && {NUMBER} = ${NUMBER}
             GROUP BY count",
         ))
@@ -92,16 +99,14 @@ impl Expense {
     ) -> Result<(), surrealdb::Error> {
         use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
 
-        db.query(format!(
-            "DELETE {TABLE}
-             WHERE 
-                {CHAT} = ${CHAT_ID}
-                && {NUMBER} = ${NUMBER}",
-        ))
-        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
-        .bind((NUMBER, number))
-        .await
-        .map(|_| {})
+        let query = db.query(BeginStatement::default());
+        delete_expenses(query, &format!("{NUMBER} = ${NUMBER}"))
+            .query(CommitStatement::default())
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+            .bind((NUMBER, number))
+            .await?
+            .check()
+            .map(|_| {})
     }
 
     pub async fn db_delete_all(
@@ -110,52 +115,69 @@ impl Expense {
     ) -> Result<(), surrealdb::Error> {
         use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
 
-        db.query(format!(
-            "DELETE {TABLE}
-             WHERE {CHAT} = ${CHAT_ID}",
-        ))
-        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
-        .await
-        .map(|_| {})
+        let query = db.query(BeginStatement::default());
+        delete_expenses(query, "true")
+            .query(CommitStatement::default())
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+            .await?
+            .check()
+            .map(|_| {})
     }
 
     pub async fn db_select(
         db: Arc<Surreal<Any>>,
         chat_id: ChatId,
     ) -> Result<Vec<Self>, surrealdb::Error> {
-        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(format!(
             "SELECT *
             FROM {TABLE}
-            WHERE {CHAT} = ${CHAT_ID}
-            ORDER BY {NUMBER} ASC",
+            WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
+            ORDER BY {OCCURRED_AT} ASC, {NUMBER} ASC",
         ))
         .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
         .await
         .and_then(|mut response| response.take::<Vec<Self>>(0))
     }
 
-    pub async fn db_select_by_descr(
+    /// Selects the expenses matching `filter`, in its order. If it searches
+    /// text, each expense comes with its description with the matches
+    /// highlighted.
+    pub async fn db_select_filtered(
         db: Arc<Surreal<Any>>,
         chat_id: ChatId,
-        fuzzy_descr: String,
-    ) -> Result<Vec<Self>, surrealdb::Error> {
-        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
-        const FUZZY_DESCR: &str = "fuzzy_descr";
-
-        db.query(format!(
-            "SELECT *
-            FROM {TABLE}
-            WHERE
-                {CHAT} = ${CHAT_ID}
-                && {DESCRIPTION} ~ ${FUZZY_DESCR}
-            ORDER BY {NUMBER} ASC",
-        ))
-        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
-        .bind((FUZZY_DESCR, fuzzy_descr))
-        .await
-        .and_then(|mut response| response.take::<Vec<Self>>(0))
+        filter: &Filter,
+        tz: &TimeZone,
+    ) -> Result<Vec<(Self, Option<String>)>, surrealdb::Error> {
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
+
+        let filter = filter.compile(tz);
+        let searched = !filter.fields.is_empty();
+        let query = db
+            .query(format!(
+                "SELECT *{fields}
+                FROM {TABLE}
+                WHERE
+                    {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
+                    && ({condition})
+                ORDER BY {order}",
+                fields = filter.fields,
+                condition = filter.condition,
+                order = filter.order,
+            ))
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)));
+        let mut response = filter.bind(query).await?;
+        let highlights = if searched {
+            response.take::<Vec<Option<String>>>((0, HIGHLIGHTED))?
+        } else {
+            Vec::new()
+        };
+        let expenses = response.take::<Vec<Self>>(0)?;
+        Ok(expenses
+            .into_iter()
+            .zip(highlights.into_iter().chain(std::iter::repeat(None)))
+            .collect())
     }
 
     pub async fn db_select_by_payer(
@@ -167,7 +189,7 @@ impl Expense {
         db.query(format!(
             "SELECT *
             FROM ${TRAVELER}->{PAID_FOR}->{TABLE}.*
-            ORDER BY {NUMBER} ASC",
+            ORDER BY {OCCURRED_AT} ASC, {NUMBER} ASC",
         ))
         .bind((TRAVELER, traveler))
         .await
@@ -179,13 +201,13 @@ impl Expense {
         chat_id: ChatId,
         number: i64,
     ) -> Result<Option<Self>, surrealdb::Error> {
-        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(format!(
             "SELECT *
             FROM {TABLE}
             WHERE 
-                {CHAT} = ${CHAT_ID}
+                {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
                 && {NUMBER} = ${NUMBER}",
         ))
         .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
@@ -202,13 +224,14 @@ impl Translate for Expense {
         indent_lvl: usize,
     ) -> String {
         let amount = MoneyWrapper::new_with_context(self.amount, ctx.clone());
+        let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
         i18n::format::FORMAT_EXPENSE.translate_with_args_indent(
             ctx,
             &hashmap! {
                 i18n::args::NUMBER.into() => self.number.into(),
                 i18n::args::DESCRIPTION.into() => self.description.clone().into(),
                 i18n::args::AMOUNT.into() => amount.to_string().into(),
-                i18n::args::DATETIME.into() => self.timestamp_utc.to_fluent_datetime().unwrap().into(),
+                i18n::args::DATETIME.into() => self.occurred_at.to_fluent_datetime(&tz).unwrap().into(),
             },
             indent_lvl,
         )
@@ -1,3 +1,5 @@
 pub mod chat;
 pub mod expense;
+pub mod topic;
 pub mod traveler;
+pub mod trip;
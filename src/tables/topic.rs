@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use surrealdb::{
+    RecordId, Surreal,
+    engine::any::Any,
+    sql::{
+        Datetime,
+        statements::{BeginStatement, CommitStatement},
+    },
+};
+use teloxide::types::{ChatId, ThreadId};
+use travel_rs_derive::Table;
+
+use super::traveler::Name;
+
+/// Keys of the `chat` records holding topic ledgers are below this, outside
+/// the range of valid Telegram chat ids (which have at most 52 significant
+/// bits).
+const LEDGER_KEY_BASE: i64 = -(1 << 53);
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Key of the `chat` record holding the ledger of a topic. It is derived from
+/// the chat and thread ids, with FNV-1a as it doesn't change across builds, so
+/// that topics created at the same time don't race for the same key. Keys of
+/// different topics colliding is unlikely, and refused by the unique index on
+/// the ledgers of the topics.
+fn ledger_key(chat_id: ChatId, thread_id: ThreadId) -> i64 {
+    let hash = chat_id
+        .0
+        .to_le_bytes()
+        .into_iter()
+        .chain(thread_id.0.0.to_le_bytes())
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        });
+    let keys = (LEDGER_KEY_BASE - i64::MIN) as u64;
+    LEDGER_KEY_BASE - 1 - (hash % keys) as i64
+}
+
+/// A forum topic of a supergroup that keeps (or kept) its own ledger.
+///
+/// The ledger is a `chat` record of its own, so trips, travelers, expenses
+/// and transfers of the topic are stored exactly as those of a whole chat.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Table)]
+pub struct Topic {
+    pub id: RecordId,
+    pub chat: RecordId,
+    pub thread: i32,
+    pub ledger: RecordId,
+    pub enabled: bool,
+}
+
+impl Topic {
+    /// Id the topic ledger is stored under, to be used in place of the chat
+    /// id in every ledger query.
+    pub fn ledger_id(&self) -> Option<ChatId> {
+        i64::try_from(self.ledger.key().clone()).ok().map(ChatId)
+    }
+
+    /// Id of the supergroup the topic belongs to.
+    pub fn chat_id(&self) -> Option<ChatId> {
+        i64::try_from(self.chat.key().clone()).ok().map(ChatId)
+    }
+
+    pub fn thread_id(&self) -> ThreadId {
+        ThreadId(teloxide::types::MessageId(self.thread))
+    }
+
+    /// Creates the topic together with its ledger. The ledger starts with the
+    /// language and currency of the supergroup and a default trip.
+    pub async fn db_create(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        thread_id: ThreadId,
+        enabled: bool,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        use super::chat::{
+            ACTIVE_TRIP, CURRENCY, LANG, LAST_INTERACTION_UTC, SETTLEMENT, SIMPLIFICATION,
+            TABLE as CHAT_TB,
+        };
+        use super::trip::{
+            CHAT as TRIP_CHAT, NAME as TRIP_NAME, NAME_LOWER as TRIP_NAME_LOWER,
+            NUMBER as TRIP_NUMBER, TABLE as TRIP_TB,
+        };
+
+        db.query(BeginStatement::default())
+            .query(format!("LET $parent = SELECT * FROM ONLY ${CHAT}"))
+            .query(format!(
+                "CREATE ${LEDGER}
+                CONTENT {{
+                    {LAST_INTERACTION_UTC}: ${LAST_INTERACTION_UTC},
+                    {LANG}: $parent.{LANG},
+                    {CURRENCY}: $parent.{CURRENCY},
+                    {SETTLEMENT}: $parent.{SETTLEMENT},
+                    {SIMPLIFICATION}: $parent.{SIMPLIFICATION},
+                }}",
+            ))
+            .query(format!(
+                "LET $trip = CREATE ONLY {TRIP_TB}
+                CONTENT {{
+                    {TRIP_CHAT}: ${LEDGER},
+                    {TRIP_NAME}: ${TRIP_NAME},
+                    {TRIP_NAME_LOWER}: string::lowercase(${TRIP_NAME}),
+                    {TRIP_NUMBER}: 1,
+                }}",
+            ))
+            .query(format!(
+                "UPDATE ${LEDGER}
+                SET {ACTIVE_TRIP} = $trip.{ID}",
+            ))
+            .query(format!(
+                "CREATE {TABLE}
+                CONTENT {{
+                    {CHAT}: ${CHAT},
+                    {THREAD}: ${THREAD},
+                    {LEDGER}: ${LEDGER},
+                    {ENABLED}: ${ENABLED},
+                }}",
+            ))
+            .query(CommitStatement::default())
+            .bind((CHAT, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+            .bind((
+                LEDGER,
+                RecordId::from_table_key(CHAT_TB, ledger_key(chat_id, thread_id)),
+            ))
+            .bind((THREAD, thread_id.0.0))
+            .bind((ENABLED, enabled))
+            .bind((LAST_INTERACTION_UTC, Datetime::default()))
+            .bind((TRIP_NAME, Name::default()))
+            .await
+            .and_then(|mut response| response.take::<Option<Self>>(4))
+    }
+
+    pub async fn db_select_by_thread(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        thread_id: ThreadId,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        use super::chat::TABLE as CHAT_TB;
+
+        db.query(format!(
+            "SELECT *
+            FROM {TABLE}
+            WHERE
+                {CHAT} = ${CHAT}
+                && {THREAD} = ${THREAD}",
+        ))
+        .bind((CHAT, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .bind((THREAD, thread_id.0.0))
+        .await
+        .and_then(|mut response| response.take::<Option<Self>>(0))
+    }
+
+    /// Returns every topic currently keeping its own ledger.
+    pub async fn db_select_enabled(db: Arc<Surreal<Any>>) -> Result<Vec<Self>, surrealdb::Error> {
+        db.query(format!(
+            "SELECT *
+            FROM {TABLE}
+            WHERE {ENABLED} = true",
+        ))
+        .await
+        .and_then(|mut response| response.take::<Vec<Self>>(0))
+    }
+
+    /// Turns the topic ledger on or off. A disabled ledger keeps its data and
+    /// is used again as soon as it is enabled back.
+    pub async fn db_set_enabled(
+        &self,
+        db: Arc<Surreal<Any>>,
+        enabled: bool,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        db.query(format!(
+            "UPDATE ONLY ${ID}
+            SET {ENABLED} = ${ENABLED}",
+        ))
+        .bind((ID, self.id.clone()))
+        .bind((ENABLED, enabled))
+        .await
+        .and_then(|mut response| response.take::<Option<Self>>(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::db, tests::TestBot};
+    use std::collections::HashSet;
+    use teloxide::types::MessageId;
+
+    #[test]
+    fn ledger_keys() {
+        let chat_id = ChatId(-1001234567890);
+        let key = ledger_key(chat_id, ThreadId(MessageId(42)));
+        assert_eq!(key, ledger_key(chat_id, ThreadId(MessageId(42))));
+        assert!(key < LEDGER_KEY_BASE);
+        assert_ne!(key, ledger_key(chat_id, ThreadId(MessageId(43))));
+        assert_ne!(
+            key,
+            ledger_key(ChatId(-1001234567891), ThreadId(MessageId(42)))
+        );
+    }
+
+    test! { create_concurrently,
+        let db = db().await;
+        let mut bot = TestBot::new(db.clone(), "/listtravelers");
+        bot.dispatch().await;
+        let chat_id = bot.chat_id();
+
+        // Topics created at the same time all get a ledger of their own
+        let tasks = (1..=8)
+            .map(|thread| {
+                let db = db.clone();
+                tokio::spawn(async move {
+                    Topic::db_create(db, chat_id, ThreadId(MessageId(thread)), true).await
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut ledgers = HashSet::new();
+        for task in tasks {
+            let topic = task.await.unwrap().unwrap().unwrap();
+            ledgers.insert(topic.ledger_id().unwrap());
+        }
+        assert_eq!(ledgers.len(), 8);
+    }
+}
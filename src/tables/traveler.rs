@@ -83,7 +83,7 @@ impl From<Name> for fluent::FluentValue<'_> {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Table)]
 pub struct Traveler {
     pub id: RecordId,
-    pub chat: RecordId,
+    pub trip: RecordId,
     pub name: Name,
     pub number: i64,
 }
@@ -94,20 +94,20 @@ impl Traveler {
         chat_id: ChatId,
         name: &Name,
     ) -> Result<Option<Self>, surrealdb::Error> {
-        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(BeginStatement::default())
             .query(format!(
                 "LET $max = math::max(
                     SELECT VALUE {NUMBER}
                     FROM {TABLE}
-                    WHERE {CHAT} = ${CHAT_ID}
+                    WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
                 ) ?? 0"
             ))
             .query(format!(
                 "CREATE {TABLE}
                 CONTENT {{
-                    {CHAT}: ${CHAT_ID},
+                    {TRIP}: ${CHAT_ID}.{ACTIVE_TRIP},
                     {NAME}: ${NAME},
                     {NAME_LOWER}: string::lowercase(${NAME}),
                     {NUMBER}: $max + 1,
@@ -125,13 +125,13 @@ impl Traveler {
         chat_id: ChatId,
         name: &Name,
     ) -> Result<Option<Count>, surrealdb::Error> {
-        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(format!(
             "SELECT count()
             FROM {TABLE}
             WHERE 
-                {CHAT} = ${CHAT_ID}
+                {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
                 && {NAME_LOWER} = string::lowercase(${NAME})
             GROUP BY count",
         ))
@@ -146,24 +146,26 @@ impl Traveler {
         chat_id: ChatId,
         name: &Name,
     ) -> Result<(), surrealdb::Error> {
-        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
-        db.query(format!("{FN_DELETE_TRAVELER}(${CHAT_ID}, ${NAME})",))
-            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
-            .bind((NAME, name.clone()))
-            .await
-            .map(|_| {})
+        db.query(format!(
+            "{FN_DELETE_TRAVELER}(${CHAT_ID}.{ACTIVE_TRIP}, ${NAME})",
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .bind((NAME, name.clone()))
+        .await
+        .map(|_| {})
     }
 
     pub async fn db_delete_all(
         db: Arc<Surreal<Any>>,
         chat_id: ChatId,
     ) -> Result<(), surrealdb::Error> {
-        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(format!(
             "DELETE {TABLE}
-             WHERE {CHAT} = ${CHAT_ID}",
+             WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}",
         ))
         .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
         .await
@@ -174,12 +176,12 @@ impl Traveler {
         db: Arc<Surreal<Any>>,
         chat_id: ChatId,
     ) -> Result<Vec<Self>, surrealdb::Error> {
-        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(format!(
             "SELECT *
             FROM {TABLE}
-            WHERE {CHAT} = ${CHAT_ID}
+            WHERE {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
             ORDER BY {NAME} ASC",
         ))
         .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
@@ -192,13 +194,13 @@ impl Traveler {
         chat_id: ChatId,
         name: &Name,
     ) -> Result<Option<Self>, surrealdb::Error> {
-        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(format!(
             "SELECT *
             FROM {TABLE}
             WHERE
-                {CHAT} = ${CHAT_ID}
+                {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
                 && {NAME_LOWER} = string::lowercase(${NAME})",
         ))
         .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
@@ -212,13 +214,13 @@ impl Traveler {
         chat_id: ChatId,
         number: i64,
     ) -> Result<Option<Self>, surrealdb::Error> {
-        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(format!(
             "SELECT *
             FROM {TABLE}
             WHERE
-                {CHAT} = ${CHAT_ID}
+                {TRIP} = ${CHAT_ID}.{ACTIVE_TRIP}
                 && {NUMBER} = ${NUMBER}",
         ))
         .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
@@ -240,7 +242,7 @@ impl Traveler {
     }
 
     /// Returns a list of `(traveler, expenses)` pairs for every traveler
-    /// in `chat_id` that has at least one associated expense.
+    /// in the active trip of `chat_id` that has at least one associated expense.
     pub async fn travelers_with_expenses(
         db: Arc<Surreal<Any>>,
         chat_id: ChatId,
@@ -0,0 +1,214 @@
+use crate::{db::Count, traveler::Name};
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use surrealdb::{
+    RecordId, Surreal,
+    engine::any::Any,
+    sql::statements::{BeginStatement, CommitStatement},
+};
+use teloxide::types::ChatId;
+use travel_rs_derive::Table;
+
+pub const NAME_LOWER: &str = "name_lower";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Table)]
+pub struct Trip {
+    pub id: RecordId,
+    pub chat: RecordId,
+    pub name: Name,
+    pub number: i64,
+    pub closed: bool,
+}
+
+impl Trip {
+    /// Creates a new trip in the chat and makes it the active one.
+    pub async fn db_create(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        name: &Name,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
+
+        db.query(BeginStatement::default())
+            .query(format!(
+                "LET $max = math::max(
+                    SELECT VALUE {NUMBER}
+                    FROM {TABLE}
+                    WHERE {CHAT} = ${CHAT_ID}
+                ) ?? 0"
+            ))
+            .query(format!(
+                "LET $trip = CREATE ONLY {TABLE}
+                CONTENT {{
+                    {CHAT}: ${CHAT_ID},
+                    {NAME}: ${NAME},
+                    {NAME_LOWER}: string::lowercase(${NAME}),
+                    {NUMBER}: $max + 1,
+                }}",
+            ))
+            .query(format!(
+                "UPDATE ${CHAT_ID}
+                SET {ACTIVE_TRIP} = $trip.{ID}"
+            ))
+            .query("RETURN $trip")
+            .query(CommitStatement::default())
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+            .bind((NAME, name.clone()))
+            .await
+            .and_then(|mut response| response.take::<Option<Self>>(3))
+    }
+
+    pub async fn db_count(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        name: &Name,
+    ) -> Result<Option<Count>, surrealdb::Error> {
+        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+
+        db.query(format!(
+            "SELECT count()
+            FROM {TABLE}
+            WHERE
+                {CHAT} = ${CHAT_ID}
+                && {NAME_LOWER} = string::lowercase(${NAME})
+            GROUP BY count",
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .bind((NAME, name.clone()))
+        .await
+        .and_then(|mut response| response.take::<Option<Count>>(0))
+    }
+
+    pub async fn db_select(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+    ) -> Result<Vec<Self>, surrealdb::Error> {
+        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+
+        db.query(format!(
+            "SELECT *
+            FROM {TABLE}
+            WHERE {CHAT} = ${CHAT_ID}
+            ORDER BY {NUMBER} ASC",
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .await
+        .and_then(|mut response| response.take::<Vec<Self>>(0))
+    }
+
+    pub async fn db_select_by_name(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        name: &Name,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+
+        db.query(format!(
+            "SELECT *
+            FROM {TABLE}
+            WHERE
+                {CHAT} = ${CHAT_ID}
+                && {NAME_LOWER} = string::lowercase(${NAME})",
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .bind((NAME, name.clone()))
+        .await
+        .and_then(|mut response| response.take::<Option<Self>>(0))
+    }
+
+    pub async fn db_select_by_number(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        number: i64,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        use super::chat::{ID as CHAT_ID, TABLE as CHAT_TB};
+
+        db.query(format!(
+            "SELECT *
+            FROM {TABLE}
+            WHERE
+                {CHAT} = ${CHAT_ID}
+                && {NUMBER} = ${NUMBER}",
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .bind((NUMBER, number))
+        .await
+        .and_then(|mut response| response.take::<Option<Self>>(0))
+    }
+
+    /// Parses a raw string as a trip number and looks it up in the DB.
+    ///
+    /// Returns `None` if parsing fails or no trip with that number exists.
+    pub async fn db_resolve_by_number(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        raw_number: &str,
+    ) -> Option<Self> {
+        let number: i64 = raw_number.parse().ok()?;
+        Self::db_select_by_number(db, chat_id, number).await.ok()?
+    }
+
+    /// Returns the trip currently active in the chat.
+    pub async fn db_select_active(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
+
+        db.query(format!("SELECT * FROM ONLY ${CHAT_ID}.{ACTIVE_TRIP}"))
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+            .await
+            .and_then(|mut response| response.take::<Option<Self>>(0))
+    }
+
+    /// Makes this trip the active one in its chat.
+    pub async fn db_activate(&self, db: Arc<Surreal<Any>>) -> Result<(), surrealdb::Error> {
+        use super::chat::ACTIVE_TRIP;
+
+        db.query(format!(
+            "UPDATE ${CHAT}
+            SET {ACTIVE_TRIP} = ${ID}"
+        ))
+        .bind((CHAT, self.chat.clone()))
+        .bind((ID, self.id.clone()))
+        .await
+        .map(|_| {})
+    }
+
+    /// Renames the trip currently active in the chat.
+    pub async fn db_rename_active(
+        db: Arc<Surreal<Any>>,
+        chat_id: ChatId,
+        name: &Name,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        use super::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
+
+        db.query(format!(
+            "UPDATE ONLY ${CHAT_ID}.{ACTIVE_TRIP}
+            SET
+                {NAME} = ${NAME},
+                {NAME_LOWER} = string::lowercase(${NAME})",
+        ))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .bind((NAME, name.clone()))
+        .await
+        .and_then(|mut response| response.take::<Option<Self>>(0))
+    }
+
+    /// Closes the trip, making it read-only, or reopens it.
+    pub async fn db_set_closed(
+        &self,
+        db: Arc<Surreal<Any>>,
+        closed: bool,
+    ) -> Result<Option<Self>, surrealdb::Error> {
+        db.query(format!(
+            "UPDATE ONLY ${ID}
+            SET {CLOSED} = ${CLOSED}",
+        ))
+        .bind((ID, self.id.clone()))
+        .bind((CLOSED, closed))
+        .await
+        .and_then(|mut response| response.take::<Option<Self>>(0))
+    }
+}
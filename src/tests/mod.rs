@@ -6,12 +6,15 @@ use crate::{
 };
 use std::sync::{Arc, Mutex};
 use surrealdb::{Surreal, engine::any::Any};
-use teloxide::types::{Chat, ChatId};
-use teloxide_tests::{MockBot, MockCallbackQuery, MockMessageText, mock_bot::DistributionKey};
+use teloxide::types::{Chat, ChatId, Message, MessageId, ThreadId};
+use teloxide_tests::{
+    MockBot, MockCallbackQuery, MockMessageDocument, MockMessageText, mock_bot::DistributionKey,
+};
 
 pub(crate) struct TestBot {
     bot: MockBot<Box<dyn std::error::Error + Send + Sync>, DistributionKey>,
     chat_id: ChatId,
+    topic_id: Option<ThreadId>,
 }
 
 impl TestBot {
@@ -28,30 +31,65 @@ impl TestBot {
         let mock_msg = mock_text_from_chat_id(text, chat_id);
         let mut bot = MockBot::new(mock_msg, handler_tree());
         bot.dependencies(deps(db_instance.clone()));
-        Self { bot, chat_id }
+        Self {
+            bot,
+            chat_id,
+            topic_id: None,
+        }
     }
 
     pub fn chat_id(&self) -> ChatId {
         self.chat_id
     }
 
+    /// Sends the following updates to the forum topic `thread`, or outside
+    /// of any topic if `None`.
+    pub fn set_topic(&mut self, thread: Option<i32>) {
+        self.topic_id = thread.map(|thread| ThreadId(MessageId(thread)));
+    }
+
     /// Updates the bot with a new message.
     /// The `text` parameter is the new message text.
     pub fn update(&mut self, text: &str) {
-        let mock_msg = mock_text_from_chat_id(text, self.chat_id);
+        let mock_msg = self.mock_text(text);
+        self.bot.update(mock_msg);
+    }
+
+    /// Updates the bot with a message carrying a document of `file_size`
+    /// bytes, with `caption` as its caption.
+    pub fn update_document(&mut self, caption: &str, file_size: u32) {
+        let mock_msg = MockMessageDocument::new()
+            .caption(caption)
+            .file_size(file_size);
+        let mock_chat = mock_msg.chat.clone();
+        let mut mock_msg = mock_msg.chat(Chat {
+            id: self.chat_id,
+            ..mock_chat
+        });
+        mock_msg.thread_id = self.topic_id;
+        mock_msg.is_topic_message = self.topic_id.is_some();
         self.bot.update(mock_msg);
     }
 
     /// Updates the bot with a callback query carrying `data`.
     /// The callback message is tied to this bot's chat ID.
     pub fn update_callback(&mut self, data: &str) {
-        let mock_msg = mock_text_from_chat_id("", self.chat_id).build();
+        let mock_msg = self.mock_text("").build();
         let cb = MockCallbackQuery::new()
             .data(data.to_owned())
             .message(mock_msg);
         self.bot.update(cb);
     }
 
+    /// Updates the bot with a callback query carrying `data`, coming from a
+    /// button of `message` (e.g. one returned by [`Self::last_sent_message`]).
+    pub fn update_callback_on(&mut self, data: &str, message: Message) {
+        let cb = MockCallbackQuery::new()
+            .data(data.to_owned())
+            .message(message);
+        self.bot.update(cb);
+    }
+
     /// Dispatches the bot and checks the last message sent.
     /// The `response` parameter is the expected response text.
     pub async fn test_last_message(&mut self, response: &str) {
@@ -83,6 +121,84 @@ impl TestBot {
             .map(|s| s.to_owned())
     }
 
+    /// Returns the last message sent, keyboard included.
+    /// Returns `None` if no messages were sent.
+    pub fn last_sent_message(&self) -> Option<Message> {
+        self.bot.get_responses().sent_messages.last().cloned()
+    }
+
+    /// Returns the text of every message sent, in order.
+    pub fn sent_texts(&self) -> Vec<String> {
+        self.bot
+            .get_responses()
+            .sent_messages_text
+            .iter()
+            .map(|sent| sent.bot_request.text.clone())
+            .collect()
+    }
+
+    /// Returns the file name and content of every document sent, in order.
+    pub fn sent_documents(&self) -> Vec<(String, String)> {
+        self.bot
+            .get_responses()
+            .sent_messages_document
+            .iter()
+            .map(|sent| {
+                (
+                    sent.bot_request.file_name.clone(),
+                    sent.bot_request.file_data.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the file name and caption of every photo sent, in order.
+    pub fn sent_photos(&self) -> Vec<(String, Option<String>)> {
+        self.bot
+            .get_responses()
+            .sent_messages_photo
+            .iter()
+            .map(|sent| {
+                (
+                    sent.bot_request.file_name.clone(),
+                    sent.bot_request.caption.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the text of the last message edited.
+    /// Returns `None` if no messages were edited.
+    pub fn last_edited_message(&self) -> Option<String> {
+        self.bot
+            .get_responses()
+            .edited_messages_text
+            .last()
+            .map(|edited| edited.bot_request.text.clone())
+    }
+
+    /// Returns the text shown by the last callback query answer.
+    /// Returns `None` if no queries were answered or the answer had no text.
+    pub fn last_callback_answer(&self) -> Option<String> {
+        self.bot
+            .get_responses()
+            .answered_callback_queries
+            .last()
+            .and_then(|answer| answer.text.clone())
+    }
+
+    /// Returns the forum topic the last message was sent to.
+    /// Returns `None` if no messages were sent or if it was sent outside of
+    /// any topic.
+    pub fn last_message_topic(&self) -> Option<i32> {
+        let responses = self.bot.get_responses();
+        responses
+            .sent_messages_text
+            .last()
+            .and_then(|msg| msg.bot_request.message_thread_id)
+            .and_then(|thread| i32::try_from(thread).ok())
+    }
+
     pub fn context(&self) -> Arc<Mutex<Context>> {
         let arc: Arc<Arc<Mutex<Context>>> = self.bot.dependencies.get();
         Arc::clone(&arc)
@@ -95,6 +211,14 @@ impl TestBot {
         let arc: Arc<Arc<PendingCommandStorage>> = self.bot.dependencies.get();
         Arc::clone(&arc)
     }
+
+    /// Builds a message with `text` for this bot's chat and current topic.
+    fn mock_text(&self, text: &str) -> MockMessageText {
+        let mut mock_msg = mock_text_from_chat_id(text, self.chat_id);
+        mock_msg.thread_id = self.topic_id;
+        mock_msg.is_topic_message = self.topic_id.is_some();
+        mock_msg
+    }
 }
 
 fn mock_text_from_chat_id(text: &str, chat_id: ChatId) -> MockMessageText {
@@ -110,7 +234,10 @@ pub mod helpers {
     use rust_decimal::Decimal;
 
     use super::*;
-    use crate::i18n::{self, Translate, TranslateWithArgs};
+    use crate::{
+        consts::TODAY_KWORD,
+        i18n::{self, Translate, TranslateWithArgs},
+    };
     use maplit::hashmap;
 
     /// Returns the localized `cancel-ok` message for the dialogue identified
@@ -145,10 +272,13 @@ pub mod helpers {
         // 2. Set amount
         bot.update(&amount.to_string());
         bot.dispatch().await;
-        // 3. Set payer
+        // 3. Set date
+        bot.update(TODAY_KWORD);
+        bot.dispatch().await;
+        // 4. Set payer
         bot.update(payer);
         bot.dispatch().await;
-        // 4. Split expense
+        // 5. Split expense
         for s in split {
             bot.update(s);
             bot.dispatch().await;
@@ -0,0 +1,71 @@
+//! Time zones of the chats, identified by their IANA names (e.g. `Asia/Tokyo`).
+//!
+//! Datetimes are stored in UTC: they are converted to the time zone of the
+//! chat when shown, and dates sent by the users are read in it.
+
+use chrono::{DateTime, Datelike, NaiveDateTime, Timelike, Utc};
+use jiff::{Timestamp, Zoned, civil, tz::TimeZone};
+
+/// Returns the time zone with the given IANA name, ignoring case, or `None`
+/// if there is no such time zone.
+pub fn find(name: &str) -> Option<TimeZone> {
+    TimeZone::get(name.trim()).ok()
+}
+
+/// Returns the IANA name of `tz`, as stored on the chat.
+pub fn name(tz: &TimeZone) -> &str {
+    tz.iana_name().unwrap_or("UTC")
+}
+
+/// Converts a UTC datetime to the local time in `tz`.
+pub fn to_zoned(datetime: DateTime<Utc>, tz: &TimeZone) -> Option<Zoned> {
+    Timestamp::new(
+        datetime.timestamp(),
+        datetime.timestamp_subsec_nanos() as i32,
+    )
+    .ok()
+    .map(|timestamp| timestamp.to_zoned(tz.clone()))
+}
+
+/// Converts a local datetime in `tz` to UTC. Local times skipped or repeated
+/// by a daylight saving time change are taken with the offset in effect
+/// before the change.
+pub fn to_utc(datetime: NaiveDateTime, tz: &TimeZone) -> Option<DateTime<Utc>> {
+    let datetime = civil::DateTime::new(
+        datetime.year().try_into().ok()?,
+        datetime.month() as i8,
+        datetime.day() as i8,
+        datetime.hour() as i8,
+        datetime.minute() as i8,
+        datetime.second() as i8,
+        0,
+    )
+    .ok()?;
+    let timestamp = datetime.to_zoned(tz.clone()).ok()?.timestamp();
+    DateTime::from_timestamp(timestamp.as_second(), timestamp.subsec_nanosecond() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_times() {
+        let tokyo = find("asia/tokyo").unwrap();
+        assert_eq!(name(&tokyo), "Asia/Tokyo");
+
+        // 23:30 in Tokyo is 14:30 UTC of the same day
+        let utc = DateTime::parse_from_rfc3339("2026-10-19T14:30:00Z")
+            .unwrap()
+            .to_utc();
+        let local = to_zoned(utc, &tokyo).unwrap();
+        assert_eq!(
+            local.datetime(),
+            civil::datetime(2026, 10, 19, 23, 30, 0, 0)
+        );
+        let naive = NaiveDateTime::parse_from_str("2026-10-19 23:30", "%Y-%m-%d %H:%M").unwrap();
+        assert_eq!(to_utc(naive, &tokyo), Some(utc));
+
+        assert!(find("Mars/Olympus_Mons").is_none());
+    }
+}
@@ -0,0 +1,193 @@
+//! Forum topics support.
+//!
+//! In forum supergroups every message carries the id of the topic (thread)
+//! it was sent in. A topic can keep its own ledger, stored under a dedicated
+//! `chat` record (see [`Topic`]), either because it was turned on with
+//! `/topicledger` or because the `bot.topic_ledgers` setting enables it for
+//! every topic. Topics without their own ledger share the one of the whole
+//! chat.
+//!
+//! Whatever the ledger, replies are sent back to the topic the update came
+//! from ([`InTopic`]) and dialogues are keyed by topic ([`DialogueKey`]), so
+//! that different topics never interfere with each other's dialogues.
+
+use crate::{settings::SETTINGS, topic::Topic};
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex},
+};
+use surrealdb::{Surreal, engine::any::Any};
+use teloxide::{
+    dispatching::dialogue::GetChatId,
+    payloads::{SendDocument, SendMessage, SendPhoto},
+    requests::HasPayload,
+    types::{ChatId, Message, ThreadId},
+};
+
+/// Dialogue keys of forum topics are allocated upwards starting from here,
+/// outside the range of valid Telegram chat ids (which have at most 52
+/// significant bits).
+const DIALOGUE_KEY_BASE: i64 = 1 << 53;
+
+/// Ledger ids of the topics currently keeping their own ledger.
+static LEDGERS: LazyLock<Mutex<HashMap<(ChatId, ThreadId), ChatId>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Dialogue storage keys assigned to forum topics during this process
+/// lifetime. Dialogues are kept in memory, so the keys need not survive a
+/// restart.
+static DIALOGUE_KEYS: LazyLock<Mutex<HashMap<(ChatId, ThreadId), ChatId>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Records whether `topic` keeps its own ledger, so that
+/// [`MessageExt::ledger_id`] can resolve it without a database round trip.
+pub fn cache_topic(topic: &Topic) {
+    let (Some(chat_id), Some(ledger_id)) = (topic.chat_id(), topic.ledger_id()) else {
+        tracing::error!("Invalid topic record: {topic:?}");
+        return;
+    };
+    let key = (chat_id, topic.thread_id());
+    let mut ledgers = LEDGERS.lock().expect("Failed to lock LEDGERS");
+    if topic.enabled {
+        ledgers.insert(key, ledger_id);
+    } else {
+        ledgers.remove(&key);
+    }
+}
+
+/// Loads every topic keeping its own ledger. Called once at startup so that
+/// callbacks coming from old messages resolve the right ledger.
+pub async fn load_topics(db: Arc<Surreal<Any>>) {
+    match Topic::db_select_enabled(db).await {
+        Ok(topics) => {
+            topics.iter().for_each(cache_topic);
+            tracing::info!("Loaded {} topic ledgers", topics.len());
+        }
+        Err(err) => tracing::error!("{err}"),
+    }
+}
+
+/// Makes sure the topic `msg` was sent in is known: when the
+/// `bot.topic_ledgers` setting is on, topics seen for the first time get
+/// their own ledger.
+pub async fn sync_topic(db: Arc<Surreal<Any>>, msg: &Message) {
+    let Some(thread_id) = msg.topic_id() else {
+        return;
+    };
+    if LEDGERS
+        .lock()
+        .expect("Failed to lock LEDGERS")
+        .contains_key(&(msg.chat.id, thread_id))
+    {
+        return;
+    }
+
+    let topic = match Topic::db_select_by_thread(db.clone(), msg.chat.id, thread_id).await {
+        Ok(None) if SETTINGS.bot.topic_ledgers => {
+            tracing::debug!(
+                "Creating ledger for topic {thread_id} of chat {}",
+                msg.chat.id
+            );
+            Topic::db_create(db, msg.chat.id, thread_id, true).await
+        }
+        res => res,
+    };
+    match topic {
+        Ok(Some(topic)) => cache_topic(&topic),
+        Ok(None) => {}
+        Err(err) => tracing::error!("{err}"),
+    }
+}
+
+/// Topic-aware accessors on incoming messages.
+pub trait MessageExt {
+    /// The forum topic the message was sent in, if any. Messages of the
+    /// "General" topic and of regular chats have none.
+    fn topic_id(&self) -> Option<ThreadId>;
+
+    /// Id the ledger of the message is stored under: the one of its topic if
+    /// the topic keeps its own ledger, the chat id otherwise.
+    fn ledger_id(&self) -> ChatId;
+
+    /// Key of the dialogues started by the message.
+    fn dialogue_key(&self) -> DialogueKey;
+}
+
+impl MessageExt for Message {
+    fn topic_id(&self) -> Option<ThreadId> {
+        self.thread_id.filter(|_| self.is_topic_message)
+    }
+
+    fn ledger_id(&self) -> ChatId {
+        self.topic_id()
+            .and_then(|thread_id| {
+                LEDGERS
+                    .lock()
+                    .expect("Failed to lock LEDGERS")
+                    .get(&(self.chat.id, thread_id))
+                    .copied()
+            })
+            .unwrap_or(self.chat.id)
+    }
+
+    fn dialogue_key(&self) -> DialogueKey {
+        let Some(thread_id) = self.topic_id() else {
+            return DialogueKey(self.chat.id);
+        };
+        let mut keys = DIALOGUE_KEYS.lock().expect("Failed to lock DIALOGUE_KEYS");
+        let next = ChatId(DIALOGUE_KEY_BASE + keys.len() as i64);
+        DialogueKey(*keys.entry((self.chat.id, thread_id)).or_insert(next))
+    }
+}
+
+/// Key dialogues are stored under: the chat id for regular chats, a key
+/// unique to the topic for forum topics. Injected as a dependency so that
+/// `enter_dialogue::<DialogueKey, _, _>()` picks it up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DialogueKey(pub ChatId);
+
+impl GetChatId for DialogueKey {
+    fn chat_id(&self) -> Option<ChatId> {
+        Some(self.0)
+    }
+}
+
+/// Payloads of the requests that can be sent to a forum topic.
+pub trait TopicPayload {
+    fn set_topic(&mut self, topic_id: Option<ThreadId>);
+}
+
+impl TopicPayload for SendMessage {
+    fn set_topic(&mut self, topic_id: Option<ThreadId>) {
+        self.message_thread_id = topic_id;
+    }
+}
+
+impl TopicPayload for SendDocument {
+    fn set_topic(&mut self, topic_id: Option<ThreadId>) {
+        self.message_thread_id = topic_id;
+    }
+}
+
+impl TopicPayload for SendPhoto {
+    fn set_topic(&mut self, topic_id: Option<ThreadId>) {
+        self.message_thread_id = topic_id;
+    }
+}
+
+/// Sends a request to the forum topic an update came from, e.g.
+/// `bot.send_message(msg.chat.id, text).in_topic(msg.topic_id())`.
+pub trait InTopic {
+    fn in_topic(self, topic_id: Option<ThreadId>) -> Self;
+}
+
+impl<R> InTopic for R
+where
+    R: HasPayload,
+    R::Payload: TopicPayload,
+{
+    fn in_topic(mut self, topic_id: Option<ThreadId>) -> Self {
+        self.payload_mut().set_topic(topic_id);
+        self
+    }
+}
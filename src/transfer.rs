@@ -4,10 +4,12 @@ use std::{
 };
 
 use crate::{
+    filter::Filter,
     i18n::{self, ToFluentDateTime, Translate, TranslateWithArgs},
     money_wrapper::MoneyWrapper,
     traveler::Name,
 };
+use jiff::tz::TimeZone;
 use maplit::hashmap;
 use rust_decimal::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -23,7 +25,7 @@ pub struct Transfer {
     pub amount: Decimal,
     pub sender_name: Name,
     pub receiver_name: Name,
-    pub chat: RecordId,
+    pub trip: RecordId,
     pub timestamp_utc: Datetime,
 }
 
@@ -32,39 +34,41 @@ impl Transfer {
         db: Arc<Surreal<Any>>,
         chat_id: ChatId,
     ) -> Result<Vec<Self>, surrealdb::Error> {
-        use crate::chat::TABLE as CHAT_TB;
+        use crate::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
         db.query(format!(
             "SELECT *
-            FROM {FN_GET_TRANSFERS}(${CHAT})
+            FROM {FN_GET_TRANSFERS}(${CHAT_ID}.{ACTIVE_TRIP})
             ORDER BY {NUMBER} ASC",
         ))
-        .bind((CHAT, RecordId::from_table_key(CHAT_TB, chat_id.0)))
+        .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)))
         .await
         .and_then(|mut response| response.take::<Vec<Self>>(0))
     }
 
-    pub async fn transfers_by_name(
+    pub async fn transfers_filtered(
         db: Arc<Surreal<Any>>,
         chat_id: ChatId,
-        name: Name,
+        filter: &Filter,
+        tz: &TimeZone,
     ) -> Result<Vec<Self>, surrealdb::Error> {
-        use crate::{
-            chat::TABLE as CHAT_TB,
-            traveler::{CHAT, NAME},
-        };
+        use crate::chat::{ACTIVE_TRIP, ID as CHAT_ID, TABLE as CHAT_TB};
 
-        db.query(format!(
-            "SELECT *
-            FROM {FN_GET_TRANSFERS}(${CHAT})
-            WHERE string::lowercase({SENDER_NAME}) = string::lowercase(${NAME})
-                || string::lowercase({RECEIVER_NAME}) = string::lowercase(${NAME})
-            ORDER BY {NUMBER} ASC",
-        ))
-        .bind((CHAT, RecordId::from_table_key(CHAT_TB, chat_id.0)))
-        .bind((NAME, name))
-        .await
-        .and_then(|mut response| response.take::<Vec<Self>>(0))
+        let filter = filter.compile(tz);
+        let query = db
+            .query(format!(
+                "SELECT *
+                FROM {FN_GET_TRANSFERS}(${CHAT_ID}.{ACTIVE_TRIP})
+                WHERE {condition}
+                ORDER BY {order}",
+                condition = filter.condition,
+                order = filter.order,
+            ))
+            .bind((CHAT_ID, RecordId::from_table_key(CHAT_TB, chat_id.0)));
+        filter
+            .bind(query)
+            .await
+            .and_then(|mut response| response.take::<Vec<Self>>(0))
     }
 }
 
@@ -75,6 +79,7 @@ impl Translate for Transfer {
         indent_lvl: usize,
     ) -> String {
         let amount = MoneyWrapper::new_with_context(self.amount, ctx.clone());
+        let tz = ctx.lock().expect("Failed to lock context").timezone.clone();
         i18n::format::FORMAT_TRANSFER.translate_with_args_indent(
             ctx,
             &hashmap! {
@@ -82,7 +87,7 @@ impl Translate for Transfer {
                 i18n::args::SENDER.into() => self.sender_name.clone().into(),
                 i18n::args::RECEIVER.into() => self.receiver_name.clone().into(),
                 i18n::args::AMOUNT.into() => amount.to_string().into(),
-                i18n::args::DATETIME.into() => self.timestamp_utc.to_fluent_datetime().unwrap().into(),
+                i18n::args::DATETIME.into() => self.timestamp_utc.to_fluent_datetime(&tz).unwrap().into(),
             },
             indent_lvl,
         )
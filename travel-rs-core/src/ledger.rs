@@ -0,0 +1,432 @@
+use crate::is_zero;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The share of an expense a traveler takes on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Split<Id> {
+    pub traveler: Id,
+    pub amount: Decimal,
+}
+
+/// An expense paid by a traveler and split among some travelers, possibly
+/// including the payer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Expense<Id> {
+    pub payer: Id,
+    pub amount: Decimal,
+    pub splits: Vec<Split<Id>>,
+}
+
+impl<Id: Clone> Expense<Id> {
+    /// Changes the expense makes to the net positions of the travelers: the
+    /// payer is owed every share, and each traveler owes their own.
+    ///
+    /// The payer is credited with the shares rather than with the amount, so
+    /// that the changes always add up to zero.
+    pub fn net_changes(&self) -> Vec<(Id, Decimal)> {
+        self.splits
+            .iter()
+            .flat_map(|split| {
+                [
+                    (self.payer.clone(), split.amount),
+                    (split.traveler.clone(), -split.amount),
+                ]
+            })
+            .collect()
+    }
+}
+
+/// Money sent by a traveler to another one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Transfer<Id> {
+    pub from: Id,
+    pub to: Id,
+    pub amount: Decimal,
+}
+
+impl<Id: Clone> Transfer<Id> {
+    /// Changes the transfer makes to the net positions of the travelers: the
+    /// sender is owed the amount, and the receiver owes it.
+    pub fn net_changes(&self) -> Vec<(Id, Decimal)> {
+        vec![
+            (self.from.clone(), self.amount),
+            (self.to.clone(), -self.amount),
+        ]
+    }
+}
+
+/// Money a traveler owes to another one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Debt<Id> {
+    pub debtor: Id,
+    pub creditor: Id,
+    pub debt: Decimal,
+}
+
+/// Returns what each traveler owes to each other one after `expenses` and
+/// `transfers`, sorted by debtor and creditor.
+///
+/// Every traveler owes the payer of an expense their share of it, less what
+/// they already sent to the payer: a transfer exceeding the shares, or made
+/// without any, leaves the receiver owing the difference. Debts in opposite
+/// directions between two travelers are kept apart, see [`net_pairs`].
+pub fn debts<Id: Clone + Ord>(
+    expenses: &[Expense<Id>],
+    transfers: &[Transfer<Id>],
+) -> Vec<Debt<Id>> {
+    let mut owed: BTreeMap<(Id, Id), Decimal> = BTreeMap::new();
+    for expense in expenses {
+        for split in expense
+            .splits
+            .iter()
+            .filter(|split| split.traveler != expense.payer)
+        {
+            *owed
+                .entry((split.traveler.clone(), expense.payer.clone()))
+                .or_default() += split.amount;
+        }
+    }
+    for transfer in transfers
+        .iter()
+        .filter(|transfer| transfer.from != transfer.to)
+    {
+        *owed
+            .entry((transfer.from.clone(), transfer.to.clone()))
+            .or_default() -= transfer.amount;
+    }
+
+    let mut debts: BTreeMap<(Id, Id), Decimal> = BTreeMap::new();
+    for ((debtor, creditor), amount) in owed {
+        if amount > Decimal::ZERO {
+            *debts.entry((debtor, creditor)).or_default() += amount;
+        } else if amount < Decimal::ZERO {
+            *debts.entry((creditor, debtor)).or_default() -= amount;
+        }
+    }
+    debts
+        .into_iter()
+        .map(|((debtor, creditor), debt)| Debt {
+            debtor,
+            creditor,
+            debt,
+        })
+        .collect()
+}
+
+/// Returns the net position of every traveler taking part in `expenses` and
+/// `transfers`: what they paid for others and sent, less what they consumed
+/// and received. Positive if the traveler is owed money, negative if they owe
+/// it.
+pub fn net_positions<Id: Clone + Ord>(
+    expenses: &[Expense<Id>],
+    transfers: &[Transfer<Id>],
+) -> BTreeMap<Id, Decimal> {
+    let mut nets: BTreeMap<Id, Decimal> = BTreeMap::new();
+    let changes = expenses
+        .iter()
+        .flat_map(Expense::net_changes)
+        .chain(transfers.iter().flat_map(Transfer::net_changes));
+    for (traveler, change) in changes {
+        *nets.entry(traveler).or_default() += change;
+    }
+    nets
+}
+
+/// Returns the net position of every traveler involved in `debts`: positive
+/// for creditors, negative for debtors.
+pub fn balances<Id: Clone + Ord>(debts: &[Debt<Id>]) -> BTreeMap<Id, Decimal> {
+    let mut balances: BTreeMap<Id, Decimal> = BTreeMap::new();
+    for debt in debts {
+        *balances.entry(debt.debtor.clone()).or_default() -= debt.debt;
+        *balances.entry(debt.creditor.clone()).or_default() += debt.debt;
+    }
+    balances
+}
+
+/// Nets the debts in opposite directions between each pair of travelers,
+/// keeping a single debt per pair in the direction of the larger one.
+pub fn net_pairs<Id: Clone + Ord>(debts: &mut Vec<Debt<Id>>) {
+    let mut pairs: Vec<Debt<Id>> = Vec::new();
+    let mut indices: BTreeMap<(Id, Id), usize> = BTreeMap::new();
+
+    for debt in debts.drain(..) {
+        let key = (debt.debtor.clone(), debt.creditor.clone());
+        let reversed = (key.1.clone(), key.0.clone());
+        if let Some(&i) = indices.get(&reversed) {
+            pairs[i].debt -= debt.debt;
+        } else if let Some(&i) = indices.get(&key) {
+            pairs[i].debt += debt.debt;
+        } else {
+            indices.insert(key, pairs.len());
+            pairs.push(debt);
+        }
+    }
+
+    *debts = pairs
+        .into_iter()
+        .filter(|pair| !is_zero(pair.debt))
+        .map(|pair| {
+            if pair.debt < Decimal::ZERO {
+                Debt {
+                    debtor: pair.creditor,
+                    creditor: pair.debtor,
+                    debt: -pair.debt,
+                }
+            } else {
+                pair
+            }
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        settlement::{SettlementMode, SimplificationMode, settle_nets, simplify_debts},
+        shares::{ShareAmount, compute_shares},
+    };
+    use proptest::prelude::*;
+
+    fn debt(debtor: &'static str, creditor: &'static str, cents: i64) -> Debt<&'static str> {
+        Debt {
+            debtor,
+            creditor,
+            debt: Decimal::new(cents, 2),
+        }
+    }
+
+    fn expense(payer: &'static str, cents: i64, among: &[&'static str]) -> Expense<&'static str> {
+        let shares = compute_shares(
+            Decimal::new(cents, 2),
+            among.iter().map(|&t| (t, ShareAmount::Dynamic)).collect(),
+        )
+        .unwrap();
+        Expense {
+            payer,
+            amount: Decimal::new(cents, 2),
+            splits: shares
+                .into_iter()
+                .map(|(traveler, amount)| Split { traveler, amount })
+                .collect(),
+        }
+    }
+
+    fn transfer(from: &'static str, to: &'static str, cents: i64) -> Transfer<&'static str> {
+        Transfer {
+            from,
+            to,
+            amount: Decimal::new(cents, 2),
+        }
+    }
+
+    #[test]
+    fn transfers_offset_shares() {
+        let expenses = vec![
+            expense("alice", 3_000, &["alice", "bob", "carol"]),
+            expense("bob", 1_000, &["alice", "bob"]),
+        ];
+        let transfers = vec![
+            transfer("carol", "alice", 400),
+            // More than Bob owes Alice: Alice now owes Bob the difference
+            transfer("bob", "alice", 1_500),
+            // Without any share: Dave owes Carol
+            transfer("carol", "dave", 200),
+        ];
+        assert_eq!(
+            debts(&expenses, &transfers),
+            vec![
+                // Alice's share of Bob's expense, plus what Bob sent in excess
+                debt("alice", "bob", 1_000),
+                debt("carol", "alice", 600),
+                debt("dave", "carol", 200),
+            ]
+        );
+    }
+
+    #[test]
+    fn net_pairs_cancels_opposite_debts() {
+        let mut debts = vec![
+            debt("alice", "bob", 1_000),
+            debt("bob", "alice", 400),
+            debt("carol", "bob", 500),
+            debt("bob", "carol", 500),
+            debt("carol", "alice", 300),
+            debt("dave", "alice", 200),
+            debt("alice", "dave", 700),
+        ];
+        net_pairs(&mut debts);
+        assert_eq!(
+            debts,
+            vec![
+                debt("alice", "bob", 600),
+                debt("carol", "alice", 300),
+                debt("alice", "dave", 500),
+            ]
+        );
+    }
+
+    #[test]
+    fn net_pairs_keeps_who_owes_whom() {
+        // Alice owes Bob and Bob owes Carol: simplifying fully would have
+        // Alice pay Carol, who never paid for her
+        let mut debts = vec![debt("alice", "bob", 1_000), debt("bob", "carol", 1_000)];
+        let raw = debts.clone();
+        net_pairs(&mut debts);
+        assert_eq!(debts, raw);
+
+        let simplified = simplify_debts(
+            debts,
+            SimplificationMode::Full,
+            SettlementMode::Exact,
+            &[],
+            &[],
+        );
+        assert_eq!(simplified, vec![debt("alice", "carol", 1_000)]);
+    }
+
+    const TRAVELERS: [&str; 5] = ["alice", "bob", "carol", "dave", "eve"];
+
+    /// Expenses split equally among some of the travelers, in cents.
+    fn expenses() -> impl Strategy<Value = Vec<Expense<&'static str>>> {
+        prop::collection::vec(
+            (
+                0..TRAVELERS.len(),
+                1i64..100_000,
+                prop::sample::subsequence(TRAVELERS.to_vec(), 1..=TRAVELERS.len()),
+            ),
+            0..10,
+        )
+        .prop_map(|expenses| {
+            expenses
+                .into_iter()
+                .map(|(payer, cents, among)| expense(TRAVELERS[payer], cents, &among))
+                .collect()
+        })
+    }
+
+    fn transfers() -> impl Strategy<Value = Vec<Transfer<&'static str>>> {
+        prop::collection::vec((0..TRAVELERS.len(), 0..TRAVELERS.len(), 1i64..50_000), 0..6)
+            .prop_map(|transfers| {
+                transfers
+                    .into_iter()
+                    .map(|(from, to, cents)| transfer(TRAVELERS[from], TRAVELERS[to], cents))
+                    .collect()
+            })
+    }
+
+    /// Whether `left` and `right` assign the same amount to every traveler,
+    /// reading a missing traveler as zero.
+    fn same_positions(left: &BTreeMap<&str, Decimal>, right: &BTreeMap<&str, Decimal>) -> bool {
+        TRAVELERS.iter().all(|traveler| {
+            let amount = |positions: &BTreeMap<&str, Decimal>| {
+                positions.get(traveler).copied().unwrap_or_default()
+            };
+            is_zero(amount(left) - amount(right))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn net_positions_sum_to_zero(expenses in expenses(), transfers in transfers()) {
+            let total: Decimal = net_positions(&expenses, &transfers).values().sum();
+            prop_assert!(is_zero(total), "{total}");
+        }
+
+        #[test]
+        fn debts_conserve_money(expenses in expenses(), transfers in transfers()) {
+            let debts = debts(&expenses, &transfers);
+            prop_assert!(debts.iter().all(|debt| debt.debt > Decimal::ZERO));
+            prop_assert!(debts.iter().all(|debt| debt.debtor != debt.creditor));
+            prop_assert!(same_positions(&balances(&debts), &net_positions(&expenses, &transfers)));
+        }
+
+        #[test]
+        fn simplification_conserves_money(
+            expenses in expenses(),
+            transfers in transfers(),
+            greedy in any::<bool>(),
+        ) {
+            let debts = debts(&expenses, &transfers);
+            let expected = balances(&debts);
+            let settlement = if greedy { SettlementMode::Greedy } else { SettlementMode::Exact };
+            for simplification in [
+                SimplificationMode::None,
+                SimplificationMode::Pairwise,
+                SimplificationMode::Full,
+            ] {
+                let simplified = simplify_debts(debts.clone(), simplification, settlement, &[], &[]);
+                prop_assert!(same_positions(&balances(&simplified), &expected));
+            }
+        }
+
+        #[test]
+        fn incremental_nets_match_a_rebuild(
+            expenses in expenses(),
+            transfers in transfers(),
+            deleted in prop::collection::vec(any::<bool>(), 1..8),
+        ) {
+            let is_deleted = |i: usize| deleted[i % deleted.len()];
+            let entries: Vec<_> = expenses
+                .iter()
+                .map(Expense::net_changes)
+                .chain(transfers.iter().map(Transfer::net_changes))
+                .collect();
+
+            // Record every entry, then undo the deleted ones
+            let mut nets: BTreeMap<&str, Decimal> = BTreeMap::new();
+            for (traveler, change) in entries.iter().flatten() {
+                *nets.entry(traveler).or_default() += change;
+            }
+            for (_, entry) in entries.iter().enumerate().filter(|(i, _)| is_deleted(*i)) {
+                for (traveler, change) in entry {
+                    *nets.entry(traveler).or_default() -= change;
+                }
+            }
+
+            let kept_expenses: Vec<_> = expenses
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !is_deleted(*i))
+                .map(|(_, expense)| expense.clone())
+                .collect();
+            let kept_transfers: Vec<_> = transfers
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !is_deleted(expenses.len() + i))
+                .map(|(_, transfer)| transfer.clone())
+                .collect();
+            let rebuilt = net_positions(&kept_expenses, &kept_transfers);
+            prop_assert!(same_positions(&nets, &rebuilt));
+
+            // Settling the nets directly conserves every balance
+            let nets: Vec<_> = nets.into_iter().collect();
+            for settlement in [SettlementMode::Greedy, SettlementMode::Exact] {
+                let settled = settle_nets(&nets, settlement, &[], &[]);
+                prop_assert!(same_positions(&balances(&settled), &rebuilt));
+            }
+        }
+
+        #[test]
+        fn settling_clears_every_debt(expenses in expenses(), transfers in transfers()) {
+            let mut transfers = transfers;
+            let plan = simplify_debts(
+                debts(&expenses, &transfers),
+                SimplificationMode::Full,
+                SettlementMode::Exact,
+                &[],
+                &[],
+            );
+            // Recording the transfers of the plan leaves nobody owing anything
+            transfers.extend(plan.into_iter().map(|debt| Transfer {
+                from: debt.debtor,
+                to: debt.creditor,
+                amount: debt.debt,
+            }));
+            let left = net_positions(&expenses, &transfers);
+            prop_assert!(left.values().all(|&net| is_zero(net)), "{left:?}");
+        }
+    }
+}
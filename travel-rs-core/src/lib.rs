@@ -0,0 +1,25 @@
+//! Ledger engine of travel-rs: computes the shares of expenses, the debts
+//! they leave between travelers and the transfers settling them.
+//!
+//! Nothing here knows about the database or the bot: travelers are
+//! identified by any ordered type, so the same functions work on record ids,
+//! names or plain indices.
+
+pub mod ledger;
+pub mod settlement;
+pub mod shares;
+
+pub use ledger::{Debt, Expense, Split, Transfer};
+pub use settlement::{SettlementMode, SimplificationMode};
+pub use shares::{ShareAmount, ShareError};
+
+use rust_decimal::Decimal;
+
+/// Amounts closer to zero than this are considered settled, absorbing the
+/// rounding of amounts split in non-terminating fractions.
+pub const ZERO_TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 12);
+
+/// Whether `amount` is zero, within [`ZERO_TOLERANCE`].
+pub fn is_zero(amount: Decimal) -> bool {
+    amount.abs() < ZERO_TOLERANCE
+}
@@ -0,0 +1,632 @@
+use crate::{
+    ZERO_TOLERANCE, is_zero,
+    ledger::{Debt, net_pairs},
+};
+use rust_decimal::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use strum::{AsRefStr, EnumString};
+
+/// Algorithm used to turn the net balances of the travelers into the
+/// transfers settling them.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, AsRefStr, EnumString,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum SettlementMode {
+    /// Minimum number of transfers, falling back to [`Self::Greedy`] for
+    /// groups too large to be solved exactly.
+    #[default]
+    Exact,
+    /// Repeatedly matches the largest debtor with the largest creditor.
+    Greedy,
+}
+
+/// How far the debts resulting from expenses and transfers are simplified
+/// before being stored.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, AsRefStr, EnumString,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum SimplificationMode {
+    /// Every debt is kept as it results from the expenses and transfers, so
+    /// travelers only pay who paid for them.
+    None,
+    /// Debts in opposite directions between the same two travelers cancel
+    /// each other out.
+    Pairwise,
+    /// Debts are replaced by the transfers settling the net balances, as
+    /// chosen by the [`SettlementMode`].
+    #[default]
+    Full,
+}
+
+/// Largest number of travelers with a non-zero balance the exact solver
+/// handles: its cost grows as `2^n`.
+pub const EXACT_SOLVER_MAX_TRAVELERS: usize = 16;
+
+/// Settles `balances` (positive for creditors, negative for debtors) by
+/// repeatedly matching the largest debtor with the largest creditor.
+/// Returns `(debtor, creditor, amount)` triples of indices into `balances`.
+fn greedy_transfers(balances: &[Decimal], members: &[usize]) -> Vec<(usize, usize, Decimal)> {
+    let mut creditors: Vec<_> = members
+        .iter()
+        .map(|&i| (i, balances[i]))
+        .filter(|&(_, v)| v > Decimal::ZERO)
+        .collect();
+    let mut debtors: Vec<_> = members
+        .iter()
+        .map(|&i| (i, balances[i]))
+        .filter(|&(_, v)| v < Decimal::ZERO)
+        .collect();
+
+    // Ties are broken by index, so that the result doesn't depend on the
+    // order the balances were collected in
+    creditors.sort_by_key(|&(i, v)| (std::cmp::Reverse(v), std::cmp::Reverse(i)));
+    debtors.sort_by_key(|&(i, v)| (v, std::cmp::Reverse(i)));
+
+    let mut transfers = Vec::new();
+    while !debtors.is_empty() && !creditors.is_empty() {
+        let (debtor, mut debtor_amount) = debtors.pop().unwrap();
+        let (creditor, mut creditor_amount) = creditors.pop().unwrap();
+
+        let amount = debtor_amount.abs().min(creditor_amount);
+        transfers.push((debtor, creditor, amount));
+
+        debtor_amount += amount;
+        if debtor_amount < Decimal::ZERO {
+            debtors.push((debtor, debtor_amount));
+        }
+
+        creditor_amount -= amount;
+        if creditor_amount > Decimal::ZERO {
+            creditors.push((creditor, creditor_amount));
+        }
+    }
+    transfers
+}
+
+/// Partitions `members` into the largest number of groups whose balances sum
+/// to zero. Settling a group of `k` travelers takes at least `k - 1`
+/// transfers, so the more groups, the fewer transfers overall.
+fn zero_sum_groups(balances: &[Decimal], members: &[usize]) -> Vec<Vec<usize>> {
+    let n = members.len();
+    let full = (1usize << n) - 1;
+
+    let mut sums = vec![Decimal::ZERO; full + 1];
+    for mask in 1..=full {
+        let lowest = mask.trailing_zeros() as usize;
+        sums[mask] = sums[mask & (mask - 1)] + balances[members[lowest]];
+    }
+
+    // groups[mask]: the largest number of zero-sum groups `mask` can be
+    // split into, counting its leftover as a group if it sums to zero
+    let mut groups = vec![0u32; full + 1];
+    for mask in 1..=full {
+        let best = (0..n)
+            .filter(|i| mask & (1 << i) != 0)
+            .map(|i| groups[mask ^ (1 << i)])
+            .max()
+            .unwrap_or(0);
+        groups[mask] = best + u32::from(is_zero(sums[mask]));
+    }
+
+    // Walk back removing one member at a time: every zero-sum mask met on
+    // the way closes a group
+    let mut partition = Vec::new();
+    let mut current = Vec::new();
+    let mut mask = full;
+    while mask != 0 {
+        if is_zero(sums[mask]) && !current.is_empty() {
+            partition.push(std::mem::take(&mut current));
+        }
+        let zero = u32::from(is_zero(sums[mask]));
+        let i = (0..n)
+            .find(|&i| mask & (1 << i) != 0 && groups[mask ^ (1 << i)] + zero == groups[mask])
+            .expect("A member leading to the optimum should exist");
+        current.push(members[i]);
+        mask ^= 1 << i;
+    }
+    if !current.is_empty() {
+        partition.push(current);
+    }
+    partition
+}
+
+/// Extra cost of a transfer that doesn't go through the traveler its debtor
+/// or creditor prefers to settle via.
+const PREFERENCE_PENALTY: i64 = 2;
+
+/// Payment preferences of the travelers, as indices into the balances.
+#[derive(Debug, Default)]
+struct Preferences {
+    /// `(debtor, creditor)` pairs where the debtor can't pay the creditor.
+    cannot_pay: HashSet<(usize, usize)>,
+    /// The traveler each traveler prefers to settle via.
+    settles_via: HashMap<usize, usize>,
+}
+
+impl Preferences {
+    fn is_empty(&self) -> bool {
+        self.cannot_pay.is_empty() && self.settles_via.is_empty()
+    }
+
+    /// Cost of moving one unit of money from `debtor` to `creditor`, among
+    /// `travelers` travelers: 1 for a transfer meeting every preference, more
+    /// for one ignoring a preferred payee, and more than any chain of allowed
+    /// transfers for a forbidden one.
+    fn cost(&self, debtor: usize, creditor: usize, travelers: usize) -> i64 {
+        let max_allowed = 1 + 2 * PREFERENCE_PENALTY;
+        if self.cannot_pay.contains(&(debtor, creditor)) {
+            return max_allowed * travelers as i64;
+        }
+        let mut cost = 1;
+        for (traveler, other) in [(debtor, creditor), (creditor, debtor)] {
+            if self
+                .settles_via
+                .get(&traveler)
+                .is_some_and(|&via| via != other)
+            {
+                cost += PREFERENCE_PENALTY;
+            }
+        }
+        cost
+    }
+
+    /// Total cost of `transfers` among `travelers` travelers.
+    fn total_cost(&self, transfers: &[(usize, usize, Decimal)], travelers: usize) -> Decimal {
+        transfers
+            .iter()
+            .map(|&(debtor, creditor, amount)| {
+                amount * Decimal::from(self.cost(debtor, creditor, travelers))
+            })
+            .sum()
+    }
+}
+
+/// Settles `balances` with the cheapest flow of money according to
+/// `preferences`, possibly passing through travelers who are already
+/// settled. Transfers a debtor can't make are only used if there is no
+/// other way to settle.
+///
+/// Uses successive shortest paths: money is repeatedly moved from a debtor
+/// to a creditor along the cheapest route, which can also reroute money
+/// moved earlier.
+fn preferred_transfers(
+    balances: &[Decimal],
+    preferences: &Preferences,
+) -> Vec<(usize, usize, Decimal)> {
+    let n = balances.len();
+    let mut to_pay: Vec<Decimal> = balances.iter().map(|&b| (-b).max(Decimal::ZERO)).collect();
+    let mut to_receive: Vec<Decimal> = balances.iter().map(|&b| b.max(Decimal::ZERO)).collect();
+    let mut flow = vec![vec![Decimal::ZERO; n]; n];
+    // Moving money from `u` to `v` reroutes money moved from `v` to `u`, if
+    // any, instead of adding a transfer
+    let reroutes = |flow: &[Vec<Decimal>], u: usize, v: usize| flow[v][u] > ZERO_TOLERANCE;
+
+    loop {
+        // Bellman-Ford from every debtor with something left to pay
+        let mut dist: Vec<Option<i64>> = to_pay
+            .iter()
+            .map(|&amount| (amount > ZERO_TOLERANCE).then_some(0))
+            .collect();
+        let mut pred: Vec<Option<usize>> = vec![None; n];
+        for _ in 0..n {
+            let mut changed = false;
+            for u in 0..n {
+                let Some(du) = dist[u] else { continue };
+                for v in (0..n).filter(|&v| v != u) {
+                    let cost = if reroutes(&flow, u, v) {
+                        -preferences.cost(v, u, n)
+                    } else {
+                        preferences.cost(u, v, n)
+                    };
+                    if dist[v].is_none_or(|dv| du + cost < dv) {
+                        dist[v] = Some(du + cost);
+                        pred[v] = Some(u);
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let Some(creditor) = (0..n)
+            .filter(|&i| to_receive[i] > ZERO_TOLERANCE)
+            .filter_map(|i| dist[i].map(|d| (d, i)))
+            .min()
+            .map(|(_, i)| i)
+        else {
+            break;
+        };
+
+        let mut path = vec![creditor];
+        while let Some(u) = pred[*path.last().unwrap()] {
+            path.push(u);
+        }
+        path.reverse();
+        let debtor = path[0];
+
+        let amount = path
+            .windows(2)
+            .filter(|edge| reroutes(&flow, edge[0], edge[1]))
+            .map(|edge| flow[edge[1]][edge[0]])
+            .fold(to_pay[debtor].min(to_receive[creditor]), Decimal::min);
+        for edge in path.windows(2) {
+            let (u, v) = (edge[0], edge[1]);
+            if reroutes(&flow, u, v) {
+                flow[v][u] -= amount;
+            } else {
+                flow[u][v] += amount;
+            }
+        }
+        to_pay[debtor] -= amount;
+        to_receive[creditor] -= amount;
+    }
+
+    (0..n)
+        .flat_map(|i| (0..n).map(move |j| (i, j)))
+        .filter(|&(i, j)| flow[i][j] > ZERO_TOLERANCE)
+        .map(|(i, j)| (i, j, flow[i][j]))
+        .collect()
+}
+
+/// Returns the transfers settling `balances` according to `mode`, as
+/// `(debtor, creditor, amount)` triples of indices into `balances`.
+fn settle_balances(balances: &[Decimal], mode: SettlementMode) -> Vec<(usize, usize, Decimal)> {
+    let members: Vec<usize> = (0..balances.len())
+        .filter(|&i| !is_zero(balances[i]))
+        .collect();
+
+    match mode {
+        SettlementMode::Exact if members.len() <= EXACT_SOLVER_MAX_TRAVELERS => {
+            zero_sum_groups(balances, &members)
+                .iter()
+                .flat_map(|group| greedy_transfers(balances, group))
+                .collect()
+        }
+        SettlementMode::Exact => {
+            tracing::debug!(
+                "{} travelers to settle, falling back to the greedy algorithm",
+                members.len()
+            );
+            greedy_transfers(balances, &members)
+        }
+        SettlementMode::Greedy => greedy_transfers(balances, &members),
+    }
+}
+
+/// Returns the transfers settling `balances` according to `mode`, unless
+/// they don't meet `preferences` and the cheapest flow of money does better.
+fn settle_with_preferences(
+    balances: &[Decimal],
+    mode: SettlementMode,
+    preferences: &Preferences,
+) -> Vec<(usize, usize, Decimal)> {
+    let transfers = settle_balances(balances, mode);
+    let n = balances.len();
+    if preferences.is_empty()
+        || transfers
+            .iter()
+            .all(|&(debtor, creditor, _)| preferences.cost(debtor, creditor, n) == 1)
+    {
+        return transfers;
+    }
+
+    let preferred = preferred_transfers(balances, preferences);
+    if preferences.total_cost(&preferred, n) < preferences.total_cost(&transfers, n) {
+        preferred
+    } else {
+        transfers
+    }
+}
+
+/// Returns the transfers settling the net positions `nets` (positive for
+/// creditors, negative for debtors) according to `mode`.
+///
+/// `cannot_pay` and `settles_via` are `(traveler, other traveler)` payment
+/// preferences, met whenever possible.
+pub fn settle_nets<Id: Clone + Ord>(
+    nets: &[(Id, Decimal)],
+    mode: SettlementMode,
+    cannot_pay: &[(Id, Id)],
+    settles_via: &[(Id, Id)],
+) -> Vec<Debt<Id>> {
+    let mut ids: Vec<Id> = Vec::new();
+    let mut indices: BTreeMap<Id, usize> = BTreeMap::new();
+    let mut balances: Vec<Decimal> = Vec::new();
+    let mut index = |id: &Id| {
+        *indices.entry(id.clone()).or_insert_with(|| {
+            ids.push(id.clone());
+            balances.push(Decimal::ZERO);
+            ids.len() - 1
+        })
+    };
+
+    let amounts: Vec<_> = nets.iter().map(|(id, net)| (index(id), *net)).collect();
+    // Travelers already settled up can still pass money on
+    let mut preferences = Preferences::default();
+    for (traveler, other) in cannot_pay {
+        preferences
+            .cannot_pay
+            .insert((index(traveler), index(other)));
+    }
+    for (traveler, via) in settles_via {
+        let pair = (index(traveler), index(via));
+        preferences.settles_via.insert(pair.0, pair.1);
+    }
+    for (i, amount) in amounts {
+        balances[i] += amount;
+    }
+
+    settle_with_preferences(&balances, mode, &preferences)
+        .into_iter()
+        .map(|(debtor, creditor, amount)| Debt {
+            debtor: ids[debtor].clone(),
+            creditor: ids[creditor].clone(),
+            debt: amount,
+        })
+        .collect()
+}
+
+/// Replaces `debts` with the transfers settling the net balances of the
+/// travelers according to `mode`.
+fn simplify_balances<Id: Clone + Ord>(
+    debts: Vec<Debt<Id>>,
+    mode: SettlementMode,
+    cannot_pay: &[(Id, Id)],
+    settles_via: &[(Id, Id)],
+) -> Vec<Debt<Id>> {
+    let nets: Vec<(Id, Decimal)> = debts
+        .into_iter()
+        .flat_map(|debt| [(debt.debtor, -debt.debt), (debt.creditor, debt.debt)])
+        .collect();
+    settle_nets(&nets, mode, cannot_pay, settles_via)
+}
+
+/// Simplifies `debts` according to `simplification`, settling them
+/// according to `settlement` when they are simplified fully.
+///
+/// `cannot_pay` and `settles_via` are `(traveler, other traveler)` payment
+/// preferences, only taken into account by a full simplification.
+pub fn simplify_debts<Id: Clone + Ord>(
+    mut debts: Vec<Debt<Id>>,
+    simplification: SimplificationMode,
+    settlement: SettlementMode,
+    cannot_pay: &[(Id, Id)],
+    settles_via: &[(Id, Id)],
+) -> Vec<Debt<Id>> {
+    match simplification {
+        SimplificationMode::None => debts,
+        SimplificationMode::Pairwise => {
+            net_pairs(&mut debts);
+            debts
+        }
+        SimplificationMode::Full => simplify_balances(debts, settlement, cannot_pay, settles_via),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn decimals(cents: &[i64]) -> Vec<Decimal> {
+        cents.iter().map(|&c| Decimal::new(c, 2)).collect()
+    }
+
+    /// Applies `transfers` to `balances`, which should all end up at zero.
+    fn assert_settled(balances: &[Decimal], transfers: &[(usize, usize, Decimal)]) {
+        let mut left = balances.to_vec();
+        for &(debtor, creditor, amount) in transfers {
+            assert!(amount > Decimal::ZERO);
+            left[debtor] += amount;
+            left[creditor] -= amount;
+        }
+        assert!(left.iter().all(|&b| is_zero(b)), "{left:?}");
+    }
+
+    /// Balances of up to 10 travelers summing to zero, in cents.
+    fn balances() -> impl Strategy<Value = Vec<Decimal>> {
+        prop::collection::vec(-5_000i64..5_000, 1..10).prop_map(|mut cents| {
+            cents.push(-cents.iter().sum::<i64>());
+            decimals(&cents)
+        })
+    }
+
+    #[test]
+    fn exact_splits_zero_sum_groups() {
+        let balances = decimals(&[500, 400, -400, -300, -200]);
+
+        let greedy = settle_balances(&balances, SettlementMode::Greedy);
+        let exact = settle_balances(&balances, SettlementMode::Exact);
+        assert_eq!(greedy.len(), 4);
+        assert_eq!(exact.len(), 3);
+        assert_settled(&balances, &greedy);
+        assert_settled(&balances, &exact);
+    }
+
+    #[test]
+    fn exact_falls_back_to_greedy() {
+        let cents: Vec<i64> = (1..=EXACT_SOLVER_MAX_TRAVELERS as i64)
+            .flat_map(|c| [c * 100 + 1, -(c * 100 + 1)])
+            .collect();
+        let balances = decimals(&cents);
+
+        let exact = settle_balances(&balances, SettlementMode::Exact);
+        assert_eq!(exact, settle_balances(&balances, SettlementMode::Greedy));
+        assert_settled(&balances, &exact);
+    }
+
+    #[test]
+    fn non_terminating_splits() {
+        // 100 split among 3, paid by the first traveler
+        let third = Decimal::ONE_HUNDRED / Decimal::from(3);
+        let balances = vec![third * Decimal::TWO, -third, -third];
+
+        let exact = settle_balances(&balances, SettlementMode::Exact);
+        assert_eq!(exact.len(), 2);
+    }
+
+    #[test]
+    fn residues_are_settled() {
+        // Rounding residues, below the tolerance, of travelers already even
+        let residue = ZERO_TOLERANCE / Decimal::TEN;
+        let mut balances = decimals(&[500, 400, -400, -300, -200]);
+        for _ in 0..EXACT_SOLVER_MAX_TRAVELERS {
+            balances.extend([residue, -residue]);
+        }
+
+        // The residues neither get transfers nor push the exact solver
+        // past its limit
+        let exact = settle_balances(&balances, SettlementMode::Exact);
+        assert_eq!(exact.len(), 3);
+        assert!(
+            exact
+                .iter()
+                .all(|&(debtor, creditor, _)| debtor < 5 && creditor < 5)
+        );
+        assert_settled(&balances, &exact);
+    }
+
+    fn preferences(cannot_pay: &[(usize, usize)], settles_via: &[(usize, usize)]) -> Preferences {
+        Preferences {
+            cannot_pay: cannot_pay.iter().copied().collect(),
+            settles_via: settles_via.iter().copied().collect(),
+        }
+    }
+
+    #[test]
+    fn preferences_route_around_cannot_pay() {
+        // The first traveler owes the second one but can't pay them: the
+        // third one, already settled, passes the money on
+        let balances = decimals(&[-1_000, 1_000, 0]);
+        let transfers = settle_with_preferences(
+            &balances,
+            SettlementMode::Exact,
+            &preferences(&[(0, 1)], &[]),
+        );
+        assert_eq!(transfers, vec![(0, 2, Decimal::TEN), (2, 1, Decimal::TEN)]);
+        assert_settled(&balances, &transfers);
+    }
+
+    #[test]
+    fn preferences_fall_back_when_unmet() {
+        let balances = decimals(&[-1_000, 1_000]);
+        let transfers = settle_with_preferences(
+            &balances,
+            SettlementMode::Exact,
+            &preferences(&[(0, 1)], &[]),
+        );
+        assert_eq!(transfers, vec![(0, 1, Decimal::TEN)]);
+    }
+
+    #[test]
+    fn preferences_settle_via_banker() {
+        // Everyone settles via the last traveler
+        let balances = decimals(&[3_000, -1_000, -1_000, -1_000]);
+        let transfers = settle_with_preferences(
+            &balances,
+            SettlementMode::Exact,
+            &preferences(&[], &[(0, 3), (1, 3), (2, 3)]),
+        );
+        assert_eq!(
+            transfers,
+            vec![
+                (1, 3, Decimal::TEN),
+                (2, 3, Decimal::TEN),
+                (3, 0, Decimal::from(30)),
+            ]
+        );
+        assert_settled(&balances, &transfers);
+    }
+
+    #[test]
+    fn preferences_keep_plan_when_met() {
+        let balances = decimals(&[500, 400, -400, -300, -200]);
+        let plan = settle_balances(&balances, SettlementMode::Exact);
+        let transfers = settle_with_preferences(
+            &balances,
+            SettlementMode::Exact,
+            &preferences(&[(2, 0)], &[]),
+        );
+        assert!(
+            plan.iter()
+                .all(|&(debtor, creditor, _)| (debtor, creditor) != (2, 0))
+        );
+        assert_eq!(transfers, plan);
+    }
+
+    #[test]
+    fn simplification_mode_from_str() {
+        assert_eq!(
+            "Pairwise".parse::<SimplificationMode>().unwrap(),
+            SimplificationMode::Pairwise
+        );
+        assert_eq!(SimplificationMode::default().as_ref(), "full");
+        assert_eq!(SimplificationMode::None.as_ref(), "none");
+        assert!("some".parse::<SimplificationMode>().is_err());
+    }
+
+    #[test]
+    fn settlement_mode_from_str() {
+        assert_eq!(
+            "Greedy".parse::<SettlementMode>().unwrap(),
+            SettlementMode::Greedy
+        );
+        assert_eq!(SettlementMode::default().as_ref(), "exact");
+        assert!("fastest".parse::<SettlementMode>().is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn exact_never_worse_than_greedy(balances in balances()) {
+            let greedy = settle_balances(&balances, SettlementMode::Greedy);
+            let exact = settle_balances(&balances, SettlementMode::Exact);
+            prop_assert!(exact.len() <= greedy.len());
+            assert_settled(&balances, &greedy);
+            assert_settled(&balances, &exact);
+        }
+
+        #[test]
+        fn preferences_always_settle(
+            balances in balances(),
+            cannot_pay in prop::collection::vec((0usize..10, 0usize..10), 0..20),
+            settles_via in prop::collection::vec((0usize..10, 0usize..10), 0..5),
+        ) {
+            let n = balances.len();
+            let pairs = |pairs: Vec<(usize, usize)>| {
+                pairs
+                    .into_iter()
+                    .map(|(i, j)| (i % n, j % n))
+                    .filter(|(i, j)| i != j)
+                    .collect::<Vec<_>>()
+            };
+            let preferences = preferences(&pairs(cannot_pay), &pairs(settles_via));
+            let plan = settle_balances(&balances, SettlementMode::Exact);
+            let transfers = settle_with_preferences(&balances, SettlementMode::Exact, &preferences);
+            assert_settled(&balances, &transfers);
+            prop_assert!(
+                preferences.total_cost(&transfers, n) <= preferences.total_cost(&plan, n)
+            );
+        }
+
+        #[test]
+        fn exact_needs_one_transfer_less_per_group(balances in balances()) {
+            let members: Vec<usize> = (0..balances.len())
+                .filter(|&i| !is_zero(balances[i]))
+                .collect();
+            let groups = zero_sum_groups(&balances, &members);
+            for group in &groups {
+                let sum: Decimal = group.iter().map(|&i| balances[i]).sum();
+                prop_assert!(sum.is_zero());
+            }
+            let exact = settle_balances(&balances, SettlementMode::Exact);
+            prop_assert_eq!(exact.len(), members.len() - groups.len());
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use rust_decimal::Decimal;
+use std::{collections::BTreeMap, fmt::Display};
+
+/// How much of an expense a traveler takes on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShareAmount {
+    /// A fixed amount.
+    Fixed(Decimal),
+    /// A percentage of what is left after the fixed amounts.
+    Percentage(Decimal),
+    /// An equal part of what is left after the fixed amounts and the
+    /// percentages.
+    Dynamic,
+}
+
+/// Why an expense can't be split as requested.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShareError {
+    /// The fixed amounts add up to more than the expense.
+    ExpenseTooHigh { tot_amount: Decimal },
+    /// There are no dynamic shares and the other ones add up to `expense`,
+    /// less than the expense.
+    ExpenseTooLow {
+        expense: Decimal,
+        tot_amount: Decimal,
+    },
+}
+
+impl Display for ShareError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShareError::ExpenseTooHigh { tot_amount } => {
+                write!(f, "The shares add up to more than {tot_amount}")
+            }
+            ShareError::ExpenseTooLow {
+                expense,
+                tot_amount,
+            } => write!(f, "The shares add up to {expense} out of {tot_amount}"),
+        }
+    }
+}
+
+impl std::error::Error for ShareError {}
+
+/// Splits `tot_amount` among the travelers of `split_among`.
+///
+/// Fixed amounts are taken first, then percentages of what is left, and
+/// the rest is divided equally among the dynamic shares.
+pub fn compute_shares<K: Ord>(
+    tot_amount: Decimal,
+    mut split_among: BTreeMap<K, ShareAmount>,
+) -> Result<BTreeMap<K, Decimal>, ShareError> {
+    // Start with the total amount to be split
+    let mut residual = tot_amount;
+    let mut count_dynamics = 0;
+
+    // First pass: subtract fixed shares and count dynamic shares
+    for share in split_among.values() {
+        match share {
+            ShareAmount::Fixed(amount) => {
+                residual -= amount;
+                // If the sum of fixed shares exceeds the total, return error
+                if residual < Decimal::ZERO {
+                    return Err(ShareError::ExpenseTooHigh { tot_amount });
+                }
+            }
+            ShareAmount::Dynamic => count_dynamics += 1,
+            ShareAmount::Percentage(_) => {} // Percentages handled in next pass
+        }
+    }
+
+    // Save the current residual for percentage calculation
+    let residual_backup = residual;
+    // Second pass: convert percentage shares to fixed amounts
+    split_among.values_mut().for_each(|share| {
+        if let ShareAmount::Percentage(amount) = share {
+            // Calculate fixed amount for this percentage
+            let fixed = residual_backup * *amount / Decimal::from(100);
+            *share = ShareAmount::Fixed(fixed);
+            residual -= fixed;
+        }
+    });
+
+    // If there are no dynamic shares and residual remains, it's too low
+    if count_dynamics == 0 && residual > Decimal::ZERO {
+        return Err(ShareError::ExpenseTooLow {
+            expense: tot_amount - residual,
+            tot_amount,
+        });
+    }
+
+    // Divide the remaining residual equally among dynamic shares
+    let split_residual = if count_dynamics > 0 {
+        residual
+            .checked_div(Decimal::from(count_dynamics))
+            .expect("count_blanks should be positive")
+    } else {
+        // No dynamic shares, so the remaining residual is not assigned to anyone
+        Decimal::ZERO
+    };
+
+    // Build the final shares map
+    Ok(split_among
+        .into_iter()
+        .map(|(name, share)| {
+            let amount = match share {
+                ShareAmount::Fixed(amount) => amount,
+                ShareAmount::Dynamic => split_residual,
+                ShareAmount::Percentage(_) => {
+                    unreachable!("Already converted to fixed amounts")
+                }
+            };
+            (name, amount)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::is_zero;
+    use proptest::prelude::*;
+
+    #[test]
+    fn fixed_percentage_and_dynamic() {
+        // 100: 40 fixed, 50% of the remaining 60, the rest split in two
+        let shares = compute_shares(
+            Decimal::ONE_HUNDRED,
+            BTreeMap::from([
+                ("alice", ShareAmount::Fixed(40.into())),
+                ("bob", ShareAmount::Percentage(50.into())),
+                ("carol", ShareAmount::Dynamic),
+                ("dave", ShareAmount::Dynamic),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(
+            shares,
+            BTreeMap::from([
+                ("alice", 40.into()),
+                ("bob", 30.into()),
+                ("carol", 15.into()),
+                ("dave", 15.into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn too_high_and_too_low() {
+        let too_high = compute_shares(
+            Decimal::TEN,
+            BTreeMap::from([("alice", ShareAmount::Fixed(11.into()))]),
+        );
+        assert_eq!(
+            too_high,
+            Err(ShareError::ExpenseTooHigh {
+                tot_amount: Decimal::TEN
+            })
+        );
+
+        let too_low = compute_shares(
+            Decimal::TEN,
+            BTreeMap::from([("alice", ShareAmount::Fixed(4.into()))]),
+        );
+        assert_eq!(
+            too_low,
+            Err(ShareError::ExpenseTooLow {
+                expense: 4.into(),
+                tot_amount: Decimal::TEN
+            })
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn shares_add_up_to_the_expense(
+            cents in 1i64..1_000_000,
+            fixed in prop::collection::vec(0i64..10_000, 0..4),
+            percentages in prop::collection::vec(0i64..=100, 0..3),
+            dynamics in 1usize..6,
+        ) {
+            let tot_amount = Decimal::new(cents, 2);
+            let mut split_among = BTreeMap::new();
+            for (i, cents) in fixed.into_iter().enumerate() {
+                split_among.insert(format!("fixed {i}"), ShareAmount::Fixed(Decimal::new(cents, 2)));
+            }
+            for (i, percentage) in percentages.into_iter().enumerate() {
+                split_among.insert(format!("percentage {i}"), ShareAmount::Percentage(percentage.into()));
+            }
+            for i in 0..dynamics {
+                split_among.insert(format!("dynamic {i}"), ShareAmount::Dynamic);
+            }
+
+            match compute_shares(tot_amount, split_among) {
+                Ok(shares) => {
+                    let sum: Decimal = shares.values().sum();
+                    prop_assert!(is_zero(sum - tot_amount), "{sum} != {tot_amount}");
+                }
+                Err(err) => prop_assert!(
+                    matches!(err, ShareError::ExpenseTooHigh { .. }),
+                    "{err}"
+                ),
+            }
+        }
+    }
+}